@@ -311,6 +311,9 @@ def external_crates_repository(name, cargo_lockfile, lockfile):
             "bitflags": crate.spec(
                 version = "^1.2.1",
             ),
+            "blst": crate.spec(
+                version = "^0.3.14",
+            ),
             "bs58": crate.spec(
                 version = "^0.5.0",
             ),
@@ -885,6 +888,9 @@ def external_crates_repository(name, cargo_lockfile, lockfile):
             "minicbor-derive": crate.spec(
                 version = "^0.13.0",
             ),
+            "ml-dsa": crate.spec(
+                version = "=0.0.4",
+            ),
             "mockall": crate.spec(
                 version = "^0.13.0",
             ),
@@ -0,0 +1,574 @@
+//! The on-disk snapshot archive format.
+//!
+//! See the crate documentation for a description of the layout.
+
+use crate::ArchiveError;
+use crate::types::{
+    ChunkHash, Global, GlobalTimer, OnLowWasmMemoryHookStatus, ReadCanisterSnapshotDataArgs,
+    ReadCanisterSnapshotMetadataResponse, SnapshotDataKind, SnapshotDataOffset, SnapshotSource,
+    UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs,
+};
+use candid::{Principal, Reserved};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// The version of the archive format written by this crate.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The name of the manifest file at the root of an archive.
+pub const MANIFEST_FILE: &str = "manifest.json";
+/// The file holding the canister's Wasm module.
+pub const WASM_MODULE_FILE: &str = "wasm_module.bin";
+/// The file holding the canister's Wasm memory.
+pub const WASM_MEMORY_FILE: &str = "wasm_memory.bin";
+/// The file holding the canister's stable memory.
+pub const STABLE_MEMORY_FILE: &str = "stable_memory.bin";
+/// The directory holding the chunks of the Wasm chunk store, one file per
+/// chunk named after the hex-encoded SHA-256 hash of its contents.
+pub const WASM_CHUNKS_DIR: &str = "wasm_chunks";
+
+/// The largest slice of a snapshot that is transferred in a single
+/// `read_canister_snapshot_data` or `upload_canister_snapshot_data` call.
+pub const MAX_SLICE_SIZE: u64 = 2_000_000;
+
+const HASH_BUFFER_SIZE: usize = 1 << 20;
+
+/// The manifest of a snapshot archive, stored as JSON in [`MANIFEST_FILE`].
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the archive format.
+    pub format_version: u32,
+    /// The canister the snapshot was downloaded from.
+    pub canister_id: Principal,
+    /// The ID of the snapshot the archive was created from.
+    #[serde(with = "hex::serde")]
+    pub snapshot_id: Vec<u8>,
+    /// The snapshot metadata that is not stored in separate files.
+    pub metadata: SnapshotMetadata,
+    /// All files of the archive except for the manifest itself.
+    pub files: Vec<FileEntry>,
+}
+
+impl Manifest {
+    /// Returns the entry of the file at the given relative path.
+    pub fn file(&self, path: &str) -> Option<&FileEntry> {
+        self.files.iter().find(|entry| entry.path == path)
+    }
+
+    /// Returns the hashes of the chunks in the Wasm chunk store.
+    pub fn wasm_chunk_hashes(&self) -> Vec<[u8; 32]> {
+        self.files
+            .iter()
+            .filter(|entry| chunk_hash_from_path(&entry.path).is_some())
+            .map(|entry| entry.sha256)
+            .collect()
+    }
+
+    fn file_size(&self, path: &str) -> u64 {
+        self.file(path).map(|entry| entry.size).unwrap_or_default()
+    }
+}
+
+/// A file of the archive along with its size and hash.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// The path of the file relative to the archive root, using `/` as separator.
+    pub path: String,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The SHA-256 hash of the file contents.
+    #[serde(with = "hex::serde")]
+    pub sha256: [u8; 32],
+}
+
+/// How the archived snapshot was created.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchivedSnapshotSource {
+    /// The snapshot was taken from a canister.
+    TakenFromCanister,
+    /// The snapshot was created by uploading metadata.
+    MetadataUpload,
+}
+
+/// An exported global as stored in the manifest.
+///
+/// Floating point values are stored as their bit patterns, so that every
+/// value (including NaNs) survives the round trip through JSON.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchivedGlobal {
+    /// A 32-bit integer global.
+    I32(i32),
+    /// A 64-bit integer global.
+    I64(i64),
+    /// The bits of a 32-bit floating point global.
+    F32Bits(u32),
+    /// The bits of a 64-bit floating point global.
+    F64Bits(u64),
+    /// A 128-bit vector global.
+    V128(u128),
+}
+
+impl From<&Global> for ArchivedGlobal {
+    fn from(global: &Global) -> Self {
+        match *global {
+            Global::I32(value) => Self::I32(value),
+            Global::I64(value) => Self::I64(value),
+            Global::F32(value) => Self::F32Bits(value.to_bits()),
+            Global::F64(value) => Self::F64Bits(value.to_bits()),
+            Global::V128(value) => Self::V128(value),
+        }
+    }
+}
+
+impl From<&ArchivedGlobal> for Global {
+    fn from(global: &ArchivedGlobal) -> Self {
+        match *global {
+            ArchivedGlobal::I32(value) => Self::I32(value),
+            ArchivedGlobal::I64(value) => Self::I64(value),
+            ArchivedGlobal::F32Bits(bits) => Self::F32(f32::from_bits(bits)),
+            ArchivedGlobal::F64Bits(bits) => Self::F64(f64::from_bits(bits)),
+            ArchivedGlobal::V128(value) => Self::V128(value),
+        }
+    }
+}
+
+/// The snapshot metadata stored in the manifest.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+pub struct SnapshotMetadata {
+    /// How the snapshot was created.
+    pub source: ArchivedSnapshotSource,
+    /// The time the snapshot was created at, in nanoseconds since the Unix epoch.
+    pub taken_at_timestamp: u64,
+    /// The canister version at the time the snapshot was created.
+    pub canister_version: u64,
+    /// The exported globals of the canister.
+    pub globals: Vec<ArchivedGlobal>,
+    /// The certified data of the canister.
+    #[serde(with = "hex::serde")]
+    pub certified_data: Vec<u8>,
+    /// The state of the global timer, if known.
+    pub global_timer: Option<GlobalTimer>,
+    /// The status of the `on_low_wasm_memory` hook, if known.
+    pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+impl From<&ReadCanisterSnapshotMetadataResponse> for SnapshotMetadata {
+    fn from(metadata: &ReadCanisterSnapshotMetadataResponse) -> Self {
+        Self {
+            source: match metadata.source {
+                SnapshotSource::TakenFromCanister(_) => ArchivedSnapshotSource::TakenFromCanister,
+                SnapshotSource::MetadataUpload(_) => ArchivedSnapshotSource::MetadataUpload,
+            },
+            taken_at_timestamp: metadata.taken_at_timestamp,
+            canister_version: metadata.canister_version,
+            globals: metadata.globals.iter().map(ArchivedGlobal::from).collect(),
+            certified_data: metadata.certified_data.clone(),
+            global_timer: metadata.global_timer,
+            on_low_wasm_memory_hook_status: metadata.on_low_wasm_memory_hook_status,
+        }
+    }
+}
+
+/// Writes a snapshot archive from data downloaded with
+/// `read_canister_snapshot_data`.
+///
+/// The requests returned by [`Self::read_requests`] may be issued in any
+/// order (and concurrently); each response is passed to [`Self::write`].
+/// The manifest is only written by [`Self::finish`], so an archive without a
+/// manifest is incomplete.
+pub struct SnapshotArchiveWriter {
+    dir: PathBuf,
+    canister_id: Principal,
+    snapshot_id: Vec<u8>,
+    metadata: ReadCanisterSnapshotMetadataResponse,
+}
+
+impl SnapshotArchiveWriter {
+    /// Creates the archive directory and its (preallocated) data files.
+    ///
+    /// The directory must either not exist yet or be empty.
+    pub fn create(
+        dir: &Path,
+        canister_id: Principal,
+        snapshot_id: Vec<u8>,
+        metadata: ReadCanisterSnapshotMetadataResponse,
+    ) -> Result<Self, ArchiveError> {
+        if dir.exists() {
+            let mut entries = std::fs::read_dir(dir).map_err(|err| io_error(dir, err))?;
+            if entries.next().is_some() {
+                return Err(ArchiveError::DirectoryNotEmpty(dir.to_path_buf()));
+            }
+        }
+        let chunks_dir = dir.join(WASM_CHUNKS_DIR);
+        std::fs::create_dir_all(&chunks_dir).map_err(|err| io_error(&chunks_dir, err))?;
+
+        for (file_name, size) in [
+            (WASM_MODULE_FILE, metadata.wasm_module_size),
+            (WASM_MEMORY_FILE, metadata.wasm_memory_size),
+            (STABLE_MEMORY_FILE, metadata.stable_memory_size),
+        ] {
+            let path = dir.join(file_name);
+            File::create(&path)
+                .and_then(|file| file.set_len(size))
+                .map_err(|err| io_error(&path, err))?;
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            canister_id,
+            snapshot_id,
+            metadata,
+        })
+    }
+
+    /// Returns the `read_canister_snapshot_data` requests needed to download
+    /// the whole snapshot, each reading at most `max_slice_size` bytes.
+    pub fn read_requests(&self, max_slice_size: u64) -> Vec<ReadCanisterSnapshotDataArgs> {
+        let mut kinds = vec![];
+        kinds.extend(
+            slices(self.metadata.wasm_module_size, max_slice_size)
+                .map(|(offset, size)| SnapshotDataKind::WasmModule { offset, size }),
+        );
+        kinds.extend(
+            slices(self.metadata.wasm_memory_size, max_slice_size)
+                .map(|(offset, size)| SnapshotDataKind::WasmMemory { offset, size }),
+        );
+        kinds.extend(
+            slices(self.metadata.stable_memory_size, max_slice_size)
+                .map(|(offset, size)| SnapshotDataKind::StableMemory { offset, size }),
+        );
+        kinds.extend(
+            self.metadata
+                .wasm_chunk_store
+                .iter()
+                .map(|ChunkHash { hash }| SnapshotDataKind::WasmChunk { hash: hash.clone() }),
+        );
+        kinds
+            .into_iter()
+            .map(|kind| ReadCanisterSnapshotDataArgs {
+                canister_id: self.canister_id,
+                snapshot_id: self.snapshot_id.clone(),
+                kind,
+            })
+            .collect()
+    }
+
+    /// Writes the response to one of the requests returned by
+    /// [`Self::read_requests`].
+    pub fn write(
+        &mut self,
+        request: &ReadCanisterSnapshotDataArgs,
+        data: &[u8],
+    ) -> Result<(), ArchiveError> {
+        let (file_name, offset, size) = match &request.kind {
+            SnapshotDataKind::WasmModule { offset, size } => (WASM_MODULE_FILE, offset, size),
+            SnapshotDataKind::WasmMemory { offset, size } => (WASM_MEMORY_FILE, offset, size),
+            SnapshotDataKind::StableMemory { offset, size } => (STABLE_MEMORY_FILE, offset, size),
+            SnapshotDataKind::WasmChunk { hash } => {
+                let actual = Sha256::digest(data);
+                if actual.as_slice() != hash.as_slice() {
+                    return Err(ArchiveError::HashMismatch {
+                        path: chunk_path(hash),
+                        expected: hex::encode(hash),
+                        actual: hex::encode(actual),
+                    });
+                }
+                let path = self.dir.join(chunk_path(hash));
+                return std::fs::write(&path, data).map_err(|err| io_error(&path, err));
+            }
+        };
+        if data.len() as u64 != *size {
+            return Err(ArchiveError::SizeMismatch {
+                path: file_name.to_string(),
+                expected: *size,
+                actual: data.len() as u64,
+            });
+        }
+        let path = self.dir.join(file_name);
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .and_then(|mut file| {
+                file.seek(SeekFrom::Start(*offset))?;
+                file.write_all(data)
+            })
+            .map_err(|err| io_error(&path, err))
+    }
+
+    /// Hashes all data files and writes the manifest, completing the archive.
+    pub fn finish(self) -> Result<Manifest, ArchiveError> {
+        let mut paths = vec![
+            WASM_MODULE_FILE.to_string(),
+            WASM_MEMORY_FILE.to_string(),
+            STABLE_MEMORY_FILE.to_string(),
+        ];
+        paths.extend(
+            self.metadata
+                .wasm_chunk_store
+                .iter()
+                .map(|ChunkHash { hash }| chunk_path(hash)),
+        );
+        let files = paths
+            .into_iter()
+            .map(|path| {
+                let (size, sha256) = hash_file(&self.dir.join(&path))?;
+                Ok(FileEntry { path, size, sha256 })
+            })
+            .collect::<Result<Vec<_>, ArchiveError>>()?;
+
+        let manifest = Manifest {
+            format_version: FORMAT_VERSION,
+            canister_id: self.canister_id,
+            snapshot_id: self.snapshot_id,
+            metadata: SnapshotMetadata::from(&self.metadata),
+            files,
+        };
+        let bytes = serde_json::to_vec_pretty(&manifest)
+            .map_err(|err| ArchiveError::InvalidManifest(err.to_string()))?;
+        // Write the manifest atomically, so that a partially written archive
+        // is never mistaken for a complete one.
+        let tmp_path = self.dir.join(format!("{MANIFEST_FILE}.tmp"));
+        let path = self.dir.join(MANIFEST_FILE);
+        std::fs::write(&tmp_path, bytes).map_err(|err| io_error(&tmp_path, err))?;
+        std::fs::rename(&tmp_path, &path).map_err(|err| io_error(&path, err))?;
+        Ok(manifest)
+    }
+}
+
+/// A complete snapshot archive whose contents were verified against its
+/// manifest.
+#[derive(Debug)]
+pub struct SnapshotArchive {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+impl SnapshotArchive {
+    /// Opens the archive in `dir`, checking the format version as well as
+    /// the size and hash of every file listed in the manifest.
+    pub fn open(dir: &Path) -> Result<Self, ArchiveError> {
+        let manifest_path = dir.join(MANIFEST_FILE);
+        let bytes = std::fs::read(&manifest_path).map_err(|err| io_error(&manifest_path, err))?;
+        let manifest: Manifest = serde_json::from_slice(&bytes)
+            .map_err(|err| ArchiveError::InvalidManifest(err.to_string()))?;
+        if manifest.format_version != FORMAT_VERSION {
+            return Err(ArchiveError::UnsupportedVersion(manifest.format_version));
+        }
+
+        for file_name in [WASM_MODULE_FILE, WASM_MEMORY_FILE, STABLE_MEMORY_FILE] {
+            if manifest.file(file_name).is_none() {
+                return Err(ArchiveError::InvalidManifest(format!(
+                    "missing entry for {file_name}"
+                )));
+            }
+        }
+        for entry in &manifest.files {
+            let is_known_file = [WASM_MODULE_FILE, WASM_MEMORY_FILE, STABLE_MEMORY_FILE]
+                .contains(&entry.path.as_str())
+                || chunk_hash_from_path(&entry.path) == Some(entry.sha256);
+            if !is_known_file {
+                return Err(ArchiveError::InvalidManifest(format!(
+                    "unexpected entry {}",
+                    entry.path
+                )));
+            }
+            let (size, sha256) = hash_file(&dir.join(&entry.path))?;
+            if size != entry.size {
+                return Err(ArchiveError::SizeMismatch {
+                    path: entry.path.clone(),
+                    expected: entry.size,
+                    actual: size,
+                });
+            }
+            if sha256 != entry.sha256 {
+                return Err(ArchiveError::HashMismatch {
+                    path: entry.path.clone(),
+                    expected: hex::encode(entry.sha256),
+                    actual: hex::encode(sha256),
+                });
+            }
+        }
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            manifest,
+        })
+    }
+
+    /// Returns the manifest of the archive.
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Returns the `upload_canister_snapshot_metadata` argument that creates
+    /// a snapshot of `canister_id` from this archive.
+    pub fn upload_metadata_args(
+        &self,
+        canister_id: Principal,
+        replace_snapshot: Option<Vec<u8>>,
+    ) -> UploadCanisterSnapshotMetadataArgs {
+        let metadata = &self.manifest.metadata;
+        UploadCanisterSnapshotMetadataArgs {
+            canister_id,
+            replace_snapshot: replace_snapshot.map(serde_bytes::ByteBuf::from),
+            wasm_module_size: self.manifest.file_size(WASM_MODULE_FILE),
+            globals: metadata.globals.iter().map(Global::from).collect(),
+            wasm_memory_size: self.manifest.file_size(WASM_MEMORY_FILE),
+            stable_memory_size: self.manifest.file_size(STABLE_MEMORY_FILE),
+            certified_data: metadata.certified_data.clone(),
+            global_timer: metadata.global_timer,
+            on_low_wasm_memory_hook_status: metadata.on_low_wasm_memory_hook_status,
+        }
+    }
+
+    /// Returns the `read_canister_snapshot_metadata` response the archive
+    /// was created from.
+    pub fn metadata(&self) -> ReadCanisterSnapshotMetadataResponse {
+        let metadata = &self.manifest.metadata;
+        ReadCanisterSnapshotMetadataResponse {
+            source: match metadata.source {
+                ArchivedSnapshotSource::TakenFromCanister => {
+                    SnapshotSource::TakenFromCanister(Reserved)
+                }
+                ArchivedSnapshotSource::MetadataUpload => SnapshotSource::MetadataUpload(Reserved),
+            },
+            taken_at_timestamp: metadata.taken_at_timestamp,
+            wasm_module_size: self.manifest.file_size(WASM_MODULE_FILE),
+            globals: metadata.globals.iter().map(Global::from).collect(),
+            wasm_memory_size: self.manifest.file_size(WASM_MEMORY_FILE),
+            stable_memory_size: self.manifest.file_size(STABLE_MEMORY_FILE),
+            wasm_chunk_store: self
+                .manifest
+                .wasm_chunk_hashes()
+                .into_iter()
+                .map(|hash| ChunkHash {
+                    hash: hash.to_vec(),
+                })
+                .collect(),
+            canister_version: metadata.canister_version,
+            certified_data: metadata.certified_data.clone(),
+            global_timer: metadata.global_timer,
+            on_low_wasm_memory_hook_status: metadata.on_low_wasm_memory_hook_status,
+        }
+    }
+
+    /// Returns the `upload_canister_snapshot_data` requests needed to upload
+    /// the archive's data to the snapshot `snapshot_id` of `canister_id`,
+    /// each carrying at most `max_slice_size` bytes.
+    ///
+    /// The data is read from disk lazily, one request at a time.
+    pub fn upload_requests(
+        &self,
+        canister_id: Principal,
+        snapshot_id: Vec<u8>,
+        max_slice_size: u64,
+    ) -> UploadRequests {
+        let mut pending = vec![];
+        for file_name in [WASM_MODULE_FILE, WASM_MEMORY_FILE, STABLE_MEMORY_FILE] {
+            for (offset, size) in slices(self.manifest.file_size(file_name), max_slice_size) {
+                let kind = match file_name {
+                    WASM_MODULE_FILE => SnapshotDataOffset::WasmModule { offset },
+                    WASM_MEMORY_FILE => SnapshotDataOffset::WasmMemory { offset },
+                    _ => SnapshotDataOffset::StableMemory { offset },
+                };
+                pending.push((self.dir.join(file_name), offset, size, kind));
+            }
+        }
+        for entry in &self.manifest.files {
+            if chunk_hash_from_path(&entry.path).is_some() {
+                pending.push((
+                    self.dir.join(&entry.path),
+                    0,
+                    entry.size,
+                    SnapshotDataOffset::WasmChunk,
+                ));
+            }
+        }
+        pending.reverse();
+        UploadRequests {
+            canister_id,
+            snapshot_id,
+            pending,
+        }
+    }
+}
+
+/// An iterator over the `upload_canister_snapshot_data` requests of an
+/// archive, see [`SnapshotArchive::upload_requests`].
+pub struct UploadRequests {
+    canister_id: Principal,
+    snapshot_id: Vec<u8>,
+    // In reverse order, so that the next request can be popped off the end.
+    pending: Vec<(PathBuf, u64, u64, SnapshotDataOffset)>,
+}
+
+impl Iterator for UploadRequests {
+    type Item = Result<UploadCanisterSnapshotDataArgs, ArchiveError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, offset, size, kind) = self.pending.pop()?;
+        let mut chunk = vec![0; size as usize];
+        let result = File::open(&path)
+            .and_then(|mut file| {
+                file.seek(SeekFrom::Start(offset))?;
+                file.read_exact(&mut chunk)
+            })
+            .map_err(|err| io_error(&path, err))
+            .map(|()| UploadCanisterSnapshotDataArgs {
+                canister_id: self.canister_id,
+                snapshot_id: self.snapshot_id.clone(),
+                kind,
+                chunk,
+            });
+        Some(result)
+    }
+}
+
+/// Splits `total` bytes into `(offset, size)` slices of at most `max_size` bytes.
+fn slices(total: u64, max_size: u64) -> impl Iterator<Item = (u64, u64)> {
+    (0..total)
+        .step_by(max_size.max(1) as usize)
+        .map(move |offset| (offset, max_size.min(total - offset)))
+}
+
+/// Returns the archive path of the chunk with the given hash.
+fn chunk_path(hash: &[u8]) -> String {
+    format!("{WASM_CHUNKS_DIR}/{}.bin", hex::encode(hash))
+}
+
+/// Returns the hash encoded in the path of a chunk file, if `path` is one.
+fn chunk_hash_from_path(path: &str) -> Option<[u8; 32]> {
+    let file_name = path.strip_prefix(WASM_CHUNKS_DIR)?.strip_prefix('/')?;
+    let mut hash = [0; 32];
+    hex::decode_to_slice(file_name.strip_suffix(".bin")?, &mut hash).ok()?;
+    Some(hash)
+}
+
+/// Returns the size and SHA-256 hash of the file at `path`.
+fn hash_file(path: &Path) -> Result<(u64, [u8; 32]), ArchiveError> {
+    let mut file = File::open(path).map_err(|err| io_error(path, err))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; HASH_BUFFER_SIZE];
+    let mut size = 0;
+    loop {
+        let n = file.read(&mut buffer).map_err(|err| io_error(path, err))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        size += n as u64;
+    }
+    Ok((size, hasher.finalize().into()))
+}
+
+fn io_error(path: &Path, source: std::io::Error) -> ArchiveError {
+    ArchiveError::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
@@ -0,0 +1,199 @@
+#![forbid(unsafe_code)]
+#![forbid(missing_docs)]
+
+//! Canister Snapshot Archives
+//!
+//! This crate defines a versioned on-disk format for canister snapshots and
+//! produces and consumes it by driving the management canister methods
+//! `read_canister_snapshot_metadata`, `read_canister_snapshot_data`,
+//! `upload_canister_snapshot_metadata` and `upload_canister_snapshot_data`.
+//! This allows moving a canister snapshot from one environment (e.g. mainnet)
+//! into another one (e.g. PocketIC) and back.
+//!
+//! # Format (version 1)
+//!
+//! An archive is a directory with the following contents:
+//!
+//! ```text
+//! <archive>/
+//! ├── manifest.json
+//! ├── wasm_module.bin
+//! ├── wasm_memory.bin
+//! ├── stable_memory.bin
+//! └── wasm_chunks/
+//!     └── <hex-encoded SHA-256 of the chunk>.bin
+//! ```
+//!
+//! The `.bin` files hold the raw bytes of the canister's Wasm module, Wasm
+//! memory, stable memory and Wasm chunk store chunks, respectively.
+//!
+//! The manifest is a JSON object with the fields:
+//!
+//! * `format_version`: the version of the format, currently `1`;
+//! * `canister_id`: the textual ID of the canister the snapshot was taken of;
+//! * `snapshot_id`: the hex-encoded ID of the snapshot;
+//! * `metadata`: the snapshot metadata not stored in a file of its own, see
+//!   [`SnapshotMetadata`]; floating point globals are stored as their bit
+//!   patterns (`f32_bits`, `f64_bits`);
+//! * `files`: the `path` (relative, `/`-separated), `size` and hex-encoded
+//!   `sha256` of every other file of the archive.
+//!
+//! The manifest is written last, so an archive without a manifest is
+//! incomplete. Readers must reject archives with an unknown
+//! `format_version` and archives whose files do not match the manifest.
+//!
+//! # Usage
+//!
+//! Synchronous callers implement [`ManagementCanister`] and use
+//! [`download_snapshot`] and [`upload_snapshot`]. Asynchronous callers drive
+//! the calls themselves using [`SnapshotArchiveWriter`] and
+//! [`SnapshotArchive`], which expose the individual requests.
+
+mod archive;
+pub mod types;
+
+pub use archive::{
+    ArchivedGlobal, ArchivedSnapshotSource, FORMAT_VERSION, FileEntry, MANIFEST_FILE,
+    MAX_SLICE_SIZE, Manifest, STABLE_MEMORY_FILE, SnapshotArchive, SnapshotArchiveWriter,
+    SnapshotMetadata, UploadRequests, WASM_CHUNKS_DIR, WASM_MEMORY_FILE, WASM_MODULE_FILE,
+};
+
+use candid::{CandidType, Decode, Encode, Principal};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+use types::{
+    ReadCanisterSnapshotDataResponse, ReadCanisterSnapshotMetadataArgs,
+    ReadCanisterSnapshotMetadataResponse, UploadCanisterSnapshotMetadataResponse,
+};
+
+/// Errors that can occur while producing or consuming a snapshot archive.
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveError {
+    /// Reading or writing a file of the archive failed.
+    #[error("I/O error on {path}: {source}")]
+    Io {
+        /// The file that could not be accessed.
+        path: PathBuf,
+        /// The underlying error.
+        source: std::io::Error,
+    },
+    /// The directory to create an archive in is not empty.
+    #[error("directory {0} is not empty")]
+    DirectoryNotEmpty(PathBuf),
+    /// The manifest could not be parsed or is inconsistent.
+    #[error("invalid manifest: {0}")]
+    InvalidManifest(String),
+    /// The archive uses a format version that is not supported.
+    #[error("unsupported archive format version {0}")]
+    UnsupportedVersion(u32),
+    /// A file does not have the size recorded in the manifest.
+    #[error("size mismatch for {path}: expected {expected} bytes, got {actual}")]
+    SizeMismatch {
+        /// The file with the unexpected size.
+        path: String,
+        /// The expected size in bytes.
+        expected: u64,
+        /// The actual size in bytes.
+        actual: u64,
+    },
+    /// A file does not have the hash recorded in the manifest.
+    #[error("hash mismatch for {path}: expected {expected}, got {actual}")]
+    HashMismatch {
+        /// The file with the unexpected hash.
+        path: String,
+        /// The expected hex-encoded SHA-256 hash.
+        expected: String,
+        /// The actual hex-encoded SHA-256 hash.
+        actual: String,
+    },
+    /// A management canister call failed.
+    #[error("call to {method} failed: {message}")]
+    Call {
+        /// The management canister method.
+        method: String,
+        /// A description of the failure.
+        message: String,
+    },
+}
+
+/// A synchronous connection to the management canister.
+pub trait ManagementCanister {
+    /// The error returned by failed calls.
+    type Error: std::fmt::Display;
+
+    /// Calls `method` of the management canister as a controller of the
+    /// canister the (Candid-encoded) `arg` refers to and returns the
+    /// Candid-encoded reply.
+    fn update_call(&self, method: &str, arg: Vec<u8>) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// Downloads the snapshot `snapshot_id` of `canister_id` into a new archive
+/// in `dir` and returns the archive's manifest.
+pub fn download_snapshot<M: ManagementCanister>(
+    management_canister: &M,
+    canister_id: Principal,
+    snapshot_id: Vec<u8>,
+    dir: &Path,
+) -> Result<Manifest, ArchiveError> {
+    let metadata: ReadCanisterSnapshotMetadataResponse = call(
+        management_canister,
+        "read_canister_snapshot_metadata",
+        &ReadCanisterSnapshotMetadataArgs {
+            canister_id,
+            snapshot_id: snapshot_id.clone(),
+        },
+    )?;
+    let mut writer = SnapshotArchiveWriter::create(dir, canister_id, snapshot_id, metadata)?;
+    for request in writer.read_requests(MAX_SLICE_SIZE) {
+        let response: ReadCanisterSnapshotDataResponse =
+            call(management_canister, "read_canister_snapshot_data", &request)?;
+        writer.write(&request, &response.chunk)?;
+    }
+    writer.finish()
+}
+
+/// Uploads the archive in `dir` as a new snapshot of `canister_id`,
+/// optionally replacing an existing snapshot, and returns the ID of the new
+/// snapshot.
+///
+/// The archive is verified against its manifest before anything is uploaded.
+pub fn upload_snapshot<M: ManagementCanister>(
+    management_canister: &M,
+    canister_id: Principal,
+    replace_snapshot: Option<Vec<u8>>,
+    dir: &Path,
+) -> Result<Vec<u8>, ArchiveError> {
+    let archive = SnapshotArchive::open(dir)?;
+    let response: UploadCanisterSnapshotMetadataResponse = call(
+        management_canister,
+        "upload_canister_snapshot_metadata",
+        &archive.upload_metadata_args(canister_id, replace_snapshot),
+    )?;
+    for request in
+        archive.upload_requests(canister_id, response.snapshot_id.clone(), MAX_SLICE_SIZE)
+    {
+        call::<_, _, ()>(
+            management_canister,
+            "upload_canister_snapshot_data",
+            &request?,
+        )?;
+    }
+    Ok(response.snapshot_id)
+}
+
+fn call<M, A, R>(management_canister: &M, method: &str, arg: &A) -> Result<R, ArchiveError>
+where
+    M: ManagementCanister,
+    A: CandidType,
+    R: CandidType + DeserializeOwned,
+{
+    let call_error = |message: String| ArchiveError::Call {
+        method: method.to_string(),
+        message,
+    };
+    let arg = Encode!(arg).map_err(|err| call_error(err.to_string()))?;
+    let reply = management_canister
+        .update_call(method, arg)
+        .map_err(|err| call_error(err.to_string()))?;
+    Decode!(&reply, R).map_err(|err| call_error(err.to_string()))
+}
@@ -0,0 +1,243 @@
+//! Candid types of the management canister methods used to download and
+//! upload canister snapshots.
+//!
+//! The types mirror the management canister interface and are defined here so
+//! that the crate can be used with any agent or test environment. The published
+//! `ic-management-canister-types` crate used by this workspace does not match
+//! the interface for these methods (e.g. it names the Wasm memory `main_memory`),
+//! so it cannot be reused. The `candid_equality` test checks that the types
+//! below match the management canister's `ic.did`.
+
+use candid::{CandidType, Deserialize, Principal, Reserved};
+use serde::Serialize;
+
+/// Argument of `read_canister_snapshot_metadata`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ReadCanisterSnapshotMetadataArgs {
+    /// The canister the snapshot belongs to.
+    pub canister_id: Principal,
+    /// The ID of the snapshot to read.
+    #[serde(with = "serde_bytes")]
+    pub snapshot_id: Vec<u8>,
+}
+
+/// The source a snapshot was created from.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub enum SnapshotSource {
+    /// The snapshot was taken from a canister.
+    #[serde(rename = "taken_from_canister")]
+    TakenFromCanister(Reserved),
+    /// The snapshot was created by uploading metadata.
+    #[serde(rename = "metadata_upload")]
+    MetadataUpload(Reserved),
+}
+
+/// The value of an exported global.
+#[derive(Copy, Clone, PartialEq, Debug, CandidType, Deserialize, Serialize)]
+pub enum Global {
+    /// A 32-bit integer global.
+    #[serde(rename = "i32")]
+    I32(i32),
+    /// A 64-bit integer global.
+    #[serde(rename = "i64")]
+    I64(i64),
+    /// A 32-bit floating point global.
+    #[serde(rename = "f32")]
+    F32(f32),
+    /// A 64-bit floating point global.
+    #[serde(rename = "f64")]
+    F64(f64),
+    /// A 128-bit vector global.
+    #[serde(rename = "v128")]
+    V128(u128),
+}
+
+/// The state of the canister's global timer.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, CandidType, Deserialize, Serialize)]
+pub enum GlobalTimer {
+    /// The timer is not set.
+    #[serde(rename = "inactive")]
+    Inactive,
+    /// The timer is set to the given time in nanoseconds since the Unix epoch.
+    #[serde(rename = "active")]
+    Active(u64),
+}
+
+/// The status of the canister's `on_low_wasm_memory` hook.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, CandidType, Deserialize, Serialize)]
+pub enum OnLowWasmMemoryHookStatus {
+    /// The hook condition is not satisfied.
+    #[serde(rename = "condition_not_satisfied")]
+    ConditionNotSatisfied,
+    /// The hook is ready to be executed.
+    #[serde(rename = "ready")]
+    Ready,
+    /// The hook was executed.
+    #[serde(rename = "executed")]
+    Executed,
+}
+
+/// The hash of a chunk in the canister's Wasm chunk store.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ChunkHash {
+    /// The SHA-256 hash of the chunk.
+    #[serde(with = "serde_bytes")]
+    pub hash: Vec<u8>,
+}
+
+/// Response of `read_canister_snapshot_metadata`.
+#[derive(Clone, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ReadCanisterSnapshotMetadataResponse {
+    /// How the snapshot was created.
+    pub source: SnapshotSource,
+    /// The time the snapshot was created at, in nanoseconds since the Unix epoch.
+    pub taken_at_timestamp: u64,
+    /// The size of the Wasm module in bytes.
+    pub wasm_module_size: u64,
+    /// The exported globals of the canister.
+    pub globals: Vec<Global>,
+    /// The size of the Wasm memory in bytes.
+    pub wasm_memory_size: u64,
+    /// The size of the stable memory in bytes.
+    pub stable_memory_size: u64,
+    /// The hashes of the chunks in the Wasm chunk store.
+    pub wasm_chunk_store: Vec<ChunkHash>,
+    /// The canister version at the time the snapshot was created.
+    pub canister_version: u64,
+    /// The certified data of the canister.
+    #[serde(with = "serde_bytes")]
+    pub certified_data: Vec<u8>,
+    /// The state of the global timer, if known.
+    pub global_timer: Option<GlobalTimer>,
+    /// The status of the `on_low_wasm_memory` hook, if known.
+    pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+/// The part of a snapshot to read with `read_canister_snapshot_data`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub enum SnapshotDataKind {
+    /// A slice of the Wasm module.
+    #[serde(rename = "wasm_module")]
+    WasmModule {
+        /// The offset of the slice in bytes.
+        offset: u64,
+        /// The size of the slice in bytes.
+        size: u64,
+    },
+    /// A slice of the Wasm memory.
+    #[serde(rename = "wasm_memory")]
+    WasmMemory {
+        /// The offset of the slice in bytes.
+        offset: u64,
+        /// The size of the slice in bytes.
+        size: u64,
+    },
+    /// A slice of the stable memory.
+    #[serde(rename = "stable_memory")]
+    StableMemory {
+        /// The offset of the slice in bytes.
+        offset: u64,
+        /// The size of the slice in bytes.
+        size: u64,
+    },
+    /// A chunk of the Wasm chunk store.
+    #[serde(rename = "wasm_chunk")]
+    WasmChunk {
+        /// The hash of the chunk.
+        #[serde(with = "serde_bytes")]
+        hash: Vec<u8>,
+    },
+}
+
+/// Argument of `read_canister_snapshot_data`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ReadCanisterSnapshotDataArgs {
+    /// The canister the snapshot belongs to.
+    pub canister_id: Principal,
+    /// The ID of the snapshot to read.
+    #[serde(with = "serde_bytes")]
+    pub snapshot_id: Vec<u8>,
+    /// The part of the snapshot to read.
+    pub kind: SnapshotDataKind,
+}
+
+/// Response of `read_canister_snapshot_data`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct ReadCanisterSnapshotDataResponse {
+    /// The requested bytes.
+    #[serde(with = "serde_bytes")]
+    pub chunk: Vec<u8>,
+}
+
+/// Argument of `upload_canister_snapshot_metadata`.
+#[derive(Clone, PartialEq, Debug, CandidType, Deserialize)]
+pub struct UploadCanisterSnapshotMetadataArgs {
+    /// The canister to create the snapshot for.
+    pub canister_id: Principal,
+    /// An existing snapshot to replace, if any.
+    pub replace_snapshot: Option<serde_bytes::ByteBuf>,
+    /// The size of the Wasm module in bytes.
+    pub wasm_module_size: u64,
+    /// The exported globals of the canister.
+    pub globals: Vec<Global>,
+    /// The size of the Wasm memory in bytes.
+    pub wasm_memory_size: u64,
+    /// The size of the stable memory in bytes.
+    pub stable_memory_size: u64,
+    /// The certified data of the canister.
+    #[serde(with = "serde_bytes")]
+    pub certified_data: Vec<u8>,
+    /// The state of the global timer, if known.
+    pub global_timer: Option<GlobalTimer>,
+    /// The status of the `on_low_wasm_memory` hook, if known.
+    pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+/// Response of `upload_canister_snapshot_metadata`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct UploadCanisterSnapshotMetadataResponse {
+    /// The ID of the newly created snapshot.
+    #[serde(with = "serde_bytes")]
+    pub snapshot_id: Vec<u8>,
+}
+
+/// The part of a snapshot to write with `upload_canister_snapshot_data`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub enum SnapshotDataOffset {
+    /// Write to the Wasm module at the given offset.
+    #[serde(rename = "wasm_module")]
+    WasmModule {
+        /// The offset in bytes.
+        offset: u64,
+    },
+    /// Write to the Wasm memory at the given offset.
+    #[serde(rename = "wasm_memory")]
+    WasmMemory {
+        /// The offset in bytes.
+        offset: u64,
+    },
+    /// Write to the stable memory at the given offset.
+    #[serde(rename = "stable_memory")]
+    StableMemory {
+        /// The offset in bytes.
+        offset: u64,
+    },
+    /// Add a chunk to the Wasm chunk store.
+    #[serde(rename = "wasm_chunk")]
+    WasmChunk,
+}
+
+/// Argument of `upload_canister_snapshot_data`.
+#[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct UploadCanisterSnapshotDataArgs {
+    /// The canister the snapshot belongs to.
+    pub canister_id: Principal,
+    /// The ID of the snapshot to write to.
+    #[serde(with = "serde_bytes")]
+    pub snapshot_id: Vec<u8>,
+    /// The part of the snapshot to write to.
+    pub kind: SnapshotDataOffset,
+    /// The bytes to write.
+    #[serde(with = "serde_bytes")]
+    pub chunk: Vec<u8>,
+}
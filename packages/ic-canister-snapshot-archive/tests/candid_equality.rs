@@ -0,0 +1,77 @@
+#![allow(unused)]
+use candid::candid_method;
+use candid::types::{Type, TypeInner, subtype::equal};
+use candid_parser::utils::CandidSource;
+use ic_canister_snapshot_archive::types::*;
+use std::path::PathBuf;
+
+#[candid_method(update)]
+fn read_canister_snapshot_metadata(
+    _: ReadCanisterSnapshotMetadataArgs,
+) -> ReadCanisterSnapshotMetadataResponse {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn read_canister_snapshot_data(
+    _: ReadCanisterSnapshotDataArgs,
+) -> ReadCanisterSnapshotDataResponse {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn upload_canister_snapshot_metadata(
+    _: UploadCanisterSnapshotMetadataArgs,
+) -> UploadCanisterSnapshotMetadataResponse {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn upload_canister_snapshot_data(_: UploadCanisterSnapshotDataArgs) {
+    unreachable!()
+}
+
+/// Returns the path of the management canister's `ic.did`, which is passed in
+/// by Bazel and located relative to this package otherwise.
+fn ic_did_path() -> PathBuf {
+    match std::env::var("IC_DID") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../rs/types/management_canister_types/tests/ic.did"),
+    }
+}
+
+/// Checks that the types of the snapshot methods used by this crate are equal
+/// to the ones declared by the management canister interface.
+#[test]
+fn candid_equality_test() {
+    let declared_interface_str =
+        std::fs::read_to_string(ic_did_path()).expect("Failed to read ic.did file");
+    let (mut env, declared) = CandidSource::Text(&declared_interface_str).load().unwrap();
+
+    candid::export_service!();
+    let implemented_interface_str = __export_service();
+    let (implemented_env, implemented) = CandidSource::Text(&implemented_interface_str)
+        .load()
+        .unwrap();
+    let implemented = env.merge_type(implemented_env, implemented.unwrap());
+
+    let methods = |service: &Type| match env.trace_type(service).unwrap().as_ref() {
+        TypeInner::Service(methods) => methods.clone(),
+        other => panic!("Expected a service, got {other:?}"),
+    };
+    let implemented_methods = methods(&implemented);
+    // Only the methods used by this crate are compared.
+    let declared_methods: Vec<_> = methods(&declared.unwrap())
+        .into_iter()
+        .filter(|(name, _)| implemented_methods.iter().any(|(used, _)| used == name))
+        .collect();
+
+    let result = equal(
+        &mut Default::default(),
+        &env,
+        &TypeInner::Service(declared_methods).into(),
+        &TypeInner::Service(implemented_methods).into(),
+    );
+    assert!(result.is_ok(), "{:?}", result.unwrap_err());
+}
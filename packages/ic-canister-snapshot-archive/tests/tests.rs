@@ -0,0 +1,266 @@
+use candid::{Decode, Encode, Principal, Reserved};
+use ic_canister_snapshot_archive::types::*;
+use ic_canister_snapshot_archive::*;
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// The contents of a snapshot held by [`FakeManagementCanister`].
+#[derive(Clone, PartialEq, Debug, Default)]
+struct FakeSnapshot {
+    wasm_module: Vec<u8>,
+    wasm_memory: Vec<u8>,
+    stable_memory: Vec<u8>,
+    chunks: BTreeMap<Vec<u8>, Vec<u8>>,
+    globals: Vec<Global>,
+    certified_data: Vec<u8>,
+    global_timer: Option<GlobalTimer>,
+    on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+}
+
+/// An in-memory management canister implementing the snapshot data methods.
+#[derive(Default)]
+struct FakeManagementCanister {
+    snapshots: RefCell<BTreeMap<Vec<u8>, FakeSnapshot>>,
+    next_snapshot_id: RefCell<u8>,
+}
+
+impl FakeManagementCanister {
+    fn insert(&self, snapshot: FakeSnapshot) -> Vec<u8> {
+        let mut next_snapshot_id = self.next_snapshot_id.borrow_mut();
+        let snapshot_id = vec![*next_snapshot_id];
+        *next_snapshot_id += 1;
+        self.snapshots
+            .borrow_mut()
+            .insert(snapshot_id.clone(), snapshot);
+        snapshot_id
+    }
+
+    fn get(&self, snapshot_id: &[u8]) -> FakeSnapshot {
+        self.snapshots.borrow()[snapshot_id].clone()
+    }
+}
+
+fn slice(data: &[u8], offset: u64, size: u64) -> Vec<u8> {
+    data[offset as usize..(offset + size) as usize].to_vec()
+}
+
+fn write_at(data: &mut [u8], offset: u64, chunk: &[u8]) {
+    let offset = offset as usize;
+    data[offset..offset + chunk.len()].copy_from_slice(chunk);
+}
+
+impl ManagementCanister for FakeManagementCanister {
+    type Error = String;
+
+    fn update_call(&self, method: &str, arg: Vec<u8>) -> Result<Vec<u8>, String> {
+        match method {
+            "read_canister_snapshot_metadata" => {
+                let args = Decode!(&arg, ReadCanisterSnapshotMetadataArgs).unwrap();
+                let snapshot = self.get(&args.snapshot_id);
+                let response = ReadCanisterSnapshotMetadataResponse {
+                    source: SnapshotSource::TakenFromCanister(Reserved),
+                    taken_at_timestamp: 42,
+                    wasm_module_size: snapshot.wasm_module.len() as u64,
+                    globals: snapshot.globals,
+                    wasm_memory_size: snapshot.wasm_memory.len() as u64,
+                    stable_memory_size: snapshot.stable_memory.len() as u64,
+                    wasm_chunk_store: snapshot
+                        .chunks
+                        .keys()
+                        .map(|hash| ChunkHash { hash: hash.clone() })
+                        .collect(),
+                    canister_version: 7,
+                    certified_data: snapshot.certified_data,
+                    global_timer: snapshot.global_timer,
+                    on_low_wasm_memory_hook_status: snapshot.on_low_wasm_memory_hook_status,
+                };
+                Ok(Encode!(&response).unwrap())
+            }
+            "read_canister_snapshot_data" => {
+                let args = Decode!(&arg, ReadCanisterSnapshotDataArgs).unwrap();
+                let snapshot = self.get(&args.snapshot_id);
+                let chunk = match args.kind {
+                    SnapshotDataKind::WasmModule { offset, size } => {
+                        slice(&snapshot.wasm_module, offset, size)
+                    }
+                    SnapshotDataKind::WasmMemory { offset, size } => {
+                        slice(&snapshot.wasm_memory, offset, size)
+                    }
+                    SnapshotDataKind::StableMemory { offset, size } => {
+                        slice(&snapshot.stable_memory, offset, size)
+                    }
+                    SnapshotDataKind::WasmChunk { hash } => snapshot.chunks[&hash].clone(),
+                };
+                Ok(Encode!(&ReadCanisterSnapshotDataResponse { chunk }).unwrap())
+            }
+            "upload_canister_snapshot_metadata" => {
+                let args = Decode!(&arg, UploadCanisterSnapshotMetadataArgs).unwrap();
+                let snapshot_id = self.insert(FakeSnapshot {
+                    wasm_module: vec![0; args.wasm_module_size as usize],
+                    wasm_memory: vec![0; args.wasm_memory_size as usize],
+                    stable_memory: vec![0; args.stable_memory_size as usize],
+                    chunks: BTreeMap::new(),
+                    globals: args.globals,
+                    certified_data: args.certified_data,
+                    global_timer: args.global_timer,
+                    on_low_wasm_memory_hook_status: args.on_low_wasm_memory_hook_status,
+                });
+                Ok(Encode!(&UploadCanisterSnapshotMetadataResponse { snapshot_id }).unwrap())
+            }
+            "upload_canister_snapshot_data" => {
+                let args = Decode!(&arg, UploadCanisterSnapshotDataArgs).unwrap();
+                let mut snapshots = self.snapshots.borrow_mut();
+                let snapshot = snapshots.get_mut(&args.snapshot_id).unwrap();
+                match args.kind {
+                    SnapshotDataOffset::WasmModule { offset } => {
+                        write_at(&mut snapshot.wasm_module, offset, &args.chunk)
+                    }
+                    SnapshotDataOffset::WasmMemory { offset } => {
+                        write_at(&mut snapshot.wasm_memory, offset, &args.chunk)
+                    }
+                    SnapshotDataOffset::StableMemory { offset } => {
+                        write_at(&mut snapshot.stable_memory, offset, &args.chunk)
+                    }
+                    SnapshotDataOffset::WasmChunk => {
+                        let hash = Sha256::digest(&args.chunk).to_vec();
+                        snapshot.chunks.insert(hash, args.chunk);
+                    }
+                }
+                Ok(Encode!().unwrap())
+            }
+            _ => Err(format!("unexpected method {method}")),
+        }
+    }
+}
+
+fn canister_id() -> Principal {
+    Principal::from_slice(&[1, 2, 3, 4])
+}
+
+fn test_snapshot() -> FakeSnapshot {
+    // Larger than `MAX_SLICE_SIZE`, so that it is transferred in several slices.
+    let wasm_memory = (0..5_000_000u32).map(|i| (i % 251) as u8).collect();
+    let chunks = [vec![1; 100], vec![2; 1000]]
+        .into_iter()
+        .map(|chunk| (Sha256::digest(&chunk).to_vec(), chunk))
+        .collect();
+    FakeSnapshot {
+        wasm_module: b"\0asm\x01\0\0\0".to_vec(),
+        wasm_memory,
+        stable_memory: vec![3; 65536],
+        chunks,
+        globals: vec![
+            Global::I32(-1),
+            Global::I64(1 << 40),
+            Global::F32(f32::NAN),
+            Global::F64(1.5),
+            Global::V128(u128::MAX),
+        ],
+        certified_data: vec![4; 32],
+        global_timer: Some(GlobalTimer::Active(123)),
+        on_low_wasm_memory_hook_status: Some(OnLowWasmMemoryHookStatus::Ready),
+    }
+}
+
+#[test]
+fn download_and_upload_round_trip() {
+    let management_canister = FakeManagementCanister::default();
+    let snapshot = test_snapshot();
+    let snapshot_id = management_canister.insert(snapshot.clone());
+    let dir = tempfile::tempdir().unwrap();
+    let archive_dir = dir.path().join("archive");
+
+    let manifest = download_snapshot(
+        &management_canister,
+        canister_id(),
+        snapshot_id.clone(),
+        &archive_dir,
+    )
+    .unwrap();
+    assert_eq!(manifest.format_version, FORMAT_VERSION);
+    assert_eq!(manifest.canister_id, canister_id());
+    assert_eq!(manifest.snapshot_id, snapshot_id);
+    assert_eq!(manifest.metadata.canister_version, 7);
+    assert_eq!(manifest.files.len(), 5);
+    assert_eq!(
+        std::fs::read(archive_dir.join(WASM_MEMORY_FILE)).unwrap(),
+        snapshot.wasm_memory
+    );
+
+    let new_snapshot_id =
+        upload_snapshot(&management_canister, canister_id(), None, &archive_dir).unwrap();
+    assert_ne!(new_snapshot_id, snapshot_id);
+    let uploaded = management_canister.get(&new_snapshot_id);
+    // NaN does not compare equal to itself, so compare the globals' bits.
+    let bits = |globals: &[Global]| globals.iter().map(ArchivedGlobal::from).collect::<Vec<_>>();
+    assert_eq!(bits(&uploaded.globals), bits(&snapshot.globals));
+    assert_eq!(
+        FakeSnapshot {
+            globals: vec![],
+            ..uploaded
+        },
+        FakeSnapshot {
+            globals: vec![],
+            ..snapshot
+        }
+    );
+}
+
+#[test]
+fn open_rejects_modified_archive() {
+    let management_canister = FakeManagementCanister::default();
+    let snapshot_id = management_canister.insert(test_snapshot());
+    let dir = tempfile::tempdir().unwrap();
+    download_snapshot(&management_canister, canister_id(), snapshot_id, dir.path()).unwrap();
+    assert!(SnapshotArchive::open(dir.path()).is_ok());
+
+    let path = dir.path().join(STABLE_MEMORY_FILE);
+    let mut data = std::fs::read(&path).unwrap();
+    data[17] ^= 1;
+    std::fs::write(&path, &data).unwrap();
+    assert!(matches!(
+        SnapshotArchive::open(dir.path()),
+        Err(ArchiveError::HashMismatch { path, .. }) if path == STABLE_MEMORY_FILE
+    ));
+
+    data.pop();
+    std::fs::write(&path, &data).unwrap();
+    assert!(matches!(
+        SnapshotArchive::open(dir.path()),
+        Err(ArchiveError::SizeMismatch { path, .. }) if path == STABLE_MEMORY_FILE
+    ));
+}
+
+#[test]
+fn open_rejects_unknown_format_version() {
+    let management_canister = FakeManagementCanister::default();
+    let snapshot_id = management_canister.insert(test_snapshot());
+    let dir = tempfile::tempdir().unwrap();
+    let mut manifest =
+        download_snapshot(&management_canister, canister_id(), snapshot_id, dir.path()).unwrap();
+
+    manifest.format_version = FORMAT_VERSION + 1;
+    std::fs::write(
+        dir.path().join(MANIFEST_FILE),
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .unwrap();
+    assert!(matches!(
+        SnapshotArchive::open(dir.path()),
+        Err(ArchiveError::UnsupportedVersion(version)) if version == FORMAT_VERSION + 1
+    ));
+}
+
+#[test]
+fn download_requires_empty_directory() {
+    let management_canister = FakeManagementCanister::default();
+    let snapshot_id = management_canister.insert(test_snapshot());
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("some_file"), b"data").unwrap();
+
+    assert!(matches!(
+        download_snapshot(&management_canister, canister_id(), snapshot_id, dir.path()),
+        Err(ArchiveError::DirectoryNotEmpty(_))
+    ));
+}
@@ -67,6 +67,7 @@ use candid::{
     utils::{ArgumentDecoder, ArgumentEncoder},
 };
 use flate2::read::GzDecoder;
+use ic_canister_snapshot_archive::{ArchiveError, Manifest};
 use ic_management_canister_types::{
     CanisterId, CanisterInstallMode, CanisterLogRecord, CanisterSettings, CanisterStatusResult,
     Snapshot,
@@ -82,7 +83,7 @@ use std::os::unix::fs::OpenOptionsExt;
 use std::{
     fs::OpenOptions,
     net::{IpAddr, SocketAddr},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Child, Command},
     sync::{Arc, mpsc::channel},
     thread,
@@ -100,6 +101,10 @@ use wslpath::windows_to_wsl;
 pub mod common;
 pub mod nonblocking;
 
+/// The snapshot archive format used by [`PocketIc::download_canister_snapshot`]
+/// and [`PocketIc::upload_canister_snapshot`].
+pub use ic_canister_snapshot_archive;
+
 const POCKET_IC_SERVER_NAME: &str = "pocket-ic-server";
 
 const MIN_SERVER_VERSION: &str = "10.0.0";
@@ -1272,6 +1277,44 @@ impl PocketIc {
         })
     }
 
+    /// Download a canister snapshot into a local snapshot archive
+    /// (see the `ic-canister-snapshot-archive` crate for the format).
+    /// The directory `archive_dir` must either not exist yet or be empty.
+    #[instrument(skip(self), fields(instance_id=self.pocket_ic.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string(), archive_dir = %archive_dir.display()))]
+    pub fn download_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        sender: Option<Principal>,
+        snapshot_id: Vec<u8>,
+        archive_dir: &Path,
+    ) -> Result<Manifest, ArchiveError> {
+        let runtime = self.runtime.clone();
+        runtime.block_on(async {
+            self.pocket_ic
+                .download_canister_snapshot(canister_id, sender, snapshot_id, archive_dir)
+                .await
+        })
+    }
+
+    /// Upload a local snapshot archive (see the `ic-canister-snapshot-archive` crate
+    /// for the format) as a new canister snapshot, optionally replacing an existing one.
+    /// Returns the ID of the new snapshot.
+    #[instrument(skip(self), fields(instance_id=self.pocket_ic.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string(), archive_dir = %archive_dir.display()))]
+    pub fn upload_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        sender: Option<Principal>,
+        replace_snapshot: Option<Vec<u8>>,
+        archive_dir: &Path,
+    ) -> Result<Vec<u8>, ArchiveError> {
+        let runtime = self.runtime.clone();
+        runtime.block_on(async {
+            self.pocket_ic
+                .upload_canister_snapshot(canister_id, sender, replace_snapshot, archive_dir)
+                .await
+        })
+    }
+
     /// Update canister settings.
     #[instrument(skip(self), fields(instance_id=self.pocket_ic.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string()))]
     pub fn update_canister_settings(
@@ -18,9 +18,16 @@ use crate::{
 use backoff::backoff::Backoff;
 use backoff::{ExponentialBackoff, ExponentialBackoffBuilder};
 use candid::{
-    Principal, decode_args, encode_args,
+    CandidType, Principal, decode_args, encode_args,
     utils::{ArgumentDecoder, ArgumentEncoder},
 };
+use ic_canister_snapshot_archive::{
+    ArchiveError, MAX_SLICE_SIZE, Manifest, SnapshotArchive, SnapshotArchiveWriter,
+    types::{
+        ReadCanisterSnapshotDataResponse, ReadCanisterSnapshotMetadataArgs,
+        ReadCanisterSnapshotMetadataResponse, UploadCanisterSnapshotMetadataResponse,
+    },
+};
 use ic_certification::{Certificate, Label, LookupResult};
 use ic_management_canister_types::{
     CanisterId, CanisterIdRecord, CanisterInstallMode, CanisterLogRecord, CanisterSettings,
@@ -40,7 +47,7 @@ use slog::Level;
 use std::fs::read_dir;
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing::{debug, instrument, warn};
 use tracing_appender::non_blocking::WorkerGuard;
@@ -1304,6 +1311,79 @@ impl PocketIc {
         .await
     }
 
+    /// Download a canister snapshot into a local snapshot archive
+    /// (see the `ic-canister-snapshot-archive` crate for the format).
+    /// The directory `archive_dir` must either not exist yet or be empty.
+    #[instrument(skip(self), fields(instance_id=self.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string(), archive_dir = %archive_dir.display()))]
+    pub async fn download_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        sender: Option<Principal>,
+        snapshot_id: Vec<u8>,
+        archive_dir: &Path,
+    ) -> Result<Manifest, ArchiveError> {
+        let (metadata,): (ReadCanisterSnapshotMetadataResponse,) = call_snapshot_archive_method(
+            self,
+            canister_id,
+            sender,
+            "read_canister_snapshot_metadata",
+            ReadCanisterSnapshotMetadataArgs {
+                canister_id,
+                snapshot_id: snapshot_id.clone(),
+            },
+        )
+        .await?;
+        let mut writer =
+            SnapshotArchiveWriter::create(archive_dir, canister_id, snapshot_id, metadata)?;
+        for request in writer.read_requests(MAX_SLICE_SIZE) {
+            let (response,): (ReadCanisterSnapshotDataResponse,) = call_snapshot_archive_method(
+                self,
+                canister_id,
+                sender,
+                "read_canister_snapshot_data",
+                request.clone(),
+            )
+            .await?;
+            writer.write(&request, &response.chunk)?;
+        }
+        writer.finish()
+    }
+
+    /// Upload a local snapshot archive (see the `ic-canister-snapshot-archive` crate
+    /// for the format) as a new canister snapshot, optionally replacing an existing one.
+    /// Returns the ID of the new snapshot.
+    #[instrument(skip(self), fields(instance_id=self.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string(), archive_dir = %archive_dir.display()))]
+    pub async fn upload_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        sender: Option<Principal>,
+        replace_snapshot: Option<Vec<u8>>,
+        archive_dir: &Path,
+    ) -> Result<Vec<u8>, ArchiveError> {
+        let archive = SnapshotArchive::open(archive_dir)?;
+        let (response,): (UploadCanisterSnapshotMetadataResponse,) = call_snapshot_archive_method(
+            self,
+            canister_id,
+            sender,
+            "upload_canister_snapshot_metadata",
+            archive.upload_metadata_args(canister_id, replace_snapshot),
+        )
+        .await?;
+        for request in
+            archive.upload_requests(canister_id, response.snapshot_id.clone(), MAX_SLICE_SIZE)
+        {
+            call_snapshot_archive_method::<_, ()>(
+                self,
+                canister_id,
+                sender,
+                "upload_canister_snapshot_data",
+                request?,
+            )
+            .await?;
+        }
+        Ok(response.snapshot_id)
+    }
+
     /// Update canister settings.
     #[instrument(skip(self), fields(instance_id=self.instance_id, canister_id = %canister_id.to_string(), sender = %sender.unwrap_or(Principal::anonymous()).to_string()))]
     pub async fn update_canister_settings(
@@ -1719,6 +1799,34 @@ where
     .await
 }
 
+/// Calls a snapshot data method of the management canister on behalf of
+/// the snapshot archive functions, converting rejections into archive errors.
+async fn call_snapshot_archive_method<Input, Output>(
+    env: &PocketIc,
+    canister_id: CanisterId,
+    sender: Option<Principal>,
+    method: &str,
+    input: Input,
+) -> Result<Output, ArchiveError>
+where
+    Input: CandidType,
+    Output: for<'a> ArgumentDecoder<'a>,
+{
+    call_candid_as(
+        env,
+        Principal::management_canister(),
+        RawEffectivePrincipal::CanisterId(canister_id.as_slice().to_vec()),
+        sender.unwrap_or(Principal::anonymous()),
+        method,
+        (input,),
+    )
+    .await
+    .map_err(|reject| ArchiveError::Call {
+        method: method.to_string(),
+        message: reject.to_string(),
+    })
+}
+
 /// Call a canister candid method, anonymous.
 /// PocketIC executes update calls synchronously, so there is no need to poll for the result.
 pub async fn call_candid<Input, Output>(
@@ -1954,6 +1954,44 @@ fn test_canister_snapshots() {
     assert_eq!(snapshots[0].id, third_snapshot.id);
 }
 
+#[test]
+fn test_canister_snapshot_archive() {
+    let pic = PocketIc::new();
+    let canister_id = deploy_counter_canister(&pic);
+
+    // We bump the counter and take a snapshot.
+    let reply = call_counter_canister(&pic, canister_id, "write");
+    assert_eq!(reply, 1_u32.to_le_bytes().to_vec());
+    pic.stop_canister(canister_id, None).unwrap();
+    let snapshot = pic.take_canister_snapshot(canister_id, None, None).unwrap();
+
+    // We download the snapshot into a local archive.
+    let archive_dir = TempDir::new().unwrap();
+    let manifest = pic
+        .download_canister_snapshot(canister_id, None, snapshot.id.clone(), archive_dir.path())
+        .unwrap();
+    assert_eq!(manifest.canister_id, canister_id);
+    assert_eq!(manifest.snapshot_id, snapshot.id);
+
+    // We upload the archive to a canister on another PocketIC instance and load it.
+    let other_pic = PocketIc::new();
+    let other_canister_id = deploy_counter_canister(&other_pic);
+    let reply = call_counter_canister(&other_pic, other_canister_id, "read");
+    assert_eq!(reply, 0_u32.to_le_bytes().to_vec());
+    let uploaded_snapshot_id = other_pic
+        .upload_canister_snapshot(other_canister_id, None, None, archive_dir.path())
+        .unwrap();
+    other_pic.stop_canister(other_canister_id, None).unwrap();
+    other_pic
+        .load_canister_snapshot(other_canister_id, None, uploaded_snapshot_id)
+        .unwrap();
+    other_pic.start_canister(other_canister_id, None).unwrap();
+
+    // The counter has the value from the downloaded snapshot.
+    let reply = call_counter_canister(&other_pic, other_canister_id, "read");
+    assert_eq!(reply, 1_u32.to_le_bytes().to_vec());
+}
+
 #[test]
 fn test_wasm_chunk_store() {
     let pic = PocketIc::new();
@@ -1,17 +1,29 @@
 use clap::{Arg, Command, arg};
 use ic_artifact_pool::{
+    backup::BackupArtifact,
     certification_pool::CertificationPoolImpl,
     consensus_pool::{PoolSectionOps, UncachedConsensusPoolImpl},
+    idkg_pool::IDkgPoolImpl,
 };
 use ic_config::artifact_pool::ArtifactPoolConfig;
-use ic_interfaces::consensus_pool::*;
+use ic_interfaces::{
+    consensus_pool::*,
+    idkg::{IDkgChangeAction, IDkgPool},
+    p2p::consensus::MutablePool,
+};
 use ic_logger::{LoggerImpl, ReplicaLogger};
 use ic_metrics::MetricsRegistry;
 use ic_types::{
-    NodeId, PrincipalId,
+    Height, NodeId, PrincipalId,
     consensus::{
-        CatchUpPackage, ConsensusMessage, ConsensusMessageHashable,
+        Block, BlockPayload, CatchUpPackage, ConsensusMessage, ConsensusMessageHashable,
         certification::CertificationMessage,
+        dkg::DkgMessageId,
+        idkg::{IDkgBlockReader, IDkgMessage, IDkgStats, RequestId},
+    },
+    crypto::{
+        CryptoHash,
+        canister_threshold_sig::idkg::{IDkgDealingSupport, IDkgTranscriptParams},
     },
     time::current_time,
 };
@@ -23,22 +35,56 @@ use std::convert::TryFrom;
 use std::io::BufRead;
 use std::io::Write;
 use std::path::PathBuf;
+use std::time::Duration;
 
 fn main() {
     let mut app = Command::new("ic-consensus-pool-util")
         .version("0.1")
         .about("IC Consensus Pool Utility")
         .subcommand(
-            Command::new("export").about("Export data to stdout").arg(
-                Arg::new("artifact")
-                    .short('a')
-                    .long("artifact")
-                    .value_name("NAME")
-                    .help("Artifact name")
-                    .num_args(1..),
-            ),
+            Command::new("export")
+                .about("Export data to stdout")
+                .arg(
+                    Arg::new("artifact")
+                        .short('a')
+                        .long("artifact")
+                        .value_name("NAME")
+                        .help("Artifact name")
+                        .num_args(1..),
+                )
+                .args(height_range_args()),
         )
         .subcommand(Command::new("import").about("Import data from stdin"))
+        .subcommand(
+            Command::new("list")
+                .about("List the artifacts in the pool by height and type")
+                .args(height_range_args()),
+        )
+        .subcommand(
+            Command::new("coverage")
+                .about("Show the number of artifacts of each type per height")
+                .args(height_range_args()),
+        )
+        .subcommand(
+            Command::new("export-backup")
+                .about(
+                    "Export the artifacts in the given height range in the format of the \
+                     consensus backup",
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("DIR")
+                        .help(
+                            "Output directory, corresponding to the \
+                             <backup_dir>/<subnet_id>/<replica_version> backup directory",
+                        )
+                        .required(true)
+                        .num_args(1),
+                )
+                .args(height_range_args()),
+        )
         .subcommand(
             Command::new("export-cup-proto")
                 .about("Export the highest CatchUpPackage protobuf (binary) data")
@@ -66,6 +112,12 @@ fn main() {
         import(path)
     } else if let Some(matches) = matches.subcommand_matches("export-cup-proto") {
         export_cup_proto(path, matches)
+    } else if let Some(matches) = matches.subcommand_matches("list") {
+        list(path, matches)
+    } else if let Some(matches) = matches.subcommand_matches("coverage") {
+        coverage(path, matches)
+    } else if let Some(matches) = matches.subcommand_matches("export-backup") {
+        export_backup(path, matches)
     } else {
         eprintln!(
             "{}",
@@ -74,7 +126,7 @@ fn main() {
     }
 }
 
-const ALL_ARTIFACT_NAMES: [&str; 13] = [
+const ALL_ARTIFACT_NAMES: [&str; 20] = [
     "RandomBeacon",
     "Finalization",
     "Notarization",
@@ -88,8 +140,83 @@ const ALL_ARTIFACT_NAMES: [&str; 13] = [
     "CatchUpPackageShare",
     "Certification",
     "CertificationShare",
+    "IDkgDealing",
+    "IDkgDealingSupport",
+    "EcdsaSigShare",
+    "SchnorrSigShare",
+    "VetKdKeyShare",
+    "IDkgComplaint",
+    "IDkgOpening",
 ];
 
+fn height_range_args() -> [Arg; 2] {
+    [
+        Arg::new("from")
+            .long("from")
+            .value_name("HEIGHT")
+            .help("Lowest height to include (inclusive)")
+            .value_parser(clap::value_parser!(u64))
+            .num_args(1),
+        Arg::new("to")
+            .long("to")
+            .value_name("HEIGHT")
+            .help("Highest height to include (inclusive)")
+            .value_parser(clap::value_parser!(u64))
+            .num_args(1),
+    ]
+}
+
+/// Returns the height range given by the `from` and `to` arguments. Without
+/// `from` (resp. `to`), the range is unbounded below (resp. above), so no
+/// artifact is filtered out by height.
+fn parse_height_range(matches: &clap::ArgMatches) -> HeightRange {
+    HeightRange::new(
+        matches
+            .get_one::<u64>("from")
+            .map(|height| Height::from(*height))
+            .unwrap_or_else(|| Height::from(0)),
+        matches
+            .get_one::<u64>("to")
+            .map(|height| Height::from(*height))
+            .unwrap_or_else(|| Height::from(u64::MAX)),
+    )
+}
+
+/// Returns the minimum and maximum height of any validated consensus or
+/// certification artifact (including shares); or `None` if both pools are
+/// empty.
+fn pool_height_range(
+    pool: &dyn ConsensusPool,
+    certification_pool: &CertificationPoolImpl,
+) -> Option<HeightRange> {
+    let validated = pool.validated();
+    let ranges = [
+        validated.random_beacon().height_range(),
+        validated.random_beacon_share().height_range(),
+        validated.random_tape().height_range(),
+        validated.random_tape_share().height_range(),
+        validated.block_proposal().height_range(),
+        validated.notarization().height_range(),
+        validated.notarization_share().height_range(),
+        validated.finalization().height_range(),
+        validated.finalization_share().height_range(),
+        validated.catch_up_package().height_range(),
+        validated.catch_up_package_share().height_range(),
+        certification_pool.validated.certifications().height_range(),
+        certification_pool
+            .validated
+            .certification_shares()
+            .height_range(),
+    ];
+    let min = ranges.iter().flatten().map(|range| range.min).min()?;
+    let max = ranges.iter().flatten().map(|range| range.max).max()?;
+    Some(HeightRange::new(min, max))
+}
+
+fn contains(range: HeightRange, height: Height) -> bool {
+    range.min <= height && height <= range.max
+}
+
 fn parse_artifact_names(names: &[&str]) -> Vec<&'static str> {
     for name in names {
         if !ALL_ARTIFACT_NAMES
@@ -127,6 +254,94 @@ fn open_certification_pool(path: &str, read_only: bool) -> CertificationPoolImpl
     CertificationPoolImpl::new(node_id, config, log, MetricsRegistry::new())
 }
 
+/// The stats are only used when executing the IDKG protocol, not for inspecting
+/// and importing artifacts.
+struct NoOpIDkgStats;
+
+impl IDkgStats for NoOpIDkgStats {
+    fn update_active_transcripts(&self, _block_reader: &dyn IDkgBlockReader) {}
+    fn update_active_pre_signatures(&self, _block_reader: &dyn IDkgBlockReader) {}
+    fn record_support_validation(&self, _support: &IDkgDealingSupport, _duration: Duration) {}
+    fn record_support_aggregation(
+        &self,
+        _transcript_params: &IDkgTranscriptParams,
+        _support_shares: &[IDkgDealingSupport],
+        _duration: Duration,
+    ) {
+    }
+    fn record_transcript_creation(
+        &self,
+        _transcript_params: &IDkgTranscriptParams,
+        _duration: Duration,
+    ) {
+    }
+    fn update_active_signature_requests(&self, _requests: Vec<RequestId>) {}
+    fn record_sig_share_validation(&self, _request_id: &RequestId, _duration: Duration) {}
+    fn record_sig_share_aggregation(&self, _request_id: &RequestId, _duration: Duration) {}
+}
+
+/// Opens the IDKG pool. In read-only mode, returns `None` if the pool has no
+/// IDKG database (e.g. on subnets without IDKG), instead of creating one.
+fn open_idkg_pool(path: &str, read_only: bool) -> Option<IDkgPoolImpl> {
+    let logger = LoggerImpl::new(&Default::default(), "dump_consensus_pool".to_string());
+    let log = ReplicaLogger::new(logger.root.clone().into());
+
+    let path = PathBuf::from(path);
+    let mut config = ArtifactPoolConfig::new(path);
+    config.persistent_pool_read_only = read_only;
+    if read_only && !config.persistent_pool_db_path().join("idkg").is_dir() {
+        return None;
+    }
+    Some(IDkgPoolImpl::new(
+        config,
+        log,
+        MetricsRegistry::new(),
+        Box::new(NoOpIDkgStats),
+    ))
+}
+
+/// Returns all validated IDKG artifacts in the given height range, with the
+/// name of their type.
+fn idkg_artifacts(
+    pool: Option<&IDkgPoolImpl>,
+    range: HeightRange,
+) -> Vec<(&'static str, IDkgMessage)> {
+    let Some(pool) = pool.map(|pool| pool.validated()) else {
+        return vec![];
+    };
+    let artifacts = pool
+        .signed_dealings()
+        .map(|(id, x)| (id, "IDkgDealing", IDkgMessage::Dealing(x)))
+        .chain(
+            pool.dealing_support()
+                .map(|(id, x)| (id, "IDkgDealingSupport", IDkgMessage::DealingSupport(x))),
+        )
+        .chain(
+            pool.ecdsa_signature_shares()
+                .map(|(id, x)| (id, "EcdsaSigShare", IDkgMessage::EcdsaSigShare(x))),
+        )
+        .chain(
+            pool.schnorr_signature_shares()
+                .map(|(id, x)| (id, "SchnorrSigShare", IDkgMessage::SchnorrSigShare(x))),
+        )
+        .chain(
+            pool.vetkd_key_shares()
+                .map(|(id, x)| (id, "VetKdKeyShare", IDkgMessage::VetKdKeyShare(x))),
+        )
+        .chain(
+            pool.complaints()
+                .map(|(id, x)| (id, "IDkgComplaint", IDkgMessage::Complaint(x))),
+        )
+        .chain(
+            pool.openings()
+                .map(|(id, x)| (id, "IDkgOpening", IDkgMessage::Opening(x))),
+        );
+    artifacts
+        .filter(|(id, _, _)| contains(range, id.height()))
+        .map(|(_, name, msg)| (name, msg))
+        .collect()
+}
+
 fn from_str<'a, T: Deserialize<'a>>(json: &'a str) -> Result<T, serde_json::Error> {
     let mut json_de = Deserializer::from_str(json);
     let bytefmt_json_de = ByteFmtDeserializer::new_hex(&mut json_de);
@@ -151,70 +366,76 @@ fn export(path: &str, matches: &clap::ArgMatches) {
 
     let consensus_pool = open_consensus_pool(path, true);
     let certification_pool = open_certification_pool(path, true);
+    let idkg_pool = open_idkg_pool(path, true);
+    let range = parse_height_range(matches);
+    let validated = consensus_pool.validated();
 
     for artifact in artifacts {
         match artifact {
             "RandomBeacon" => {
-                for x in consensus_pool.validated().random_beacon().get_all() {
+                for x in validated.random_beacon().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "Finalization" => {
-                for x in consensus_pool.validated().finalization().get_all() {
+                for x in validated.finalization().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "Notarization" => {
-                for x in consensus_pool.validated().notarization().get_all() {
+                for x in validated.notarization().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "BlockProposal" => {
-                for x in consensus_pool.validated().block_proposal().get_all() {
+                for x in validated.block_proposal().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "RandomBeaconShare" => {
-                for x in consensus_pool.validated().random_beacon_share().get_all() {
+                for x in validated.random_beacon_share().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "NotarizationShare" => {
-                for x in consensus_pool.validated().notarization_share().get_all() {
+                for x in validated.notarization_share().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "FinalizationShare" => {
-                for x in consensus_pool.validated().finalization_share().get_all() {
+                for x in validated.finalization_share().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "RandomTape" => {
-                for x in consensus_pool.validated().random_tape().get_all() {
+                for x in validated.random_tape().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "RandomTapeShare" => {
-                for x in consensus_pool.validated().random_tape_share().get_all() {
+                for x in validated.random_tape_share().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "CatchUpPackage" => {
-                for x in consensus_pool.validated().catch_up_package().get_all() {
+                for x in validated.catch_up_package().get_by_height_range(range) {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "CatchUpPackageShare" => {
-                for x in consensus_pool
-                    .validated()
+                for x in validated
                     .catch_up_package_share()
-                    .get_all()
+                    .get_by_height_range(range)
                 {
                     println!("{}", to_string(&x.into_message()));
                 }
             }
             "Certification" => {
-                for x in certification_pool.validated.certifications().get_all() {
+                for x in certification_pool
+                    .validated
+                    .certifications()
+                    .get_by_height_range(range)
+                {
                     println!("{}", to_string(&CertificationMessage::Certification(x)));
                 }
             }
@@ -222,7 +443,7 @@ fn export(path: &str, matches: &clap::ArgMatches) {
                 for x in certification_pool
                     .validated
                     .certification_shares()
-                    .get_all()
+                    .get_by_height_range(range)
                 {
                     println!(
                         "{}",
@@ -230,6 +451,15 @@ fn export(path: &str, matches: &clap::ArgMatches) {
                     );
                 }
             }
+            "IDkgDealing" | "IDkgDealingSupport" | "EcdsaSigShare" | "SchnorrSigShare"
+            | "VetKdKeyShare" | "IDkgComplaint" | "IDkgOpening" => {
+                for (_, x) in idkg_artifacts(idkg_pool.as_ref(), range)
+                    .into_iter()
+                    .filter(|(name, _)| *name == artifact)
+                {
+                    println!("{}", to_string(&x));
+                }
+            }
             _ => unreachable!("Unsupported artifact name: {}", artifact),
         }
     }
@@ -238,6 +468,7 @@ fn export(path: &str, matches: &clap::ArgMatches) {
 fn import(path: &str) {
     let mut consensus_pool = open_consensus_pool(path, false);
     let certification_pool = open_certification_pool(path, false);
+    let mut idkg_pool = open_idkg_pool(path, false).expect("Failed to open IDKG pool");
     let stdin = std::io::stdin();
     for line in stdin.lock().lines() {
         let s = line.expect("Cannot read input");
@@ -250,6 +481,8 @@ fn import(path: &str) {
             consensus_pool.validated.mutate(ops);
         } else if let Ok(msg) = from_str::<CertificationMessage>(&s) {
             certification_pool.validated.insert(msg)
+        } else if let Ok(msg) = from_str::<IDkgMessage>(&s) {
+            idkg_pool.apply(vec![IDkgChangeAction::AddToValidated(msg)]);
         } else {
             panic!("Failed to parse JSON: {}", s);
         }
@@ -273,3 +506,193 @@ fn export_cup_proto(path: &str, matches: &clap::ArgMatches) {
     file.write_all(&buf)
         .unwrap_or_else(|err| panic!("Cannot write to file {filename}: {err:?}"));
 }
+
+fn to_hex(hash: &CryptoHash) -> String {
+    hash.0.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Describes the DKG payload of a block proposal.
+fn dkg_details(payload: &BlockPayload) -> String {
+    match payload {
+        BlockPayload::Summary(_) => "dkg=summary".to_string(),
+        BlockPayload::Data(data) => format!("dkg_dealings={}", data.dkg.messages.len()),
+    }
+}
+
+/// Prints one line per artifact in the height range, ordered by height:
+/// height, artifact type, artifact hash and (for block proposals) the kind
+/// of DKG payload. DKG dealings are not persisted in a pool of their own, so
+/// the ones included in block proposals are listed at the block's height.
+fn list(path: &str, matches: &clap::ArgMatches) {
+    let consensus_pool = open_consensus_pool(path, true);
+    let certification_pool = open_certification_pool(path, true);
+    let idkg_pool = open_idkg_pool(path, true);
+    let range = parse_height_range(matches);
+    let validated = consensus_pool.validated();
+
+    let mut rows = Vec::new();
+    let mut push_consensus = |name: &'static str, msg: ConsensusMessage| {
+        let id = msg.get_id();
+        let details = match &msg {
+            ConsensusMessage::BlockProposal(proposal) => {
+                let block: &Block = proposal.as_ref();
+                if let BlockPayload::Data(data) = block.payload.as_ref() {
+                    for dealing in data.dkg.messages.iter() {
+                        let dealing_id = DkgMessageId::from(dealing);
+                        rows.push((
+                            id.height,
+                            "DkgDealing",
+                            to_hex(dealing_id.hash.get_ref()),
+                            format!(
+                                "dealer={} dkg_start_height={}",
+                                dealing.signature.signer, dealing_id.height
+                            ),
+                        ));
+                    }
+                }
+                dkg_details(block.payload.as_ref())
+            }
+            _ => String::new(),
+        };
+        rows.push((id.height, name, to_hex(id.hash.digest()), details));
+    };
+    validated
+        .random_beacon()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("RandomBeacon", x.into_message()));
+    validated
+        .random_tape()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("RandomTape", x.into_message()));
+    validated
+        .block_proposal()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("BlockProposal", x.into_message()));
+    validated
+        .notarization()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("Notarization", x.into_message()));
+    validated
+        .finalization()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("Finalization", x.into_message()));
+    validated
+        .catch_up_package()
+        .get_by_height_range(range)
+        .for_each(|x| push_consensus("CatchUpPackage", x.into_message()));
+    for x in certification_pool
+        .validated
+        .certifications()
+        .get_by_height_range(range)
+    {
+        rows.push((
+            x.height,
+            "Certification",
+            to_hex(x.signed.content.hash.get_ref()),
+            String::new(),
+        ));
+    }
+    for (name, x) in idkg_artifacts(idkg_pool.as_ref(), range) {
+        let id = x.message_id();
+        rows.push((id.height(), name, to_hex(&id.hash()), String::new()));
+    }
+
+    rows.sort_by_key(|(height, _, _, _)| *height);
+    for (height, name, hash, details) in rows {
+        println!("{height}\t{name}\t{hash}\t{details}");
+    }
+}
+
+/// Prints a table with the number of validated artifacts of each type per
+/// height, for the heights in the given range that are covered by the pools.
+/// Heights without a notarization or finalization are where consensus is stuck.
+fn coverage(path: &str, matches: &clap::ArgMatches) {
+    let consensus_pool = open_consensus_pool(path, true);
+    let certification_pool = open_certification_pool(path, true);
+    let range = parse_height_range(matches);
+    let validated = consensus_pool.validated();
+
+    println!(
+        "HEIGHT\tBEACON\tPROPOSALS\tNOTARIZATION_SHARES\tNOTARIZATIONS\t\
+         FINALIZATION_SHARES\tFINALIZATIONS\tCUP\tCERTIFICATIONS"
+    );
+    let Some(pool_range) = pool_height_range(&consensus_pool, &certification_pool) else {
+        return;
+    };
+    for height in range.min.max(pool_range.min).get()..=range.max.min(pool_range.max).get() {
+        let height = Height::from(height);
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            height,
+            validated.random_beacon().get_by_height(height).count(),
+            validated.block_proposal().get_by_height(height).count(),
+            validated.notarization_share().get_by_height(height).count(),
+            validated.notarization().get_by_height(height).count(),
+            validated.finalization_share().get_by_height(height).count(),
+            validated.finalization().get_by_height(height).count(),
+            validated.catch_up_package().get_by_height(height).count(),
+            certification_pool
+                .validated
+                .certifications()
+                .get_by_height(height)
+                .count(),
+        );
+    }
+}
+
+/// Writes the artifacts in the height range that the consensus backup stores
+/// into the output directory, using the same file layout and encoding.
+fn export_backup(path: &str, matches: &clap::ArgMatches) {
+    let output = matches
+        .get_one::<String>("output")
+        .expect("Expect an output directory");
+    let output = PathBuf::from(output);
+    let consensus_pool = open_consensus_pool(path, true);
+    let range = parse_height_range(matches);
+    let validated = consensus_pool.validated();
+
+    let artifacts = validated
+        .random_beacon()
+        .get_by_height_range(range)
+        .map(ConsensusMessage::RandomBeacon)
+        .chain(
+            validated
+                .random_tape()
+                .get_by_height_range(range)
+                .map(ConsensusMessage::RandomTape),
+        )
+        .chain(
+            validated
+                .block_proposal()
+                .get_by_height_range(range)
+                .map(ConsensusMessage::BlockProposal),
+        )
+        .chain(
+            validated
+                .notarization()
+                .get_by_height_range(range)
+                .map(ConsensusMessage::Notarization),
+        )
+        .chain(
+            validated
+                .finalization()
+                .get_by_height_range(range)
+                .map(ConsensusMessage::Finalization),
+        )
+        .chain(
+            validated
+                .catch_up_package()
+                .get_by_height_range(range)
+                .map(ConsensusMessage::CatchUpPackage),
+        )
+        .flat_map(BackupArtifact::try_from);
+
+    let mut count = 0;
+    for artifact in artifacts {
+        artifact
+            .write_to_disk(&output)
+            .unwrap_or_else(|err| panic!("Cannot write to {}: {err:?}", output.display()));
+        count += 1;
+    }
+    eprintln!("Exported {count} artifacts to {}", output.display());
+}
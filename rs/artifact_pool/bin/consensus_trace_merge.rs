@@ -0,0 +1,126 @@
+//! Merges the consensus traces exported by the replicas of a subnet (via the
+//! `/_/consensus_trace` endpoint) into a single timeline.
+//!
+//! Each line of the timeline contains the height, the offset in milliseconds
+//! from the first event any node recorded at that height, the node and the
+//! event. With `--summary`, one line per height and node is printed instead,
+//! with the offsets at which the node saw the height finalized and delivered
+//! the batch.
+use clap::{Arg, ArgAction, Command};
+use ic_types::{
+    Height, NodeId, Time,
+    consensus::trace::{ConsensusTraceEvent, ConsensusTraceExport},
+};
+use std::collections::BTreeMap;
+
+fn main() {
+    let matches = Command::new("ic-consensus-trace-merge")
+        .version("0.1")
+        .about("Merges the consensus traces of several replicas into a timeline")
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .help("Print the finalization and batch delivery offsets per height and node")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("FILES")
+                .help("JSON files exported from /_/consensus_trace, one per node")
+                .required(true)
+                .num_args(1..),
+        )
+        .get_matches();
+
+    let exports: Vec<ConsensusTraceExport> = matches
+        .get_many::<String>("FILES")
+        .expect("Missing trace files")
+        .map(|file| {
+            let json = std::fs::read_to_string(file)
+                .unwrap_or_else(|err| panic!("Cannot read {file}: {err:?}"));
+            serde_json::from_str(&json).unwrap_or_else(|err| panic!("Cannot parse {file}: {err:?}"))
+        })
+        .collect();
+
+    let timeline = merge(exports);
+    if matches.get_flag("summary") {
+        print_summary(&timeline);
+    } else {
+        print_timeline(&timeline);
+    }
+}
+
+/// The events of all nodes per height, ordered by time.
+type Timeline = BTreeMap<Height, Vec<(Time, NodeId, ConsensusTraceEvent)>>;
+
+fn merge(exports: Vec<ConsensusTraceExport>) -> Timeline {
+    let mut timeline = Timeline::new();
+    for export in exports {
+        for trace in export.heights {
+            let events = timeline.entry(trace.height).or_default();
+            events.extend(
+                trace
+                    .events
+                    .into_iter()
+                    .map(|event| (event.time, export.node_id, event.event)),
+            );
+        }
+    }
+    for events in timeline.values_mut() {
+        events.sort_by_key(|(time, node_id, _)| (*time, *node_id));
+    }
+    timeline
+}
+
+/// Returns the offset of `time` from `start` in milliseconds.
+fn offset_millis(start: Time, time: Time) -> f64 {
+    time.saturating_duration_since(start).as_secs_f64() * 1_000.0
+}
+
+fn print_timeline(timeline: &Timeline) {
+    println!("HEIGHT\tOFFSET_MS\tNODE\tEVENT");
+    for (height, events) in timeline {
+        let Some((start, _, _)) = events.first() else {
+            continue;
+        };
+        for (time, node_id, event) in events {
+            println!(
+                "{height}\t{:.1}\t{node_id}\t{}",
+                offset_millis(*start, *time),
+                serde_json::to_string(event).expect("Failed to serialize to JSON"),
+            );
+        }
+    }
+}
+
+fn print_summary(timeline: &Timeline) {
+    println!("HEIGHT\tNODE\tFINALIZED_MS\tDELIVERED_MS");
+    for (height, events) in timeline {
+        let Some((start, _, _)) = events.first() else {
+            continue;
+        };
+        let mut nodes: BTreeMap<NodeId, (Option<Time>, Option<Time>)> = BTreeMap::new();
+        for (time, node_id, event) in events {
+            let (finalized, delivered) = nodes.entry(*node_id).or_default();
+            match event {
+                ConsensusTraceEvent::Finalized { .. } => {
+                    finalized.get_or_insert(*time);
+                }
+                ConsensusTraceEvent::BatchDelivered => {
+                    delivered.get_or_insert(*time);
+                }
+                _ => {}
+            }
+        }
+        let format = |time: Option<Time>| {
+            time.map(|time| format!("{:.1}", offset_millis(*start, time)))
+                .unwrap_or_else(|| "-".to_string())
+        };
+        for (node_id, (finalized, delivered)) in nodes {
+            println!(
+                "{height}\t{node_id}\t{}\t{}",
+                format(finalized),
+                format(delivered)
+            );
+        }
+    }
+}
@@ -1930,7 +1930,7 @@ impl PersistentIDkgPoolSection {
 
         let mut path = config.persistent_pool_validated_persistent_db_path;
         path.push("idkg");
-        if let Err(err) = std::fs::create_dir_all(path.as_path()) {
+        if !read_only && let Err(err) = std::fs::create_dir_all(path.as_path()) {
             panic!("Error creating IDKG dir {path:?}: {err:?}")
         }
         let db_env = Arc::new(create_db_env(
@@ -60,6 +60,41 @@ impl Ed25519KeyPair {
     }
 }
 
+/// An ML-DSA-65 (FIPS 204) key pair
+///
+/// Only the 32-byte seed is kept; the expanded key pair is derived from it
+/// deterministically whenever it is needed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MlDsa65KeyPair {
+    pub seed: [u8; 32],
+}
+
+impl MlDsa65KeyPair {
+    pub fn generate<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        Self { seed: rng.r#gen() }
+    }
+
+    fn expand(&self) -> ml_dsa::KeyPair<ml_dsa::MlDsa65> {
+        use ml_dsa::KeyGen;
+        ml_dsa::MlDsa65::key_gen_internal(&self.seed.into())
+    }
+
+    /// Returns the raw (FIPS 204 encoded) public key.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.expand().verifying_key().encode().to_vec()
+    }
+
+    /// Signs `msg` deterministically with an empty context string.
+    pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
+        self.expand()
+            .signing_key()
+            .sign_deterministic(msg, &[])
+            .expect("signing with an empty context cannot fail")
+            .encode()
+            .to_vec()
+    }
+}
+
 impl Secp256k1KeyPair {
     pub fn sign(&self, msg: &[u8]) -> Vec<u8> {
         self.sk.sign_message_with_ecdsa(msg).to_vec()
@@ -79,6 +114,7 @@ impl Secp256k1KeyPair {
 pub enum SigKeys {
     Ed25519(Ed25519KeyPair),
     EcdsaSecp256k1(Secp256k1KeyPair),
+    MlDsa65(MlDsa65KeyPair),
 }
 
 impl SigKeys {
@@ -135,6 +171,10 @@ impl Sender {
         Sender::SigKeys(SigKeys::Ed25519(keys))
     }
 
+    pub fn from_ml_dsa_65_key_pair(keys: MlDsa65KeyPair) -> Self {
+        Sender::SigKeys(SigKeys::MlDsa65(keys))
+    }
+
     pub fn from_secp256k1_keys(
         sk_bytes: &[u8],
         pk_bytes: &[u8],
@@ -164,6 +204,9 @@ impl Sender {
                 SigKeys::EcdsaSecp256k1(key_pair) => {
                     PrincipalId::new_self_authenticating(&key_pair.pk.serialize_der())
                 }
+                SigKeys::MlDsa65(key_pair) => PrincipalId::new_self_authenticating(
+                    &ml_dsa_65_public_key_to_der(key_pair.public_key()),
+                ),
             },
             Self::ExternalHsm { pub_key, .. } => PrincipalId::new_self_authenticating(pub_key),
             Self::Anonymous => PrincipalId::new_anonymous(),
@@ -195,6 +238,7 @@ impl Sender {
                 SigKeys::EcdsaSecp256k1(key_pair) => {
                     Ok(Some(key_pair.sk.sign_message_with_ecdsa(&msg).to_vec()))
                 }
+                SigKeys::MlDsa65(key_pair) => Ok(Some(key_pair.sign(&msg))),
             },
             Self::ExternalHsm { sign, .. } => sign(&msg).map(Some),
             Self::Anonymous => Ok(None),
@@ -210,6 +254,9 @@ impl Sender {
                     Some(ed25519_public_key_to_der(key_pair.public_key.to_vec()))
                 }
                 SigKeys::EcdsaSecp256k1(key_pair) => Some(key_pair.pk.serialize_der()),
+                SigKeys::MlDsa65(key_pair) => {
+                    Some(ml_dsa_65_public_key_to_der(key_pair.public_key()))
+                }
             },
             Self::ExternalHsm { pub_key, .. } => Some(pub_key.clone()),
             Self::Anonymous => None,
@@ -244,3 +291,15 @@ pub fn ed25519_public_key_from_der(mut key_der: Vec<u8>) -> Vec<u8> {
     key_der.drain(0..12);
     key_der
 }
+
+/// DER-encodes a raw ML-DSA-65 public key as SubjectPublicKeyInfo, as
+/// specified in RFC 9881.
+///
+/// # Panics
+/// If `key` is not a 1952-byte ML-DSA-65 public key.
+pub fn ml_dsa_65_public_key_to_der(key: Vec<u8>) -> Vec<u8> {
+    ic_crypto_internal_basic_sig_ml_dsa::public_key_to_der(
+        &ic_crypto_internal_basic_sig_ml_dsa::types::PublicKeyBytes(key),
+    )
+    .expect("invalid ML-DSA-65 public key")
+}
@@ -64,3 +64,41 @@ fn should_fail_to_read_key_for_wrong_curve() {
         .map(|_| ())
         .expect_err("The base64 payload should be a secp256k1 key");
 }
+
+#[test]
+fn should_der_encode_ml_dsa_65_public_key_of_sender() {
+    use super::{MlDsa65KeyPair, Sender};
+    use ic_base_types::PrincipalId;
+    use rand::SeedableRng;
+
+    let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(42);
+    let key_pair = MlDsa65KeyPair::generate(rng);
+    let sender = Sender::from_ml_dsa_65_key_pair(key_pair);
+
+    let pk_der = sender.sender_pubkey_der().expect("missing public key");
+
+    assert_eq!(
+        ic_crypto_internal_basic_sig_ml_dsa::public_key_from_der(&pk_der),
+        Ok(ic_crypto_internal_basic_sig_ml_dsa::types::PublicKeyBytes(
+            key_pair.public_key()
+        ))
+    );
+    assert_eq!(
+        sender.get_principal_id(),
+        PrincipalId::new_self_authenticating(&pk_der)
+    );
+}
+
+#[test]
+fn should_sign_deterministically_with_ml_dsa_65() {
+    use super::MlDsa65KeyPair;
+    use rand::SeedableRng;
+
+    let rng = &mut rand_chacha::ChaCha20Rng::seed_from_u64(42);
+    let key_pair = MlDsa65KeyPair::generate(rng);
+
+    let signature = key_pair.sign(b"message");
+
+    assert_eq!(signature.len(), 3309);
+    assert_eq!(signature, key_pair.sign(b"message"));
+}
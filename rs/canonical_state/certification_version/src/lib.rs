@@ -11,6 +11,8 @@ pub enum CertificationVersion {
     V21 = 21,
     /// Switch from `RequestOrResponse` to `StreamMessage`, adding `refund` variant.
     V22 = 22,
+    /// Added `congestion_level` to stream headers.
+    V23 = 23,
 }
 
 #[derive(Eq, PartialEq, Debug)]
@@ -54,7 +56,7 @@ pub const MIN_SUPPORTED_CERTIFICATION_VERSION: CertificationVersion = Certificat
 ///
 /// The replica will panic if requested to certify using a version higher than
 /// this.
-pub const MAX_SUPPORTED_CERTIFICATION_VERSION: CertificationVersion = CertificationVersion::V22;
+pub const MAX_SUPPORTED_CERTIFICATION_VERSION: CertificationVersion = CertificationVersion::V23;
 
 /// Returns a list of all certification versions from `MIN_SUPPORTED_CERTIFICATION_VERSION`
 /// up to `MAX_SUPPORTED_CERTIFICATION_VERSION`.
@@ -282,6 +282,7 @@ impl TryFrom<StreamHeaderV19> for ic_types::xnet::StreamHeader {
             header.signals_end.into(),
             reject_signals,
             flags,
+            0,
         ))
     }
 }
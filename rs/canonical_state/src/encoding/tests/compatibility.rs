@@ -71,6 +71,7 @@ fn canonical_encoding_minimal_stream_header() {
             256.into(),
             VecDeque::new(),
             StreamFlags::default(),
+            0,
         );
 
         assert_eq!(
@@ -80,6 +81,58 @@ fn canonical_encoding_minimal_stream_header() {
     }
 }
 
+/// Canonical CBOR encoding of:
+///
+/// ```no_run
+/// StreamHeader {
+///     begin: 23.into(),
+///     end: 25.into(),
+///     signals_end: 256.into(),
+///     reject_signals: Default::default(),
+///     flags: Default::default(),
+///     congestion_level: 3,
+/// }
+/// ```
+///
+/// Expected:
+///
+/// ```text
+/// A4         # map(4)
+///    00      # field_index(StreamHeader::begin)
+///    17      # unsigned(23)
+///    01      # field_index(StreamHeader::end)
+///    18 19   # unsigned(25)
+///    02      # field_index(StreamHeader::signals_end)
+///    19 0100 # unsigned(256)
+///    06      # field_index(StreamHeader::congestion_level)
+///    03      # unsigned(3)
+/// ```
+///
+/// Before certification version 23 the congestion level is not encoded.
+#[test]
+fn canonical_encoding_stream_header_with_congestion_level() {
+    for certification_version in all_supported_versions() {
+        let header = StreamHeader::new(
+            23.into(),
+            25.into(),
+            256.into(),
+            VecDeque::new(),
+            StreamFlags::default(),
+            3,
+        );
+
+        let expected = if certification_version >= CertificationVersion::V23 {
+            "A4 00 17 01 18 19 02 19 01 00 06 03"
+        } else {
+            "A3 00 17 01 18 19 02 19 01 00"
+        };
+        assert_eq!(
+            expected,
+            as_hex(&encode_stream_header(&header, certification_version))
+        );
+    }
+}
+
 /// Canonical CBOR encoding of:
 ///
 /// ```no_run
@@ -160,6 +213,7 @@ fn canonical_encoding_stream_header() {
             StreamFlags {
                 deprecated_responses_only: true,
             },
+            0,
         );
 
         assert_eq!(
@@ -1438,6 +1492,7 @@ fn stream_header(certification_version: CertificationVersion) -> StreamHeader {
         StreamFlags {
             deprecated_responses_only: true,
         },
+        0,
     )
 }
 
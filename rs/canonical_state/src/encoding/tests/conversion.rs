@@ -6,7 +6,8 @@ use ic_certification_version::CertificationVersion;
 use ic_error_types::RejectCode;
 use ic_protobuf::proxy::ProxyDecodeError;
 use ic_types::messages::{Payload, RejectContext, StreamMessage};
-use ic_types::xnet::RejectReason;
+use ic_types::xnet::testing::StreamHeaderTesting;
+use ic_types::xnet::{MAX_STREAM_CONGESTION_LEVEL, RejectReason};
 use std::convert::{TryFrom, TryInto};
 use strum::{EnumCount, IntoEnumIterator};
 
@@ -159,6 +160,42 @@ fn try_from_stream_header_with_field_index_3_populated() {
     );
 }
 
+/// Decoding a header with a congestion level above the maximum should return an
+/// error but not panic.
+#[test]
+fn try_from_stream_header_with_congestion_level_above_maximum() {
+    let mut header = types::StreamHeader::from((
+        &stream_header(MAX_SUPPORTED_CERTIFICATION_VERSION),
+        MAX_SUPPORTED_CERTIFICATION_VERSION,
+    ));
+    header.congestion_level = MAX_STREAM_CONGESTION_LEVEL as u64 + 1;
+
+    assert_matches!(
+        ic_types::xnet::StreamHeader::try_from(header),
+        Err(ProxyDecodeError::Other(message)) if message.contains("congestion level 5 exceeds maximum 4")
+    );
+}
+
+#[test]
+fn roundtrip_conversion_stream_header_with_congestion_level() {
+    let mut header = stream_header(CertificationVersion::V23);
+    header.set_congestion_level(MAX_STREAM_CONGESTION_LEVEL);
+
+    for certification_version in all_supported_versions() {
+        let decoded: ic_types::xnet::StreamHeader =
+            types::StreamHeader::from((&header, certification_version))
+                .try_into()
+                .unwrap();
+
+        // The congestion level is dropped before certification version 23.
+        if certification_version >= CertificationVersion::V23 {
+            assert_eq!(header, decoded);
+        } else {
+            assert_eq!(0, decoded.congestion_level());
+        }
+    }
+}
+
 #[test]
 fn roundtrip_conversion_request() {
     for certification_version in all_supported_versions() {
@@ -26,7 +26,7 @@ pub fn stream_header(_certification_version: CertificationVersion) -> StreamHead
         deprecated_responses_only: true,
     };
 
-    StreamHeader::new(23.into(), 25.into(), 256.into(), reject_signals, flags)
+    StreamHeader::new(23.into(), 25.into(), 256.into(), reject_signals, flags, 0)
 }
 
 pub fn request(_certification_version: CertificationVersion) -> StreamMessage {
@@ -16,7 +16,7 @@ use ic_protobuf::proxy::ProxyDecodeError;
 use ic_types::{
     Time,
     time::CoarseTime,
-    xnet::{RejectReason, RejectSignal, StreamIndex},
+    xnet::{MAX_STREAM_CONGESTION_LEVEL, RejectReason, RejectSignal, StreamIndex},
 };
 use serde::{Deserialize, Serialize};
 use std::{
@@ -42,6 +42,8 @@ pub struct StreamHeader {
     pub flags: u64,
     #[serde(default, skip_serializing_if = "RejectSignals::is_empty")]
     pub reject_signals: RejectSignals,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub congestion_level: u64,
 }
 
 /// Delta encoded reject signals: the last signal is encoded as the delta
@@ -256,6 +258,13 @@ impl From<(&ic_types::xnet::StreamHeader, CertificationVersion)> for StreamHeade
         )
             .into();
 
+        // The congestion level is only certified starting with certification version 23.
+        let congestion_level = if certification_version >= CertificationVersion::V23 {
+            header.congestion_level() as u64
+        } else {
+            0
+        };
+
         Self {
             begin: header.begin().get(),
             end: header.end().get(),
@@ -263,6 +272,7 @@ impl From<(&ic_types::xnet::StreamHeader, CertificationVersion)> for StreamHeade
             reserved_3: 0,
             flags,
             reject_signals,
+            congestion_level,
         }
     }
 }
@@ -282,6 +292,12 @@ impl TryFrom<StreamHeader> for ic_types::xnet::StreamHeader {
                 header.flags, STREAM_SUPPORTED_FLAGS,
             )));
         }
+        if header.congestion_level > MAX_STREAM_CONGESTION_LEVEL as u64 {
+            return Err(ProxyDecodeError::Other(format!(
+                "StreamHeader: congestion level {} exceeds maximum {}",
+                header.congestion_level, MAX_STREAM_CONGESTION_LEVEL,
+            )));
+        }
         let flags = ic_types::xnet::StreamFlags {
             deprecated_responses_only: header.flags
                 & StreamFlagBits::DeprecatedResponsesOnly as u64
@@ -296,6 +312,7 @@ impl TryFrom<StreamHeader> for ic_types::xnet::StreamHeader {
             header.signals_end.into(),
             reject_signals,
             flags,
+            header.congestion_level as u8,
         ))
     }
 }
@@ -363,6 +363,7 @@ mod tests {
             11.into(),
             VecDeque::default(),
             StreamFlags::default(),
+            0,
         );
 
         let stream = Stream::new(
@@ -229,7 +229,7 @@ pub(crate) fn arb_valid_versioned_message()
         ),
         (
             arbitrary::stream_message_with_config(true),
-            Just(CertificationVersion::V22..=CertificationVersion::V22)
+            Just(CertificationVersion::V22..=MAX_SUPPORTED_CERTIFICATION_VERSION)
         ),
     ]
 }
@@ -5,6 +5,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::net::SocketAddr;
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::path::PathBuf;
@@ -38,6 +39,36 @@ pub enum CspVaultType {
         logic: PathBuf,
         metrics: Option<PathBuf>,
     },
+    /// A remote CspVault reachable over vsock (e.g., running in a separate VM
+    /// on the same host), authenticated with mutual TLS.
+    Vsock {
+        cid: u32,
+        port: u32,
+        tls: RemoteCspVaultTlsConfig,
+    },
+    /// A remote CspVault reachable over TCP, authenticated with mutual TLS.
+    Tcp {
+        address: SocketAddr,
+        tls: RemoteCspVaultTlsConfig,
+    },
+}
+
+/// Mutual TLS configuration for a remote CspVault that is not reachable over
+/// a Unix domain socket.
+///
+/// The same structure is used on both ends of the connection: each side
+/// presents `certificate` (with the matching `private_key`) and only accepts
+/// peers whose certificate chains up to `ca_certificate`. All files are PEM
+/// encoded.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[cfg_attr(test, derive(Arbitrary))]
+pub struct RemoteCspVaultTlsConfig {
+    #[cfg_attr(test, proptest(strategy = "any::<String>().prop_map(PathBuf::from)"))]
+    pub certificate: PathBuf,
+    #[cfg_attr(test, proptest(strategy = "any::<String>().prop_map(PathBuf::from)"))]
+    pub private_key: PathBuf,
+    #[cfg_attr(test, proptest(strategy = "any::<String>().prop_map(PathBuf::from)"))]
+    pub ca_certificate: PathBuf,
 }
 
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
@@ -93,6 +124,33 @@ impl CryptoConfig {
         }
     }
 
+    /// Returns a new CryptoConfig with the given `crypto_root` path, with the
+    /// CspVault reachable over vsock at the given `cid` and `port`.
+    pub fn new_with_vsock_vault(
+        crypto_root: PathBuf,
+        cid: u32,
+        port: u32,
+        tls: RemoteCspVaultTlsConfig,
+    ) -> Self {
+        Self {
+            crypto_root,
+            csp_vault_type: CspVaultType::Vsock { cid, port, tls },
+        }
+    }
+
+    /// Returns a new CryptoConfig with the given `crypto_root` path, with the
+    /// CspVault reachable over TCP at the given `address`.
+    pub fn new_with_tcp_vault(
+        crypto_root: PathBuf,
+        address: SocketAddr,
+        tls: RemoteCspVaultTlsConfig,
+    ) -> Self {
+        Self {
+            crypto_root,
+            csp_vault_type: CspVaultType::Tcp { address, tls },
+        }
+    }
+
     /// Creates a new CryptoConfig in a temporary directory for testing.
     /// The directory has the permissions required for storing crypto state (see
     /// [`Self::check_dir_has_required_permissions`]) and will be automatically
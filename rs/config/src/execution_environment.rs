@@ -182,6 +182,13 @@ pub const MAX_ENVIRONMENT_VARIABLE_NAME_LENGTH: usize = 128;
 /// Environment variables are sized to comfortably accommodate the root key.
 pub const MAX_ENVIRONMENT_VARIABLE_VALUE_LENGTH: usize = 128;
 
+/// The maximum number of methods a canister can declare latency-sensitive.
+/// The limit keeps the per-round cost of inspecting the input queues low.
+pub const MAX_LATENCY_SENSITIVE_METHODS: usize = 8;
+
+/// The maximum length of the name of a latency-sensitive method.
+pub const MAX_LATENCY_SENSITIVE_METHOD_NAME_LENGTH: usize = 128;
+
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Config {
@@ -337,6 +344,10 @@ pub struct Config {
     /// by controllers.
     pub canister_snapshot_upload: FlagStatus,
 
+    /// Whether incremental canister snapshots, i.e. snapshots that only store
+    /// the memory pages that differ from a parent snapshot, can be taken.
+    pub incremental_canister_snapshots: FlagStatus,
+
     /// Whether environment variables are supported.
     pub environment_variables: FlagStatus,
 
@@ -349,6 +360,16 @@ pub struct Config {
     /// The maximum length of an environment variable value.
     pub max_environment_variable_value_length: usize,
 
+    /// Whether canisters can declare latency-sensitive methods that are
+    /// preferred by the scheduler.
+    pub latency_sensitive_methods: FlagStatus,
+
+    /// The maximum number of latency-sensitive methods per canister.
+    pub max_latency_sensitive_methods: usize,
+
+    /// The maximum length of the name of a latency-sensitive method.
+    pub max_latency_sensitive_method_name_length: usize,
+
     /// Enables the replicated inter-canister calls to `fetch_canister_logs`.
     pub replicated_inter_canister_log_fetch: FlagStatus,
 
@@ -440,10 +461,14 @@ impl Default for Config {
             max_number_of_snapshots_per_canister: MAX_NUMBER_OF_SNAPSHOTS_PER_CANISTER,
             canister_snapshot_download: FlagStatus::Enabled,
             canister_snapshot_upload: FlagStatus::Enabled,
+            incremental_canister_snapshots: FlagStatus::Disabled,
             environment_variables: FlagStatus::Enabled,
             max_environment_variables: MAX_ENVIRONMENT_VARIABLES,
             max_environment_variable_name_length: MAX_ENVIRONMENT_VARIABLE_NAME_LENGTH,
             max_environment_variable_value_length: MAX_ENVIRONMENT_VARIABLE_VALUE_LENGTH,
+            latency_sensitive_methods: FlagStatus::Disabled,
+            max_latency_sensitive_methods: MAX_LATENCY_SENSITIVE_METHODS,
+            max_latency_sensitive_method_name_length: MAX_LATENCY_SENSITIVE_METHOD_NAME_LENGTH,
             replicated_inter_canister_log_fetch: FlagStatus::Disabled,
             fetch_canister_logs_filter: FlagStatus::Disabled,
         }
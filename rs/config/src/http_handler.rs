@@ -56,6 +56,9 @@ pub struct Config {
     /// Serving at most `max_call_concurrent_requests` requests concurrently for endpoint `/api/v2/query`.
     pub max_query_concurrent_requests: usize,
 
+    /// Serving at most `max_dry_run_concurrent_requests` requests concurrently for endpoint `/api/v2/canister/{effective_canister_id}/dry_run`.
+    pub max_dry_run_concurrent_requests: usize,
+
     /// Serving at most `max_pprof_concurrent_requests` requessts concurrently for all endpoints under `/_/pprof`.
     pub max_pprof_concurrent_requests: usize,
 
@@ -82,6 +85,7 @@ impl Default for Config {
             max_status_concurrent_requests: 100,
             max_call_concurrent_requests: 50,
             max_query_concurrent_requests: QUERY_EXECUTION_THREADS_TOTAL * 100,
+            max_dry_run_concurrent_requests: QUERY_EXECUTION_THREADS_TOTAL * 10,
             max_pprof_concurrent_requests: 5,
             ingress_message_certificate_timeout_seconds: 10,
             max_tracing_flamegraph_concurrent_requests: 5,
@@ -21,6 +21,9 @@ pub const MAX_STREAM_MESSAGES: usize = 10_000;
 pub struct Config {
     pub xnet_ip_addr: String,
     pub xnet_port: u16,
+    /// Whether the XNet client should advertise `Accept-Encoding: zstd` and
+    /// thus request zstd compressed stream slices from other subnets.
+    pub xnet_zstd_compression: bool,
 }
 
 impl Default for Config {
@@ -28,6 +31,7 @@ impl Default for Config {
         Self {
             xnet_ip_addr: "127.0.0.1".to_string(),
             xnet_port: 2497,
+            xnet_zstd_compression: false,
         }
     }
 }
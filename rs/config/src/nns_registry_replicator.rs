@@ -12,12 +12,22 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     /// The duration to
     pub poll_delay_duration_ms: u64,
+    /// The number of registry versions whose changelog is kept in the local
+    /// store. Once as many versions were added, older versions are compacted
+    /// into a checkpoint and their changelog entries are deleted. If not set,
+    /// the local store keeps the changelog of all versions.
+    ///
+    /// Note that switching over to a new NNS subnet requires the changelog of
+    /// all versions.
+    #[serde(default)]
+    pub local_store_retained_versions: Option<u64>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             poll_delay_duration_ms: 5000,
+            local_store_retained_versions: None,
         }
     }
 }
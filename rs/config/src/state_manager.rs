@@ -17,6 +17,11 @@ pub struct Config {
     /// A config for LSMT storage.
     #[serde(default = "lsmt_config_default")]
     pub lsmt_config: LsmtConfig,
+    /// An optional read-only directory of old states (checkpoints named by
+    /// their hex encoded height, like under `<state_root>/checkpoints`) that
+    /// state sync may copy matching chunks from instead of fetching them.
+    #[serde(default)]
+    pub state_sync_old_states_dir: Option<PathBuf>,
 }
 
 impl Config {
@@ -25,6 +30,7 @@ impl Config {
             state_root,
             file_backed_memory_allocator: file_backed_memory_allocator_default(),
             lsmt_config: lsmt_config_default(),
+            state_sync_old_states_dir: None,
         }
     }
 
@@ -134,6 +134,12 @@ pub const SCHNORR_SIGNATURE_FEE: Cycles = Cycles::new(10 * B as u128);
 /// cover the cost of the subnet.
 pub const VETKD_FEE: Cycles = Cycles::new(10 * B as u128);
 
+/// 10B cycles corresponds to 1 SDR cent. Assuming we can create 1 signature per
+/// second, that would come to  26k SDR per month if we spent the whole time
+/// creating signatures. At 13 nodes and 2k SDR per node per month this would
+/// cover the cost of the subnet.
+pub const BLS_SIGNATURE_FEE: Cycles = Cycles::new(10 * B as u128);
+
 /// Default subnet size which is used to scale cycles cost according to a subnet replication factor.
 ///
 /// All initial costs were calculated with the assumption that a subnet had 13 replicas.
@@ -441,6 +447,9 @@ pub struct CyclesAccountManagerConfig {
     /// Amount to charge for vet KD.
     pub vetkd_fee: Cycles,
 
+    /// Amount to charge for a threshold BLS signature.
+    pub bls_signature_fee: Cycles,
+
     /// A linear factor of the baseline cost to be charged for HTTP requests per node.
     /// The cost of an HTTP request is represented by a quadratic function due to the communication complexity of the subnet.
     pub http_request_linear_baseline_fee: Cycles,
@@ -497,6 +506,7 @@ impl CyclesAccountManagerConfig {
             ecdsa_signature_fee: ECDSA_SIGNATURE_FEE,
             schnorr_signature_fee: SCHNORR_SIGNATURE_FEE,
             vetkd_fee: VETKD_FEE,
+            bls_signature_fee: BLS_SIGNATURE_FEE,
             http_request_linear_baseline_fee: Cycles::new(3_000_000),
             http_request_quadratic_baseline_fee: Cycles::new(60_000),
             http_request_per_byte_fee: Cycles::new(400),
@@ -538,6 +548,7 @@ impl CyclesAccountManagerConfig {
             ecdsa_signature_fee: ECDSA_SIGNATURE_FEE,
             schnorr_signature_fee: SCHNORR_SIGNATURE_FEE,
             vetkd_fee: VETKD_FEE,
+            bls_signature_fee: BLS_SIGNATURE_FEE,
             http_request_linear_baseline_fee: Cycles::new(0),
             http_request_quadratic_baseline_fee: Cycles::new(0),
             http_request_per_byte_fee: Cycles::new(0),
@@ -567,6 +578,7 @@ impl CyclesAccountManagerConfig {
             ecdsa_signature_fee: Cycles::zero(),
             schnorr_signature_fee: Cycles::zero(),
             vetkd_fee: Cycles::zero(),
+            bls_signature_fee: Cycles::zero(),
             http_request_linear_baseline_fee: Cycles::zero(),
             http_request_quadratic_baseline_fee: Cycles::zero(),
             http_request_per_byte_fee: Cycles::zero(),
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::default::Default;
 
 /// The transport format specified in the ic.json
@@ -14,6 +15,37 @@ pub struct TransportConfig {
     /// Transport creates 'max_streams' logical streams/channels between two peers.
     /// Channel ids should be within [0..max_streams).
     pub max_streams: usize,
+
+    /// Overrides of the default traffic shaping of the transport.
+    pub traffic_shaping: TrafficShapingConfig,
+}
+
+/// Traffic classes of the transport, in decreasing order of importance.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrafficClass {
+    Critical,
+    Default,
+    Bulk,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub struct TrafficClassConfig {
+    /// Relative share of the bandwidth of a congested connection.
+    pub weight: u32,
+    /// Optional limit of the bytes per second that are sent to a single peer.
+    pub max_bytes_per_second_per_peer: Option<u64>,
+}
+
+/// Overrides of the default traffic shaping of the transport. Both maps are
+/// merged into the defaults, replacing the entries with the same key.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TrafficShapingConfig {
+    /// Request URI path prefixes mapped to the traffic class of requests to them.
+    pub routes: BTreeMap<String, TrafficClass>,
+    /// The configuration of the shaped classes.
+    pub classes: BTreeMap<TrafficClass, TrafficClassConfig>,
 }
 
 impl Default for TransportConfig {
@@ -23,6 +55,7 @@ impl Default for TransportConfig {
             node_ip: String::default(),
             listening_port: u16::default(),
             max_streams: 1,
+            traffic_shaping: TrafficShapingConfig::default(),
         }
     }
 }
@@ -46,7 +46,7 @@ pub fn get_vetkey_public_keys(
 
     let (master_keys, dkg_ids) = transcripts
         .iter()
-        // Filter out transcripts that are not for VetKD
+        // Filter out transcripts that are not for a NiDkg-based chain key
         .filter_map(|(tag, &transcript)| match tag {
             NiDkgTag::HighThresholdForKey(key_id) => Some((key_id, transcript)),
             _ => None,
@@ -66,11 +66,12 @@ pub fn get_vetkey_public_keys(
         )
         // Unzip the data into the two maps that delivery needs
         .map(|(key_id, pubkey, ni_dkg_id)| {
+            let master_key_id = MasterPublicKeyId::from(key_id.clone());
             (
                 (
-                    key_id.clone().into(),
+                    master_key_id.clone(),
                     MasterPublicKey {
-                        algorithm_id: AlgorithmId::VetKD,
+                        algorithm_id: AlgorithmId::from(&master_key_id),
                         public_key: pubkey.into_bytes().to_vec(),
                     },
                 ),
@@ -143,10 +144,7 @@ pub(crate) fn vetkd_key_ids_for_subnet(
     let keys = chain_key_config
         .key_configs
         .into_iter()
-        .filter_map(|config| match config.key_id {
-            MasterPublicKeyId::VetKd(key_id) => Some(NiDkgMasterPublicKeyId::VetKd(key_id)),
-            _ => None,
-        })
+        .filter_map(|config| NiDkgMasterPublicKeyId::try_from(config.key_id).ok())
         .collect::<Vec<_>>();
 
     Ok(keys)
@@ -725,7 +725,7 @@ mod tests {
             match &key_id {
                 MasterPublicKeyId::Ecdsa(_) => context_transcripts += 5, // quadruple + key transcript
                 MasterPublicKeyId::Schnorr(_) => context_transcripts += 2, // blinder + key transcript
-                MasterPublicKeyId::VetKd(_) => {}                          // No IDkgTranscripts
+                MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {} // No IDkgTranscripts
             }
             contexts.insert(
                 callback_id,
@@ -1292,7 +1292,9 @@ mod tests {
                         signature: vec![2; 32],
                     })
                 }
-                MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                    panic!("not applicable to NiDkg keys")
+                }
             },
         );
 
@@ -2151,7 +2153,9 @@ mod tests {
                         &mut rng,
                     ))
                 }
-                MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                    panic!("not applicable to NiDkg keys")
+                }
             };
             payload_0.available_pre_signatures.insert(
                 payload_0.uid_generator.next_pre_signature_id(),
@@ -396,8 +396,8 @@ fn make_new_pre_signatures_if_needed_helper(
                 ));
                 new_pre_signatures.insert(uid_generator.next_pre_signature_id(), pre_signature);
             }
-            MasterPublicKeyId::VetKd(_vetkd_key_id) => {
-                // vetKD does not have pre-signatures
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                // NiDkg keys do not have pre-signatures
             }
         };
     }
@@ -550,8 +550,8 @@ fn start_pre_signature_in_creation(
                 blinder_config,
             ))
         }
-        MasterPublicKeyId::VetKd(_vetkd_key_id) => {
-            // vetKD does not have pre-signatures
+        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+            // NiDkg keys do not have pre-signatures
             unreachable!("Not an IDkg Key ID");
         }
     }
@@ -700,7 +700,9 @@ pub(super) mod test_utils {
                     blinder_config_ref,
                 ))
             }
-            MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                panic!("not applicable to NiDkg keys")
+            }
         };
         let configs = pre_signature
             .iter_transcript_configs_in_creation()
@@ -1534,7 +1536,9 @@ pub(super) mod tests {
         let expected_transcript_ids = match key_id.inner() {
             MasterPublicKeyId::Ecdsa(_) => 2 * expected_pre_signatures_in_creation,
             MasterPublicKeyId::Schnorr(_) => expected_pre_signatures_in_creation,
-            MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                panic!("not applicable to NiDkg keys")
+            }
         };
         assert_eq!(transcript_ids.len(), expected_transcript_ids);
         assert_eq!(
@@ -1616,10 +1620,19 @@ pub(super) mod tests {
     }
 
     #[test]
-    fn test_ecdsa_update_pre_signatures_in_creation() {
+    fn test_ecdsa_update_pre_signatures_in_creation_all_curves() {
+        for key_id in [
+            fake_ecdsa_idkg_master_public_key_id(),
+            fake_ecdsa_secp256r1_idkg_master_public_key_id(),
+        ] {
+            println!("Running test for key ID {key_id}");
+            test_ecdsa_update_pre_signatures_in_creation(key_id);
+        }
+    }
+
+    fn test_ecdsa_update_pre_signatures_in_creation(key_id: IDkgMasterPublicKeyId) {
         let mut rng = reproducible_rng();
         let subnet_id = subnet_test_id(1);
-        let key_id = fake_ecdsa_idkg_master_public_key_id();
         let (mut payload, env, mut block_reader) =
             set_up(&mut rng, subnet_id, vec![key_id.clone()], Height::from(100));
         let mut transcripts = BTreeMap::new();
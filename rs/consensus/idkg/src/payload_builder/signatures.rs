@@ -323,7 +323,9 @@ mod tests {
                             signature: vec![i as u8; 32],
                         })
                     }
-                    MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                    MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                        panic!("not applicable to NiDkg keys")
+                    }
                 },
             );
         }
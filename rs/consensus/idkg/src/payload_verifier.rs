@@ -1164,7 +1164,9 @@ mod test {
                     MasterPublicKeyId::Schnorr(_) => {
                         SignWithSchnorrReply { signature: vec![] }.encode()
                     }
-                    MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                    MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                        panic!("not applicable to NiDkg keys")
+                    }
                 }),
             ));
 
@@ -632,10 +632,12 @@ impl ThresholdSigner for ThresholdSignerImpl {
                         height,
                     })
                 }
-                ThresholdArguments::VetKd(args) => Some(RequestId {
-                    callback_id: *callback_id,
-                    height: args.height,
-                }),
+                ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => {
+                    context.nidkg_height().map(|height| RequestId {
+                        callback_id: *callback_id,
+                        height,
+                    })
+                }
             })
             .collect();
         idkg_pool
@@ -883,6 +885,7 @@ mod tests {
         consensus::idkg::*,
         crypto::{
             AlgorithmId, ExtendedDerivationPath, canister_threshold_sig::idkg::IDkgReceivers,
+            threshold_sig::ni_dkg::NiDkgMasterPublicKeyId,
         },
         time::UNIX_EPOCH,
     };
@@ -1262,7 +1265,9 @@ mod tests {
                 let expected_complaints_count = match key_id.inner() {
                     MasterPublicKeyId::Ecdsa(_) => requested_signatures_count * 5,
                     MasterPublicKeyId::Schnorr(_) => requested_signatures_count * 2,
-                    MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                    MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                        panic!("not applicable to NiDkg keys")
+                    }
                 };
                 let complaints = transcript_loader.returned_complaints();
                 assert_eq!(change_set.len(), complaints.len());
@@ -1379,7 +1384,9 @@ mod tests {
                             &ThresholdSigInputs::Schnorr(inputs.as_ref()),
                         );
                     }
-                    MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+                    MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                        panic!("not applicable to NiDkg keys")
+                    }
                 }
             })
         })
@@ -1513,7 +1520,9 @@ mod tests {
                 fake_schnorr_idkg_master_public_key_id(SchnorrAlgorithm::Ed25519).into()
             }
             MasterPublicKeyId::Schnorr(_) => fake_vetkd_master_public_key_id(),
-            MasterPublicKeyId::VetKd(_) => fake_ecdsa_idkg_master_public_key_id().into(),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                fake_ecdsa_idkg_master_public_key_id().into()
+            }
         };
         let message = create_signature_share(&key_id_wrong_scheme, NODE_2, id_2);
         let msg_id_2 = message.message_id();
@@ -2150,7 +2159,7 @@ mod tests {
                         key_id: key_id.clone(),
                         input: Arc::new(vec![]),
                         transport_public_key: vec![],
-                        ni_dkg_id: fake_dkg_id(key_id),
+                        ni_dkg_id: fake_dkg_id(NiDkgMasterPublicKeyId::VetKd(key_id.clone())),
                         height,
                     }),
                     pseudo_random_id: [1; 32],
@@ -885,14 +885,16 @@ pub(crate) fn create_signature_share_with_nonce(
                 sig_share_raw: vec![nonce],
             },
         }),
-        MasterPublicKeyId::VetKd(_) => IDkgMessage::VetKdKeyShare(VetKdKeyShare {
-            signer_id,
-            request_id,
-            share: VetKdEncryptedKeyShare {
-                encrypted_key_share: VetKdEncryptedKeyShareContent(vec![nonce]),
-                node_signature: vec![nonce],
-            },
-        }),
+        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+            IDkgMessage::VetKdKeyShare(VetKdKeyShare {
+                signer_id,
+                request_id,
+                share: VetKdEncryptedKeyShare {
+                    encrypted_key_share: VetKdEncryptedKeyShareContent(vec![nonce]),
+                    node_signature: vec![nonce],
+                },
+            })
+        }
     }
 }
 
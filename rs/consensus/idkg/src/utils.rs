@@ -17,7 +17,7 @@ use ic_protobuf::registry::subnet::v1 as pb;
 use ic_registry_client_helpers::subnet::SubnetRegistry;
 use ic_registry_subnet_features::ChainKeyConfig;
 use ic_replicated_state::metadata_state::subnet_call_context_manager::{
-    SignWithThresholdContext, ThresholdArguments,
+    BlsArguments, SignWithThresholdContext, ThresholdArguments, VetKdArguments,
 };
 use ic_types::{
     Height, RegistryVersion, SubnetId,
@@ -31,12 +31,9 @@ use ic_types::{
             common::{BuildSignatureInputsError, ThresholdSigInputs},
         },
     },
-    crypto::{
-        canister_threshold_sig::{
-            MasterPublicKey, ThresholdEcdsaSigInputs, ThresholdSchnorrSigInputs,
-            idkg::{IDkgTranscript, IDkgTranscriptOperation, InitialIDkgDealings},
-        },
-        vetkd::{VetKdArgs, VetKdDerivationContext},
+    crypto::canister_threshold_sig::{
+        MasterPublicKey, ThresholdEcdsaSigInputs, ThresholdSchnorrSigInputs,
+        idkg::{IDkgTranscript, IDkgTranscriptOperation, InitialIDkgDealings},
     },
     messages::CallbackId,
     registry::RegistryClientError,
@@ -285,21 +282,16 @@ pub(super) fn build_signature_inputs<'a>(
             );
             Ok((request_id, inputs))
         }
-        ThresholdArguments::VetKd(args) => {
+        ThresholdArguments::VetKd(VetKdArguments { height, .. })
+        | ThresholdArguments::Bls(BlsArguments { height, .. }) => {
             let request_id = RequestId {
                 callback_id,
-                height: args.height,
+                height: *height,
             };
-            let inputs = ThresholdSigInputs::VetKd(VetKdArgs {
-                context: VetKdDerivationContext {
-                    caller: context.request.sender.into(),
-                    context: context.derivation_path.iter().flatten().cloned().collect(),
-                },
-                ni_dkg_id: args.ni_dkg_id.clone(),
-                input: args.input.to_vec(),
-                transport_public_key: args.transport_public_key.clone(),
-            });
-            Ok((request_id, inputs))
+            let args = context
+                .vetkd_protocol_args()
+                .ok_or(BuildSignatureInputsError::ContextIncomplete)?;
+            Ok((request_id, ThresholdSigInputs::VetKd(args)))
         }
     }
 }
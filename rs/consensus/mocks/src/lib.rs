@@ -49,6 +49,8 @@ mock! {
             payload: &Payload,
             past_payloads: &[(Height, Time, Payload)],
         ) -> ValidationResult<PayloadValidationError>;
+
+        fn has_pending_ingress(&self) -> bool;
     }
 }
 
@@ -89,6 +91,10 @@ impl PayloadBuilder for RefMockPayloadBuilder {
             .unwrap()
             .validate_payload(height, proposal_context, payload, past_payloads)
     }
+
+    fn has_pending_ingress(&self) -> bool {
+        self.mock.read().unwrap().has_pending_ingress()
+    }
 }
 
 pub struct Dependencies {
@@ -18,16 +18,26 @@ mod random_beacon_maker;
 mod random_tape_maker;
 mod share_aggregator;
 mod status;
+pub mod trace;
 pub mod validator;
 
 #[cfg(all(test, feature = "proptest"))]
 mod proptests;
 
 use crate::consensus::{
-    block_maker::BlockMaker, catchup_package_maker::CatchUpPackageMaker, finalizer::Finalizer,
-    metrics::ConsensusMetrics, notary::Notary, payload_builder::PayloadBuilderImpl,
-    priority::new_bouncer, purger::Purger, random_beacon_maker::RandomBeaconMaker,
-    random_tape_maker::RandomTapeMaker, share_aggregator::ShareAggregator, validator::Validator,
+    block_maker::BlockMaker,
+    catchup_package_maker::CatchUpPackageMaker,
+    finalizer::Finalizer,
+    metrics::ConsensusMetrics,
+    notary::Notary,
+    payload_builder::PayloadBuilderImpl,
+    priority::new_bouncer,
+    purger::Purger,
+    random_beacon_maker::RandomBeaconMaker,
+    random_tape_maker::RandomTapeMaker,
+    share_aggregator::ShareAggregator,
+    trace::{ConsensusTrace, MAX_TRACED_HEIGHTS},
+    validator::Validator,
 };
 use ic_consensus_dkg::DkgKeyManager;
 use ic_consensus_utils::{
@@ -138,6 +148,7 @@ pub struct ConsensusImpl {
     aggregator: ShareAggregator,
     purger: Purger,
     metrics: ConsensusMetrics,
+    trace: Arc<ConsensusTrace>,
     time_source: Arc<dyn TimeSource>,
     registry_client: Arc<dyn RegistryClient>,
     state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
@@ -211,6 +222,11 @@ impl ConsensusImpl {
         last_invoked.insert(ConsensusSubcomponent::Aggregator, current_time);
         last_invoked.insert(ConsensusSubcomponent::Purger, current_time);
 
+        let trace = Arc::new(ConsensusTrace::new(
+            Arc::clone(&time_source) as Arc<_>,
+            MAX_TRACED_HEIGHTS,
+        ));
+
         ConsensusImpl {
             dkg_key_manager,
             notary: Notary::new(
@@ -219,6 +235,7 @@ impl ConsensusImpl {
                 membership.clone(),
                 crypto.clone(),
                 state_manager.clone(),
+                payload_builder.clone(),
                 metrics_registry.clone(),
                 logger.clone(),
             ),
@@ -229,6 +246,7 @@ impl ConsensusImpl {
                 crypto.clone(),
                 message_routing.clone(),
                 ingress_selector.clone(),
+                Arc::clone(&trace),
                 logger.clone(),
                 metrics_registry.clone(),
             ),
@@ -297,6 +315,7 @@ impl ConsensusImpl {
                 metrics_registry.clone(),
             ),
             metrics: ConsensusMetrics::new(metrics_registry),
+            trace,
             log: logger,
             time_source,
             registry_client,
@@ -308,6 +327,11 @@ impl ConsensusImpl {
         }
     }
 
+    /// Returns the per-height trace of the consensus events of this replica.
+    pub fn trace(&self) -> Arc<ConsensusTrace> {
+        Arc::clone(&self.trace)
+    }
+
     /// Call the given sub-component's `on_state_change` function, mark the
     /// time it takes to complete, increment its invocation counter, and mark
     /// the size of the [`Mutations`] result.
@@ -533,6 +557,7 @@ impl<T: ConsensusPool> PoolMutationsProducer<T> for ConsensusImpl {
         );
         let unit_delay = settings.unit_delay;
         let current_time = self.time_source.get_relative_time();
+        self.trace.record_mutations(&changeset);
         for (component, last_invoked_time) in self.last_invoked.borrow().iter() {
             let time_since_last_invoked =
                 current_time.saturating_duration_since(*last_invoked_time);
@@ -7,8 +7,8 @@ use crate::consensus::{
 use ic_consensus_dkg::payload_builder::create_payload as create_dkg_payload;
 use ic_consensus_idkg::{self as idkg, metrics::IDkgPayloadMetrics};
 use ic_consensus_utils::{
-    find_lowest_ranked_non_disqualified_proposals, get_notarization_delay_settings,
-    get_subnet_record,
+    find_lowest_ranked_non_disqualified_proposals, get_initial_notary_delay,
+    get_notarization_delay_settings, get_subnet_record, has_pending_input,
     membership::Membership,
     pool_reader::{PoolReader, UnexpectedChainLength},
 };
@@ -143,6 +143,12 @@ impl BlockMaker {
                         rank,
                         self.time_source.as_ref(),
                         Some(&self.metrics),
+                        || {
+                            has_pending_input(
+                                self.payload_builder.as_ref(),
+                                self.state_manager.get_latest_state().get_ref(),
+                            )
+                        },
                     )
                 {
                     self.propose_block(pool, rank, parent)
@@ -219,14 +225,15 @@ impl BlockMaker {
         // The idea behind setting this value to the initial_notary_delay is that when
         // replicas' clocks fall behind, they'll still be incrementing the block time at
         // a degraded, but reasonable rate, instead of the time falling flat. We add 1ns
-        // to the initial_notary_delay to ensure the delta is always > 0.
+        // to the initial_notary_delay to ensure the delta is always > 0. With adaptive
+        // block time, we use the smallest initial_notary_delay the subnet may use.
         let monotonic_block_increment = get_notarization_delay_settings(
             &self.log,
             &*self.registry_client,
             self.replica_config.subnet_id,
             registry_version,
         )
-        .initial_notary_delay
+        .min_initial_notary_delay()
             + Duration::from_nanos(1);
 
         // If we have previously tried to make a payload but got an error at the given
@@ -558,7 +565,10 @@ fn count_non_rank_0_blocks(pool: &PoolReader, block: Block) -> usize {
 }
 
 /// Calculate the required delay for block making based on the block maker's
-/// rank and the number of non-0-rank blocks in its ancestry.
+/// rank and the number of non-0-rank blocks in its ancestry. With adaptive block
+/// time, the delay is further increased by how much the initial notary delay
+/// of this round exceeds its minimum, so that the whole round stretches on an
+/// idle subnet. `has_pending_input` is forwarded to [`get_initial_notary_delay`].
 pub(super) fn get_block_maker_delay(
     log: &ReplicaLogger,
     registry_client: &dyn RegistryClient,
@@ -568,9 +578,13 @@ pub(super) fn get_block_maker_delay(
     registry_version: RegistryVersion,
     rank: Rank,
     metrics: Option<&BlockMakerMetrics>,
+    has_pending_input: impl FnOnce() -> bool,
 ) -> Duration {
     let settings =
         get_notarization_delay_settings(log, registry_client, subnet_id, registry_version);
+    let adaptive_delay =
+        get_initial_notary_delay(&settings, pool, parent.clone(), has_pending_input)
+            .saturating_sub(settings.min_initial_notary_delay());
     // If this is not a Rank-0 block maker, check how many non-rank-0 blocks have been notarized in
     // the past, and increase the delay if there have been too many.
     let dynamic_delay = if rank > Rank(0)
@@ -584,7 +598,7 @@ pub(super) fn get_block_maker_delay(
         Duration::ZERO
     };
 
-    settings.unit_delay * rank.0 as u32 + dynamic_delay
+    settings.unit_delay * rank.0 as u32 + dynamic_delay + adaptive_delay
 }
 
 /// Return true if the time since round start is greater than the required block
@@ -599,6 +613,7 @@ pub(super) fn is_time_to_make_block(
     rank: Rank,
     time_source: &dyn TimeSource,
     metrics: Option<&BlockMakerMetrics>,
+    has_pending_input: impl FnOnce() -> bool,
 ) -> bool {
     let Some(registry_version) = pool.registry_version(height) else {
         return false;
@@ -612,6 +627,7 @@ pub(super) fn is_time_to_make_block(
         registry_version,
         rank,
         metrics,
+        has_pending_input,
     );
 
     // If the relative time indicates that not enough time has passed, we fall
@@ -740,6 +756,7 @@ mod tests {
                     RegistryVersion::from(10),
                     Rank(4),
                     /*metrics=*/ None,
+                    /*has_pending_input=*/ || false,
                 );
             let expected_context = ValidationContext {
                 certified_height,
@@ -1344,6 +1361,7 @@ mod tests {
                 RegistryVersion::from(registry_version),
                 block_maker_rank,
                 /*metrics=*/ None,
+                /*has_pending_input=*/ || false,
             )
         })
     }
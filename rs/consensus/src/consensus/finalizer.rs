@@ -19,6 +19,7 @@
 use crate::consensus::{
     batch_delivery::deliver_batches_with_result_processor,
     metrics::{BatchStats, BlockStats, FinalizerMetrics},
+    trace::ConsensusTrace,
 };
 use ic_consensus_utils::{
     crypto::ConsensusCrypto, membership::Membership, pool_reader::PoolReader,
@@ -45,6 +46,7 @@ pub(crate) struct Finalizer {
     pub(crate) crypto: Arc<dyn ConsensusCrypto>,
     message_routing: Arc<dyn MessageRouting>,
     ingress_selector: Arc<dyn IngressSelector>,
+    trace: Arc<ConsensusTrace>,
     log: ReplicaLogger,
     metrics: FinalizerMetrics,
     prev_finalized_height: RefCell<Height>,
@@ -60,6 +62,7 @@ impl Finalizer {
         crypto: Arc<dyn ConsensusCrypto>,
         message_routing: Arc<dyn MessageRouting>,
         ingress_selector: Arc<dyn IngressSelector>,
+        trace: Arc<ConsensusTrace>,
         log: ReplicaLogger,
         metrics_registry: MetricsRegistry,
     ) -> Self {
@@ -70,6 +73,7 @@ impl Finalizer {
             crypto,
             message_routing,
             ingress_selector,
+            trace,
             log,
             metrics: FinalizerMetrics::new(metrics_registry),
             prev_finalized_height: RefCell::new(Height::from(0)),
@@ -132,6 +136,8 @@ impl Finalizer {
                         .observe(now.duration_since(last_batch_delivered_at).as_secs_f64());
                 }
                 self.last_batch_delivered_at.borrow_mut().replace(now);
+                self.trace
+                    .record_batch_delivery(Height::from(block_stats.block_height));
                 // Batch creation time is essentially wall time (on some replica), so the median
                 // duration across the subnet is meaningful.
                 self.metrics.batch_delivery_latency.observe(
@@ -253,7 +259,9 @@ impl Finalizer {
 mod tests {
     //! Finalizer unit tests
     use super::*;
+    use crate::consensus::trace::MAX_TRACED_HEIGHTS;
     use ic_consensus_mocks::{Dependencies, dependencies, dependencies_with_subnet_params};
+    use ic_interfaces::consensus::ConsensusTraceReader;
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
     use ic_test_utilities::{
@@ -263,7 +271,7 @@ mod tests {
     use ic_test_utilities_types::ids::{node_test_id, subnet_test_id};
     use ic_types::{
         RegistryVersion,
-        consensus::{HasHeight, HashedBlock},
+        consensus::{HasHeight, HashedBlock, trace::ConsensusTraceEvent},
     };
     use std::sync::Arc;
 
@@ -277,6 +285,7 @@ mod tests {
                 membership,
                 registry,
                 crypto,
+                time_source,
                 ..
             } = dependencies(pool_config, 1);
             let message_routing = FakeMessageRouting::new();
@@ -288,6 +297,7 @@ mod tests {
 
             let message_routing = Arc::new(message_routing);
             let ingress_selector = Arc::new(FakeIngressSelector::new());
+            let trace = Arc::new(ConsensusTrace::new(time_source.clone(), MAX_TRACED_HEIGHTS));
 
             let finalizer = Finalizer::new(
                 replica_config,
@@ -296,6 +306,7 @@ mod tests {
                 crypto,
                 message_routing.clone(),
                 ingress_selector,
+                trace.clone(),
                 no_op_logger(),
                 MetricsRegistry::new(),
             );
@@ -313,6 +324,15 @@ mod tests {
             assert!(!b.is_empty());
             // First block, nothing to remove.
             assert!(shares.is_empty());
+            // The delivery is recorded in the consensus trace.
+            let traces =
+                trace.get_height_traces(Height::from(0)..=Height::from(u64::MAX), usize::MAX);
+            assert_eq!(traces.len(), 1);
+            assert_eq!(traces[0].height, Height::from(1));
+            assert_eq!(
+                traces[0].events[0].event,
+                ConsensusTraceEvent::BatchDelivered
+            );
 
             // 3. When notarization exists, create a finalization share
             pool.insert_validated(pool.make_next_beacon());
@@ -354,6 +374,7 @@ mod tests {
                 membership,
                 registry,
                 crypto,
+                time_source,
                 ..
             } = dependencies_with_subnet_params(
                 pool_config,
@@ -386,6 +407,7 @@ mod tests {
                 crypto,
                 message_routing.clone(),
                 ingress_selector,
+                Arc::new(ConsensusTrace::new(time_source.clone(), MAX_TRACED_HEIGHTS)),
                 no_op_logger(),
                 metrics_registry,
             );
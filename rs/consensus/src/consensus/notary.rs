@@ -29,11 +29,12 @@ use crate::consensus::{
 };
 use ic_consensus_utils::{
     crypto::ConsensusCrypto,
-    find_lowest_ranked_non_disqualified_proposals, get_notarization_delay_settings,
+    find_lowest_ranked_non_disqualified_proposals, get_initial_notary_delay,
+    get_notarization_delay_settings, has_pending_input,
     membership::{Membership, MembershipError},
     pool_reader::PoolReader,
 };
-use ic_interfaces::time_source::TimeSource;
+use ic_interfaces::{consensus::PayloadBuilder, time_source::TimeSource};
 use ic_interfaces_state_manager::StateManager;
 use ic_logger::{ReplicaLogger, error, trace, warn};
 use ic_metrics::MetricsRegistry;
@@ -42,7 +43,7 @@ use ic_replicated_state::ReplicatedState;
 use ic_types::{
     Height,
     consensus::{
-        BlockProposal, HasBlockHash, HasHeight, HasRank, HashedBlock, NotarizationContent,
+        Block, BlockProposal, HasBlockHash, HasHeight, HasRank, HashedBlock, NotarizationContent,
         NotarizationShare, RandomBeacon, Rank,
     },
     replica_config::ReplicaConfig,
@@ -66,6 +67,7 @@ pub(crate) struct Notary {
     membership: Arc<Membership>,
     pub(crate) crypto: Arc<dyn ConsensusCrypto>,
     state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
+    payload_builder: Arc<dyn PayloadBuilder>,
     log: ReplicaLogger,
     metrics: NotaryMetrics,
 }
@@ -77,6 +79,7 @@ impl Notary {
         membership: Arc<Membership>,
         crypto: Arc<dyn ConsensusCrypto>,
         state_manager: Arc<dyn StateManager<State = ReplicatedState>>,
+        payload_builder: Arc<dyn PayloadBuilder>,
         metrics_registry: MetricsRegistry,
         log: ReplicaLogger,
     ) -> Notary {
@@ -86,6 +89,7 @@ impl Notary {
             membership,
             crypto,
             state_manager,
+            payload_builder,
             log,
             metrics: NotaryMetrics::new(metrics_registry),
         }
@@ -104,7 +108,7 @@ impl Notary {
             }
             let height = notarized_height.increment();
             for proposal in find_lowest_ranked_non_disqualified_proposals(pool, height) {
-                if let Some(elapsed) = self.time_to_notarize(pool, height, proposal.rank())
+                if let Some(elapsed) = self.time_to_notarize(pool, &proposal)
                     && !self.is_proposal_already_notarized_by_me(pool, &proposal)
                     && let Some(s) = self.notarize_block(pool, &proposal.content)
                 {
@@ -117,20 +121,29 @@ impl Notary {
     }
 
     /// Return the time since round start, if it is greater than required
-    /// notarization delay for the given block rank, or None otherwise.
+    /// notarization delay for the given block proposal, or None otherwise.
     fn time_to_notarize(
         &self,
         pool: &PoolReader<'_>,
-        height: Height,
-        rank: Rank,
+        proposal: &BlockProposal,
     ) -> Option<Duration> {
+        let height = proposal.height();
+        let parent = pool.get_parent(&proposal.content)?.into_inner();
         let adjusted_notary_delay = get_adjusted_notary_delay(
             self.membership.as_ref(),
             pool,
             self.state_manager.as_ref(),
             &self.log,
-            height,
-            rank,
+            parent,
+            proposal.rank(),
+            || {
+                let payload = proposal.as_ref().payload.as_ref();
+                (!payload.is_summary() && !payload.as_data().batch.is_empty())
+                    || has_pending_input(
+                        self.payload_builder.as_ref(),
+                        self.state_manager.get_latest_state().get_ref(),
+                    )
+            },
         )?;
 
         let now_relative = self.time_source.get_relative_time();
@@ -234,22 +247,30 @@ enum NotaryDelay {
 /// height. Return `None` when the registry is unavailable, or when the notary has
 /// reached a hard limit (either notarization/certification or notarization/CUP gap
 /// limits).
-/// Use membership and height to determine the notarization settings that should be used.
+/// Use membership and height to determine the notarization settings that should be used,
+/// and the chain ending in `parent` to determine the initial delay of adaptive block time.
+/// The initial delay is not stretched while `has_pending_input` returns `true`.
 fn get_adjusted_notary_delay(
     membership: &Membership,
     pool: &PoolReader<'_>,
     state_manager: &dyn StateManager<State = ReplicatedState>,
     log: &ReplicaLogger,
-    height: Height,
+    parent: Block,
     rank: Rank,
+    has_pending_input: impl FnOnce() -> bool,
 ) -> Option<Duration> {
+    let settings = get_notarization_delay_settings(
+        log,
+        &*membership.registry_client,
+        membership.subnet_id,
+        pool.registry_version(parent.height.increment())?,
+    );
+    let settings = NotarizationDelaySettings {
+        initial_notary_delay: get_initial_notary_delay(&settings, pool, parent, has_pending_input),
+        ..settings
+    };
     match get_adjusted_notary_delay_from_settings(
-        get_notarization_delay_settings(
-            log,
-            &*membership.registry_client,
-            membership.subnet_id,
-            pool.registry_version(height)?,
-        ),
+        settings,
         pool,
         state_manager,
         membership,
@@ -381,13 +402,20 @@ mod tests {
     //! Notary unit tests
     use super::*;
     use assert_matches::assert_matches;
-    use ic_consensus_mocks::{Dependencies, dependencies_with_subnet_params};
+    use ic_consensus_mocks::{Dependencies, MockPayloadBuilder, dependencies_with_subnet_params};
     use ic_interfaces::{consensus_pool::ConsensusPool, time_source::TimeSource};
     use ic_logger::replica_logger::no_op_logger;
     use ic_metrics::MetricsRegistry;
     use ic_test_utilities_consensus::fake::*;
     use ic_test_utilities_registry::SubnetRecordBuilder;
-    use ic_test_utilities_types::ids::{node_test_id, subnet_test_id};
+    use ic_test_utilities_types::{
+        ids::{node_test_id, subnet_test_id},
+        messages::SignedIngressBuilder,
+    };
+    use ic_types::{
+        batch::{BatchPayload, IngressPayload},
+        consensus::{BlockPayload, DataPayload, Payload},
+    };
     use std::{
         sync::{Arc, RwLock},
         time::Duration,
@@ -439,6 +467,7 @@ mod tests {
                 membership.clone(),
                 crypto,
                 state_manager.clone(),
+                Arc::new(MockPayloadBuilder::new()),
                 metrics_registry,
                 no_op_logger(),
             );
@@ -458,8 +487,9 @@ mod tests {
                             &PoolReader::new(&pool),
                             state_manager.as_ref(),
                             &no_op_logger(),
-                            Height::from(1),
+                            PoolReader::new(&pool).get_finalized_tip(),
                             Rank(0),
+                            /*has_pending_input=*/ || false,
                         )
                         .unwrap(),
                 )
@@ -507,8 +537,9 @@ mod tests {
                             &PoolReader::new(&pool),
                             state_manager.as_ref(),
                             &no_op_logger(),
-                            Height::from(1),
+                            PoolReader::new(&pool).get_finalized_tip(),
                             Rank(9),
+                            /*has_pending_input=*/ || false,
                         )
                         .unwrap(),
                 )
@@ -524,8 +555,9 @@ mod tests {
                             &PoolReader::new(&pool),
                             state_manager.as_ref(),
                             &no_op_logger(),
-                            Height::from(1),
+                            PoolReader::new(&pool).get_finalized_tip(),
                             twenty_block.rank(),
+                            /*has_pending_input=*/ || false,
                         )
                         .unwrap(),
                 )
@@ -629,6 +661,7 @@ mod tests {
                 membership.clone(),
                 crypto,
                 state_manager.clone(),
+                Arc::new(MockPayloadBuilder::new()),
                 metrics_registry,
                 no_op_logger(),
             );
@@ -657,8 +690,9 @@ mod tests {
                     &PoolReader::new(&pool),
                     state_manager.as_ref(),
                     &no_op_logger(),
-                    Height::from(5),
+                    PoolReader::new(&pool).get_finalized_tip(),
                     Rank(0),
+                    /*has_pending_input=*/ || false,
                 )
                 .unwrap(),
             );
@@ -673,6 +707,7 @@ mod tests {
             let settings = NotarizationDelaySettings {
                 unit_delay: Duration::from_secs(1),
                 initial_notary_delay: Duration::from_secs(0),
+                adaptive_block_time: None,
             };
             let committee = (0..3).map(node_test_id).collect::<Vec<_>>();
             /* use large enough DKG interval to trigger notarization/CUP gap limit */
@@ -777,6 +812,7 @@ mod tests {
             let settings = NotarizationDelaySettings {
                 unit_delay: Duration::from_secs(1),
                 initial_notary_delay,
+                adaptive_block_time: None,
             };
             let committee = (0..3).map(node_test_id).collect::<Vec<_>>();
             let record = SubnetRecordBuilder::from(&committee)
@@ -886,6 +922,7 @@ mod tests {
             let settings = NotarizationDelaySettings {
                 unit_delay: Duration::from_secs(1),
                 initial_notary_delay,
+                adaptive_block_time: None,
             };
             let committee = (0..3).map(node_test_id).collect::<Vec<_>>();
             let Dependencies {
@@ -963,4 +1000,102 @@ mod tests {
             );
         });
     }
+
+    #[test]
+    fn test_get_adjusted_notary_delay_adaptive_block_time() {
+        ic_test_utilities::artifact_pool_config::with_test_pool_config(|pool_config| {
+            let min_notary_delay = Duration::from_millis(100);
+            let max_notary_delay = Duration::from_secs(1);
+            let committee = (0..3).map(node_test_id).collect::<Vec<_>>();
+            let Dependencies {
+                mut pool,
+                state_manager,
+                membership,
+                ..
+            } = dependencies_with_subnet_params(
+                pool_config,
+                subnet_test_id(0),
+                vec![(
+                    1,
+                    SubnetRecordBuilder::from(&committee)
+                        .with_dkg_interval_length(99)
+                        .with_adaptive_block_time(min_notary_delay, max_notary_delay)
+                        .build(),
+                )],
+            );
+
+            // Keep certification in sync with finalization, so that no backlog
+            // delay is added.
+            let certified_height = Arc::new(RwLock::new(Height::from(0)));
+            let certified_height_clone = Arc::clone(&certified_height);
+            state_manager
+                .get_mut()
+                .expect_latest_certified_height()
+                .returning(move || *certified_height_clone.read().unwrap());
+
+            let adaptive_delay = |pool: &dyn ConsensusPool| {
+                let reader = PoolReader::new(pool);
+                get_adjusted_notary_delay(
+                    membership.as_ref(),
+                    &reader,
+                    state_manager.as_ref(),
+                    &no_op_logger(),
+                    reader.get_finalized_tip(),
+                    Rank(0),
+                    /*has_pending_input=*/ || false,
+                )
+                .unwrap()
+            };
+
+            // Only the genesis summary block exists, so the subnet counts as loaded.
+            assert_eq!(adaptive_delay(&pool), min_notary_delay);
+
+            // Every empty block doubles the delay.
+            *certified_height.write().unwrap() = pool.advance_round_normal_operation();
+            assert_eq!(adaptive_delay(&pool), min_notary_delay * 2);
+            *certified_height.write().unwrap() = pool.advance_round_normal_operation_n(2);
+            assert_eq!(adaptive_delay(&pool), min_notary_delay * 8);
+
+            // The delay is capped at the configured maximum.
+            *certified_height.write().unwrap() = pool.advance_round_normal_operation_n(5);
+            assert_eq!(adaptive_delay(&pool), max_notary_delay);
+
+            // The delay is not stretched while this node has pending input.
+            let reader = PoolReader::new(&pool);
+            assert_eq!(
+                get_adjusted_notary_delay(
+                    membership.as_ref(),
+                    &reader,
+                    state_manager.as_ref(),
+                    &no_op_logger(),
+                    reader.get_finalized_tip(),
+                    Rank(0),
+                    /*has_pending_input=*/ || true,
+                ),
+                Some(min_notary_delay)
+            );
+
+            // A block carrying ingress resets the delay to the minimum.
+            let mut block = pool.make_next_block();
+            let dkg = block.as_ref().payload.as_ref().as_data().dkg.clone();
+            let ingress = IngressPayload::from(vec![SignedIngressBuilder::new().nonce(0).build()]);
+            block.content.as_mut().payload = Payload::new(
+                ic_types::crypto::crypto_hash,
+                BlockPayload::Data(DataPayload {
+                    batch: BatchPayload {
+                        ingress,
+                        ..BatchPayload::default()
+                    },
+                    dkg,
+                    idkg: None,
+                }),
+            );
+            block.update_content();
+            pool.insert_validated(block.clone());
+            pool.notarize(&block);
+            pool.finalize(&block);
+            *certified_height.write().unwrap() = block.height();
+            assert_eq!(adaptive_delay(&pool), min_notary_delay);
+        });
+    }
 }
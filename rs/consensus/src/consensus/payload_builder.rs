@@ -33,6 +33,7 @@ pub struct PayloadBuilderImpl {
     subnet_id: SubnetId,
     node_id: NodeId,
     registry_client: Arc<dyn RegistryClient>,
+    ingress_selector: Arc<dyn IngressSelector>,
     section_builder: Vec<BatchPayloadSectionBuilder>,
     metrics: PayloadBuilderMetrics,
     logger: ReplicaLogger,
@@ -54,7 +55,7 @@ impl PayloadBuilderImpl {
         logger: ReplicaLogger,
     ) -> Self {
         let section_builder = vec![
-            BatchPayloadSectionBuilder::Ingress(ingress_selector),
+            BatchPayloadSectionBuilder::Ingress(Arc::clone(&ingress_selector)),
             BatchPayloadSectionBuilder::SelfValidating(self_validating_payload_builder),
             BatchPayloadSectionBuilder::XNet(xnet_payload_builder),
             BatchPayloadSectionBuilder::CanisterHttp(canister_http_payload_builder),
@@ -66,6 +67,7 @@ impl PayloadBuilderImpl {
             subnet_id,
             node_id,
             registry_client,
+            ingress_selector,
             section_builder,
             metrics: PayloadBuilderMetrics::new(metrics),
             logger,
@@ -169,6 +171,10 @@ impl PayloadBuilder for PayloadBuilderImpl {
 
         Ok(())
     }
+
+    fn has_pending_ingress(&self) -> bool {
+        self.ingress_selector.has_pending_ingress()
+    }
 }
 
 impl PayloadBuilderImpl {
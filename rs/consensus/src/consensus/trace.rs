@@ -0,0 +1,266 @@
+//! Records a bounded, per-height trace of the consensus events of this replica,
+//! which can be exported for offline analysis of slow rounds.
+//!
+//! Events are derived from the [`Mutations`] returned by consensus, so that
+//! no subcomponent needs to be aware of the trace, with the exception of batch
+//! delivery, which is reported by the finalizer. All events are timestamped
+//! with the relative time of the same [`TimeSource`].
+use ic_interfaces::{
+    consensus::ConsensusTraceReader,
+    consensus_pool::{ChangeAction, Mutations},
+    time_source::TimeSource,
+};
+use ic_types::{
+    Height, Time,
+    consensus::{
+        Block, ConsensusMessage, HasBlockHash, HasHeight, HasRank,
+        trace::{ConsensusTraceEvent, HeightTrace, TracedConsensusEvent},
+    },
+    crypto::CryptoHashOf,
+};
+use std::{
+    collections::BTreeMap,
+    ops::RangeInclusive,
+    sync::{Arc, Mutex},
+};
+
+/// The number of most recent heights for which events are kept.
+pub const MAX_TRACED_HEIGHTS: usize = 500;
+
+/// The maximum number of events kept per height. Every node sends at most a
+/// handful of artifacts per height, so this is only reached if nodes equivocate.
+const MAX_EVENTS_PER_HEIGHT: usize = 1_000;
+
+/// A bounded buffer of the consensus events of the most recent heights. Once
+/// [`MAX_TRACED_HEIGHTS`] heights are traced, the lowest height is evicted.
+pub struct ConsensusTrace {
+    heights: Mutex<BTreeMap<Height, Vec<TracedConsensusEvent>>>,
+    max_heights: usize,
+    time_source: Arc<dyn TimeSource>,
+}
+
+impl ConsensusTrace {
+    /// Creates an empty trace keeping the events of at most `max_heights` heights,
+    /// timestamped with the relative time of `time_source`.
+    pub fn new(time_source: Arc<dyn TimeSource>, max_heights: usize) -> Self {
+        Self {
+            heights: Mutex::new(BTreeMap::new()),
+            max_heights,
+            time_source,
+        }
+    }
+
+    /// Records the events corresponding to the given consensus mutations.
+    pub(crate) fn record_mutations(&self, mutations: &Mutations) {
+        let time = self.time_source.get_relative_time();
+        for action in mutations {
+            let (msg, own) = match action {
+                ChangeAction::AddToValidated(artifact) => (&artifact.msg, true),
+                ChangeAction::MoveToValidated(msg) => (msg, false),
+                _ => continue,
+            };
+            if let Some(event) = trace_event(msg, own) {
+                self.record(msg.height(), time, event);
+            }
+        }
+    }
+
+    /// Records the delivery of the batch at the given height.
+    pub(crate) fn record_batch_delivery(&self, height: Height) {
+        let time = self.time_source.get_relative_time();
+        self.record(height, time, ConsensusTraceEvent::BatchDelivered);
+    }
+
+    fn record(&self, height: Height, time: Time, event: ConsensusTraceEvent) {
+        let mut heights = self.heights.lock().unwrap();
+        if heights.len() >= self.max_heights
+            && !heights.contains_key(&height)
+            && heights
+                .first_key_value()
+                .is_some_and(|(lowest, _)| height < *lowest)
+        {
+            // Events of heights that have already been evicted are dropped.
+            return;
+        }
+        let events = heights.entry(height).or_default();
+        if events.len() < MAX_EVENTS_PER_HEIGHT {
+            events.push(TracedConsensusEvent { time, event });
+        }
+        while heights.len() > self.max_heights {
+            heights.pop_first();
+        }
+    }
+}
+
+impl ConsensusTraceReader for ConsensusTrace {
+    fn get_height_traces(
+        &self,
+        heights: RangeInclusive<Height>,
+        max_heights: usize,
+    ) -> Vec<HeightTrace> {
+        // `BTreeMap::range` panics on inverted ranges.
+        if heights.is_empty() {
+            return Vec::new();
+        }
+        self.heights
+            .lock()
+            .unwrap()
+            .range(heights)
+            .take(max_heights)
+            .map(|(height, events)| HeightTrace {
+                height: *height,
+                events: events.clone(),
+            })
+            .collect()
+    }
+}
+
+fn trace_event(msg: &ConsensusMessage, own: bool) -> Option<ConsensusTraceEvent> {
+    let event = match msg {
+        ConsensusMessage::BlockProposal(proposal) if own => ConsensusTraceEvent::BlockProposed {
+            block_hash: to_hex(proposal.content.get_hash()),
+            rank: proposal.rank().0,
+        },
+        ConsensusMessage::BlockProposal(proposal) => ConsensusTraceEvent::BlockProposalValidated {
+            block_hash: to_hex(proposal.content.get_hash()),
+            rank: proposal.rank().0,
+            block_maker: proposal.signature.signer,
+        },
+        ConsensusMessage::NotarizationShare(share) if own => {
+            ConsensusTraceEvent::NotarizationShareSent {
+                block_hash: to_hex(share.block_hash()),
+            }
+        }
+        ConsensusMessage::NotarizationShare(share) => {
+            ConsensusTraceEvent::NotarizationShareReceived {
+                block_hash: to_hex(share.block_hash()),
+                signer: share.signature.signer,
+            }
+        }
+        ConsensusMessage::Notarization(notarization) => ConsensusTraceEvent::Notarized {
+            block_hash: to_hex(notarization.block_hash()),
+        },
+        ConsensusMessage::FinalizationShare(share) if own => {
+            ConsensusTraceEvent::FinalizationShareSent {
+                block_hash: to_hex(share.block_hash()),
+            }
+        }
+        ConsensusMessage::Finalization(finalization) => ConsensusTraceEvent::Finalized {
+            block_hash: to_hex(finalization.block_hash()),
+        },
+        _ => return None,
+    };
+    Some(event)
+}
+
+fn to_hex(hash: &CryptoHashOf<Block>) -> String {
+    hex::encode(&hash.get_ref().0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_interfaces::consensus_pool::ValidatedConsensusArtifact;
+    use ic_test_utilities_consensus::{fake::*, make_genesis};
+    use ic_test_utilities_time::FastForwardTimeSource;
+    use ic_test_utilities_types::ids::node_test_id;
+    use ic_types::consensus::{
+        BlockProposal, ConsensusMessageHashable, NotarizationShare, Rank, dkg::DkgSummary,
+    };
+    use std::time::Duration;
+
+    fn proposal(rank: u64) -> BlockProposal {
+        let genesis = make_genesis(DkgSummary::fake());
+        let mut block = Block::from_parent(genesis.content.block.as_ref());
+        block.rank = Rank(rank);
+        BlockProposal::fake(block, node_test_id(rank))
+    }
+
+    fn all_heights(trace: &ConsensusTrace) -> Vec<Height> {
+        trace
+            .get_height_traces(Height::from(0)..=Height::from(u64::MAX), usize::MAX)
+            .into_iter()
+            .map(|trace| trace.height)
+            .collect()
+    }
+
+    #[test]
+    fn records_events_per_height() {
+        let time_source = FastForwardTimeSource::new();
+        let trace = ConsensusTrace::new(time_source.clone(), MAX_TRACED_HEIGHTS);
+        let own = proposal(0);
+        let other = proposal(1);
+        let share = NotarizationShare::fake(own.as_ref(), node_test_id(2));
+        let time = time_source.get_relative_time();
+        trace.record_mutations(&vec![
+            ChangeAction::AddToValidated(ValidatedConsensusArtifact {
+                msg: own.clone().into_message(),
+                timestamp: time,
+            }),
+            ChangeAction::MoveToValidated(other.clone().into_message()),
+            ChangeAction::MoveToValidated(share.clone().into_message()),
+            ChangeAction::PurgeValidatedBelow(Height::from(1)),
+        ]);
+        time_source.advance_time(Duration::from_millis(10));
+        trace.record_batch_delivery(Height::from(1));
+
+        let traces = trace.get_height_traces(Height::from(0)..=Height::from(u64::MAX), usize::MAX);
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].height, Height::from(1));
+        // Mutations and batch delivery are timestamped by the same clock.
+        assert_eq!(traces[0].events[0].time, time);
+        assert_eq!(traces[0].events[3].time, time + Duration::from_millis(10));
+        let events: Vec<_> = traces[0].events.iter().map(|e| e.event.clone()).collect();
+        assert_eq!(
+            events,
+            vec![
+                ConsensusTraceEvent::BlockProposed {
+                    block_hash: to_hex(own.content.get_hash()),
+                    rank: 0,
+                },
+                ConsensusTraceEvent::BlockProposalValidated {
+                    block_hash: to_hex(other.content.get_hash()),
+                    rank: 1,
+                    block_maker: node_test_id(1),
+                },
+                ConsensusTraceEvent::NotarizationShareReceived {
+                    block_hash: to_hex(own.content.get_hash()),
+                    signer: node_test_id(2),
+                },
+                ConsensusTraceEvent::BatchDelivered,
+            ]
+        );
+    }
+
+    #[test]
+    fn evicts_lowest_heights() {
+        let trace = ConsensusTrace::new(FastForwardTimeSource::new(), 2);
+        for height in [1, 2, 3] {
+            trace.record_batch_delivery(Height::from(height));
+        }
+        // Events of evicted heights are dropped.
+        trace.record_batch_delivery(Height::from(1));
+
+        assert_eq!(all_heights(&trace), vec![Height::from(2), Height::from(3)]);
+    }
+
+    #[test]
+    fn returns_requested_height_range_up_to_limit() {
+        let trace = ConsensusTrace::new(FastForwardTimeSource::new(), MAX_TRACED_HEIGHTS);
+        for height in 1..=10 {
+            trace.record_batch_delivery(Height::from(height));
+        }
+        assert_eq!(all_heights(&trace).len(), 10);
+
+        let heights: Vec<_> = trace
+            .get_height_traces(Height::from(3)..=Height::from(8), 4)
+            .into_iter()
+            .map(|trace| trace.height)
+            .collect();
+        assert_eq!(heights, (3..=6).map(Height::from).collect::<Vec<_>>());
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let inverted = Height::from(8)..=Height::from(3);
+        assert!(trace.get_height_traces(inverted, 4).is_empty());
+    }
+}
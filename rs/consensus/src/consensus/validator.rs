@@ -11,7 +11,7 @@ use ic_consensus_idkg::{self as idkg};
 use ic_consensus_utils::{
     RoundRobin, active_high_threshold_nidkg_id, active_low_threshold_nidkg_id,
     crypto::ConsensusCrypto,
-    get_oldest_idkg_state_registry_version,
+    get_oldest_idkg_state_registry_version, has_pending_input,
     membership::{Membership, MembershipError},
     pool_reader::{PoolReader, UnexpectedChainLength},
 };
@@ -1069,7 +1069,8 @@ impl Validator {
 
             // We only validate blocks from a block maker of a certain rank after a
             // rank-based delay. If this time has not elapsed yet, we ignore the block for
-            // now.
+            // now. A proposal carrying batch input justifies the minimum adaptive delay
+            // even if this node has no pending input of its own.
             if !is_time_to_make_block(
                 &self.log,
                 self.registry_client.as_ref(),
@@ -1080,6 +1081,14 @@ impl Validator {
                 proposal.rank(),
                 self.time_source.as_ref(),
                 /*metrics=*/ None,
+                || {
+                    let payload = proposal.as_ref().payload.as_ref();
+                    (!payload.is_summary() && !payload.as_data().batch.is_empty())
+                        || has_pending_input(
+                            self.payload_builder.as_ref(),
+                            self.state_manager.get_latest_state().get_ref(),
+                        )
+                },
             ) {
                 continue;
             }
@@ -2662,6 +2671,7 @@ pub mod test {
                     pool_reader.registry_version(test_block.height()).unwrap(),
                     rank,
                     /*metrics=*/ None,
+                    /*has_pending_input=*/ || false,
                 );
 
             time_source.set_time(parent.context.time + delay).unwrap();
@@ -3465,6 +3475,7 @@ pub mod test {
                 pool_reader.registry_version(test_block.height()).unwrap(),
                 rank,
                 /*metrics=*/ None,
+                /*has_pending_input=*/ || false,
             );
             test_block.content.as_mut().rank = rank;
             test_block.content.as_mut().context.time += delay;
@@ -3517,6 +3528,7 @@ pub mod test {
                 pool_reader.registry_version(test_block.height()).unwrap(),
                 rank,
                 /*metrics=*/ None,
+                /*has_pending_input=*/ || false,
             );
             test_block.content.as_mut().rank = rank;
             test_block.content.as_mut().context.time += delay;
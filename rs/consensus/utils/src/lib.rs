@@ -1,7 +1,7 @@
 //! Consensus utility functions
 use crate::{crypto::Aggregate, membership::Membership, pool_reader::PoolReader};
 use ic_interfaces::{
-    consensus::{PayloadValidationError, PayloadValidationFailure},
+    consensus::{PayloadBuilder, PayloadValidationError, PayloadValidationFailure},
     consensus_pool::ConsensusPoolCache,
     validation::ValidationError,
 };
@@ -18,7 +18,10 @@ use ic_types::{
         threshold_sig::ni_dkg::{NiDkgId, NiDkgReceivers, NiDkgTag, NiDkgTranscript},
     },
 };
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
 
 pub mod bouncer_metrics;
 pub mod crypto;
@@ -126,6 +129,57 @@ pub fn get_notarization_delay_settings(
     }
 }
 
+/// The maximum number of ancestors inspected when determining the adaptive
+/// initial notary delay. Since the delay doubles with every idle block, this
+/// comfortably covers any sensible ratio between the configured bounds.
+const ADAPTIVE_BLOCK_TIME_LOOK_BACK_DISTANCE: usize = 16;
+
+/// Returns the initial notary delay for the round following `parent`.
+///
+/// Without adaptive block time this is the static `initial_notary_delay`.
+/// Otherwise the delay starts at the configured minimum and doubles for every
+/// consecutive idle block (a data block without any batch input, i.e. no
+/// ingress, xnet, http or query stats payload) at the tip of the chain ending
+/// in `parent`, capped at the configured maximum. Summary blocks are skipped.
+/// The delay is only stretched while the node has no input of its own waiting
+/// to be included, as reported by `has_pending_input` (see
+/// [`has_pending_input`]); otherwise the configured minimum is returned.
+pub fn get_initial_notary_delay(
+    settings: &NotarizationDelaySettings,
+    pool: &PoolReader<'_>,
+    parent: Block,
+    has_pending_input: impl FnOnce() -> bool,
+) -> Duration {
+    let Some(adaptive) = &settings.adaptive_block_time else {
+        return settings.initial_notary_delay;
+    };
+    if has_pending_input() {
+        return adaptive.min_notary_delay;
+    }
+    let idle_blocks = pool
+        .chain_iterator(parent)
+        .filter(|block| !block.payload.as_ref().is_summary())
+        .take(ADAPTIVE_BLOCK_TIME_LOOK_BACK_DISTANCE)
+        .take_while(|block| block.payload.as_ref().as_data().batch.is_empty())
+        .count();
+    adaptive
+        .min_notary_delay
+        .saturating_mul(1 << idle_blocks)
+        .min(adaptive.max_notary_delay)
+}
+
+/// Returns `true` if there is input that should be included in a block soon,
+/// i.e. the ingress pool is not empty or the latest state has outgoing xnet
+/// messages that other subnets are waiting for.
+pub fn has_pending_input(payload_builder: &dyn PayloadBuilder, state: &ReplicatedState) -> bool {
+    payload_builder.has_pending_ingress()
+        || state
+            .metadata
+            .streams()
+            .values()
+            .any(|stream| !stream.messages().is_empty())
+}
+
 /// Aggregate shares into complete artifacts
 ///
 /// Consensus receives many artifact signature shares during its lifetime.
@@ -9,7 +9,7 @@ use crate::utils::{
 use ic_consensus_utils::{
     crypto::ConsensusCrypto, get_registry_version_and_interval_length_at_height,
 };
-use ic_error_types::RejectCode;
+use ic_error_types::{RejectCode, UserError};
 use ic_interfaces::crypto::ErrorReproducibility;
 use ic_interfaces::{
     batch_payload::{BatchPayloadBuilder, IntoMessages, PastPayload, ProposalContext},
@@ -21,22 +21,25 @@ use ic_interfaces::{
 use ic_interfaces_registry::RegistryClient;
 use ic_interfaces_state_manager::StateReader;
 use ic_logger::{ReplicaLogger, warn};
-use ic_management_canister_types_private::{MasterPublicKeyId, Payload, VetKdDeriveKeyResult};
+use ic_management_canister_types_private::{
+    BlsPublicKeyResponse, MasterPublicKeyId, Payload, SignWithBlsReply, VetKdDeriveKeyResult,
+};
 use ic_metrics::MetricsRegistry;
 use ic_registry_client_helpers::chain_keys::ChainKeysRegistry;
 use ic_registry_client_helpers::subnet::SubnetRegistry;
 use ic_replicated_state::{
-    ReplicatedState,
-    metadata_state::subnet_call_context_manager::{SignWithThresholdContext, ThresholdArguments},
+    ReplicatedState, metadata_state::subnet_call_context_manager::SignWithThresholdContext,
+};
+use ic_types::crypto::vetkd::{
+    VetKdKeyShareCombinationError, VetKdKeyVerificationError, VetKdOutput,
 };
-use ic_types::crypto::vetkd::{VetKdKeyShareCombinationError, VetKdKeyVerificationError};
 use ic_types::{
     CountBytes, Height, NumBytes, SubnetId, Time,
     batch::{
         ConsensusResponse, ValidationContext, VetKdAgreement, VetKdErrorCode, VetKdPayload,
         bytes_to_vetkd_payload, vetkd_payload_to_bytes,
     },
-    crypto::vetkd::{VetKdArgs, VetKdDerivationContext, VetKdEncryptedKey},
+    crypto::vetkd::VetKdEncryptedKey,
     messages::{CallbackId, Payload as ResponsePayload, RejectContext},
 };
 use num_traits::ops::saturating::SaturatingSub;
@@ -171,7 +174,7 @@ impl VetKdPayloadBuilderImpl {
             .into_iter()
             .map(|key_config| key_config.key_id)
             // Skip keys that don't need to run NIDKG protocol
-            .filter(|key_id| key_id.is_vetkd_key())
+            .filter(|key_id| key_id.is_nidkg_key())
             // Skip keys that are disabled
             .filter(|key_id| {
                 enabled_subnets
@@ -216,8 +219,8 @@ impl VetKdPayloadBuilderImpl {
                 .signature_request_contexts()
                 .par_iter()
                 .flat_map(|(callback_id, context)| {
-                    if !context.is_vetkd() {
-                        // Skip non-vetkd contexts.
+                    if !context.is_nidkg() {
+                        // Skip contexts that aren't answered by the vetKD protocol.
                         return None;
                     }
 
@@ -236,27 +239,14 @@ impl VetKdPayloadBuilderImpl {
                     }
 
                     let shares = grouped_shares.get(callback_id)?;
-                    let ThresholdArguments::VetKd(ctxt_args) = &context.args else {
-                        return None;
-                    };
-                    let args = VetKdArgs {
-                        context: VetKdDerivationContext {
-                            caller: context.request.sender.into(),
-                            context: context.derivation_path.iter().flatten().cloned().collect(),
-                        },
-                        ni_dkg_id: ctxt_args.ni_dkg_id.clone(),
-                        input: ctxt_args.input.to_vec(),
-                        transport_public_key: ctxt_args.transport_public_key.clone(),
-                    };
+                    let args = context.vetkd_protocol_args()?;
                     let key_id = context.key_id();
                     match self.crypto.combine_encrypted_key_shares(shares, &args) {
                         Ok(key) => {
                             self.metrics
                                 .payload_metrics_inc("vetkd_agreement_completed", &key_id);
-                            let result = VetKdDeriveKeyResult {
-                                encrypted_key: key.encrypted_key,
-                            };
-                            Some((*callback_id, VetKdAgreement::Success(result.encode())))
+                            let data = encode_reply(args.output, key)?;
+                            Some((*callback_id, VetKdAgreement::Success(data)))
                         }
                         Err(
                             VetKdKeyShareCombinationError::UnsatisfiedReconstructionThreshold {
@@ -321,7 +311,7 @@ impl VetKdPayloadBuilderImpl {
                         return invalid_artifact_err(InvalidVetKdPayloadReason::MissingContext(id));
                     };
 
-                    if !context.is_vetkd() {
+                    if !context.is_nidkg() {
                         return invalid_artifact_err(
                             InvalidVetKdPayloadReason::UnexpectedIDkgContext(id),
                         );
@@ -370,29 +360,17 @@ impl VetKdPayloadBuilderImpl {
         context: &SignWithThresholdContext,
         data: Vec<u8>,
     ) -> Result<(), PayloadValidationError> {
-        let ThresholdArguments::VetKd(ctxt_args) = &context.args else {
+        let Some(args) = context.vetkd_protocol_args() else {
             return invalid_artifact_err(InvalidVetKdPayloadReason::UnexpectedIDkgContext(id));
         };
-        let args = VetKdArgs {
-            context: VetKdDerivationContext {
-                caller: context.request.sender.into(),
-                context: context.derivation_path.iter().flatten().cloned().collect(),
-            },
-            ni_dkg_id: ctxt_args.ni_dkg_id.clone(),
-            input: ctxt_args.input.to_vec(),
-            transport_public_key: ctxt_args.transport_public_key.clone(),
-        };
-        let reply = match VetKdDeriveKeyResult::decode(&data) {
-            Ok(data) => data,
+        let encrypted_key = match decode_reply(args.output, &data) {
+            Ok(encrypted_key) => encrypted_key,
             Err(error) => {
                 return invalid_artifact_err(InvalidVetKdPayloadReason::DecodingError(format!(
                     "{error:?}",
                 )));
             }
         };
-        let encrypted_key = VetKdEncryptedKey {
-            encrypted_key: reply.encrypted_key,
-        };
         self.crypto
             .verify_encrypted_key(&encrypted_key, &args)
             .map_err(|err| {
@@ -416,6 +394,57 @@ impl VetKdPayloadBuilderImpl {
     }
 }
 
+/// Encodes the reply to the request that `key` was derived for with `output`,
+/// or returns `None` if the key does not have the expected shape.
+fn encode_reply(output: VetKdOutput, key: VetKdEncryptedKey) -> Option<Vec<u8>> {
+    match output {
+        VetKdOutput::EncryptedKey => Some(
+            VetKdDeriveKeyResult {
+                encrypted_key: key.encrypted_key,
+            }
+            .encode(),
+        ),
+        VetKdOutput::BlsPublicKey => Some(
+            BlsPublicKeyResponse {
+                public_key: key.encrypted_key,
+            }
+            .encode(),
+        ),
+        VetKdOutput::BlsSignature => {
+            let (public_key, signature) = key.bls_public_key_and_signature()?;
+            Some(
+                SignWithBlsReply {
+                    signature: signature.to_vec(),
+                    public_key: public_key.to_vec(),
+                }
+                .encode(),
+            )
+        }
+    }
+}
+
+/// Decodes the key that was derived with `output` from the reply in `data`.
+fn decode_reply(output: VetKdOutput, data: &[u8]) -> Result<VetKdEncryptedKey, UserError> {
+    match output {
+        VetKdOutput::EncryptedKey => {
+            VetKdDeriveKeyResult::decode(data).map(|reply| VetKdEncryptedKey {
+                encrypted_key: reply.encrypted_key,
+            })
+        }
+        VetKdOutput::BlsPublicKey => {
+            BlsPublicKeyResponse::decode(data).map(|reply| VetKdEncryptedKey {
+                encrypted_key: reply.public_key,
+            })
+        }
+        VetKdOutput::BlsSignature => SignWithBlsReply::decode(data).map(|reply| {
+            VetKdEncryptedKey::from_bls_public_key_and_signature(
+                &reply.public_key,
+                &reply.signature,
+            )
+        }),
+    }
+}
+
 impl BatchPayloadBuilder for VetKdPayloadBuilderImpl {
     fn build_payload(
         &self,
@@ -559,8 +588,9 @@ fn reject_if_invalid(
     // We time out vetKD requests that take longer than one DKG interval.
     // Otherwise the required NiDKG transcript might disappear before we
     // can complete the request.
-    if let ThresholdArguments::VetKd(args) = &context.args
-        && args.height < request_expiry.height
+    if context
+        .nidkg_height()
+        .is_some_and(|height| height < request_expiry.height)
     {
         if let Some(metrics) = metrics {
             metrics.payload_errors_inc("expired_transcript", &key_id);
@@ -1198,7 +1228,7 @@ mod tests {
                         MasterPublicKeyId::Ecdsa(_) | MasterPublicKeyId::Schnorr(_) => {
                             assert!(!payload.vetkd_agreements.contains_key(&id));
                         }
-                        MasterPublicKeyId::VetKd(_) => {
+                        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
                             assert_eq!(
                                 payload.vetkd_agreements.get(&id).unwrap(),
                                 &VetKdAgreement::Reject(expected_error)
@@ -3,7 +3,8 @@ use ic_interfaces::batch_payload::PastPayload;
 use ic_management_canister_types_private::{EcdsaKeyId, MasterPublicKeyId, VetKdKeyId};
 use ic_registry_subnet_features::{ChainKeyConfig, KeyConfig};
 use ic_replicated_state::metadata_state::subnet_call_context_manager::{
-    EcdsaArguments, SchnorrArguments, SignWithThresholdContext, ThresholdArguments, VetKdArguments,
+    BlsArguments, EcdsaArguments, SchnorrArguments, SignWithThresholdContext, ThresholdArguments,
+    VetKdArguments,
 };
 use ic_test_utilities_types::messages::RequestBuilder;
 use ic_types::consensus::idkg::{EcdsaSigShare, IDkgMessage, RequestId, SchnorrSigShare};
@@ -21,7 +22,7 @@ use ic_types::{
     messages::CallbackId,
     time::UNIX_EPOCH,
 };
-use ic_types_test_utils::ids::{node_test_id, subnet_test_id};
+use ic_types_test_utils::ids::{canister_test_id, node_test_id, subnet_test_id};
 use std::str::FromStr;
 use std::{collections::BTreeMap, sync::Arc};
 use strum::EnumCount;
@@ -110,11 +111,11 @@ pub(super) fn make_chain_key_config() -> ChainKeyConfig {
     }
 }
 
-pub(super) fn fake_dkg_id(key_id: VetKdKeyId) -> NiDkgId {
+pub(super) fn fake_dkg_id(key_id: NiDkgMasterPublicKeyId) -> NiDkgId {
     NiDkgId {
         start_block_height: Height::from(0),
         dealer_subnet: subnet_test_id(0),
-        dkg_tag: NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::VetKd(key_id)),
+        dkg_tag: NiDkgTag::HighThresholdForKey(key_id),
         target_subnet: NiDkgTargetSubnet::Local,
     }
 }
@@ -136,7 +137,14 @@ pub(super) fn fake_signature_request_args(key_id: MasterPublicKeyId) -> Threshol
             key_id: key_id.clone(),
             input: Arc::new(vec![1; 32]),
             transport_public_key: vec![1; 32],
-            ni_dkg_id: fake_dkg_id(key_id),
+            ni_dkg_id: fake_dkg_id(NiDkgMasterPublicKeyId::VetKd(key_id)),
+            height: Height::from(100),
+        }),
+        MasterPublicKeyId::Bls(key_id) => ThresholdArguments::Bls(BlsArguments {
+            key_id: key_id.clone(),
+            canister_id: canister_test_id(1),
+            message: Some(Arc::new(vec![1; 32])),
+            ni_dkg_id: fake_dkg_id(NiDkgMasterPublicKeyId::Bls(key_id)),
             height: Height::from(100),
         }),
     }
@@ -198,14 +206,16 @@ pub(super) fn make_shares(
                         sig_share_raw: vec![],
                     },
                 }),
-                ThresholdArguments::VetKd(_) => IDkgMessage::VetKdKeyShare(VetKdKeyShare {
-                    signer_id,
-                    request_id,
-                    share: VetKdEncryptedKeyShare {
-                        encrypted_key_share: VetKdEncryptedKeyShareContent(vec![]),
-                        node_signature: vec![],
-                    },
-                }),
+                ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => {
+                    IDkgMessage::VetKdKeyShare(VetKdKeyShare {
+                        signer_id,
+                        request_id,
+                        share: VetKdEncryptedKeyShare {
+                            encrypted_key_share: VetKdEncryptedKeyShareContent(vec![]),
+                            node_signature: vec![],
+                        },
+                    })
+                }
             };
             messages.push(message);
         }
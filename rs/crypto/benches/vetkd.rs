@@ -15,7 +15,9 @@ use ic_interfaces::crypto::VetKdProtocol;
 use ic_management_canister_types_private::{VetKdCurve, VetKdKeyId};
 use ic_types::crypto::threshold_sig::ni_dkg::config::NiDkgConfig;
 use ic_types::crypto::threshold_sig::ni_dkg::{NiDkgMasterPublicKeyId, NiDkgTag, NiDkgTranscript};
-use ic_types::crypto::vetkd::{VetKdArgs, VetKdDerivationContext, VetKdEncryptedKeyShare};
+use ic_types::crypto::vetkd::{
+    VetKdArgs, VetKdDerivationContext, VetKdEncryptedKeyShare, VetKdOutput,
+};
 use ic_types::{NodeId, NumberOfNodes};
 use ic_types_test_utils::ids::canister_test_id;
 use ic_vetkeys::TransportSecretKey;
@@ -74,6 +76,7 @@ fn bench_create_encrypted_key_share<M: Measurement, C: CryptoComponentRng>(
                     context: random_derivation_context_with_n_bytes(32, rng),
                     input: random_n_bytes(32, rng),
                     transport_public_key: random_transports_secret_key(rng).public_key(),
+                    output: VetKdOutput::EncryptedKey,
                 };
                 let creator = crypto_for(random_node_in(config.receivers().get(), rng), env);
                 (creator, vetkd_args)
@@ -102,6 +105,7 @@ fn bench_verify_encrypted_key_share<M: Measurement, C: CryptoComponentRng>(
                     context: random_derivation_context_with_n_bytes(32, rng),
                     input: random_n_bytes(32, rng),
                     transport_public_key: random_transports_secret_key(rng).public_key(),
+                    output: VetKdOutput::EncryptedKey,
                 };
                 let creator_id = random_node_in(config.receivers().get(), rng);
                 let creator = crypto_for(creator_id, env);
@@ -138,6 +142,7 @@ fn bench_combine_encrypted_key_shares<M: Measurement, C: CryptoComponentRng>(
                         context: random_derivation_context_with_n_bytes(32, rng),
                         input: random_n_bytes(32, rng),
                         transport_public_key: random_transports_secret_key(rng).public_key(),
+                        output: VetKdOutput::EncryptedKey,
                     };
                     let num_of_shares = NumberOfNodes::from(num_of_shares_to_combine as u32);
                     let key_shares = create_and_verify_key_shares_for_each(
@@ -174,6 +179,7 @@ fn bench_verify_encrypted_key<M: Measurement, C: CryptoComponentRng>(
                     context: random_derivation_context_with_n_bytes(32, rng),
                     input: random_n_bytes(32, rng),
                     transport_public_key: random_transports_secret_key(rng).public_key(),
+                    output: VetKdOutput::EncryptedKey,
                 };
                 let num_of_shares = config.threshold().get();
                 let key_shares = create_and_verify_key_shares_for_each(
@@ -0,0 +1,143 @@
+//! API for ML-DSA-65 basic signatures
+use super::types;
+use ic_crypto_internal_basic_sig_der_utils as der_utils;
+use ic_crypto_secrets_containers::SecretArray;
+use ic_types::crypto::{AlgorithmId, CryptoError, CryptoResult};
+use ml_dsa::{
+    B32, EncodedSignature, EncodedVerifyingKey, KeyGen, MlDsa65, Signature, VerifyingKey,
+};
+use rand::{CryptoRng, Rng};
+
+/// The context string used for all signatures.
+///
+/// FIPS 204 allows binding a signature to an application context. Domain
+/// separation is already part of every signed IC message, so the empty
+/// context is used.
+const CONTEXT: &[u8] = &[];
+
+/// Generates an ML-DSA-65 keypair.
+pub fn keypair_from_rng<R: Rng + CryptoRng>(
+    csprng: &mut R,
+) -> (types::SecretKeyBytes, types::PublicKeyBytes) {
+    let mut seed = [0u8; types::SecretKeyBytes::SIZE];
+    csprng.fill_bytes(&mut seed);
+    let key_pair = MlDsa65::key_gen_internal(&B32::from(seed));
+    let sk = types::SecretKeyBytes(SecretArray::new_and_zeroize_argument(&mut seed));
+    let pk = types::PublicKeyBytes(key_pair.verifying_key().encode().to_vec());
+    (sk, pk)
+}
+
+/// The object identifier for ML-DSA-65 public keys
+///
+/// See [RFC 9881](https://www.rfc-editor.org/rfc/rfc9881), which specifies
+/// that the parameters of the algorithm identifier are absent.
+pub fn algorithm_identifier() -> der_utils::PkixAlgorithmIdentifier {
+    der_utils::PkixAlgorithmIdentifier::new_with_empty_param(simple_asn1::oid!(
+        2, 16, 840, 1, 101, 3, 4, 3, 18
+    ))
+}
+
+/// Decodes an ML-DSA-65 public key from a DER-encoded SubjectPublicKeyInfo.
+///
+/// Uses the ML-DSA-65 object identifier (OID) 2.16.840.1.101.3.4.3.18.
+///
+/// # Errors
+/// * `MalformedPublicKey` if the input is not a valid DER-encoding, or the
+///   OID is incorrect, or the key length is incorrect.
+pub fn public_key_from_der(pk_der: &[u8]) -> CryptoResult<types::PublicKeyBytes> {
+    let pk_bytes = der_utils::parse_public_key(
+        pk_der,
+        AlgorithmId::MlDsa65,
+        algorithm_identifier(),
+        Some(types::PublicKeyBytes::SIZE),
+    )?;
+    Ok(types::PublicKeyBytes(pk_bytes))
+}
+
+/// Encodes the given `key` as DER-encoded ML-DSA-65 SubjectPublicKeyInfo.
+///
+/// # Errors
+/// * `MalformedPublicKey` if the key has the wrong length or cannot be encoded.
+pub fn public_key_to_der(key: &types::PublicKeyBytes) -> CryptoResult<Vec<u8>> {
+    let malformed = |internal_error: String| CryptoError::MalformedPublicKey {
+        algorithm: AlgorithmId::MlDsa65,
+        key_bytes: Some(key.0.clone()),
+        internal_error,
+    };
+    if key.0.len() != types::PublicKeyBytes::SIZE {
+        return Err(malformed(format!(
+            "expected {} bytes but got {}",
+            types::PublicKeyBytes::SIZE,
+            key.0.len()
+        )));
+    }
+    der_utils::subject_public_key_info_der(algorithm_identifier().oid, &key.0).map_err(malformed)
+}
+
+/// Signs a message with an ML-DSA-65 secret key.
+///
+/// Signing is deterministic, i.e. the hedged randomness of FIPS 204 is not used.
+///
+/// # Errors
+/// * `InvalidArgument` if signing fails
+pub fn sign(msg: &[u8], sk: &types::SecretKeyBytes) -> CryptoResult<types::SignatureBytes> {
+    let key_pair = MlDsa65::key_gen_internal(&B32::from(*sk.0.expose_secret()));
+    let signature = key_pair
+        .signing_key()
+        .sign_deterministic(msg, CONTEXT)
+        .map_err(|e| CryptoError::InvalidArgument {
+            message: format!("ML-DSA-65 signing failed: {e}"),
+        })?;
+    Ok(types::SignatureBytes(signature.encode().to_vec()))
+}
+
+/// Verifies a signature using an ML-DSA-65 public key.
+///
+/// # Errors
+/// * `MalformedPublicKey` if the public key is malformed
+/// * `MalformedSignature` if the signature is malformed
+/// * `SignatureVerification` if the signature is invalid
+pub fn verify(
+    sig: &types::SignatureBytes,
+    msg: &[u8],
+    pk: &types::PublicKeyBytes,
+) -> CryptoResult<()> {
+    let encoded_pk = EncodedVerifyingKey::<MlDsa65>::try_from(pk.0.as_slice()).map_err(|_| {
+        CryptoError::MalformedPublicKey {
+            algorithm: AlgorithmId::MlDsa65,
+            key_bytes: Some(pk.0.clone()),
+            internal_error: format!(
+                "expected {} bytes but got {}",
+                types::PublicKeyBytes::SIZE,
+                pk.0.len()
+            ),
+        }
+    })?;
+    let public_key = VerifyingKey::<MlDsa65>::decode(&encoded_pk);
+
+    let malformed_signature = |internal_error: String| CryptoError::MalformedSignature {
+        algorithm: AlgorithmId::MlDsa65,
+        sig_bytes: sig.0.clone(),
+        internal_error,
+    };
+    let encoded_sig = EncodedSignature::<MlDsa65>::try_from(sig.0.as_slice()).map_err(|_| {
+        malformed_signature(format!(
+            "expected {} bytes but got {}",
+            types::SignatureBytes::SIZE,
+            sig.0.len()
+        ))
+    })?;
+    let signature = Signature::<MlDsa65>::decode(&encoded_sig)
+        .ok_or_else(|| malformed_signature("invalid signature encoding".to_string()))?;
+
+    if public_key.verify_with_context(msg, CONTEXT, &signature) {
+        Ok(())
+    } else {
+        Err(CryptoError::SignatureVerification {
+            algorithm: AlgorithmId::MlDsa65,
+            public_key_bytes: pk.0.clone(),
+            sig_bytes: sig.0.clone(),
+            internal_error: "ML-DSA-65 signature verification failed".to_string(),
+        })
+    }
+}
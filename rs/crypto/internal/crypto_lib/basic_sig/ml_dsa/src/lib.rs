@@ -0,0 +1,15 @@
+#![forbid(unsafe_code)]
+#![deny(clippy::unwrap_used)]
+
+//! Basic signatures implemented with ML-DSA-65 (FIPS 204)
+//!
+//! The implementation is provided by the RustCrypto `ml-dsa` crate, which is
+//! pure Rust and follows the final FIPS 204 standard, but is pre-1.0 and has
+//! not been independently audited. It is therefore pinned to an exact version
+//! in the workspace, and only verification is reachable from the replica
+//! (via the standalone signature verifier); key generation and signing are
+//! used by clients and tests. The crate is accessed only through this module
+//! so that it can be replaced without touching its users.
+pub mod api;
+pub mod types;
+pub use api::*;
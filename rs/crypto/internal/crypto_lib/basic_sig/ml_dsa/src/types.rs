@@ -0,0 +1,28 @@
+//! Types for ML-DSA-65 basic signatures
+use ic_crypto_secrets_containers::SecretArray;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A wrapper for the ML-DSA-65 secret key seed.
+///
+/// FIPS 204 derives the full key pair deterministically from a 32-byte seed,
+/// so only the seed is stored.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize, Zeroize, ZeroizeOnDrop)]
+pub struct SecretKeyBytes(pub SecretArray<{ SecretKeyBytes::SIZE }>);
+impl SecretKeyBytes {
+    pub const SIZE: usize = 32;
+}
+
+/// A wrapper for encoded ML-DSA-65 public key bytes.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PublicKeyBytes(pub Vec<u8>);
+impl PublicKeyBytes {
+    pub const SIZE: usize = 1952;
+}
+
+/// A wrapper for encoded ML-DSA-65 signature bytes.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct SignatureBytes(pub Vec<u8>);
+impl SignatureBytes {
+    pub const SIZE: usize = 3309;
+}
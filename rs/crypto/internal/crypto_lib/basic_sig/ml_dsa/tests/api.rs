@@ -0,0 +1,104 @@
+use assert_matches::assert_matches;
+use ic_crypto_internal_basic_sig_ml_dsa::{self as ml_dsa, types};
+use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
+use ic_types::crypto::{AlgorithmId, CryptoError};
+
+#[test]
+fn should_verify_valid_signature() {
+    let rng = &mut reproducible_rng();
+    let (sk, pk) = ml_dsa::keypair_from_rng(rng);
+    let msg = b"some message";
+
+    let sig = ml_dsa::sign(msg, &sk).expect("failed to sign");
+
+    assert_eq!(pk.0.len(), types::PublicKeyBytes::SIZE);
+    assert_eq!(sig.0.len(), types::SignatureBytes::SIZE);
+    assert_eq!(ml_dsa::verify(&sig, msg, &pk), Ok(()));
+}
+
+#[test]
+fn should_sign_deterministically() {
+    let rng = &mut reproducible_rng();
+    let (sk, _pk) = ml_dsa::keypair_from_rng(rng);
+
+    let sig_1 = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+    let sig_2 = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+
+    assert_eq!(sig_1, sig_2);
+}
+
+#[test]
+fn should_fail_to_verify_signature_on_different_message() {
+    let rng = &mut reproducible_rng();
+    let (sk, pk) = ml_dsa::keypair_from_rng(rng);
+    let sig = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+
+    let result = ml_dsa::verify(&sig, b"other message", &pk);
+
+    assert_matches!(
+        result,
+        Err(CryptoError::SignatureVerification {
+            algorithm: AlgorithmId::MlDsa65,
+            ..
+        })
+    );
+}
+
+#[test]
+fn should_fail_to_verify_signature_with_different_key() {
+    let rng = &mut reproducible_rng();
+    let (sk, _pk) = ml_dsa::keypair_from_rng(rng);
+    let (_other_sk, other_pk) = ml_dsa::keypair_from_rng(rng);
+    let sig = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+
+    let result = ml_dsa::verify(&sig, b"message", &other_pk);
+
+    assert_matches!(result, Err(CryptoError::SignatureVerification { .. }));
+}
+
+#[test]
+fn should_return_malformed_signature_on_wrong_length() {
+    let rng = &mut reproducible_rng();
+    let (sk, pk) = ml_dsa::keypair_from_rng(rng);
+    let mut sig = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+    sig.0.pop();
+
+    let result = ml_dsa::verify(&sig, b"message", &pk);
+
+    assert_matches!(result, Err(CryptoError::MalformedSignature { .. }));
+}
+
+#[test]
+fn should_return_malformed_public_key_on_wrong_length() {
+    let rng = &mut reproducible_rng();
+    let (sk, mut pk) = ml_dsa::keypair_from_rng(rng);
+    let sig = ml_dsa::sign(b"message", &sk).expect("failed to sign");
+    pk.0.push(0);
+
+    let result = ml_dsa::verify(&sig, b"message", &pk);
+
+    assert_matches!(result, Err(CryptoError::MalformedPublicKey { .. }));
+}
+
+#[test]
+fn should_der_encode_and_decode_public_key() {
+    let rng = &mut reproducible_rng();
+    let (_sk, pk) = ml_dsa::keypair_from_rng(rng);
+
+    let pk_der = ml_dsa::public_key_to_der(&pk).expect("failed to encode");
+
+    assert_eq!(pk_der.len(), 1974);
+    assert_eq!(ml_dsa::public_key_from_der(&pk_der), Ok(pk));
+}
+
+#[test]
+fn should_fail_to_decode_public_key_with_wrong_oid() {
+    let ed25519_pk_der = hex::decode(
+        "302a300506032b6570032100b3997656ba51ff6da37b61d8d549ec80717266ecf48fb5da52b654412634844c",
+    )
+    .expect("invalid hex");
+
+    let result = ml_dsa::public_key_from_der(&ed25519_pk_der);
+
+    assert_matches!(result, Err(CryptoError::MalformedPublicKey { .. }));
+}
@@ -478,3 +478,286 @@ impl EncryptedKeyShare {
         output
     }
 }
+
+/// The domain separator of threshold BLS signatures
+///
+/// Signatures follow the minimal-public-key-size variant of
+/// draft-irtf-cfrg-bls-signature-05 with the proof-of-possession
+/// ciphersuite: public keys are in G1, signatures are in G2, and the
+/// message is hashed as is.
+pub const BLS_SIGNATURE_DST: &[u8; 43] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Check that `pk` in G1 has the same discrete logarithm as `verification_pk`
+/// in G2, i.e. e(pk, g2) == e(g1, verification_pk)
+fn check_public_key_validity(pk: &G1Affine, verification_pk: &G2Affine) -> bool {
+    let verification_pk_prepared = G2Prepared::from(verification_pk);
+    Gt::multipairing(&[
+        (pk, G2Prepared::neg_generator()),
+        (G1Affine::generator(), &verification_pk_prepared),
+    ])
+    .is_identity()
+}
+
+/// Check that `signature` is a BLS signature on `message` under `pk`,
+/// i.e. e(pk, H(message)) == e(g1, signature)
+fn check_signature_validity(pk: &G1Affine, signature: &G2Affine, message: &[u8]) -> bool {
+    let msg_prepared = G2Prepared::from(&G2Affine::hash(BLS_SIGNATURE_DST, message));
+    let signature_prepared = G2Prepared::from(signature);
+    Gt::multipairing(&[
+        (pk, &msg_prepared),
+        (&G1Affine::generator().neg(), &signature_prepared),
+    ])
+    .is_identity()
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Error indicating that deserializing a BLS public key or signature (share) failed
+pub enum BlsDeserializationError {
+    /// One or more of the points were not valid
+    InvalidEncoding,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+/// A share of the derived BLS public key in G1, optionally together with a
+/// share of a BLS signature in G2
+///
+/// The public key share is what allows the signature share to be checked,
+/// since the public key shares known from the transcript are in G2.
+pub struct BlsSignatureShare {
+    pk: G1Affine,
+    signature: Option<G2Affine>,
+}
+
+impl BlsSignatureShare {
+    /// The length of the serialized encoding of a public key share
+    pub const PUBLIC_KEY_BYTES: usize = G1Affine::BYTES;
+
+    /// The length of the serialized encoding of a share with a signature
+    pub const BYTES: usize = G1Affine::BYTES + G2Affine::BYTES;
+
+    /// Create a new share of the public key derived for `context` and, if a
+    /// message is given, a share of the signature on it.
+    pub fn create(
+        master_pk: &G2Affine,
+        node_sk: &Scalar,
+        context: &DerivationContext,
+        message: Option<&[u8]>,
+    ) -> Self {
+        let (_dpk, delta) = context.derive_key(master_pk);
+
+        let dsk = delta + node_sk;
+
+        let pk = G1Affine::from(G1Affine::generator() * &dsk);
+        let signature =
+            message.map(|m| G2Affine::from(G2Affine::hash(BLS_SIGNATURE_DST, m) * &dsk));
+
+        Self { pk, signature }
+    }
+
+    /// Check if this share is valid with respect to the node's public key
+    /// share in G2
+    pub fn is_valid(
+        &self,
+        master_pk: &G2Affine,
+        node_pk: &G2Affine,
+        context: &DerivationContext,
+        message: Option<&[u8]>,
+    ) -> bool {
+        let (_dpk, offset) = context.derive_key(master_pk);
+
+        let derived_node_key = G2Affine::from(G2Affine::generator() * &offset + node_pk);
+
+        check_bls_validity(
+            &self.pk,
+            self.signature.as_ref(),
+            &derived_node_key,
+            message,
+        )
+    }
+
+    /// Deserialize a share
+    pub fn deserialize(val: &[u8]) -> Result<Self, BlsDeserializationError> {
+        let (pk, signature) = deserialize_bls(val)?;
+        Ok(Self { pk, signature })
+    }
+
+    /// Serialize a share
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_bls(&self.pk, self.signature.as_ref())
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+/// A derived BLS public key in G1, optionally together with a BLS signature
+/// in G2
+pub struct BlsSignature {
+    pk: G1Affine,
+    signature: Option<G2Affine>,
+}
+
+impl BlsSignature {
+    /// Combine, unchecked.
+    /// The returned public key and signature may be invalid.
+    fn combine_unchecked(
+        nodes: &BTreeMap<NodeIndex, BlsSignatureShare>,
+        reconstruction_threshold: usize,
+    ) -> Result<Self, EncryptedKeyCombinationError> {
+        if nodes.len() < reconstruction_threshold {
+            return Err(EncryptedKeyCombinationError::InsufficientShares);
+        }
+
+        let l = LagrangeCoefficients::at_zero(&NodeIndices::from_map(nodes));
+
+        let pk = l
+            .interpolate_g1(&nodes.iter().map(|i| &i.1.pk).collect::<Vec<_>>())
+            .expect("Number of nodes and shares guaranteed equal");
+        let signatures = nodes
+            .values()
+            .map(|share| share.signature.as_ref())
+            .collect::<Option<Vec<_>>>();
+        let signature = match signatures {
+            Some(signatures) if !signatures.is_empty() => Some(
+                l.interpolate_g2(&signatures)
+                    .expect("Number of nodes and shares guaranteed equal"),
+            ),
+            _ => None,
+        };
+
+        Ok(Self { pk, signature })
+    }
+
+    /// Combines all the given shares into a public key and signature.
+    ///
+    /// If the result is Ok(), the returned public key and signature are
+    /// guaranteed to be valid.
+    /// Does not take the nodes' individual public keys as input.
+    pub fn combine_all(
+        nodes: &BTreeMap<NodeIndex, BlsSignatureShare>,
+        reconstruction_threshold: usize,
+        master_pk: &G2Affine,
+        context: &DerivationContext,
+        message: Option<&[u8]>,
+    ) -> Result<Self, EncryptedKeyCombinationError> {
+        let c = Self::combine_unchecked(nodes, reconstruction_threshold)?;
+        if c.is_valid(master_pk, context, message) {
+            Ok(c)
+        } else {
+            Err(EncryptedKeyCombinationError::InvalidShares)
+        }
+    }
+
+    /// Filters the valid shares from the given ones, and combines them into a
+    /// valid public key and signature, if possible.
+    /// Takes also the nodes' individual public keys as input, see
+    /// [`EncryptedKey::combine_valid_shares`].
+    pub fn combine_valid_shares(
+        nodes: &BTreeMap<NodeIndex, (G2Affine, BlsSignatureShare)>,
+        reconstruction_threshold: usize,
+        master_pk: &G2Affine,
+        context: &DerivationContext,
+        message: Option<&[u8]>,
+    ) -> Result<Self, EncryptedKeyCombinationError> {
+        if nodes.len() < reconstruction_threshold {
+            return Err(EncryptedKeyCombinationError::InsufficientShares);
+        }
+
+        let mut valid_shares = BTreeMap::new();
+
+        for (node_index, (node_pk, node_share)) in nodes.iter() {
+            if node_share.is_valid(master_pk, node_pk, context, message) {
+                valid_shares.insert(*node_index, node_share.clone());
+
+                if valid_shares.len() >= reconstruction_threshold {
+                    break;
+                }
+            }
+        }
+
+        if valid_shares.len() < reconstruction_threshold {
+            return Err(EncryptedKeyCombinationError::InsufficientValidKeyShares);
+        }
+
+        let c = Self::combine_unchecked(&valid_shares, reconstruction_threshold)?;
+
+        if c.is_valid(master_pk, context, message) {
+            Ok(c)
+        } else {
+            Err(EncryptedKeyCombinationError::ReconstructionFailed)
+        }
+    }
+
+    /// Check if this public key is the one derived for `context`, and if the
+    /// signature, if any, is valid on `message`
+    pub fn is_valid(
+        &self,
+        master_pk: &G2Affine,
+        context: &DerivationContext,
+        message: Option<&[u8]>,
+    ) -> bool {
+        let dpk = DerivedPublicKey::derive_sub_key(master_pk, context);
+        check_bls_validity(&self.pk, self.signature.as_ref(), &dpk.pt, message)
+    }
+
+    /// Return the public key
+    pub fn public_key(&self) -> &G1Affine {
+        &self.pk
+    }
+
+    /// Return the signature, if any
+    pub fn signature(&self) -> Option<&G2Affine> {
+        self.signature.as_ref()
+    }
+
+    /// Deserialize a public key, optionally followed by a signature
+    pub fn deserialize(val: &[u8]) -> Result<Self, BlsDeserializationError> {
+        let (pk, signature) = deserialize_bls(val)?;
+        Ok(Self { pk, signature })
+    }
+
+    /// Serialize the public key, followed by the signature if any
+    pub fn serialize(&self) -> Vec<u8> {
+        serialize_bls(&self.pk, self.signature.as_ref())
+    }
+}
+
+fn check_bls_validity(
+    pk: &G1Affine,
+    signature: Option<&G2Affine>,
+    verification_pk: &G2Affine,
+    message: Option<&[u8]>,
+) -> bool {
+    if !check_public_key_validity(pk, verification_pk) {
+        return false;
+    }
+    match (signature, message) {
+        (None, None) => true,
+        (Some(signature), Some(message)) => check_signature_validity(pk, signature, message),
+        (_, _) => false,
+    }
+}
+
+fn deserialize_bls(val: &[u8]) -> Result<(G1Affine, Option<G2Affine>), BlsDeserializationError> {
+    if val.len() != G1Affine::BYTES && val.len() != G1Affine::BYTES + G2Affine::BYTES {
+        return Err(BlsDeserializationError::InvalidEncoding);
+    }
+    let pk = G1Affine::deserialize(&&val[..G1Affine::BYTES])
+        .map_err(|_| BlsDeserializationError::InvalidEncoding)?;
+    let signature = if val.len() > G1Affine::BYTES {
+        Some(
+            G2Affine::deserialize(&&val[G1Affine::BYTES..])
+                .map_err(|_| BlsDeserializationError::InvalidEncoding)?,
+        )
+    } else {
+        None
+    };
+    Ok((pk, signature))
+}
+
+fn serialize_bls(pk: &G1Affine, signature: Option<&G2Affine>) -> Vec<u8> {
+    let mut output = Vec::with_capacity(G1Affine::BYTES + G2Affine::BYTES);
+    output.extend_from_slice(&pk.serialize());
+    if let Some(signature) = signature {
+        output.extend_from_slice(&signature.serialize());
+    }
+    output
+}
@@ -0,0 +1,79 @@
+//! Checks that the threshold BLS signatures that the replica returns verify
+//! with blst under the minimal-public-key-size proof-of-possession ciphersuite.
+use blst::{BLST_ERROR, min_pk};
+use ic_crypto_internal_bls12_381_type::{G2Affine, Polynomial, Scalar};
+use ic_crypto_internal_bls12_381_vetkd::*;
+use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
+use rand::Rng;
+use std::collections::BTreeMap;
+
+const DST_POP: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const DST_NUL: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+#[test]
+fn should_use_the_min_pk_pop_ciphersuite() {
+    assert_eq!(&BLS_SIGNATURE_DST[..], DST_POP);
+}
+
+#[test]
+fn should_produce_min_pk_pop_signatures_verifiable_with_blst() {
+    let rng = &mut reproducible_rng();
+
+    let nodes = 4;
+    let threshold = 3;
+    let poly = Polynomial::random(threshold, rng);
+    let master_pk = G2Affine::from(G2Affine::generator() * poly.coeff(0));
+
+    let context = DerivationContext::new(&rng.r#gen::<[u8; 10]>(), b"context");
+    let message = rng.r#gen::<[u8; 32]>();
+
+    let shares: BTreeMap<NodeIndex, BlsSignatureShare> = (0..nodes)
+        .map(|node| {
+            let node_sk = poly.evaluate_at(&Scalar::from_node_index(node));
+            let share = BlsSignatureShare::create(&master_pk, &node_sk, &context, Some(&message));
+            (node, share)
+        })
+        .collect();
+    let combined =
+        BlsSignature::combine_all(&shares, threshold, &master_pk, &context, Some(&message))
+            .expect("failed to combine shares");
+
+    let public_key = combined.public_key().serialize();
+    let signature = combined
+        .signature()
+        .expect("a signature was requested")
+        .serialize();
+    assert_eq!(public_key.len(), 48);
+    assert_eq!(signature.len(), 96);
+
+    let blst_signature =
+        min_pk::Signature::sig_validate(&signature, true).expect("invalid signature");
+    let blst_public_key = min_pk::PublicKey::key_validate(&public_key).expect("invalid key");
+
+    assert_eq!(
+        blst_signature.verify(true, &message, DST_POP, &[], &blst_public_key, true),
+        BLST_ERROR::BLST_SUCCESS
+    );
+    assert_ne!(
+        blst_signature.verify(true, b"other message", DST_POP, &[], &blst_public_key, true),
+        BLST_ERROR::BLST_SUCCESS
+    );
+    assert_ne!(
+        blst_signature.verify(true, &message, DST_NUL, &[], &blst_public_key, true),
+        BLST_ERROR::BLST_SUCCESS
+    );
+
+    // A public key combined without a signature is the same key.
+    let public_key_shares: BTreeMap<NodeIndex, BlsSignatureShare> = (0..nodes)
+        .map(|node| {
+            let node_sk = poly.evaluate_at(&Scalar::from_node_index(node));
+            let share = BlsSignatureShare::create(&master_pk, &node_sk, &context, None);
+            (node, share)
+        })
+        .collect();
+    let public_key_only =
+        BlsSignature::combine_all(&public_key_shares, threshold, &master_pk, &context, None)
+            .expect("failed to combine shares");
+    assert_eq!(public_key_only.public_key().serialize(), public_key);
+    assert_eq!(public_key_only.signature(), None);
+}
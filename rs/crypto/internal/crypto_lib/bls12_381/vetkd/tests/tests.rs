@@ -444,3 +444,134 @@ fn test_protocol_execution() {
         assert_eq!(k, vetkey);
     }
 }
+
+#[test]
+fn test_bls_signature_protocol_execution() {
+    let rng = &mut reproducible_rng();
+
+    let nodes = 13;
+    let threshold = 5;
+
+    let setup = VetkdTestProtocolSetup::new(rng, nodes, threshold);
+    let proto = VetkdTestProtocolExecution::new(rng, &setup);
+
+    let create_shares = |message: Option<&[u8]>| {
+        let mut node_info = BTreeMap::new();
+        for (node_idx, (node_pk, node_sk)) in setup.node_key_material.iter().enumerate() {
+            let share =
+                BlsSignatureShare::create(&setup.master_pk, node_sk, &proto.context, message);
+
+            assert!(share.is_valid(&setup.master_pk, node_pk, &proto.context, message));
+
+            // check that the serialization round trips:
+            let share_bytes = share.serialize();
+            let expected_len = match message {
+                Some(_) => BlsSignatureShare::BYTES,
+                None => BlsSignatureShare::PUBLIC_KEY_BYTES,
+            };
+            assert_eq!(share_bytes.len(), expected_len);
+            assert_eq!(
+                BlsSignatureShare::deserialize(&share_bytes),
+                Ok(share.clone())
+            );
+
+            node_info.insert(node_idx as NodeIndex, (node_pk.clone(), share));
+        }
+        node_info
+    };
+    let without_public_keys = |node_info: &BTreeMap<NodeIndex, (G2Affine, BlsSignatureShare)>| {
+        node_info
+            .iter()
+            .map(|(idx, (_pk, share))| (*idx, share.clone()))
+            .collect::<BTreeMap<_, _>>()
+    };
+
+    let message = proto.input.as_slice();
+    let node_info = create_shares(Some(message));
+    let shares = without_public_keys(&node_info);
+
+    let mut signatures = vec![];
+    for rec_threshold in 1..nodes {
+        let subset = random_subset(rng, &shares, rec_threshold);
+        match BlsSignature::combine_all(
+            &subset,
+            threshold,
+            &setup.master_pk,
+            &proto.context,
+            Some(message),
+        ) {
+            Ok(signature) => {
+                assert!(rec_threshold >= threshold);
+                assert!(signature.is_valid(&setup.master_pk, &proto.context, Some(message)));
+                assert!(!signature.is_valid(&setup.master_pk, &proto.context, Some(b"other")));
+                assert!(!signature.is_valid(&setup.master_pk, &proto.context, None));
+                signatures.push(signature);
+            }
+            Err(e) => {
+                assert!(rec_threshold < threshold);
+                assert_eq!(e, EncryptedKeyCombinationError::InsufficientShares);
+            }
+        }
+    }
+    // BLS signatures are deterministic
+    for signature in &signatures {
+        assert_eq!(signature, &signatures[0]);
+    }
+
+    // The public key is the G1 counterpart of the derived public key in G2
+    assert_eq!(
+        Gt::pairing(signatures[0].public_key(), G2Affine::generator()),
+        Gt::pairing(G1Affine::generator(), proto.derived_pk.point())
+    );
+
+    // The public key alone can be combined from shares without a signature
+    let public_key_shares = without_public_keys(&create_shares(None));
+    let public_key = BlsSignature::combine_all(
+        &public_key_shares,
+        threshold,
+        &setup.master_pk,
+        &proto.context,
+        None,
+    )
+    .expect("failed to combine public key shares");
+    assert_eq!(public_key.public_key(), signatures[0].public_key());
+    assert_eq!(public_key.signature(), None);
+    assert_eq!(
+        BlsSignature::deserialize(&signatures[0].serialize()),
+        Ok(signatures[0].clone())
+    );
+
+    // Shares of a signature on another message are detected
+    let wrong_node_info = create_shares(Some(b"another message"));
+    let mut mixed = random_subset(rng, &shares, threshold - 1);
+    let unused_idx = (0..nodes as NodeIndex)
+        .find(|idx| !mixed.contains_key(idx))
+        .expect("there is an unused index");
+    mixed.insert(unused_idx, wrong_node_info[&unused_idx].1.clone());
+    assert_eq!(
+        BlsSignature::combine_all(
+            &mixed,
+            threshold,
+            &setup.master_pk,
+            &proto.context,
+            Some(message)
+        ),
+        Err(EncryptedKeyCombinationError::InvalidShares)
+    );
+
+    let mut mixed_info = random_subset(rng, &node_info, threshold);
+    for (idx, (pk, share)) in random_subset(rng, &wrong_node_info, 4) {
+        // Avoid overwriting existing valid shares
+        mixed_info.insert(idx + 10000, (pk, share));
+    }
+    assert_eq!(
+        BlsSignature::combine_valid_shares(
+            &mixed_info,
+            threshold,
+            &setup.master_pk,
+            &proto.context,
+            Some(message)
+        ),
+        Ok(signatures[0].clone())
+    );
+}
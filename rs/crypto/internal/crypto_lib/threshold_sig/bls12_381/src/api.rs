@@ -35,8 +35,8 @@
 //!   the corresponding internal methods and serialises the responses.
 use super::crypto;
 use super::types::{
-    CombinedSignatureBytes, IndividualSignature, IndividualSignatureBytes, PublicCoefficients,
-    SecretKeyBytes,
+    CombinedSignature, CombinedSignatureBytes, IndividualSignature, IndividualSignatureBytes,
+    PublicCoefficients, SecretKeyBytes,
 };
 use crate::api::threshold_sign_error::ClibThresholdSignError;
 use crate::types::PublicKey;
@@ -48,6 +48,7 @@ use ic_types::{
     NodeIndex, NumberOfNodes,
     crypto::{CryptoError, CryptoResult},
 };
+use rand::{CryptoRng, RngCore};
 use std::convert::{TryFrom, TryInto};
 
 pub mod dkg_errors;
@@ -241,6 +242,41 @@ pub fn verify_combined_signature(
     crypto::verify_combined_sig(message, &signature, &pk)
 }
 
+/// Verifies that a batch of combined signatures is valid.
+///
+/// This is considerably faster than calling [`verify_combined_signature`]
+/// for each signature, but a failure does not indicate which of the
+/// signatures is invalid. Callers that need per-signature results must
+/// verify individually if the batch is rejected.
+///
+/// # Arguments
+/// * `messages_sigs_pks` contains, for each signature, the signed bytes, the
+///   combined signature and the combined public key.
+/// * `rng` is used to randomize the batch verification.
+/// # Panics
+/// This method is not expected to panic.
+/// # Errors
+/// * If any signature or public key cannot be parsed, or if at least one of
+///   the signatures is not valid, this will return an error.
+pub fn verify_combined_signature_batch<R: RngCore + CryptoRng>(
+    messages_sigs_pks: &[(&[u8], &CombinedSignatureBytes, &PublicKeyBytes)],
+    rng: &mut R,
+) -> CryptoResult<()> {
+    let parsed = messages_sigs_pks
+        .iter()
+        .map(|(message, signature, public_key)| {
+            let signature = CombinedSignature::try_from(*signature)?;
+            let pk = PublicKey::try_from(*public_key)?;
+            Ok((signature, pk, *message))
+        })
+        .collect::<CryptoResult<Vec<_>>>()?;
+    let batch: Vec<_> = parsed
+        .iter()
+        .map(|(signature, pk, message)| (signature, pk, *message))
+        .collect();
+    crypto::verify_combined_sigs_batch(&batch, rng)
+}
+
 /// Verifies that a combined signature is valid, making use of a cache
 ///
 /// The cache is a global shared signature cache defined in `cache.rs`
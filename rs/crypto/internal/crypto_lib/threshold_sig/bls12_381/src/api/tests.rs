@@ -253,6 +253,40 @@ fn should_invalid_threshold_signatures_not_be_cached() {
     }
 }
 
+#[test]
+fn should_verify_batch_of_combined_signatures_and_reject_if_one_is_invalid() {
+    let rng = &mut reproducible_rng();
+    let threshold = NumberOfNodes::from(1);
+
+    let mut messages_sigs_pks = Vec::new();
+    for i in 0..10_u8 {
+        let (public_coefficients, secret_keys) =
+            util::generate_threshold_key(Seed::from_rng(rng), threshold, threshold)
+                .expect("Failed to deal");
+        // Some keys sign several messages and some messages are signed by
+        // several keys, so that all batching strategies are exercised.
+        for message in [vec![i], vec![i, i], b"common message".to_vec()] {
+            let signature = tsig::sign_message(&message, &secret_keys[0]).expect("Failed to sign");
+            let combined = tsig::combine_signatures(&[Some(signature)], threshold)
+                .expect("Failed to combine signatures");
+            let public_key = tsig::combined_public_key(&public_coefficients)
+                .expect("Failed to get combined public key");
+            messages_sigs_pks.push((message, combined, public_key));
+        }
+    }
+    let batch: Vec<_> = messages_sigs_pks
+        .iter()
+        .map(|(message, signature, public_key)| (message.as_slice(), signature, public_key))
+        .collect();
+
+    assert_eq!(tsig::verify_combined_signature_batch(&batch, rng), Ok(()));
+
+    let (_, other_signature, _) = &messages_sigs_pks[1];
+    let mut corrupted_batch = batch.clone();
+    corrupted_batch[0].1 = other_signature;
+    assert!(tsig::verify_combined_signature_batch(&corrupted_batch, rng).is_err());
+}
+
 proptest! {
         #![proptest_config(ProptestConfig {
             cases: 4,
@@ -7,13 +7,16 @@ use super::types::{
 use crate::api::dkg_errors::InvalidArgumentError;
 
 use crate::types::PublicKey;
-use ic_crypto_internal_bls12_381_type::{G1Projective, G2Affine, Scalar, verify_bls_signature};
+use ic_crypto_internal_bls12_381_type::{
+    G1Affine, G1Projective, G2Affine, Scalar, verify_bls_signature, verify_bls_signature_batch,
+};
 use ic_crypto_internal_seed::Seed;
 use ic_crypto_internal_types::sign::threshold_sig::public_key::bls12_381::PublicKeyBytes;
 use ic_types::{
     NodeIndex, NumberOfNodes,
     crypto::{AlgorithmId, CryptoError, CryptoResult},
 };
+use rand::{CryptoRng, RngCore};
 
 /// Domain separator for Hash-to-G1 to be used for signature generation as
 /// as specified in the Basic ciphersuite in https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-4.2.1
@@ -264,6 +267,45 @@ pub(crate) fn verify_combined_sig(
     }
 }
 
+/// Verifies a batch of combined signatures against the provided public keys.
+///
+/// # Returns
+/// * OK, if every signature is a valid BLS signature on its message
+/// * Err, otherwise. The error does not identify which signature is invalid.
+pub(crate) fn verify_combined_sigs_batch<R: RngCore + CryptoRng>(
+    sigs_pks_msgs: &[(&CombinedSignature, &PublicKey, &[u8])],
+    rng: &mut R,
+) -> CryptoResult<()> {
+    let sigs: Vec<G1Affine> = sigs_pks_msgs
+        .iter()
+        .map(|(sig, _pk, _msg)| sig.to_affine())
+        .collect();
+    let pks: Vec<G2Affine> = sigs_pks_msgs
+        .iter()
+        .map(|(_sig, pk, _msg)| pk.0.to_affine())
+        .collect();
+    let msgs: Vec<G1Affine> = sigs_pks_msgs
+        .iter()
+        .map(|(_sig, _pk, msg)| hash_message_to_g1(msg).to_affine())
+        .collect();
+    let batch: Vec<_> = sigs
+        .iter()
+        .zip(pks.iter())
+        .zip(msgs.iter())
+        .map(|((sig, pk), msg)| (sig, pk, msg))
+        .collect();
+
+    match verify_bls_signature_batch(&batch, rng) {
+        true => Ok(()),
+        false => Err(CryptoError::SignatureVerification {
+            algorithm: AlgorithmId::ThresBls12_381,
+            public_key_bytes: vec![],
+            sig_bytes: vec![],
+            internal_error: "Invalid combined threshold signature in batch".to_string(),
+        }),
+    }
+}
+
 /// Verifies an individual or combined signature against the provided public
 /// key.
 fn verify(message: &[u8], signature: &Signature, public_key: &PublicKey) -> bool {
@@ -1050,6 +1050,17 @@ pub trait VetKdCspVault {
         context: VetKdDerivationContext,
         input: Vec<u8>,
     ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
+
+    /// Generates a share of a derived BLS public key and, if a message is
+    /// given, of a BLS signature on it (cf.
+    /// [`ic_types::crypto::vetkd::VetKdOutput`]).
+    fn create_vetkd_bls_share(
+        &self,
+        key_id: KeyId,
+        master_public_key: Vec<u8>,
+        context: VetKdDerivationContext,
+        message: Option<Vec<u8>>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
 }
 
 /// Vault-level error for vetKD key share creation.
@@ -5,7 +5,7 @@ use crate::types::CspSecretKey;
 use crate::vault::api::{VetKdCspVault, VetKdEncryptedKeyShareCreationVaultError};
 use crate::vault::local_csp_vault::LocalCspVault;
 use ic_crypto_internal_bls12_381_vetkd::{
-    DerivationContext, EncryptedKeyShare, G2Affine, PairingInvalidPoint, Scalar,
+    BlsSignatureShare, DerivationContext, EncryptedKeyShare, G2Affine, PairingInvalidPoint, Scalar,
     TransportPublicKey, TransportPublicKeyDeserializationError,
 };
 use ic_crypto_internal_logmon::metrics::{MetricsDomain, MetricsResult, MetricsScope};
@@ -44,6 +44,28 @@ impl<R: Rng + CryptoRng, S: SecretKeyStore, C: SecretKeyStore, P: PublicKeyStore
         );
         result
     }
+
+    /// Generates a share of a derived BLS public key and, if a message is
+    /// given, of a BLS signature on it.
+    fn create_vetkd_bls_share(
+        &self,
+        key_id: KeyId,
+        master_public_key: Vec<u8>,
+        context: VetKdDerivationContext,
+        message: Option<Vec<u8>>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        let start_time = self.metrics.now();
+        let result =
+            self.create_vetkd_bls_share_internal(key_id, master_public_key, context, message);
+        self.metrics.observe_duration_seconds(
+            MetricsDomain::VetKd,
+            MetricsScope::Local,
+            "create_vetkd_bls_share",
+            MetricsResult::from(&result),
+            start_time,
+        );
+        result
+    }
 }
 
 impl<R: Rng + CryptoRng, S: SecretKeyStore, C: SecretKeyStore, P: PublicKeyStore>
@@ -69,25 +91,7 @@ impl<R: Rng + CryptoRng, S: SecretKeyStore, C: SecretKeyStore, P: PublicKeyStore
                 }
             })?;
 
-        let secret_key_from_store = self.sks_read_lock().get(&key_id).ok_or(
-            VetKdEncryptedKeyShareCreationVaultError::SecretKeyMissingOrWrongType(format!(
-                "missing key with ID {key_id}"
-            )),
-        )?;
-        let secret_bls_scalar =
-            if let CspSecretKey::ThresBls12_381(secret_key_bytes) = &secret_key_from_store {
-                // We use the unchecked deserialization here because it is slightly cheaper, but mainly because
-                // it cannot fail, and the data is anyway trusted as it comes from the secret key store.
-                Ok(Scalar::deserialize_unchecked(
-                    secret_key_bytes.inner_secret().expose_secret(),
-                ))
-            } else {
-                Err(
-                    VetKdEncryptedKeyShareCreationVaultError::SecretKeyMissingOrWrongType(format!(
-                        "wrong secret key type for key with ID {key_id}: expected ThresBls12_381"
-                    )),
-                )
-            }?;
+        let secret_bls_scalar = self.secret_bls_scalar(key_id)?;
 
         // Create encrypted key share using our library
         let encrypted_key_share = EncryptedKeyShare::create(
@@ -103,4 +107,52 @@ impl<R: Rng + CryptoRng, S: SecretKeyStore, C: SecretKeyStore, P: PublicKeyStore
             encrypted_key_share.serialize().to_vec(),
         ))
     }
+
+    fn create_vetkd_bls_share_internal(
+        &self,
+        key_id: KeyId,
+        master_public_key: Vec<u8>,
+        context: VetKdDerivationContext,
+        message: Option<Vec<u8>>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        let master_public_key =
+            G2Affine::deserialize(&master_public_key).map_err(|_: PairingInvalidPoint| {
+                VetKdEncryptedKeyShareCreationVaultError::InvalidArgumentMasterPublicKey
+            })?;
+
+        let secret_bls_scalar = self.secret_bls_scalar(key_id)?;
+
+        let share = BlsSignatureShare::create(
+            &master_public_key,
+            &secret_bls_scalar,
+            &DerivationContext::new(context.caller.as_slice(), &context.context),
+            message.as_deref(),
+        );
+
+        Ok(VetKdEncryptedKeyShareContent(share.serialize()))
+    }
+
+    fn secret_bls_scalar(
+        &self,
+        key_id: KeyId,
+    ) -> Result<Scalar, VetKdEncryptedKeyShareCreationVaultError> {
+        let secret_key_from_store = self.sks_read_lock().get(&key_id).ok_or(
+            VetKdEncryptedKeyShareCreationVaultError::SecretKeyMissingOrWrongType(format!(
+                "missing key with ID {key_id}"
+            )),
+        )?;
+        if let CspSecretKey::ThresBls12_381(secret_key_bytes) = &secret_key_from_store {
+            // We use the unchecked deserialization here because it is slightly cheaper, but mainly because
+            // it cannot fail, and the data is anyway trusted as it comes from the secret key store.
+            Ok(Scalar::deserialize_unchecked(
+                secret_key_bytes.inner_secret().expose_secret(),
+            ))
+        } else {
+            Err(
+                VetKdEncryptedKeyShareCreationVaultError::SecretKeyMissingOrWrongType(format!(
+                    "wrong secret key type for key with ID {key_id}: expected ThresBls12_381"
+                )),
+            )
+        }
+    }
 }
@@ -3,7 +3,9 @@ use crate::types::CspSecretKey;
 use crate::vault::api::{VetKdCspVault, VetKdEncryptedKeyShareCreationVaultError};
 use crate::{LocalCspVault, key_id::KeyId};
 use assert_matches::assert_matches;
-use ic_crypto_internal_bls12_381_vetkd::{G1Affine, G2Affine, Scalar};
+use ic_crypto_internal_bls12_381_vetkd::{
+    BlsSignatureShare, DerivationContext, G1Affine, G2Affine, Scalar,
+};
 use ic_crypto_internal_multi_sig_bls12381::types as multi_types;
 use ic_crypto_internal_threshold_sig_bls12381::types as threshold_types;
 use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
@@ -115,6 +117,78 @@ fn should_fail_to_create_key_share_if_key_in_secret_key_store_has_wrong_type() {
     );
 }
 
+#[test]
+fn should_correctly_create_vetkd_bls_share() {
+    let rng = &mut reproducible_rng();
+    let test_env = CreateVetKdKeyShareTestSetup::new(rng);
+    let master_public_key = G2Affine::deserialize(&test_env.master_public_key).unwrap();
+    let context = DerivationContext::new(
+        test_env.context.caller.as_slice(),
+        &test_env.context.context,
+    );
+    let message = test_env.input.clone();
+
+    let result = test_env.create_vetkd_bls_share(Some(message.clone()));
+
+    let share = BlsSignatureShare::deserialize(&result.expect("failed to create share").0)
+        .expect("invalid share");
+    // With a single node, the node's public key share is the master public key.
+    assert!(share.is_valid(
+        &master_public_key,
+        &master_public_key,
+        &context,
+        Some(&message)
+    ));
+}
+
+#[test]
+fn should_correctly_create_vetkd_bls_public_key_share() {
+    let rng = &mut reproducible_rng();
+    let test_env = CreateVetKdKeyShareTestSetup::new(rng);
+    let master_public_key = G2Affine::deserialize(&test_env.master_public_key).unwrap();
+    let context = DerivationContext::new(
+        test_env.context.caller.as_slice(),
+        &test_env.context.context,
+    );
+
+    let result = test_env.create_vetkd_bls_share(None);
+
+    let share_bytes = result.expect("failed to create share").0;
+    assert_eq!(share_bytes.len(), BlsSignatureShare::PUBLIC_KEY_BYTES);
+    let share = BlsSignatureShare::deserialize(&share_bytes).expect("invalid share");
+    assert!(share.is_valid(&master_public_key, &master_public_key, &context, None));
+}
+
+#[test]
+fn should_fail_to_create_bls_share_with_invalid_master_public_key() {
+    let rng = &mut reproducible_rng();
+    let mut test_env = CreateVetKdKeyShareTestSetup::new(rng);
+    test_env.master_public_key = b"invalid-master-public-key".to_vec();
+    test_env.secret_key_store_override = Some(MockSecretKeyStore::new());
+
+    let result = test_env.create_vetkd_bls_share(Some(b"message".to_vec()));
+
+    assert_matches!(
+        result,
+        Err(VetKdEncryptedKeyShareCreationVaultError::InvalidArgumentMasterPublicKey)
+    );
+}
+
+#[test]
+fn should_fail_to_create_bls_share_if_key_is_missing_in_secret_key_store() {
+    let mut rng = reproducible_rng();
+    let mut test_env = CreateVetKdKeyShareTestSetup::new(&mut rng);
+
+    test_env.secret_key_store_return_override = Some(None);
+
+    let result = test_env.create_vetkd_bls_share(Some(b"message".to_vec()));
+
+    assert_matches!(
+        result, Err(VetKdEncryptedKeyShareCreationVaultError::SecretKeyMissingOrWrongType(error))
+        if error.contains("missing key with ID")
+    );
+}
+
 struct CreateVetKdKeyShareTestSetup {
     key_id: KeyId,
     master_public_key: Vec<u8>,
@@ -156,6 +230,36 @@ impl CreateVetKdKeyShareTestSetup {
     pub fn create_encrypted_vetkd_key_share(
         self,
     ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        let (key_id, master_public_key, transport_public_key, context, input) = (
+            self.key_id,
+            self.master_public_key.clone(),
+            self.transport_public_key.clone(),
+            self.context.clone(),
+            self.input.clone(),
+        );
+        self.vault().create_encrypted_vetkd_key_share(
+            key_id,
+            master_public_key,
+            transport_public_key,
+            context,
+            input,
+        )
+    }
+
+    pub fn create_vetkd_bls_share(
+        self,
+        message: Option<Vec<u8>>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        let (key_id, master_public_key, context) = (
+            self.key_id,
+            self.master_public_key.clone(),
+            self.context.clone(),
+        );
+        self.vault()
+            .create_vetkd_bls_share(key_id, master_public_key, context, message)
+    }
+
+    fn vault(self) -> impl VetKdCspVault {
         let node_sks = if let Some(node_sks_override) = self.secret_key_store_override {
             node_sks_override
         } else {
@@ -178,18 +282,10 @@ impl CreateVetKdKeyShareTestSetup {
             node_sks
         };
 
-        let vault = LocalCspVault::builder_for_test()
+        LocalCspVault::builder_for_test()
             .with_rng(self.rng)
             .with_mock_stores()
             .with_node_secret_key_store(node_sks)
-            .build();
-
-        vault.create_encrypted_vetkd_key_share(
-            self.key_id,
-            self.master_public_key.clone(),
-            self.transport_public_key.clone(),
-            self.context.clone(),
-            self.input.clone(),
-        )
+            .build()
     }
 }
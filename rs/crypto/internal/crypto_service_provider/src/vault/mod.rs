@@ -1,6 +1,6 @@
 use self::api::CspVault;
 use self::local_csp_vault::ProdLocalCspVault;
-use self::remote_csp_vault::RemoteCspVault;
+use self::remote_csp_vault::{RemoteCspVault, RemoteCspVaultEndpoint};
 use crate::vault::api::{
     CspBasicSignatureError, CspMultiSignatureError, CspSecretKeyStoreContainsError,
 };
@@ -20,12 +20,12 @@ pub mod test_utils;
 
 /// Creates a production-grade crypto vault.
 ///
-/// If the `config`'s vault type is `UnixSocket`, a `tokio_runtime_handle`
-/// is provided, which is then used for the `async`hronous communication
-/// with the vault via RPC.
+/// If the `config`'s vault type is a remote one (`UnixSocket`, `Vsock` or
+/// `Tcp`), a `tokio_runtime_handle` is provided, which is then used for the
+/// `async`hronous communication with the vault via RPC.
 ///
 /// # Panics
-/// Panics if the `config`'s vault type is `UnixSocket` and
+/// Panics if the `config`'s vault type is a remote one and
 /// `tokio_runtime_handle` is `None`.
 pub fn vault_from_config(
     config: &CryptoConfig,
@@ -46,6 +46,14 @@ pub fn vault_from_config(
             logger,
             metrics,
         ),
+        CspVaultType::Vsock { .. } | CspVaultType::Tcp { .. } => remote_vault(
+            RemoteCspVaultEndpoint::from_vault_type(&config.csp_vault_type)
+                .expect("vsock and TCP vaults are remote"),
+            tokio_runtime_handle.expect("missing tokio runtime handle"),
+            config,
+            logger,
+            metrics,
+        ),
     }
 }
 
@@ -89,6 +97,25 @@ fn unix_socket_vault(
     Arc::new(vault)
 }
 
+fn remote_vault(
+    endpoint: RemoteCspVaultEndpoint,
+    rt_handle: tokio::runtime::Handle,
+    config: &CryptoConfig,
+    logger: ReplicaLogger,
+    metrics: Arc<CryptoMetrics>,
+) -> Arc<dyn CspVault> {
+    info!(
+        logger,
+        "Proceeding with a remote csp_vault at {}, CryptoConfig: {:?}", endpoint, config
+    );
+    let vault = RemoteCspVault::builder_for_endpoint(endpoint.clone(), rt_handle)
+        .with_logger(logger)
+        .with_metrics(metrics)
+        .build()
+        .unwrap_or_else(|e| panic!("Could not connect to CspVault at {endpoint}: {e:?}"));
+    Arc::new(vault)
+}
+
 impl From<CspBasicSignatureError> for CryptoError {
     fn from(e: CspBasicSignatureError) -> CryptoError {
         match e {
@@ -315,6 +315,7 @@ enum CspVaultMethod {
     CreateEcdsaSigShare,
     CreateSchnorrSigShare,
     CreateEncryptedVetKdKeyShare,
+    CreateVetKdBlsShare,
     NewPublicSeed,
 }
 
@@ -393,6 +394,7 @@ impl CspVaultMethod {
             CspVaultMethod::CreateEncryptedVetKdKeyShare => {
                 (MetricsDomain::VetKd, "create_encrypted_vetkd_key_share")
             }
+            CspVaultMethod::CreateVetKdBlsShare => (MetricsDomain::VetKd, "create_vetkd_bls_share"),
             CspVaultMethod::NewPublicSeed => (MetricsDomain::PublicSeed, "new_public_seed"),
         }
     }
@@ -433,6 +435,7 @@ impl From<&TarpcCspVaultRequest> for CspVaultMethod {
             Req::CreateEcdsaSigShare { .. } => Method::CreateEcdsaSigShare,
             Req::CreateSchnorrSigShare { .. } => Method::CreateSchnorrSigShare,
             Req::CreateEncryptedVetkdKeyShare { .. } => Method::CreateEncryptedVetKdKeyShare,
+            Req::CreateVetkdBlsShare { .. } => Method::CreateVetKdBlsShare,
             Req::NewPublicSeed { .. } => Method::NewPublicSeed,
         }
     }
@@ -473,6 +476,7 @@ impl From<&TarpcCspVaultResponse> for CspVaultMethod {
             Resp::CreateEcdsaSigShare { .. } => Method::CreateEcdsaSigShare,
             Resp::CreateSchnorrSigShare { .. } => Method::CreateSchnorrSigShare,
             Resp::CreateEncryptedVetkdKeyShare { .. } => Method::CreateEncryptedVetKdKeyShare,
+            Resp::CreateVetkdBlsShare { .. } => Method::CreateVetKdBlsShare,
             Resp::NewPublicSeed { .. } => Method::NewPublicSeed,
         }
     }
@@ -0,0 +1,278 @@
+use crate::vault::remote_csp_vault::tls;
+use ic_config::crypto::{CspVaultType, RemoteCspVaultTlsConfig};
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream, UnixListener, UnixStream};
+use tokio_rustls::TlsAcceptor;
+#[cfg(target_os = "linux")]
+use vsock_lib::stream::{AsyncVsockListener, AsyncVsockStream, VMADDR_CID_ANY};
+
+/// The maximum time a client may take to complete the TLS handshake after its
+/// connection was accepted.
+pub(super) const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The transport over which a client reaches a remote CSP vault server.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RemoteCspVaultEndpoint {
+    /// A Unix domain socket on the same host. Access control is left to the
+    /// file system permissions of the socket.
+    UnixSocket(PathBuf),
+    /// A vsock address, e.g., of a separate VM on the same host.
+    Vsock {
+        cid: u32,
+        port: u32,
+        tls: RemoteCspVaultTlsConfig,
+    },
+    /// A TCP address.
+    Tcp {
+        address: SocketAddr,
+        tls: RemoteCspVaultTlsConfig,
+    },
+}
+
+impl RemoteCspVaultEndpoint {
+    /// Returns the endpoint of the remote vault configured by `vault_type`, or
+    /// `None` if the vault runs in the replica process.
+    pub fn from_vault_type(vault_type: &CspVaultType) -> Option<Self> {
+        match vault_type {
+            CspVaultType::InReplica => None,
+            CspVaultType::UnixSocket { logic, .. } => Some(Self::UnixSocket(logic.clone())),
+            CspVaultType::Vsock { cid, port, tls } => Some(Self::Vsock {
+                cid: *cid,
+                port: *port,
+                tls: tls.clone(),
+            }),
+            CspVaultType::Tcp { address, tls } => Some(Self::Tcp {
+                address: *address,
+                tls: tls.clone(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for RemoteCspVaultEndpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnixSocket(path) => write!(f, "unix:{}", path.display()),
+            Self::Vsock { cid, port, .. } => write!(f, "vsock:{cid}:{port}"),
+            Self::Tcp { address, .. } => write!(f, "tcp:{address}"),
+        }
+    }
+}
+
+/// A bidirectional byte stream that can carry the vault's RPC traffic.
+pub(super) trait AsyncReadWrite: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> AsyncReadWrite for T {}
+
+/// A plain (i.e., not yet TLS-protected) vsock or TCP connection.
+pub(super) enum RemoteStream {
+    Tcp(TcpStream),
+    #[cfg(target_os = "linux")]
+    Vsock(AsyncVsockStream),
+}
+
+impl RemoteStream {
+    pub(super) async fn connect_tcp(address: SocketAddr) -> io::Result<Self> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        Ok(Self::Tcp(stream))
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(super) async fn connect_vsock(cid: u32, port: u32) -> io::Result<Self> {
+        Ok(Self::Vsock(AsyncVsockStream::connect(cid, port).await?))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub(super) async fn connect_vsock(_cid: u32, _port: u32) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "vsock is only supported on Linux",
+        ))
+    }
+}
+
+impl AsyncRead for RemoteStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_read(cx, buf),
+            #[cfg(target_os = "linux")]
+            Self::Vsock(stream) => Pin::new(stream).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for RemoteStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_write(cx, buf),
+            #[cfg(target_os = "linux")]
+            Self::Vsock(stream) => Pin::new(stream).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_flush(cx),
+            #[cfg(target_os = "linux")]
+            Self::Vsock(stream) => Pin::new(stream).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Self::Tcp(stream) => Pin::new(stream).poll_shutdown(cx),
+            #[cfg(target_os = "linux")]
+            Self::Vsock(stream) => Pin::new(stream).poll_shutdown(cx),
+        }
+    }
+}
+
+/// The listener a remote CSP vault server accepts client connections on.
+///
+/// Connections accepted over vsock or TCP must complete a mutual TLS
+/// handshake before any RPC is served.
+pub enum CspVaultListener {
+    UnixSocket(UnixListener),
+    Tcp {
+        listener: TcpListener,
+        tls_acceptor: TlsAcceptor,
+    },
+    #[cfg(target_os = "linux")]
+    Vsock {
+        listener: AsyncVsockListener,
+        tls_acceptor: TlsAcceptor,
+    },
+}
+
+impl From<UnixListener> for CspVaultListener {
+    fn from(listener: UnixListener) -> Self {
+        Self::UnixSocket(listener)
+    }
+}
+
+impl CspVaultListener {
+    /// Binds a listener for clients connecting to `endpoint`.
+    ///
+    /// For vsock, the listener accepts connections from any CID on the
+    /// endpoint's port, since the endpoint's CID is the one of the server.
+    pub async fn bind(endpoint: &RemoteCspVaultEndpoint) -> io::Result<Self> {
+        match endpoint {
+            RemoteCspVaultEndpoint::UnixSocket(path) => {
+                Ok(Self::UnixSocket(UnixListener::bind(path)?))
+            }
+            RemoteCspVaultEndpoint::Tcp { address, tls } => {
+                Self::tcp(TcpListener::bind(address).await?, tls)
+            }
+            #[cfg(target_os = "linux")]
+            RemoteCspVaultEndpoint::Vsock { port, tls, .. } => Ok(Self::Vsock {
+                listener: AsyncVsockListener::bind(VMADDR_CID_ANY, *port)?,
+                tls_acceptor: tls::tls_acceptor(tls)?,
+            }),
+            #[cfg(not(target_os = "linux"))]
+            RemoteCspVaultEndpoint::Vsock { .. } => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "vsock is only supported on Linux",
+            )),
+        }
+    }
+
+    /// Wraps an already bound TCP `listener`, requiring clients to
+    /// authenticate according to `tls`.
+    pub fn tcp(listener: TcpListener, tls: &RemoteCspVaultTlsConfig) -> io::Result<Self> {
+        Ok(Self::Tcp {
+            listener,
+            tls_acceptor: tls::tls_acceptor(tls)?,
+        })
+    }
+
+    /// Returns a human-readable description of the local address, for logging.
+    pub fn local_addr(&self) -> String {
+        match self {
+            Self::UnixSocket(listener) => format!("{:?}", listener.local_addr()),
+            Self::Tcp { listener, .. } => format!("{:?}", listener.local_addr()),
+            #[cfg(target_os = "linux")]
+            Self::Vsock { listener, .. } => format!("{:?}", listener.local_addr()),
+        }
+    }
+
+    /// Accepts a new connection, returning it together with a human-readable
+    /// description of the peer. The TLS handshake (if any) is not performed
+    /// here so that a slow client cannot block accepting other clients.
+    pub(super) async fn accept(&self) -> io::Result<(AcceptedConnection, String)> {
+        match self {
+            Self::UnixSocket(listener) => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((AcceptedConnection::UnixSocket(stream), format!("{addr:?}")))
+            }
+            Self::Tcp {
+                listener,
+                tls_acceptor,
+            } => {
+                let (stream, addr) = listener.accept().await?;
+                stream.set_nodelay(true)?;
+                Ok((
+                    AcceptedConnection::Tls(RemoteStream::Tcp(stream), tls_acceptor.clone()),
+                    addr.to_string(),
+                ))
+            }
+            #[cfg(target_os = "linux")]
+            Self::Vsock {
+                listener,
+                tls_acceptor,
+            } => {
+                let (stream, addr) = listener.accept().await?;
+                Ok((
+                    AcceptedConnection::Tls(RemoteStream::Vsock(stream), tls_acceptor.clone()),
+                    format!("{addr:?}"),
+                ))
+            }
+        }
+    }
+}
+
+pub(super) enum AcceptedConnection {
+    UnixSocket(UnixStream),
+    Tls(RemoteStream, TlsAcceptor),
+}
+
+impl AcceptedConnection {
+    /// Completes the TLS handshake (if any) and returns the stream to serve
+    /// RPCs on. Fails with [`io::ErrorKind::TimedOut`] if the handshake does
+    /// not complete within [`TLS_HANDSHAKE_TIMEOUT`], so that clients stalling
+    /// the handshake do not hold on to server resources.
+    pub(super) async fn establish(self) -> io::Result<Box<dyn AsyncReadWrite>> {
+        match self {
+            Self::UnixSocket(stream) => Ok(Box::new(stream)),
+            Self::Tls(stream, tls_acceptor) => {
+                let tls_stream =
+                    tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, tls_acceptor.accept(stream))
+                        .await
+                        .map_err(|_elapsed| {
+                            io::Error::new(
+                                io::ErrorKind::TimedOut,
+                                format!(
+                                    "TLS handshake not completed within {TLS_HANDSHAKE_TIMEOUT:?}"
+                                ),
+                            )
+                        })??;
+                Ok(Box::new(tls_stream))
+            }
+        }
+    }
+}
@@ -35,22 +35,26 @@ use ic_types::{NodeId, NodeIndex, NumberOfNodes, Randomness};
 use serde_bytes::ByteBuf;
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
-use tokio::net::UnixListener;
 
 const FOUR_GIGA_BYTES: usize = 4 * 1024 * 1024 * 1024;
 mod codec;
+mod endpoint;
+mod robust_remote_stream;
 mod robust_unix_socket;
 mod tarpc_csp_vault_client;
 mod tarpc_csp_vault_server;
+mod tls;
 
 use crate::ExternalPublicKeys;
 use crate::key_id::KeyId;
 pub use crate::vault::local_csp_vault::ProdLocalCspVault;
+pub use endpoint::{CspVaultListener, RemoteCspVaultEndpoint};
 use ic_crypto_internal_logmon::metrics::CryptoMetrics;
 use ic_crypto_node_key_validation::ValidNodePublicKeys;
 use std::sync::Arc;
 pub use tarpc_csp_vault_client::{RemoteCspVault, RemoteCspVaultBuilder};
 pub use tarpc_csp_vault_server::{TarpcCspVaultServerImpl, TarpcCspVaultServerImplBuilder};
+pub use tls::REMOTE_CSP_VAULT_TLS_SERVER_NAME;
 use tokio_util::codec::LengthDelimitedCodec;
 use tokio_util::codec::length_delimited::Builder;
 
@@ -252,12 +256,20 @@ pub trait TarpcCspVault {
         input: ByteBuf,
     ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
 
+    // Corresponds to `VetKdCspVault.create_vetkd_bls_share`
+    async fn create_vetkd_bls_share(
+        key_id: KeyId,
+        master_public_key: ByteBuf,
+        context: VetKdDerivationContext,
+        message: Option<ByteBuf>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
+
     async fn new_public_seed() -> Result<Seed, PublicRandomSeedGeneratorError>;
 }
 
 pub async fn run_csp_vault_server(
     sks_dir: &Path,
-    listener: UnixListener,
+    listener: impl Into<CspVaultListener>,
     logger: ReplicaLogger,
     metrics: CryptoMetrics,
 ) {
@@ -0,0 +1,147 @@
+use crate::vault::remote_csp_vault::endpoint::{RemoteStream, TLS_HANDSHAKE_TIMEOUT};
+use crate::vault::remote_csp_vault::tls;
+use ic_logger::{ReplicaLogger, debug, info, new_logger, warn};
+use rustls::pki_types::ServerName;
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::io::Error;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use stubborn_io::ReconnectOptions;
+use stubborn_io::strategies::ExpBackoffStrategy;
+use stubborn_io::tokio::{StubbornIo, UnderlyingIo};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio_rustls::TlsConnector;
+use tokio_rustls::client::TlsStream;
+
+/// The vsock or TCP address of a remote CSP vault server.
+#[derive(Copy, Clone, Debug)]
+pub enum RemoteAddress {
+    Vsock { cid: u32, port: u32 },
+    Tcp(SocketAddr),
+}
+
+impl fmt::Display for RemoteAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Vsock { cid, port } => write!(f, "vsock:{cid}:{port}"),
+            Self::Tcp(address) => write!(f, "tcp:{address}"),
+        }
+    }
+}
+
+/// Everything needed to (re-)establish a TLS connection to a remote CSP vault.
+#[derive(Clone)]
+pub struct RemoteTarget {
+    address: RemoteAddress,
+    tls_connector: TlsConnector,
+    server_name: ServerName<'static>,
+    logger: ReplicaLogger,
+}
+
+pub struct RobustTlsStream(TlsStream<RemoteStream>);
+
+impl AsyncRead for RobustTlsStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for RobustTlsStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, Error>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+impl UnderlyingIo<RemoteTarget> for RobustTlsStream {
+    fn establish(target: RemoteTarget) -> Pin<Box<dyn Future<Output = io::Result<Self>> + Send>> {
+        Box::pin(async move {
+            debug!(
+                target.logger,
+                "Trying to (re-)connect to {}", target.address
+            );
+            let stream = match target.address {
+                RemoteAddress::Vsock { cid, port } => {
+                    RemoteStream::connect_vsock(cid, port).await?
+                }
+                RemoteAddress::Tcp(address) => RemoteStream::connect_tcp(address).await?,
+            };
+            let stream = tokio::time::timeout(
+                TLS_HANDSHAKE_TIMEOUT,
+                target.tls_connector.connect(target.server_name, stream),
+            )
+            .await
+            .map_err(|_elapsed| {
+                io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("TLS handshake not completed within {TLS_HANDSHAKE_TIMEOUT:?}"),
+                )
+            })??;
+            Ok(RobustTlsStream(stream))
+        })
+    }
+}
+
+pub type RobustRemoteSocket = StubbornIo<RobustTlsStream, RemoteTarget>;
+
+/// Connects to a remote CSP vault at `address` over mutual TLS. Like
+/// `robust_unix_socket::connect`, the connection is transparently
+/// re-established with exponential backoff if it drops.
+pub async fn connect(
+    address: RemoteAddress,
+    tls_connector: TlsConnector,
+    logger: ReplicaLogger,
+) -> io::Result<RobustRemoteSocket> {
+    const MINIMUM_DELAY: Duration = Duration::from_millis(100);
+    const MAXIMUM_DELAY: Duration = Duration::from_secs(5);
+    const EXPONENTIAL_BACKOFF_FACTOR: f64 = 2.0;
+    const JITTER_AMOUNT: f64 = 0.05;
+    let options = ReconnectOptions::new()
+        .with_on_disconnect_callback({
+            let logger = new_logger!(logger);
+            move || {
+                warn!(
+                    logger,
+                    "Detected disconnection from {}. Attempting to reconnect...", address
+                );
+            }
+        })
+        .with_on_connect_callback({
+            let logger = new_logger!(logger);
+            move || debug!(logger, "Successfully (re-)connected to {}", address)
+        })
+        .with_on_connect_fail_callback({
+            let logger = new_logger!(logger);
+            move || info!(logger, "Failed to reconnect to {}", address)
+        })
+        .with_retries_generator(|| {
+            ExpBackoffStrategy::new(MINIMUM_DELAY, EXPONENTIAL_BACKOFF_FACTOR, JITTER_AMOUNT)
+                .with_max(MAXIMUM_DELAY)
+        });
+    let target = RemoteTarget {
+        address,
+        tls_connector,
+        server_name: tls::server_name(),
+        logger,
+    };
+    RobustRemoteSocket::connect_with_options(target, options).await
+}
@@ -15,11 +15,14 @@ use crate::vault::api::{
 };
 use crate::vault::remote_csp_vault::ThresholdSchnorrCreateSigShareVaultError;
 use crate::vault::remote_csp_vault::codec::{Bincode, CspVaultObserver, ObservableCodec};
+use crate::vault::remote_csp_vault::robust_remote_stream::RemoteAddress;
 use crate::vault::remote_csp_vault::{
-    FOUR_GIGA_BYTES, TarpcCspVaultClient, remote_vault_codec_builder, robust_unix_socket,
+    FOUR_GIGA_BYTES, RemoteCspVaultEndpoint, TarpcCspVaultClient, remote_vault_codec_builder,
+    robust_remote_stream, robust_unix_socket, tls,
 };
 use crate::{ExternalPublicKeys, TlsHandshakeCspVault};
 use core::future::Future;
+use ic_config::crypto::RemoteCspVaultTlsConfig;
 use ic_crypto_internal_seed::Seed;
 use ic_crypto_internal_threshold_sig_bls12381::api::dkg_errors::InternalError;
 use ic_crypto_internal_threshold_sig_bls12381::api::ni_dkg_errors::{
@@ -55,6 +58,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 use tarpc::serde_transport;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tracing::instrument;
 
 #[cfg(test)]
@@ -124,10 +128,19 @@ impl RemoteCspVault {
     ) -> RemoteCspVaultBuilder {
         RemoteCspVaultBuilder::new(socket_path, rt_handle)
     }
+
+    /// Returns a builder for a `RemoteCspVault` that communicates with a
+    /// server at the given `endpoint`, which may be on another host or VM.
+    pub fn builder_for_endpoint(
+        endpoint: RemoteCspVaultEndpoint,
+        rt_handle: tokio::runtime::Handle,
+    ) -> RemoteCspVaultBuilder {
+        RemoteCspVaultBuilder::new_with_endpoint(endpoint, rt_handle)
+    }
 }
 
 pub struct RemoteCspVaultBuilder {
-    socket_path: PathBuf,
+    endpoint: RemoteCspVaultEndpoint,
     rt_handle: tokio::runtime::Handle,
     max_frame_length: usize,
     rpc_timeout: Duration,
@@ -140,8 +153,15 @@ pub struct RemoteCspVaultBuilder {
 
 impl RemoteCspVaultBuilder {
     pub fn new(socket_path: PathBuf, rt_handle: tokio::runtime::Handle) -> Self {
+        Self::new_with_endpoint(RemoteCspVaultEndpoint::UnixSocket(socket_path), rt_handle)
+    }
+
+    pub fn new_with_endpoint(
+        endpoint: RemoteCspVaultEndpoint,
+        rt_handle: tokio::runtime::Handle,
+    ) -> Self {
         RemoteCspVaultBuilder {
-            socket_path,
+            endpoint,
             rt_handle,
             max_frame_length: FOUR_GIGA_BYTES,
             rpc_timeout: DEFAULT_RPC_TIMEOUT,
@@ -194,30 +214,37 @@ impl RemoteCspVaultBuilder {
     }
 
     pub fn build(self) -> Result<RemoteCspVault, RemoteCspVaultError> {
-        let conn = self
-            .rt_handle
-            .block_on(robust_unix_socket::connect(
-                self.socket_path.clone(),
-                new_logger!(&self.logger),
-            ))
-            .map_err(|e| RemoteCspVaultError::TransportError {
-                server_address: self.socket_path.to_string_lossy().to_string(),
-                message: e.to_string(),
-            })?;
-        let transport = serde_transport::new(
-            remote_vault_codec_builder()
-                .max_frame_length(self.max_frame_length)
-                .new_framed(conn),
-            ObservableCodec::new(
-                Bincode::default(),
-                CspVaultObserver::new(new_logger!(&self.logger), Arc::clone(&self.metrics)),
-            ),
-        );
-        let client = {
-            let _enter_guard = self.rt_handle.enter();
-            TarpcCspVaultClient::new(Default::default(), transport).spawn()
+        let transport_error = |e: std::io::Error| RemoteCspVaultError::TransportError {
+            server_address: self.endpoint.to_string(),
+            message: e.to_string(),
+        };
+        let client = match &self.endpoint {
+            RemoteCspVaultEndpoint::UnixSocket(socket_path) => {
+                let conn = self
+                    .rt_handle
+                    .block_on(robust_unix_socket::connect(
+                        socket_path.clone(),
+                        new_logger!(&self.logger),
+                    ))
+                    .map_err(transport_error)?;
+                self.spawn_client(conn)
+            }
+            RemoteCspVaultEndpoint::Vsock { cid, port, tls } => {
+                let address = RemoteAddress::Vsock {
+                    cid: *cid,
+                    port: *port,
+                };
+                self.spawn_client(self.connect_remote(address, tls).map_err(transport_error)?)
+            }
+            RemoteCspVaultEndpoint::Tcp { address, tls } => {
+                let address = RemoteAddress::Tcp(*address);
+                self.spawn_client(self.connect_remote(address, tls).map_err(transport_error)?)
+            }
         };
-        debug!(self.logger, "Instantiated remote CSP vault client");
+        debug!(
+            self.logger,
+            "Instantiated remote CSP vault client for {}", self.endpoint
+        );
         Ok(RemoteCspVault {
             tarpc_csp_client: client,
             rpc_timeout: self.rpc_timeout,
@@ -233,6 +260,36 @@ impl RemoteCspVaultBuilder {
     pub fn build_expecting_ok(self) -> RemoteCspVault {
         self.build().expect("error building RemoteCspVault")
     }
+
+    fn connect_remote(
+        &self,
+        address: RemoteAddress,
+        tls_config: &RemoteCspVaultTlsConfig,
+    ) -> std::io::Result<robust_remote_stream::RobustRemoteSocket> {
+        let tls_connector = tls::tls_connector(tls_config)?;
+        self.rt_handle.block_on(robust_remote_stream::connect(
+            address,
+            tls_connector,
+            new_logger!(&self.logger),
+        ))
+    }
+
+    fn spawn_client<S>(&self, conn: S) -> TarpcCspVaultClient
+    where
+        S: AsyncRead + AsyncWrite + Send + 'static,
+    {
+        let transport = serde_transport::new(
+            remote_vault_codec_builder()
+                .max_frame_length(self.max_frame_length)
+                .new_framed(conn),
+            ObservableCodec::new(
+                Bincode::default(),
+                CspVaultObserver::new(new_logger!(&self.logger), Arc::clone(&self.metrics)),
+            ),
+        );
+        let _enter_guard = self.rt_handle.enter();
+        TarpcCspVaultClient::new(Default::default(), transport).spawn()
+    }
 }
 
 fn deadline_from_now(timeout: Duration) -> SystemTime {
@@ -841,6 +898,31 @@ impl VetKdCspVault for RemoteCspVault {
             )
         })
     }
+
+    #[instrument(skip_all)]
+    #[inline]
+    fn create_vetkd_bls_share(
+        &self,
+        key_id: KeyId,
+        master_public_key: Vec<u8>,
+        context: VetKdDerivationContext,
+        message: Option<Vec<u8>>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        self.tokio_block_on(self.tarpc_csp_client.create_vetkd_bls_share(
+            context_with_timeout(self.rpc_timeout),
+            key_id,
+            ByteBuf::from(master_public_key),
+            context,
+            message.map(ByteBuf::from),
+        ))
+        .unwrap_or_else(|rpc_error: tarpc::client::RpcError| {
+            Err(
+                VetKdEncryptedKeyShareCreationVaultError::TransientInternalError(
+                    rpc_error.to_string(),
+                ),
+            )
+        })
+    }
 }
 
 impl PublicRandomSeedGenerator for RemoteCspVault {
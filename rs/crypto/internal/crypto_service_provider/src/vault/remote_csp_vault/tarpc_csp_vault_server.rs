@@ -14,8 +14,8 @@ use crate::vault::api::{
 };
 use crate::vault::local_csp_vault::{LocalCspVault, ProdLocalCspVault};
 use crate::vault::remote_csp_vault::ThresholdSchnorrCreateSigShareVaultError;
+use crate::vault::remote_csp_vault::{CspVaultListener, TarpcCspVault, remote_vault_codec_builder};
 use crate::vault::remote_csp_vault::{FOUR_GIGA_BYTES, PksAndSksContainsErrors};
-use crate::vault::remote_csp_vault::{TarpcCspVault, remote_vault_codec_builder};
 use futures::StreamExt;
 use ic_crypto_internal_logmon::metrics::CryptoMetrics;
 use ic_crypto_internal_seed::Seed;
@@ -52,18 +52,22 @@ use serde_bytes::ByteBuf;
 use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tarpc::server::BaseChannel;
 #[allow(unused_imports)]
 use tarpc::server::Serve;
 use tarpc::{context, serde_transport, server::Channel};
-use tokio::net::UnixListener;
 
 use super::codec::{Bincode, CspVaultObserver, ObservableCodec};
 
+/// The time to wait before accepting further connections after accepting a
+/// connection failed.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
 /// Crypto service provider (CSP) vault server based on the tarpc RPC framework.
 pub struct TarpcCspVaultServerImpl<C: CspVault> {
     local_csp_vault: Arc<C>,
-    listener: UnixListener,
+    listener: CspVaultListener,
     thread_pool: Arc<ThreadPool>,
     max_frame_length: usize,
     metrics: Arc<CryptoMetrics>,
@@ -551,6 +555,26 @@ impl<C: CspVault + 'static> TarpcCspVault for TarpcCspVaultServerWorker<C> {
         execute_on_thread_pool(&self.thread_pool, job).await
     }
 
+    async fn create_vetkd_bls_share(
+        self,
+        _: context::Context,
+        key_id: KeyId,
+        master_public_key: ByteBuf,
+        context: VetKdDerivationContext,
+        message: Option<ByteBuf>,
+    ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError> {
+        let vault = self.local_csp_vault;
+        let job = move || {
+            vault.create_vetkd_bls_share(
+                key_id,
+                master_public_key.into_vec(),
+                context,
+                message.map(ByteBuf::into_vec),
+            )
+        };
+        execute_on_thread_pool(&self.thread_pool, job).await
+    }
+
     async fn new_public_seed(
         self,
         _: context::Context,
@@ -619,8 +643,13 @@ impl<C> TarpcCspVaultServerImplBuilder<C> {
 }
 
 impl<C: CspVault> TarpcCspVaultServerImplBuilder<C> {
-    pub fn build(&self, listener: UnixListener) -> TarpcCspVaultServerImpl<C> {
-        info!(&self.logger, "Starting new RPC CSP vault server");
+    pub fn build(&self, listener: impl Into<CspVaultListener>) -> TarpcCspVaultServerImpl<C> {
+        let listener = listener.into();
+        info!(
+            &self.logger,
+            "Starting new RPC CSP vault server listening at {}",
+            listener.local_addr()
+        );
         let local_csp_vault: Arc<C> =
             (self.local_csp_vault_factory)(&self.logger, Arc::clone(&self.metrics));
         TarpcCspVaultServerImpl {
@@ -669,20 +698,44 @@ impl<C: CspVault + 'static> TarpcCspVaultServerImpl<C> {
 
         // Listen for connections; spawns one `tokio` task per client.
         loop {
-            let (conn, _addr) = self.listener.accept().await.unwrap_or_else(|e| {
-                panic!(
-                    "Error listening at socket {:?}: {}",
-                    &self.listener.local_addr(),
-                    e
-                )
-            });
+            let (conn, peer) = match self.listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    // Errors such as running out of file descriptors or a peer
+                    // resetting the connection before it is accepted must not
+                    // take down the vault. Back off briefly to avoid spinning
+                    // on persistent errors.
+                    warn!(
+                        self.logger,
+                        "Error accepting connection at socket {}: {}",
+                        self.listener.local_addr(),
+                        e
+                    );
+                    tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                    continue;
+                }
+            };
             let local_csp_vault = Arc::clone(&self.local_csp_vault);
             let thread_pool = Arc::clone(&self.thread_pool);
             let codec = ObservableCodec::new(
                 Bincode::default(),
                 CspVaultObserver::new(new_logger!(&self.logger), Arc::clone(&self.metrics)),
             );
+            let logger = new_logger!(&self.logger);
             tokio::spawn(async move {
+                // Connections over vsock or TCP first complete the mutual TLS
+                // handshake. A client failing it is dropped without affecting
+                // the other clients.
+                let conn = match conn.establish().await {
+                    Ok(conn) => conn,
+                    Err(e) => {
+                        warn!(
+                            logger,
+                            "Failed to establish connection with CSP vault client {}: {}", peer, e
+                        );
+                        return;
+                    }
+                };
                 let framed = codec_builder.new_framed(conn);
                 let transport = serde_transport::new(framed, codec);
                 let worker = TarpcCspVaultServerWorker {
@@ -0,0 +1,110 @@
+//! Mutual TLS between a remote CSP vault client and server.
+//!
+//! Used for transports that, unlike Unix domain sockets, are not protected by
+//! file system permissions (i.e., vsock and TCP). Both sides present a
+//! certificate and only accept peers whose certificate chains up to the
+//! configured CA certificate.
+use ic_config::crypto::RemoteCspVaultTlsConfig;
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{ClientConfig, RootCertStore, ServerConfig};
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+/// The DNS name that the certificate of a remote CSP vault server must be
+/// valid for.
+///
+/// The vault is not addressed by a DNS name (in particular not over vsock),
+/// so the client always verifies the server certificate against this fixed
+/// name instead of the host it connects to.
+pub const REMOTE_CSP_VAULT_TLS_SERVER_NAME: &str = "ic-crypto-csp-vault";
+
+pub(super) fn tls_connector(config: &RemoteCspVaultTlsConfig) -> io::Result<TlsConnector> {
+    let provider = crypto_provider();
+    let server_verifier = WebPkiServerVerifier::builder_with_provider(
+        Arc::new(load_root_cert_store(&config.ca_certificate)?),
+        Arc::clone(&provider),
+    )
+    .build()
+    .map_err(io::Error::other)?;
+    let client_config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_webpki_verifier(server_verifier)
+        .with_client_auth_cert(
+            load_certificates(&config.certificate)?,
+            load_private_key(&config.private_key)?,
+        )
+        .map_err(io::Error::other)?;
+    Ok(TlsConnector::from(Arc::new(client_config)))
+}
+
+pub(super) fn tls_acceptor(config: &RemoteCspVaultTlsConfig) -> io::Result<TlsAcceptor> {
+    let provider = crypto_provider();
+    let client_verifier = WebPkiClientVerifier::builder_with_provider(
+        Arc::new(load_root_cert_store(&config.ca_certificate)?),
+        Arc::clone(&provider),
+    )
+    .build()
+    .map_err(io::Error::other)?;
+    let server_config = ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_client_cert_verifier(client_verifier)
+        .with_single_cert(
+            load_certificates(&config.certificate)?,
+            load_private_key(&config.private_key)?,
+        )
+        .map_err(io::Error::other)?;
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
+
+pub(super) fn server_name() -> ServerName<'static> {
+    ServerName::try_from(REMOTE_CSP_VAULT_TLS_SERVER_NAME)
+        .expect("the remote CSP vault server name must be a valid DNS name")
+}
+
+fn crypto_provider() -> Arc<CryptoProvider> {
+    Arc::new(rustls::crypto::ring::default_provider())
+}
+
+fn load_root_cert_store(path: &Path) -> io::Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for certificate in load_certificates(path)? {
+        roots.add(certificate).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid CA certificate in {}: {e}", path.display()),
+            )
+        })?;
+    }
+    Ok(roots)
+}
+
+fn load_certificates(path: &Path) -> io::Result<Vec<CertificateDer<'static>>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let certificates = rustls_pemfile::certs(&mut reader).collect::<Result<Vec<_>, _>>()?;
+    if certificates.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no certificate found in {}", path.display()),
+        ));
+    }
+    Ok(certificates)
+}
+
+fn load_private_key(path: &Path) -> io::Result<PrivateKeyDer<'static>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    rustls_pemfile::private_key(&mut reader)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no private key found in {}", path.display()),
+        )
+    })
+}
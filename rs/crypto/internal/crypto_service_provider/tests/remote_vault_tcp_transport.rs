@@ -0,0 +1,95 @@
+use assert_matches::assert_matches;
+use ic_crypto_internal_csp::key_id::KeyId;
+use ic_crypto_internal_csp::vault::api::{BasicSignatureCspVault, CspBasicSignatureError};
+use ic_crypto_internal_csp::vault::remote_csp_vault::{RemoteCspVault, RemoteCspVaultEndpoint};
+use ic_crypto_temp_crypto_vault::{
+    TempCspVaultServerProcess, TempTlsCredentials, run_temp_csp_vault_server_process_if_requested,
+};
+use ic_types::crypto::AlgorithmId;
+use std::time::Duration;
+
+const SERVER_TEST_NAME: &str = "vault_server_process";
+
+/// Not a test: the body of the vault server process spawned by the tests below.
+#[test]
+#[ignore]
+fn vault_server_process() {
+    run_temp_csp_vault_server_process_if_requested();
+}
+
+#[test]
+fn should_sign_with_vault_in_separate_process_over_tcp() {
+    let credentials = TempTlsCredentials::generate();
+    let server = TempCspVaultServerProcess::start(SERVER_TEST_NAME, credentials.server.clone());
+    let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+    let client = vault_client(server.endpoint(credentials.client.clone()), &rt)
+        .expect("failed to connect to vault server");
+
+    let public_key = client
+        .gen_node_signing_key_pair()
+        .expect("failed generating node signing key pair");
+    let signature = client.sign(
+        AlgorithmId::Ed25519,
+        b"message".to_vec(),
+        KeyId::from(&public_key),
+    );
+
+    assert_matches!(signature, Ok(_));
+}
+
+#[test]
+fn should_reconnect_after_vault_server_process_restarted() {
+    let credentials = TempTlsCredentials::generate();
+    let mut server = TempCspVaultServerProcess::start(SERVER_TEST_NAME, credentials.server.clone());
+    let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+    let client = vault_client(server.endpoint(credentials.client.clone()), &rt)
+        .expect("failed to connect to vault server");
+    let key_id = KeyId::from(
+        &client
+            .gen_node_signing_key_pair()
+            .expect("failed generating node signing key pair"),
+    );
+    let sign = || client.sign(AlgorithmId::Ed25519, b"message".to_vec(), key_id);
+    let signature_before_restart = sign().expect("failed to sign");
+
+    server.restart();
+
+    // The first request after the restart may fail while the client detects
+    // the disconnection, after which the connection is re-established.
+    let signature_after_restart = sign()
+        .or_else(|_| sign())
+        .expect("failed to sign after restart");
+    assert_eq!(signature_before_restart, signature_after_restart);
+}
+
+#[test]
+fn should_fail_with_client_certificate_from_untrusted_ca() {
+    let credentials = TempTlsCredentials::generate();
+    let untrusted_credentials = TempTlsCredentials::generate();
+    let server = TempCspVaultServerProcess::start(SERVER_TEST_NAME, credentials.server.clone());
+    let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+    let mut client_tls = untrusted_credentials.client.clone();
+    client_tls.ca_certificate = credentials.client.ca_certificate.clone();
+
+    // Depending on when the server aborts the TLS handshake, the client fails
+    // either to connect or to send its first request.
+    if let Ok(client) = vault_client(server.endpoint(client_tls), &rt) {
+        assert_matches!(
+            client.sign(
+                AlgorithmId::Ed25519,
+                b"message".to_vec(),
+                KeyId::from([0; 32])
+            ),
+            Err(CspBasicSignatureError::TransientInternalError { .. })
+        );
+    }
+}
+
+fn vault_client(
+    endpoint: RemoteCspVaultEndpoint,
+    rt: &tokio::runtime::Runtime,
+) -> Result<RemoteCspVault, impl std::fmt::Debug> {
+    RemoteCspVault::builder_for_endpoint(endpoint, rt.handle().clone())
+        .with_rpc_timeouts(Duration::from_secs(10))
+        .build()
+}
@@ -0,0 +1,63 @@
+#![cfg(target_os = "linux")]
+use ic_crypto_internal_csp::key_id::KeyId;
+use ic_crypto_internal_csp::vault::api::BasicSignatureCspVault;
+use ic_crypto_internal_csp::vault::remote_csp_vault::{
+    CspVaultListener, RemoteCspVault, RemoteCspVaultEndpoint, TarpcCspVaultServerImpl,
+};
+use ic_crypto_temp_crypto_vault::TempTlsCredentials;
+use ic_types::crypto::AlgorithmId;
+use rand::Rng;
+use std::time::Duration;
+use vsock_lib::stream::AsyncVsockStream;
+
+/// The CID addressing the local host, see `vsock(7)`.
+const VMADDR_CID_LOCAL: u32 = 1;
+
+/// Runs a vault server listening on vsock in-process and connects to it over
+/// the vsock loopback transport.
+#[test]
+#[ignore] // The test requires vsock loopback support (the `vsock_loopback` kernel module), which is not available on CI.
+fn should_sign_with_vault_over_vsock() {
+    let credentials = TempTlsCredentials::generate();
+    let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+    let port = rand::thread_rng().gen_range(10_000..60_000);
+    let server_endpoint = RemoteCspVaultEndpoint::Vsock {
+        cid: VMADDR_CID_LOCAL,
+        port,
+        tls: credentials.server.clone(),
+    };
+
+    let listener = rt
+        .block_on(CspVaultListener::bind(&server_endpoint))
+        .expect("failed to listen on vsock");
+    // The probe connection is accepted by the server once it runs, and is
+    // dropped when it fails the TLS handshake, without affecting other clients.
+    rt.block_on(AsyncVsockStream::connect(VMADDR_CID_LOCAL, port))
+        .expect("vsock loopback is not available");
+    let sks_dir = tempfile::tempdir().expect("failed to create temporary directory");
+    let server = TarpcCspVaultServerImpl::builder(sks_dir.path()).build(listener);
+    rt.spawn(server.run());
+
+    let client = RemoteCspVault::builder_for_endpoint(
+        RemoteCspVaultEndpoint::Vsock {
+            cid: VMADDR_CID_LOCAL,
+            port,
+            tls: credentials.client.clone(),
+        },
+        rt.handle().clone(),
+    )
+    .with_rpc_timeouts(Duration::from_secs(10))
+    .build()
+    .expect("failed to connect to vault server");
+
+    let public_key = client
+        .gen_node_signing_key_pair()
+        .expect("failed generating node signing key pair");
+    client
+        .sign(
+            AlgorithmId::Ed25519,
+            b"message".to_vec(),
+            KeyId::from(&public_key),
+        )
+        .expect("failed to sign");
+}
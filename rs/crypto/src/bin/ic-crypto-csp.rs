@@ -1,6 +1,8 @@
 use clap::Parser;
 use ic_adapter_metrics_server::start_metrics_grpc;
+use ic_config::crypto::CspVaultType;
 use ic_config::{Config, ConfigSource};
+use ic_crypto_internal_csp::vault::remote_csp_vault::{CspVaultListener, RemoteCspVaultEndpoint};
 use ic_crypto_internal_logmon::metrics::CryptoMetrics;
 use ic_http_endpoints_async_utils::incoming_from_nth_systemd_socket;
 use ic_logger::{info, new_replica_logger_from_config};
@@ -18,7 +20,9 @@ const IC_CRYPTO_CSP_SOCKET_FILENAME: &str = "ic-crypto-csp.socket";
     version = "0.1",
     author = "Internet Computer Developers",
     about = "NOTE: This binary is intended to be started as socket-activated \
-               systemd service with a single socket named ic-crypto-csp.socket"
+               systemd service with sockets named ic-crypto-csp.socket. If the \
+               replica configuration specifies a vsock or TCP vault, the server \
+               listens there instead and only the metrics socket is provided by systemd."
 )]
 struct Opts {
     /// Sets the replica configuration file
@@ -38,8 +42,25 @@ fn main() {
 
     let sks_dir = ic_config.crypto.crypto_root.as_path();
 
-    ensure_n_named_systemd_sockets(2);
-    let systemd_socket_listener = listener_from_first_systemd_socket(rt.handle().clone());
+    // For vault types reachable over vsock or TCP the server binds the listener
+    // itself, and systemd only provides the metrics socket.
+    let (listener, metrics_socket_num): (CspVaultListener, i32) =
+        match &ic_config.crypto.csp_vault_type {
+            vault_type @ (CspVaultType::Vsock { .. } | CspVaultType::Tcp { .. }) => {
+                ensure_n_named_systemd_sockets(1);
+                let endpoint = RemoteCspVaultEndpoint::from_vault_type(vault_type)
+                    .expect("vsock and TCP vaults are remote");
+                let listener = rt
+                    .block_on(CspVaultListener::bind(&endpoint))
+                    .unwrap_or_else(|e| panic!("Failed to listen at {endpoint}: {e}"));
+                (listener, 1)
+            }
+            CspVaultType::InReplica | CspVaultType::UnixSocket { .. } => {
+                ensure_n_named_systemd_sockets(2);
+                let listener = listener_from_first_systemd_socket(rt.handle().clone());
+                (listener.into(), 2)
+            }
+        };
 
     // The `AsyncGuard` must be kept in scope for asynchronously logged messages to appear in the logs.
     let (logger, _async_log_guard) = new_replica_logger_from_config(&ic_config.csp_vault_logger);
@@ -47,8 +68,8 @@ fn main() {
     info!(logger;
         crypto.method_name => "main",
         crypto.description => format!(
-            "Starting CspVault server listening at systemd socket '{:?}', with SKS-data in '{}' ...",
-            systemd_socket_listener.local_addr().expect("failed to get local socket address"),
+            "Starting CspVault server listening at '{}', with SKS-data in '{}' ...",
+            listener.local_addr(),
             sks_dir.display()
         )
     );
@@ -65,6 +86,8 @@ fn main() {
     //  - 2: stderr,
     //  - 3: the logic communication socket (handled by `listener_from_first_systemd_socket`)
     //  - 4: the metrics socket (handled by `incoming_from_nth_systemd_socket`)
+    // If the logic socket is not provided by systemd (vsock or TCP vaults), the
+    // metrics socket is FD(3) instead.
     // The file descriptors from 3 onwards correspond to the order the sockets are described in
     // the systemd configuration file.
     // `incoming_from_nth_systemd_socket` starts from FD(3) if the passed `socket_num` is 1, and
@@ -73,9 +96,8 @@ fn main() {
     // Systemd Socket config: ic-crypto-csp.socket
     // Systemd Service config: ic-crypto-csp.service
     {
-        const METRICS_SOCKET_NUM: i32 = 2;
         let _enter_guard = rt.handle().enter();
-        let stream = unsafe { incoming_from_nth_systemd_socket(METRICS_SOCKET_NUM) };
+        let stream = unsafe { incoming_from_nth_systemd_socket(metrics_socket_num) };
         start_metrics_grpc(global_metrics, logger.clone(), stream);
     }
 
@@ -83,10 +105,7 @@ fn main() {
     cleanup_obsolete_canister_sks_file_if_it_exists(sks_dir, &logger, &metrics);
 
     rt.block_on(ic_crypto_internal_csp::run_csp_vault_server(
-        sks_dir,
-        systemd_socket_listener,
-        logger,
-        metrics,
+        sks_dir, listener, logger, metrics,
     ));
 }
 
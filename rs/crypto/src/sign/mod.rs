@@ -36,8 +36,8 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
 
 pub(crate) use basic_sig::{BasicSigVerifierInternal, BasicSignerInternal};
-pub(crate) use threshold_sig::lazily_calculated_public_key_from_store;
 pub use threshold_sig::{ThresholdSigDataStore, ThresholdSigDataStoreImpl};
+pub(crate) use threshold_sig::{TranscriptData, lazily_calculated_public_key_from_store};
 
 mod basic_sig;
 mod canister_threshold_sig;
@@ -1,7 +1,7 @@
 use super::*;
 pub use crate::sign::threshold_sig::store::ThresholdSigDataStore;
 pub use crate::sign::threshold_sig::store::ThresholdSigDataStoreImpl;
-use crate::sign::threshold_sig::store::TranscriptData;
+pub(crate) use crate::sign::threshold_sig::store::TranscriptData;
 use ic_crypto_internal_csp::api::{CspThresholdSignError, ThresholdSignatureCspClient};
 use ic_crypto_internal_csp::types::CspPublicCoefficients;
 use ic_crypto_internal_types::sign::threshold_sig::public_key::CspThresholdSigPublicKey;
@@ -2,12 +2,14 @@ use super::get_log_id;
 use crate::sign::BasicSigVerifierInternal;
 use crate::sign::BasicSignerInternal;
 use crate::sign::ThresholdSigDataStore;
+use crate::sign::TranscriptData;
 use crate::sign::lazily_calculated_public_key_from_store;
 use crate::{CryptoComponentImpl, LockableThresholdSigDataStore};
 use ic_crypto_internal_bls12_381_vetkd::{
-    DerivationContext, EncryptedKeyCombinationError, EncryptedKeyShare,
-    EncryptedKeyShareDeserializationError, G2Affine, NodeIndex, PairingInvalidPoint,
-    TransportPublicKey, TransportPublicKeyDeserializationError,
+    BlsDeserializationError, BlsSignature, BlsSignatureShare, DerivationContext,
+    EncryptedKeyCombinationError, EncryptedKeyShare, EncryptedKeyShareDeserializationError,
+    G2Affine, NodeIndex, PairingInvalidPoint, TransportPublicKey,
+    TransportPublicKeyDeserializationError,
 };
 use ic_crypto_internal_csp::api::CspSigner;
 use ic_crypto_internal_csp::api::ThresholdSignatureCspClient;
@@ -27,6 +29,7 @@ use ic_types::crypto::threshold_sig::ni_dkg::NiDkgId;
 use ic_types::crypto::vetkd::{
     VetKdArgs, VetKdEncryptedKey, VetKdEncryptedKeyShare, VetKdKeyShareCombinationError,
     VetKdKeyShareCreationError, VetKdKeyShareVerificationError, VetKdKeyVerificationError,
+    VetKdOutput,
 };
 use ic_types::crypto::{BasicSig, BasicSigOf};
 use std::collections::BTreeMap;
@@ -234,15 +237,28 @@ fn create_encrypted_key_share_internal<S: CspSigner>(
             })?,
     };
 
-    let encrypted_key_share = vault
-        .create_encrypted_vetkd_key_share(
+    let encrypted_key_share = match args.output {
+        VetKdOutput::EncryptedKey => vault.create_encrypted_vetkd_key_share(
             key_id,
             master_public_key.as_bytes().to_vec(),
             args.transport_public_key,
             args.context,
             args.input,
-        )
-        .map_err(vetkd_key_share_creation_error_from_vault_error)?;
+        ),
+        VetKdOutput::BlsPublicKey => vault.create_vetkd_bls_share(
+            key_id,
+            master_public_key.as_bytes().to_vec(),
+            args.context,
+            None,
+        ),
+        VetKdOutput::BlsSignature => vault.create_vetkd_bls_share(
+            key_id,
+            master_public_key.as_bytes().to_vec(),
+            args.context,
+            Some(args.input),
+        ),
+    }
+    .map_err(vetkd_key_share_creation_error_from_vault_error)?;
 
     let signature = BasicSignerInternal::sign_basic(
         csp_signer,
@@ -350,6 +366,18 @@ fn combine_encrypted_key_shares_internal<C: ThresholdSignatureCspClient>(
                 VetKdKeyShareCombinationError::InvalidArgumentMasterPublicKey
             }
         })?;
+    if args.output != VetKdOutput::EncryptedKey {
+        return combine_bls_shares(
+            lockable_threshold_sig_data_store,
+            threshold_sig_csp_client,
+            logger,
+            &transcript_data_from_store,
+            reconstruction_threshold,
+            &master_public_key,
+            shares,
+            args,
+        );
+    }
     let transport_public_key = TransportPublicKey::deserialize(&args.transport_public_key)
         .map_err(|e| match e {
             TransportPublicKeyDeserializationError::InvalidPublicKey => {
@@ -391,40 +419,32 @@ fn combine_encrypted_key_shares_internal<C: ThresholdSignatureCspClient>(
         &args.input,
     ) {
         Ok(encrypted_key) => Ok(encrypted_key),
-        Err(EncryptedKeyCombinationError::InsufficientShares) => {
-            Err(VetKdKeyShareCombinationError::UnsatisfiedReconstructionThreshold {
+        Err(EncryptedKeyCombinationError::InsufficientShares) => Err(
+            VetKdKeyShareCombinationError::UnsatisfiedReconstructionThreshold {
                 threshold: reconstruction_threshold,
-                share_count: clib_shares_for_combine_all.len()
-            })
-        }
+                share_count: clib_shares_for_combine_all.len(),
+            },
+        ),
         Err(EncryptedKeyCombinationError::InvalidShares) => {
-            info!(logger, "EncryptedKey::combine_all failed with InvalidShares, \
+            info!(
+                logger,
+                "EncryptedKey::combine_all failed with InvalidShares, \
                 falling back to EncryptedKey::combine_valid_shares"
             );
 
-            let clib_shares_for_combine_valid: BTreeMap<NodeIndex, (G2Affine, EncryptedKeyShare)> = clib_shares
-                .into_iter()
-                .map(|(node_id, node_index, clib_share)| {
-                    let node_public_key = lazily_calculated_public_key_from_store(
-                        lockable_threshold_sig_data_store,
-                        threshold_sig_csp_client,
-                        &args.ni_dkg_id,
-                        node_id,
-                    )
-                    .map_err(|e| {
-                        VetKdKeyShareCombinationError::IndividualPublicKeyComputationError(e)
-                    })?;
-                    let node_public_key_g2affine = match node_public_key {
-                        CspThresholdSigPublicKey::ThresBls12_381(public_key_bytes) => {
-                            G2Affine::deserialize(&public_key_bytes.0)
-                            .map_err(|_: PairingInvalidPoint| VetKdKeyShareCombinationError::InternalError(
-                                format!("individual public key of node with ID {node_id} in threshold sig data store")
-                            ))
-                        }
-                    }?;
-                    Ok((node_index, (node_public_key_g2affine, clib_share.clone())))
-                })
-                .collect::<Result<_, _>>()?;
+            let clib_shares_for_combine_valid: BTreeMap<NodeIndex, (G2Affine, EncryptedKeyShare)> =
+                clib_shares
+                    .into_iter()
+                    .map(|(node_id, node_index, clib_share)| {
+                        let node_public_key_g2affine = individual_public_key_g2affine(
+                            lockable_threshold_sig_data_store,
+                            threshold_sig_csp_client,
+                            &args.ni_dkg_id,
+                            node_id,
+                        )?;
+                        Ok((node_index, (node_public_key_g2affine, clib_share.clone())))
+                    })
+                    .collect::<Result<_, _>>()?;
 
             ic_crypto_internal_bls12_381_vetkd::EncryptedKey::combine_valid_shares(
                 &clib_shares_for_combine_valid,
@@ -439,55 +459,162 @@ fn combine_encrypted_key_shares_internal<C: ThresholdSignatureCspClient>(
                     "failed to combine the valid encrypted vetKD key shares: {e:?}"
                 ))
             })
-        },
-        Err(other_error) => {
-            Err(VetKdKeyShareCombinationError::CombinationError(format!(
-                "failed to combine the valid encrypted vetKD key shares: {other_error:?}"
-            )))
         }
+        Err(other_error) => Err(VetKdKeyShareCombinationError::CombinationError(format!(
+            "failed to combine the valid encrypted vetKD key shares: {other_error:?}"
+        ))),
     }
     .map(|encrypted_key| VetKdEncryptedKey {
         encrypted_key: encrypted_key.serialize().to_vec(),
     })
 }
 
+/// Combines shares of a BLS public key, and of a signature if
+/// `args.output` is [`VetKdOutput::BlsSignature`], analogously to
+/// combining encrypted key shares.
+#[allow(clippy::too_many_arguments)]
+fn combine_bls_shares<C: ThresholdSignatureCspClient>(
+    lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
+    threshold_sig_csp_client: &C,
+    logger: &ReplicaLogger,
+    transcript_data_from_store: &TranscriptData,
+    reconstruction_threshold: usize,
+    master_public_key: &G2Affine,
+    shares: &BTreeMap<NodeId, VetKdEncryptedKeyShare>,
+    args: &VetKdArgs,
+) -> Result<VetKdEncryptedKey, VetKdKeyShareCombinationError> {
+    let clib_shares: Vec<(NodeId, NodeIndex, BlsSignatureShare)> = shares
+        .iter()
+        .map(|(&node_id, share)| {
+            let node_index = transcript_data_from_store.index(node_id).ok_or(
+                VetKdKeyShareCombinationError::InternalError(format!(
+                    "missing index for node with ID {node_id} in threshold \
+                        sig data store for NI-DKG ID {}",
+                    args.ni_dkg_id
+                )),
+            )?;
+            let clib_share = BlsSignatureShare::deserialize(&share.encrypted_key_share.0).map_err(
+                |e| match e {
+                    BlsDeserializationError::InvalidEncoding => {
+                        VetKdKeyShareCombinationError::InvalidArgumentEncryptedKeyShare
+                    }
+                },
+            )?;
+            Ok((node_id, *node_index, clib_share))
+        })
+        .collect::<Result<_, _>>()?;
+    let clib_shares_for_combine_all: BTreeMap<NodeIndex, BlsSignatureShare> = clib_shares
+        .iter()
+        .map(|(_node_id, node_index, clib_share)| (*node_index, clib_share.clone()))
+        .collect();
+    let context = DerivationContext::new(args.context.caller.as_slice(), &args.context.context);
+    let message = bls_message(args);
+
+    match BlsSignature::combine_all(
+        &clib_shares_for_combine_all,
+        reconstruction_threshold,
+        master_public_key,
+        &context,
+        message,
+    ) {
+        Ok(signature) => Ok(signature),
+        Err(EncryptedKeyCombinationError::InsufficientShares) => Err(
+            VetKdKeyShareCombinationError::UnsatisfiedReconstructionThreshold {
+                threshold: reconstruction_threshold,
+                share_count: clib_shares_for_combine_all.len(),
+            },
+        ),
+        Err(EncryptedKeyCombinationError::InvalidShares) => {
+            info!(
+                logger,
+                "BlsSignature::combine_all failed with InvalidShares, \
+                falling back to BlsSignature::combine_valid_shares"
+            );
+
+            let clib_shares_for_combine_valid: BTreeMap<NodeIndex, (G2Affine, BlsSignatureShare)> =
+                clib_shares
+                    .into_iter()
+                    .map(|(node_id, node_index, clib_share)| {
+                        let node_public_key = individual_public_key_g2affine(
+                            lockable_threshold_sig_data_store,
+                            threshold_sig_csp_client,
+                            &args.ni_dkg_id,
+                            node_id,
+                        )?;
+                        Ok((node_index, (node_public_key, clib_share)))
+                    })
+                    .collect::<Result<_, _>>()?;
+
+            BlsSignature::combine_valid_shares(
+                &clib_shares_for_combine_valid,
+                reconstruction_threshold,
+                master_public_key,
+                &context,
+                message,
+            )
+            .map_err(|e| {
+                VetKdKeyShareCombinationError::CombinationError(format!(
+                    "failed to combine the valid BLS shares: {e:?}"
+                ))
+            })
+        }
+        Err(other_error) => Err(VetKdKeyShareCombinationError::CombinationError(format!(
+            "failed to combine the valid BLS shares: {other_error:?}"
+        ))),
+    }
+    .map(|signature| VetKdEncryptedKey {
+        encrypted_key: signature.serialize(),
+    })
+}
+
+fn individual_public_key_g2affine<C: ThresholdSignatureCspClient>(
+    lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
+    threshold_sig_csp_client: &C,
+    ni_dkg_id: &NiDkgId,
+    node_id: NodeId,
+) -> Result<G2Affine, VetKdKeyShareCombinationError> {
+    let node_public_key = lazily_calculated_public_key_from_store(
+        lockable_threshold_sig_data_store,
+        threshold_sig_csp_client,
+        ni_dkg_id,
+        node_id,
+    )
+    .map_err(VetKdKeyShareCombinationError::IndividualPublicKeyComputationError)?;
+    match node_public_key {
+        CspThresholdSigPublicKey::ThresBls12_381(public_key_bytes) => {
+            G2Affine::deserialize(&public_key_bytes.0).map_err(|_: PairingInvalidPoint| {
+                VetKdKeyShareCombinationError::InternalError(format!(
+                    "individual public key of node with ID {node_id} in threshold sig data store"
+                ))
+            })
+        }
+    }
+}
+
+/// Returns the message to sign if `args` request a BLS signature.
+fn bls_message(args: &VetKdArgs) -> Option<&[u8]> {
+    match args.output {
+        VetKdOutput::BlsSignature => Some(&args.input),
+        VetKdOutput::EncryptedKey | VetKdOutput::BlsPublicKey => None,
+    }
+}
+
 fn verify_encrypted_key_internal(
     lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
     key: &VetKdEncryptedKey,
     args: &VetKdArgs,
 ) -> Result<(), VetKdKeyVerificationError> {
+    if args.output != VetKdOutput::EncryptedKey {
+        return verify_bls_internal(lockable_threshold_sig_data_store, key, args);
+    }
     let encrypted_key =
         ic_crypto_internal_bls12_381_vetkd::EncryptedKey::deserialize(&key.encrypted_key)
         .map_err(|e| match e {
             ic_crypto_internal_bls12_381_vetkd::EncryptedKeyDeserializationError::InvalidEncryptedKey => VetKdKeyVerificationError::InvalidArgumentEncryptedKey,
         })?;
 
-    let master_public_key = {
-        let pub_coeffs_from_store = lockable_threshold_sig_data_store
-            .read()
-            .transcript_data(&args.ni_dkg_id)
-            .map(|data| data.public_coefficients().clone())
-            .ok_or_else(|| {
-                VetKdKeyVerificationError::ThresholdSigDataNotFound(
-                    ThresholdSigDataNotFoundError::ThresholdSigDataNotFound {
-                        dkg_id: args.ni_dkg_id.clone(),
-                    },
-                )
-            })?;
-        match pub_coeffs_from_store {
-            PublicCoefficients::Bls12_381(bls_coeffs_trusted) => {
-                master_pubkey_from_coeffs(&bls_coeffs_trusted.coefficients, &args.ni_dkg_id)
-                    .map_err(|error| match error {
-                        MasterPubkeyFromCoeffsError::InternalError(msg) => {
-                            VetKdKeyVerificationError::InternalError(msg)
-                        }
-                        MasterPubkeyFromCoeffsError::InvalidArgumentMasterPublicKey => {
-                            VetKdKeyVerificationError::InvalidArgumentMasterPublicKey
-                        }
-                    })?
-            }
-        }
-    };
+    let master_public_key =
+        verification_master_public_key(lockable_threshold_sig_data_store, args)?;
 
     let transport_public_key = TransportPublicKey::deserialize(&args.transport_public_key)
         .map_err(|e| match e {
@@ -507,6 +634,61 @@ fn verify_encrypted_key_internal(
     }
 }
 
+fn verify_bls_internal(
+    lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
+    key: &VetKdEncryptedKey,
+    args: &VetKdArgs,
+) -> Result<(), VetKdKeyVerificationError> {
+    let signature = BlsSignature::deserialize(&key.encrypted_key).map_err(|e| match e {
+        BlsDeserializationError::InvalidEncoding => {
+            VetKdKeyVerificationError::InvalidArgumentEncryptedKey
+        }
+    })?;
+
+    let master_public_key =
+        verification_master_public_key(lockable_threshold_sig_data_store, args)?;
+
+    match signature.is_valid(
+        &master_public_key,
+        &DerivationContext::new(args.context.caller.as_slice(), &args.context.context),
+        bls_message(args),
+    ) {
+        true => Ok(()),
+        false => Err(VetKdKeyVerificationError::VerificationError),
+    }
+}
+
+fn verification_master_public_key(
+    lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
+    args: &VetKdArgs,
+) -> Result<G2Affine, VetKdKeyVerificationError> {
+    let pub_coeffs_from_store = lockable_threshold_sig_data_store
+        .read()
+        .transcript_data(&args.ni_dkg_id)
+        .map(|data| data.public_coefficients().clone())
+        .ok_or_else(|| {
+            VetKdKeyVerificationError::ThresholdSigDataNotFound(
+                ThresholdSigDataNotFoundError::ThresholdSigDataNotFound {
+                    dkg_id: args.ni_dkg_id.clone(),
+                },
+            )
+        })?;
+    match pub_coeffs_from_store {
+        PublicCoefficients::Bls12_381(bls_coeffs_trusted) => {
+            master_pubkey_from_coeffs(&bls_coeffs_trusted.coefficients, &args.ni_dkg_id).map_err(
+                |error| match error {
+                    MasterPubkeyFromCoeffsError::InternalError(msg) => {
+                        VetKdKeyVerificationError::InternalError(msg)
+                    }
+                    MasterPubkeyFromCoeffsError::InvalidArgumentMasterPublicKey => {
+                        VetKdKeyVerificationError::InvalidArgumentMasterPublicKey
+                    }
+                },
+            )
+        }
+    }
+}
+
 fn ensure_sufficient_shares_to_fail_fast(
     shares: &BTreeMap<NodeId, VetKdEncryptedKeyShare>,
     lockable_threshold_sig_data_store: &LockableThresholdSigDataStore,
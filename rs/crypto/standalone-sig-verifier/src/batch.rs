@@ -0,0 +1,137 @@
+//! Batch verification of signatures
+use crate::verify_basic_sig_by_public_key;
+use ic_crypto_internal_threshold_sig_bls12381 as bls12_381;
+use ic_crypto_internal_types::sign::threshold_sig::public_key::bls12_381::PublicKeyBytes as BlsPublicKeyBytes;
+use ic_types::crypto::{AlgorithmId, CryptoError, CryptoResult};
+use rand::{CryptoRng, Rng};
+
+/// A signature to be verified as part of a batch.
+///
+/// The `public_key` and `signature` are encoded as expected by
+/// [`verify_basic_sig_by_public_key`] for basic signature algorithms. For
+/// [`AlgorithmId::ThresBls12_381`], they are a raw (96-byte) combined public
+/// key and a raw (48-byte) combined threshold signature.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BatchVerificationItem<'a> {
+    pub algorithm_id: AlgorithmId,
+    pub public_key: &'a [u8],
+    pub message: &'a [u8],
+    pub signature: &'a [u8],
+}
+
+/// Verifies a batch of signatures of possibly different algorithms.
+///
+/// Ed25519 signatures are verified with randomized batch verification, and
+/// BLS12-381 combined threshold signatures with aggregated pairings. If a
+/// batch is rejected, its signatures are verified individually to identify
+/// the invalid ones. Signatures of any other algorithm are verified
+/// individually with [`verify_basic_sig_by_public_key`].
+///
+/// Returns one result per item, in the order of `items`. The result for an
+/// item is the same as if it had been verified on its own.
+pub fn verify_sigs_batch_by_public_key<R: Rng + CryptoRng>(
+    items: &[BatchVerificationItem<'_>],
+    rng: &mut R,
+) -> Vec<CryptoResult<()>> {
+    let mut results: Vec<Option<CryptoResult<()>>> = vec![None; items.len()];
+
+    let ed25519_indices = indices_of(items, AlgorithmId::Ed25519);
+    if ed25519_indices.len() > 1 && verify_ed25519_batch(items, &ed25519_indices, rng) {
+        for &i in &ed25519_indices {
+            results[i] = Some(Ok(()));
+        }
+    }
+
+    let bls_indices = indices_of(items, AlgorithmId::ThresBls12_381);
+    if bls_indices.len() > 1 && verify_bls12_381_batch(items, &bls_indices, rng) {
+        for &i in &bls_indices {
+            results[i] = Some(Ok(()));
+        }
+    }
+
+    results
+        .into_iter()
+        .zip(items)
+        .map(|(result, item)| result.unwrap_or_else(|| verify_individually(item)))
+        .collect()
+}
+
+fn indices_of(items: &[BatchVerificationItem<'_>], algorithm_id: AlgorithmId) -> Vec<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| item.algorithm_id == algorithm_id)
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns true iff all Ed25519 signatures at `indices` are valid.
+fn verify_ed25519_batch<R: Rng + CryptoRng>(
+    items: &[BatchVerificationItem<'_>],
+    indices: &[usize],
+    rng: &mut R,
+) -> bool {
+    let Ok(keys) = indices
+        .iter()
+        .map(|&i| ic_ed25519::PublicKey::deserialize_raw(items[i].public_key))
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return false;
+    };
+    let messages: Vec<&[u8]> = indices.iter().map(|&i| items[i].message).collect();
+    let signatures: Vec<&[u8]> = indices.iter().map(|&i| items[i].signature).collect();
+    ic_ed25519::PublicKey::batch_verify(&messages, &signatures, &keys, rng).is_ok()
+}
+
+/// Returns true iff all BLS12-381 combined signatures at `indices` are valid.
+fn verify_bls12_381_batch<R: Rng + CryptoRng>(
+    items: &[BatchVerificationItem<'_>],
+    indices: &[usize],
+    rng: &mut R,
+) -> bool {
+    let Ok(parsed) = indices
+        .iter()
+        .map(|&i| parse_bls12_381(&items[i]).map(|(sig, pk)| (items[i].message, sig, pk)))
+        .collect::<CryptoResult<Vec<_>>>()
+    else {
+        return false;
+    };
+    let batch: Vec<_> = parsed
+        .iter()
+        .map(|(message, sig, pk)| (*message, sig, pk))
+        .collect();
+    bls12_381::api::verify_combined_signature_batch(&batch, rng).is_ok()
+}
+
+fn parse_bls12_381(
+    item: &BatchVerificationItem<'_>,
+) -> CryptoResult<(bls12_381::types::CombinedSignatureBytes, BlsPublicKeyBytes)> {
+    let signature = bls12_381::types::CombinedSignatureBytes::try_from(&item.signature.to_vec())?;
+    let public_key = <[u8; BlsPublicKeyBytes::SIZE]>::try_from(item.public_key).map_err(|_| {
+        CryptoError::MalformedPublicKey {
+            algorithm: AlgorithmId::ThresBls12_381,
+            key_bytes: Some(item.public_key.to_vec()),
+            internal_error: format!(
+                "Incorrect length. Expected {} bytes but found {} bytes",
+                BlsPublicKeyBytes::SIZE,
+                item.public_key.len()
+            ),
+        }
+    })?;
+    Ok((signature, BlsPublicKeyBytes(public_key)))
+}
+
+fn verify_individually(item: &BatchVerificationItem<'_>) -> CryptoResult<()> {
+    match item.algorithm_id {
+        AlgorithmId::ThresBls12_381 => {
+            let (signature, public_key) = parse_bls12_381(item)?;
+            bls12_381::api::verify_combined_signature(item.message, signature, public_key)
+        }
+        algorithm_id => verify_basic_sig_by_public_key(
+            algorithm_id,
+            item.message,
+            item.signature,
+            item.public_key,
+        ),
+    }
+}
@@ -1,7 +1,9 @@
 use ic_types::crypto::{AlgorithmId, CryptoError, CryptoResult, threshold_sig::IcRootOfTrust};
 
+mod batch;
 mod sign_utils;
 
+pub use batch::{BatchVerificationItem, verify_sigs_batch_by_public_key};
 pub use sign_utils::{
     KeyBytesContentType, ecdsa_p256_signature_from_der_bytes, ed25519_public_key_to_der,
     rsa_signature_from_bytes, user_public_key_from_bytes,
@@ -56,6 +58,15 @@ pub fn verify_basic_sig_by_public_key(
             // RSA hashes the message using SHA-256
             public_key.verify_pkcs1_sha256(msg, &signature_bytes)
         }
+        AlgorithmId::MlDsa65 => {
+            use ic_crypto_internal_basic_sig_ml_dsa as ml_dsa;
+
+            let public_key = ml_dsa::types::PublicKeyBytes(public_key_bytes);
+            let signature = ml_dsa::types::SignatureBytes(signature_bytes);
+
+            // ML-DSA signs the message itself, so no pre-hashing is applied.
+            ml_dsa::verify(&signature, msg, &public_key)
+        }
         algorithm => Err(CryptoError::AlgorithmNotSupported {
             algorithm,
             reason: "Not supported for basic signature verification".to_string(),
@@ -5,6 +5,7 @@ use ic_crypto_internal_basic_sig_ecdsa_secp256k1 as ecdsa_secp256k1;
 use ic_crypto_internal_basic_sig_ecdsa_secp256r1 as ecdsa_secp256r1;
 use ic_crypto_internal_basic_sig_ed25519 as ed25519;
 use ic_crypto_internal_basic_sig_iccsa as iccsa;
+use ic_crypto_internal_basic_sig_ml_dsa as ml_dsa;
 use ic_crypto_internal_basic_sig_rsa_pkcs1 as rsa;
 use ic_types::crypto::{AlgorithmId, BasicSig, CryptoError, CryptoResult, UserPublicKey};
 
@@ -18,6 +19,7 @@ pub enum KeyBytesContentType {
     EcdsaP256PublicKeyDerWrappedCose,
     RsaSha256PublicKeyDerWrappedCose,
     IcCanisterSignatureAlgPublicKeyDer,
+    MlDsa65PublicKeyDer,
 }
 
 fn cose_key_bytes_content_type(alg_id: AlgorithmId) -> Option<KeyBytesContentType> {
@@ -61,6 +63,12 @@ pub fn user_public_key_from_bytes(
             AlgorithmId::EcdsaP256,
             KeyBytesContentType::EcdsaP256PublicKeyDer,
         )
+    } else if pkix_algo_id == ml_dsa::algorithm_identifier() {
+        (
+            ml_dsa::public_key_from_der(bytes)?.0,
+            AlgorithmId::MlDsa65,
+            KeyBytesContentType::MlDsa65PublicKeyDer,
+        )
     } else if pkix_algo_id == cose::algorithm_identifier() {
         let (alg_id, bytes) = cose::parse_cose_public_key(&pk_bytes)?;
         let key_bytes = user_public_key_from_bytes(&bytes)?;
@@ -1,4 +1,5 @@
 use ic_crypto_internal_basic_sig_der_utils::subject_public_key_info_der;
+use ic_crypto_internal_basic_sig_ml_dsa as ml_dsa;
 use ic_crypto_internal_test_vectors::test_data;
 use ic_crypto_standalone_sig_verifier::{
     KeyBytesContentType, ecdsa_p256_signature_from_der_bytes, user_public_key_from_bytes,
@@ -59,6 +60,19 @@ fn should_correctly_parse_der_encoded_firefox_ecdsa_p256_pk() {
     assert_eq!(bytes_type, KeyBytesContentType::EcdsaP256PublicKeyDer);
 }
 
+#[test]
+fn should_correctly_parse_der_encoded_ml_dsa_65_pk() {
+    let rng = &mut ic_crypto_test_utils_reproducible_rng::reproducible_rng();
+    let (_sk, raw_pk) = ml_dsa::keypair_from_rng(rng);
+    let pk_der = ml_dsa::public_key_to_der(&raw_pk).unwrap();
+
+    let (pk, bytes_type) = user_public_key_from_bytes(&pk_der).unwrap();
+
+    assert_eq!(pk.algorithm_id, AlgorithmId::MlDsa65);
+    assert_eq!(pk.key, raw_pk.0);
+    assert_eq!(bytes_type, KeyBytesContentType::MlDsa65PublicKeyDer);
+}
+
 #[test]
 fn should_correctly_parse_der_encoded_ecdsa_secp256k1_pk() {
     let pk_der = hex::decode(test_data::ECDSA_SECP256K1_PK_DER_HEX).unwrap();
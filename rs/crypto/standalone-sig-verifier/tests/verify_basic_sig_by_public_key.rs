@@ -3,11 +3,12 @@ use ic_crypto_standalone_sig_verifier::verify_basic_sig_by_public_key;
 use ic_types::crypto::{AlgorithmId, CryptoError, CryptoResult};
 use strum::IntoEnumIterator;
 
-const SUPPORTED_ALGORITHM_IDS: [AlgorithmId; 4] = [
+const SUPPORTED_ALGORITHM_IDS: [AlgorithmId; 5] = [
     AlgorithmId::Ed25519,
     AlgorithmId::EcdsaP256,
     AlgorithmId::EcdsaSecp256k1,
     AlgorithmId::RsaSha256,
+    AlgorithmId::MlDsa65,
 ];
 
 #[test]
@@ -172,6 +173,52 @@ mod rsa_sha_256 {
     }
 }
 
+mod ml_dsa_65 {
+    use crate::assert_wrong_algorithm_used;
+    use assert_matches::assert_matches;
+    use ic_crypto_internal_basic_sig_ml_dsa as ml_dsa;
+    use ic_crypto_standalone_sig_verifier::verify_basic_sig_by_public_key;
+    use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
+    use ic_types::crypto::{AlgorithmId, CryptoError};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn should_accept_valid_signature_smoke_test() {
+        let (msg, sig, pk) = test_vector();
+        let result = verify_basic_sig_by_public_key(AlgorithmId::MlDsa65, &msg, &sig, &pk);
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn should_reject_invalid_signature_smoke_test() {
+        let (msg, mut sig, pk) = test_vector();
+        sig.as_mut_slice()[0] ^= 0x01;
+        let result = verify_basic_sig_by_public_key(AlgorithmId::MlDsa65, &msg, &sig, &pk);
+        assert_matches!(
+            result,
+            Err(CryptoError::SignatureVerification { .. })
+                | Err(CryptoError::MalformedSignature { .. })
+        );
+    }
+
+    #[test]
+    fn should_error_on_every_other_algorithm_ids() {
+        for wrong_algorithm_id in AlgorithmId::iter().filter(|id| *id != AlgorithmId::MlDsa65) {
+            let (msg, sig, pk) = test_vector();
+            let result = verify_basic_sig_by_public_key(wrong_algorithm_id, &msg, &sig, &pk);
+            assert_wrong_algorithm_used(result, wrong_algorithm_id);
+        }
+    }
+
+    fn test_vector() -> (Vec<u8>, Vec<u8>, Vec<u8>) {
+        let rng = &mut reproducible_rng();
+        let (sk, pk) = ml_dsa::keypair_from_rng(rng);
+        let msg = b"some message".to_vec();
+        let sig = ml_dsa::sign(&msg, &sk).expect("failed to sign");
+        (msg, sig.0, pk.0)
+    }
+}
+
 fn assert_wrong_algorithm_used(result: CryptoResult<()>, wrong_algorithm_id: AlgorithmId) {
     assert_matches!(
         result,
@@ -0,0 +1,141 @@
+use assert_matches::assert_matches;
+use ic_crypto_internal_seed::Seed;
+use ic_crypto_internal_threshold_sig_bls12381::api as bls12_381;
+use ic_crypto_standalone_sig_verifier::{
+    BatchVerificationItem, verify_basic_sig_by_public_key, verify_sigs_batch_by_public_key,
+};
+use ic_crypto_test_utils_reproducible_rng::{ReproducibleRng, reproducible_rng};
+use ic_types::NumberOfNodes;
+use ic_types::crypto::{AlgorithmId, CryptoError};
+
+struct TestSig {
+    algorithm_id: AlgorithmId,
+    public_key: Vec<u8>,
+    message: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+impl TestSig {
+    fn item(&self) -> BatchVerificationItem<'_> {
+        BatchVerificationItem {
+            algorithm_id: self.algorithm_id,
+            public_key: &self.public_key,
+            message: &self.message,
+            signature: &self.signature,
+        }
+    }
+}
+
+fn ed25519_sig(rng: &mut ReproducibleRng, message: &[u8]) -> TestSig {
+    let sk = ic_ed25519::PrivateKey::generate_using_rng(rng);
+    TestSig {
+        algorithm_id: AlgorithmId::Ed25519,
+        public_key: sk.public_key().serialize_raw().to_vec(),
+        message: message.to_vec(),
+        signature: sk.sign_message(message).to_vec(),
+    }
+}
+
+fn ecdsa_p256_sig(rng: &mut ReproducibleRng, message: &[u8]) -> TestSig {
+    let sk = ic_secp256r1::PrivateKey::generate_using_rng(rng);
+    TestSig {
+        algorithm_id: AlgorithmId::EcdsaP256,
+        public_key: sk.public_key().serialize_sec1(false),
+        message: message.to_vec(),
+        signature: sk.sign_message(message).to_vec(),
+    }
+}
+
+fn bls12_381_sig(rng: &mut ReproducibleRng, message: &[u8]) -> TestSig {
+    let threshold = NumberOfNodes::from(1);
+    let (public_coefficients, secret_keys) =
+        bls12_381::generate_threshold_key(Seed::from_rng(rng), threshold, threshold)
+            .expect("failed to generate threshold key");
+    let signature = bls12_381::sign_message(message, &secret_keys[0]).expect("failed to sign");
+    let combined = bls12_381::combine_signatures(&[Some(signature)], threshold)
+        .expect("failed to combine signatures");
+    let public_key = bls12_381::combined_public_key(&public_coefficients)
+        .expect("failed to compute combined public key");
+    TestSig {
+        algorithm_id: AlgorithmId::ThresBls12_381,
+        public_key: public_key.0.to_vec(),
+        message: message.to_vec(),
+        signature: combined.0.to_vec(),
+    }
+}
+
+fn mixed_sigs(rng: &mut ReproducibleRng) -> Vec<TestSig> {
+    let mut sigs = Vec::new();
+    for i in 0..5_u8 {
+        let message = [b'm', i];
+        sigs.push(ed25519_sig(rng, &message));
+        sigs.push(bls12_381_sig(rng, &message));
+        sigs.push(ecdsa_p256_sig(rng, &message));
+    }
+    sigs
+}
+
+#[test]
+fn should_accept_batch_of_valid_signatures_of_mixed_algorithms() {
+    let rng = &mut reproducible_rng();
+    let sigs = mixed_sigs(rng);
+    let items: Vec<_> = sigs.iter().map(TestSig::item).collect();
+
+    let results = verify_sigs_batch_by_public_key(&items, rng);
+
+    assert_eq!(results.len(), items.len());
+    assert!(results.iter().all(|result| result.is_ok()), "{results:?}");
+}
+
+#[test]
+fn should_accept_empty_batch() {
+    let rng = &mut reproducible_rng();
+
+    assert_eq!(verify_sigs_batch_by_public_key(&[], rng), vec![]);
+}
+
+#[test]
+fn should_identify_invalid_signatures_in_batch() {
+    let rng = &mut reproducible_rng();
+    let mut sigs = mixed_sigs(rng);
+    let invalid_indices = [0, 4, 8];
+    for i in invalid_indices {
+        sigs[i].message.push(0);
+    }
+    let items: Vec<_> = sigs.iter().map(TestSig::item).collect();
+
+    let results = verify_sigs_batch_by_public_key(&items, rng);
+
+    for (i, result) in results.iter().enumerate() {
+        if invalid_indices.contains(&i) {
+            assert_matches!(result, Err(CryptoError::SignatureVerification { .. }));
+        } else {
+            assert_eq!(result, &Ok(()), "item {i} should be valid");
+        }
+    }
+}
+
+#[test]
+fn should_return_same_results_as_individual_verification_for_malformed_items() {
+    let rng = &mut reproducible_rng();
+    let mut sigs = mixed_sigs(rng);
+    sigs[0].public_key.pop();
+    sigs[1].signature.pop();
+    sigs.push(TestSig {
+        algorithm_id: AlgorithmId::Placeholder,
+        public_key: vec![],
+        message: vec![],
+        signature: vec![],
+    });
+    let items: Vec<_> = sigs.iter().map(TestSig::item).collect();
+
+    let results = verify_sigs_batch_by_public_key(&items, rng);
+
+    assert_matches!(results[0], Err(CryptoError::MalformedPublicKey { .. }));
+    assert_matches!(results[1], Err(CryptoError::MalformedSignature { .. }));
+    assert_eq!(
+        results[items.len() - 1],
+        verify_basic_sig_by_public_key(AlgorithmId::Placeholder, &[], &[], &[])
+    );
+    assert!(results[2..items.len() - 1].iter().all(|r| r.is_ok()));
+}
@@ -8,6 +8,11 @@ use std::sync::Arc;
 use tempfile::TempDir;
 use tokio::net::UnixListener;
 
+mod process;
+pub use process::{
+    TempCspVaultServerProcess, TempTlsCredentials, run_temp_csp_vault_server_process_if_requested,
+};
+
 pub struct RemoteVaultEnvironment<C> {
     pub vault_server: TempCspVaultServer<C>,
     pub vault_client_runtime: TokioRuntimeOrHandle,
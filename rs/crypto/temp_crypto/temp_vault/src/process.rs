@@ -0,0 +1,236 @@
+//! A CSP vault server running in a separate process and reachable over TCP
+//! with mutual TLS, mimicking a vault deployed in an isolated VM.
+//!
+//! The server process is the test executable itself: the test spawning the
+//! server re-executes the current binary, asking the test harness to run only
+//! a dedicated (ignored) test that calls
+//! [`run_temp_csp_vault_server_process_if_requested`].
+use ic_config::crypto::{CryptoConfig, RemoteCspVaultTlsConfig};
+use ic_crypto_internal_csp::vault::remote_csp_vault::{
+    CspVaultListener, REMOTE_CSP_VAULT_TLS_SERVER_NAME, RemoteCspVaultEndpoint,
+    TarpcCspVaultServerImpl,
+};
+use rcgen::{
+    BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+    KeyPair, KeyUsagePurpose,
+};
+use std::io::{BufRead, BufReader};
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use tempfile::TempDir;
+
+const CRYPTO_ROOT_ENV_VAR: &str = "IC_CRYPTO_TEMP_VAULT_SERVER_CRYPTO_ROOT";
+const ADDRESS_ENV_VAR: &str = "IC_CRYPTO_TEMP_VAULT_SERVER_ADDRESS";
+const CERTIFICATE_ENV_VAR: &str = "IC_CRYPTO_TEMP_VAULT_SERVER_CERTIFICATE";
+const PRIVATE_KEY_ENV_VAR: &str = "IC_CRYPTO_TEMP_VAULT_SERVER_PRIVATE_KEY";
+const CA_CERTIFICATE_ENV_VAR: &str = "IC_CRYPTO_TEMP_VAULT_SERVER_CA_CERTIFICATE";
+const LISTENING_AT_PREFIX: &str = "ic-crypto-temp-vault-server listening at ";
+
+/// Certificates and keys for a vault server and a vault client, issued by a
+/// common temporary CA. The files are deleted when the struct goes out of scope.
+pub struct TempTlsCredentials {
+    _temp_dir: TempDir,
+    pub server: RemoteCspVaultTlsConfig,
+    pub client: RemoteCspVaultTlsConfig,
+}
+
+impl TempTlsCredentials {
+    pub fn generate() -> Self {
+        let temp_dir = tempfile::Builder::new()
+            .prefix("ic_crypto_vault_tls_")
+            .tempdir()
+            .expect("failed to create temporary directory");
+
+        let ca_key = KeyPair::generate().expect("failed to generate CA key");
+        let ca_cert = {
+            let mut params = CertificateParams::new(Vec::<String>::new())
+                .expect("failed to create CA certificate parameters");
+            params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            params.key_usages = vec![KeyUsagePurpose::KeyCertSign];
+            params
+                .distinguished_name
+                .push(DnType::CommonName, "ic-crypto-temp-vault-ca");
+            params
+                .self_signed(&ca_key)
+                .expect("failed to self-sign CA certificate")
+        };
+        let ca_certificate = write_file(temp_dir.path(), "ca.pem", &ca_cert.pem());
+
+        let server = issue(
+            temp_dir.path(),
+            "server",
+            ExtendedKeyUsagePurpose::ServerAuth,
+            (&ca_cert, &ca_key),
+            &ca_certificate,
+        );
+        let client = issue(
+            temp_dir.path(),
+            "client",
+            ExtendedKeyUsagePurpose::ClientAuth,
+            (&ca_cert, &ca_key),
+            &ca_certificate,
+        );
+
+        Self {
+            _temp_dir: temp_dir,
+            server,
+            client,
+        }
+    }
+}
+
+fn issue(
+    dir: &Path,
+    name: &str,
+    usage: ExtendedKeyUsagePurpose,
+    (ca_cert, ca_key): (&Certificate, &KeyPair),
+    ca_certificate: &Path,
+) -> RemoteCspVaultTlsConfig {
+    let key = KeyPair::generate().expect("failed to generate key");
+    let mut params = CertificateParams::new(vec![REMOTE_CSP_VAULT_TLS_SERVER_NAME.to_string()])
+        .expect("failed to create certificate parameters");
+    params.extended_key_usages = vec![usage];
+    let cert = params
+        .signed_by(&key, ca_cert, ca_key)
+        .expect("failed to sign certificate");
+    RemoteCspVaultTlsConfig {
+        certificate: write_file(dir, &format!("{name}.pem"), &cert.pem()),
+        private_key: write_file(dir, &format!("{name}_key.pem"), &key.serialize_pem()),
+        ca_certificate: ca_certificate.to_path_buf(),
+    }
+}
+
+fn write_file(dir: &Path, name: &str, contents: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, contents)
+        .unwrap_or_else(|e| panic!("failed to write {}: {e}", path.display()));
+    path
+}
+
+/// A CSP vault server running in a child process, listening on a local TCP
+/// port. The process is killed when the struct goes out of scope.
+pub struct TempCspVaultServerProcess {
+    server_test_name: String,
+    tls: RemoteCspVaultTlsConfig,
+    crypto_root: TempDir,
+    address: SocketAddr,
+    child: Option<Child>,
+}
+
+impl TempCspVaultServerProcess {
+    /// Starts a vault server in a child process by running the test named
+    /// `server_test_name` of the current test executable. That test must call
+    /// [`run_temp_csp_vault_server_process_if_requested`].
+    pub fn start(server_test_name: &str, tls: RemoteCspVaultTlsConfig) -> Self {
+        let (config, crypto_root) = CryptoConfig::new_in_temp_dir();
+        assert_eq!(config.crypto_root, crypto_root.path());
+        let mut server = Self {
+            server_test_name: server_test_name.to_string(),
+            tls,
+            crypto_root,
+            address: SocketAddr::from(([127, 0, 0, 1], 0)),
+            child: None,
+        };
+        server.spawn();
+        server
+    }
+
+    /// Returns the endpoint for a client authenticating with `client_tls`.
+    pub fn endpoint(&self, client_tls: RemoteCspVaultTlsConfig) -> RemoteCspVaultEndpoint {
+        RemoteCspVaultEndpoint::Tcp {
+            address: self.address,
+            tls: client_tls,
+        }
+    }
+
+    /// Kills the server process. The vault's key stores are kept.
+    pub fn kill(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ignore_if_already_exited = child.kill();
+            let _ = child.wait();
+        }
+    }
+
+    /// Kills the server process (if running) and starts a new one on the same
+    /// address with the same key stores.
+    pub fn restart(&mut self) {
+        self.kill();
+        self.spawn();
+    }
+
+    fn spawn(&mut self) {
+        let mut child = Command::new(std::env::current_exe().expect("no current executable"))
+            .args([
+                self.server_test_name.as_str(),
+                "--exact",
+                "--ignored",
+                "--nocapture",
+                "--test-threads=1",
+            ])
+            .env(CRYPTO_ROOT_ENV_VAR, self.crypto_root.path())
+            .env(ADDRESS_ENV_VAR, self.address.to_string())
+            .env(CERTIFICATE_ENV_VAR, &self.tls.certificate)
+            .env(PRIVATE_KEY_ENV_VAR, &self.tls.private_key)
+            .env(CA_CERTIFICATE_ENV_VAR, &self.tls.ca_certificate)
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to spawn vault server process");
+
+        let mut stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let mut line = String::new();
+        self.address = loop {
+            line.clear();
+            let bytes_read = stdout
+                .read_line(&mut line)
+                .expect("failed to read from vault server process");
+            assert_ne!(
+                bytes_read, 0,
+                "vault server process exited before listening"
+            );
+            if let Some(address) = line.trim().strip_prefix(LISTENING_AT_PREFIX) {
+                break address.parse().expect("invalid address");
+            }
+        };
+        // Keep draining the child's output so that it never blocks on a full pipe.
+        std::thread::spawn(move || for _line in stdout.lines().map_while(Result::ok) {});
+        self.child = Some(child);
+    }
+}
+
+impl Drop for TempCspVaultServerProcess {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+/// Runs a CSP vault server until the process is killed, if the current
+/// process was spawned by [`TempCspVaultServerProcess`]. Returns immediately
+/// otherwise.
+pub fn run_temp_csp_vault_server_process_if_requested() {
+    let Ok(crypto_root) = std::env::var(CRYPTO_ROOT_ENV_VAR) else {
+        return;
+    };
+    let env_var = |name: &str| std::env::var(name).unwrap_or_else(|_| panic!("missing {name}"));
+    let address: SocketAddr = env_var(ADDRESS_ENV_VAR)
+        .parse()
+        .expect("invalid vault server address");
+    let tls = RemoteCspVaultTlsConfig {
+        certificate: PathBuf::from(env_var(CERTIFICATE_ENV_VAR)),
+        private_key: PathBuf::from(env_var(PRIVATE_KEY_ENV_VAR)),
+        ca_certificate: PathBuf::from(env_var(CA_CERTIFICATE_ENV_VAR)),
+    };
+
+    let rt = tokio::runtime::Runtime::new().expect("failed to create runtime");
+    rt.block_on(async move {
+        let listener = tokio::net::TcpListener::bind(address)
+            .await
+            .unwrap_or_else(|e| panic!("failed to bind to {address}: {e}"));
+        let local_address = listener.local_addr().expect("failed to get local address");
+        let listener =
+            CspVaultListener::tcp(listener, &tls).expect("failed to set up TLS for listener");
+        let server = TarpcCspVaultServerImpl::builder(Path::new(&crypto_root)).build(listener);
+        println!("{LISTENING_AT_PREFIX}{local_address}");
+        server.run().await
+    });
+}
@@ -231,6 +231,14 @@ mock! {
             context: VetKdDerivationContext,
             input: Vec<u8>,
         ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
+
+        fn create_vetkd_bls_share(
+            &self,
+            key_id: KeyId,
+            master_public_key: Vec<u8>,
+            context: VetKdDerivationContext,
+            message: Option<Vec<u8>>,
+        ) -> Result<VetKdEncryptedKeyShareContent, VetKdEncryptedKeyShareCreationVaultError>;
     }
 
     impl SecretKeyStoreCspVault for LocalCspVault{
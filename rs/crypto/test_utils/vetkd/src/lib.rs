@@ -1,6 +1,7 @@
 use ic_crypto_internal_bls12_381_type::{G1Affine, G2Affine, Scalar};
 use ic_crypto_internal_bls12_381_vetkd::{
-    DerivationContext, EncryptedKey, EncryptedKeyShare, TransportPublicKey,
+    BlsSignature, BlsSignatureShare, DerivationContext, EncryptedKey, EncryptedKeyShare,
+    TransportPublicKey,
 };
 use rand_chacha::rand_core::SeedableRng;
 
@@ -66,4 +67,28 @@ impl PrivateKey {
 
         ek.serialize().to_vec()
     }
+
+    /// Returns the BLS public key in G1 derived for `canister_id` and
+    /// `context`, and the BLS signature on `message` under it if any.
+    pub fn bls_protocol(
+        &self,
+        canister_id: &[u8],
+        context: &[u8],
+        message: Option<&[u8]>,
+    ) -> (Vec<u8>, Option<Vec<u8>>) {
+        let dc = DerivationContext::new(canister_id, context);
+
+        let share = BlsSignatureShare::create(&self.public_point, &self.secret_key, &dc, message);
+
+        let mut shares = std::collections::BTreeMap::new();
+        shares.insert(0, share);
+
+        let signature = BlsSignature::combine_all(&shares, 1, &self.public_point, &dc, message)
+            .expect("Failed to combine single BlsSignatureShare to a BlsSignature");
+
+        (
+            signature.public_key().serialize().to_vec(),
+            signature.signature().map(|s| s.serialize().to_vec()),
+        )
+    }
 }
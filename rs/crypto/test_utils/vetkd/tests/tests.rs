@@ -1,3 +1,5 @@
+use ic_crypto_internal_bls12_381_type::G2Affine;
+use ic_crypto_internal_bls12_381_vetkd::{BlsSignature, DerivationContext};
 use ic_crypto_test_utils_vetkd::*;
 use ic_vetkeys::{EncryptedVetKey, MasterPublicKey, TransportSecretKey};
 use rand::Rng;
@@ -32,3 +34,30 @@ fn should_generate_valid_bls_signature() {
 
     assert!(ek.decrypt_and_verify(&tsk, &dpk, &input).is_ok());
 }
+
+#[test]
+fn should_generate_valid_min_pk_bls_signature() {
+    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+
+    let pk = PrivateKey::generate(&rng.r#gen::<[u8; 32]>());
+
+    let canister_id = rng.r#gen::<[u8; 32]>();
+    let context = rng.r#gen::<[u8; 32]>();
+    let message = rng.r#gen::<[u8; 32]>();
+
+    let (public_key, signature) = pk.bls_protocol(&canister_id, &context, Some(&message));
+    let signature = signature.expect("a signature was requested");
+    assert_eq!(public_key.len(), 48);
+    assert_eq!(signature.len(), 96);
+
+    let master_pk = G2Affine::deserialize(&pk.public_key_bytes()).unwrap();
+    let dc = DerivationContext::new(&canister_id, &context);
+    let combined = BlsSignature::deserialize(&[public_key.clone(), signature].concat()).unwrap();
+    assert!(combined.is_valid(&master_pk, &dc, Some(&message)));
+    assert!(!combined.is_valid(&master_pk, &dc, Some(b"other message")));
+
+    assert_eq!(
+        pk.bls_protocol(&canister_id, &context, None),
+        (public_key, None)
+    );
+}
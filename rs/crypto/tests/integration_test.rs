@@ -827,6 +827,10 @@ fn algorithm_id_should_match_algorithm_id_proto() {
         AlgorithmIdProto::ThresholdEd25519 as i32
     );
     assert_eq!(AlgorithmId::VetKD as i32, AlgorithmIdProto::Vetkd as i32);
+    assert_eq!(
+        AlgorithmId::MlDsa65 as i32,
+        AlgorithmIdProto::MlDsa65 as i32
+    );
 }
 
 #[test]
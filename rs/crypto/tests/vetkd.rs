@@ -170,6 +170,7 @@ impl VetKDTestClient {
             },
             input: self.input.clone(),
             transport_public_key: self.tpk.clone(),
+            output: VetKdOutput::EncryptedKey,
         }
     }
 
@@ -227,6 +228,66 @@ fn should_consistently_derive_the_same_vetkey_given_sufficient_shares() {
     assert!(keys.iter().all(|k| *k == keys[0]));
 }
 
+#[test]
+fn should_combine_a_verifiable_bls_public_key_and_signature() {
+    let mut rng = reproducible_rng();
+    let server = VetKDTestServer::new(&mut rng);
+    let client = VetKDTestClient::new(&mut rng, &server);
+    let signature_args = VetKdArgs {
+        output: VetKdOutput::BlsSignature,
+        ..client.create_args(&server.dkg_id)
+    };
+    let public_key_args = VetKdArgs {
+        output: VetKdOutput::BlsPublicKey,
+        ..client.create_args(&server.dkg_id)
+    };
+
+    let shares = server
+        .create_key_shares(&signature_args, &mut rng)
+        .expect("Share creation failed");
+    assert_eq!(
+        server.verify_key_shares(&shares, &signature_args, &mut rng),
+        Ok(())
+    );
+    let signature = server
+        .combine_key_shares(&shares, &signature_args, &mut rng)
+        .expect("Share combination failed")
+        .1;
+    assert_eq!(
+        server.verify_encrypted_key(&signature, &signature_args, &mut rng),
+        Ok(())
+    );
+    let (public_key, _signature) = signature
+        .bls_public_key_and_signature()
+        .expect("not a BLS public key and signature");
+
+    let other_input_args = VetKdArgs {
+        input: b"some other input".to_vec(),
+        ..signature_args.clone()
+    };
+    assert_eq!(
+        server.verify_encrypted_key(&signature, &other_input_args, &mut rng),
+        Err(VetKdKeyVerificationError::VerificationError)
+    );
+
+    let public_key_shares = server
+        .create_key_shares(&public_key_args, &mut rng)
+        .expect("Share creation failed");
+    let public_key_only = server
+        .combine_key_shares(&public_key_shares, &public_key_args, &mut rng)
+        .expect("Share combination failed")
+        .1;
+    assert_eq!(
+        server.verify_encrypted_key(&public_key_only, &public_key_args, &mut rng),
+        Ok(())
+    );
+    assert_eq!(public_key_only.encrypted_key, public_key);
+    assert_eq!(
+        server.verify_encrypted_key(&public_key_only, &signature_args, &mut rng),
+        Err(VetKdKeyVerificationError::VerificationError)
+    );
+}
+
 mod create_encrypted_key_share {
 
     use super::*;
@@ -756,6 +756,15 @@ impl CyclesAccountManager {
         self.scale_cost(self.config.vetkd_fee, subnet_size, cost_schedule)
     }
 
+    /// Amount to charge for a threshold BLS signature.
+    pub fn bls_signature_fee(
+        &self,
+        subnet_size: usize,
+        cost_schedule: CanisterCyclesCostSchedule,
+    ) -> Cycles {
+        self.scale_cost(self.config.bls_signature_fee, subnet_size, cost_schedule)
+    }
+
     ////////////////////////////////////////////////////////////////////////////
     //
     // Storage
@@ -1085,6 +1094,7 @@ impl CyclesAccountManager {
                     | CyclesUseCase::ECDSAOutcalls
                     | CyclesUseCase::SchnorrOutcalls
                     | CyclesUseCase::VetKd
+                    | CyclesUseCase::BlsOutcalls
                     | CyclesUseCase::HTTPOutcalls
                     | CyclesUseCase::DeletedCanisters
                     | CyclesUseCase::NonConsumed
@@ -7,16 +7,16 @@ use ic_error_types::UserError;
 use ic_logger::{ReplicaLogger, info};
 use ic_management_canister_types_private::{
     BitcoinGetBalanceArgs, BitcoinGetBlockHeadersArgs, BitcoinGetCurrentFeePercentilesArgs,
-    BitcoinGetUtxosArgs, BitcoinSendTransactionArgs, CanisterIdRecord, CanisterInfoRequest,
-    CanisterMetadataRequest, ClearChunkStoreArgs, DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs,
-    FetchCanisterLogsRequest, InstallChunkedCodeArgs, InstallCodeArgsV2, ListCanisterSnapshotArgs,
-    LoadCanisterSnapshotArgs, MasterPublicKeyId, Method as Ic00Method, NodeMetricsHistoryArgs,
-    Payload, ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs,
+    BitcoinGetUtxosArgs, BitcoinSendTransactionArgs, BlsPublicKeyArgs, CanisterIdRecord,
+    CanisterInfoRequest, CanisterMetadataRequest, ClearChunkStoreArgs, DeleteCanisterSnapshotArgs,
+    ECDSAPublicKeyArgs, FetchCanisterLogsRequest, InstallChunkedCodeArgs, InstallCodeArgsV2,
+    ListCanisterSnapshotArgs, LoadCanisterSnapshotArgs, MasterPublicKeyId, Method as Ic00Method,
+    NodeMetricsHistoryArgs, Payload, ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs,
     ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs, ReshareChainKeyArgs,
-    SchnorrPublicKeyArgs, SignWithECDSAArgs, SignWithSchnorrArgs, StoredChunksArgs, SubnetInfoArgs,
-    TakeCanisterSnapshotArgs, UninstallCodeArgs, UpdateSettingsArgs,
-    UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs, UploadChunkArgs,
-    VetKdDeriveKeyArgs, VetKdPublicKeyArgs,
+    SchnorrPublicKeyArgs, SignWithBlsArgs, SignWithECDSAArgs, SignWithSchnorrArgs,
+    StoredChunksArgs, SubnetInfoArgs, TakeCanisterSnapshotArgs, UninstallCodeArgs,
+    UpdateSettingsArgs, UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs,
+    UploadChunkArgs, VetKdDeriveKeyArgs, VetKdPublicKeyArgs,
 };
 use ic_replicated_state::NetworkTopology;
 use itertools::Itertools;
@@ -271,6 +271,24 @@ pub(super) fn resolve_destination(
                 ChainKeySubnetKind::HoldsEnabledKey,
             )
         }
+        Ok(Ic00Method::BlsPublicKey) => {
+            let args = BlsPublicKeyArgs::decode(payload)?;
+            route_chain_key_message(
+                &MasterPublicKeyId::Bls(args.key_id),
+                network_topology,
+                &None,
+                ChainKeySubnetKind::HoldsEnabledKey,
+            )
+        }
+        Ok(Ic00Method::SignWithBls) => {
+            let args = SignWithBlsArgs::decode(payload)?;
+            route_chain_key_message(
+                &MasterPublicKeyId::Bls(args.key_id),
+                network_topology,
+                &None,
+                ChainKeySubnetKind::HoldsEnabledKey,
+            )
+        }
         Ok(Ic00Method::UploadChunk) => {
             let args = UploadChunkArgs::decode(payload)?;
             let canister_id = args.get_canister_id();
@@ -492,8 +510,8 @@ mod tests {
     use ic_base_types::RegistryVersion;
     use ic_logger::no_op_logger;
     use ic_management_canister_types_private::{
-        DerivationPath, EcdsaCurve, EcdsaKeyId, SchnorrAlgorithm, SchnorrKeyId, SignWithECDSAArgs,
-        VetKdCurve, VetKdKeyId,
+        BlsCurve, BlsKeyId, DerivationPath, EcdsaCurve, EcdsaKeyId, SchnorrAlgorithm, SchnorrKeyId,
+        SignWithECDSAArgs, VetKdCurve, VetKdKeyId,
     };
     use ic_replicated_state::SubnetTopology;
     use ic_test_utilities_types::ids::{canister_test_id, node_test_id, subnet_test_id};
@@ -523,6 +541,13 @@ mod tests {
         }
     }
 
+    fn bls_key_id(i: u8) -> BlsKeyId {
+        BlsKeyId {
+            curve: BlsCurve::Bls12_381_G2,
+            name: format!("key_id{i}"),
+        }
+    }
+
     fn ecdsa_master_key_id(i: u8) -> MasterPublicKeyId {
         MasterPublicKeyId::Ecdsa(ecdsa_key_id(i))
     }
@@ -535,6 +560,10 @@ mod tests {
         MasterPublicKeyId::VetKd(vetkd_key_id(i))
     }
 
+    fn bls_master_key_id(i: u8) -> MasterPublicKeyId {
+        MasterPublicKeyId::Bls(bls_key_id(i))
+    }
+
     /// Two subnets have key_id1, but only one of the subnets is enabled to use it.
     /// Only one subnet has key_id2, and it isn't enabled to use it.
     fn network_with_chain_key_subnets(
@@ -576,6 +605,10 @@ mod tests {
         network_with_chain_key_subnets(vetkd_master_key_id(1), vetkd_master_key_id(2))
     }
 
+    fn network_with_bls_subnets() -> NetworkTopology {
+        network_with_chain_key_subnets(bls_master_key_id(1), bls_master_key_id(2))
+    }
+
     fn network_without_chain_key_subnets() -> NetworkTopology {
         NetworkTopology::default()
     }
@@ -619,6 +652,15 @@ mod tests {
         Encode!(&args).unwrap()
     }
 
+    fn bls_sign_request(key_id: BlsKeyId) -> Vec<u8> {
+        let args = SignWithBlsArgs {
+            message: vec![1; 32],
+            derivation_path: DerivationPath::new(vec![ByteBuf::from(vec![0; 10])]),
+            key_id,
+        };
+        Encode!(&args).unwrap()
+    }
+
     fn ecdsa_public_key_request(key_id: EcdsaKeyId) -> Vec<u8> {
         let args = ECDSAPublicKeyArgs {
             canister_id: Some(canister_test_id(1)),
@@ -646,6 +688,15 @@ mod tests {
         Encode!(&args).unwrap()
     }
 
+    fn bls_public_key_request(key_id: BlsKeyId) -> Vec<u8> {
+        let args = BlsPublicKeyArgs {
+            canister_id: Some(canister_test_id(1)),
+            derivation_path: DerivationPath::new(vec![ByteBuf::from(vec![0; 10])]),
+            key_id,
+        };
+        Encode!(&args).unwrap()
+    }
+
     #[test]
     fn resolve_reshare_chain_key() {
         let logger = no_op_logger();
@@ -653,6 +704,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_eq!(
                 resolve_destination(
@@ -677,6 +729,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_matches!(
                 resolve_destination(
@@ -708,6 +761,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_matches!(
                 resolve_destination(
@@ -739,6 +793,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_matches!(
                     resolve_destination(
@@ -771,6 +826,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_matches!(
                 resolve_destination(
@@ -815,6 +871,16 @@ mod tests {
                 Ic00Method::VetKdDeriveKey,
                 vetkd_derive_key_request(vetkd_key_id(1)),
             ),
+            (
+                network_with_bls_subnets(),
+                Ic00Method::SignWithBls,
+                bls_sign_request(bls_key_id(1)),
+            ),
+            (
+                network_with_bls_subnets(),
+                Ic00Method::BlsPublicKey,
+                bls_public_key_request(bls_key_id(1)),
+            ),
         ] {
             assert_eq!(
                 resolve_destination(
@@ -851,6 +917,16 @@ mod tests {
                 vetkd_derive_key_request(vetkd_key_id(1)),
                 vetkd_master_key_id(1),
             ),
+            (
+                Ic00Method::SignWithBls,
+                bls_sign_request(bls_key_id(1)),
+                bls_master_key_id(1),
+            ),
+            (
+                Ic00Method::BlsPublicKey,
+                bls_public_key_request(bls_key_id(1)),
+                bls_master_key_id(1),
+            ),
         ] {
             assert_matches!(resolve_destination(
                 &network_without_chain_key_subnets(),
@@ -916,6 +992,7 @@ mod tests {
             (network_with_ecdsa_subnets(), ecdsa_master_key_id(1)),
             (network_with_schnorr_subnets(), schnorr_master_key_id(1)),
             (network_with_vetkd_subnets(), vetkd_master_key_id(1)),
+            (network_with_bls_subnets(), bls_master_key_id(1)),
         ] {
             assert_eq!(
                 resolve_destination(
@@ -940,6 +1017,7 @@ mod tests {
             (ecdsa_master_key_id(1), network_with_ecdsa_subnets()),
             (schnorr_master_key_id(1), network_with_schnorr_subnets()),
             (vetkd_master_key_id(1), network_with_vetkd_subnets()),
+            (bls_master_key_id(1), network_with_bls_subnets()),
         ] {
             assert_eq!(
                 route_chain_key_message(
@@ -961,6 +1039,7 @@ mod tests {
             (ecdsa_master_key_id(1), network_with_ecdsa_subnets()),
             (schnorr_master_key_id(1), network_with_schnorr_subnets()),
             (vetkd_master_key_id(1), network_with_vetkd_subnets()),
+            (bls_master_key_id(1), network_with_bls_subnets()),
         ] {
             let subnet_id = subnet_test_id(1);
             match route_chain_key_message(
@@ -984,6 +1063,7 @@ mod tests {
             (ecdsa_master_key_id(1), network_with_ecdsa_subnets()),
             (schnorr_master_key_id(1), network_with_schnorr_subnets()),
             (vetkd_master_key_id(1), network_with_vetkd_subnets()),
+            (bls_master_key_id(1), network_with_bls_subnets()),
         ] {
             let unknown_subnet_id = subnet_test_id(3);
             match route_chain_key_message(
@@ -1009,6 +1089,7 @@ mod tests {
             (ecdsa_master_key_id(1), network_with_ecdsa_subnets()),
             (schnorr_master_key_id(1), network_with_schnorr_subnets()),
             (vetkd_master_key_id(1), network_with_vetkd_subnets()),
+            (bls_master_key_id(1), network_with_bls_subnets()),
         ] {
             let subnet_id = subnet_test_id(2);
             match route_chain_key_message(
@@ -273,6 +273,8 @@ impl SystemStateModifications {
             | Ok(Ic00Method::SignWithSchnorr)
             | Ok(Ic00Method::VetKdPublicKey)
             | Ok(Ic00Method::VetKdDeriveKey)
+            | Ok(Ic00Method::BlsPublicKey)
+            | Ok(Ic00Method::SignWithBls)
             | Ok(Ic00Method::ProvisionalTopUpCanister)
             | Ok(Ic00Method::BitcoinSendTransactionInternal)
             | Ok(Ic00Method::BitcoinGetSuccessors)
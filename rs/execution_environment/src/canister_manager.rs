@@ -103,6 +103,7 @@ pub(crate) struct CanisterManager {
     ingress_history_writer: Arc<dyn IngressHistoryWriter<State = ReplicatedState>>,
     fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
     environment_variables_flag: FlagStatus,
+    latency_sensitive_methods_flag: FlagStatus,
 }
 
 impl CanisterManager {
@@ -114,6 +115,7 @@ impl CanisterManager {
         ingress_history_writer: Arc<dyn IngressHistoryWriter<State = ReplicatedState>>,
         fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
         environment_variables_flag: FlagStatus,
+        latency_sensitive_methods_flag: FlagStatus,
     ) -> Self {
         CanisterManager {
             hypervisor,
@@ -123,6 +125,7 @@ impl CanisterManager {
             ingress_history_writer,
             fd_factory,
             environment_variables_flag,
+            latency_sensitive_methods_flag,
         }
     }
 
@@ -156,6 +159,8 @@ impl CanisterManager {
             | Ok(Ic00Method::SignWithSchnorr)
             | Ok(Ic00Method::VetKdPublicKey)
             | Ok(Ic00Method::VetKdDeriveKey)
+            | Ok(Ic00Method::BlsPublicKey)
+            | Ok(Ic00Method::SignWithBls)
             // "DepositCycles" can be called by anyone however as ingress message
             // cannot carry cycles, it does not make sense to allow them from users.
             | Ok(Ic00Method::DepositCycles)
@@ -277,6 +282,32 @@ impl CanisterManager {
         Ok(())
     }
 
+    /// Validates the latency-sensitive methods of the canister.
+    /// - the number of methods cannot exceed the given maximum.
+    /// - the name of each method cannot exceed the given maximum length.
+    fn validate_latency_sensitive_methods(
+        &self,
+        settings: &CanisterSettings,
+    ) -> Result<(), CanisterManagerError> {
+        if let Some(latency_sensitive_methods) = settings.latency_sensitive_methods() {
+            if latency_sensitive_methods.len() > self.config.max_latency_sensitive_methods {
+                return Err(CanisterManagerError::LatencySensitiveMethodsTooMany {
+                    max: self.config.max_latency_sensitive_methods,
+                    count: latency_sensitive_methods.len(),
+                });
+            }
+            for name in latency_sensitive_methods.iter() {
+                if name.len() > self.config.max_latency_sensitive_method_name_length {
+                    return Err(CanisterManagerError::LatencySensitiveMethodNameTooLong {
+                        name: name.clone(),
+                        max_name_length: self.config.max_latency_sensitive_method_name_length,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Validates the new canisters settings:
     /// - memory allocation:
     ///     - it cannot be lower than the current canister memory usage.
@@ -291,6 +322,9 @@ impl CanisterManager {
     /// - environment variables:
     ///     - the number of environment variables cannot exceed the given maximum.
     ///     - the key and value of each environment variable cannot exceed the given maximum length.
+    /// - latency-sensitive methods:
+    ///     - the number of methods cannot exceed the given maximum.
+    ///     - the name of each method cannot exceed the given maximum length.
     /// - log memory limit:
     ///     - must be at least the specified minimum.
     ///     - must not exceed the specified maximum.
@@ -315,6 +349,7 @@ impl CanisterManager {
         canister_reserved_balance_limit: Option<Cycles>,
     ) -> Result<ValidatedCanisterSettings, CanisterManagerError> {
         self.validate_environment_variables(&settings)?;
+        self.validate_latency_sensitive_methods(&settings)?;
 
         let old_memory_bytes = canister_memory_allocation.allocated_bytes(canister_memory_usage);
         let new_memory_bytes = match settings.memory_allocation {
@@ -500,6 +535,7 @@ impl CanisterManager {
             log_memory_limit,
             settings.wasm_memory_limit(),
             settings.environment_variables().cloned(),
+            settings.latency_sensitive_methods().cloned(),
         ))
     }
 
@@ -596,6 +632,11 @@ impl CanisterManager {
         {
             canister.system_state.environment_variables = environment_variables.clone();
         }
+        if let Some(latency_sensitive_methods) = settings.latency_sensitive_methods()
+            && self.latency_sensitive_methods_flag == FlagStatus::Enabled
+        {
+            canister.system_state.latency_sensitive_methods = latency_sensitive_methods.clone();
+        }
     }
 
     /// Tries to apply the requested settings on the canister identified by
@@ -1160,6 +1201,12 @@ impl CanisterManager {
             wasm_memory_limit.map(|x| x.get()),
             wasm_memory_threshold.get(),
             canister.system_state.environment_variables.clone(),
+            canister
+                .system_state
+                .latency_sensitive_methods
+                .iter()
+                .cloned()
+                .collect(),
         ))
     }
 
@@ -1897,6 +1944,10 @@ impl CanisterManager {
     /// and delete it before creating a new one.
     /// Failure to do so will result in the creation of a new snapshot being unsuccessful.
     ///
+    /// If the `parent_snapshot` parameter is `Some`, an incremental snapshot is
+    /// created that only stores the memory pages that differ from the given
+    /// snapshot of the same canister, and only these pages are charged for.
+    ///
     /// If the new snapshot cannot be created, an appropriate error will be returned.
     pub(crate) fn take_canister_snapshot(
         &self,
@@ -1904,6 +1955,7 @@ impl CanisterManager {
         sender: PrincipalId,
         canister: &mut CanisterState,
         replace_snapshot: Option<SnapshotId>,
+        parent_snapshot: Option<SnapshotId>,
         state: &mut ReplicatedState,
         round_limits: &mut RoundLimits,
         resource_saturation: &ResourceSaturation,
@@ -1912,10 +1964,26 @@ impl CanisterManager {
         validate_controller(canister, &sender)?;
         let canister_id = canister.canister_id();
 
+        if let Some(parent_snapshot_id) = parent_snapshot {
+            // Perform access validation, but don't use the result.
+            let _ = self.get_snapshot(canister_id, parent_snapshot_id, state)?;
+            // The parent must outlive the new snapshot.
+            if replace_snapshot == Some(parent_snapshot_id) {
+                return Err(CanisterManagerError::CanisterSnapshotHasDependents {
+                    canister_id,
+                    snapshot_id: parent_snapshot_id,
+                });
+            }
+        }
+
         let replace_snapshot_size = match replace_snapshot {
-            Some(replace_snapshot_id) => self
-                .get_snapshot(canister_id, replace_snapshot_id, state)?
-                .size(),
+            Some(replace_snapshot_id) => {
+                let size = self
+                    .get_snapshot(canister_id, replace_snapshot_id, state)?
+                    .size();
+                self.validate_no_dependents(canister_id, replace_snapshot_id, state)?;
+                size
+            }
             None => {
                 // No replace snapshot ID provided, check whether the maximum number of snapshots
                 // has been reached.
@@ -1943,18 +2011,16 @@ impl CanisterManager {
             });
         }
 
-        let new_snapshot_size = canister.snapshot_size_bytes();
         let old_memory_usage = canister.memory_usage();
-        let new_memory_usage = canister
-            .memory_usage()
-            .saturating_add(&new_snapshot_size)
-            .saturating_sub(&replace_snapshot_size);
 
         // Compute cycles for instructions spent taking a snapshot of the canister.
+        // Taking an incremental snapshot reads all of the canister's memory if
+        // its files were merged since the parent snapshot was taken, so the
+        // instructions are based on the full snapshot size.
         let instructions = self
             .config
             .canister_snapshot_baseline_instructions
-            .saturating_add(&new_snapshot_size.get().into());
+            .saturating_add(&canister.snapshot_size_bytes().get().into());
         let cycles_for_instructions = self.cycles_account_manager.execution_cost(
             instructions,
             subnet_size,
@@ -1966,6 +2032,50 @@ impl CanisterManager {
             WasmExecutionMode::Wasm32,
         );
 
+        // An incremental snapshot can only be sized after comparing the canister's
+        // memories against the ones of the parent snapshot. Before doing that work,
+        // check that the canister can afford the snapshot assuming it stores no
+        // pages at all, so that frozen or underfunded canisters are rejected early.
+        let new_incremental_snapshot = match parent_snapshot {
+            Some(parent_snapshot_id) => {
+                self.cycles_and_memory_usage_checks(
+                    subnet_size,
+                    state.get_own_cost_schedule(),
+                    canister,
+                    sender,
+                    cycles_for_instructions,
+                    round_limits,
+                    old_memory_usage.saturating_sub(&replace_snapshot_size),
+                    old_memory_usage,
+                    resource_saturation,
+                )?;
+                let parent_memories = state
+                    .canister_snapshots
+                    .reassemble_memories(parent_snapshot_id, None)
+                    .map_err(CanisterManagerError::from)?;
+                Some(
+                    CanisterSnapshot::incremental_from_canister(
+                        canister,
+                        state.time(),
+                        parent_snapshot_id,
+                        &parent_memories,
+                        Arc::clone(&self.fd_factory),
+                    )
+                    .map_err(CanisterManagerError::from)?,
+                )
+            }
+            None => None,
+        };
+
+        let new_snapshot_size = match &new_incremental_snapshot {
+            Some(snapshot) => snapshot.size(),
+            None => canister.snapshot_size_bytes(),
+        };
+        let new_memory_usage = canister
+            .memory_usage()
+            .saturating_add(&new_snapshot_size)
+            .saturating_sub(&replace_snapshot_size);
+
         let validated_cycles_and_memory_usage = self.cycles_and_memory_usage_checks(
             subnet_size,
             state.get_own_cost_schedule(),
@@ -1979,8 +2089,11 @@ impl CanisterManager {
         )?;
 
         // Create new snapshot.
-        let new_snapshot = CanisterSnapshot::from_canister(canister, state.time())
-            .map_err(CanisterManagerError::from)?;
+        let new_snapshot = match new_incremental_snapshot {
+            Some(snapshot) => snapshot,
+            None => CanisterSnapshot::from_canister(canister, state.time())
+                .map_err(CanisterManagerError::from)?,
+        };
 
         // Delete old snapshot identified by `replace_snapshot`.
         if let Some(replace_snapshot) = replace_snapshot {
@@ -2206,7 +2319,7 @@ impl CanisterManager {
 
         let (_old_execution_state, mut system_state, scheduler_state) = canister_clone.into_parts();
 
-        let (instructions_used, new_execution_state) = {
+        let (instructions_used, new_execution_state, root_snapshot_id) = {
             let new_wasm_hash = WasmHash::from(&execution_snapshot.wasm_binary);
             let compilation_cost_handling = if state
                 .metadata
@@ -2251,45 +2364,32 @@ impl CanisterManager {
 
             new_execution_state.exported_globals = execution_snapshot.exported_globals.clone();
 
-            if canister_id == snapshot.canister_id() {
-                new_execution_state.stable_memory = Memory::from(&execution_snapshot.stable_memory);
-                new_execution_state.wasm_memory = Memory::from(&execution_snapshot.wasm_memory);
-            } else {
-                let new_stable_memory = match Memory::try_from((
-                    &execution_snapshot.stable_memory,
-                    Arc::clone(&self.fd_factory),
-                )) {
-                    Ok(memory) => memory,
-                    Err(_) => {
-                        return (
-                            Err(CanisterManagerError::CanisterSnapshotNotLoadable {
-                                canister_id,
-                                snapshot_id,
-                            }),
-                            instructions_used,
-                        );
-                    }
-                };
-                new_execution_state.stable_memory = new_stable_memory;
-
-                let new_wasm_memory = match Memory::try_from((
-                    &execution_snapshot.wasm_memory,
-                    Arc::clone(&self.fd_factory),
-                )) {
-                    Ok(memory) => memory,
-                    Err(_) => {
-                        return (
-                            Err(CanisterManagerError::CanisterSnapshotNotLoadable {
-                                canister_id,
-                                snapshot_id,
-                            }),
-                            instructions_used,
-                        );
-                    }
-                };
-                new_execution_state.wasm_memory = new_wasm_memory;
-            }
-            (instructions_used, Some(new_execution_state))
+            // Loading onto a different canister requires clean copies of the
+            // snapshot's memories, backed by a fresh page allocator.
+            let fd_factory =
+                (canister_id != snapshot.canister_id()).then(|| Arc::clone(&self.fd_factory));
+            let memories = match state
+                .canister_snapshots
+                .reassemble_memories(snapshot_id, fd_factory)
+            {
+                Ok(memories) => memories,
+                Err(_) => {
+                    return (
+                        Err(CanisterManagerError::CanisterSnapshotNotLoadable {
+                            canister_id,
+                            snapshot_id,
+                        }),
+                        instructions_used,
+                    );
+                }
+            };
+            new_execution_state.stable_memory = Memory::from(&memories.stable_memory);
+            new_execution_state.wasm_memory = Memory::from(&memories.wasm_memory);
+            (
+                instructions_used,
+                Some(new_execution_state),
+                memories.root_snapshot_id,
+            )
         };
 
         system_state.wasm_chunk_store = snapshot.chunk_store().clone();
@@ -2406,10 +2506,17 @@ impl CanisterManager {
         round_limits
             .subnet_available_memory
             .update_execution_memory_unchecked(available_execution_memory_change);
-        state
-            .metadata
-            .unflushed_checkpoint_ops
-            .load_snapshot(canister_id, snapshot_id);
+        if root_snapshot_id == snapshot_id {
+            state
+                .metadata
+                .unflushed_checkpoint_ops
+                .load_snapshot(canister_id, snapshot_id);
+        } else {
+            state
+                .metadata
+                .unflushed_checkpoint_ops
+                .load_incremental_snapshot(canister_id, snapshot_id, root_snapshot_id);
+        }
 
         if self.config.rate_limiting_of_heap_delta == FlagStatus::Enabled {
             new_canister.scheduler_state.heap_delta_debit = new_canister
@@ -2471,6 +2578,7 @@ impl CanisterManager {
 
         // perform access validation, but don't use the result
         let _ = self.get_snapshot(canister.canister_id(), delete_snapshot_id, state)?;
+        self.validate_no_dependents(canister.canister_id(), delete_snapshot_id, state)?;
 
         let old_snapshot = state.delete_snapshot(delete_snapshot_id);
         // Already confirmed that `old_snapshot` exists.
@@ -2572,14 +2680,20 @@ impl CanisterManager {
 
         let res = match kind {
             CanisterSnapshotDataKind::StableMemory { offset, size } => {
-                let stable_memory = snapshot.execution_snapshot().stable_memory.clone();
+                let stable_memory = state
+                    .canister_snapshots
+                    .reassemble_memories(snapshot_id, None)?
+                    .stable_memory;
                 match CanisterSnapshot::get_memory_chunk(stable_memory, offset, size) {
                     Ok(chunk) => Ok(chunk),
                     Err(e) => Err(e.into()),
                 }
             }
             CanisterSnapshotDataKind::WasmMemory { offset, size } => {
-                let main_memory = snapshot.execution_snapshot().wasm_memory.clone();
+                let main_memory = state
+                    .canister_snapshots
+                    .reassemble_memories(snapshot_id, None)?
+                    .wasm_memory;
                 match CanisterSnapshot::get_memory_chunk(main_memory, offset, size) {
                     Ok(chunk) => Ok(chunk),
                     Err(e) => Err(e.into()),
@@ -2648,9 +2762,13 @@ impl CanisterManager {
             })?;
 
         let replace_snapshot_size = match args.replace_snapshot() {
-            Some(replace_snapshot_id) => self
-                .get_snapshot(canister_id, replace_snapshot_id, state)?
-                .size(),
+            Some(replace_snapshot_id) => {
+                let size = self
+                    .get_snapshot(canister_id, replace_snapshot_id, state)?
+                    .size();
+                self.validate_no_dependents(canister_id, replace_snapshot_id, state)?;
+                size
+            }
             None => {
                 // No replace snapshot ID provided, check whether the maximum number of snapshots
                 // has been reached.
@@ -2785,6 +2903,10 @@ impl CanisterManager {
         if snapshot.source() != SnapshotSource::MetadataUpload(candid::Reserved) {
             return Err(CanisterManagerError::CanisterSnapshotImmutable);
         }
+        // Ensure no incremental snapshot was taken on top of this snapshot yet.
+        self.validate_no_dependents(canister.canister_id(), snapshot_id, state)?;
+        let snapshot: &mut Arc<CanisterSnapshot> =
+            self.get_snapshot_mut(canister.canister_id(), snapshot_id, state)?;
 
         if self.config.rate_limiting_of_heap_delta == FlagStatus::Enabled
             && canister.scheduler_state.heap_delta_debit >= self.config.heap_delta_rate_limit
@@ -2945,6 +3067,24 @@ impl CanisterManager {
         );
     }
 
+    /// Returns an error if incremental snapshots were taken on top of the
+    /// given snapshot, in which case the snapshot must not be deleted or
+    /// modified.
+    fn validate_no_dependents(
+        &self,
+        canister_id: CanisterId,
+        snapshot_id: SnapshotId,
+        state: &ReplicatedState,
+    ) -> Result<(), CanisterManagerError> {
+        if state.canister_snapshots.has_dependents(snapshot_id) {
+            return Err(CanisterManagerError::CanisterSnapshotHasDependents {
+                canister_id,
+                snapshot_id,
+            });
+        }
+        Ok(())
+    }
+
     /// Returns the cycles and instructions that should be charged for this data upload operation.
     fn get_bytes_and_instructions(
         &self,
@@ -19,7 +19,8 @@ use ic_config::{
     execution_environment::{
         CANISTER_GUARANTEED_CALLBACK_QUOTA, Config, DEFAULT_WASM_MEMORY_LIMIT,
         MAX_ENVIRONMENT_VARIABLE_NAME_LENGTH, MAX_ENVIRONMENT_VARIABLE_VALUE_LENGTH,
-        MAX_ENVIRONMENT_VARIABLES, MAX_NUMBER_OF_SNAPSHOTS_PER_CANISTER,
+        MAX_ENVIRONMENT_VARIABLES, MAX_LATENCY_SENSITIVE_METHOD_NAME_LENGTH,
+        MAX_LATENCY_SENSITIVE_METHODS, MAX_NUMBER_OF_SNAPSHOTS_PER_CANISTER,
         SUBNET_CALLBACK_SOFT_LIMIT,
     },
     flag_status::FlagStatus,
@@ -291,6 +292,7 @@ impl CanisterManagerBuilder {
             ingress_history_writer,
             Arc::new(TestPageAllocatorFileDescriptorImpl),
             FlagStatus::Disabled,
+            FlagStatus::Enabled,
         )
     }
 }
@@ -334,6 +336,8 @@ fn canister_manager_config(
         MAX_ENVIRONMENT_VARIABLES,
         MAX_ENVIRONMENT_VARIABLE_NAME_LENGTH,
         MAX_ENVIRONMENT_VARIABLE_VALUE_LENGTH,
+        MAX_LATENCY_SENSITIVE_METHODS,
+        MAX_LATENCY_SENSITIVE_METHOD_NAME_LENGTH,
     )
 }
 
@@ -6879,6 +6883,7 @@ fn cannot_rename_with_snapshots() {
     env2.take_canister_snapshot(TakeCanisterSnapshotArgs {
         canister_id: canister_id2.into(),
         replace_snapshot: None,
+        parent_snapshot: None,
     })
     .unwrap();
 
@@ -7936,3 +7941,137 @@ fn set_heap_and_stable_memory_during_reinstall() {
 
     check_data(&mut test, canister_id);
 }
+
+#[test]
+fn test_latency_sensitive_methods_are_updated_on_update_settings() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_execution_config(Config {
+            latency_sensitive_methods: FlagStatus::Enabled,
+            ..Default::default()
+        })
+        .build();
+    let canister_id = test.create_canister(Cycles::new(1_000_000_000_000_000));
+
+    let args = UpdateSettingsArgs {
+        canister_id: canister_id.get(),
+        settings: CanisterSettingsArgsBuilder::new()
+            .with_latency_sensitive_methods(vec!["place_order".to_string()])
+            .build(),
+        sender_canister_version: None,
+    };
+    test.subnet_message(Method::UpdateSettings, args.encode())
+        .unwrap();
+
+    let canister = test.canister_state(canister_id);
+    assert_eq!(
+        canister.system_state.latency_sensitive_methods,
+        BTreeSet::from(["place_order".to_string()])
+    );
+    let status = test.canister_status(canister_id).unwrap();
+    assert_eq!(
+        status.settings().latency_sensitive_methods(),
+        &["place_order".to_string()]
+    );
+
+    // Latency-sensitive methods are unchanged when not specified.
+    let args = UpdateSettingsArgs {
+        canister_id: canister_id.get(),
+        settings: CanisterSettingsArgsBuilder::new().build(),
+        sender_canister_version: None,
+    };
+    test.subnet_message(Method::UpdateSettings, args.encode())
+        .unwrap();
+
+    let canister = test.canister_state(canister_id);
+    assert_eq!(
+        canister.system_state.latency_sensitive_methods,
+        BTreeSet::from(["place_order".to_string()])
+    );
+}
+
+#[test]
+fn test_latency_sensitive_methods_are_not_set_when_disabled() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_execution_config(Config {
+            latency_sensitive_methods: FlagStatus::Disabled,
+            ..Default::default()
+        })
+        .build();
+
+    let canister_id = test
+        .create_canister_with_settings(
+            Cycles::new(1_000_000_000_000_000),
+            CanisterSettingsArgsBuilder::new()
+                .with_latency_sensitive_methods(vec!["place_order".to_string()])
+                .build(),
+        )
+        .unwrap();
+
+    let canister = test.canister_state(canister_id);
+    assert!(canister.system_state.latency_sensitive_methods.is_empty());
+}
+
+#[test]
+fn test_latency_sensitive_methods_are_not_set_when_too_many() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_execution_config(Config {
+            latency_sensitive_methods: FlagStatus::Enabled,
+            ..Default::default()
+        })
+        .build();
+
+    let methods = (0..MAX_LATENCY_SENSITIVE_METHODS + 1)
+        .map(|i| format!("method_{i}"))
+        .collect::<Vec<_>>();
+
+    let err = test
+        .create_canister_with_settings(
+            Cycles::new(1_000_000_000_000_000),
+            CanisterSettingsArgsBuilder::new()
+                .with_latency_sensitive_methods(methods.clone())
+                .build(),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        UserError::new(
+            ErrorCode::InvalidManagementPayload,
+            format!(
+                "Too many latency-sensitive methods: {} (max: {})",
+                methods.len(),
+                MAX_LATENCY_SENSITIVE_METHODS
+            )
+        )
+    );
+}
+
+#[test]
+fn test_latency_sensitive_methods_are_not_set_duplicate_names() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_execution_config(Config {
+            latency_sensitive_methods: FlagStatus::Enabled,
+            ..Default::default()
+        })
+        .build();
+
+    let err = test
+        .create_canister_with_settings(
+            Cycles::new(1_000_000_000_000_000),
+            CanisterSettingsArgsBuilder::new()
+                .with_latency_sensitive_methods(vec![
+                    "place_order".to_string(),
+                    "place_order".to_string(),
+                ])
+                .build(),
+        )
+        .unwrap_err();
+
+    assert_eq!(
+        err,
+        UserError::new(
+            ErrorCode::InvalidManagementPayload,
+            "Duplicate latency-sensitive methods are not allowed".to_string(),
+        )
+    );
+}
@@ -94,6 +94,8 @@ pub(crate) struct CanisterMgrConfig {
     pub(crate) max_environment_variables: usize,
     pub(crate) max_environment_variable_name_length: usize,
     pub(crate) max_environment_variable_value_length: usize,
+    pub(crate) max_latency_sensitive_methods: usize,
+    pub(crate) max_latency_sensitive_method_name_length: usize,
 }
 
 impl CanisterMgrConfig {
@@ -119,6 +121,8 @@ impl CanisterMgrConfig {
         max_environment_variables: usize,
         max_environment_variable_name_length: usize,
         max_environment_variable_value_length: usize,
+        max_latency_sensitive_methods: usize,
+        max_latency_sensitive_method_name_length: usize,
     ) -> Self {
         Self {
             subnet_memory_capacity,
@@ -141,6 +145,8 @@ impl CanisterMgrConfig {
             max_environment_variables,
             max_environment_variable_name_length,
             max_environment_variable_value_length,
+            max_latency_sensitive_methods,
+            max_latency_sensitive_method_name_length,
         }
     }
 }
@@ -441,6 +447,10 @@ pub(crate) enum CanisterManagerError {
     },
     CanisterSnapshotNotEnoughCycles(CanisterOutOfCyclesError),
     CanisterSnapshotImmutable,
+    CanisterSnapshotHasDependents {
+        canister_id: CanisterId,
+        snapshot_id: SnapshotId,
+    },
     CanisterSnapshotInconsistent {
         message: String,
     },
@@ -478,6 +488,14 @@ pub(crate) enum CanisterManagerError {
         value: String,
         max_value_length: usize,
     },
+    LatencySensitiveMethodsTooMany {
+        max: usize,
+        count: usize,
+    },
+    LatencySensitiveMethodNameTooLong {
+        name: String,
+        max_name_length: usize,
+    },
     CanisterMetadataNoWasmModule {
         canister_id: CanisterId,
     },
@@ -672,6 +690,10 @@ impl AsErrorHelp for CanisterManagerError {
                 suggestion: "Only canister snapshots created by metadata upload can be mutated.".to_string(),
                 doc_link: "".to_string(),
             },
+            CanisterManagerError::CanisterSnapshotHasDependents { .. } => ErrorHelp::UserError {
+                suggestion: "Delete the incremental snapshots taken on top of this snapshot first.".to_string(),
+                doc_link: "".to_string(),
+            },
             CanisterManagerError::LongExecutionAlreadyInProgress { .. } => ErrorHelp::UserError {
                 suggestion: "Try waiting for the long execution to complete.".to_string(),
                 doc_link: doc_ref("long-execution-already-in-progress"),
@@ -729,6 +751,14 @@ impl AsErrorHelp for CanisterManagerError {
                 suggestion: "Shorten the environment variable value to fit within the allowed limit.".to_string(),
                 doc_link: "".to_string(),
             },
+            CanisterManagerError::LatencySensitiveMethodsTooMany { .. } => ErrorHelp::UserError {
+                suggestion: "Try reducing the number of latency-sensitive methods.".to_string(),
+                doc_link: "".to_string(),
+            },
+            CanisterManagerError::LatencySensitiveMethodNameTooLong { .. } => ErrorHelp::UserError {
+                suggestion: "Only declare methods whose names fit within the allowed limit as latency-sensitive.".to_string(),
+                doc_link: "".to_string(),
+            },
             CanisterManagerError::CanisterMetadataNoWasmModule { .. } => ErrorHelp::UserError {
                 suggestion: "If you are a controller of the canister, install a Wasm module containing a metadata section with the given name.".to_string(),
                 doc_link: "canister-metadata-no-wasm-module".to_string(),
@@ -1035,6 +1065,15 @@ impl From<CanisterManagerError> for UserError {
                 ErrorCode::CanisterSnapshotImmutable,
                 "Only canister snapshots created by metadata upload can be mutated.".to_string(),
             ),
+            CanisterSnapshotHasDependents {
+                canister_id,
+                snapshot_id,
+            } => Self::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "The snapshot {snapshot_id} of canister {canister_id} cannot be deleted or modified because incremental snapshots were taken on top of it.{additional_help}",
+                ),
+            ),
             CanisterSnapshotNotController {
                 sender,
                 canister_id,
@@ -1123,6 +1162,19 @@ impl From<CanisterManagerError> for UserError {
                     "Environment variable value \"{value}\" exceeds the maximum allowed length of {max_value_length}."
                 ),
             ),
+            LatencySensitiveMethodsTooMany { max, count } => Self::new(
+                ErrorCode::InvalidManagementPayload,
+                format!("Too many latency-sensitive methods: {count} (max: {max})"),
+            ),
+            LatencySensitiveMethodNameTooLong {
+                name,
+                max_name_length,
+            } => Self::new(
+                ErrorCode::InvalidManagementPayload,
+                format!(
+                    "Latency-sensitive method name \"{name}\" exceeds the maximum allowed length of {max_name_length}."
+                ),
+            ),
             CanisterMetadataNoWasmModule { canister_id } => Self::new(
                 ErrorCode::CanisterRejectedMessage,
                 format!(
@@ -1170,6 +1222,18 @@ impl From<CanisterSnapshotError> for CanisterManagerError {
             CanisterSnapshotError::InvalidMetadata { reason } => {
                 CanisterManagerError::InvalidSettings { message: reason }
             }
+            CanisterSnapshotError::SnapshotNotFound(snapshot_id) => {
+                CanisterManagerError::CanisterSnapshotNotFound {
+                    canister_id: snapshot_id.get_canister_id(),
+                    snapshot_id,
+                }
+            }
+            CanisterSnapshotError::NotLoadable(snapshot_id) => {
+                CanisterManagerError::CanisterSnapshotNotLoadable {
+                    canister_id: snapshot_id.get_canister_id(),
+                    snapshot_id,
+                }
+            }
         }
     }
 }
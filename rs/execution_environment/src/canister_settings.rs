@@ -5,7 +5,7 @@ use ic_types::{
     ComputeAllocation, Cycles, InvalidComputeAllocationError, MemoryAllocation, PrincipalId,
 };
 use num_traits::cast::ToPrimitive;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryFrom;
 
 #[cfg(test)]
@@ -28,6 +28,7 @@ pub(crate) struct CanisterSettings {
     pub(crate) log_memory_limit: Option<NumBytes>,
     pub(crate) wasm_memory_limit: Option<NumBytes>,
     pub(crate) environment_variables: Option<EnvironmentVariables>,
+    pub(crate) latency_sensitive_methods: Option<BTreeSet<String>>,
 }
 
 impl CanisterSettings {
@@ -42,6 +43,7 @@ impl CanisterSettings {
         log_memory_limit: Option<NumBytes>,
         wasm_memory_limit: Option<NumBytes>,
         environment_variables: Option<EnvironmentVariables>,
+        latency_sensitive_methods: Option<BTreeSet<String>>,
     ) -> Self {
         Self {
             controllers,
@@ -54,6 +56,7 @@ impl CanisterSettings {
             log_memory_limit,
             wasm_memory_limit,
             environment_variables,
+            latency_sensitive_methods,
         }
     }
 
@@ -96,6 +99,10 @@ impl CanisterSettings {
     pub fn environment_variables(&self) -> Option<&EnvironmentVariables> {
         self.environment_variables.as_ref()
     }
+
+    pub fn latency_sensitive_methods(&self) -> Option<&BTreeSet<String>> {
+        self.latency_sensitive_methods.as_ref()
+    }
 }
 
 impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
@@ -181,6 +188,18 @@ impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
             None => None,
         };
 
+        let latency_sensitive_methods = match input.latency_sensitive_methods {
+            Some(methods) => {
+                let original_length = methods.len();
+                let latency_sensitive_methods = methods.into_iter().collect::<BTreeSet<String>>();
+                if latency_sensitive_methods.len() != original_length {
+                    return Err(UpdateSettingsError::DuplicateLatencySensitiveMethods);
+                }
+                Some(latency_sensitive_methods)
+            }
+            None => None,
+        };
+
         Ok(CanisterSettings::new(
             input
                 .controllers
@@ -194,6 +213,7 @@ impl TryFrom<CanisterSettingsArgs> for CanisterSettings {
             log_memory_limit,
             wasm_memory_limit,
             environment_variables,
+            latency_sensitive_methods,
         ))
     }
 }
@@ -220,6 +240,7 @@ pub(crate) struct CanisterSettingsBuilder {
     log_memory_limit: Option<NumBytes>,
     wasm_memory_limit: Option<NumBytes>,
     environment_variables: Option<EnvironmentVariables>,
+    latency_sensitive_methods: Option<BTreeSet<String>>,
 }
 
 #[allow(dead_code)]
@@ -236,6 +257,7 @@ impl CanisterSettingsBuilder {
             log_memory_limit: None,
             wasm_memory_limit: None,
             environment_variables: None,
+            latency_sensitive_methods: None,
         }
     }
 
@@ -251,6 +273,7 @@ impl CanisterSettingsBuilder {
             log_memory_limit: self.log_memory_limit,
             wasm_memory_limit: self.wasm_memory_limit,
             environment_variables: self.environment_variables,
+            latency_sensitive_methods: self.latency_sensitive_methods,
         }
     }
 
@@ -323,6 +346,16 @@ impl CanisterSettingsBuilder {
             ..self
         }
     }
+
+    pub fn with_latency_sensitive_methods(
+        self,
+        latency_sensitive_methods: BTreeSet<String>,
+    ) -> Self {
+        Self {
+            latency_sensitive_methods: Some(latency_sensitive_methods),
+            ..self
+        }
+    }
 }
 
 pub enum UpdateSettingsError {
@@ -333,6 +366,7 @@ pub enum UpdateSettingsError {
     WasmMemoryLimitOutOfRange { provided: candid::Nat },
     WasmMemoryThresholdOutOfRange { provided: candid::Nat },
     DuplicateEnvironmentVariables,
+    DuplicateLatencySensitiveMethods,
     LogMemoryLimitOutOfRange { provided: candid::Nat },
 }
 
@@ -380,6 +414,10 @@ impl From<UpdateSettingsError> for UserError {
                 ErrorCode::InvalidManagementPayload,
                 "Duplicate environment variables are not allowed".to_string(),
             ),
+            UpdateSettingsError::DuplicateLatencySensitiveMethods => UserError::new(
+                ErrorCode::InvalidManagementPayload,
+                "Duplicate latency-sensitive methods are not allowed".to_string(),
+            ),
             UpdateSettingsError::LogMemoryLimitOutOfRange { provided } => UserError::new(
                 ErrorCode::CanisterContractViolation,
                 format!(
@@ -408,6 +446,7 @@ pub(crate) struct ValidatedCanisterSettings {
     log_memory_limit: Option<NumBytes>,
     wasm_memory_limit: Option<NumBytes>,
     environment_variables: Option<EnvironmentVariables>,
+    latency_sensitive_methods: Option<BTreeSet<String>>,
 }
 
 impl ValidatedCanisterSettings {
@@ -423,6 +462,7 @@ impl ValidatedCanisterSettings {
         log_memory_limit: Option<NumBytes>,
         wasm_memory_limit: Option<NumBytes>,
         environment_variables: Option<EnvironmentVariables>,
+        latency_sensitive_methods: Option<BTreeSet<String>>,
     ) -> Self {
         Self {
             controllers,
@@ -436,6 +476,7 @@ impl ValidatedCanisterSettings {
             log_memory_limit,
             wasm_memory_limit,
             environment_variables,
+            latency_sensitive_methods,
         }
     }
 
@@ -482,4 +523,8 @@ impl ValidatedCanisterSettings {
     pub fn environment_variables(&self) -> Option<&EnvironmentVariables> {
         self.environment_variables.as_ref()
     }
+
+    pub fn latency_sensitive_methods(&self) -> Option<&BTreeSet<String>> {
+        self.latency_sensitive_methods.as_ref()
+    }
 }
@@ -36,15 +36,16 @@ use ic_interfaces::execution_environment::{
 use ic_limits::{LOG_CANISTER_OPERATION_CYCLES_THRESHOLD, SMALL_APP_SUBNET_MAX_SIZE};
 use ic_logger::{ReplicaLogger, error, info, warn};
 use ic_management_canister_types_private::{
-    CanisterChangeOrigin, CanisterHttpRequestArgs, CanisterIdRecord, CanisterInfoRequest,
-    CanisterInfoResponse, CanisterMetadataRequest, CanisterStatusType, ClearChunkStoreArgs,
-    CreateCanisterArgs, DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs, ECDSAPublicKeyResponse,
-    EmptyBlob, FetchCanisterLogsRequest, IC_00, InstallChunkedCodeArgs, InstallCodeArgsV2,
-    ListCanisterSnapshotArgs, LoadCanisterSnapshotArgs, MasterPublicKeyId, Method as Ic00Method,
-    NodeMetricsHistoryArgs, Payload as Ic00Payload, ProvisionalCreateCanisterWithCyclesArgs,
-    ProvisionalTopUpCanisterArgs, ReadCanisterSnapshotDataArgs, ReadCanisterSnapshotMetadataArgs,
-    RenameCanisterArgs, ReshareChainKeyArgs, SchnorrAlgorithm, SchnorrPublicKeyArgs,
-    SchnorrPublicKeyResponse, SetupInitialDKGArgs, SignWithECDSAArgs, SignWithSchnorrArgs,
+    BlsKeyId, BlsPublicKeyArgs, CanisterChangeOrigin, CanisterHttpRequestArgs, CanisterIdRecord,
+    CanisterInfoRequest, CanisterInfoResponse, CanisterMetadataRequest, CanisterStatusType,
+    ClearChunkStoreArgs, CreateCanisterArgs, DeleteCanisterSnapshotArgs, ECDSAPublicKeyArgs,
+    ECDSAPublicKeyResponse, EmptyBlob, FetchCanisterLogsRequest, IC_00, InstallChunkedCodeArgs,
+    InstallCodeArgsV2, ListCanisterSnapshotArgs, LoadCanisterSnapshotArgs, MasterPublicKeyId,
+    Method as Ic00Method, NodeMetricsHistoryArgs, Payload as Ic00Payload,
+    ProvisionalCreateCanisterWithCyclesArgs, ProvisionalTopUpCanisterArgs,
+    ReadCanisterSnapshotDataArgs, ReadCanisterSnapshotMetadataArgs, RenameCanisterArgs,
+    ReshareChainKeyArgs, SchnorrAlgorithm, SchnorrPublicKeyArgs, SchnorrPublicKeyResponse,
+    SetupInitialDKGArgs, SignWithBlsArgs, SignWithECDSAArgs, SignWithSchnorrArgs,
     SignWithSchnorrAux, StoredChunksArgs, SubnetInfoArgs, SubnetInfoResponse,
     TakeCanisterSnapshotArgs, UninstallCodeArgs, UpdateSettingsArgs,
     UploadCanisterSnapshotDataArgs, UploadCanisterSnapshotMetadataArgs,
@@ -61,7 +62,7 @@ use ic_replicated_state::{
         system_state::{CyclesUseCase, PausedExecutionId},
     },
     metadata_state::subnet_call_context_manager::{
-        EcdsaArguments, InstallCodeCall, InstallCodeCallId, ReshareChainKeyContext,
+        BlsArguments, EcdsaArguments, InstallCodeCall, InstallCodeCallId, ReshareChainKeyContext,
         SchnorrArguments, SetupInitialDkgContext, SignWithThresholdContext, StopCanisterCall,
         SubnetCallContext, ThresholdArguments, VetKdArguments,
     },
@@ -1335,6 +1336,108 @@ impl ExecutionEnvironment {
                 }
             },
 
+            Ok(Ic00Method::BlsPublicKey) => match &msg {
+                CanisterCall::Request(request) => {
+                    if payload.is_empty() {
+                        use ic_types::messages;
+                        state.push_subnet_output_response(
+                            Response {
+                                originator: request.sender,
+                                respondent: CanisterId::from(self.own_subnet_id),
+                                originator_reply_callback: request.sender_reply_callback,
+                                refund: request.payment,
+                                response_payload: messages::Payload::Reject(
+                                    messages::RejectContext::new(
+                                        ic_error_types::RejectCode::CanisterReject,
+                                        "Message payload empty",
+                                    ),
+                                ),
+                                deadline: request.deadline,
+                            }
+                            .into(),
+                        );
+                        return (state, Some(NumInstructions::from(0)));
+                    }
+
+                    match self.bls_public_key(
+                        request,
+                        payload,
+                        chain_key_data,
+                        &mut state,
+                        rng,
+                        registry_settings,
+                        current_round,
+                    ) {
+                        Err(err) => ExecuteSubnetMessageResult::Finished {
+                            response: Err(err),
+                            refund: msg.take_cycles(),
+                        },
+                        Ok(()) => {
+                            self.metrics.observe_message_with_label(
+                                &request.method_name,
+                                since.elapsed().as_secs_f64(),
+                                SUBMITTED_OUTCOME_LABEL.into(),
+                                SUCCESS_STATUS_LABEL.into(),
+                            );
+                            ExecuteSubnetMessageResult::Processing
+                        }
+                    }
+                }
+                CanisterCall::Ingress(_) => {
+                    self.reject_unexpected_ingress(Ic00Method::BlsPublicKey)
+                }
+            },
+
+            Ok(Ic00Method::SignWithBls) => match &msg {
+                CanisterCall::Request(request) => {
+                    if payload.is_empty() {
+                        use ic_types::messages;
+                        state.push_subnet_output_response(
+                            Response {
+                                originator: request.sender,
+                                respondent: CanisterId::from(self.own_subnet_id),
+                                originator_reply_callback: request.sender_reply_callback,
+                                refund: request.payment,
+                                response_payload: messages::Payload::Reject(
+                                    messages::RejectContext::new(
+                                        ic_error_types::RejectCode::CanisterReject,
+                                        "Message payload empty",
+                                    ),
+                                ),
+                                deadline: request.deadline,
+                            }
+                            .into(),
+                        );
+                        return (state, Some(NumInstructions::from(0)));
+                    }
+
+                    match self.sign_with_bls(
+                        request,
+                        payload,
+                        chain_key_data,
+                        &mut state,
+                        rng,
+                        registry_settings,
+                        current_round,
+                    ) {
+                        Err(err) => ExecuteSubnetMessageResult::Finished {
+                            response: Err(err),
+                            refund: msg.take_cycles(),
+                        },
+                        Ok(()) => {
+                            self.metrics.observe_message_with_label(
+                                &request.method_name,
+                                since.elapsed().as_secs_f64(),
+                                SUBMITTED_OUTCOME_LABEL.into(),
+                                SUCCESS_STATUS_LABEL.into(),
+                            );
+                            ExecuteSubnetMessageResult::Processing
+                        }
+                    }
+                }
+                CanisterCall::Ingress(_) => self.reject_unexpected_ingress(Ic00Method::SignWithBls),
+            },
+
             Ok(Ic00Method::ProvisionalCreateCanisterWithCycles) => {
                 let res =
                     ProvisionalCreateCanisterWithCyclesArgs::decode(payload).and_then(|args| {
@@ -2459,6 +2562,18 @@ impl ExecutionEnvironment {
         round_limits: &mut RoundLimits,
     ) -> (Result<Vec<u8>, UserError>, NumInstructions) {
         let canister_id = args.get_canister_id();
+        let parent_snapshot = args.parent_snapshot();
+        if parent_snapshot.is_some()
+            && self.config.incremental_canister_snapshots == FlagStatus::Disabled
+        {
+            return (
+                Err(UserError::new(
+                    ErrorCode::InvalidManagementPayload,
+                    "Incremental canister snapshots are not enabled.".to_string(),
+                )),
+                NumInstructions::new(0),
+            );
+        }
         // Take canister out.
         let mut canister = match state.take_canister_state(&canister_id) {
             None => {
@@ -2481,6 +2596,7 @@ impl ExecutionEnvironment {
             sender,
             &mut canister,
             replace_snapshot,
+            parent_snapshot,
             state,
             round_limits,
             &resource_saturation,
@@ -3318,6 +3434,113 @@ impl ExecutionEnvironment {
         )
     }
 
+    /// The BLS public key is in G1 and therefore cannot be derived from the
+    /// subnet's master public key in G2, so it is computed by the subnet like
+    /// a signature.
+    fn bls_public_key(
+        &self,
+        request: &Request,
+        payload: &[u8],
+        chain_key_data: &ChainKeyData,
+        state: &mut ReplicatedState,
+        rng: &mut dyn RngCore,
+        registry_settings: &RegistryExecutionSettings,
+        current_round: ExecutionRound,
+    ) -> Result<(), UserError> {
+        let args = BlsPublicKeyArgs::decode(payload)?;
+        self.request_bls(
+            request,
+            args.key_id,
+            args.canister_id.unwrap_or(request.sender),
+            None,
+            args.derivation_path.into_inner(),
+            chain_key_data,
+            state,
+            rng,
+            registry_settings,
+            current_round,
+        )
+    }
+
+    fn sign_with_bls(
+        &self,
+        request: &Request,
+        payload: &[u8],
+        chain_key_data: &ChainKeyData,
+        state: &mut ReplicatedState,
+        rng: &mut dyn RngCore,
+        registry_settings: &RegistryExecutionSettings,
+        current_round: ExecutionRound,
+    ) -> Result<(), UserError> {
+        let args = SignWithBlsArgs::decode(payload)?;
+        self.request_bls(
+            request,
+            args.key_id,
+            request.sender,
+            Some(args.message),
+            args.derivation_path.into_inner(),
+            chain_key_data,
+            state,
+            rng,
+            registry_settings,
+            current_round,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn request_bls(
+        &self,
+        request: &Request,
+        bls_key_id: BlsKeyId,
+        canister_id: CanisterId,
+        message: Option<Vec<u8>>,
+        derivation_path: Vec<Vec<u8>>,
+        chain_key_data: &ChainKeyData,
+        state: &mut ReplicatedState,
+        rng: &mut dyn RngCore,
+        registry_settings: &RegistryExecutionSettings,
+        current_round: ExecutionRound,
+    ) -> Result<(), UserError> {
+        let key_id = NiDkgMasterPublicKeyId::Bls(bls_key_id.clone());
+        let _master_public_key_exists = get_master_public_key(
+            &chain_key_data.master_public_keys,
+            self.own_subnet_id,
+            &key_id.clone().into(),
+        )?;
+        let Some(ni_dkg_id) = chain_key_data.nidkg_ids.get(&key_id) else {
+            warn!(
+                self.log,
+                "No NiDkgId delivered to answer BLS signature request for key {}.", key_id
+            );
+            return Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Subnet {} does not hold NiDkgTranscript for key {}.",
+                    self.own_subnet_id, key_id
+                ),
+            ));
+        };
+        self.sign_with_threshold(
+            (*request).clone(),
+            ThresholdArguments::Bls(BlsArguments {
+                key_id: bls_key_id,
+                canister_id,
+                message: message.map(Arc::new),
+                ni_dkg_id: ni_dkg_id.clone(),
+                height: Height::new(current_round.get()),
+            }),
+            derivation_path,
+            registry_settings
+                .chain_key_settings
+                .get(&key_id.into())
+                .map(|setting| setting.max_queue_size)
+                .unwrap_or_default(),
+            state,
+            rng,
+            registry_settings.subnet_size,
+        )
+    }
+
     fn calculate_signature_fee(
         &self,
         args: &ThresholdArguments,
@@ -3329,6 +3552,7 @@ impl ExecutionEnvironment {
             ThresholdArguments::Ecdsa(_) => cam.ecdsa_signature_fee(subnet_size, cost_schedule),
             ThresholdArguments::Schnorr(_) => cam.schnorr_signature_fee(subnet_size, cost_schedule),
             ThresholdArguments::VetKd(_) => cam.vetkd_fee(subnet_size, cost_schedule),
+            ThresholdArguments::Bls(_) => cam.bls_signature_fee(subnet_size, cost_schedule),
         }
     }
 
@@ -3389,6 +3613,7 @@ impl ExecutionEnvironment {
                     }
                     ThresholdArguments::Schnorr(_) => CyclesUseCase::SchnorrOutcalls,
                     ThresholdArguments::VetKd(_) => CyclesUseCase::VetKd,
+                    ThresholdArguments::Bls(_) => CyclesUseCase::BlsOutcalls,
                 };
                 state
                     .metadata
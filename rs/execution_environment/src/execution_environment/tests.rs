@@ -3,8 +3,8 @@ use ic_base_types::{NumBytes, NumSeconds};
 use ic_btc_interface::NetworkInRequest;
 use ic_error_types::{ErrorCode, RejectCode, UserError};
 use ic_management_canister_types_private::{
-    self as ic00, BitcoinGetUtxosArgs, BoundedHttpHeaders, CanisterChange, CanisterHttpRequestArgs,
-    CanisterIdRecord, CanisterMetadataRequest, CanisterMetadataResponse,
+    self as ic00, BitcoinGetUtxosArgs, BlsCurve, BlsKeyId, BoundedHttpHeaders, CanisterChange,
+    CanisterHttpRequestArgs, CanisterIdRecord, CanisterMetadataRequest, CanisterMetadataResponse,
     CanisterSettingsArgsBuilder, CanisterStatusResultV2, CanisterStatusType, ClearChunkStoreArgs,
     DerivationPath, EcdsaCurve, EcdsaKeyId, EmptyBlob, FetchCanisterLogsRequest, HttpMethod, IC_00,
     LogVisibilityV2, MasterPublicKeyId, Method, OnLowWasmMemoryHookStatus, Payload as Ic00Payload,
@@ -224,6 +224,12 @@ fn sign_with_threshold_key_payload(method: Method, key_id: MasterPublicKeyId) ->
             key_id: into_inner_vetkd(key_id),
         }
         .encode(),
+        Method::SignWithBls => ic00::SignWithBlsArgs {
+            message: vec![1; 32],
+            derivation_path: DerivationPath::new(vec![]),
+            key_id: into_inner_bls(key_id),
+        }
+        .encode(),
         _ => panic!("unexpected method"),
     }
 }
@@ -1054,6 +1060,7 @@ fn get_canister_status_memory_metrics_snapshots_size() {
         TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         }
         .encode(),
     )
@@ -2906,6 +2913,13 @@ fn make_vetkd_key(name: &str) -> MasterPublicKeyId {
     })
 }
 
+fn make_bls_key(name: &str) -> MasterPublicKeyId {
+    MasterPublicKeyId::Bls(BlsKeyId {
+        curve: BlsCurve::Bls12_381_G2,
+        name: name.to_string(),
+    })
+}
+
 fn into_inner_ecdsa(key_id: MasterPublicKeyId) -> EcdsaKeyId {
     match key_id {
         MasterPublicKeyId::Ecdsa(key) => key,
@@ -2927,6 +2941,13 @@ fn into_inner_vetkd(key_id: MasterPublicKeyId) -> VetKdKeyId {
     }
 }
 
+fn into_inner_bls(key_id: MasterPublicKeyId) -> BlsKeyId {
+    match key_id {
+        MasterPublicKeyId::Bls(key) => key,
+        _ => panic!("unexpected key_id type"),
+    }
+}
+
 #[test]
 fn canister_output_queue_does_not_overflow_when_calling_ic00() {
     let own_subnet = subnet_test_id(1);
@@ -4290,6 +4311,65 @@ fn test_vetkd_derive_key_api_is_enabled() {
     );
 }
 
+#[test]
+fn test_sign_with_bls_api_is_enabled() {
+    // Arrange.
+    let key_id = make_bls_key("some_key");
+    let own_subnet = subnet_test_id(1);
+    let nns_subnet = subnet_test_id(2);
+    let nns_canister = canister_test_id(0x10);
+    let mut test = ExecutionTestBuilder::new()
+        .with_own_subnet_id(own_subnet)
+        .with_nns_subnet_id(nns_subnet)
+        .with_caller(nns_subnet, nns_canister)
+        .with_chain_key(key_id.clone())
+        .build();
+    let canister_id = test.universal_canister().unwrap();
+    // Check that the SubnetCallContextManager is empty.
+    assert_eq!(
+        test.state()
+            .metadata
+            .subnet_call_context_manager
+            .sign_with_threshold_contexts_count(&key_id),
+        0
+    );
+
+    // Act.
+    let method = Method::SignWithBls;
+    let run = wasm()
+        .call_with_cycles(
+            ic00::IC_00,
+            method,
+            call_args()
+                .other_side(sign_with_threshold_key_payload(method, key_id.clone()))
+                .on_reject(wasm().reject_message().reject()),
+            Cycles::from(100_000_000_000u128),
+        )
+        .build();
+    let (_, ingress_status) = test.ingress_raw(canister_id, "update", run);
+
+    // Assert.
+    // Check that the request is accepted and processing.
+    assert_eq!(
+        ingress_status,
+        IngressStatus::Known {
+            receiver: canister_id.get(),
+            user_id: test.user_id(),
+            time: test.time(),
+            state: IngressState::Processing,
+        }
+    );
+    // Check that the SubnetCallContextManager contains the request.
+    assert_eq!(
+        test.state()
+            .metadata
+            .subnet_call_context_manager
+            .sign_with_bls_contexts()
+            .len(),
+        1
+    );
+}
+
 #[test]
 fn reshare_chain_key_api_is_disabled() {
     let own_subnet = subnet_test_id(1);
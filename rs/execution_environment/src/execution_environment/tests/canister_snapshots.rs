@@ -1,6 +1,6 @@
 use assert_matches::assert_matches;
 use candid::{Decode, Encode, Reserved};
-use ic_base_types::NumBytes;
+use ic_base_types::{NumBytes, NumSeconds};
 use ic_config::subnet_config::SubnetConfig;
 use ic_error_types::{ErrorCode, RejectCode, UserError};
 use ic_management_canister_types_private::{
@@ -1547,6 +1547,221 @@ fn helper_delete_snapshot(
         .unwrap();
 }
 
+fn helper_take_incremental_snapshot(
+    test: &mut ExecutionTest,
+    canister_id: CanisterId,
+    parent_snapshot_id: SnapshotId,
+) -> SnapshotId {
+    let args: TakeCanisterSnapshotArgs =
+        TakeCanisterSnapshotArgs::new(canister_id, None).with_parent_snapshot(parent_snapshot_id);
+    let result = test.subnet_message("take_canister_snapshot", args.encode());
+    let response = CanisterSnapshotResponse::decode(&result.unwrap().bytes()).unwrap();
+    response.snapshot_id()
+}
+
+fn write_stable_memory(test: &mut ExecutionTest, canister_id: CanisterId, data: &[u8]) {
+    let result = test
+        .ingress(
+            canister_id,
+            "update",
+            wasm().stable_write(0, data).reply().build(),
+        )
+        .unwrap();
+    assert_eq!(result, WasmResult::Reply(vec![]));
+}
+
+fn read_stable_memory(test: &mut ExecutionTest, canister_id: CanisterId, size: u32) -> Vec<u8> {
+    let result = test
+        .ingress(
+            canister_id,
+            "update",
+            wasm().stable_read(0, size).append_and_reply().build(),
+        )
+        .unwrap();
+    match result {
+        WasmResult::Reply(bytes) => bytes,
+        WasmResult::Reject(msg) => panic!("Unexpected reject: {msg}"),
+    }
+}
+
+#[test]
+fn take_incremental_canister_snapshot_fails_when_disabled() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister_id = test
+        .canister_from_cycles_and_binary(
+            Cycles::new(1_000_000_000_000),
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+        )
+        .unwrap();
+    let (snapshot_id, _) = helper_take_snapshot(&mut test, canister_id);
+
+    let args: TakeCanisterSnapshotArgs =
+        TakeCanisterSnapshotArgs::new(canister_id, None).with_parent_snapshot(snapshot_id);
+    let error = test
+        .subnet_message("take_canister_snapshot", args.encode())
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InvalidManagementPayload);
+    assert_eq!(
+        test.state()
+            .canister_snapshots
+            .list_snapshots(canister_id)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn take_incremental_canister_snapshot_only_stores_changed_pages() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_incremental_canister_snapshots()
+        .build();
+    let canister_id = test
+        .canister_from_cycles_and_binary(
+            Cycles::new(1_000_000_000_000),
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+        )
+        .unwrap();
+    grow_stable_memory(&mut test, canister_id, WASM_PAGE_SIZE_IN_BYTES as u64, 10);
+
+    let (parent_snapshot_id, _) = helper_take_snapshot(&mut test, canister_id);
+    write_stable_memory(&mut test, canister_id, &[1, 2, 3]);
+    let snapshot_id = helper_take_incremental_snapshot(&mut test, canister_id, parent_snapshot_id);
+
+    let parent_snapshot = test
+        .state()
+        .canister_snapshots
+        .get(parent_snapshot_id)
+        .unwrap();
+    let snapshot = test.state().canister_snapshots.get(snapshot_id).unwrap();
+    let parent = snapshot.parent().unwrap();
+    assert_eq!(parent.snapshot_id, parent_snapshot_id);
+    assert!(!parent.stable_memory_pages.is_empty());
+    assert!(snapshot.size() < parent_snapshot.size());
+    assert!(
+        test.state()
+            .canister_snapshots
+            .has_dependents(parent_snapshot_id)
+    );
+
+    let unflushed_changes = test.state_mut().metadata.unflushed_checkpoint_ops.take();
+    assert_eq!(
+        unflushed_changes,
+        vec![
+            UnflushedCheckpointOp::TakeSnapshot(canister_id, parent_snapshot_id),
+            UnflushedCheckpointOp::TakeIncrementalSnapshot(canister_id, snapshot_id),
+        ]
+    );
+}
+
+#[test]
+fn load_incremental_canister_snapshot_restores_memory() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_incremental_canister_snapshots()
+        .build();
+    let canister_id = test
+        .canister_from_cycles_and_binary(
+            Cycles::new(1_000_000_000_000),
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+        )
+        .unwrap();
+    grow_stable_memory(&mut test, canister_id, WASM_PAGE_SIZE_IN_BYTES as u64, 1);
+
+    write_stable_memory(&mut test, canister_id, &[1, 1, 1]);
+    let (parent_snapshot_id, _) = helper_take_snapshot(&mut test, canister_id);
+    write_stable_memory(&mut test, canister_id, &[2, 2, 2]);
+    let snapshot_id = helper_take_incremental_snapshot(&mut test, canister_id, parent_snapshot_id);
+    write_stable_memory(&mut test, canister_id, &[3, 3, 3]);
+
+    helper_load_snapshot(&mut test, canister_id, snapshot_id);
+    assert_eq!(read_stable_memory(&mut test, canister_id, 3), vec![2, 2, 2]);
+
+    helper_load_snapshot(&mut test, canister_id, parent_snapshot_id);
+    assert_eq!(read_stable_memory(&mut test, canister_id, 3), vec![1, 1, 1]);
+
+    let unflushed_changes = test.state_mut().metadata.unflushed_checkpoint_ops.take();
+    assert_eq!(
+        unflushed_changes,
+        vec![
+            UnflushedCheckpointOp::TakeSnapshot(canister_id, parent_snapshot_id),
+            UnflushedCheckpointOp::TakeIncrementalSnapshot(canister_id, snapshot_id),
+            UnflushedCheckpointOp::LoadIncrementalSnapshot(
+                canister_id,
+                snapshot_id,
+                parent_snapshot_id
+            ),
+            UnflushedCheckpointOp::LoadSnapshot(canister_id, parent_snapshot_id),
+        ]
+    );
+}
+
+#[test]
+fn delete_parent_of_incremental_canister_snapshot_fails() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_incremental_canister_snapshots()
+        .build();
+    let canister_id = test
+        .canister_from_cycles_and_binary(
+            Cycles::new(1_000_000_000_000),
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+        )
+        .unwrap();
+    let (parent_snapshot_id, _) = helper_take_snapshot(&mut test, canister_id);
+    let snapshot_id = helper_take_incremental_snapshot(&mut test, canister_id, parent_snapshot_id);
+
+    // The parent cannot be deleted or replaced while the incremental snapshot exists.
+    let args = DeleteCanisterSnapshotArgs::new(canister_id, parent_snapshot_id);
+    let error = test
+        .subnet_message("delete_canister_snapshot", args.encode())
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::CanisterRejectedMessage);
+    let args = TakeCanisterSnapshotArgs::new(canister_id, Some(parent_snapshot_id));
+    let error = test
+        .subnet_message("take_canister_snapshot", args.encode())
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::CanisterRejectedMessage);
+
+    // Deleting the incremental snapshot first makes the parent deletable.
+    helper_delete_snapshot(&mut test, canister_id, snapshot_id);
+    helper_delete_snapshot(&mut test, canister_id, parent_snapshot_id);
+    assert!(
+        test.state()
+            .canister_snapshots
+            .list_snapshots(canister_id)
+            .is_empty()
+    );
+}
+
+#[test]
+fn take_incremental_canister_snapshot_of_frozen_canister_fails() {
+    let mut test = ExecutionTestBuilder::new()
+        .with_incremental_canister_snapshots()
+        .build();
+    let canister_id = test
+        .canister_from_cycles_and_binary(
+            Cycles::new(1_000_000_000_000),
+            UNIVERSAL_CANISTER_WASM.to_vec(),
+        )
+        .unwrap();
+    let (parent_snapshot_id, _) = helper_take_snapshot(&mut test, canister_id);
+    test.update_freezing_threshold(canister_id, NumSeconds::new(100_000_000_000_000))
+        .unwrap();
+
+    // The canister is rejected before its memories are compared against the parent.
+    let args: TakeCanisterSnapshotArgs =
+        TakeCanisterSnapshotArgs::new(canister_id, None).with_parent_snapshot(parent_snapshot_id);
+    let error = test
+        .subnet_message("take_canister_snapshot", args.encode())
+        .unwrap_err();
+    assert_eq!(error.code(), ErrorCode::InsufficientCyclesInMemoryGrow);
+    assert_eq!(
+        test.state()
+            .canister_snapshots
+            .list_snapshots(canister_id)
+            .len(),
+        1
+    );
+}
+
 #[test]
 fn take_and_delete_canister_snapshot_updates_hook_condition() {
     const CYCLES: Cycles = Cycles::new(1_000_000_000_000);
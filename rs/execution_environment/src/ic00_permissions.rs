@@ -37,6 +37,8 @@ impl Ic00MethodPermissions {
             | Ic00Method::SignWithSchnorr
             | Ic00Method::VetKdPublicKey
             | Ic00Method::VetKdDeriveKey
+            | Ic00Method::BlsPublicKey
+            | Ic00Method::SignWithBls
             | Ic00Method::BitcoinGetBalance
             | Ic00Method::BitcoinGetUtxos
             | Ic00Method::BitcoinGetBlockHeaders
@@ -28,7 +28,8 @@ use ic_config::{execution_environment::Config, subnet_config::SubnetConfig};
 use ic_cycles_account_manager::CyclesAccountManager;
 use ic_embedders::wasm_executor::WasmExecutor;
 use ic_interfaces::execution_environment::{
-    IngressFilterService, IngressHistoryReader, QueryExecutionService, Scheduler,
+    DryRunExecutionService, IngressFilterService, IngressHistoryReader, QueryExecutionService,
+    Scheduler,
 };
 use ic_interfaces_state_manager::StateReader;
 use ic_logger::ReplicaLogger;
@@ -43,7 +44,7 @@ use ic_types::{
 };
 pub use metrics::IngressFilterMetrics;
 pub use query_handler::InternalHttpQueryHandler;
-use query_handler::{HttpQueryHandler, QueryScheduler};
+use query_handler::{HttpDryRunHandler, HttpQueryHandler, QueryScheduler};
 pub use scheduler::RoundSchedule;
 use scheduler::SchedulerImpl;
 use std::{path::Path, sync::Arc};
@@ -86,6 +87,7 @@ pub struct ExecutionServices {
     pub ingress_history_writer: Arc<IngressHistoryWriterImpl>,
     pub ingress_history_reader: Box<dyn IngressHistoryReader>,
     pub query_execution_service: QueryExecutionService,
+    pub dry_run_execution_service: DryRunExecutionService,
     pub https_outcalls_service: QueryExecutionService,
     pub scheduler: Box<dyn Scheduler<State = ReplicatedState>>,
     pub query_stats_payload_builder: QueryStatsPayloadBuilderParams,
@@ -144,6 +146,12 @@ impl ExecutionServices {
             "regular",
             true,
         );
+        let dry_run_execution_service = HttpDryRunHandler::new_service(
+            Arc::clone(&sync_query_handler) as Arc<_>,
+            query_scheduler.clone(),
+            Arc::clone(&state_reader),
+            true,
+        );
         let https_outcalls_service = HttpQueryHandler::new_service(
             Arc::clone(&sync_query_handler) as Arc<_>,
             query_scheduler.clone(),
@@ -172,6 +180,7 @@ impl ExecutionServices {
             ingress_history_writer,
             ingress_history_reader,
             query_execution_service,
+            dry_run_execution_service,
             https_outcalls_service,
             scheduler,
             query_stats_payload_builder,
@@ -360,6 +369,8 @@ fn setup_execution_helper(
         config.max_environment_variables,
         config.max_environment_variable_name_length,
         config.max_environment_variable_value_length,
+        config.max_latency_sensitive_methods,
+        config.max_latency_sensitive_method_name_length,
     );
     let canister_manager = Arc::new(CanisterManager::new(
         Arc::clone(&hypervisor),
@@ -369,6 +380,7 @@ fn setup_execution_helper(
         Arc::clone(&ingress_history_writer) as Arc<_>,
         Arc::clone(&fd_factory),
         config.environment_variables,
+        config.latency_sensitive_methods,
     ));
 
     let exec_env = Arc::new(ExecutionEnvironment::new(
@@ -178,6 +178,7 @@ pub(crate) struct QueryHandlerMetrics {
     pub query: ScopedMetrics,
     pub query_initial_call: ScopedMetrics,
     pub query_spawned_calls: ScopedMetrics,
+    pub query_dry_run: ScopedMetrics,
     pub query_critical_error: IntCounter,
     /// The total number of tracked System API calls invoked during the query execution.
     pub query_system_api_calls: IntCounterVec,
@@ -266,6 +267,28 @@ impl QueryHandlerMetrics {
                     metrics_registry,
                 ),
             },
+            query_dry_run: ScopedMetrics {
+                duration: duration_histogram(
+                    "execution_query_dry_run_duration_seconds",
+                    "The duration of dry runs of update calls",
+                    metrics_registry,
+                ),
+                instructions: instructions_histogram(
+                    "execution_query_dry_run_instructions",
+                    "The number of instructions executed in dry runs of update calls",
+                    metrics_registry,
+                ),
+                slices: slices_histogram(
+                    "execution_query_dry_run_slices",
+                    "The number of slices executed in dry runs of update calls",
+                    metrics_registry,
+                ),
+                messages: messages_histogram(
+                    "execution_query_dry_run_messages",
+                    "The number of messages executed in dry runs of update calls",
+                    metrics_registry,
+                ),
+            },
             query_critical_error: metrics_registry.error_counter(QUERY_HANDLER_CRITICAL_ERROR),
             query_system_api_calls: metrics_registry.int_counter_vec(
                 "execution_query_system_api_calls_total",
@@ -22,6 +22,7 @@ use ic_crypto_tree_hash::{Label, LabeledTree, LabeledTree::SubTree, flatmap};
 use ic_cycles_account_manager::CyclesAccountManager;
 use ic_error_types::{ErrorCode, UserError};
 use ic_interfaces::execution_environment::{
+    DryRunExecutionResponse, DryRunExecutionService, DryRunReport, QueryContinuationToken,
     QueryExecutionError, QueryExecutionInput, QueryExecutionResponse, QueryExecutionService,
 };
 use ic_interfaces_state_manager::{Labeled, StateReader};
@@ -33,6 +34,7 @@ use ic_replicated_state::ReplicatedState;
 use ic_types::QueryStatsEpoch;
 use ic_types::batch::QueryStats;
 use ic_types::messages::CertificateDelegationMetadata;
+use ic_types::state_manager::StateManagerError;
 use ic_types::{
     CanisterId, NumInstructions,
     ingress::WasmResult,
@@ -96,6 +98,35 @@ fn get_latest_certified_state_and_data_certificate(
         })
 }
 
+/// Returns the state a `QueryContinuationToken` pins queries to `canister_id` to.
+fn get_pinned_state(
+    state_reader: &dyn StateReader<State = ReplicatedState>,
+    token: QueryContinuationToken,
+    canister_id: CanisterId,
+) -> Result<Labeled<Arc<ReplicatedState>>, QueryExecutionError> {
+    if token.canister_id != canister_id {
+        return Err(QueryExecutionError::ContinuationTokenCanisterMismatch {
+            token_canister_id: token.canister_id,
+            canister_id,
+        });
+    }
+    // Tokens are provided by users, so the height must be checked to not
+    // execute queries against uncertified states.
+    if token.height > state_reader.latest_certified_height() {
+        return Err(QueryExecutionError::PinnedStateNotCertified(token.height));
+    }
+    state_reader
+        .get_state_at(token.height)
+        .map_err(|err| match err {
+            StateManagerError::StateNotCommittedYet(height) => {
+                QueryExecutionError::PinnedStateNotCertified(height)
+            }
+            StateManagerError::StateRemoved(height) => {
+                QueryExecutionError::PinnedStateRemoved(height)
+            }
+        })
+}
+
 fn label<T: Into<Label>>(t: T) -> Label {
     t.into()
 }
@@ -174,6 +205,38 @@ impl InternalHttpQueryHandler {
         data_certificate: Vec<u8>,
         certificate_delegation_metadata: Option<CertificateDelegationMetadata>,
         enable_query_stats_tracking: bool,
+    ) -> Result<WasmResult, UserError> {
+        self.execute_query(
+            query,
+            state,
+            Some(data_certificate),
+            certificate_delegation_metadata,
+            enable_query_stats_tracking,
+        )
+    }
+
+    /// Handle a query of type `Query` against a state pinned by a
+    /// `QueryContinuationToken`.
+    ///
+    /// The certification of the pinned state is not retained, so the query is
+    /// executed without a data certificate. The query cache only holds results
+    /// for the latest certified state and is bypassed.
+    pub fn query_at_pinned_state(
+        &self,
+        query: Query,
+        state: Labeled<Arc<ReplicatedState>>,
+        enable_query_stats_tracking: bool,
+    ) -> Result<WasmResult, UserError> {
+        self.execute_query(query, state, None, None, enable_query_stats_tracking)
+    }
+
+    fn execute_query(
+        &self,
+        query: Query,
+        state: Labeled<Arc<ReplicatedState>>,
+        data_certificate: Option<Vec<u8>>,
+        certificate_delegation_metadata: Option<CertificateDelegationMetadata>,
+        enable_query_stats_tracking: bool,
     ) -> Result<WasmResult, UserError> {
         let measurement_scope = MeasurementScope::root(&self.metrics.query);
 
@@ -248,23 +311,95 @@ impl InternalHttpQueryHandler {
             None
         };
 
-        // Check the query cache first (if the query caching is enabled).
+        // Check the query cache first (if the query caching is enabled and the
+        // query is executed against the latest certified state).
         // If a valid cache entry found, the result will be immediately returned.
         // Otherwise, the key will be kept for the `push` below.
-        let cache_entry_key = if self.config.query_caching == FlagStatus::Enabled {
-            let key = query_cache::EntryKey::new(&query, certificate_delegation_metadata);
+        let cache_entry_key =
+            if self.config.query_caching == FlagStatus::Enabled && data_certificate.is_some() {
+                let key = query_cache::EntryKey::new(&query, certificate_delegation_metadata);
+                let state = state.get_ref().as_ref();
+                if let Some(result) =
+                    self.query_cache
+                        .get_valid_result(&key, state, query_stats_collector)
+                {
+                    return result;
+                }
+                Some(key)
+            } else {
+                None
+            };
+
+        let mut context = self.query_context(
+            // For composite queries, the set of evaluated canisters is not known in advance,
+            // so the whole state is needed to capture later the state of the call graph.
+            // The clone should not be expensive, as the state is `Labeled<Arc<ReplicatedState>>`.
+            state.clone(),
+            data_certificate,
+            query.receiver,
+            query_stats_collector,
+        );
+
+        let result = context.run(query, &self.metrics, &measurement_scope);
+        context.accumulate_transient_errors_from_result(result.as_ref());
+        context.observe_metrics(&self.metrics);
+
+        // Add the query execution result to the query cache (if the query caching is enabled).
+        // Query caching is disabled if the key is set to `None`.
+        if let Some(key) = cache_entry_key {
             let state = state.get_ref().as_ref();
-            if let Some(result) =
-                self.query_cache
-                    .get_valid_result(&key, state, query_stats_collector)
-            {
-                return result;
-            }
-            Some(key)
+            let counters = context.system_api_call_counters();
+            let stats = context.evaluated_canister_stats();
+            let errors = context.transient_errors();
+            self.query_cache
+                .push(key, &result, state, counters, stats, errors);
+        }
+        result
+    }
+
+    /// Handles a dry run of the update method called by `query` against the
+    /// latest certified state. Dry runs are accounted for in the query stats
+    /// like queries, but bypass the query cache.
+    pub fn dry_run(
+        &self,
+        query: Query,
+        state: Labeled<Arc<ReplicatedState>>,
+        enable_query_stats_tracking: bool,
+    ) -> Result<DryRunReport, UserError> {
+        let measurement_scope = MeasurementScope::root(&self.metrics.query_dry_run);
+
+        if query.receiver == CanisterId::ic_00() {
+            return Err(UserError::new(
+                ErrorCode::CanisterMethodNotFound,
+                format!(
+                    "Dry runs of management canister method {} are not supported.",
+                    query.method_name
+                ),
+            ));
+        }
+
+        let query_stats_collector = if self.config.query_stats_aggregation == FlagStatus::Enabled
+            && enable_query_stats_tracking
+        {
+            Some(&self.local_query_execution_stats)
         } else {
             None
         };
 
+        let mut context = self.query_context(state, None, query.receiver, query_stats_collector);
+        let result = context.dry_run(query, &measurement_scope);
+        context.accumulate_transient_errors_from_result(result.as_ref());
+        context.observe_metrics(&self.metrics);
+        result
+    }
+
+    fn query_context<'a>(
+        &'a self,
+        state: Labeled<Arc<ReplicatedState>>,
+        data_certificate: Option<Vec<u8>>,
+        canister_id: CanisterId,
+        query_stats_collector: Option<&'a QueryStatsCollector>,
+    ) -> query_context::QueryContext<'a> {
         // Letting the canister grow arbitrarily when executing the
         // query is fine as we do not persist state modifications.
         let subnet_available_memory = full_subnet_memory_capacity(&self.config);
@@ -273,14 +408,11 @@ impl InternalHttpQueryHandler {
         // implicit bound anyway.
         let subnet_available_callbacks = self.config.subnet_callback_soft_limit as i64;
 
-        let mut context = query_context::QueryContext::new(
+        query_context::QueryContext::new(
             &self.log,
             self.hypervisor.as_ref(),
             self.own_subnet_type,
-            // For composite queries, the set of evaluated canisters is not known in advance,
-            // so the whole state is needed to capture later the state of the call graph.
-            // The clone should not be expensive, as the state is `Labeled<Arc<ReplicatedState>>`.
-            state.clone(),
+            state,
             data_certificate,
             subnet_available_memory,
             subnet_available_callbacks,
@@ -291,27 +423,11 @@ impl InternalHttpQueryHandler {
             self.config.max_query_call_walltime,
             self.config.instruction_overhead_per_query_call,
             self.config.composite_queries,
-            query.receiver,
+            canister_id,
             &self.metrics.query_critical_error,
             query_stats_collector,
             Arc::clone(&self.cycles_account_manager),
-        );
-
-        let result = context.run(query, &self.metrics, &measurement_scope);
-        context.accumulate_transient_errors_from_result(result.as_ref());
-        context.observe_metrics(&self.metrics);
-
-        // Add the query execution result to the query cache (if the query caching is enabled).
-        // Query caching is disabled if the key is set to `None`.
-        if let Some(key) = cache_entry_key {
-            let state = state.get_ref().as_ref();
-            let counters = context.system_api_call_counters();
-            let stats = context.evaluated_canister_stats();
-            let errors = context.transient_errors();
-            self.query_cache
-                .push(key, &result, state, counters, stats, errors);
-        }
-        result
+        )
     }
 }
 
@@ -379,6 +495,7 @@ impl Service<QueryExecutionInput> for HttpQueryHandler {
         QueryExecutionInput {
             query,
             certificate_delegation_with_metadata,
+            continuation_token,
         }: QueryExecutionInput,
     ) -> Self::Future {
         let internal = Arc::clone(&self.internal);
@@ -405,35 +522,121 @@ impl Service<QueryExecutionInput> for HttpQueryHandler {
                         None => (None, None),
                     };
 
+                let result = match continuation_token {
+                    Some(token) => get_pinned_state(state_reader.as_ref(), token, query.receiver)
+                        .map(|state| {
+                            let time = state.get_ref().metadata.batch_time;
+                            let response = internal.query_at_pinned_state(
+                                query,
+                                state,
+                                enable_query_stats_tracking,
+                            );
+                            (response, time, token)
+                        }),
+                    None => match get_latest_certified_state_and_data_certificate(
+                        state_reader,
+                        certificate_delegation,
+                        query.receiver,
+                    ) {
+                        Some((state, cert)) => {
+                            let time = state.get_ref().metadata.batch_time;
+
+                            let certified_height_used_for_execution = state.height();
+                            let height_diff = certified_height_used_for_execution
+                                .get()
+                                .saturating_sub(latest_certified_height_pre_schedule.get());
+                            http_query_handler_metrics
+                                .height_diff_during_query_scheduling
+                                .observe(height_diff as f64);
+
+                            let token = QueryContinuationToken {
+                                height: certified_height_used_for_execution,
+                                canister_id: query.receiver,
+                            };
+                            let response = internal.query(
+                                query,
+                                state,
+                                cert,
+                                certificate_delegation_metadata,
+                                enable_query_stats_tracking,
+                            );
+
+                            Ok((response, time, token))
+                        }
+                        None => Err(QueryExecutionError::CertifiedStateUnavailable),
+                    },
+                };
+
+                let _ = tx.send(Ok(result));
+            }
+            start.elapsed()
+        });
+        Box::pin(async move {
+            rx.await
+                .expect("The sender was dropped before sending the message.")
+        })
+    }
+}
+
+#[derive(Clone)]
+/// Struct that is responsible for handling dry runs of update calls sent by
+/// users. Dry runs share the query scheduler with queries.
+pub(crate) struct HttpDryRunHandler {
+    internal: Arc<InternalHttpQueryHandler>,
+    state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+    query_scheduler: QueryScheduler,
+    enable_query_stats_tracking: bool,
+}
+
+impl HttpDryRunHandler {
+    pub(crate) fn new_service(
+        internal: Arc<InternalHttpQueryHandler>,
+        query_scheduler: QueryScheduler,
+        state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
+        enable_query_stats_tracking: bool,
+    ) -> DryRunExecutionService {
+        BoxCloneService::new(Self {
+            internal,
+            state_reader,
+            query_scheduler,
+            enable_query_stats_tracking,
+        })
+    }
+}
+
+impl Service<Query> for HttpDryRunHandler {
+    type Response = DryRunExecutionResponse;
+    type Error = Infallible;
+    #[allow(clippy::type_complexity)]
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, query: Query) -> Self::Future {
+        let internal = Arc::clone(&self.internal);
+        let state_reader = Arc::clone(&self.state_reader);
+        let (tx, rx) = oneshot::channel();
+        let canister_id = query.receiver;
+        let enable_query_stats_tracking = self.enable_query_stats_tracking;
+        self.query_scheduler.push(canister_id, move || {
+            let start = std::time::Instant::now();
+            if !tx.is_closed() {
+                // As for queries, the state must be retrieved right before the
+                // execution to not hold on to older states while queued up.
                 let result = match get_latest_certified_state_and_data_certificate(
                     state_reader,
-                    certificate_delegation,
-                    query.receiver,
+                    None,
+                    canister_id,
                 ) {
-                    Some((state, cert)) => {
+                    Some((state, _cert)) => {
                         let time = state.get_ref().metadata.batch_time;
-
-                        let certified_height_used_for_execution = state.height();
-                        let height_diff = certified_height_used_for_execution
-                            .get()
-                            .saturating_sub(latest_certified_height_pre_schedule.get());
-                        http_query_handler_metrics
-                            .height_diff_during_query_scheduling
-                            .observe(height_diff as f64);
-
-                        let response = internal.query(
-                            query,
-                            state,
-                            cert,
-                            certificate_delegation_metadata,
-                            enable_query_stats_tracking,
-                        );
-
-                        Ok((response, time))
+                        let report = internal.dry_run(query, state, enable_query_stats_tracking);
+                        Ok((report, time))
                     }
                     None => Err(QueryExecutionError::CertifiedStateUnavailable),
                 };
-
                 let _ = tx.send(Ok(result));
             }
             start.elapsed()
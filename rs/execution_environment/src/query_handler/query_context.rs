@@ -1,7 +1,7 @@
 use super::query_call_graph::evaluate_query_call_graph;
 use crate::{
     NonReplicatedQueryKind, RoundInstructions,
-    execution::common::{self, validate_method},
+    execution::common::{self, validate_canister, validate_method},
     execution::nonreplicated_query::execute_non_replicated_query,
     execution_environment::{RoundLimits, as_round_instructions},
     hypervisor::Hypervisor,
@@ -19,8 +19,8 @@ use ic_embedders::wasmtime_embedder::system_api::{
 };
 use ic_error_types::{ErrorCode, RejectCode, UserError};
 use ic_interfaces::execution_environment::{
-    ExecutionMode, HypervisorError, MessageMemoryUsage, SubnetAvailableMemory,
-    SystemApiCallCounters,
+    DryRunOutgoingRequest, DryRunReport, ExecutionMode, HypervisorError, MessageMemoryUsage,
+    SubnetAvailableMemory, SystemApiCallCounters,
 };
 use ic_interfaces_state_manager::Labeled;
 use ic_limits::SMALL_APP_SUBNET_MAX_SIZE;
@@ -36,7 +36,7 @@ use ic_types::{
     ingress::WasmResult,
     messages::{
         CallContextId, CallbackId, NO_DEADLINE, Payload, Query, QuerySource, RejectContext,
-        Request, RequestOrResponse, Response,
+        Request, RequestMetadata, RequestOrResponse, Response,
     },
     methods::{FuncRef, WasmClosure, WasmMethod},
 };
@@ -95,8 +95,9 @@ pub(super) struct QueryContext<'a> {
     // The state against which all queries in the context will be executed.
     state: Labeled<Arc<ReplicatedState>>,
     network_topology: Arc<NetworkTopology>,
-    // Certificate for certified queries + canister ID of the root query of this context
-    data_certificate: (Vec<u8>, CanisterId),
+    // Certificate for certified queries (if available) + canister ID of the root query of
+    // this context
+    data_certificate: (Option<Vec<u8>>, CanisterId),
     max_instructions_per_query: NumInstructions,
     max_query_call_graph_depth: usize,
     instruction_overhead_per_query_call: RoundInstructions,
@@ -126,7 +127,7 @@ impl<'a> QueryContext<'a> {
         hypervisor: &'a Hypervisor,
         own_subnet_type: SubnetType,
         state: Labeled<Arc<ReplicatedState>>,
-        data_certificate: Vec<u8>,
+        data_certificate: Option<Vec<u8>>,
         subnet_available_memory: SubnetAvailableMemory,
         subnet_available_callbacks: i64,
         canister_guaranteed_callback_quota: u64,
@@ -386,21 +387,7 @@ impl<'a> QueryContext<'a> {
             );
         }
         let cost_schedule = self.get_cost_schedule();
-        let subnet_size = self
-            .network_topology
-            .get_subnet_size(&self.cycles_account_manager.get_subnet_id())
-            .unwrap_or(SMALL_APP_SUBNET_MAX_SIZE);
-        if self.cycles_account_manager.freeze_threshold_cycles(
-            canister.system_state.freeze_threshold,
-            canister.system_state.memory_allocation,
-            canister.memory_usage(),
-            canister.message_memory_usage(),
-            canister.scheduler_state.compute_allocation,
-            subnet_size,
-            self.get_cost_schedule(),
-            canister.system_state.reserved_balance(),
-        ) > canister.system_state.balance()
-        {
+        if self.is_frozen(&canister) {
             let canister_id = canister.canister_id();
             return (
                 canister,
@@ -437,9 +424,211 @@ impl<'a> QueryContext<'a> {
             );
         self.add_system_api_call_counters(system_api_call_counters);
         let instructions_executed = instruction_limit - instructions_left;
+        self.register_query_stats(
+            canister.canister_id(),
+            instructions_executed,
+            method_payload,
+            &result,
+            measurement_scope,
+        );
+
+        if let Some(call_context_id) = call_context_id {
+            let _action = self.finish(
+                &mut canister,
+                call_context_id,
+                None,
+                Ok(None),
+                instructions_executed,
+            );
+        }
+        (canister, result)
+    }
+
+    /// Executes the given update method sent by an end user without
+    /// committing any of its changes and reports its cost.
+    ///
+    /// The method is executed with the semantics of an update call, i.e. it
+    /// may set timers and make outgoing calls, but the calls are not sent:
+    /// they are listed in the report instead. Since the execution happens on
+    /// this replica only, it runs in non-replicated mode.
+    pub(super) fn dry_run(
+        &mut self,
+        query: Query,
+        measurement_scope: &MeasurementScope,
+    ) -> Result<DryRunReport, UserError> {
+        let canister_id = query.receiver;
+        let mut canister = self
+            .state
+            .get_ref()
+            .get_active_canister(&canister_id)?
+            .clone();
+        let user_id = match &query.source {
+            QuerySource::User { user_id, .. } => *user_id,
+            QuerySource::System => {
+                return Err(UserError::new(
+                    ErrorCode::CanisterContractViolation,
+                    "Dry runs can only be requested by end users.",
+                ));
+            }
+        };
+        validate_canister(&canister)?;
+        let method = WasmMethod::Update(query.method_name.clone());
+        validate_method(&method, &canister).map_err(|err| err.into_user_error(&canister_id))?;
+        if self.is_frozen(&canister) {
+            return Err(UserError::new(
+                ErrorCode::CanisterOutOfCycles,
+                format!(
+                    "Canister {canister_id} is unable to process update calls because it's frozen. Please top up the canister with cycles and try again."
+                ),
+            ));
+        }
+
+        let cost_schedule = self.get_cost_schedule();
+        let instruction_limit = self.max_instructions_per_query.min(NumInstructions::new(
+            self.round_limits.instructions.get().max(0) as u64,
+        ));
+        let instruction_limits = InstructionLimits::new(instruction_limit, instruction_limit);
+        let execution_parameters = self.execution_parameters(&canister, instruction_limits);
 
+        let time = self.state.get_ref().time();
+        let memory_usage = canister.memory_usage();
+        let message_memory_usage = canister.message_memory_usage();
+        let balance_before = canister.system_state.balance();
+        // The method was validated above, so the execution state exists.
+        let execution_state = canister.execution_state.clone().unwrap();
+        let wasm_execution_mode = execution_state.wasm_execution_mode;
+        let call_context_id = canister
+            .system_state
+            .new_call_context(
+                CallOrigin::Ingress(user_id, query.id()),
+                Cycles::zero(),
+                time,
+                RequestMetadata::for_new_call_tree(time),
+            )
+            .map_err(|err| UserError::new(ErrorCode::CanisterStopped, err.to_string()))?;
+        let api_type = ApiType::update(
+            time,
+            query.method_payload.clone(),
+            Cycles::zero(),
+            user_id.get(),
+            call_context_id,
+        );
+
+        // The changes to the execution state are dropped with the output
+        // execution state and the canister clone is discarded after the
+        // outgoing requests have been collected.
+        let (output, _execution_state, system_state) = self.hypervisor.execute(
+            api_type,
+            time,
+            canister.system_state,
+            memory_usage,
+            message_memory_usage,
+            execution_parameters,
+            FuncRef::Method(method),
+            execution_state,
+            &self.network_topology,
+            &mut self.round_limits,
+            self.query_critical_error,
+            &CallTreeMetricsNoOp,
+            time,
+            cost_schedule,
+        );
+        canister.system_state = system_state;
+        self.add_system_api_call_counters(output.system_api_call_counters);
+
+        let instructions_used = instruction_limit - output.num_instructions_left;
+        let result = output
+            .wasm_result
+            .map_err(|err| err.into_user_error(&canister_id));
+        self.register_query_stats(
+            canister_id,
+            instructions_used,
+            &query.method_payload,
+            &result,
+            measurement_scope,
+        );
+
+        let subnet_size = self
+            .network_topology
+            .get_subnet_size(&self.cycles_account_manager.get_subnet_id())
+            .unwrap_or(SMALL_APP_SUBNET_MAX_SIZE);
+        let execution_cost = self.cycles_account_manager.execution_cost(
+            instructions_used,
+            subnet_size,
+            cost_schedule,
+            wasm_execution_mode,
+        );
+        // The balance decreases by the fees and cycles of outgoing calls and
+        // by the cycles burned or moved to other canisters during execution.
+        let cycles_charged = execution_cost + (balance_before - canister.system_state.balance());
+
+        // Requests already in the output queues of the certified state are
+        // not part of the report.
+        let outgoing_messages: Vec<_> = canister.output_into_iter().collect();
+        let call_context_manager = canister.system_state.call_context_manager();
+        let outgoing_requests = outgoing_messages
+            .into_iter()
+            .filter_map(|msg| match msg {
+                RequestOrResponse::Request(request) => Some(request),
+                RequestOrResponse::Response(_) => None,
+            })
+            .filter(|request| {
+                call_context_manager
+                    .and_then(|manager| manager.callback(request.sender_reply_callback))
+                    .is_some_and(|callback| callback.call_context_id == call_context_id)
+            })
+            .map(|request| DryRunOutgoingRequest {
+                receiver: request.receiver,
+                method_name: request.method_name.clone(),
+                payment: request.payment,
+                payload_size: NumBytes::from(request.method_payload.len() as u64),
+            })
+            .collect();
+
+        Ok(DryRunReport {
+            result,
+            instructions_used,
+            cycles_charged,
+            dirty_pages: output.instance_stats.dirty_pages() as u64,
+            heap_growth: NumBytes::from(
+                output
+                    .new_memory_usage
+                    .map_or(0, |usage| usage.get().saturating_sub(memory_usage.get())),
+            ),
+            outgoing_requests,
+        })
+    }
+
+    /// Returns true if the canister is below its freezing threshold.
+    fn is_frozen(&self, canister: &CanisterState) -> bool {
+        let subnet_size = self
+            .network_topology
+            .get_subnet_size(&self.cycles_account_manager.get_subnet_id())
+            .unwrap_or(SMALL_APP_SUBNET_MAX_SIZE);
+        self.cycles_account_manager.freeze_threshold_cycles(
+            canister.system_state.freeze_threshold,
+            canister.system_state.memory_allocation,
+            canister.memory_usage(),
+            canister.message_memory_usage(),
+            canister.scheduler_state.compute_allocation,
+            subnet_size,
+            self.get_cost_schedule(),
+            canister.system_state.reserved_balance(),
+        ) > canister.system_state.balance()
+    }
+
+    /// Adds the statistics of a single execution to the evaluated canister
+    /// stats and the query aggregator.
+    fn register_query_stats(
+        &mut self,
+        canister_id: CanisterId,
+        instructions_executed: NumInstructions,
+        method_payload: &[u8],
+        result: &Result<Option<WasmResult>, UserError>,
+        measurement_scope: &MeasurementScope,
+    ) {
         let ingress_payload_size = method_payload.len();
-        let egress_payload_size = match &result {
+        let egress_payload_size = match result {
             Ok(result) => match result {
                 Some(WasmResult::Reply(vec)) => vec.len(),
                 Some(WasmResult::Reject(_)) => 0,
@@ -455,10 +644,10 @@ impl<'a> QueryContext<'a> {
             ingress_payload_size: ingress_payload_size as u64,
             egress_payload_size: egress_payload_size as u64,
         };
-        self.add_evaluated_canister_stats(canister.canister_id(), &stats);
+        self.add_evaluated_canister_stats(canister_id, &stats);
         if let Some(query_stats) = self.local_query_execution_stats {
             query_stats.set_epoch_from_height(self.state.height());
-            query_stats.register_query_statistics(canister.canister_id(), &stats);
+            query_stats.register_query_statistics(canister_id, &stats);
         }
 
         measurement_scope.add(
@@ -466,17 +655,6 @@ impl<'a> QueryContext<'a> {
             NumSlices::from(1),
             NumMessages::from(1),
         );
-
-        if let Some(call_context_id) = call_context_id {
-            let _action = self.finish(
-                &mut canister,
-                call_context_id,
-                None,
-                Ok(None),
-                instructions_executed,
-            );
-        }
-        (canister, result)
     }
 
     /// Adds up System API call counters.
@@ -1089,7 +1267,7 @@ impl<'a> QueryContext<'a> {
         if canister_id != &self.data_certificate.1 {
             None
         } else {
-            Some(self.data_certificate.0.clone())
+            self.data_certificate.0.clone()
         }
     }
 
@@ -1,12 +1,15 @@
 use crate::InternalHttpQueryHandler;
-use ic_base_types::{CanisterId, NumSeconds};
+use ic_base_types::{CanisterId, NumBytes, NumSeconds};
 use ic_config::execution_environment::INSTRUCTION_OVERHEAD_PER_QUERY_CALL;
 use ic_error_types::{ErrorCode, UserError};
+use ic_interfaces::execution_environment::DryRunReport;
+use ic_interfaces_state_manager::Labeled;
+use ic_replicated_state::NumWasmPages;
 use ic_test_utilities::universal_canister::{call_args, wasm};
 use ic_test_utilities_execution_environment::{ExecutionTest, ExecutionTestBuilder};
 use ic_test_utilities_types::ids::user_test_id;
 use ic_types::{
-    Cycles, NumInstructions,
+    Cycles, Height, NumInstructions,
     ingress::WasmResult,
     messages::{Query, QuerySource},
 };
@@ -1253,3 +1256,82 @@ fn query_call_exceeds_instructions_limit() {
             )
     );
 }
+
+fn dry_run(
+    test: &ExecutionTest,
+    canister_id: CanisterId,
+    method_name: &str,
+    payload: Vec<u8>,
+) -> Result<DryRunReport, UserError> {
+    let query = Query {
+        source: QuerySource::User {
+            user_id: user_test_id(1),
+            ingress_expiry: 0,
+            nonce: None,
+        },
+        receiver: canister_id,
+        method_name: method_name.to_string(),
+        method_payload: payload,
+    };
+    let state = Labeled::new(Height::new(0), Arc::new(test.state().clone()));
+    downcast_query_handler(test.query_handler()).dry_run(query, state, false)
+}
+
+#[test]
+fn dry_run_reports_cost_without_committing_state() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister_a = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let canister_b = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+    let balance_before = test.canister_state(canister_a).system_state.balance();
+
+    let payload = wasm()
+        .stable_grow(1)
+        .stable_write(0, b"dirty")
+        .api_global_timer_set(1)
+        .call_with_cycles(canister_b, "update", call_args(), 1_000_u128)
+        .reply_data(b"done")
+        .build();
+    let report = dry_run(&test, canister_a, "update", payload).unwrap();
+
+    assert_eq!(report.result, Ok(Some(WasmResult::Reply(b"done".to_vec()))));
+    assert!(report.instructions_used > NumInstructions::new(0));
+    assert!(report.dirty_pages > 0);
+    assert!(report.heap_growth >= NumBytes::new(64 * 1024));
+    // The charge includes the cycles attached to the outgoing call.
+    assert!(report.cycles_charged > Cycles::new(1_000));
+    assert_eq!(report.outgoing_requests.len(), 1);
+    assert_eq!(report.outgoing_requests[0].receiver, canister_b);
+    assert_eq!(report.outgoing_requests[0].method_name, "update");
+    assert_eq!(report.outgoing_requests[0].payment, Cycles::new(1_000));
+
+    // None of the changes were committed.
+    let canister = test.canister_state(canister_a);
+    assert_eq!(canister.system_state.balance(), balance_before);
+    assert!(!canister.has_output());
+    assert_eq!(
+        test.execution_state(canister_a).stable_memory.size,
+        NumWasmPages::new(0)
+    );
+}
+
+#[test]
+fn dry_run_executes_in_non_replicated_mode() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+
+    let payload = wasm().in_replicated_execution().reply_int().build();
+    let report = dry_run(&test, canister, "update", payload).unwrap();
+    assert_eq!(
+        report.result,
+        Ok(Some(WasmResult::Reply(0_u32.to_le_bytes().to_vec())))
+    );
+}
+
+#[test]
+fn dry_run_requires_update_method() {
+    let mut test = ExecutionTestBuilder::new().build();
+    let canister = test.universal_canister_with_cycles(CYCLES_BALANCE).unwrap();
+
+    let err = dry_run(&test, canister, "query", wasm().reply().build()).unwrap_err();
+    assert_eq!(err.code(), ErrorCode::CanisterMethodNotFound);
+}
@@ -1111,6 +1111,8 @@ impl SchedulerImpl {
                         compute_allocation: Default::default(), // not used
                         long_execution_mode: canister.scheduler_state.long_execution_mode,
                         has_aborted_or_paused_execution: true,
+                        has_latency_sensitive_input: false,
+                        output_congestion_level: 0,
                     })
                 } else {
                     None
@@ -1473,12 +1475,14 @@ impl Scheduler for SchedulerImpl {
         let round_schedule = {
             let _timer = self.metrics.round_scheduling_duration.start_timer();
 
+            let output_congestion_levels = output_congestion_levels(&state);
             RoundSchedule::apply_scheduling_strategy(
                 &round_log,
                 self.config.scheduler_cores,
                 current_round,
                 self.config.accumulated_priority_reset_interval,
                 &mut state.canister_states,
+                &output_congestion_levels,
                 &self.metrics,
             )
         };
@@ -1934,6 +1938,37 @@ fn execute_canisters_on_thread(
     }
 }
 
+/// Returns the highest congestion level signalled by the remote subnets that
+/// each canister has requests queued up for. Canisters without requests for
+/// congested subnets are omitted.
+fn output_congestion_levels(state: &ReplicatedState) -> BTreeMap<CanisterId, u8> {
+    let congested_subnets: BTreeMap<SubnetId, u8> = state
+        .metadata
+        .streams()
+        .iter()
+        .map(|(subnet_id, stream)| (*subnet_id, stream.reverse_stream_congestion_level()))
+        .filter(|(_, level)| *level > 0)
+        .collect();
+    if congested_subnets.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let network_topology = &state.metadata.network_topology;
+    state
+        .canisters_iter()
+        .filter_map(|canister| {
+            let level = canister
+                .system_state
+                .queues()
+                .output_request_receivers()
+                .filter_map(|receiver| network_topology.route(receiver.get()))
+                .filter_map(|subnet_id| congested_subnets.get(&subnet_id).copied())
+                .max()?;
+            Some((canister.canister_id(), level))
+        })
+        .collect()
+}
+
 /// Updates end-of-round replicated state metrics (canisters, queues, cycles,
 /// etc.).
 fn observe_replicated_state_metrics(
@@ -2271,6 +2306,8 @@ fn get_instructions_limits_for_subnet_message(
             | SignWithSchnorr
             | VetKdPublicKey
             | VetKdDeriveKey
+            | BlsPublicKey
+            | SignWithBls
             | StartCanister
             | StopCanister
             | UninstallCode
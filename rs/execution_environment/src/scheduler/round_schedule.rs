@@ -27,6 +27,13 @@ pub(super) struct CanisterRoundState {
     /// True when there is an aborted or paused long update execution.
     /// Note: this doesn't include paused or aborted install codes.
     pub(super) has_aborted_or_paused_execution: bool,
+    /// True when a message for one of the canister's latency-sensitive methods
+    /// is enqueued in its input queues and the canister has not used up
+    /// its fair share, i.e. its accumulated priority is not negative.
+    pub(super) has_latency_sensitive_input: bool,
+    /// Highest congestion level signalled by a remote subnet that the canister
+    /// has requests queued up for. Zero if none of its outputs is congested.
+    pub(super) output_congestion_level: u8,
 }
 
 /// Represents three ordered active Canister ID groups to schedule.
@@ -362,6 +369,8 @@ impl RoundSchedule {
             (
                 std::cmp::Reverse(rs.long_execution_mode),
                 std::cmp::Reverse(rs.has_aborted_or_paused_execution),
+                rs.output_congestion_level,
+                std::cmp::Reverse(rs.has_latency_sensitive_input),
                 std::cmp::Reverse(rs.accumulated_priority),
                 rs.canister_id,
             )
@@ -373,12 +382,17 @@ impl RoundSchedule {
     ///
     /// A shorter description of the scheduling strategy is available in the note
     /// section about [Scheduler and AccumulatedPriority] in types/src/lib.rs
+    ///
+    /// Canisters with requests for congested remote subnets (as per
+    /// `output_congestion_levels`) are ordered after all other new executions,
+    /// so that they produce fewer requests while the receivers catch up.
     pub(super) fn apply_scheduling_strategy(
         logger: &ReplicaLogger,
         scheduler_cores: usize,
         current_round: ExecutionRound,
         accumulated_priority_reset_interval: ExecutionRound,
         canister_states: &mut BTreeMap<CanisterId, CanisterState>,
+        output_congestion_levels: &BTreeMap<CanisterId, u8>,
         metrics: &SchedulerMetrics,
     ) -> RoundSchedule {
         let number_of_canisters = canister_states.len();
@@ -431,13 +445,39 @@ impl RoundSchedule {
 
             let compute_allocation = canister.scheduler_state.compute_allocation;
             let accumulated_priority = canister.scheduler_state.accumulated_priority;
+            // Latency-sensitive input moves the canister ahead of canisters with
+            // a higher accumulated priority. Every full execution is charged as
+            // usual, so the boost is bounded: once the canister has used up its
+            // fair share (its accumulated priority turns negative), it is ordered
+            // by priority again until it has caught up.
+            let has_latency_sensitive_input = !has_aborted_or_paused_execution
+                && accumulated_priority.get() >= 0
+                && canister.has_latency_sensitive_input();
+            // Paused and aborted executions are never held back, so that they
+            // release their resources as soon as possible.
+            let output_congestion_level = if has_aborted_or_paused_execution {
+                0
+            } else {
+                output_congestion_levels
+                    .get(&canister_id)
+                    .copied()
+                    .unwrap_or_default()
+            };
             round_states.push(CanisterRoundState {
                 canister_id,
                 accumulated_priority,
                 compute_allocation,
                 long_execution_mode: canister.scheduler_state.long_execution_mode,
                 has_aborted_or_paused_execution,
+                has_latency_sensitive_input,
+                output_congestion_level,
             });
+            if has_latency_sensitive_input {
+                metrics.scheduler_latency_sensitive_canisters.inc();
+            }
+            if output_congestion_level > 0 {
+                metrics.scheduler_congested_output_canisters.inc();
+            }
 
             total_compute_allocation_percent += compute_allocation.as_percent() as i64;
             accumulated_priority_invariant += accumulated_priority;
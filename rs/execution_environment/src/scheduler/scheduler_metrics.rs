@@ -108,6 +108,8 @@ pub(super) struct SchedulerMetrics {
     pub(super) scheduler_cores_invariant_broken: IntCounter,
     pub(super) scheduler_accumulated_priority_invariant: IntGauge,
     pub(super) scheduler_accumulated_priority_deviation: Gauge,
+    pub(super) scheduler_latency_sensitive_canisters: IntCounter,
+    pub(super) scheduler_congested_output_canisters: IntCounter,
     pub(super) subnet_memory_usage_invariant: IntCounter,
     pub(super) total_canister_balance: Gauge,
     pub(super) total_canister_reserved_balance: Gauge,
@@ -713,6 +715,16 @@ impl SchedulerMetrics {
                 "scheduler_accumulated_priority_deviation",
                 "The standard deviation of accumulated priorities on the subnet."
             ),
+            scheduler_latency_sensitive_canisters: metrics_registry.int_counter(
+                "scheduler_latency_sensitive_canisters_total",
+                "Total number of times a canister was prioritized for a round because \
+                      of pending input for one of its latency-sensitive methods.",
+            ),
+            scheduler_congested_output_canisters: metrics_registry.int_counter(
+                "scheduler_congested_output_canisters_total",
+                "Total number of times a canister was deprioritized for a round because \
+                      of pending requests for a congested remote subnet.",
+            ),
             subnet_memory_usage_invariant: metrics_registry.error_counter(SUBNET_MEMORY_USAGE_INVARIANT_BROKEN),
             total_canister_balance: metrics_registry.gauge(
                 "scheduler_canister_balance_cycles_total",
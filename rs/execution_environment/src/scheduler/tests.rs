@@ -20,8 +20,11 @@ use ic_management_canister_types_private::{
 };
 use ic_registry_routing_table::CanisterIdRange;
 use ic_registry_subnet_type::SubnetType;
-use ic_replicated_state::testing::{CanisterQueuesTesting, SystemStateTesting};
+use ic_replicated_state::testing::{
+    CanisterQueuesTesting, ReplicatedStateTesting, SystemStateTesting,
+};
 use ic_replicated_state::{
+    Stream,
     canister_state::system_state::{CyclesUseCase, PausedExecutionId},
     metadata_state::subnet_call_context_manager::EcdsaMatchedPreSignature,
 };
@@ -5624,6 +5627,157 @@ fn scheduler_resets_accumulated_priorities() {
     assert_eq!(scheduler_cores, num_executed_second_messages);
 }
 
+#[test]
+fn scheduler_prefers_latency_sensitive_input_within_fair_share() {
+    fn is_executed(test: &SchedulerTest, ingress_id: &MessageId) -> bool {
+        // There is no response, so executed messages are in the failed state.
+        matches!(
+            test.ingress_status(ingress_id),
+            IngressStatus::Known {
+                state: IngressState::Failed(_),
+                ..
+            }
+        )
+    }
+
+    let scheduler_cores = 2;
+    let subnet_config = SubnetConfig::new(SubnetType::Application);
+    let mut test = SchedulerTestBuilder::new()
+        .with_scheduler_config(SchedulerConfig {
+            scheduler_cores,
+            // Increase the overhead to execute just one message per round per core
+            instruction_overhead_per_execution: subnet_config
+                .scheduler_config
+                .max_instructions_per_round,
+            accumulated_priority_reset_interval: 100.into(),
+            ..subnet_config.scheduler_config
+        })
+        .build();
+
+    let mut first_ingress_ids = vec![];
+    for _ in 0..scheduler_cores * 2 {
+        let canister_id = test.create_canister();
+        first_ingress_ids.push(test.send_ingress(canister_id, ingress(5)));
+    }
+    // The latency-sensitive canister has the highest canister ID, so it would
+    // be scheduled last among canisters with the same accumulated priority.
+    let latency_sensitive_canister_id = test.create_canister();
+    test.canister_state_mut(latency_sensitive_canister_id)
+        .system_state
+        .latency_sensitive_methods = BTreeSet::from(["update".to_string()]);
+    let first_ingress_id = test.send_ingress(latency_sensitive_canister_id, ingress(5));
+    let second_ingress_id = test.send_ingress(latency_sensitive_canister_id, ingress(5));
+
+    // The pending `update` call moves the canister to the front of the schedule.
+    test.execute_round(ExecutionRoundType::OrdinaryRound);
+    assert!(is_executed(&test, &first_ingress_id));
+    assert_eq!(
+        scheduler_cores - 1,
+        first_ingress_ids
+            .iter()
+            .filter(|id| is_executed(&test, id))
+            .count()
+    );
+
+    // Having used more than its fair share, the canister is scheduled by its
+    // accumulated priority again and the canisters that did not execute in the
+    // first round go first.
+    test.execute_round(ExecutionRoundType::OrdinaryRound);
+    assert!(!is_executed(&test, &second_ingress_id));
+    assert_eq!(
+        2 * scheduler_cores - 1,
+        first_ingress_ids
+            .iter()
+            .filter(|id| is_executed(&test, id))
+            .count()
+    );
+}
+
+#[test]
+fn scheduler_deprioritizes_canisters_with_output_to_congested_subnet() {
+    fn is_executed(test: &SchedulerTest, ingress_id: &MessageId) -> bool {
+        // There is no response, so executed messages are in the failed state.
+        matches!(
+            test.ingress_status(ingress_id),
+            IngressStatus::Known {
+                state: IngressState::Failed(_),
+                ..
+            }
+        )
+    }
+
+    let scheduler_cores = 2;
+    let subnet_config = SubnetConfig::new(SubnetType::Application);
+    let mut test = SchedulerTestBuilder::new()
+        .with_scheduler_config(SchedulerConfig {
+            scheduler_cores,
+            // Increase the overhead to execute just one message per round per core
+            instruction_overhead_per_execution: subnet_config
+                .scheduler_config
+                .max_instructions_per_round,
+            accumulated_priority_reset_interval: 100.into(),
+            ..subnet_config.scheduler_config
+        })
+        .build();
+
+    // Route the xnet canister to a remote subnet that signals congestion.
+    let remote_subnet_id = subnet_test_id(3);
+    let xnet_canister_id = test.xnet_canister_id();
+    let state = test.state_mut();
+    Arc::make_mut(&mut state.metadata.network_topology.routing_table)
+        .insert(
+            CanisterIdRange {
+                start: xnet_canister_id,
+                end: xnet_canister_id,
+            },
+            remote_subnet_id,
+        )
+        .unwrap();
+    state.modify_streams(|streams| {
+        let mut stream = Stream::default();
+        stream.set_reverse_stream_congestion_level(2);
+        streams.insert(remote_subnet_id, stream);
+    });
+
+    // The congested canister has the lowest canister ID, so it would be
+    // scheduled first among canisters with the same accumulated priority.
+    let congested_canister_id = test.create_canister();
+    let congested_ingress_id = test.send_ingress(congested_canister_id, ingress(5));
+    test.canister_state_mut(congested_canister_id)
+        .push_output_request(
+            RequestBuilder::default()
+                .sender(congested_canister_id)
+                .receiver(xnet_canister_id)
+                .build()
+                .into(),
+            UNIX_EPOCH,
+        )
+        .unwrap();
+    let mut other_ingress_ids = vec![];
+    for _ in 0..scheduler_cores {
+        let canister_id = test.create_canister();
+        other_ingress_ids.push(test.send_ingress(canister_id, ingress(5)));
+    }
+
+    // The pending request for the congested subnet moves the canister to the
+    // back of the schedule.
+    test.execute_round(ExecutionRoundType::OrdinaryRound);
+    assert!(!is_executed(&test, &congested_ingress_id));
+    assert!(other_ingress_ids.iter().all(|id| is_executed(&test, id)));
+    assert_eq!(
+        Some(1.0),
+        fetch_counter(
+            test.metrics_registry(),
+            "scheduler_congested_output_canisters_total"
+        )
+    );
+
+    // The canister is only ordered after the others, so it still executes
+    // once they are idle.
+    test.execute_round(ExecutionRoundType::OrdinaryRound);
+    assert!(is_executed(&test, &congested_ingress_id));
+}
+
 #[test]
 fn test_is_next_method_added_to_task_queue() {
     let mut test = SchedulerTestBuilder::new().build();
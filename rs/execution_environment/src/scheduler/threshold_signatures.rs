@@ -344,7 +344,9 @@ mod tests {
                     }),
                 })
             }
-            MasterPublicKeyId::VetKd(_) => panic!("vetKD does not have pre-signatures"),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                panic!("NiDkg keys do not have pre-signatures")
+            }
         };
         let context = SignWithThresholdContext {
             request: RequestBuilder::new().build(),
@@ -406,7 +408,9 @@ mod tests {
                 assert_eq!(pre_sig.height, expected_height);
                 assert_eq!(pre_sig.id.0, expected_id);
             }
-            ThresholdArguments::VetKd(_) => panic!("Unexpected VetKD context"),
+            ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => {
+                panic!("Unexpected NiDkg context")
+            }
         }
         assert!(!context.requires_pre_signature());
     }
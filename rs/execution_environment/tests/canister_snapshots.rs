@@ -882,6 +882,7 @@ fn take_frozen_canister_snapshot_fails() {
     let args = TakeCanisterSnapshotArgs {
         canister_id: canister_id.get(),
         replace_snapshot: None,
+        parent_snapshot: None,
     };
     let err = env.take_canister_snapshot(args).unwrap_err();
     assert_eq!(err.code(), ErrorCode::InsufficientCyclesInMemoryGrow);
@@ -1056,6 +1057,7 @@ fn canister_snapshots_and_memory_allocation() {
         let args = TakeCanisterSnapshotArgs {
             canister_id: canister_id.get(),
             replace_snapshot: None,
+            parent_snapshot: None,
         };
         env.take_canister_snapshot(args).unwrap();
     }
@@ -1141,6 +1141,8 @@ fn dts_aborted_execution_does_not_block_subnet_messages() {
             | Method::SignWithSchnorr
             | Method::VetKdPublicKey
             | Method::VetKdDeriveKey
+            | Method::BlsPublicKey
+            | Method::SignWithBls
             | Method::BitcoinGetBalance
             | Method::BitcoinGetUtxos
             | Method::BitcoinGetBlockHeaders
@@ -1226,6 +1228,7 @@ fn dts_aborted_execution_does_not_block_subnet_messages() {
                 let args = TakeCanisterSnapshotArgs {
                     canister_id: aborted_canister_id.get(),
                     replace_snapshot: None,
+                    parent_snapshot: None,
                 }
                 .encode();
                 (method, call_args().other_side(args))
@@ -0,0 +1,147 @@
+use ic_interfaces::execution_environment::{QueryContinuationToken, QueryExecutionError};
+use ic_interfaces_state_manager::StateReader;
+use ic_management_canister_types_private::CanisterInstallMode;
+use ic_registry_subnet_type::SubnetType;
+use ic_state_machine_tests::{StateMachine, StateMachineBuilder, UserError, WasmResult};
+use ic_test_utilities::universal_canister::{UNIVERSAL_CANISTER_WASM, wasm};
+use ic_types::{CanisterId, Cycles, Height, PrincipalId};
+
+fn setup(remove_old_states: bool) -> (StateMachine, CanisterId) {
+    let env = StateMachineBuilder::new()
+        .with_subnet_type(SubnetType::Application)
+        .with_checkpoints_enabled(false)
+        .with_remove_old_states(remove_old_states)
+        .build();
+    let canister_id =
+        env.create_canister_with_cycles(None, Cycles::from(301_000_000_000_u128), None);
+    env.install_wasm_in_mode(
+        canister_id,
+        CanisterInstallMode::Install,
+        UNIVERSAL_CANISTER_WASM.to_vec(),
+        vec![],
+    )
+    .unwrap();
+
+    (env, canister_id)
+}
+
+fn set_global_data(env: &StateMachine, canister_id: CanisterId, data: &[u8]) {
+    env.execute_ingress(
+        canister_id,
+        "update",
+        wasm().set_global_data(data).reply().build(),
+    )
+    .unwrap();
+}
+
+fn query(
+    env: &StateMachine,
+    canister_id: CanisterId,
+    payload: Vec<u8>,
+    continuation_token: Option<QueryContinuationToken>,
+) -> Result<(Result<WasmResult, UserError>, QueryContinuationToken), QueryExecutionError> {
+    env.query_with_continuation_token(
+        PrincipalId::new_anonymous(),
+        canister_id,
+        "query",
+        payload,
+        continuation_token,
+    )
+}
+
+fn get_global_data() -> Vec<u8> {
+    wasm().get_global_data().append_and_reply().build()
+}
+
+#[test]
+fn query_with_continuation_token_reads_pinned_state() {
+    let (env, canister_id) = setup(false);
+    set_global_data(&env, canister_id, b"first");
+
+    let (result, token) = query(&env, canister_id, get_global_data(), None).unwrap();
+    assert_eq!(result, Ok(WasmResult::Reply(b"first".to_vec())));
+    assert_eq!(token.canister_id, canister_id);
+    assert_eq!(token.height, env.state_manager.latest_certified_height());
+
+    set_global_data(&env, canister_id, b"second");
+
+    // A follow-up query with the token still observes the pinned state.
+    let (result, next_token) = query(&env, canister_id, get_global_data(), Some(token)).unwrap();
+    assert_eq!(result, Ok(WasmResult::Reply(b"first".to_vec())));
+    assert_eq!(next_token, token);
+
+    // A query without a token observes the latest state.
+    let (result, _) = query(&env, canister_id, get_global_data(), None).unwrap();
+    assert_eq!(result, Ok(WasmResult::Reply(b"second".to_vec())));
+}
+
+#[test]
+fn query_with_continuation_token_has_no_data_certificate() {
+    let (env, canister_id) = setup(false);
+    let data_certificate_present = wasm().data_certificate_present().reply_int().build();
+
+    let (result, token) = query(&env, canister_id, data_certificate_present.clone(), None).unwrap();
+    assert_eq!(result, Ok(WasmResult::Reply(vec![1, 0, 0, 0])));
+
+    let (result, _) = query(&env, canister_id, data_certificate_present, Some(token)).unwrap();
+    assert_eq!(result, Ok(WasmResult::Reply(vec![0, 0, 0, 0])));
+}
+
+#[test]
+fn query_with_continuation_token_fails_for_other_canister() {
+    let (env, canister_id) = setup(false);
+    let other_canister_id =
+        env.create_canister_with_cycles(None, Cycles::from(301_000_000_000_u128), None);
+    let (_, token) = query(&env, canister_id, get_global_data(), None).unwrap();
+
+    assert!(matches!(
+        query(&env, other_canister_id, get_global_data(), Some(token)),
+        Err(QueryExecutionError::ContinuationTokenCanisterMismatch {
+            token_canister_id,
+            canister_id: actual_canister_id,
+        }) if token_canister_id == canister_id && actual_canister_id == other_canister_id
+    ));
+}
+
+#[test]
+fn query_with_continuation_token_fails_for_uncertified_height() {
+    let (env, canister_id) = setup(false);
+    let token = QueryContinuationToken {
+        height: Height::new(1_000_000),
+        canister_id,
+    };
+
+    assert!(matches!(
+        query(&env, canister_id, get_global_data(), Some(token)),
+        Err(QueryExecutionError::PinnedStateNotCertified(height)) if height == token.height
+    ));
+}
+
+#[test]
+fn query_with_continuation_token_fails_for_removed_state() {
+    let (env, canister_id) = setup(true);
+    let (_, token) = query(&env, canister_id, get_global_data(), None).unwrap();
+
+    for _ in 0..3 {
+        set_global_data(&env, canister_id, b"data");
+    }
+
+    assert!(matches!(
+        query(&env, canister_id, get_global_data(), Some(token)),
+        Err(QueryExecutionError::PinnedStateRemoved(height)) if height == token.height
+    ));
+}
+
+#[test]
+fn continuation_token_bytes_round_trip() {
+    let token = QueryContinuationToken {
+        height: Height::new(42),
+        canister_id: CanisterId::from_u64(7),
+    };
+
+    assert_eq!(
+        QueryContinuationToken::try_from(token.to_bytes().as_slice()),
+        Ok(token)
+    );
+    assert!(QueryContinuationToken::try_from(&[1, 2, 3][..]).is_err());
+}
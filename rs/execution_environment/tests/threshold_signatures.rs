@@ -1,9 +1,10 @@
 use candid::Decode;
 use ic_base_types::PrincipalId;
 use ic_management_canister_types_private::{
-    self as ic00, CanisterInstallMode, DerivationPath, ECDSAPublicKeyResponse, EcdsaCurve,
-    EcdsaKeyId, MasterPublicKeyId, Method, Payload as Ic00Payload, SchnorrAlgorithm, SchnorrKeyId,
-    SchnorrPublicKeyResponse, SignWithBip341Aux, SignWithECDSAReply, SignWithSchnorrAux,
+    self as ic00, BlsCurve, BlsKeyId, BlsPublicKeyResponse, CanisterInstallMode, DerivationPath,
+    ECDSAPublicKeyResponse, EcdsaCurve, EcdsaKeyId, MasterPublicKeyId, Method,
+    Payload as Ic00Payload, SchnorrAlgorithm, SchnorrKeyId, SchnorrPublicKeyResponse,
+    SignWithBip341Aux, SignWithBlsReply, SignWithECDSAReply, SignWithSchnorrAux,
     SignWithSchnorrReply, VetKdCurve, VetKdDeriveKeyResult, VetKdKeyId, VetKdPublicKeyResult,
 };
 use ic_registry_subnet_type::SubnetType;
@@ -34,6 +35,13 @@ fn make_ecdsa_key(name: &str) -> MasterPublicKeyId {
     })
 }
 
+fn make_ecdsa_secp256r1_key(name: &str) -> MasterPublicKeyId {
+    MasterPublicKeyId::Ecdsa(EcdsaKeyId {
+        curve: EcdsaCurve::Secp256r1,
+        name: name.to_string(),
+    })
+}
+
 fn make_ed25519_key(name: &str) -> MasterPublicKeyId {
     MasterPublicKeyId::Schnorr(SchnorrKeyId {
         algorithm: SchnorrAlgorithm::Ed25519,
@@ -55,6 +63,13 @@ fn make_vetkd_key(name: &str) -> MasterPublicKeyId {
     })
 }
 
+fn make_bls_key(name: &str) -> MasterPublicKeyId {
+    MasterPublicKeyId::Bls(BlsKeyId {
+        curve: BlsCurve::Bls12_381_G2,
+        name: name.to_string(),
+    })
+}
+
 fn into_inner_ecdsa(key_id: MasterPublicKeyId) -> EcdsaKeyId {
     match key_id {
         MasterPublicKeyId::Ecdsa(key) => key,
@@ -76,6 +91,13 @@ fn into_inner_vetkd(key_id: MasterPublicKeyId) -> VetKdKeyId {
     }
 }
 
+fn into_inner_bls(key_id: MasterPublicKeyId) -> BlsKeyId {
+    match key_id {
+        MasterPublicKeyId::Bls(key) => key,
+        _ => panic!("unexpected key_id type"),
+    }
+}
+
 fn reshare_chain_key_payload(
     method: Method,
     key_id: MasterPublicKeyId,
@@ -131,6 +153,14 @@ fn sign_with_threshold_key_payload(method: Method, key_id: MasterPublicKeyId) ->
             }
         }
         .encode(),
+        Method::SignWithBls => ic00::SignWithBlsArgs {
+            message: vec![1; 32],
+            derivation_path: DerivationPath::new(vec![]),
+            key_id: into_inner_bls(key_id),
+        }
+        .encode(),
+        // The BLS public key is computed by the subnet like a signature.
+        Method::BlsPublicKey => threshold_public_key_payload(method, key_id),
         _ => panic!("unexpected method"),
     }
 }
@@ -155,6 +185,12 @@ fn threshold_public_key_payload(method: Method, key_id: MasterPublicKeyId) -> Ve
             key_id: into_inner_vetkd(key_id),
         }
         .encode(),
+        Method::BlsPublicKey => ic00::BlsPublicKeyArgs {
+            canister_id: None,
+            derivation_path: DerivationPath::new(vec![]),
+            key_id: into_inner_bls(key_id),
+        }
+        .encode(),
         _ => panic!("unexpected method"),
     }
 }
@@ -243,6 +279,7 @@ fn reshare_chain_key_test_cases() -> Vec<(Method, MasterPublicKeyId)> {
         (Method::ReshareChainKey, make_ed25519_key("some_key")),
         (Method::ReshareChainKey, make_bip340_key("some_key")),
         (Method::ReshareChainKey, make_vetkd_key("some_key")),
+        (Method::ReshareChainKey, make_bls_key("some_key")),
     ]
 }
 
@@ -392,6 +429,12 @@ fn test_sign_with_threshold_key_fee_charged() {
             1_000_000,
             2_000_000,
         ),
+        (
+            Method::SignWithECDSA,
+            make_ecdsa_secp256r1_key("some_key"),
+            1_000_000,
+            2_000_000,
+        ),
         (
             Method::SignWithSchnorr,
             make_ed25519_key("some_key"),
@@ -410,6 +453,18 @@ fn test_sign_with_threshold_key_fee_charged() {
             1_000_000,
             2_000_000,
         ),
+        (
+            Method::SignWithBls,
+            make_bls_key("some_key"),
+            1_000_000,
+            2_000_000,
+        ),
+        (
+            Method::BlsPublicKey,
+            make_bls_key("some_key"),
+            1_000_000,
+            2_000_000,
+        ),
     ];
     for (method, key_id, fee, payment) in test_cases {
         let own_subnet = subnet_test_id(1);
@@ -421,6 +476,7 @@ fn test_sign_with_threshold_key_fee_charged() {
             .with_ecdsa_signature_fee(fee)
             .with_schnorr_signature_fee(fee)
             .with_vetkd_derive_key_fee(fee)
+            .with_bls_signature_fee(fee)
             .with_chain_key(key_id.clone())
             .build();
 
@@ -450,6 +506,7 @@ fn test_sign_with_threshold_key_fee_charged() {
             Method::SignWithECDSA => env.sign_with_ecdsa_contexts(),
             Method::SignWithSchnorr => env.sign_with_schnorr_contexts(),
             Method::VetKdDeriveKey => env.vetkd_derive_key_contexts(),
+            Method::SignWithBls | Method::BlsPublicKey => env.sign_with_bls_contexts(),
             _ => panic!("Unexpected method"),
         };
         let (_, context) = contexts.iter().next().unwrap();
@@ -465,6 +522,8 @@ fn test_sign_with_threshold_key_fee_charged() {
             Method::SignWithECDSA => expect_reply::<SignWithECDSAReply>(result).signature,
             Method::SignWithSchnorr => expect_reply::<SignWithSchnorrReply>(result).signature,
             Method::VetKdDeriveKey => expect_reply::<VetKdDeriveKeyResult>(result).encrypted_key,
+            Method::SignWithBls => expect_reply::<SignWithBlsReply>(result).signature,
+            Method::BlsPublicKey => expect_reply::<BlsPublicKeyResponse>(result).public_key,
             _ => panic!("Unexpected method"),
         };
         // Expect non-empty signature.
@@ -472,10 +531,80 @@ fn test_sign_with_threshold_key_fee_charged() {
     }
 }
 
+#[test]
+fn test_sign_with_ecdsa_secp256r1_signature_verifies() {
+    let key_id = make_ecdsa_secp256r1_key("some_key");
+    let env = StateMachineBuilder::new()
+        .with_checkpoints_enabled(false)
+        .with_chain_key(key_id.clone())
+        .build();
+    let canister_id = create_universal_canister(&env);
+
+    let public_key = expect_reply::<ECDSAPublicKeyResponse>(execute_threshold_public_key(
+        &env,
+        canister_id,
+        Method::ECDSAPublicKey,
+        key_id.clone(),
+    ))
+    .public_key;
+    let signature = expect_reply::<SignWithECDSAReply>(execute_sign_with_threshold(
+        &env,
+        canister_id,
+        Method::SignWithECDSA,
+        key_id,
+    ))
+    .signature;
+
+    let public_key = ic_secp256r1::PublicKey::deserialize_sec1(&public_key).unwrap();
+    assert!(public_key.verify_signature_prehashed(&[1; 32], &signature));
+}
+
+#[test]
+fn test_sign_with_bls_signature_verifies() {
+    let key_id = make_bls_key("some_key");
+    let env = StateMachineBuilder::new()
+        .with_checkpoints_enabled(false)
+        .with_chain_key(key_id.clone())
+        .build();
+    let canister_id = create_universal_canister(&env);
+
+    let public_key = expect_reply::<BlsPublicKeyResponse>(execute_threshold_public_key(
+        &env,
+        canister_id,
+        Method::BlsPublicKey,
+        key_id.clone(),
+    ))
+    .public_key;
+    let reply = expect_reply::<SignWithBlsReply>(execute_sign_with_threshold(
+        &env,
+        canister_id,
+        Method::SignWithBls,
+        key_id,
+    ));
+    assert_eq!(reply.public_key, public_key);
+
+    let public_key = blst::min_pk::PublicKey::key_validate(&public_key).unwrap();
+    let signature = blst::min_pk::Signature::sig_validate(&reply.signature, true).unwrap();
+    let dst = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    assert_eq!(
+        signature.verify(true, &[1; 32], dst, &[], &public_key, true),
+        blst::BLST_ERROR::BLST_SUCCESS
+    );
+    assert_ne!(
+        signature.verify(true, &[2; 32], dst, &[], &public_key, true),
+        blst::BLST_ERROR::BLST_SUCCESS
+    );
+}
+
 #[test]
 fn test_sign_with_threshold_key_rejected_without_fee() {
     let test_cases = vec![
         (Method::SignWithECDSA, make_ecdsa_key("some_key"), 2_000_000),
+        (
+            Method::SignWithECDSA,
+            make_ecdsa_secp256r1_key("some_key"),
+            2_000_000,
+        ),
         (
             Method::SignWithSchnorr,
             make_ed25519_key("some_key"),
@@ -491,6 +620,8 @@ fn test_sign_with_threshold_key_rejected_without_fee() {
             make_vetkd_key("some_key"),
             2_000_000,
         ),
+        (Method::SignWithBls, make_bls_key("some_key"), 2_000_000),
+        (Method::BlsPublicKey, make_bls_key("some_key"), 2_000_000),
     ];
     for (method, key_id, fee) in test_cases {
         let own_subnet = subnet_test_id(1);
@@ -502,6 +633,7 @@ fn test_sign_with_threshold_key_rejected_without_fee() {
             .with_ecdsa_signature_fee(fee)
             .with_schnorr_signature_fee(fee)
             .with_vetkd_derive_key_fee(fee)
+            .with_bls_signature_fee(fee)
             .with_chain_key(key_id.clone())
             .build();
 
@@ -553,6 +685,16 @@ fn test_sign_with_threshold_key_unknown_key_rejected() {
             make_vetkd_key("correct_key"),
             make_vetkd_key("wrong_key"),
         ),
+        (
+            Method::SignWithBls,
+            make_bls_key("correct_key"),
+            make_bls_key("wrong_key"),
+        ),
+        (
+            Method::BlsPublicKey,
+            make_bls_key("correct_key"),
+            make_bls_key("wrong_key"),
+        ),
     ];
     for (method, correct_key, wrong_key) in test_cases {
         let own_subnet = subnet_test_id(1);
@@ -743,6 +885,33 @@ fn test_signing_disabled_vs_unknown_key_on_public_key_and_signing_requests() {
     }
 }
 
+#[test]
+fn test_bls_public_key_with_signing_disabled_key_rejected() {
+    // Unlike other public keys, the BLS public key is computed by the subnet
+    // like a signature, so it requires an enabled key.
+    let signing_disabled_key = make_bls_key("signing_disabled_key");
+    let env = StateMachineBuilder::new()
+        .with_checkpoints_enabled(false)
+        .with_subnet_type(SubnetType::System)
+        .with_subnet_id(subnet_test_id(1))
+        .with_nns_subnet_id(subnet_test_id(2))
+        .with_disabled_chain_key(signing_disabled_key.clone())
+        .build();
+    let canister_id = create_universal_canister(&env);
+
+    for method in [Method::BlsPublicKey, Method::SignWithBls] {
+        expect_contains!(
+            get_reject_message(execute_sign_with_threshold(
+                &env,
+                canister_id,
+                method,
+                signing_disabled_key.clone(),
+            )),
+            "Requested unknown or disabled threshold key"
+        );
+    }
+}
+
 #[test]
 fn test_threshold_key_public_key_req_with_unknown_key_rejected() {
     let test_cases = vec![
@@ -799,6 +968,8 @@ fn test_sign_with_threshold_key_fee_ignored_for_nns() {
         (Method::SignWithSchnorr, make_ed25519_key("some_key")),
         (Method::SignWithSchnorr, make_bip340_key("some_key")),
         (Method::VetKdDeriveKey, make_vetkd_key("some_key")),
+        (Method::SignWithBls, make_bls_key("some_key")),
+        (Method::BlsPublicKey, make_bls_key("some_key")),
     ];
     for (method, key_id) in test_cases {
         let fee = 1_000_000;
@@ -811,6 +982,7 @@ fn test_sign_with_threshold_key_fee_ignored_for_nns() {
             .with_ecdsa_signature_fee(fee)
             .with_schnorr_signature_fee(fee)
             .with_vetkd_derive_key_fee(fee)
+            .with_bls_signature_fee(fee)
             .with_chain_key(key_id.clone())
             .build();
 
@@ -837,6 +1009,7 @@ fn test_sign_with_threshold_key_fee_ignored_for_nns() {
             Method::SignWithECDSA => env.sign_with_ecdsa_contexts(),
             Method::SignWithSchnorr => env.sign_with_schnorr_contexts(),
             Method::VetKdDeriveKey => env.vetkd_derive_key_contexts(),
+            Method::SignWithBls | Method::BlsPublicKey => env.sign_with_bls_contexts(),
             _ => panic!("Unexpected method"),
         };
         let (_, context) = contexts.iter().next().unwrap();
@@ -851,6 +1024,8 @@ fn test_sign_with_threshold_key_queue_fills_up() {
         (Method::SignWithSchnorr, make_ed25519_key("some_key"), 20),
         (Method::SignWithSchnorr, make_bip340_key("some_key"), 20),
         (Method::VetKdDeriveKey, make_vetkd_key("some_key"), 20),
+        (Method::SignWithBls, make_bls_key("some_key"), 20),
+        (Method::BlsPublicKey, make_bls_key("some_key"), 20),
     ];
     for (method, key_id, max_queue_size) in test_cases {
         let fee = 1_000_000;
@@ -865,6 +1040,7 @@ fn test_sign_with_threshold_key_queue_fills_up() {
             .with_ecdsa_signature_fee(fee)
             .with_schnorr_signature_fee(fee)
             .with_vetkd_derive_key_fee(fee)
+            .with_bls_signature_fee(fee)
             .with_chain_key(key_id.clone())
             // Turn off automatic ECDSA signatures to fill up the queue.
             .with_ecdsa_signing_enabled(false)
@@ -0,0 +1,76 @@
+//! Module that deals with requests to /_/consensus_trace
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+};
+use ic_interfaces::consensus::ConsensusTraceReader;
+use ic_types::{Height, NodeId, consensus::trace::ConsensusTraceExport};
+use serde::Deserialize;
+use std::sync::Arc;
+
+/// The maximum number of heights returned by a single request. Consensus keeps
+/// at most a bounded number of events per height, so this bounds the size of
+/// the response.
+pub const MAX_CONSENSUS_TRACE_HEIGHTS: usize = 100;
+
+#[derive(Clone)]
+pub(crate) struct ConsensusTraceService {
+    node_id: NodeId,
+    consensus_trace: Arc<dyn ConsensusTraceReader>,
+}
+
+impl ConsensusTraceService {
+    pub(crate) fn route() -> &'static str {
+        "/_/consensus_trace"
+    }
+}
+
+impl ConsensusTraceService {
+    pub(crate) fn new_router(
+        node_id: NodeId,
+        consensus_trace: Arc<dyn ConsensusTraceReader>,
+    ) -> Router {
+        let state = Self {
+            node_id,
+            consensus_trace,
+        };
+        Router::new().route_service(
+            Self::route(),
+            axum::routing::get(consensus_trace).with_state(state),
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct ConsensusTraceParams {
+    from_height: Option<u64>,
+    to_height: Option<u64>,
+}
+
+/// Returns the consensus events recorded by this replica as JSON.
+///
+/// Supported query arguments are `from_height` and `to_height`, restricting
+/// the (inclusive) range of heights to return. At most
+/// [`MAX_CONSENSUS_TRACE_HEIGHTS`] heights are returned, starting with the
+/// lowest one; if more heights are available, `next_height` is set to the
+/// `from_height` of the follow-up request.
+async fn consensus_trace(
+    Query(params): Query<ConsensusTraceParams>,
+    State(state): State<ConsensusTraceService>,
+) -> Json<ConsensusTraceExport> {
+    let from_height = Height::from(params.from_height.unwrap_or(0));
+    let to_height = Height::from(params.to_height.unwrap_or(u64::MAX));
+    let mut heights = state
+        .consensus_trace
+        .get_height_traces(from_height..=to_height, MAX_CONSENSUS_TRACE_HEIGHTS + 1);
+    let next_height = if heights.len() > MAX_CONSENSUS_TRACE_HEIGHTS {
+        heights.pop().map(|trace| trace.height)
+    } else {
+        None
+    };
+    Json(ConsensusTraceExport {
+        node_id: state.node_id,
+        heights,
+        next_height,
+    })
+}
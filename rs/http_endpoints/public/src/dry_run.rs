@@ -0,0 +1,276 @@
+//! Module that deals with requests to /api/v2/canister/.../dry_run
+//!
+//! A dry run executes an update method against the latest certified state
+//! without committing any of its changes and returns a report of what the
+//! call would have cost. The request envelope is the one of a query. As the
+//! report is only advisory, it is not signed by the replica.
+
+use crate::{
+    ReplicaHealthStatus,
+    common::{Cbor, WithTimeout, build_validator, validation_error_to_http_error},
+};
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, State},
+    response::{IntoResponse, Response},
+};
+use crossbeam::atomic::AtomicCell;
+use http::Request;
+use hyper::StatusCode;
+use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
+use ic_error_types::{ErrorCode, RejectCode, UserError};
+use ic_interfaces::{
+    execution_environment::{DryRunExecutionService, DryRunReport, QueryExecutionError},
+    time_source::{SysTimeSource, TimeSource},
+};
+use ic_interfaces_registry::RegistryClient;
+use ic_logger::ReplicaLogger;
+use ic_registry_client_helpers::crypto::root_of_trust::RegistryRootOfTrustProvider;
+use ic_types::{
+    CanisterId,
+    ingress::WasmResult,
+    malicious_flags::MaliciousFlags,
+    messages::{
+        Blob, HasCanisterId, HttpDryRunRequest, HttpDryRunResponse, HttpQueryContent,
+        HttpQueryResponse, HttpQueryResponseReply, HttpRequest, HttpRequestEnvelope, Query,
+    },
+};
+use ic_validator::HttpRequestVerifier;
+use std::sync::Arc;
+use std::{
+    convert::{Infallible, TryFrom},
+    sync::Mutex,
+};
+use tower::{ServiceBuilder, ServiceExt, util::BoxCloneService};
+
+#[derive(Clone)]
+pub struct DryRunService {
+    log: ReplicaLogger,
+    health_status: Arc<AtomicCell<ReplicaHealthStatus>>,
+    time_source: Arc<dyn TimeSource>,
+    validator: Arc<dyn HttpRequestVerifier<Query, RegistryRootOfTrustProvider>>,
+    registry_client: Arc<dyn RegistryClient>,
+    dry_run_execution_service: Arc<Mutex<DryRunExecutionService>>,
+}
+
+pub struct DryRunServiceBuilder {
+    log: ReplicaLogger,
+    health_status: Option<Arc<AtomicCell<ReplicaHealthStatus>>>,
+    malicious_flags: Option<MaliciousFlags>,
+    time_source: Option<Arc<dyn TimeSource>>,
+    ingress_verifier: Arc<dyn IngressSigVerifier + Send + Sync>,
+    registry_client: Arc<dyn RegistryClient>,
+    dry_run_execution_service: DryRunExecutionService,
+}
+
+impl DryRunService {
+    pub(crate) fn route() -> &'static str {
+        "/api/v2/canister/{effective_canister_id}/dry_run"
+    }
+}
+
+impl DryRunServiceBuilder {
+    pub fn builder(
+        log: ReplicaLogger,
+        registry_client: Arc<dyn RegistryClient>,
+        ingress_verifier: Arc<dyn IngressSigVerifier + Send + Sync>,
+        dry_run_execution_service: DryRunExecutionService,
+    ) -> Self {
+        Self {
+            log,
+            health_status: None,
+            malicious_flags: None,
+            time_source: None,
+            ingress_verifier,
+            registry_client,
+            dry_run_execution_service,
+        }
+    }
+
+    pub(crate) fn with_malicious_flags(mut self, malicious_flags: MaliciousFlags) -> Self {
+        self.malicious_flags = Some(malicious_flags);
+        self
+    }
+
+    pub fn with_time_source(mut self, time_source: Arc<dyn TimeSource>) -> Self {
+        self.time_source = Some(time_source);
+        self
+    }
+
+    pub fn with_health_status(
+        mut self,
+        health_status: Arc<AtomicCell<ReplicaHealthStatus>>,
+    ) -> Self {
+        self.health_status = Some(health_status);
+        self
+    }
+
+    pub fn build_router(self) -> Router {
+        let state = DryRunService {
+            log: self.log,
+            health_status: self
+                .health_status
+                .unwrap_or_else(|| Arc::new(AtomicCell::new(ReplicaHealthStatus::Healthy))),
+            time_source: self.time_source.unwrap_or(Arc::new(SysTimeSource::new())),
+            validator: build_validator(self.ingress_verifier, self.malicious_flags),
+            registry_client: self.registry_client,
+            dry_run_execution_service: Arc::new(Mutex::new(self.dry_run_execution_service)),
+        };
+        Router::new().route_service(
+            DryRunService::route(),
+            axum::routing::post(dry_run)
+                .with_state(state)
+                .layer(ServiceBuilder::new().layer(DefaultBodyLimit::disable())),
+        )
+    }
+
+    pub fn build_service(self) -> BoxCloneService<Request<Body>, Response, Infallible> {
+        let router = self.build_router();
+        BoxCloneService::new(router.into_service())
+    }
+}
+
+fn into_http_query_response(result: Result<WasmResult, UserError>) -> HttpQueryResponse {
+    match result {
+        Ok(WasmResult::Reply(vec)) => HttpQueryResponse::Replied {
+            reply: HttpQueryResponseReply { arg: Blob(vec) },
+        },
+        Ok(WasmResult::Reject(message)) => HttpQueryResponse::Rejected {
+            error_code: ErrorCode::CanisterRejectedMessage.to_string(),
+            reject_code: RejectCode::CanisterReject as u64,
+            reject_message: message,
+        },
+        Err(user_error) => HttpQueryResponse::Rejected {
+            error_code: user_error.code().to_string(),
+            reject_code: user_error.reject_code() as u64,
+            reject_message: user_error.description().to_string(),
+        },
+    }
+}
+
+pub(crate) async fn dry_run(
+    axum::extract::Path(effective_canister_id): axum::extract::Path<CanisterId>,
+    State(DryRunService {
+        log,
+        health_status,
+        time_source,
+        validator,
+        registry_client,
+        dry_run_execution_service,
+    }): State<DryRunService>,
+    WithTimeout(Cbor(request)): WithTimeout<Cbor<HttpRequestEnvelope<HttpQueryContent>>>,
+) -> impl IntoResponse {
+    if health_status.load() != ReplicaHealthStatus::Healthy {
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+        let text = format!(
+            "Replica is unhealthy: {:?}. Check the /api/v2/status for more information.",
+            health_status.load(),
+        );
+        return (status, text).into_response();
+    }
+
+    let registry_version = registry_client.get_latest_version();
+
+    // Convert the message to a strongly-typed struct, making structural validations
+    // on the way.
+    let request = match HttpRequest::<Query>::try_from(request) {
+        Ok(request) => request,
+        Err(e) => {
+            let status = StatusCode::BAD_REQUEST;
+            let text = format!("Malformed request: {e:?}");
+            return (status, text).into_response();
+        }
+    };
+    let canister_id = request.content().canister_id();
+    if canister_id != effective_canister_id {
+        let status = StatusCode::BAD_REQUEST;
+        let text = format!(
+            "Specified CanisterId {canister_id} does not match effective canister id in URL {effective_canister_id}"
+        );
+        return (status, text).into_response();
+    }
+
+    let root_of_trust_provider =
+        RegistryRootOfTrustProvider::new(Arc::clone(&registry_client), registry_version);
+    // Since spawn blocking requires 'static we can't use any references
+    let request_c = request.clone();
+    match tokio::task::spawn_blocking(move || {
+        validator.validate_request(
+            &request_c,
+            time_source.get_relative_time(),
+            &root_of_trust_provider,
+        )
+    })
+    .await
+    {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => {
+            let http_err = validation_error_to_http_error(&request, err, &log);
+            return (http_err.status, http_err.message).into_response();
+        }
+        Err(_) => {
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let dry_run_execution_service = dry_run_execution_service.lock().unwrap().clone();
+    let dry_run_execution_response = dry_run_execution_service
+        .oneshot(request.take_content())
+        .await
+        .unwrap();
+
+    let (report, timestamp) = match dry_run_execution_response {
+        Err(QueryExecutionError::CertifiedStateUnavailable) => {
+            let status = StatusCode::SERVICE_UNAVAILABLE;
+            let text = "Certified state unavailable. Please try again.".to_string();
+            return (status, text).into_response();
+        }
+        Err(err) => {
+            let status = StatusCode::INTERNAL_SERVER_ERROR;
+            return (status, err.to_string()).into_response();
+        }
+        Ok((report, time)) => (report, time),
+    };
+
+    let response = match report {
+        Ok(DryRunReport {
+            result,
+            instructions_used,
+            cycles_charged,
+            dirty_pages,
+            heap_growth,
+            outgoing_requests,
+        }) => HttpDryRunResponse {
+            response: result.transpose().map(into_http_query_response),
+            timestamp,
+            instructions_used: instructions_used.get(),
+            cycles_charged: cycles_charged.get(),
+            dirty_pages,
+            heap_growth: heap_growth.get(),
+            outgoing_requests: outgoing_requests
+                .into_iter()
+                .map(|request| HttpDryRunRequest {
+                    receiver: request.receiver,
+                    method_name: request.method_name,
+                    payment: request.payment.get(),
+                    payload_size: request.payload_size.get(),
+                })
+                .collect(),
+        },
+        // The call could not be executed, so there is nothing to report but
+        // the error.
+        Err(user_error) => HttpDryRunResponse {
+            response: Some(into_http_query_response(Err(user_error))),
+            timestamp,
+            instructions_used: 0,
+            cycles_charged: 0,
+            dirty_pages: 0,
+            heap_growth: 0,
+            outgoing_requests: vec![],
+        },
+    };
+
+    Cbor(response).into_response()
+}
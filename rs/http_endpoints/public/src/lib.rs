@@ -7,7 +7,9 @@
 //! Specification](https://internetcomputer.org/docs/current/references/ic-interface-spec)
 mod catch_up_package;
 mod common;
+mod consensus_trace;
 mod dashboard;
+pub mod dry_run;
 mod health_status_refresher;
 pub mod metrics;
 mod pprof;
@@ -29,6 +31,7 @@ pub use call::{
 };
 use common::CONTENT_TYPE_CBOR;
 pub use common::{cors_layer, make_plaintext_response};
+pub use dry_run::DryRunServiceBuilder;
 use ic_http_endpoints_async_utils::start_tcp_listener;
 use ic_nns_delegation_manager::NNSDelegationReader;
 pub use query::QueryServiceBuilder;
@@ -38,6 +41,7 @@ pub use read_state::subnet::SubnetReadStateServiceBuilder;
 use crate::{
     catch_up_package::CatchUpPackageService,
     common::map_box_error_to_response,
+    consensus_trace::ConsensusTraceService,
     dashboard::DashboardService,
     health_status_refresher::HealthStatusRefreshLayer,
     metrics::{
@@ -65,9 +69,10 @@ use ic_config::http_handler::Config;
 use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
 use ic_crypto_tls_interfaces::TlsConfig;
 use ic_interfaces::{
+    consensus::ConsensusTraceReader,
     consensus_pool::ConsensusPoolCache,
     crypto::BasicSigner,
-    execution_environment::{IngressFilterService, QueryExecutionService},
+    execution_environment::{DryRunExecutionService, IngressFilterService, QueryExecutionService},
     ingress_pool::IngressPoolThrottler,
 };
 use ic_interfaces_registry::RegistryClient;
@@ -127,8 +132,10 @@ struct HttpHandler {
     call_v4_router: Router,
     query_v2_router: Router,
     query_v3_router: Router,
+    dry_run_router: Router,
     catchup_router: Router,
     dashboard_router: Router,
+    consensus_trace_router: Router,
     status_router: Router,
     canister_read_state_v2_router: Router,
     canister_read_state_v3_router: Router,
@@ -249,6 +256,7 @@ pub fn start_server(
     config: Config,
     ingress_filter: IngressFilterService,
     query_execution_service: QueryExecutionService,
+    dry_run_execution_service: DryRunExecutionService,
     ingress_throttler: Arc<RwLock<dyn IngressPoolThrottler + Send + Sync>>,
     ingress_tx: Sender<UnvalidatedArtifactMutation<SignedIngress>>,
     state_reader: Arc<dyn StateReader<State = ReplicatedState>>,
@@ -261,6 +269,7 @@ pub fn start_server(
     nns_subnet_id: SubnetId,
     log: ReplicaLogger,
     consensus_pool_cache: Arc<dyn ConsensusPoolCache>,
+    consensus_trace: Arc<dyn ConsensusTraceReader>,
     subnet_type: SubnetType,
     malicious_flags: MaliciousFlags,
     nns_delegation_reader: NNSDelegationReader,
@@ -341,6 +350,16 @@ pub fn start_server(
     let query_v2_router = query_router(query::Version::V2);
     let query_v3_router = query_router(query::Version::V3);
 
+    let dry_run_router = DryRunServiceBuilder::builder(
+        log.clone(),
+        registry_client.clone(),
+        ingress_verifier.clone(),
+        dry_run_execution_service,
+    )
+    .with_health_status(health_status.clone())
+    .with_malicious_flags(malicious_flags.clone())
+    .build_router();
+
     let canister_read_state_router = |version| {
         CanisterReadStateServiceBuilder::builder(
             log.clone(),
@@ -384,6 +403,7 @@ pub fn start_server(
     let dashboard_router =
         DashboardService::new_router(config.clone(), subnet_type, state_reader.clone());
     let catchup_router = CatchUpPackageService::new_router(consensus_pool_cache.clone());
+    let consensus_trace_router = ConsensusTraceService::new_router(node_id, consensus_trace);
 
     let pprof_home_router = PprofHomeService::new_router();
     let pprof_profile_router = PprofProfileService::new_router(pprof_collector.clone());
@@ -414,9 +434,11 @@ pub fn start_server(
         call_v4_router,
         query_v2_router,
         query_v3_router,
+        dry_run_router,
         status_router,
         catchup_router,
         dashboard_router,
+        consensus_trace_router,
         canister_read_state_v2_router,
         canister_read_state_v3_router,
         subnet_read_state_v2_router,
@@ -605,6 +627,9 @@ fn make_router(
             .merge(http_handler.query_v3_router.layer(service_builder(
                 GlobalConcurrencyLimitLayer::new(config.max_query_concurrent_requests),
             )))
+            .merge(http_handler.dry_run_router.layer(service_builder(
+                GlobalConcurrencyLimitLayer::new(config.max_dry_run_concurrent_requests),
+            )))
             .merge(
                 http_handler
                     .subnet_read_state_v2_router
@@ -639,6 +664,9 @@ fn make_router(
             .merge(http_handler.dashboard_router.layer(service_builder(
                 GlobalConcurrencyLimitLayer::new(config.max_dashboard_concurrent_requests),
             )))
+            .merge(http_handler.consensus_trace_router.layer(service_builder(
+                GlobalConcurrencyLimitLayer::new(config.max_dashboard_concurrent_requests),
+            )))
             .merge(
                 http_handler
                     .pprof_home_router
@@ -757,7 +785,7 @@ pub(crate) mod tests {
     use super::*;
 
     use crate::read_state::subnet::SubnetReadStateService;
-    use crate::{common::Cbor, query::QueryService};
+    use crate::{common::Cbor, dry_run::DryRunService, query::QueryService};
 
     use axum::body::Body;
     use bytes::Bytes;
@@ -809,12 +837,16 @@ pub(crate) mod tests {
                 QueryService::route(query::Version::V3),
                 axum::routing::post(dummy_cbor),
             ),
+            dry_run_router: Router::new()
+                .route(DryRunService::route(), axum::routing::post(dummy_cbor)),
             catchup_router: Router::new().route(
                 CatchUpPackageService::route(),
                 axum::routing::post(dummy_cbor),
             ),
             dashboard_router: Router::new()
                 .route(DashboardService::route(), axum::routing::get(dummy)),
+            consensus_trace_router: Router::new()
+                .route(ConsensusTraceService::route(), axum::routing::get(dummy)),
             status_router: Router::new().route(StatusService::route(), axum::routing::get(dummy)),
             canister_read_state_v2_router: Router::new().route(
                 CanisterReadStateService::route(read_state::canister::Version::V2),
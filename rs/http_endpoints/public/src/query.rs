@@ -18,7 +18,9 @@ use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
 use ic_error_types::{ErrorCode, RejectCode};
 use ic_interfaces::{
     crypto::BasicSigner,
-    execution_environment::{QueryExecutionError, QueryExecutionInput, QueryExecutionService},
+    execution_environment::{
+        QueryContinuationToken, QueryExecutionError, QueryExecutionInput, QueryExecutionService,
+    },
     time_source::{SysTimeSource, TimeSource},
 };
 use ic_interfaces_registry::RegistryClient;
@@ -30,9 +32,8 @@ use ic_types::{
     ingress::WasmResult,
     malicious_flags::MaliciousFlags,
     messages::{
-        Blob, HasCanisterId, HttpQueryContent, HttpQueryResponse, HttpQueryResponseReply,
-        HttpRequest, HttpRequestEnvelope, HttpSignedQueryResponse, NodeSignature, Query,
-        QueryResponseHash,
+        Blob, HasCanisterId, HttpQueryRequestEnvelope, HttpQueryResponse, HttpQueryResponseReply,
+        HttpRequest, HttpSignedQueryResponse, NodeSignature, Query, QueryResponseHash,
     },
 };
 use ic_validator::HttpRequestVerifier;
@@ -176,7 +177,10 @@ pub(crate) async fn query(
         query_execution_service,
         version,
     }): State<QueryService>,
-    WithTimeout(Cbor(request)): WithTimeout<Cbor<HttpRequestEnvelope<HttpQueryContent>>>,
+    WithTimeout(Cbor(HttpQueryRequestEnvelope {
+        envelope: request,
+        continuation_token,
+    })): WithTimeout<Cbor<HttpQueryRequestEnvelope>>,
 ) -> impl IntoResponse {
     if health_status.load() != ReplicaHealthStatus::Healthy {
         let status = StatusCode::SERVICE_UNAVAILABLE;
@@ -208,6 +212,18 @@ pub(crate) async fn query(
         return (status, text).into_response();
     }
 
+    let continuation_token = match continuation_token
+        .map(|token| QueryContinuationToken::try_from(token.0.as_slice()))
+        .transpose()
+    {
+        Ok(continuation_token) => continuation_token,
+        Err(err) => {
+            let status = StatusCode::BAD_REQUEST;
+            let text = format!("Malformed continuation token: {err}");
+            return (status, text).into_response();
+        }
+    };
+
     let root_of_trust_provider =
         RegistryRootOfTrustProvider::new(Arc::clone(&registry_client), registry_version);
     // Since spawn blocking requires 'static we can't use any references
@@ -245,19 +261,33 @@ pub(crate) async fn query(
     let query_execution_input = QueryExecutionInput {
         query: user_query.clone(),
         certificate_delegation_with_metadata: delegation_from_nns,
+        continuation_token,
     };
     let query_execution_response = query_execution_service
         .oneshot(query_execution_input)
         .await
         .unwrap();
 
-    let (response, timestamp) = match query_execution_response {
+    let (response, timestamp, continuation_token) = match query_execution_response {
         Err(QueryExecutionError::CertifiedStateUnavailable) => {
             let status = StatusCode::SERVICE_UNAVAILABLE;
             let text = "Certified state unavailable. Please try again.".to_string();
             return (status, text).into_response();
         }
-        Ok((response, time)) => (response, time),
+        Err(err @ QueryExecutionError::PinnedStateNotCertified(_)) => {
+            let status = StatusCode::SERVICE_UNAVAILABLE;
+            return (status, err.to_string()).into_response();
+        }
+        // The token is for another canister or its state was removed, so the
+        // client has to start over without a token.
+        Err(
+            err @ (QueryExecutionError::ContinuationTokenCanisterMismatch { .. }
+            | QueryExecutionError::PinnedStateRemoved(_)),
+        ) => {
+            let status = StatusCode::BAD_REQUEST;
+            return (status, err.to_string()).into_response();
+        }
+        Ok((response, time, continuation_token)) => (response, time, continuation_token),
     };
 
     let query_response = match response {
@@ -303,6 +333,7 @@ pub(crate) async fn query(
             let signed_query_response = HttpSignedQueryResponse {
                 response: query_response,
                 node_signature,
+                continuation_token: Some(Blob(continuation_token.to_bytes())),
             };
 
             Cbor(signed_query_response).into_response()
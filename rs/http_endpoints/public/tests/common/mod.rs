@@ -13,9 +13,11 @@ use ic_crypto_tree_hash::{LabeledTree, MatchPatternPath, MixedHashTree};
 use ic_error_types::UserError;
 use ic_http_endpoints_public::start_server;
 use ic_interfaces::{
+    consensus::ConsensusTraceReader,
     consensus_pool::ConsensusPoolCache,
     execution_environment::{
-        IngressFilterService, QueryExecutionInput, QueryExecutionResponse, QueryExecutionService,
+        DryRunExecutionResponse, DryRunExecutionService, IngressFilterService, QueryExecutionInput,
+        QueryExecutionResponse, QueryExecutionService,
     },
     ingress_pool::IngressPoolThrottler,
 };
@@ -51,7 +53,10 @@ use ic_types::{
     CryptoHashOfPartialState, Height, RegistryVersion,
     artifact::UnvalidatedArtifactMutation,
     batch::RawQueryStats,
-    consensus::certification::{Certification, CertificationContent},
+    consensus::{
+        certification::{Certification, CertificationContent},
+        trace::HeightTrace,
+    },
     crypto::{
         CombinedThresholdSig, CombinedThresholdSigOf, CryptoHash, Signed,
         threshold_sig::{
@@ -60,13 +65,16 @@ use ic_types::{
         },
     },
     malicious_flags::MaliciousFlags,
-    messages::{CertificateDelegation, MessageId, SignedIngress},
+    messages::{CertificateDelegation, MessageId, Query, SignedIngress},
     signature::ThresholdSignature,
     time::UNIX_EPOCH,
 };
 use mockall::{mock, predicate::*};
 use prost::Message;
-use std::{collections::BTreeMap, convert::Infallible, net::SocketAddr, sync::Arc, sync::RwLock};
+use std::{
+    collections::BTreeMap, convert::Infallible, net::SocketAddr, ops::RangeInclusive, sync::Arc,
+    sync::RwLock,
+};
 use tokio::{
     net::{TcpSocket, TcpStream},
     sync::{
@@ -80,6 +88,7 @@ use tower_test::mock::Handle;
 
 pub type IngressFilterHandle = Handle<(ProvisionalWhitelist, SignedIngress), Result<(), UserError>>;
 pub type QueryExecutionHandle = Handle<QueryExecutionInput, QueryExecutionResponse>;
+pub type DryRunExecutionHandle = Handle<Query, DryRunExecutionResponse>;
 
 fn setup_query_execution_mock() -> (QueryExecutionService, QueryExecutionHandle) {
     let (service, handle) = tower_test::mock::pair::<QueryExecutionInput, QueryExecutionResponse>();
@@ -101,6 +110,26 @@ fn setup_query_execution_mock() -> (QueryExecutionService, QueryExecutionHandle)
     (BoxCloneService::new(infallible_service), handle)
 }
 
+fn setup_dry_run_execution_mock() -> (DryRunExecutionService, DryRunExecutionHandle) {
+    let (service, handle) = tower_test::mock::pair::<Query, DryRunExecutionResponse>();
+
+    let infallible_service = tower::service_fn(move |request: Query| {
+        let mut service_clone = service.clone();
+        async move {
+            Ok::<DryRunExecutionResponse, Infallible>(
+                service_clone
+                    .ready()
+                    .await
+                    .expect("Mocking Infallible service. Waiting for readiness failed.")
+                    .call(request)
+                    .await
+                    .expect("Mocking Infallible service and can therefore not return an error."),
+            )
+        }
+    });
+    (BoxCloneService::new(infallible_service), handle)
+}
+
 #[allow(clippy::type_complexity)]
 pub fn setup_ingress_filter_mock() -> (IngressFilterService, IngressFilterHandle) {
     let (service, handle) =
@@ -362,10 +391,23 @@ mock! {
     }
 }
 
+mock! {
+    pub ConsensusTraceReader {}
+
+    impl ConsensusTraceReader for ConsensusTraceReader {
+        fn get_height_traces(
+            &self,
+            heights: RangeInclusive<Height>,
+            max_heights: usize,
+        ) -> Vec<HeightTrace>;
+    }
+}
+
 pub struct HttpEndpointHandles {
     pub ingress_filter: IngressFilterHandle,
     pub ingress_rx: Receiver<UnvalidatedArtifactMutation<SignedIngress>>,
     pub query_execution: QueryExecutionHandle,
+    pub dry_run_execution: DryRunExecutionHandle,
     pub terminal_state_ingress_messages: Sender<(MessageId, Height)>,
     pub certified_height_watcher: watch::Sender<Height>,
 }
@@ -465,6 +507,7 @@ impl HttpEndpointBuilder {
 
         let (ingress_filter, ingress_filter_handle) = setup_ingress_filter_mock();
         let (query_exe, query_exe_handler) = setup_query_execution_mock();
+        let (dry_run_exe, dry_run_exe_handler) = setup_dry_run_execution_mock();
         let (certified_height_watcher_tx, certified_height_watcher_rx) =
             watch::channel(self.certified_height.unwrap_or_default());
         let builder = self.delegation_from_nns.map(|delegation| {
@@ -483,12 +526,18 @@ impl HttpEndpointBuilder {
 
         let (ingress_tx, ingress_rx) = channel(self.ingress_channel_capacity);
 
+        let mut consensus_trace = MockConsensusTraceReader::new();
+        consensus_trace
+            .expect_get_height_traces()
+            .returning(|_, _| Vec::new());
+
         start_server(
             self.rt_handle,
             &metrics,
             self.config,
             ingress_filter,
             query_exe,
+            dry_run_exe,
             self.ingress_pool_throttler,
             ingress_tx,
             self.state_manager,
@@ -501,6 +550,7 @@ impl HttpEndpointBuilder {
             nns_subnet_id,
             log,
             self.consensus_cache,
+            Arc::new(consensus_trace),
             SubnetType::Application,
             MaliciousFlags::default(),
             nns_delegation_reader,
@@ -515,6 +565,7 @@ impl HttpEndpointBuilder {
             ingress_filter: ingress_filter_handle,
             ingress_rx,
             query_execution: query_exe_handler,
+            dry_run_execution: dry_run_exe_handler,
             terminal_state_ingress_messages: terminal_state_ingress_messages_tx,
             certified_height_watcher: certified_height_watcher_tx,
         }
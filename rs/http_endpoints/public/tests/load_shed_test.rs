@@ -16,9 +16,10 @@ use ic_http_endpoints_public::read_state;
 use ic_http_endpoints_test_agent::{
     self, Call, CanisterReadState, IngressMessage, Query, wait_for_status_healthy,
 };
+use ic_interfaces::execution_environment::{DryRunReport, QueryContinuationToken};
 use ic_interfaces_state_manager_mocks::MockStateManager;
 use ic_pprof::{Error, PprofCollector};
-use ic_types::PrincipalId;
+use ic_types::{CanisterId, Cycles, Height, NumBytes, NumInstructions, PrincipalId};
 use ic_types::{ingress::WasmResult, time::current_time};
 use rstest::rstest;
 use std::{
@@ -75,6 +76,10 @@ fn test_load_shedding_query(
         resp.send_response(Ok((
             Ok(WasmResult::Reply("success".into())),
             current_time(),
+            QueryContinuationToken {
+                height: Height::new(0),
+                canister_id: CanisterId::ic_00(),
+            },
         )))
     });
 
@@ -101,6 +106,124 @@ fn test_load_shedding_query(
     });
 }
 
+/// Test that the `/dry_run` endpoint has its own concurrency limiter: when its
+/// load shedder kicks in we return 429, while queries are still served.
+#[test]
+fn test_load_shedding_dry_run() {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+
+    let config = Config {
+        listen_addr: addr,
+        max_dry_run_concurrent_requests: 1,
+        ..Default::default()
+    };
+
+    let handlers = HttpEndpointBuilder::new(rt.handle().clone(), config).run();
+    let mut dry_run_execution = handlers.dry_run_execution;
+    let mut query_execution = handlers.query_execution;
+
+    let dry_run_exec_running = Arc::new(Notify::new());
+    let load_shedder_returned = Arc::new(Notify::new());
+
+    let load_shedder_returned_clone = load_shedder_returned.clone();
+    let dry_run_exec_running_clone = dry_run_exec_running.clone();
+
+    // This request will be load shedded.
+    let load_shedded_request = rt.spawn(async move {
+        dry_run_exec_running_clone.notified().await;
+
+        let response = Query::new(
+            PrincipalId::default(),
+            PrincipalId::default(),
+            query::Version::V2,
+        )
+        .dry_run(addr)
+        .await;
+
+        load_shedder_returned_clone.notify_one();
+
+        response
+    });
+
+    // Mock dry run exec service, blocking until the load shedder returned.
+    let query_served = Arc::new(Notify::new());
+    let query_served_clone = query_served.clone();
+    rt.spawn(async move {
+        let (_, resp) = dry_run_execution.next_request().await.unwrap();
+        dry_run_exec_running.notify_one();
+        load_shedder_returned.notified().await;
+        query_served_clone.notified().await;
+
+        resp.send_response(Ok((
+            Ok(DryRunReport {
+                result: Ok(None),
+                instructions_used: NumInstructions::new(0),
+                cycles_charged: Cycles::zero(),
+                dirty_pages: 0,
+                heap_growth: NumBytes::new(0),
+                outgoing_requests: vec![],
+            }),
+            current_time(),
+        )))
+    });
+
+    // Mock query exec service.
+    rt.spawn(async move {
+        let (_, resp) = query_execution.next_request().await.unwrap();
+        resp.send_response(Ok((
+            Ok(WasmResult::Reply("success".into())),
+            current_time(),
+            QueryContinuationToken {
+                height: Height::new(0),
+                canister_id: CanisterId::ic_00(),
+            },
+        )))
+    });
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let dry_run_response = tokio::spawn(
+            Query::new(
+                PrincipalId::default(),
+                PrincipalId::default(),
+                query::Version::V2,
+            )
+            .dry_run(addr),
+        );
+
+        let response = load_shedded_request.await.unwrap();
+        assert_eq!(
+            StatusCode::TOO_MANY_REQUESTS,
+            response.status(),
+            "Concurrent dry run was not load shedded.",
+        );
+
+        // Queries are not limited by the in-flight dry run.
+        let response = Query::new(
+            PrincipalId::default(),
+            PrincipalId::default(),
+            query::Version::V2,
+        )
+        .query(addr)
+        .await;
+        assert_eq!(
+            StatusCode::OK,
+            response.status(),
+            "Received unexpected response: {response:?}"
+        );
+        query_served.notify_one();
+
+        let response = dry_run_response.await.unwrap();
+        assert_eq!(
+            StatusCode::OK,
+            response.status(),
+            "Received unexpected response: {response:?}"
+        );
+    });
+}
+
 /// Test concurrency limiter for `/read_state` endpoint and that when the load shedder kicks in
 /// we return 429.
 /// Test scenario:
@@ -28,7 +28,9 @@ use ic_http_endpoints_public::{query, read_state};
 use ic_http_endpoints_test_agent::{
     self, APPLICATION_CBOR, Call, CanisterReadState, IngressMessage, Query, wait_for_status_healthy,
 };
-use ic_interfaces::execution_environment::QueryExecutionError;
+use ic_interfaces::execution_environment::{
+    DryRunOutgoingRequest, DryRunReport, QueryContinuationToken, QueryExecutionError,
+};
 use ic_interfaces_mocks::consensus_pool::MockConsensusPoolCache;
 use ic_interfaces_registry_mocks::MockRegistryClient;
 use ic_interfaces_state_manager::CertifiedStateSnapshot;
@@ -42,7 +44,8 @@ use ic_replicated_state::ReplicatedState;
 use ic_test_utilities_state::ReplicatedStateBuilder;
 use ic_test_utilities_types::ids::{NODE_1, canister_test_id, subnet_test_id, user_test_id};
 use ic_types::{
-    CryptoHashOfPartialState, Height, PrincipalId, RegistryVersion,
+    CanisterId, CryptoHashOfPartialState, Height, NumBytes, NumInstructions, PrincipalId,
+    RegistryVersion,
     artifact::UnvalidatedArtifactMutation,
     consensus::certification::{Certification, CertificationContent},
     crypto::{
@@ -53,7 +56,7 @@ use ic_types::{
         },
     },
     ingress::WasmResult,
-    messages::{Blob, Certificate, CertificateDelegation},
+    messages::{Blob, Certificate, CertificateDelegation, HttpDryRunRequest, HttpDryRunResponse},
     signature::ThresholdSignature,
     time::current_time,
 };
@@ -236,6 +239,10 @@ fn test_unauthorized_query(
             resp.send_response(Ok((
                 Ok(WasmResult::Reply("success".into())),
                 current_time(),
+                QueryContinuationToken {
+                    height: Height::new(0),
+                    canister_id: CanisterId::ic_00(),
+                },
             )))
         }
     });
@@ -428,6 +435,10 @@ fn test_request_timeout(#[values(query::Version::V2, query::Version::V3)] versio
             resp.send_response(Ok((
                 Ok(WasmResult::Reply("success".into())),
                 current_time(),
+                QueryContinuationToken {
+                    height: Height::new(0),
+                    canister_id: CanisterId::ic_00(),
+                },
             )))
         }
     });
@@ -716,6 +727,160 @@ fn test_query_endpoint_returns_service_unavailable_on_missing_state(
     })
 }
 
+/// This test verifies that the query endpoint returns the continuation token of
+/// a query and passes a token sent with a follow-up query on to the
+/// [`QueryExecutionService`](ic_interfaces::execution_environment::QueryExecutionService),
+/// so that a client can fetch the second page of a paginated query.
+#[rstest]
+fn test_query_endpoint_passes_continuation_token_through(
+    #[values(query::Version::V2, query::Version::V3)] version: query::Version,
+) {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+    let config = Config {
+        listen_addr: addr,
+        ..Default::default()
+    };
+
+    let mut handlers = HttpEndpointBuilder::new(rt.handle().clone(), config).run();
+
+    let canister = canister_test_id(1);
+    let token = QueryContinuationToken {
+        height: Height::new(42),
+        canister_id: canister,
+    };
+    // Replies with the first page to queries without a token, and with the
+    // second page to queries with the token.
+    rt.spawn(async move {
+        loop {
+            let (input, resp) = handlers.query_execution.next_request().await.unwrap();
+            let page = match input.continuation_token {
+                None => "page 1",
+                Some(received) if received == token => "page 2",
+                Some(_) => "unexpected token",
+            };
+            resp.send_response(Ok((
+                Ok(WasmResult::Reply(page.into())),
+                current_time(),
+                token,
+            )))
+        }
+    });
+
+    let reply_and_token = |response: &[u8]| {
+        let response: serde_cbor::Value = serde_cbor::from_slice(response).unwrap();
+        let serde_cbor::Value::Map(response) = response else {
+            panic!("Expected a map, got {response:?}");
+        };
+        let field = |name: &str| response.get(&serde_cbor::Value::Text(name.into())).cloned();
+        let Some(serde_cbor::Value::Map(reply)) = field("reply") else {
+            panic!("Expected a reply, got {response:?}");
+        };
+        let Some(serde_cbor::Value::Bytes(arg)) = reply.get(&serde_cbor::Value::Text("arg".into()))
+        else {
+            panic!("Expected a reply arg, got {reply:?}");
+        };
+        let Some(serde_cbor::Value::Bytes(token)) = field("continuation_token") else {
+            panic!("Expected a continuation token, got {response:?}");
+        };
+        (String::from_utf8(arg.clone()).unwrap(), token)
+    };
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let response = Query::new(canister.get(), canister.get(), version)
+            .query(addr)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        let (page, returned_token) = reply_and_token(&response.bytes().await.unwrap());
+        assert_eq!(page, "page 1");
+        assert_eq!(returned_token, token.to_bytes());
+
+        let response = Query::new(canister.get(), canister.get(), version)
+            .with_continuation_token(returned_token)
+            .query(addr)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+        let (page, _) = reply_and_token(&response.bytes().await.unwrap());
+        assert_eq!(page, "page 2");
+
+        let response = Query::new(canister.get(), canister.get(), version)
+            .with_continuation_token(vec![1, 2, 3])
+            .query(addr)
+            .await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    })
+}
+
+/// This test verifies that the dry run endpoint returns the report of the
+/// [`DryRunExecutionService`](ic_interfaces::execution_environment::DryRunExecutionService)
+/// as CBOR.
+#[test]
+fn test_dry_run_endpoint_returns_report() {
+    let rt = Runtime::new().unwrap();
+    let addr = get_free_localhost_socket_addr();
+    let config = Config {
+        listen_addr: addr,
+        ..Default::default()
+    };
+
+    let mut handlers = HttpEndpointBuilder::new(rt.handle().clone(), config).run();
+
+    let canister = canister_test_id(1);
+    let timestamp = current_time();
+    rt.spawn(async move {
+        loop {
+            let (_, resp) = handlers.dry_run_execution.next_request().await.unwrap();
+            resp.send_response(Ok((
+                Ok(DryRunReport {
+                    result: Ok(None),
+                    instructions_used: NumInstructions::new(1_000),
+                    cycles_charged: ic_types::Cycles::new(2_000),
+                    dirty_pages: 3,
+                    heap_growth: NumBytes::new(4_096),
+                    outgoing_requests: vec![DryRunOutgoingRequest {
+                        receiver: canister,
+                        method_name: "transfer".to_string(),
+                        payment: ic_types::Cycles::new(5),
+                        payload_size: NumBytes::new(6),
+                    }],
+                }),
+                timestamp,
+            )))
+        }
+    });
+
+    rt.block_on(async {
+        wait_for_status_healthy(&addr).await.unwrap();
+
+        let response = Query::new(canister.get(), canister.get(), query::Version::V2)
+            .dry_run(addr)
+            .await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let response: HttpDryRunResponse =
+            serde_cbor::from_slice(&response.bytes().await.unwrap()).unwrap();
+        assert_eq!(
+            response,
+            HttpDryRunResponse {
+                response: None,
+                timestamp,
+                instructions_used: 1_000,
+                cycles_charged: 2_000,
+                dirty_pages: 3,
+                heap_growth: 4_096,
+                outgoing_requests: vec![HttpDryRunRequest {
+                    receiver: canister,
+                    method_name: "transfer".to_string(),
+                    payment: 5,
+                    payload_size: 6,
+                }],
+            }
+        );
+    })
+}
+
 #[rstest]
 fn can_retrieve_subnet_metrics(
     #[values(read_state::subnet::Version::V2, read_state::subnet::Version::V3)]
@@ -3,8 +3,9 @@ use ic_http_endpoints_public::{query, read_state};
 use ic_types::{
     PrincipalId,
     messages::{
-        Blob, HttpCallContent, HttpCanisterUpdate, HttpQueryContent, HttpReadState,
-        HttpReadStateContent, HttpRequestEnvelope, HttpUserQuery, MessageId, SignedIngress,
+        Blob, HttpCallContent, HttpCanisterUpdate, HttpQueryContent, HttpQueryRequestEnvelope,
+        HttpReadState, HttpReadStateContent, HttpRequestEnvelope, HttpUserQuery, MessageId,
+        SignedIngress,
     },
     time::current_time,
 };
@@ -169,6 +170,7 @@ pub struct Query {
     canister_id: PrincipalId,
     effective_canister_id: PrincipalId,
     version: query::Version,
+    continuation_token: Option<Vec<u8>>,
 }
 
 impl Query {
@@ -181,31 +183,18 @@ impl Query {
             canister_id,
             effective_canister_id,
             version,
+            continuation_token: None,
         }
     }
 
-    pub async fn query(self, addr: SocketAddr) -> reqwest::Response {
-        let ingress_expiry = (current_time() + INGRESS_EXPIRY_DURATION).as_nanos_since_unix_epoch();
-
-        let call_content = HttpQueryContent::Query {
-            query: HttpUserQuery {
-                canister_id: Blob(self.canister_id.into_vec()),
-                method_name: METHOD_NAME.to_string(),
-                arg: Blob(ARG),
-                sender: Blob(SENDER.into_vec()),
-                ingress_expiry,
-                nonce: None,
-            },
-        };
-
-        let envelope = HttpRequestEnvelope {
-            content: call_content,
-            sender_pubkey: None,
-            sender_sig: None,
-            sender_delegation: None,
-        };
+    /// Sends the query with the given continuation token.
+    pub fn with_continuation_token(mut self, continuation_token: Vec<u8>) -> Self {
+        self.continuation_token = Some(continuation_token);
+        self
+    }
 
-        let body = serde_cbor::to_vec(&envelope).unwrap();
+    pub async fn query(self, addr: SocketAddr) -> reqwest::Response {
+        let body = self.envelope_body();
         let version_str = match self.version {
             query::Version::V2 => "v2",
             query::Version::V3 => "v3",
@@ -223,6 +212,50 @@ impl Query {
             .await
             .unwrap()
     }
+
+    /// Sends the query envelope to the `dry_run` endpoint, which ignores the version.
+    pub async fn dry_run(self, addr: SocketAddr) -> reqwest::Response {
+        let body = self.envelope_body();
+        let url = format!(
+            "http://{addr}/api/v2/canister/{}/dry_run",
+            self.effective_canister_id
+        );
+
+        reqwest::Client::new()
+            .post(url)
+            .body(body)
+            .header(CONTENT_TYPE, APPLICATION_CBOR)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    fn envelope_body(&self) -> Vec<u8> {
+        let ingress_expiry = (current_time() + INGRESS_EXPIRY_DURATION).as_nanos_since_unix_epoch();
+
+        let call_content = HttpQueryContent::Query {
+            query: HttpUserQuery {
+                canister_id: Blob(self.canister_id.into_vec()),
+                method_name: METHOD_NAME.to_string(),
+                arg: Blob(ARG),
+                sender: Blob(SENDER.into_vec()),
+                ingress_expiry,
+                nonce: None,
+            },
+        };
+
+        let envelope = HttpQueryRequestEnvelope {
+            envelope: HttpRequestEnvelope {
+                content: call_content,
+                sender_pubkey: None,
+                sender_sig: None,
+                sender_delegation: None,
+            },
+            continuation_token: self.continuation_token.clone().map(Blob),
+        };
+
+        serde_cbor::to_vec(&envelope).unwrap()
+    }
 }
 
 pub struct CanisterReadState {
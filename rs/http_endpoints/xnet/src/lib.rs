@@ -2,7 +2,12 @@
 mod tests;
 
 use axum::{body::Body, extract::State, response::IntoResponse, routing::any};
-use hyper::{Request, Response, StatusCode, body::Incoming};
+use hyper::{
+    HeaderMap, Request, Response, StatusCode,
+    body::Incoming,
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+    http::response::Builder,
+};
 use hyper_util::{rt::TokioIo, server::graceful::GracefulShutdown};
 use ic_config::message_routing::Config;
 use ic_crypto_tls_interfaces::TlsConfig;
@@ -36,6 +41,8 @@ pub struct XNetEndpointMetrics {
     pub slice_payload_size: Histogram,
     /// Status 200 response size in bytes, by resource.
     pub response_size: HistogramVec,
+    /// Ratio of uncompressed to compressed size of zstd compressed slices.
+    pub slice_compression_ratio: Histogram,
     pub connections_total: IntCounter,
     pub closed_connections_total: IntCounter,
 }
@@ -43,6 +50,7 @@ pub struct XNetEndpointMetrics {
 const METRIC_REQUEST_DURATION: &str = "xnet_endpoint_request_duration_seconds";
 const METRIC_SLICE_PAYLOAD_SIZE: &str = "xnet_endpoint_slice_payload_size_bytes";
 const METRIC_RESPONSE_SIZE: &str = "xnet_endpoint_response_size_bytes";
+const METRIC_SLICE_COMPRESSION_RATIO: &str = "xnet_endpoint_slice_compression_ratio";
 const METRIC_CONNECTIONS: &str = "xnet_endpoint_connections_total";
 const METRIC_CLOSED_CONNECTIONS: &str = "xnet_endpoint_closed_connections_total";
 
@@ -53,6 +61,9 @@ const RESOURCE_UNKNOWN: &str = "unknown";
 
 const XNET_ENDPOINT_MAX_CONCURRENT_REQUESTS: usize = 4;
 
+/// Content coding of zstd compressed stream slices.
+const ZSTD_ENCODING: &str = "zstd";
+
 impl XNetEndpointMetrics {
     pub fn new(metrics_registry: &MetricsRegistry) -> Self {
         Self {
@@ -76,6 +87,12 @@ impl XNetEndpointMetrics {
                 decimal_buckets(1, 6),
                 &["resource"],
             ),
+            slice_compression_ratio: metrics_registry.histogram(
+                METRIC_SLICE_COMPRESSION_RATIO,
+                "Ratio of uncompressed to compressed size of zstd compressed slices",
+                // 1 - 500
+                decimal_buckets(0, 2),
+            ),
             connections_total: metrics_registry.int_counter(
                 METRIC_CONNECTIONS,
                 "Total number of accepted XNet TCP connections.",
@@ -98,6 +115,10 @@ impl XNetEndpointMetrics {
 ///   - Returns a stream slice for the given `SubnetId` with up to `msg_limit`
 ///     messages beginning at `msg_begin`, witness beginning at `witness_begin`
 ///     (`msg_begin` if missing), of up to `byte_limit` bytes.
+///   - If the request's `Accept-Encoding` header includes `zstd`, the encoded
+///     slice is zstd compressed and the response carries a matching
+///     `Content-Encoding` header. `byte_limit` applies to the uncompressed
+///     slice.
 pub struct XNetEndpoint {
     server_address: SocketAddr,
     shutdown_notify: Arc<Notify>,
@@ -170,6 +191,7 @@ async fn handle_xnet_request(
     let metrics = ctx.metrics.clone();
     let log = ctx.log.clone();
     let certified_stream_store = ctx.certified_stream_store.clone();
+    let accepts_zstd = accepts_zstd(request.headers());
 
     ok(tokio::task::spawn_blocking(move || {
         let _permit = owned_permit;
@@ -181,7 +203,7 @@ async fn handle_xnet_request(
                 .map(|pq| pq.as_str())
                 .unwrap_or(""),
         ) {
-            Ok(url) => route_request(url, certified_stream_store.as_ref(), &metrics),
+            Ok(url) => route_request(url, accepts_zstd, certified_stream_store.as_ref(), &metrics),
             Err(e) => {
                 let msg = format!("Invalid URL {}: {}", request.uri(), e);
                 warn!(log, "{}", msg);
@@ -353,10 +375,36 @@ impl XNetEndpoint {
     }
 }
 
+/// Returns `true` if the given request headers accept zstd compressed content,
+/// i.e. `Accept-Encoding` lists `zstd` with a non-zero quality value.
+fn accepts_zstd(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut params = coding.split(';').map(str::trim);
+            params
+                .next()
+                .is_some_and(|coding| coding.eq_ignore_ascii_case(ZSTD_ENCODING))
+                && params.all(|param| !is_zero_quality(param))
+        })
+}
+
+/// Returns `true` if `param` is a `q` parameter with a value of zero.
+fn is_zero_quality(param: &str) -> bool {
+    param
+        .strip_prefix("q=")
+        .and_then(|q| q.parse::<f64>().ok())
+        .is_some_and(|q| q == 0.0)
+}
+
 /// Routes an `XNetEndpoint` request to the appropriate handler; or produces an
 /// HTTP 404 Not Found response if the URL doesn't match any handler.
 fn route_request(
     url: Url,
+    accepts_zstd: bool,
     certified_stream_store: &impl CertifiedStreamStore,
     metrics: &XNetEndpointMetrics,
 ) -> Response<Body> {
@@ -409,6 +457,7 @@ fn route_request(
                 msg_begin,
                 msg_limit,
                 byte_limit,
+                accepts_zstd,
                 certified_stream_store,
                 metrics,
             )
@@ -437,14 +486,16 @@ fn handle_streams(
     observe_response_size(|| json_response(&subnets), RESOURCE_STREAMS, metrics)
 }
 
-/// Returns a stream slice for the given subnet; or a 404 response if a stream
-/// for the respective subnet does not exist.
+/// Returns a stream slice for the given subnet (zstd compressed if
+/// `accepts_zstd`); or a 404 response if a stream for the respective subnet
+/// does not exist.
 fn handle_stream(
     subnet_id: SubnetId,
     witness_begin: Option<StreamIndex>,
     msg_begin: Option<StreamIndex>,
     msg_limit: Option<usize>,
     byte_limit: Option<usize>,
+    accepts_zstd: bool,
     certified_stream_store: &impl CertifiedStreamStore,
     metrics: &XNetEndpointMetrics,
 ) -> Response<Body> {
@@ -461,7 +512,13 @@ fn handle_stream(
                 .slice_payload_size
                 .observe(stream.payload.len() as f64);
             observe_response_size(
-                || proto_response::<_, pb::CertifiedStreamSlice>(stream),
+                || {
+                    if accepts_zstd {
+                        zstd_proto_response::<_, pb::CertifiedStreamSlice>(stream, metrics)
+                    } else {
+                        proto_response::<_, pb::CertifiedStreamSlice>(stream)
+                    }
+                },
                 RESOURCE_STREAM,
                 metrics,
             )
@@ -509,16 +566,44 @@ where
     let buf = M::proxy_encode(r);
     let size_bytes = buf.len();
 
+    let response = proto_response_builder().body(buf.into()).unwrap();
+
+    (response, size_bytes)
+}
+
+/// Serializes the response as Protobuf and compresses it using zstd, recording
+/// the achieved compression ratio.
+pub(crate) fn zstd_proto_response<R, M>(
+    r: R,
+    metrics: &XNetEndpointMetrics,
+) -> (Response<Body>, usize)
+where
+    M: ProtoProxy<R>,
+{
+    let buf = M::proxy_encode(r);
+    let compressed = zstd::bulk::compress(&buf, zstd::DEFAULT_COMPRESSION_LEVEL)
+        .expect("Failed to compress response");
+    let size_bytes = compressed.len();
+    metrics
+        .slice_compression_ratio
+        .observe(buf.len() as f64 / size_bytes.max(1) as f64);
+
+    let response = proto_response_builder()
+        .header(CONTENT_ENCODING, ZSTD_ENCODING)
+        .body(compressed.into())
+        .unwrap();
+
+    (response, size_bytes)
+}
+
+/// Returns a response builder with the headers of a Protobuf response.
+fn proto_response_builder() -> Builder {
     // Headers borrowed from Spring Framework -- https://bit.ly/32EDqoo -- and Google's Protobuf
     // reference -- https://bit.ly/35Q4yml. Might come in handy for e.g. a browser extension.
-    let response = Response::builder()
+    Response::builder()
         .header("Content-Type", "application/x-protobuf")
         .header("X-Protobuf-Schema", "certified_stream_slice.proto")
         .header("X-Protobuf-Message", "xnet.v1.CertifiedStreamSlice")
-        .body(buf.into())
-        .unwrap();
-
-    (response, size_bytes)
 }
 
 /// Produces a 204 No Content response.
@@ -93,6 +93,7 @@ fn query_streams() {
         let config = Config {
             xnet_ip_addr: addr.ip().to_string(),
             xnet_port: addr.port(),
+            ..Default::default()
         };
 
         let xnet_endpoint = XNetEndpoint::new(
@@ -134,6 +135,7 @@ fn query_stream() {
         let config = Config {
             xnet_ip_addr: addr.ip().to_string(),
             xnet_port: addr.port(),
+            ..Default::default()
         };
 
         let xnet_endpoint = XNetEndpoint::new(
@@ -204,6 +206,7 @@ fn query_stream_parallel() {
         let config = Config {
             xnet_ip_addr: addr.ip().to_string(),
             xnet_port: addr.port(),
+            ..Default::default()
         };
 
         let xnet_endpoint = XNetEndpoint::new(
@@ -254,6 +257,7 @@ async fn handle_streams() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -289,6 +293,7 @@ async fn handle_existing_stream_impl(
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -439,6 +444,7 @@ async fn handle_stream_with_witness_begin() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -468,6 +474,7 @@ async fn handle_stream_no_index() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -504,6 +511,7 @@ async fn handle_stream_with_byte_limit() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -528,6 +536,73 @@ async fn handle_stream_with_byte_limit() {
     );
 }
 
+#[tokio::test]
+async fn handle_stream_zstd_compressed() {
+    let fixture = EndpointTestFixture::with_replicated_state();
+
+    let msg_limit = 20;
+    let url = Url::parse(&format!(
+        "http://localhost/api/v1/stream/{DST_SUBNET}?msg_begin={STREAM_BEGIN}&msg_limit={msg_limit}"
+    ))
+    .unwrap();
+
+    let response = route_request(
+        url,
+        true,
+        &*fixture.state_manager,
+        &XNetEndpointMetrics::new(&fixture.metrics),
+    );
+    assert_eq!(
+        Some(ZSTD_ENCODING),
+        response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .map(|value| value.to_str().unwrap())
+    );
+    let (status_code, body) = parse_response(response).await;
+
+    // The decompressed body is the exact same encoded slice.
+    let body = zstd::bulk::decompress(&body, 1 << 20).unwrap();
+    assert_response_is_slice(
+        status_code,
+        body,
+        STREAM_BEGIN,
+        STREAM_BEGIN,
+        msg_limit,
+        None,
+    );
+    assert_eq!(
+        metric_vec(&[(&[("resource", "stream"), ("status", "200")], 1)]),
+        fixture.request_counts()
+    );
+    assert_eq!(1, fixture.slice_payload_size_stats().count);
+    assert_eq!(
+        1,
+        fetch_histogram_stats(&fixture.metrics, METRIC_SLICE_COMPRESSION_RATIO)
+            .unwrap()
+            .count
+    );
+}
+
+#[test]
+fn accepts_zstd_parses_accept_encoding() {
+    fn accepts(values: &[&str]) -> bool {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(ACCEPT_ENCODING, value.parse().unwrap());
+        }
+        accepts_zstd(&headers)
+    }
+
+    assert!(!accepts(&[]));
+    assert!(!accepts(&["gzip, br"]));
+    assert!(!accepts(&["zstd;q=0"]));
+    assert!(!accepts(&["gzip, zstd; q=0.0"]));
+    assert!(accepts(&["zstd"]));
+    assert!(accepts(&["gzip, ZSTD;q=0.5"]));
+    assert!(accepts(&["gzip", "zstd"]));
+}
+
 #[tokio::test]
 async fn handle_stream_nonexistent() {
     let fixture = EndpointTestFixture::with_replicated_state();
@@ -536,6 +611,7 @@ async fn handle_stream_nonexistent() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -557,6 +633,7 @@ async fn handle_bad_api_path() {
 
     let response = route_request(
         url,
+        false,
         &*fixture.state_manager,
         &XNetEndpointMetrics::new(&fixture.metrics),
     );
@@ -350,11 +350,12 @@ async fn transform_adapter_response(
     let query_execution_input = QueryExecutionInput {
         query,
         certificate_delegation_with_metadata: delegation_from_nns,
+        continuation_token: None,
     };
 
     match Oneshot::new(query_handler, query_execution_input).await {
         Ok(query_response) => match query_response {
-            Ok((res, _time)) => match res {
+            Ok((res, _time, _token)) => match res {
                 Ok(wasm_result) => match wasm_result {
                     WasmResult::Reply(reply) => Ok(reply),
                     WasmResult::Reject(reject_message) => {
@@ -393,12 +394,14 @@ mod tests {
         HttpsOutcallRequest, HttpsOutcallResponse,
         https_outcalls_service_server::{HttpsOutcallsService, HttpsOutcallsServiceServer},
     };
-    use ic_interfaces::execution_environment::{QueryExecutionError, QueryExecutionResponse};
+    use ic_interfaces::execution_environment::{
+        QueryContinuationToken, QueryExecutionError, QueryExecutionResponse,
+    };
     use ic_logger::replica_logger::no_op_logger;
     use ic_test_utilities_types::messages::RequestBuilder;
     use ic_types::canister_http::{Replication, Transform};
     use ic_types::{
-        Time, canister_http::CanisterHttpMethod, messages::CallbackId, time::UNIX_EPOCH,
+        Height, Time, canister_http::CanisterHttpMethod, messages::CallbackId, time::UNIX_EPOCH,
         time::current_time,
     };
     use std::convert::TryFrom;
@@ -712,6 +715,10 @@ mod tests {
                         + 1
                 ])),
                 current_time(),
+                QueryContinuationToken {
+                    height: Height::new(0),
+                    canister_id: CanisterId::ic_00(),
+                },
             )));
         });
 
@@ -860,6 +867,10 @@ mod tests {
                     .unwrap(),
                 )),
                 current_time(),
+                QueryContinuationToken {
+                    height: Height::new(0),
+                    canister_id: CanisterId::ic_00(),
+                },
             )));
         });
 
@@ -9,3 +9,6 @@ mod host;
 pub use host::server::run_server;
 
 pub mod protocol;
+
+#[cfg(target_os = "linux")]
+pub mod stream;
@@ -0,0 +1,123 @@
+//! Asynchronous (tokio) wrappers around the blocking vsock primitives.
+//!
+//! The command protocol between GuestOS and HostOS uses blocking sockets, which
+//! is fine for its one-shot request/response exchanges. Long-lived connections
+//! that are multiplexed by a tokio runtime (e.g., an RPC channel) need
+//! non-blocking sockets registered with the runtime's reactor instead, which is
+//! what [`AsyncVsockStream`] and [`AsyncVsockListener`] provide.
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+use tokio::io::unix::AsyncFd;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use vsock::{VsockListener, VsockStream};
+
+pub use vsock::{VMADDR_CID_ANY, VMADDR_CID_HOST, VsockAddr};
+
+/// A vsock stream that implements tokio's [`AsyncRead`] and [`AsyncWrite`].
+pub struct AsyncVsockStream {
+    inner: AsyncFd<VsockStream>,
+}
+
+impl AsyncVsockStream {
+    /// Connects to the given `cid` and `port`.
+    ///
+    /// Establishing a vsock connection is a blocking operation, so it is
+    /// performed on tokio's blocking thread pool.
+    pub async fn connect(cid: u32, port: u32) -> io::Result<Self> {
+        let stream =
+            tokio::task::spawn_blocking(move || VsockStream::connect(&VsockAddr::new(cid, port)))
+                .await
+                .map_err(io::Error::other)??;
+        Self::from_std(stream)
+    }
+
+    fn from_std(stream: VsockStream) -> io::Result<Self> {
+        stream.set_nonblocking(true)?;
+        Ok(Self {
+            inner: AsyncFd::new(stream)?,
+        })
+    }
+
+    pub fn peer_addr(&self) -> io::Result<VsockAddr> {
+        self.inner.get_ref().peer_addr()
+    }
+}
+
+impl AsyncRead for AsyncVsockStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            let mut guard = ready!(self.inner.poll_read_ready(cx))?;
+            let unfilled = buf.initialize_unfilled();
+            match guard.try_io(|inner| inner.get_ref().read(unfilled)) {
+                Ok(Ok(len)) => {
+                    buf.advance(len);
+                    return Poll::Ready(Ok(()));
+                }
+                Ok(Err(err)) => return Poll::Ready(Err(err)),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+}
+
+impl AsyncWrite for AsyncVsockStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        loop {
+            let mut guard = ready!(self.inner.poll_write_ready(cx))?;
+            match guard.try_io(|inner| inner.get_ref().write(buf)) {
+                Ok(result) => return Poll::Ready(result),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Writes go straight to the socket, there is nothing to flush.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.inner.get_ref().shutdown(std::net::Shutdown::Write))
+    }
+}
+
+/// A vsock listener that accepts connections as [`AsyncVsockStream`]s.
+pub struct AsyncVsockListener {
+    inner: AsyncFd<VsockListener>,
+}
+
+impl AsyncVsockListener {
+    /// Binds a listener to the given `cid` and `port`. Use
+    /// [`vsock::VMADDR_CID_ANY`] to accept connections from any peer.
+    pub fn bind(cid: u32, port: u32) -> io::Result<Self> {
+        let listener = VsockListener::bind(&VsockAddr::new(cid, port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self {
+            inner: AsyncFd::new(listener)?,
+        })
+    }
+
+    pub async fn accept(&self) -> io::Result<(AsyncVsockStream, VsockAddr)> {
+        loop {
+            let mut guard = self.inner.readable().await?;
+            match guard.try_io(|inner| inner.get_ref().accept()) {
+                Ok(Ok((stream, addr))) => return Ok((AsyncVsockStream::from_std(stream)?, addr)),
+                Ok(Err(err)) => return Err(err),
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    pub fn local_addr(&self) -> io::Result<VsockAddr> {
+        self.inner.get_ref().local_addr()
+    }
+}
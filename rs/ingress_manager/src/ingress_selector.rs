@@ -24,9 +24,11 @@ use ic_types::{
     batch::{IngressPayload, ValidationContext},
     consensus::Payload,
     ingress::{IngressSets, IngressStatus},
-    messages::{MessageId, SignedIngress, extract_effective_canister_id},
+    messages::{
+        HttpRequest, MessageId, SignedIngress, SignedIngressContent, extract_effective_canister_id,
+    },
 };
-use ic_validator::RequestValidationError;
+use ic_validator::{CanisterIdSet, RequestValidationError};
 use std::{collections::BTreeMap, collections::HashMap, sync::Arc};
 
 /// Number of round-robin iterations that need to happen, before we weaken the selection
@@ -171,6 +173,7 @@ impl IngressSelector for IngressManager {
                         &past_ingress_set,
                         messages_in_payload.len(),
                         &mut cycles_needed,
+                        None,
                     );
                     // Any message that generates validation errors gets removed from
                     // the canister's queue.
@@ -331,7 +334,8 @@ impl IngressSelector for IngressManager {
         // Tracks the sum of cycles needed per canister.
         let mut cycles_needed: BTreeMap<CanisterId, Cycles> = BTreeMap::new();
 
-        // Validate each ingress message in the payload
+        // Deserialize each ingress message in the payload
+        let mut ingress_messages = Vec::with_capacity(payload.message_count());
         for (ingress_id, maybe_ingress) in payload.iter() {
             let ingress = match maybe_ingress {
                 Ok(ingress) => ingress,
@@ -354,8 +358,27 @@ impl IngressSelector for IngressManager {
                 ));
             }
 
+            ingress_messages.push((ingress_id.clone(), ingress));
+        }
+
+        // Validate the requests of all messages at once, so that their
+        // signatures are verified as a batch.
+        let requests: Vec<&HttpRequest<SignedIngressContent>> = ingress_messages
+            .iter()
+            .map(|(_, ingress)| ingress.as_ref())
+            .collect();
+        let request_validations = self.request_validator.validate_requests(
+            &requests,
+            context.time,
+            &self.registry_root_of_trust_provider(context.registry_version),
+        );
+
+        // Validate each ingress message in the payload
+        for ((ingress_id, ingress), request_validation) in
+            ingress_messages.into_iter().zip(request_validations)
+        {
             self.validate_ingress(
-                ingress_id.clone(),
+                ingress_id,
                 &ingress,
                 &state,
                 context,
@@ -363,6 +386,7 @@ impl IngressSelector for IngressManager {
                 &past_ingress,
                 0, // message count is checked above.
                 &mut cycles_needed,
+                Some(request_validation),
             )?;
         }
 
@@ -428,6 +452,11 @@ impl IngressSelector for IngressManager {
     fn request_purge_finalized_messages(&self, message_ids: Vec<IngressMessageId>) {
         self.messages_to_purge.write().unwrap().push(message_ids)
     }
+
+    fn has_pending_ingress(&self) -> bool {
+        let ingress_pool = self.ingress_pool.read().unwrap();
+        ingress_pool.validated().size() > 0 || ingress_pool.unvalidated().size() > 0
+    }
 }
 
 impl IngressManager {
@@ -442,6 +471,7 @@ impl IngressManager {
         past_ingress_set: &IngressSetChain<IngressHistorySet>,
         num_messages: usize,
         cycles_needed: &mut BTreeMap<CanisterId, Cycles>,
+        request_validation: Option<Result<CanisterIdSet, RequestValidationError>>,
     ) -> ValidationResult<IngressPayloadValidationError> {
         let ingress_message_size = signed_ingress.count_bytes();
         // The message is invalid if its size is larger than the configured maximum.
@@ -545,12 +575,16 @@ impl IngressManager {
         };
 
         // Do not include the message if it is considered invalid with
-        // respect to the given context (expiry & registry_version).
-        if let Err(err) = self.request_validator.validate_request(
-            signed_ingress.as_ref(),
-            context.time,
-            &self.registry_root_of_trust_provider(context.registry_version),
-        ) {
+        // respect to the given context (expiry & registry_version). The
+        // request may already have been validated as part of a batch.
+        let request_validation = request_validation.unwrap_or_else(|| {
+            self.request_validator.validate_request(
+                signed_ingress.as_ref(),
+                context.time,
+                &self.registry_root_of_trust_provider(context.registry_version),
+            )
+        });
+        if let Err(err) = request_validation {
             let message_id = MessageId::from(&ingress_id);
             return Err(ValidationError::InvalidArtifact(match err {
                 RequestValidationError::InvalidRequestExpiry(msg)
@@ -55,5 +55,7 @@ mock! {
      ) -> ic_types::ingress::IngressSets;
 
      fn request_purge_finalized_messages(&self, message_ids: Vec<ic_types::artifact::IngressMessageId>);
+
+     fn has_pending_ingress(&self) -> bool;
    }
 }
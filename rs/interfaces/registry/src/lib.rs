@@ -156,8 +156,22 @@ pub trait RegistryDataProvider: Send + Sync {
     /// return records must represent all updates to the registry for all
     /// versions in the interval (version..max_v]. In particular, each version
     /// must be fully contained.
+    ///
+    /// (3) If `version` is below the version returned by
+    /// `get_oldest_version`, (2) only holds for the versions in the interval
+    /// [oldest_version..max_v].
     fn get_updates_since(
         &self,
         version: RegistryVersion,
     ) -> Result<Vec<RegistryRecord>, RegistryDataProviderError>;
+
+    /// Returns the oldest version of the registry that the data provider can
+    /// represent, e.g., because the history below it was pruned.
+    ///
+    /// The records returned by `get_updates_since` for an older version only
+    /// hold the latest record of every key up to the oldest version, so that
+    /// the registry at the versions below it cannot be reconstructed.
+    fn get_oldest_version(&self) -> Result<RegistryVersion, RegistryDataProviderError> {
+        Ok(ZERO_REGISTRY_VERSION)
+    }
 }
@@ -21,9 +21,10 @@ use ic_base_types::{NumBytes, SubnetId};
 use ic_types::{
     Height, Time,
     batch::{BatchPayload, ValidationContext},
-    consensus::{Payload, block_maker::SubnetRecords},
+    consensus::{Payload, block_maker::SubnetRecords, trace::HeightTrace},
     registry::RegistryClientError,
 };
+use std::ops::RangeInclusive;
 
 pub mod errors;
 
@@ -56,6 +57,24 @@ pub trait PayloadBuilder: Send + Sync {
         payload: &Payload,
         past_payloads: &[(Height, Time, Payload)],
     ) -> ValidationResult<PayloadValidationError>;
+
+    /// Returns `true` if there may be ingress messages waiting to be included
+    /// in a block. See [`IngressSelector::has_pending_ingress`].
+    ///
+    /// [`IngressSelector::has_pending_ingress`]: crate::ingress_manager::IngressSelector::has_pending_ingress
+    fn has_pending_ingress(&self) -> bool;
+}
+
+/// Gives access to the per-height trace of consensus events recorded by this
+/// replica.
+pub trait ConsensusTraceReader: Send + Sync {
+    /// Returns the recorded traces of the heights in the given range, in
+    /// increasing height order, and at most `max_heights` of them.
+    fn get_height_traces(
+        &self,
+        heights: RangeInclusive<Height>,
+        max_heights: usize,
+    ) -> Vec<HeightTrace>;
 }
 
 #[derive(Debug)]
@@ -8,8 +8,8 @@ use ic_management_canister_types_private::MasterPublicKeyId;
 use ic_registry_provisional_whitelist::ProvisionalWhitelist;
 use ic_registry_subnet_type::SubnetType;
 use ic_types::{
-    Cycles, ExecutionRound, Height, NodeId, NumInstructions, Randomness, RegistryVersion,
-    ReplicaVersion, Time,
+    CanisterId, Cycles, ExecutionRound, Height, NodeId, NumInstructions, PrincipalId, Randomness,
+    RegistryVersion, ReplicaVersion, Time,
     batch::{CanisterCyclesCostSchedule, ChainKeyData},
     ingress::{IngressStatus, WasmResult},
     messages::{
@@ -551,12 +551,66 @@ pub type IngressFilterService =
 pub enum QueryExecutionError {
     #[error("Certified state is not available yet")]
     CertifiedStateUnavailable,
+    #[error("Continuation token is for canister {token_canister_id}, not {canister_id}")]
+    ContinuationTokenCanisterMismatch {
+        token_canister_id: CanisterId,
+        canister_id: CanisterId,
+    },
+    #[error("Certified state at height {0} is not available yet")]
+    PinnedStateNotCertified(Height),
+    #[error("Certified state at height {0} is no longer available")]
+    PinnedStateRemoved(Height),
+}
+
+/// Pins a sequence of queries to the certified state the first of them was
+/// executed against, e.g. to read a large result in several pages from the
+/// same state.
+///
+/// Every query execution returns a token for the state it was executed
+/// against. Passing that token with follow-up queries to the same canister
+/// executes them against the same state, for as long as the state manager
+/// retains that state. As the certification of older states is not retained,
+/// follow-up queries are executed without a data certificate.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct QueryContinuationToken {
+    pub height: Height,
+    pub canister_id: CanisterId,
+}
+
+impl QueryContinuationToken {
+    /// Encodes the token as the big-endian height followed by the canister ID.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.height.get().to_be_bytes().to_vec();
+        bytes.extend_from_slice(self.canister_id.get_ref().as_slice());
+        bytes
+    }
+}
+
+impl TryFrom<&[u8]> for QueryContinuationToken {
+    type Error = String;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < 8 {
+            return Err(format!(
+                "Continuation token must be at least 8 bytes long, got {}",
+                bytes.len()
+            ));
+        }
+        let (height, canister_id) = bytes.split_at(8);
+        let canister_id = PrincipalId::try_from(canister_id)
+            .map_err(|err| format!("Invalid canister ID in continuation token: {err}"))?;
+        Ok(Self {
+            height: Height::new(u64::from_be_bytes(height.try_into().unwrap())),
+            canister_id: CanisterId::unchecked_from_principal(canister_id),
+        })
+    }
 }
 
 /// The response type to a `call()` request in [`QueryExecutionService`].
-/// An Ok response contains the response from the canister and the batch time at the time of execution.
+/// An Ok response contains the response from the canister, the batch time at the time of execution
+/// and a token to execute follow-up queries against the same state.
 pub type QueryExecutionResponse =
-    Result<(Result<WasmResult, UserError>, Time), QueryExecutionError>;
+    Result<(Result<WasmResult, UserError>, Time, QueryContinuationToken), QueryExecutionError>;
 
 /// The input type to a `call()` request in [`QueryExecutionService`].
 #[derive(Debug)]
@@ -564,12 +618,53 @@ pub struct QueryExecutionInput {
     pub query: Query,
     pub certificate_delegation_with_metadata:
         Option<(CertificateDelegation, CertificateDelegationMetadata)>,
+    /// If set, the query is executed against the state the token was issued
+    /// for instead of the latest certified state.
+    pub continuation_token: Option<QueryContinuationToken>,
 }
 
 /// Interface for the component to execute queries.
 pub type QueryExecutionService =
     BoxCloneService<QueryExecutionInput, QueryExecutionResponse, Infallible>;
 
+/// A request that a canister would have sent during a dry run.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct DryRunOutgoingRequest {
+    pub receiver: CanisterId,
+    pub method_name: String,
+    pub payment: Cycles,
+    pub payload_size: NumBytes,
+}
+
+/// Report of executing an update method against the latest certified state
+/// without committing any of its changes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DryRunReport {
+    /// The result of the call, or `None` if the call did not reply and would
+    /// have awaited the responses to its outgoing requests.
+    pub result: Result<Option<WasmResult>, UserError>,
+    pub instructions_used: NumInstructions,
+    /// The cycles the canister would have been charged for executing the call,
+    /// including the fees and the cycles attached to its outgoing requests.
+    /// The ingress induction fee is not included as it depends on the size of
+    /// the signed envelope.
+    pub cycles_charged: Cycles,
+    /// The number of OS pages of heap and stable memory the call dirtied.
+    pub dirty_pages: u64,
+    pub heap_growth: NumBytes,
+    pub outgoing_requests: Vec<DryRunOutgoingRequest>,
+}
+
+/// The response type to a `call()` request in [`DryRunExecutionService`].
+/// An Ok response contains the report or the error preventing the execution
+/// and the batch time at the time of execution.
+pub type DryRunExecutionResponse =
+    Result<(Result<DryRunReport, UserError>, Time), QueryExecutionError>;
+
+/// Interface for the component to dry-run update calls. Dry runs are
+/// scheduled, limited and accounted for like queries.
+pub type DryRunExecutionService = BoxCloneService<Query, DryRunExecutionResponse, Infallible>;
+
 /// Errors that can be returned when reading/writing from/to ingress history.
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum IngressHistoryError {
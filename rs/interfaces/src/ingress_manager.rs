@@ -159,6 +159,10 @@ pub trait IngressSelector: Send + Sync {
     ///
     /// The actual purge is not required to happen immediately.
     fn request_purge_finalized_messages(&self, message_ids: Vec<IngressMessageId>);
+
+    /// Returns `true` if the ingress pool is not empty, i.e. there may be
+    /// ingress messages waiting to be included in a block.
+    fn has_pending_ingress(&self) -> bool;
 }
 
 /*
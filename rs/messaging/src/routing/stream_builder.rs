@@ -37,6 +37,8 @@ struct StreamBuilderMetrics {
     pub stream_begin: IntGaugeVec,
     /// Signals end, by remote subnet.
     pub signals_end: IntGaugeVec,
+    /// Congestion level signalled by the remote subnet, by remote subnet.
+    pub remote_congestion_level: IntGaugeVec,
     /// Routed XNet messages, by type and status.
     pub routed_messages: IntCounterVec,
     /// Successfully routed XNet messages' total payload size.
@@ -56,6 +58,7 @@ const METRIC_STREAM_MESSAGES: &str = "mr_stream_messages";
 const METRIC_STREAM_BYTES: &str = "mr_stream_bytes";
 const METRIC_STREAM_BEGIN: &str = "mr_stream_begin";
 const METRIC_SIGNALS_END: &str = "mr_signals_end";
+const METRIC_REMOTE_CONGESTION_LEVEL: &str = "mr_remote_congestion_level";
 const METRIC_ROUTED_MESSAGES: &str = "mr_routed_message_count";
 const METRIC_ROUTED_PAYLOAD_SIZES: &str = "mr_routed_payload_size_bytes";
 
@@ -99,6 +102,11 @@ impl StreamBuilderMetrics {
             "Signals end, by remote subnet",
             &[LABEL_REMOTE],
         );
+        let remote_congestion_level = metrics_registry.int_gauge_vec(
+            METRIC_REMOTE_CONGESTION_LEVEL,
+            "Congestion level signalled by the remote subnet, by remote subnet.",
+            &[LABEL_REMOTE],
+        );
         let routed_messages = metrics_registry.int_counter_vec(
             METRIC_ROUTED_MESSAGES,
             "Routed XNet messages, by type and status.",
@@ -141,6 +149,7 @@ impl StreamBuilderMetrics {
             stream_bytes,
             stream_begin,
             signals_end,
+            remote_congestion_level,
             routed_messages,
             routed_payload_sizes,
             critical_error_infinite_loops,
@@ -163,7 +172,10 @@ pub(crate) trait StreamBuilder: Send {
 /// Routes messages from canister output queues into streams, up to the specified limits.
 ///
 /// At most `max_stream_messages` are enqueued into a stream; but only until its
-/// `count_bytes()` is greater than or equal to `target_stream_size_bytes`.
+/// `count_bytes()` is greater than or equal to `target_stream_size_bytes`. For
+/// requests, both limits are halved for every congestion level signalled by the
+/// remote subnet, so requests are held back in the output queues of the
+/// producing canisters instead of piling up in the stream.
 pub(crate) struct StreamBuilderImpl {
     subnet_id: SubnetId,
     max_stream_messages: usize,
@@ -280,18 +292,26 @@ impl StreamBuilderImpl {
         }
 
         // Tests whether a stream is over the message count limit, byte limit or (if
-        // directed at a system subnet) over `2 * SYSTEM_SUBNET_STREAM_MSG_LIMIT`.
+        // directed at a system subnet) over `2 * SYSTEM_SUBNET_STREAM_MSG_LIMIT`. For
+        // requests, the message count and byte limits are scaled down by the
+        // congestion level signalled by the remote subnet.
         let is_at_limit = |stream: &btree_map::Entry<SubnetId, Stream>,
-                           destination_subnet_type: SubnetType|
+                           destination_subnet_type: SubnetType,
+                           is_request: bool|
          -> bool {
             let stream = match stream {
                 btree_map::Entry::Occupied(occupied_entry) => occupied_entry.get(),
                 btree_map::Entry::Vacant(_) => return false,
             };
             let stream_messages_len = stream.messages().len();
+            let congestion_level = if is_request {
+                stream.reverse_stream_congestion_level()
+            } else {
+                0
+            };
 
-            if stream_messages_len >= self.max_stream_messages
-                || stream.count_bytes() >= self.target_stream_size_bytes
+            if stream_messages_len >= self.max_stream_messages >> congestion_level
+                || stream.count_bytes() >= self.target_stream_size_bytes >> congestion_level
             {
                 // At limit if message count or byte size limits (enforced across all outgoing
                 // streams) are hit.
@@ -352,6 +372,7 @@ impl StreamBuilderImpl {
                             *subnet_types
                                 .get(&dst_subnet_id)
                                 .unwrap_or(&SubnetType::Application),
+                            matches!(msg, RequestOrResponse::Request(_)),
                         )
                     {
                         // Stream full, skip all other messages to this destination.
@@ -501,26 +522,33 @@ impl StreamBuilderImpl {
                     stream.count_bytes(),
                     stream.messages_begin(),
                     stream.signals_end(),
+                    stream.reverse_stream_congestion_level(),
                 )
             })
-            .for_each(|(subnet, len, size_bytes, begin, signals_end)| {
-                self.metrics
-                    .stream_messages
-                    .with_label_values(&[&subnet])
-                    .set(len as i64);
-                self.metrics
-                    .stream_bytes
-                    .with_label_values(&[&subnet])
-                    .set(size_bytes as i64);
-                self.metrics
-                    .stream_begin
-                    .with_label_values(&[&subnet])
-                    .set(begin.get() as i64);
-                self.metrics
-                    .signals_end
-                    .with_label_values(&[&subnet])
-                    .set(signals_end.get() as i64);
-            });
+            .for_each(
+                |(subnet, len, size_bytes, begin, signals_end, remote_congestion_level)| {
+                    self.metrics
+                        .stream_messages
+                        .with_label_values(&[&subnet])
+                        .set(len as i64);
+                    self.metrics
+                        .stream_bytes
+                        .with_label_values(&[&subnet])
+                        .set(size_bytes as i64);
+                    self.metrics
+                        .stream_begin
+                        .with_label_values(&[&subnet])
+                        .set(begin.get() as i64);
+                    self.metrics
+                        .signals_end
+                        .with_label_values(&[&subnet])
+                        .set(signals_end.get() as i64);
+                    self.metrics
+                        .remote_congestion_level
+                        .with_label_values(&[&subnet])
+                        .set(remote_congestion_level as i64);
+                },
+            );
 
         {
             // Record the enqueuing time of any messages newly enqueued into `streams`.
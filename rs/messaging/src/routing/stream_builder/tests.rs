@@ -529,6 +529,46 @@ fn build_streams_impl_respects_message_limit() {
     build_streams_impl_respects_limits(4, 1_000_000, 4);
 }
 
+// Tests that the message limit for requests is halved for every congestion
+// level signalled by the remote subnet.
+#[test]
+fn build_streams_impl_scales_down_limits_under_congestion() {
+    with_test_replica_logger(|log| {
+        let msgs = generate_messages_for_test(/* senders = */ 2, /* receivers = */ 2);
+        assert!(msgs.len() > 8);
+
+        let (stream_builder, mut provided_state, metrics_registry) =
+            new_fixture_with_limits(&log, 8, TARGET_STREAM_SIZE_BYTES);
+        provided_state.metadata.network_topology.routing_table = Arc::new(RoutingTable::try_from(
+            btreemap! {
+                CanisterIdRange{ start: CanisterId::from(0), end: CanisterId::from(0xfff) } => REMOTE_SUBNET,
+            },
+        ).unwrap());
+        provided_state.put_canister_states(canister_states_with_outputs(msgs));
+
+        // The remote subnet signalled congestion level 1.
+        let mut stream = Stream::default();
+        stream.set_reverse_stream_congestion_level(1);
+        provided_state.with_streams(btreemap![REMOTE_SUBNET => stream]);
+
+        let result_state = stream_builder.build_streams_impl(provided_state);
+
+        // Only half of the message limit was routed.
+        assert_eq!(
+            4,
+            result_state
+                .get_stream(&REMOTE_SUBNET)
+                .unwrap()
+                .messages()
+                .len()
+        );
+        assert_eq!(
+            metric_vec(&[(&[(LABEL_REMOTE, &REMOTE_SUBNET.to_string())], 1)]),
+            fetch_int_gauge_vec(&metrics_registry, METRIC_REMOTE_CONGESTION_LEVEL)
+        );
+    });
+}
+
 // Tests that messages addressed to canisters not mapped to a known subnet
 // result in reject Responses.
 #[test]
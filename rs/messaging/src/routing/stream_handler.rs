@@ -12,6 +12,7 @@ use ic_interfaces::messaging::{
 use ic_logger::{ReplicaLogger, debug, error, info, trace};
 use ic_metrics::MetricsRegistry;
 use ic_metrics::buckets::{add_bucket, decimal_buckets};
+use ic_replicated_state::canister_state::DEFAULT_QUEUE_CAPACITY;
 use ic_replicated_state::metadata_state::{Stream, StreamMap};
 use ic_replicated_state::replicated_state::{
     LABEL_VALUE_QUEUE_FULL, MR_SYNTHETIC_REJECT_MESSAGE_MAX_LEN, ReplicatedStateMessageRouting,
@@ -21,7 +22,10 @@ use ic_types::messages::{
     MAX_INTER_CANISTER_PAYLOAD_IN_BYTES_U64, MAX_RESPONSE_COUNT_BYTES, Payload, Refund,
     RejectContext, Request, RequestOrResponse, Response, StreamMessage,
 };
-use ic_types::xnet::{RejectReason, RejectSignal, StreamIndex, StreamIndexedQueue, StreamSlice};
+use ic_types::xnet::{
+    MAX_STREAM_CONGESTION_LEVEL, RejectReason, RejectSignal, StreamIndex, StreamIndexedQueue,
+    StreamSlice,
+};
 use ic_types::{CanisterId, SubnetId};
 use prometheus::{Histogram, IntCounter, IntCounterVec, IntGaugeVec};
 use std::cell::RefCell;
@@ -42,6 +46,8 @@ struct StreamHandlerMetrics {
     pub gced_xnet_reject_signals: IntCounter,
     /// Change in stream flags observed.
     pub stream_flags_changes: IntCounter,
+    /// Congestion level signalled to remote subnets, by remote subnet.
+    pub signalled_congestion_level: IntGaugeVec,
     /// Backlog of XNet messages based on end in stream header and last message
     /// in slice, per subnet.
     pub xnet_message_backlog: IntGaugeVec,
@@ -66,6 +72,7 @@ const METRIC_INDUCTED_XNET_PAYLOAD_SIZES: &str = "mr_inducted_xnet_payload_size_
 const METRIC_GCED_XNET_MESSAGES: &str = "mr_gced_xnet_message_count";
 const METRIC_GCED_XNET_REJECT_SIGNALS: &str = "mr_gced_xnet_reject_signal_count";
 const METRIC_STREAM_FLAGS_CHANGES: &str = "mr_stream_flags_changes_count";
+const METRIC_SIGNALLED_CONGESTION_LEVEL: &str = "mr_signalled_congestion_level";
 
 const METRIC_XNET_MESSAGE_BACKLOG: &str = "mr_xnet_message_backlog";
 
@@ -120,6 +127,11 @@ impl StreamHandlerMetrics {
             METRIC_STREAM_FLAGS_CHANGES,
             "Change in stream flags observed.",
         );
+        let signalled_congestion_level = metrics_registry.int_gauge_vec(
+            METRIC_SIGNALLED_CONGESTION_LEVEL,
+            "Congestion level signalled to remote subnets, by remote subnet.",
+            &[LABEL_REMOTE],
+        );
         let xnet_message_backlog = metrics_registry.int_gauge_vec(
             METRIC_XNET_MESSAGE_BACKLOG,
             "Backlog of XNet messages, by sending subnet.",
@@ -165,6 +177,7 @@ impl StreamHandlerMetrics {
             gced_xnet_messages,
             gced_xnet_reject_signals,
             stream_flags_changes,
+            signalled_congestion_level,
             xnet_message_backlog,
             critical_error_bad_reject_signal_for_response,
             critical_error_induct_response_failed,
@@ -407,6 +420,9 @@ impl StreamHandlerImpl {
                         stream.set_reverse_stream_flags(*stream_slice.header().flags());
                         self.metrics.stream_flags_changes.inc();
                     }
+                    stream.set_reverse_stream_congestion_level(
+                        stream_slice.header().congestion_level(),
+                    );
 
                     // Respond to rejected requests and reroute rejected responses.
                     self.handle_rejected_messages(
@@ -643,6 +659,12 @@ impl StreamHandlerImpl {
     /// See [`Self::induct_message`] for the possible outcomes of inducting a
     /// message.
     ///
+    /// Also updates the congestion level signalled in the header of each reverse
+    /// stream (except the loopback stream) to the highest congestion level of the
+    /// canisters targeted by the inducted requests (see [`input_queues_congestion_level`]). In
+    /// the absence of such congestion the level decays by one per slice, so that
+    /// the sender speeds up gradually.
+    ///
     /// Updates `available_guaranteed_response_memory` to reflect change in memory
     /// usage, in such a way that it remains a lower-bound estimate of the actual
     /// available guaranteed response memory.
@@ -658,6 +680,7 @@ impl StreamHandlerImpl {
             // Output stream, for resulting signals and (in the initial iteration) reject
             // `Responses`.
             let stream = streams.entry(remote_subnet_id).or_default();
+            let mut congestion_level = 0;
 
             while let Some((stream_index, msg)) = stream_slice.pop_message() {
                 assert_eq!(
@@ -675,6 +698,10 @@ impl StreamHandlerImpl {
                     stream.reject_signals().len(),
                 );
 
+                let request_receiver = match &msg {
+                    StreamMessage::Request(request) => Some(request.receiver),
+                    StreamMessage::Response(_) | StreamMessage::Refund(_) => None,
+                };
                 self.induct_message(
                     msg,
                     remote_subnet_id,
@@ -682,6 +709,23 @@ impl StreamHandlerImpl {
                     stream,
                     available_guaranteed_response_memory,
                 );
+                if let Some(receiver) = request_receiver {
+                    let queue_full = stream.reject_signals().back()
+                        == Some(&RejectSignal::new(RejectReason::QueueFull, stream_index));
+                    let receiver_congestion_level = if queue_full {
+                        MAX_STREAM_CONGESTION_LEVEL
+                    } else {
+                        state
+                            .canister_state(&receiver)
+                            .map(|canister| {
+                                input_queues_congestion_level(
+                                    canister.system_state.queues().input_queues_message_count(),
+                                )
+                            })
+                            .unwrap_or_default()
+                    };
+                    congestion_level = congestion_level.max(receiver_congestion_level);
+                }
 
                 #[cfg(debug_assertions)]
                 {
@@ -696,6 +740,16 @@ impl StreamHandlerImpl {
                     state.assert_balance_with_messages(expected_balance);
                 }
             }
+
+            if remote_subnet_id != self.subnet_id {
+                let congestion_level =
+                    congestion_level.max(stream.congestion_level().saturating_sub(1));
+                stream.set_congestion_level(congestion_level);
+                self.metrics
+                    .signalled_congestion_level
+                    .with_label_values(&[&remote_subnet_id.to_string()])
+                    .set(congestion_level as i64);
+            }
         }
 
         state.put_streams(streams);
@@ -1148,6 +1202,15 @@ impl StreamHandlerImpl {
     }
 }
 
+/// Maps the number of messages in the input queues of a canister onto a
+/// congestion level in `[0, MAX_STREAM_CONGESTION_LEVEL]`, linearly, reaching
+/// the maximum as the count approaches `DEFAULT_QUEUE_CAPACITY`.
+pub(crate) fn input_queues_congestion_level(input_queues_message_count: usize) -> u8 {
+    let levels = MAX_STREAM_CONGESTION_LEVEL as usize + 1;
+    (input_queues_message_count * levels / DEFAULT_QUEUE_CAPACITY)
+        .min(MAX_STREAM_CONGESTION_LEVEL as usize) as u8
+}
+
 /// Returns a migration trace for `canister_id` in the network topology in `state` (if any).
 fn migration_trace(state: &ReplicatedState, canister_id: CanisterId) -> Option<Vec<SubnetId>> {
     state
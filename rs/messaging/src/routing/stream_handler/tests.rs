@@ -1067,6 +1067,45 @@ fn garbage_collect_local_state_success() {
     );
 }
 
+/// Tests that the congestion level signalled in the header of an incoming slice
+/// is recorded in the respective outgoing stream.
+#[test]
+fn garbage_collect_local_state_records_reverse_stream_congestion_level() {
+    with_test_setup(
+        // An outgoing stream with one message.
+        btreemap![REMOTE_SUBNET => StreamConfig {
+            begin: 31,
+            messages: vec![Request(*LOCAL_CANISTER, *REMOTE_CANISTER)],
+            signals_end: 43,
+            ..StreamConfig::default()
+        }],
+        // An incoming stream slice signalling congestion.
+        btreemap![REMOTE_SUBNET => StreamSliceConfig {
+            signals_end: 31,
+            messages_begin: 43,
+            congestion_level: 3,
+            ..StreamSliceConfig::default()
+        }],
+        |stream_handler, state, slices, _| {
+            assert_eq!(
+                0,
+                state
+                    .get_stream(&REMOTE_SUBNET)
+                    .unwrap()
+                    .reverse_stream_congestion_level()
+            );
+
+            let pruned_state =
+                stream_handler.garbage_collect_local_state(state, &mut (i64::MAX / 2), &slices);
+
+            let stream = pruned_state.get_stream(&REMOTE_SUBNET).unwrap();
+            assert_eq!(3, stream.reverse_stream_congestion_level());
+            // The congestion level signalled to the remote subnet is unaffected.
+            assert_eq!(0, stream.congestion_level());
+        },
+    );
+}
+
 /// Tests that garbage collecting a provided `ReplicatedState` results in all
 /// messages with matching signals being garbage collected or rerouted, as
 /// appropriate.
@@ -2761,6 +2800,138 @@ fn induct_stream_slices_with_refunds() {
 /// Tests that messages in the loopback stream and incoming slices are inducted
 /// (with signals added appropriately); and messages present in the initial
 /// state are garbage collected or rerouted as appropriate.
+/// Pushes `count` requests from `LOCAL_CANISTER` into its own input queue.
+fn fill_local_canister_input_queue(state: &mut ReplicatedState, count: usize) {
+    for i in 0..count {
+        state
+            .push_input(
+                RequestBuilder::new()
+                    .sender(*LOCAL_CANISTER)
+                    .receiver(*LOCAL_CANISTER)
+                    .sender_reply_callback(CallbackId::new(1_000 + i as u64))
+                    .build()
+                    .into(),
+                &mut (i64::MAX / 2),
+            )
+            .unwrap();
+    }
+}
+
+/// Tests that inducting requests signals the input queue occupancy of their
+/// receivers as congestion level in the reverse stream; and that the signalled
+/// congestion level decays by one level per slice without requests.
+#[test]
+fn induct_stream_slices_signals_congestion_level() {
+    with_test_setup(
+        btreemap![REMOTE_SUBNET => StreamConfig {
+            signals_end: 43,
+            ..StreamConfig::default()
+        }],
+        // An incoming stream slice with one request @43.
+        btreemap![REMOTE_SUBNET => StreamSliceConfig {
+            messages_begin: 43,
+            messages: vec![Request(*REMOTE_CANISTER, *LOCAL_CANISTER)],
+            ..StreamSliceConfig::default()
+        }],
+        |stream_handler, mut state, slices, metrics| {
+            // Together with the inducted request, the input queue will hold 3/5 of
+            // its capacity.
+            fill_local_canister_input_queue(&mut state, 3 * DEFAULT_QUEUE_CAPACITY / 5 - 1);
+
+            let state = stream_handler.induct_stream_slices(state, slices, &mut (i64::MAX / 2));
+
+            assert_eq!(
+                3,
+                state.get_stream(&REMOTE_SUBNET).unwrap().congestion_level()
+            );
+            assert_eq!(
+                metric_vec(&[(&[(LABEL_REMOTE, &REMOTE_SUBNET.to_string())], 3)]),
+                metrics.fetch_int_gauge_vec(METRIC_SIGNALLED_CONGESTION_LEVEL)
+            );
+
+            // An empty slice does not observe any congestion, the level decays.
+            let empty_slice = stream_slice_from_config(StreamSliceConfig {
+                messages_begin: 44,
+                ..StreamSliceConfig::default()
+            });
+            let state = stream_handler.induct_stream_slices(
+                state,
+                btreemap![REMOTE_SUBNET => empty_slice],
+                &mut (i64::MAX / 2),
+            );
+
+            assert_eq!(
+                2,
+                state.get_stream(&REMOTE_SUBNET).unwrap().congestion_level()
+            );
+        },
+    );
+}
+
+/// Tests that a request rejected because of a full input queue signals the
+/// maximum congestion level.
+#[test]
+fn induct_stream_slices_queue_full_signals_maximum_congestion_level() {
+    with_test_setup(
+        btreemap![REMOTE_SUBNET => StreamConfig {
+            signals_end: 43,
+            ..StreamConfig::default()
+        }],
+        // An incoming stream slice with one request @43.
+        btreemap![REMOTE_SUBNET => StreamSliceConfig {
+            messages_begin: 43,
+            messages: vec![Request(*REMOTE_CANISTER, *LOCAL_CANISTER)],
+            ..StreamSliceConfig::default()
+        }],
+        |stream_handler, mut state, slices, _| {
+            // Fill up the input queue from `REMOTE_CANISTER`.
+            for i in 0..DEFAULT_QUEUE_CAPACITY {
+                state
+                    .push_input(
+                        RequestBuilder::new()
+                            .sender(*REMOTE_CANISTER)
+                            .receiver(*LOCAL_CANISTER)
+                            .sender_reply_callback(CallbackId::new(1_000 + i as u64))
+                            .build()
+                            .into(),
+                        &mut (i64::MAX / 2),
+                    )
+                    .unwrap();
+            }
+
+            let state = stream_handler.induct_stream_slices(state, slices, &mut (i64::MAX / 2));
+
+            let stream = state.get_stream(&REMOTE_SUBNET).unwrap();
+            assert_eq!(
+                &VecDeque::from([RejectSignal::new(RejectReason::QueueFull, 43.into())]),
+                stream.reject_signals()
+            );
+            assert_eq!(MAX_STREAM_CONGESTION_LEVEL, stream.congestion_level());
+        },
+    );
+}
+
+/// Tests that no congestion level is signalled in the loopback stream.
+#[test]
+fn induct_loopback_stream_does_not_signal_congestion_level() {
+    with_local_test_setup(
+        btreemap![LOCAL_SUBNET => StreamConfig {
+            messages: vec![Request(*LOCAL_CANISTER, *LOCAL_CANISTER)],
+            ..StreamConfig::default()
+        }],
+        |stream_handler, mut state, _| {
+            fill_local_canister_input_queue(&mut state, DEFAULT_QUEUE_CAPACITY / 2);
+
+            let state = stream_handler.induct_loopback_stream(state, &mut (i64::MAX / 2));
+
+            assert_eq!(
+                0,
+                state.get_stream(&LOCAL_SUBNET).unwrap().congestion_level()
+            );
+        },
+    );
+}
+
 #[test]
 fn process_stream_slices_with_reject_signals_partial_success() {
     with_test_setup(
@@ -3372,6 +3543,7 @@ fn with_test_setup_and_config(
                 signals_end: slice_config.signals_end,
                 reject_signals: slice_config.reject_signals,
                 flags: slice_config.flags,
+                congestion_level: slice_config.congestion_level,
                 messages_begin: slice_config.messages_begin,
                 messages: messages_from_builders(slice_config.messages),
             });
@@ -3471,6 +3643,7 @@ struct StreamSliceConfig<C: IntoIterator + Default> {
     signals_end: u64,
     reject_signals: Vec<RejectSignal>,
     flags: StreamFlags,
+    congestion_level: u8,
     messages_begin: u64,
     messages: C,
 }
@@ -3492,6 +3665,7 @@ fn stream_slice_from_config(config: StreamSliceConfig<Vec<StreamMessage>>) -> St
         .signals_end(config.signals_end.into())
         .reject_signals(config.reject_signals.into())
         .flags(config.flags)
+        .congestion_level(config.congestion_level)
         .build();
     let mut queue = StreamIndexedQueue::<StreamMessage>::with_begin(config.messages_begin.into());
     for msg in config.messages.into_iter() {
@@ -239,6 +239,8 @@ impl FulfillSubnetRentalRequest {
             ssh_backup_access: vec![],
             ssh_readonly_access: vec![],
 
+            adaptive_block_time_config: None,
+
             // Obsolete
             ingress_bytes_per_block_soft_cap: 0,
             gossip_max_artifact_streams_per_peer: 0,
@@ -52,6 +52,7 @@ fn test_submit_and_accept_update_subnet_proposal() {
                 ssh_backup_access: vec![],
                 chain_key_config: None,
                 canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+                adaptive_block_time_config: None,
             };
 
             let key = make_subnet_record_key(subnet_id);
@@ -90,6 +91,7 @@ fn test_submit_and_accept_update_subnet_proposal() {
                 max_number_of_canisters: Some(200),
                 ssh_readonly_access: Some(vec!["pub_key_0".to_string()]),
                 ssh_backup_access: Some(vec!["pub_key_1".to_string()]),
+                adaptive_block_time_config: None,
                 chain_key_config: None,
                 chain_key_signing_enable: None,
                 chain_key_signing_disable: None,
@@ -170,6 +172,7 @@ fn test_submit_and_accept_update_subnet_proposal() {
                     ssh_backup_access: vec!["pub_key_1".to_string()],
                     chain_key_config: None,
                     canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+                    adaptive_block_time_config: None,
                 }
             );
             Ok(())
@@ -46,6 +46,11 @@ pub struct RegistryReplicatorArgs {
     /// The delay between NNS polls in milliseconds
     #[clap(long, default_value = "5000")]
     pub poll_delay_duration_ms: u64,
+
+    /// If set, only the changelog of this many registry versions is kept in
+    /// the local store, and older versions are compacted into a checkpoint
+    #[clap(long)]
+    pub local_store_retained_versions: Option<u64>,
 }
 
 impl RegistryReplicatorArgs {
@@ -69,6 +74,8 @@ impl RegistryReplicatorArgs {
             .local_store
             .clone_from(&self.local_store_path);
         config.nns_registry_replicator.poll_delay_duration_ms = self.poll_delay_duration_ms;
+        config.nns_registry_replicator.local_store_retained_versions =
+            self.local_store_retained_versions;
 
         (config, _dir)
     }
@@ -531,6 +531,7 @@ mod test {
             ssh_backup_access: vec![],
             chain_key_config: None,
             canister_cycles_cost_schedule: 0,
+            adaptive_block_time_config: None,
         }
     }
 
@@ -2,7 +2,9 @@
 //!
 //! (1) It polls one of the NNS Nodes for registry updates on a regular basis,
 //! verifies the response using the public key configured in the registry and
-//! applies the received changelog to the Registry Local Store.
+//! applies the received changelog to the Registry Local Store. If configured,
+//! it also compacts the Registry Local Store, so that only the history of the
+//! latest registry versions is kept.
 //!
 //! (2) In case of a "switch-over" or starting a new independent NNS subnet, the
 //! Registry Replicator modifies the Registry Local Store before rebooting:
@@ -38,8 +40,10 @@ use ic_http_endpoints_metrics::MetricsHttpEndpoint;
 use ic_interfaces_registry::{RegistryClient, RegistryDataProvider, ZERO_REGISTRY_VERSION};
 use ic_logger::{ReplicaLogger, debug, info, warn};
 use ic_metrics::MetricsRegistry;
-use ic_registry_client::client::RegistryClientImpl;
-use ic_registry_local_store::{Changelog, ChangelogEntry, KeyMutation, LocalStore, LocalStoreImpl};
+use ic_registry_client::{client::RegistryClientImpl, watch::RegistryWatch};
+use ic_registry_local_store::{
+    Changelog, ChangelogEntry, KeyMutation, LocalStore, LocalStoreImpl, VersionPrunedError,
+};
 use ic_registry_nns_data_provider::registry::RegistryCanister;
 use ic_types::{NodeId, RegistryVersion, crypto::threshold_sig::ThresholdSigPublicKey};
 use metrics::RegistryreplicatorMetrics;
@@ -64,7 +68,13 @@ pub struct RegistryReplicator {
     logger: ReplicaLogger,
     node_id: Option<NodeId>,
     registry_client: Arc<dyn RegistryClient>,
+    /// The concrete registry client, if it was created by the replicator, to
+    /// support watches.
+    watchable_registry_client: Option<Arc<RegistryClientImpl>>,
     local_store: Arc<dyn LocalStore>,
+    /// The concrete local store and the number of versions whose history it
+    /// retains, if the local store is to be compacted after polls.
+    local_store_compaction: Option<(Arc<LocalStoreImpl>, u64)>,
     started: Arc<AtomicBool>,
     cancelled: Arc<AtomicBool>,
     poll_delay: Duration,
@@ -84,7 +94,9 @@ impl RegistryReplicator {
             logger,
             node_id: None,
             registry_client,
+            watchable_registry_client: None,
             local_store,
+            local_store_compaction: None,
             started: Arc::new(AtomicBool::new(false)),
             cancelled: Arc::new(AtomicBool::new(false)),
             poll_delay,
@@ -109,14 +121,20 @@ impl RegistryReplicator {
         // Initialize registry client and start polling/caching *local* store for
         // updates
         let registry_client = Self::initialize_registry_client(local_store.clone());
+        let local_store_compaction = config
+            .nns_registry_replicator
+            .local_store_retained_versions
+            .map(|retained_versions| (local_store.clone(), retained_versions));
 
         let metrics = Arc::new(RegistryreplicatorMetrics::new(&MetricsRegistry::global()));
 
         Self {
             logger,
             node_id,
-            registry_client,
+            registry_client: registry_client.clone(),
+            watchable_registry_client: Some(registry_client),
             local_store,
+            local_store_compaction,
             started: Arc::new(AtomicBool::new(false)),
             cancelled: Arc::new(AtomicBool::new(false)),
             poll_delay,
@@ -150,7 +168,7 @@ impl RegistryReplicator {
     /// provider for registry updates
     fn initialize_registry_client(
         data_provider: Arc<dyn RegistryDataProvider>,
-    ) -> Arc<dyn RegistryClient> {
+    ) -> Arc<RegistryClientImpl> {
         let metrics_registry = MetricsRegistry::global();
         let registry_client = Arc::new(RegistryClientImpl::new(
             data_provider,
@@ -214,13 +232,17 @@ impl RegistryReplicator {
         nns_urls: Vec<Url>,
         nns_pub_key: Option<ThresholdSigPublicKey>,
     ) {
-        // If the local registry store is not empty, exit.
-        if !self
+        // If the local registry store is not empty, exit. A store whose
+        // history was pruned is not empty either.
+        let is_empty = match self
             .local_store
             .get_changelog_since_version(ZERO_REGISTRY_VERSION)
-            .expect("Could not read registry local store.")
-            .is_empty()
         {
+            Ok(changelog) => changelog.is_empty(),
+            Err(e) if VersionPrunedError::is_version_pruned(&e) => false,
+            Err(e) => panic!("Could not read registry local store: {e:?}"),
+        };
+        if !is_empty {
             info!(
                 self.logger,
                 "Local registry store is not empty, skipping initialization."
@@ -334,6 +356,7 @@ impl RegistryReplicator {
         let registry_client = self.registry_client.clone();
         let cancelled = Arc::clone(&self.cancelled);
         let poll_delay = self.poll_delay;
+        let local_store_compaction = self.local_store_compaction.clone();
 
         let future = async move {
             // TODO: consider having only one way of cancelling this future,
@@ -357,6 +380,11 @@ impl RegistryReplicator {
                     .registry_version
                     .set(registry_client.get_latest_version().get() as i64);
 
+                if let Some((local_store, retained_versions)) = &local_store_compaction {
+                    compact_local_store_if_due(&logger, local_store.clone(), *retained_versions)
+                        .await;
+                }
+
                 tokio::select! {
                    _ = tokio::time::sleep(poll_delay) => {}
                    _ = cancellation_token.cancelled() => break
@@ -421,6 +449,41 @@ impl RegistryReplicator {
     pub fn get_local_store(&self) -> Arc<dyn LocalStore> {
         self.local_store.clone()
     }
+
+    /// Registers a watch for all keys starting with `key_prefix` with the
+    /// registry client, see [`RegistryClientImpl::watch`].
+    ///
+    /// Returns `None` if the replicator was created with an externally
+    /// provided registry client, which might not support watches.
+    pub fn watch(&self, key_prefix: &str) -> Option<RegistryWatch<Vec<u8>>> {
+        self.watchable_registry_client
+            .as_ref()
+            .map(|registry_client| registry_client.watch(key_prefix))
+    }
+}
+
+/// Compacts the local store once `retained_versions` versions were stored
+/// since the latest checkpoint.
+///
+/// Compacting writes the checkpoint and deletes up to twice
+/// `retained_versions` changelog entries, so it runs on a blocking thread
+/// instead of stalling the runtime.
+async fn compact_local_store_if_due(
+    logger: &ReplicaLogger,
+    local_store: Arc<LocalStoreImpl>,
+    retained_versions: u64,
+) {
+    match tokio::task::spawn_blocking(move || local_store.compact_if_due(retained_versions))
+        .await
+        .unwrap()
+    {
+        Ok(Some(version)) => info!(
+            logger,
+            "Compacted the registry local store at version {}", version
+        ),
+        Ok(None) => {}
+        Err(e) => warn!(logger, "Compacting the registry local store failed: {}", e),
+    }
 }
 
 impl Drop for RegistryReplicator {
@@ -428,3 +491,85 @@ impl Drop for RegistryReplicator {
         self.stop_polling();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_logger::no_op_logger;
+    use std::collections::BTreeMap;
+    use tempfile::TempDir;
+
+    const KEYS: u64 = 10;
+
+    // Every version sets one of `KEYS` keys to the version and deletes another.
+    fn get_changelog(versions: u64) -> Changelog {
+        (1..=versions)
+            .map(|v| {
+                vec![
+                    KeyMutation {
+                        key: format!("key_{}", v % KEYS),
+                        value: Some(v.to_le_bytes().to_vec()),
+                    },
+                    KeyMutation {
+                        key: format!("key_{}", (v + KEYS / 2) % KEYS),
+                        value: None,
+                    },
+                ]
+            })
+            .collect()
+    }
+
+    fn registry_at(changelog: &Changelog, version: u64) -> BTreeMap<String, Vec<u8>> {
+        let mut registry = BTreeMap::new();
+        for km in changelog.iter().take(version as usize).flatten() {
+            match &km.value {
+                Some(value) => registry.insert(km.key.clone(), value.clone()),
+                None => registry.remove(&km.key),
+            };
+        }
+        registry
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn concurrent_reader_never_sees_half_deleted_changelog() {
+        let tempdir = TempDir::new().unwrap();
+        let changelog = get_changelog(2000);
+        let local_store =
+            Arc::new(LocalStoreImpl::from_changelog(changelog.clone(), tempdir.path()).unwrap());
+        let compacted = Arc::new(AtomicBool::new(false));
+
+        let reader = {
+            let local_store = local_store.clone();
+            let compacted = compacted.clone();
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while reads == 0 || !compacted.load(Ordering::Relaxed) {
+                    // Every read must yield the registry at some version, even
+                    // while the changelog entries are being deleted.
+                    let records = local_store
+                        .get_updates_since(ZERO_REGISTRY_VERSION)
+                        .unwrap();
+                    let version = records.iter().map(|r| r.version.get()).max();
+                    let mut registry = BTreeMap::new();
+                    for record in records {
+                        match record.value {
+                            Some(value) => registry.insert(record.key, value),
+                            None => registry.remove(&record.key),
+                        };
+                    }
+                    assert_eq!(registry, registry_at(&changelog, version.unwrap_or(0)));
+                    reads += 1;
+                }
+            })
+        };
+
+        compact_local_store_if_due(&no_op_logger(), local_store.clone(), 100).await;
+        compacted.store(true, Ordering::Relaxed);
+        reader.join().unwrap();
+
+        assert_eq!(
+            local_store.get_oldest_version().unwrap(),
+            RegistryVersion::from(2000)
+        );
+    }
+}
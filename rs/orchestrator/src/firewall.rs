@@ -2,7 +2,7 @@ use crate::{
     catch_up_package_provider::CatchUpPackageProvider,
     error::{OrchestratorError, OrchestratorResult},
     metrics::OrchestratorMetrics,
-    registry_helper::RegistryHelper,
+    registry_helper::{RegistryChanges, RegistryHelper},
 };
 use ic_config::firewall::{
     BoundaryNodeConfig as BoundaryNodeFirewallConfig, FIREWALL_FILE_DEFAULT_PATH,
@@ -49,6 +49,12 @@ pub(crate) struct Firewall {
     boundary_node_config: BoundaryNodeFirewallConfig,
     compiled_config: String,
     last_applied_version: Arc<RwLock<RegistryVersion>>,
+    /// Watches the registry keys the firewall config depends on. If `None`,
+    /// the config is recomputed for every new registry version.
+    registry_changes: Option<RegistryChanges>,
+    /// The oldest registry version in use by the local CUP when the config was
+    /// last computed, which determines the whitelisted node IPs.
+    last_oldest_registry_version_in_use: Option<RegistryVersion>,
     /// If true, write the file content even if no change was detected in registry, i.e. first time
     must_write: bool,
     /// If false, do not update the firewall rules (test mode)
@@ -64,6 +70,7 @@ impl Firewall {
         replica_config: ReplicaFirewallConfig,
         boundary_node_config: BoundaryNodeFirewallConfig,
         catchup_package_provider: Arc<CatchUpPackageProvider>,
+        registry_changes: Option<RegistryChanges>,
         logger: ReplicaLogger,
     ) -> Self {
         // Disable if the config is the default one (e.g if we're in a test)
@@ -87,6 +94,8 @@ impl Firewall {
             logger,
             compiled_config: Default::default(),
             last_applied_version: Default::default(),
+            registry_changes,
+            last_oldest_registry_version_in_use: None,
             must_write: true,
             enabled,
             node_id,
@@ -146,6 +155,12 @@ impl Firewall {
         }
     }
 
+    fn get_oldest_registry_version_in_use(&self) -> Option<RegistryVersion> {
+        self.catchup_package_provider
+            .get_local_cup()
+            .map(|latest_cup| latest_cup.get_oldest_registry_version_in_use())
+    }
+
     // Get all the registry versions between the latest CUP and the latest version in the registry (inclusive)
     fn get_registry_versions(&mut self, registry_version: RegistryVersion) -> Vec<RegistryVersion> {
         self.catchup_package_provider
@@ -327,15 +342,28 @@ impl Firewall {
     }
 
     /// Checks for the firewall configuration that applies to this node
+    ///
+    /// `changes_pending` indicates whether any registry key the config depends
+    /// on might have changed since the config was last computed.
     fn check_for_firewall_config(
         &mut self,
         registry_version: RegistryVersion,
+        changes_pending: bool,
     ) -> OrchestratorResult<()> {
         if *self.last_applied_version.read().unwrap() == registry_version {
             // No update in the registry, so no need to re-check
             return Ok(());
         }
 
+        let oldest_registry_version_in_use = self.get_oldest_registry_version_in_use();
+        if !changes_pending
+            && oldest_registry_version_in_use == self.last_oldest_registry_version_in_use
+        {
+            // The new registry versions do not affect the config
+            *self.last_applied_version.write().unwrap() = registry_version;
+            return Ok(());
+        }
+
         let role = self.get_role(registry_version)?;
 
         // This is the eventual list of rules fetched from the registry.
@@ -451,6 +479,7 @@ impl Firewall {
                 .set(i64::try_from(registry_version.get()).unwrap_or(-1));
         }
         *self.last_applied_version.write().unwrap() = registry_version;
+        self.last_oldest_registry_version_in_use = oldest_registry_version_in_use;
 
         Ok(())
     }
@@ -471,17 +500,28 @@ impl Firewall {
         if !self.enabled {
             return;
         }
+        // Drain the watches before reading the latest version, so that no
+        // change up to that version goes unnoticed.
+        let changes_pending = self
+            .registry_changes
+            .as_mut()
+            .is_none_or(RegistryChanges::pending);
         let registry_version = self.registry.get_latest_version();
         debug!(
             self.logger,
             "Checking for firewall config registry version: {}", registry_version
         );
 
-        if let Err(e) = self.check_for_firewall_config(registry_version) {
-            info!(
+        match self.check_for_firewall_config(registry_version, changes_pending) {
+            Ok(()) => {
+                if let Some(registry_changes) = self.registry_changes.as_mut() {
+                    registry_changes.applied();
+                }
+            }
+            Err(e) => info!(
                 self.logger,
                 "Failed to check for firewall config at version {}: {}", registry_version, e
-            )
+            ),
         }
     }
 
@@ -924,7 +964,7 @@ mod tests {
         );
 
         firewall
-            .check_for_firewall_config(RegistryVersion::new(1))
+            .check_for_firewall_config(RegistryVersion::new(1), /*changes_pending=*/ true)
             .expect("Should successfully produce a firewall config");
 
         let golden = String::from_utf8(golden_bytes.to_vec()).unwrap();
@@ -940,6 +980,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn config_is_not_recomputed_without_changes() {
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let nftables_config_path = tmp_dir.path().join("nftables.conf");
+        let config = get_config();
+        let mut replica_firewall_config = config.firewall.unwrap();
+        replica_firewall_config
+            .config_file
+            .clone_from(&nftables_config_path);
+        let mut firewall = set_up_firewall_dependencies(
+            replica_firewall_config,
+            config.boundary_node_firewall.unwrap(),
+            tmp_dir.path(),
+            Role::AssignedReplica(subnet_test_id(1)),
+            node_test_id(0),
+        );
+
+        firewall
+            .check_for_firewall_config(RegistryVersion::new(1), /*changes_pending=*/ true)
+            .unwrap();
+        assert!(nftables_config_path.exists());
+        std::fs::remove_file(&nftables_config_path).unwrap();
+
+        // Version 2 does not exist in the registry, so reading it would fail.
+        firewall
+            .check_for_firewall_config(RegistryVersion::new(2), /*changes_pending=*/ false)
+            .unwrap();
+        assert!(!nftables_config_path.exists());
+        assert_eq!(
+            *firewall.get_last_applied_version().read().unwrap(),
+            RegistryVersion::new(2)
+        );
+
+        assert!(
+            firewall
+                .check_for_firewall_config(RegistryVersion::new(3), /*changes_pending=*/ true)
+                .is_err()
+        );
+    }
+
     /// Returns the `ic.json5` config filled with some dummy values.
     fn get_config() -> ConfigOptional {
         let template = config::guestos::generate_ic_config::IcConfigTemplate {
@@ -1018,6 +1098,7 @@ mod tests {
             config,
             boundary_node_config,
             Arc::new(catch_up_package_provider),
+            /*registry_changes=*/ None,
             no_op_logger(),
         )
     }
@@ -9,7 +9,7 @@ use crate::{
     metrics::OrchestratorMetrics,
     process_manager::ProcessManager,
     registration::NodeRegistration,
-    registry_helper::RegistryHelper,
+    registry_helper::{RegistryChanges, RegistryHelper},
     ssh_access_manager::SshAccessManager,
     upgrade::{OrchestratorControlFlow, Upgrade},
 };
@@ -26,6 +26,10 @@ use ic_http_endpoints_metrics::MetricsHttpEndpoint;
 use ic_image_upgrader::ImageUpgrader;
 use ic_logger::{ReplicaLogger, error, info, warn};
 use ic_metrics::MetricsRegistry;
+use ic_registry_keys::{
+    API_BOUNDARY_NODE_RECORD_KEY_PREFIX, FIREWALL_RULES_RECORD_KEY_PREFIX, NODE_RECORD_KEY_PREFIX,
+    SUBNET_RECORD_KEY_PREFIX, make_subnet_list_record_key, make_unassigned_nodes_config_record_key,
+};
 use ic_registry_replicator::RegistryReplicator;
 use ic_sys::utility_command::UtilityCommand;
 use ic_types::{ReplicaVersion, SubnetId, hostos_version::HostosVersion};
@@ -196,6 +200,27 @@ impl Orchestrator {
             registry_client.clone(),
             logger.clone(),
         ));
+        // Watches of the registry keys the firewall and the SSH access manager
+        // depend on, so that they only re-read the registry if one of them
+        // changed.
+        let firewall_registry_changes = RegistryChanges::watch(
+            &registry_replicator,
+            &[
+                NODE_RECORD_KEY_PREFIX,
+                SUBNET_RECORD_KEY_PREFIX,
+                &make_subnet_list_record_key(),
+                FIREWALL_RULES_RECORD_KEY_PREFIX,
+                API_BOUNDARY_NODE_RECORD_KEY_PREFIX,
+            ],
+        );
+        let ssh_access_registry_changes = RegistryChanges::watch(
+            &registry_replicator,
+            &[
+                SUBNET_RECORD_KEY_PREFIX,
+                &make_unassigned_nodes_config_record_key(),
+                API_BOUNDARY_NODE_RECORD_KEY_PREFIX,
+            ],
+        );
 
         let c_log = logger.clone();
         let c_registry = registry.clone();
@@ -317,6 +342,7 @@ impl Orchestrator {
             config.firewall.clone(),
             config.boundary_node_firewall.clone(),
             cup_provider.clone(),
+            firewall_registry_changes,
             logger.clone(),
         );
 
@@ -331,6 +357,7 @@ impl Orchestrator {
             Arc::clone(&registry),
             Arc::clone(&metrics),
             node_id,
+            ssh_access_registry_changes,
             logger.clone(),
         );
 
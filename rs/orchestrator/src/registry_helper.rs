@@ -7,12 +7,14 @@ use ic_protobuf::registry::{
     hostos_version::v1::HostosVersionRecord, node::v1::IPv4InterfaceConfig,
     replica_version::v1::ReplicaVersionRecord, subnet::v1::SubnetRecord,
 };
+use ic_registry_client::watch::RegistryWatch;
 use ic_registry_client_helpers::{
     api_boundary_node::ApiBoundaryNodeRegistry, firewall::FirewallRegistry,
     hostos_version::HostosRegistry, node::NodeRegistry, node_operator::NodeOperatorRegistry,
     subnet::SubnetRegistry, unassigned_nodes::UnassignedNodeRegistry,
 };
 use ic_registry_keys::FirewallRulesScope;
+use ic_registry_replicator::RegistryReplicator;
 use ic_types::{
     NodeId, PrincipalId, RegistryVersion, ReplicaVersion, SubnetId, consensus::CatchUpPackage,
     hostos_version::HostosVersion,
@@ -322,3 +324,102 @@ impl RegistryHelper {
         Ok(result)
     }
 }
+
+/// Tracks whether any of the registry keys a component depends on changed
+/// since the component last applied the registry, using watches.
+///
+/// This allows the component to skip re-reading the registry for versions
+/// that do not touch any of its keys.
+pub(crate) struct RegistryChanges {
+    watches: Vec<RegistryWatch<Vec<u8>>>,
+    pending: bool,
+}
+
+impl RegistryChanges {
+    pub(crate) fn new(watches: Vec<RegistryWatch<Vec<u8>>>) -> Self {
+        Self {
+            watches,
+            // The component has not applied the registry yet.
+            pending: true,
+        }
+    }
+
+    /// Watches all keys starting with one of `key_prefixes`.
+    ///
+    /// Returns `None` if the registry client of `registry_replicator` does not
+    /// support watches.
+    pub(crate) fn watch(
+        registry_replicator: &RegistryReplicator,
+        key_prefixes: &[&str],
+    ) -> Option<Self> {
+        key_prefixes
+            .iter()
+            .map(|key_prefix| registry_replicator.watch(key_prefix))
+            .collect::<Option<Vec<_>>>()
+            .map(Self::new)
+    }
+
+    /// Returns `true` if a watched key changed since [`Self::applied`] was last
+    /// called, or if it was never called.
+    ///
+    /// Must be called before reading the latest registry version, so that all
+    /// changes up to that version are accounted for.
+    pub(crate) fn pending(&mut self) -> bool {
+        for watch in &self.watches {
+            // Both changes and resyncs require re-reading the registry.
+            if watch.receiver().try_iter().count() > 0 {
+                self.pending = true;
+            }
+        }
+        self.pending
+    }
+
+    /// Marks all changes returned by [`Self::pending`] as applied.
+    pub(crate) fn applied(&mut self) {
+        self.pending = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_registry_client::client::RegistryClientImpl;
+    use ic_registry_proto_data_provider::ProtoRegistryDataProvider;
+
+    #[test]
+    fn registry_changes_are_pending_until_applied() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry_client = RegistryClientImpl::new(data_provider.clone(), None);
+        let mut changes = RegistryChanges::new(vec![registry_client.watch("A")]);
+
+        // Initially, the registry has not been applied.
+        assert!(changes.pending());
+        changes.applied();
+        assert!(!changes.pending());
+
+        // Changes to keys that are not watched are ignored.
+        data_provider
+            .add(
+                "B",
+                RegistryVersion::from(1),
+                Some(FirewallRuleSet::default()),
+            )
+            .unwrap();
+        registry_client.poll_once().unwrap();
+        assert!(!changes.pending());
+
+        data_provider
+            .add(
+                "A",
+                RegistryVersion::from(2),
+                Some(FirewallRuleSet::default()),
+            )
+            .unwrap();
+        registry_client.poll_once().unwrap();
+        assert!(changes.pending());
+        // The changes remain pending until they are applied.
+        assert!(changes.pending());
+        changes.applied();
+        assert!(!changes.pending());
+    }
+}
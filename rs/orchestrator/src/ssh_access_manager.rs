@@ -1,7 +1,7 @@
 use crate::{
     error::{OrchestratorError, OrchestratorResult},
     metrics::OrchestratorMetrics,
-    registry_helper::RegistryHelper,
+    registry_helper::{RegistryChanges, RegistryHelper},
 };
 use ic_logger::{ReplicaLogger, debug, warn};
 use ic_registry_client_helpers::unassigned_nodes::UnassignedNodeRegistry;
@@ -27,6 +27,9 @@ pub(crate) struct SshAccessManager {
     node_id: NodeId,
     logger: ReplicaLogger,
     last_applied_parameters: Arc<RwLock<SshAccessParameters>>,
+    /// Watches the registry keys the keysets depend on. If `None`, the keysets
+    /// are re-read for every new registry version.
+    registry_changes: Option<RegistryChanges>,
 }
 
 impl SshAccessManager {
@@ -34,6 +37,7 @@ impl SshAccessManager {
         registry: Arc<RegistryHelper>,
         metrics: Arc<OrchestratorMetrics>,
         node_id: NodeId,
+        registry_changes: Option<RegistryChanges>,
         logger: ReplicaLogger,
     ) -> Self {
         Self {
@@ -42,17 +46,32 @@ impl SshAccessManager {
             node_id,
             logger,
             last_applied_parameters: Default::default(),
+            registry_changes,
         }
     }
 
     /// Checks for changes in the keysets, and updates the node accordingly.
     pub(crate) fn check_for_keyset_changes(&mut self, subnet_id: Option<SubnetId>) {
+        // Drain the watches before reading the latest version, so that no
+        // change up to that version goes unnoticed.
+        let changes_pending = self
+            .registry_changes
+            .as_mut()
+            .is_none_or(RegistryChanges::pending);
         let registry_version = self.registry.get_latest_version();
-        let last_applied_parameters = self.last_applied_parameters.read().unwrap();
-        if last_applied_parameters.registry_version == registry_version
-            && last_applied_parameters.subnet_id == subnet_id
-        {
-            return;
+        let mut last_applied_parameters = self.last_applied_parameters.write().unwrap();
+        if last_applied_parameters.subnet_id == subnet_id {
+            if last_applied_parameters.registry_version == registry_version {
+                return;
+            }
+            if !changes_pending {
+                // The new registry versions do not affect the keysets
+                last_applied_parameters.registry_version = registry_version;
+                self.metrics
+                    .ssh_access_registry_version
+                    .set(registry_version.get() as i64);
+                return;
+            }
         }
         drop(last_applied_parameters);
         debug!(
@@ -84,6 +103,9 @@ impl SshAccessManager {
             self.metrics
                 .ssh_access_registry_version
                 .set(registry_version.get() as i64);
+            if let Some(registry_changes) = self.registry_changes.as_mut() {
+                registry_changes.applied();
+            }
         }
     }
 
@@ -1079,6 +1079,7 @@ mod tests {
             MasterPublicKeyId::Ecdsa(ref mut key_id) => key_id.name = name.into(),
             MasterPublicKeyId::Schnorr(ref mut key_id) => key_id.name = name.into(),
             MasterPublicKeyId::VetKd(ref mut key_id) => key_id.name = name.into(),
+            MasterPublicKeyId::Bls(ref mut key_id) => key_id.name = name.into(),
         }
         key_id
     }
@@ -1099,7 +1100,8 @@ mod tests {
 
         if let Some((key_id, transcript)) = key_transcript {
             match (&key_id, transcript) {
-                (MasterPublicKeyId::VetKd(_), KeyTranscript::NiDkg(transcript)) => {
+                (MasterPublicKeyId::VetKd(_), KeyTranscript::NiDkg(transcript))
+                | (MasterPublicKeyId::Bls(_), KeyTranscript::NiDkg(transcript)) => {
                     nidkg_transcripts.insert(transcript.dkg_id.dkg_tag.clone(), transcript);
                 }
                 (MasterPublicKeyId::Ecdsa(_), KeyTranscript::IDkg(transcript))
@@ -1198,6 +1200,9 @@ mod tests {
                     EcdsaCurve::Secp256k1 => {
                         self.generate_idkg_key_transcript(AlgorithmId::ThresholdEcdsaSecp256k1)
                     }
+                    EcdsaCurve::Secp256r1 => {
+                        self.generate_idkg_key_transcript(AlgorithmId::ThresholdEcdsaSecp256r1)
+                    }
                 },
                 MasterPublicKeyId::Schnorr(schnorr_key_id) => match schnorr_key_id.algorithm {
                     SchnorrAlgorithm::Bip340Secp256k1 => {
@@ -1208,7 +1213,9 @@ mod tests {
                         self.generate_idkg_key_transcript(AlgorithmId::ThresholdEd25519)
                     }
                 },
-                MasterPublicKeyId::VetKd(_) => self.generate_nidkg_key_transcript(key_id),
+                MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                    self.generate_nidkg_key_transcript(key_id)
+                }
             };
             (key_id.clone(), transcript)
         }
@@ -1229,13 +1236,11 @@ mod tests {
         }
 
         fn generate_nidkg_key_transcript(&mut self, key_id: &MasterPublicKeyId) -> KeyTranscript {
-            let MasterPublicKeyId::VetKd(vetkd_key_id) = key_id.clone() else {
+            let Ok(nidkg_key_id) = NiDkgMasterPublicKeyId::try_from(key_id.clone()) else {
                 panic!("Can't generate nidkg transcript for {key_id}");
             };
             let mut config = RandomNiDkgConfig::builder()
-                .dkg_tag(NiDkgTag::HighThresholdForKey(
-                    NiDkgMasterPublicKeyId::VetKd(vetkd_key_id),
-                ))
+                .dkg_tag(NiDkgTag::HighThresholdForKey(nidkg_key_id))
                 .subnet_size(4);
 
             if let Some(version) = self.nidkg_registry_version {
@@ -1,7 +1,13 @@
-use std::{backtrace::Backtrace, collections::HashMap, ops::Range, sync::Arc, time::Duration};
+use std::{
+    backtrace::Backtrace, collections::HashMap, net::SocketAddr, ops::Range, sync::Arc,
+    time::Duration,
+};
 
 use futures::StreamExt;
 use ic_logger::{ReplicaLogger, replica_logger::no_op_logger};
+use ic_memory_transport::{
+    LatencyDistribution, LinkConditions, NetworkSimulation, TransportRouter,
+};
 use ic_p2p_test_utils::{
     consensus::{TestConsensus, U64Artifact},
     fully_connected_localhost_subnet, start_consensus_manager,
@@ -10,11 +16,15 @@ use ic_p2p_test_utils::{
         wait_for, wait_for_timeout, waiter_fut,
     },
 };
+use ic_quic_transport::SubnetTopology;
 use ic_test_utilities_logger::with_test_replica_logger;
 use ic_types::{NodeId, RegistryVersion};
 use ic_types_test_utils::ids::{NODE_1, NODE_2, NODE_3, node_test_id};
 use rand::{Rng, rngs::ThreadRng};
-use tokio::{sync::Notify, task::JoinSet};
+use tokio::{
+    sync::{Notify, watch},
+    task::JoinSet,
+};
 use tokio_util::time::DelayQueue;
 use turmoil::Builder;
 
@@ -758,3 +768,91 @@ fn test_large_msgs() {
         sim.run().unwrap();
     });
 }
+
+/// Test that an artifact reaches all peers over lossy links with varying latency, including
+/// a peer that is partitioned from the producer when the artifact is produced.
+///
+/// The test runs on a current-thread runtime with paused time, so that the simulation is
+/// reproducible from its seed.
+#[test]
+fn test_artifact_sent_under_adverse_network_conditions() {
+    const HEAL_AFTER: Duration = Duration::from_secs(5);
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    with_test_replica_logger(|log| {
+        rt.block_on(async {
+            let nodes = [NODE_1, NODE_2, NODE_3];
+            let start = tokio::time::Instant::now();
+            let simulation = NetworkSimulation::new(3)
+                .with_default_link(LinkConditions {
+                    latency: LatencyDistribution::Uniform {
+                        min: Duration::from_millis(5),
+                        max: Duration::from_millis(100),
+                    },
+                    loss_probability: 0.1,
+                    bandwidth_bytes_per_second: Some(10_000_000),
+                })
+                .with_partition(Duration::ZERO, vec![vec![NODE_1, NODE_2], vec![NODE_3]])
+                .with_heal(HEAL_AFTER);
+            let mut transport_router = TransportRouter::with_network_simulation(simulation);
+            let topology = SubnetTopology::new(
+                nodes
+                    .iter()
+                    .map(|node| (*node, SocketAddr::from(([127, 0, 0, 1], 4100)))),
+                RegistryVersion::from(1),
+                RegistryVersion::from(1),
+            );
+            let (_topology_sender, topology_watcher) = watch::channel(topology);
+
+            let mut processors = Vec::new();
+            let mut guards = Vec::new();
+            for (i, node) in nodes.iter().enumerate() {
+                let processor = TestConsensus::new(log.clone(), *node, 1024 * (i + 1), i % 2 == 0);
+                let (join_guard, consensus_builder) =
+                    start_consensus_manager(log.clone(), rt.handle().clone(), processor.clone());
+                let transport = transport_router.add_peer(
+                    *node,
+                    consensus_builder.router(),
+                    Duration::ZERO,
+                    100_000_000,
+                );
+                let shutdowns =
+                    consensus_builder.start(Arc::new(transport), topology_watcher.clone());
+                guards.push((join_guard, shutdowns));
+                processors.push(processor);
+            }
+
+            processors[0].push_advert(1);
+
+            // NODE_2 receives the artifact while NODE_3 is still cut off.
+            tokio::time::timeout(HEAL_AFTER - Duration::from_millis(500), async {
+                while processors[1].received_advert_count(1) == 0 {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            })
+            .await
+            .expect("NODE_2 did not receive the artifact before the heal.");
+            tokio::time::sleep_until(start + HEAL_AFTER - Duration::from_millis(100)).await;
+            assert_eq!(
+                processors[2].received_advert_count(1),
+                0,
+                "NODE_3 received the artifact while partitioned."
+            );
+
+            let all_received = async {
+                while !processors[1..]
+                    .iter()
+                    .all(|processor| processor.received_advert_count(1) > 0)
+                {
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+            };
+            tokio::time::timeout(Duration::from_secs(60), all_received)
+                .await
+                .expect("Not all peers received the artifact.");
+        });
+    });
+}
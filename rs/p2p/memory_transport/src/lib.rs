@@ -33,6 +33,10 @@
 /// ┌──────┐   │                  │    ┌──────┐
 /// │ Node ├───┘                  └────┤ Node │
 /// └──────┘                           └──────┘
+///
+/// Additionally, a [`NetworkSimulation`] can be used to inject per-link
+/// latency distributions, message loss, bandwidth limits and scripted
+/// partitions, reproducible from a seed.
 use async_trait::async_trait;
 use axum::{
     Router,
@@ -57,6 +61,9 @@ use tokio::{
 };
 use tower::ServiceExt;
 
+mod simulation;
+pub use simulation::{Delivery, LatencyDistribution, LinkConditions, NetworkSimulation};
+
 #[derive(Clone)]
 pub struct PeerHandle {
     rpc_tx: UnboundedSender<(Request<Bytes>, oneshot::Sender<Response<Bytes>>)>,
@@ -94,8 +101,17 @@ pub struct TransportRouter {
 }
 
 impl TransportRouter {
-    #[allow(clippy::disallowed_methods)]
     pub fn new() -> Self {
+        Self::new_with_simulation(None)
+    }
+
+    /// Creates a router that applies the conditions of `simulation` to all messages.
+    pub fn with_network_simulation(simulation: NetworkSimulation) -> Self {
+        Self::new_with_simulation(Some(Arc::new(simulation)))
+    }
+
+    #[allow(clippy::disallowed_methods)]
+    fn new_with_simulation(simulation: Option<Arc<NetworkSimulation>>) -> Self {
         let (router_req_tx, mut router_req_rx) =
             unbounded_channel::<(Request<Bytes>, NodeId, oneshot::Sender<Response<Bytes>>)>();
         let (router_resp_tx, mut router_resp_rx) =
@@ -107,10 +123,10 @@ impl TransportRouter {
             loop {
                 select! {
                     Some((req,dest,resp)) = router_req_rx.recv() => {
-                        Self::handle_incoming_request(peers_c.clone(), req, dest, resp);
+                        Self::handle_incoming_request(peers_c.clone(), simulation.clone(), req, dest, resp);
                     }
                     Some((req,dest,resp)) = router_resp_rx.recv() => {
-                        Self::handle_incoming_response(peers_c.clone(), req, dest, resp);
+                        Self::handle_incoming_response(peers_c.clone(), simulation.clone(), req, dest, resp);
                     }
                     else => break,
                 }
@@ -178,21 +194,25 @@ impl TransportRouter {
     /// After using the requested resources the request is delivered to the peer.
     fn handle_incoming_request(
         peers: Arc<RwLock<HashMap<NodeId, PeerHandle>>>,
+        simulation: Option<Arc<NetworkSimulation>>,
         req: Request<Bytes>,
         dest: NodeId,
         resp: oneshot::Sender<Response<Bytes>>,
     ) {
         let request_size = request_size(&req);
-        let origin_id = req.extensions().get::<NodeId>().unwrap();
+        let origin_id = *req.extensions().get::<NodeId>().unwrap();
         let peers_g = peers.read().unwrap();
-        if !peers_g.contains_key(&dest) || !peers_g.contains_key(origin_id) {
+        if !peers_g.contains_key(&dest) || !peers_g.contains_key(&origin_id) {
             return;
         }
         let dest_ph = peers_g.get(&dest).unwrap().clone();
-        let origin_ph = peers_g.get(origin_id).unwrap().clone();
+        let origin_ph = peers_g.get(&origin_id).unwrap().clone();
         drop(peers_g);
 
         let req_fut = async move {
+            if !simulate_link(simulation.as_deref(), &origin_id, &dest, request_size).await {
+                return;
+            }
             let _permit = origin_ph
                 .up_capacity
                 .acquire_many(request_size as u32)
@@ -215,21 +235,25 @@ impl TransportRouter {
     /// After using the requested resources the response is delivered.
     fn handle_incoming_response(
         peers: Arc<RwLock<HashMap<NodeId, PeerHandle>>>,
+        simulation: Option<Arc<NetworkSimulation>>,
         req: Response<Bytes>,
         dest: NodeId,
         resp: oneshot::Sender<Response<Bytes>>,
     ) {
         let response_size = response_size(&req);
-        let origin_id = req.extensions().get::<NodeId>().unwrap();
+        let origin_id = *req.extensions().get::<NodeId>().unwrap();
         let peers_g = peers.read().unwrap();
-        if !peers_g.contains_key(&dest) || !peers_g.contains_key(origin_id) {
+        if !peers_g.contains_key(&dest) || !peers_g.contains_key(&origin_id) {
             return;
         }
         let dest_ph = peers_g.get(&dest).unwrap().clone();
-        let origin_ph = peers_g.get(origin_id).unwrap().clone();
+        let origin_ph = peers_g.get(&origin_id).unwrap().clone();
         drop(peers_g);
 
         let resp_fut = async move {
+            if !simulate_link(simulation.as_deref(), &origin_id, &dest, response_size).await {
+                return;
+            }
             let _permit = origin_ph
                 .up_capacity
                 .acquire_many(response_size as u32)
@@ -250,6 +274,34 @@ impl TransportRouter {
     }
 }
 
+/// Applies the simulated conditions of the link from `origin` to `dest` to a message of
+/// `size` bytes. Returns false if the message is lost, in which case the corresponding
+/// rpc fails. The caller must hold on to the response channel of the rpc until this
+/// returns, so that the rpc only fails once the loss timeout has elapsed.
+async fn simulate_link(
+    simulation: Option<&NetworkSimulation>,
+    origin: &NodeId,
+    dest: &NodeId,
+    size: usize,
+) -> bool {
+    let Some(simulation) = simulation else {
+        return true;
+    };
+    let sent_at = tokio::time::Instant::now();
+    let delivered = match simulation.transmit(origin, dest, size, sent_at) {
+        Delivery::Dropped => false,
+        Delivery::After(delay) => {
+            tokio::time::sleep(delay).await;
+            // A partition that started while the message was in flight drops it as well.
+            !simulation.is_partitioned(origin, dest, tokio::time::Instant::now())
+        }
+    };
+    if !delivered {
+        tokio::time::sleep_until(sent_at + simulation.loss_timeout()).await;
+    }
+    delivered
+}
+
 #[derive(Clone)]
 pub struct PeerTransport {
     node_id: NodeId,
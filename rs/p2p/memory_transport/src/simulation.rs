@@ -0,0 +1,334 @@
+//! Deterministic simulation of adverse network conditions on top of the memory transport.
+//!
+//! A [`NetworkSimulation`] describes the conditions of each directed link between two
+//! nodes (latency distribution, message loss and bandwidth) and a script of partitions
+//! that are applied at given offsets from the start of the simulation.
+//!
+//! All random decisions of a link are drawn from a random number generator that is
+//! derived from the seed of the simulation and the two nodes of the link. Hence, the
+//! sequence of latencies and losses of a link only depends on the seed and the sequence
+//! of messages sent over that link, not on the interleaving with other links.
+//!
+//! Note that the transport delivers whole messages, so loss is modeled on the message
+//! level: a lost request or response makes the corresponding rpc fail once the loss
+//! timeout has elapsed, like a QUIC connection that times out.
+//!
+//! All points in time are taken from the tokio clock. For a run to be reproducible, the
+//! simulation must be driven by a current-thread runtime with paused time, see
+//! [`tokio::time::pause`].
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::Mutex,
+    time::Duration,
+};
+
+use ic_types::NodeId;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use tokio::time::Instant;
+
+/// Distribution of the one-way latency of a link.
+#[derive(Clone, Debug, PartialEq)]
+pub enum LatencyDistribution {
+    Constant(Duration),
+    /// Uniformly distributed in `[min, max]`.
+    Uniform {
+        min: Duration,
+        max: Duration,
+    },
+}
+
+impl LatencyDistribution {
+    fn sample(&self, rng: &mut ChaCha8Rng) -> Duration {
+        match self {
+            LatencyDistribution::Constant(latency) => *latency,
+            LatencyDistribution::Uniform { min, max } if min < max => rng.gen_range(*min..=*max),
+            LatencyDistribution::Uniform { min, .. } => *min,
+        }
+    }
+}
+
+/// Conditions of a directed link between two nodes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkConditions {
+    pub latency: LatencyDistribution,
+    /// Probability in `[0, 1]` that a message sent over the link is lost.
+    pub loss_probability: f64,
+    /// Optional bandwidth of the link. Messages sent over a link are serialized, i.e. a
+    /// message can only be transmitted once all previous messages have been transmitted.
+    pub bandwidth_bytes_per_second: Option<u64>,
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self {
+            latency: LatencyDistribution::Constant(Duration::ZERO),
+            loss_probability: 0.0,
+            bandwidth_bytes_per_second: None,
+        }
+    }
+}
+
+/// The time after which an rpc fails if its request or response is lost, i.e. the idle
+/// timeout of the QUIC transport.
+const DEFAULT_LOSS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The fate of a message sent over a simulated link.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Delivery {
+    Dropped,
+    After(Duration),
+}
+
+struct LinkState {
+    rng: ChaCha8Rng,
+    /// Point in time at which the link has transmitted all messages sent so far.
+    busy_until: Instant,
+}
+
+pub struct NetworkSimulation {
+    seed: u64,
+    start: Instant,
+    loss_timeout: Duration,
+    default_link: LinkConditions,
+    links: HashMap<(NodeId, NodeId), LinkConditions>,
+    /// Partitions by offset from the start of the simulation, in ascending order. A
+    /// partition consists of groups of nodes that can only reach nodes of the same group.
+    /// Nodes that are not part of any group form an additional group. `None` heals the
+    /// network.
+    partitions: Vec<(Duration, Option<Vec<BTreeSet<NodeId>>>)>,
+    link_states: Mutex<HashMap<(NodeId, NodeId), LinkState>>,
+}
+
+impl NetworkSimulation {
+    /// Creates a simulation without any adverse conditions, which starts now.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            start: Instant::now(),
+            loss_timeout: DEFAULT_LOSS_TIMEOUT,
+            default_link: LinkConditions::default(),
+            links: HashMap::new(),
+            partitions: Vec::new(),
+            link_states: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets the time after which an rpc fails if its request or response is lost.
+    pub fn with_loss_timeout(mut self, loss_timeout: Duration) -> Self {
+        self.loss_timeout = loss_timeout;
+        self
+    }
+
+    /// Returns the time after which an rpc fails if its request or response is lost.
+    pub fn loss_timeout(&self) -> Duration {
+        self.loss_timeout
+    }
+
+    /// Sets the conditions of all links without explicitly configured conditions.
+    pub fn with_default_link(mut self, conditions: LinkConditions) -> Self {
+        self.default_link = conditions;
+        self
+    }
+
+    /// Sets the conditions of the link from `from` to `to`.
+    pub fn with_link(mut self, from: NodeId, to: NodeId, conditions: LinkConditions) -> Self {
+        self.links.insert((from, to), conditions);
+        self
+    }
+
+    /// Partitions the network into `groups` once `at` has elapsed since the start.
+    pub fn with_partition(mut self, at: Duration, groups: Vec<Vec<NodeId>>) -> Self {
+        let groups = groups
+            .into_iter()
+            .map(|group| group.into_iter().collect())
+            .collect();
+        self.add_partition(at, Some(groups));
+        self
+    }
+
+    /// Heals all partitions once `at` has elapsed since the start.
+    pub fn with_heal(mut self, at: Duration) -> Self {
+        self.add_partition(at, None);
+        self
+    }
+
+    fn add_partition(&mut self, at: Duration, partition: Option<Vec<BTreeSet<NodeId>>>) {
+        let index = self.partitions.partition_point(|(offset, _)| *offset <= at);
+        self.partitions.insert(index, (at, partition));
+    }
+
+    /// Returns true if `from` can't reach `to` at time `now`.
+    pub fn is_partitioned(&self, from: &NodeId, to: &NodeId, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.start);
+        let active = self
+            .partitions
+            .iter()
+            .take_while(|(offset, _)| *offset <= elapsed)
+            .last();
+        match active {
+            Some((_, Some(groups))) => {
+                let group_of = |node| groups.iter().position(|group| group.contains(node));
+                group_of(from) != group_of(to)
+            }
+            _ => false,
+        }
+    }
+
+    /// Decides whether a message of `size` bytes sent from `from` to `to` at time `now`
+    /// is lost, or else after which delay it is delivered.
+    pub fn transmit(&self, from: &NodeId, to: &NodeId, size: usize, now: Instant) -> Delivery {
+        if self.is_partitioned(from, to, now) {
+            return Delivery::Dropped;
+        }
+
+        let conditions = self.links.get(&(*from, *to)).unwrap_or(&self.default_link);
+        let mut link_states = self.link_states.lock().unwrap();
+        let state = link_states
+            .entry((*from, *to))
+            .or_insert_with(|| LinkState {
+                rng: ChaCha8Rng::seed_from_u64(self.seed ^ link_seed(from, to)),
+                busy_until: now,
+            });
+
+        // Always draw both values, so that the sequence of draws of a link doesn't depend
+        // on the outcome of previous draws.
+        let lost = state
+            .rng
+            .gen_bool(conditions.loss_probability.clamp(0.0, 1.0));
+        let latency = conditions.latency.sample(&mut state.rng);
+        if lost {
+            return Delivery::Dropped;
+        }
+
+        let transmission = conditions
+            .bandwidth_bytes_per_second
+            .map(|bandwidth| Duration::from_secs_f64(size as f64 / bandwidth.max(1) as f64))
+            .unwrap_or_default();
+        state.busy_until = state.busy_until.max(now) + transmission;
+        Delivery::After(state.busy_until - now + latency)
+    }
+}
+
+/// FNV-1a hash of the ids of both nodes, which is stable across platforms and releases.
+fn link_seed(from: &NodeId, to: &NodeId) -> u64 {
+    from.get()
+        .as_slice()
+        .iter()
+        .chain(to.get().as_slice())
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_types::PrincipalId;
+
+    fn node(id: u64) -> NodeId {
+        NodeId::from(PrincipalId::new_node_test_id(id))
+    }
+
+    fn lossy_link() -> LinkConditions {
+        LinkConditions {
+            latency: LatencyDistribution::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(100),
+            },
+            loss_probability: 0.3,
+            bandwidth_bytes_per_second: None,
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_deliveries() {
+        let now = Instant::now();
+        let deliveries = |seed| {
+            let simulation = NetworkSimulation::new(seed).with_default_link(lossy_link());
+            (0..100)
+                .map(|i| simulation.transmit(&node(i % 3), &node(3), 1000, now))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(deliveries(42), deliveries(42));
+        assert_ne!(deliveries(42), deliveries(43));
+    }
+
+    #[test]
+    fn deliveries_of_a_link_do_not_depend_on_other_links() {
+        let now = Instant::now();
+        let a = NetworkSimulation::new(7).with_default_link(lossy_link());
+        let b = NetworkSimulation::new(7).with_default_link(lossy_link());
+
+        let mut deliveries_a = Vec::new();
+        let mut deliveries_b = Vec::new();
+        for _ in 0..50 {
+            deliveries_a.push(a.transmit(&node(1), &node(2), 1000, now));
+            // Interleave traffic on another link only in `b`.
+            b.transmit(&node(2), &node(1), 1000, now);
+            deliveries_b.push(b.transmit(&node(1), &node(2), 1000, now));
+        }
+        assert_eq!(deliveries_a, deliveries_b);
+    }
+
+    #[test]
+    fn loss_probability_is_respected() {
+        let now = Instant::now();
+        let simulation = NetworkSimulation::new(1).with_default_link(lossy_link());
+        let dropped = (0..10_000)
+            .filter(|_| simulation.transmit(&node(1), &node(2), 1, now) == Delivery::Dropped)
+            .count();
+        assert!(
+            (2_500..3_500).contains(&dropped),
+            "{dropped} messages dropped"
+        );
+    }
+
+    #[test]
+    fn bandwidth_serializes_messages() {
+        let now = Instant::now();
+        let simulation = NetworkSimulation::new(1).with_link(
+            node(1),
+            node(2),
+            LinkConditions {
+                latency: LatencyDistribution::Constant(Duration::from_millis(10)),
+                loss_probability: 0.0,
+                bandwidth_bytes_per_second: Some(1_000_000),
+            },
+        );
+
+        for i in 1..=3 {
+            assert_eq!(
+                simulation.transmit(&node(1), &node(2), 100_000, now),
+                Delivery::After(Duration::from_millis(100 * i + 10))
+            );
+        }
+        // The default link in the other direction is unaffected.
+        assert_eq!(
+            simulation.transmit(&node(2), &node(1), 100_000, now),
+            Delivery::After(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn scripted_partitions_are_applied_and_healed() {
+        let now = Instant::now();
+        let simulation = NetworkSimulation::new(1)
+            .with_heal(Duration::from_secs(20))
+            .with_partition(Duration::from_secs(10), vec![vec![node(1), node(2)]]);
+
+        let at = |secs| now + Duration::from_secs(secs);
+        assert!(!simulation.is_partitioned(&node(1), &node(3), at(5)));
+        assert!(simulation.is_partitioned(&node(1), &node(3), at(15)));
+        assert!(simulation.is_partitioned(&node(3), &node(2), at(15)));
+        assert!(!simulation.is_partitioned(&node(1), &node(2), at(15)));
+        // Nodes outside of all groups can reach each other.
+        assert!(!simulation.is_partitioned(&node(3), &node(4), at(15)));
+        assert_eq!(
+            simulation.transmit(&node(1), &node(3), 1, at(15)),
+            Delivery::Dropped
+        );
+        assert!(!simulation.is_partitioned(&node(1), &node(3), at(25)));
+    }
+}
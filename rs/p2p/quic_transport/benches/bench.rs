@@ -17,7 +17,9 @@ use ic_metrics::MetricsRegistry;
 use ic_p2p_test_utils::{
     RegistryConsensusHandle, create_registry_handle, temp_crypto_component_with_tls_keys,
 };
-use ic_quic_transport::{QuicTransport, SubnetTopology, Transport, create_udp_socket};
+use ic_quic_transport::{
+    QuicTransport, SubnetTopology, TrafficShapingConfig, Transport, create_udp_socket,
+};
 use ic_types_test_utils::ids::node_test_id;
 use tokio::{
     runtime::{Handle, Runtime},
@@ -59,6 +61,7 @@ fn spawn_transport(
         watch_rx,
         create_udp_socket(&rt, node_addr),
         Router::new().route("/", any(pong)),
+        TrafficShapingConfig::default(),
     ));
     (transport, node_id, node_addr)
 }
@@ -14,6 +14,7 @@ use crate::{
         INFALIBBLE, QuicTransportMetrics, observe_conn_error, observe_read_to_end_error,
         observe_stopped_error, observe_write_error,
     },
+    traffic_shaping::TrafficShaper,
 };
 
 static CONN_ID_SEQ: AtomicU64 = AtomicU64::new(1);
@@ -23,15 +24,21 @@ pub struct ConnectionHandle {
     conn: Connection,
     metrics: QuicTransportMetrics,
     conn_id: ConnId,
+    traffic_shaper: TrafficShaper,
 }
 
 impl ConnectionHandle {
-    pub fn new(conn: Connection, metrics: QuicTransportMetrics) -> Self {
+    pub(crate) fn new(
+        conn: Connection,
+        metrics: QuicTransportMetrics,
+        traffic_shaper: TrafficShaper,
+    ) -> Self {
         let conn_id = CONN_ID_SEQ.fetch_add(1, Ordering::SeqCst);
         Self {
             conn,
             conn_id: conn_id.into(),
             metrics,
+            traffic_shaper,
         }
     }
 
@@ -42,6 +49,10 @@ impl ConnectionHandle {
     pub fn conn(&self) -> &Connection {
         &self.conn
     }
+
+    pub(crate) fn traffic_shaper(&self) -> &TrafficShaper {
+        &self.traffic_shaper
+    }
     /// Executes an RPC operation over an already-established connection.
     ///
     /// This method leverages the QUIC transport layer, which continuously monitors the connection’s health
@@ -86,13 +97,14 @@ impl ConnectionHandle {
             .get::<MessagePriority>()
             .copied()
             .unwrap_or_default();
-        let _ = send_stream.set_priority(priority.into());
+        let traffic_class = self.traffic_shaper.classify(request.uri().path());
+        let _ = send_stream.set_priority(traffic_class.clamp_stream_priority(i32::from(priority)));
 
         bytes_sent_counter.inc_by(request.body().len() as u64);
         let request_bytes = into_request_bytes(request);
 
-        send_stream
-            .write_all(&request_bytes)
+        self.traffic_shaper
+            .write_all(send_stream, traffic_class, &request_bytes)
             .await
             .inspect_err(|err| {
                 observe_write_error(
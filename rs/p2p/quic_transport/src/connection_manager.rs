@@ -57,6 +57,7 @@ use crate::{
     Shutdown, SubnetTopology,
     connection_handle::ConnectionHandle,
     metrics::{CONNECTION_RESULT_FAILED_LABEL, CONNECTION_RESULT_SUCCESS_LABEL},
+    traffic_shaping::{TrafficShaper, TrafficShapingConfig},
 };
 use crate::{metrics::QuicTransportMetrics, request_handler::start_stream_acceptor};
 
@@ -126,6 +127,7 @@ struct ConnectionManager {
     endpoint: Endpoint,
     transport_config: Arc<quinn::TransportConfig>,
     router: Router,
+    traffic_shaping_config: Arc<TrafficShapingConfig>,
 }
 
 #[derive(Debug, Error)]
@@ -189,6 +191,7 @@ pub(crate) fn start_connection_manager(
     watcher: tokio::sync::watch::Receiver<SubnetTopology>,
     socket: Arc<dyn AsyncUdpSocket>,
     router: Router,
+    traffic_shaping_config: TrafficShapingConfig,
 ) -> Shutdown {
     let topology = watcher.borrow().clone();
 
@@ -256,6 +259,7 @@ pub(crate) fn start_connection_manager(
         inbound_connecting: JoinSet::new(),
         active_connections: JoinMap::new(),
         router,
+        traffic_shaping_config: Arc::new(traffic_shaping_config),
     };
     Shutdown::spawn_on_with_cancellation(
         |cancellation: CancellationToken| manager.run(cancellation),
@@ -502,7 +506,11 @@ impl ConnectionManager {
         // This should be done while holding a write lock to the peer map
         // such that the next read call sees the new id.
 
-        let connection_handle = ConnectionHandle::new(connection, self.metrics.clone());
+        let connection_handle = ConnectionHandle::new(
+            connection,
+            self.metrics.clone(),
+            TrafficShaper::new(self.traffic_shaping_config.clone(), self.metrics.clone()),
+        );
 
         // dropping the old connection will result in closing it
         if let Some(old_conn) = peer_map_mut.insert(peer_id, connection_handle.clone()) {
@@ -10,6 +10,9 @@
 //!  - Request Handler (request_handler.rs): Accepts streams on an active connection.
 //!    Spawned by the connection manager for each connection.
 //!  - Connection Handle (connection_handle.rs): Provides rpc and push interfaces to a peer.
+//!  - Traffic Shaping (traffic_shaping.rs): Schedules the sends of a connection by traffic
+//!    class. Consensus-critical traffic gets strict priority, all other classes share the
+//!    connection by weight and can be rate limited per peer.
 //!
 //! API:
 //!  - Constructor takes a topology watcher. The topology defines the
@@ -67,7 +70,9 @@ mod connection_handle;
 mod connection_manager;
 mod metrics;
 mod request_handler;
+mod traffic_shaping;
 pub use crate::connection_manager::create_udp_socket;
+pub use crate::traffic_shaping::{TrafficClass, TrafficClassConfig, TrafficShapingConfig};
 
 /// On purpose the value is big, otherwise there is risk of not processing important consensus messages.
 /// E.g. summary blocks generated by the consensus protocol for 40 node subnet can be bigger than 5MB.
@@ -153,6 +158,7 @@ impl QuicTransport {
         udp_socket: Arc<dyn AsyncUdpSocket>,
         // Make sure this is respected https://docs.rs/axum/latest/axum/struct.Router.html#a-note-about-performance
         router: Router,
+        traffic_shaping_config: TrafficShapingConfig,
     ) -> QuicTransport {
         info!(log, "Starting Quic transport.");
 
@@ -169,6 +175,7 @@ impl QuicTransport {
             topology_watcher,
             udp_socket,
             router,
+            traffic_shaping_config,
         );
 
         QuicTransport {
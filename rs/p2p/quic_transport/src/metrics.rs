@@ -12,6 +12,7 @@ const REQUEST_TASK_MONITOR_NAME: &str = "quic_transport_request_handler";
 const HANDLER_LABEL: &str = "handler";
 const ERROR_TYPE_LABEL: &str = "error";
 const QUINN_API_LABEL: &str = "quinn_api";
+const TRAFFIC_CLASS_LABEL: &str = "class";
 pub(crate) const CONNECTION_RESULT_SUCCESS_LABEL: &str = "success";
 pub(crate) const CONNECTION_RESULT_FAILED_LABEL: &str = "failed";
 pub(crate) const ERROR_TYPE_APP: &str = "app";
@@ -54,6 +55,10 @@ pub struct QuicTransportMetrics {
     pub connection_handle_errors_total: IntCounterVec,
     pub connection_handle_outgoing_streams_total: IntGauge,
     pub connection_handle_incoming_streams_total: IntGauge,
+    // Traffic shaping
+    pub traffic_class_bytes_sent_total: IntCounterVec,
+    pub traffic_class_wait_duration_seconds: HistogramVec,
+    pub traffic_class_throttled_total: IntCounterVec,
     // Quinn
     quinn_path_rtt_seconds: GaugeVec,
     quinn_path_congestion_window: IntGaugeVec,
@@ -166,6 +171,23 @@ impl QuicTransportMetrics {
                 "quic_transport_connection_handle_outgoing_streams_total",
                 "The number of concurrent outgoing streams accross all connections.",
             ),
+            // Traffic shaping
+            traffic_class_bytes_sent_total: metrics_registry.int_counter_vec(
+                "quic_transport_traffic_class_bytes_sent_total",
+                "Bytes written to streams by traffic class.",
+                &[TRAFFIC_CLASS_LABEL],
+            ),
+            traffic_class_wait_duration_seconds: metrics_registry.histogram_vec(
+                "quic_transport_traffic_class_wait_duration_seconds",
+                "Time a chunk of shaped traffic waited to be scheduled by traffic class.",
+                decimal_buckets(-4, 0),
+                &[TRAFFIC_CLASS_LABEL],
+            ),
+            traffic_class_throttled_total: metrics_registry.int_counter_vec(
+                "quic_transport_traffic_class_throttled_total",
+                "Number of times a traffic class exceeded its per-peer rate limit.",
+                &[TRAFFIC_CLASS_LABEL],
+            ),
             // Quinn stats
             quinn_path_rtt_seconds: metrics_registry.gauge_vec(
                 "quic_transport_quinn_path_rtt_seconds",
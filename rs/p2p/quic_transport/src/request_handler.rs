@@ -30,6 +30,7 @@ use crate::{
         ERROR_TYPE_APP, INFALIBBLE, QuicTransportMetrics, STREAM_TYPE_BIDI, observe_conn_error,
         observe_read_to_end_error, observe_stopped_error, observe_write_error,
     },
+    traffic_shaping::TrafficShaper,
 };
 
 const QUIC_METRIC_SCRAPE_INTERVAL: Duration = Duration::from_secs(5);
@@ -81,6 +82,7 @@ pub async fn start_stream_acceptor(
                                 handle_bi_stream(
                                     peer_id,
                                     conn_handle.conn_id(),
+                                    conn_handle.traffic_shaper().clone(),
                                     metrics.clone(),
                                     router.clone(),
                                     send_stream,
@@ -113,6 +115,7 @@ pub async fn start_stream_acceptor(
 async fn handle_bi_stream(
     peer_id: NodeId,
     conn_id: ConnId,
+    traffic_shaper: TrafficShaper,
     metrics: QuicTransportMetrics,
     router: Router,
     mut send_stream_guard: ResetStreamOnDrop,
@@ -122,8 +125,11 @@ async fn handle_bi_stream(
     let mut request = read_request(recv_stream, &metrics).await?;
     request.extensions_mut().insert::<NodeId>(peer_id);
     request.extensions_mut().insert::<ConnId>(conn_id);
+    // The response belongs to the same traffic class as the request.
+    let traffic_class = traffic_shaper.classify(request.uri().path());
 
     let send_stream = &mut send_stream_guard.send_stream;
+    let _ = send_stream.set_priority(traffic_class.stream_priority());
     let svc = router.oneshot(request);
     let stopped_fut = send_stream.stopped();
     let response = tokio::select! {
@@ -147,8 +153,8 @@ async fn handle_bi_stream(
     // if the other peer has closed the connection. In this case `accept_bi` in the peer event
     // loop will close this connection.
     let response_bytes = to_response_bytes(response).await?;
-    send_stream
-        .write_all(&response_bytes)
+    traffic_shaper
+        .write_all(send_stream, traffic_class, &response_bytes)
         .await
         .inspect_err(|err| {
             observe_write_error(err, "write_all", &metrics.request_handle_errors_total);
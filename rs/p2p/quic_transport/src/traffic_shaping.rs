@@ -0,0 +1,521 @@
+//! Per-peer traffic shaping.
+//!
+//! Every request, and the response to it, is assigned a [`TrafficClass`] based on the
+//! path of the request URI. Sends of [`TrafficClass::Critical`] traffic bypass the shaping
+//! and are sent with strict priority over all other streams of the connection.
+//!
+//! Sends of all other classes are split into chunks of `SHAPING_CHUNK_SIZE_BYTES` and
+//! scheduled per connection (i.e. per peer) with start-time fair queuing: if several
+//! classes compete for the connection, each class gets a share of the bandwidth that is
+//! proportional to its weight. Additionally, each class can be rate limited per peer.
+//!
+//! Scheduling only decides the order in which chunks are handed to the connection; a
+//! chunk holds no connection-wide resource while it is written. This way a stream that
+//! is blocked by the flow control of its receiver never delays the other streams of the
+//! connection. How the data handed to a congested connection is transmitted is decided
+//! by the stream priorities of the classes.
+//!
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use quinn::{SendStream, WriteError};
+use tokio::{sync::Notify, time::Instant};
+
+use crate::metrics::QuicTransportMetrics;
+
+/// Size of the chunks in which shaped sends are scheduled.
+const SHAPING_CHUNK_SIZE_BYTES: usize = 64 * 1024;
+
+/// Traffic classes in decreasing order of importance.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrafficClass {
+    /// Consensus-critical traffic. Never delayed by traffic of the other classes.
+    Critical,
+    #[default]
+    Default,
+    /// Bulk transfers, e.g. state sync chunks and artifact downloads.
+    Bulk,
+}
+
+impl TrafficClass {
+    const ALL: [TrafficClass; 3] = [
+        TrafficClass::Critical,
+        TrafficClass::Default,
+        TrafficClass::Bulk,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrafficClass::Critical => "critical",
+            TrafficClass::Default => "default",
+            TrafficClass::Bulk => "bulk",
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// The base priority of QUIC streams carrying traffic of this class.
+    pub(crate) fn stream_priority(&self) -> i32 {
+        self.stream_priority_range().0
+    }
+
+    /// The range of priorities, as `(min, max)`, of QUIC streams carrying traffic
+    /// of this class. The ranges of the classes do not overlap, so that message
+    /// priorities never lift traffic above a more important class.
+    fn stream_priority_range(&self) -> (i32, i32) {
+        match self {
+            TrafficClass::Critical => (2, 3),
+            TrafficClass::Default => (0, 1),
+            TrafficClass::Bulk => (-1, -1),
+        }
+    }
+
+    /// Returns the stream priority for traffic of this class with the given message
+    /// priority, clamped to the priority range of the class.
+    pub(crate) fn clamp_stream_priority(&self, priority: i32) -> i32 {
+        let (min, max) = self.stream_priority_range();
+        min.saturating_add(priority).clamp(min, max)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrafficClassConfig {
+    /// Relative share of the bandwidth of a congested connection.
+    pub weight: u32,
+    /// Optional limit of the bytes per second that are sent to a single peer.
+    pub max_bytes_per_second_per_peer: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrafficShapingConfig {
+    /// Request URI paths mapped to the traffic class of requests to them. The longest
+    /// matching path prefix wins. Requests to any other path are `TrafficClass::Default`.
+    pub routes: BTreeMap<String, TrafficClass>,
+    /// The configuration of the shaped classes. The configuration of
+    /// `TrafficClass::Critical` is ignored, as critical traffic is never shaped.
+    pub classes: BTreeMap<TrafficClass, TrafficClassConfig>,
+}
+
+impl Default for TrafficShapingConfig {
+    fn default() -> Self {
+        let critical = [
+            "/consensus/update",
+            "/certification/update",
+            "/dkg/update",
+            "/idkg/update",
+        ];
+        // Block and ingress fetches for block making are on the critical path of
+        // consensus, so `/consensus/rpc` and `/block/ingress/rpc` stay `Default`.
+        let bulk = [
+            "/state-sync/chunk",
+            "/certification/rpc",
+            "/dkg/rpc",
+            "/idkg/rpc",
+            "/ingress/rpc",
+            "/canisterhttp/rpc",
+        ];
+        let routes = critical
+            .into_iter()
+            .map(|path| (path.to_string(), TrafficClass::Critical))
+            .chain(
+                bulk.into_iter()
+                    .map(|path| (path.to_string(), TrafficClass::Bulk)),
+            )
+            .collect();
+
+        let classes = BTreeMap::from([
+            (
+                TrafficClass::Default,
+                TrafficClassConfig {
+                    weight: 4,
+                    max_bytes_per_second_per_peer: None,
+                },
+            ),
+            (
+                TrafficClass::Bulk,
+                TrafficClassConfig {
+                    weight: 1,
+                    max_bytes_per_second_per_peer: None,
+                },
+            ),
+        ]);
+
+        Self { routes, classes }
+    }
+}
+
+impl TrafficShapingConfig {
+    /// Returns the traffic class of requests to `path`.
+    pub fn classify(&self, path: &str) -> TrafficClass {
+        self.routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, class)| *class)
+            .unwrap_or_default()
+    }
+}
+
+/// Scheduler of the shaped sends of a single connection.
+#[derive(Clone)]
+pub(crate) struct TrafficShaper {
+    config: Arc<TrafficShapingConfig>,
+    state: Arc<Mutex<SchedulerState>>,
+    notify: Arc<Notify>,
+    metrics: QuicTransportMetrics,
+}
+
+impl std::fmt::Debug for TrafficShaper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrafficShaper")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TrafficShaper {
+    pub(crate) fn new(config: Arc<TrafficShapingConfig>, metrics: QuicTransportMetrics) -> Self {
+        let state = SchedulerState::new(&config, Instant::now());
+        Self {
+            config,
+            state: Arc::new(Mutex::new(state)),
+            notify: Arc::new(Notify::new()),
+            metrics,
+        }
+    }
+
+    pub(crate) fn classify(&self, path: &str) -> TrafficClass {
+        self.config.classify(path)
+    }
+
+    /// Writes `bytes` to `send_stream`, subject to the scheduling of `class`.
+    ///
+    /// Note: The method is cancel-safe.
+    pub(crate) async fn write_all(
+        &self,
+        send_stream: &mut SendStream,
+        class: TrafficClass,
+        bytes: &[u8],
+    ) -> Result<(), WriteError> {
+        let bytes_sent = self
+            .metrics
+            .traffic_class_bytes_sent_total
+            .with_label_values(&[class.as_str()]);
+        if class == TrafficClass::Critical {
+            send_stream.write_all(bytes).await?;
+            bytes_sent.inc_by(bytes.len() as u64);
+            return Ok(());
+        }
+
+        for chunk in bytes.chunks(SHAPING_CHUNK_SIZE_BYTES) {
+            // The grant is not held across the write, see the module documentation.
+            self.acquire(class, chunk.len()).await;
+            send_stream.write_all(chunk).await?;
+            bytes_sent.inc_by(chunk.len() as u64);
+        }
+        Ok(())
+    }
+
+    /// Waits until `class` is next in line and within its rate limit, and accounts
+    /// `len` bytes to it.
+    async fn acquire(&self, class: TrafficClass, len: usize) {
+        let _timer = self
+            .metrics
+            .traffic_class_wait_duration_seconds
+            .with_label_values(&[class.as_str()])
+            .start_timer();
+        let _waiting = WaitGuard::new(self, class);
+
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            let grant = self
+                .state
+                .lock()
+                .unwrap()
+                .try_grant(class, len, Instant::now());
+            match grant {
+                Grant::Granted => {
+                    // The virtual time of `class` advanced, which may unblock other classes.
+                    self.notify.notify_waiters();
+                    return;
+                }
+                Grant::Throttled(delay) => {
+                    self.metrics
+                        .traffic_class_throttled_total
+                        .with_label_values(&[class.as_str()])
+                        .inc();
+                    // Another class might be able to send while `class` is throttled.
+                    self.notify.notify_waiters();
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {},
+                        _ = notified => {},
+                    }
+                }
+                Grant::Blocked => notified.await,
+            }
+        }
+    }
+}
+
+/// Marks a class as waiting for as long as the guard is alive.
+struct WaitGuard<'a> {
+    shaper: &'a TrafficShaper,
+    class: TrafficClass,
+}
+
+impl<'a> WaitGuard<'a> {
+    fn new(shaper: &'a TrafficShaper, class: TrafficClass) -> Self {
+        shaper.state.lock().unwrap().start_waiting(class);
+        Self { shaper, class }
+    }
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        self.shaper.state.lock().unwrap().stop_waiting(self.class);
+        self.shaper.notify.notify_waiters();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Grant {
+    Granted,
+    /// The class exceeded its rate limit and may send again after the given delay.
+    Throttled(Duration),
+    /// Another class is next in line.
+    Blocked,
+}
+
+#[derive(Debug)]
+struct ClassState {
+    weight: f64,
+    max_bytes_per_second: Option<f64>,
+    /// Number of sends of this class waiting to be scheduled.
+    waiting: usize,
+    /// Start-time fair queuing tag of the next chunk of this class.
+    virtual_time: f64,
+    /// Token bucket of the rate limit, in bytes. Can become negative, in which case
+    /// the class is throttled until the debt is paid off.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl ClassState {
+    fn throttled_for(&mut self, now: Instant) -> Option<Duration> {
+        let rate = self.max_bytes_per_second?;
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        // Allow bursts of up to one second worth of traffic.
+        self.tokens = (self.tokens + elapsed * rate).min(rate);
+        self.last_refill = now;
+        (self.tokens < 0.0).then(|| Duration::from_secs_f64(-self.tokens / rate))
+    }
+}
+
+#[derive(Debug)]
+struct SchedulerState {
+    classes: Vec<ClassState>,
+    /// The virtual time of the most recently scheduled chunk.
+    virtual_clock: f64,
+}
+
+impl SchedulerState {
+    fn new(config: &TrafficShapingConfig, now: Instant) -> Self {
+        let classes = TrafficClass::ALL
+            .iter()
+            .map(|class| {
+                let class_config = config.classes.get(class);
+                let max_bytes_per_second = class_config
+                    .and_then(|c| c.max_bytes_per_second_per_peer)
+                    .map(|rate| rate as f64);
+                ClassState {
+                    weight: class_config.map_or(1, |c| c.weight.max(1)) as f64,
+                    max_bytes_per_second,
+                    waiting: 0,
+                    virtual_time: 0.0,
+                    tokens: max_bytes_per_second.unwrap_or_default(),
+                    last_refill: now,
+                }
+            })
+            .collect();
+        Self {
+            classes,
+            virtual_clock: 0.0,
+        }
+    }
+
+    fn start_waiting(&mut self, class: TrafficClass) {
+        let virtual_clock = self.virtual_clock;
+        let state = &mut self.classes[class.index()];
+        if state.waiting == 0 {
+            // An idle class must not accumulate credit for the time it was idle.
+            state.virtual_time = state.virtual_time.max(virtual_clock);
+        }
+        state.waiting += 1;
+    }
+
+    fn stop_waiting(&mut self, class: TrafficClass) {
+        self.classes[class.index()].waiting -= 1;
+    }
+
+    fn try_grant(&mut self, class: TrafficClass, len: usize, now: Instant) -> Grant {
+        if let Some(delay) = self.classes[class.index()].throttled_for(now) {
+            return Grant::Throttled(delay);
+        }
+        let virtual_time = self.classes[class.index()].virtual_time;
+        let mut competitors = self
+            .classes
+            .iter_mut()
+            .enumerate()
+            .filter(|(index, state)| *index != class.index() && state.waiting > 0);
+        if competitors.any(|(_, state)| {
+            state.virtual_time < virtual_time && state.throttled_for(now).is_none()
+        }) {
+            return Grant::Blocked;
+        }
+
+        let state = &mut self.classes[class.index()];
+        self.virtual_clock = state.virtual_time;
+        state.virtual_time += len as f64 / state.weight;
+        state.tokens -= len as f64;
+        Grant::Granted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bulk_rate: Option<u64>) -> TrafficShapingConfig {
+        let mut config = TrafficShapingConfig::default();
+        config
+            .classes
+            .get_mut(&TrafficClass::Bulk)
+            .unwrap()
+            .max_bytes_per_second_per_peer = bulk_rate;
+        config
+    }
+
+    #[test]
+    fn classify_uses_longest_matching_prefix() {
+        let mut config = TrafficShapingConfig::default();
+        config
+            .routes
+            .insert("/state-sync".to_string(), TrafficClass::Critical);
+
+        assert_eq!(config.classify("/consensus/update"), TrafficClass::Critical);
+        assert_eq!(config.classify("/consensus/rpc"), TrafficClass::Default);
+        assert_eq!(config.classify("/block/ingress/rpc"), TrafficClass::Default);
+        assert_eq!(config.classify("/idkg/rpc"), TrafficClass::Bulk);
+        assert_eq!(config.classify("/state-sync/chunk"), TrafficClass::Bulk);
+        assert_eq!(
+            config.classify("/state-sync/advert"),
+            TrafficClass::Critical
+        );
+        assert_eq!(config.classify("/ingress/update"), TrafficClass::Default);
+    }
+
+    #[test]
+    fn congested_connection_is_shared_by_weight() {
+        let now = Instant::now();
+        let mut state = SchedulerState::new(&config(None), now);
+        state.start_waiting(TrafficClass::Default);
+        state.start_waiting(TrafficClass::Bulk);
+
+        let mut granted = BTreeMap::<TrafficClass, usize>::new();
+        for _ in 0..100 {
+            let class = [TrafficClass::Default, TrafficClass::Bulk]
+                .into_iter()
+                .find(|class| {
+                    state.try_grant(*class, SHAPING_CHUNK_SIZE_BYTES, now) == Grant::Granted
+                })
+                .expect("one of the classes must be granted");
+            *granted.entry(class).or_default() += 1;
+        }
+
+        assert_eq!(granted[&TrafficClass::Default], 80);
+        assert_eq!(granted[&TrafficClass::Bulk], 20);
+    }
+
+    #[test]
+    fn idle_class_does_not_accumulate_credit() {
+        let now = Instant::now();
+        let mut state = SchedulerState::new(&config(None), now);
+        state.start_waiting(TrafficClass::Default);
+        for _ in 0..10 {
+            assert_eq!(
+                state.try_grant(TrafficClass::Default, SHAPING_CHUNK_SIZE_BYTES, now),
+                Grant::Granted
+            );
+        }
+
+        // Bulk starts competing only now and must not get the next 10 chunks in a row.
+        state.start_waiting(TrafficClass::Bulk);
+        assert_eq!(
+            state.try_grant(TrafficClass::Bulk, SHAPING_CHUNK_SIZE_BYTES, now),
+            Grant::Granted
+        );
+        assert_eq!(
+            state.try_grant(TrafficClass::Bulk, SHAPING_CHUNK_SIZE_BYTES, now),
+            Grant::Blocked
+        );
+    }
+
+    #[test]
+    fn message_priority_is_clamped_to_class() {
+        for class in TrafficClass::ALL {
+            let (min, max) = class.stream_priority_range();
+            assert_eq!(class.stream_priority(), min);
+            assert_eq!(class.clamp_stream_priority(0), min);
+            assert_eq!(class.clamp_stream_priority(i32::MAX), max);
+        }
+        assert_eq!(TrafficClass::Bulk.clamp_stream_priority(1), -1);
+        assert_eq!(TrafficClass::Default.clamp_stream_priority(1), 1);
+        assert!(
+            TrafficClass::Default.clamp_stream_priority(1)
+                < TrafficClass::Critical.clamp_stream_priority(0)
+        );
+    }
+
+    #[test]
+    fn rate_limited_class_is_throttled() {
+        let rate = 2 * SHAPING_CHUNK_SIZE_BYTES as u64;
+        let now = Instant::now();
+        let mut state = SchedulerState::new(&config(Some(rate)), now);
+        state.start_waiting(TrafficClass::Bulk);
+
+        for _ in 0..3 {
+            assert_eq!(
+                state.try_grant(TrafficClass::Bulk, SHAPING_CHUNK_SIZE_BYTES, now),
+                Grant::Granted
+            );
+        }
+        // The burst of one second worth of traffic is exceeded by one chunk.
+        assert_eq!(
+            state.try_grant(TrafficClass::Bulk, SHAPING_CHUNK_SIZE_BYTES, now),
+            Grant::Throttled(Duration::from_millis(500))
+        );
+        // A throttled class does not block other classes.
+        state.start_waiting(TrafficClass::Default);
+        assert_eq!(
+            state.try_grant(TrafficClass::Default, SHAPING_CHUNK_SIZE_BYTES, now),
+            Grant::Granted
+        );
+        state.stop_waiting(TrafficClass::Default);
+
+        let later = now + Duration::from_millis(500);
+        assert_eq!(
+            state.try_grant(TrafficClass::Bulk, SHAPING_CHUNK_SIZE_BYTES, later),
+            Grant::Granted
+        );
+    }
+}
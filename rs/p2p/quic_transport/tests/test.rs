@@ -15,7 +15,7 @@ use ic_p2p_test_utils::{
         wait_for_timeout,
     },
 };
-use ic_quic_transport::{QuicTransport, Transport, create_udp_socket};
+use ic_quic_transport::{QuicTransport, TrafficShapingConfig, Transport, create_udp_socket};
 use ic_test_utilities_logger::with_test_replica_logger;
 use ic_types_test_utils::ids::{NODE_1, NODE_2, NODE_3, NODE_4, NODE_5};
 use tokio::{
@@ -111,6 +111,7 @@ fn test_graceful_shutdown() {
             topology_watcher.clone(),
             create_udp_socket(rt.handle(), socket_1),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         let mut transport_2 = Arc::new(QuicTransport::start(
@@ -123,6 +124,7 @@ fn test_graceful_shutdown() {
             topology_watcher,
             create_udp_socket(rt.handle(), socket_2),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         registry_handler.add_node(
@@ -188,6 +190,7 @@ fn test_real_socket() {
             topology_watcher.clone(),
             create_udp_socket(rt.handle(), socket_1),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         let transport_2 = Arc::new(QuicTransport::start(
@@ -200,6 +203,7 @@ fn test_real_socket() {
             topology_watcher,
             create_udp_socket(rt.handle(), socket_2),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         registry_handler.add_node(
@@ -257,6 +261,7 @@ fn test_real_socket_large_msg() {
             topology_watcher.clone(),
             create_udp_socket(rt.handle(), socket_1),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         let transport_2 = Arc::new(QuicTransport::start(
@@ -269,6 +274,7 @@ fn test_real_socket_large_msg() {
             topology_watcher,
             create_udp_socket(rt.handle(), socket_2),
             ConnectivityChecker::router(),
+            TrafficShapingConfig::default(),
         ));
 
         registry_handler.add_node(
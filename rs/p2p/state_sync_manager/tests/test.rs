@@ -13,7 +13,9 @@ use crate::common::{
 use common::SharableMockStateSync;
 use ic_interfaces::p2p::state_sync::{AddChunkError, ChunkId, StateSyncArtifactId};
 use ic_logger::info;
-use ic_memory_transport::TransportRouter;
+use ic_memory_transport::{
+    LatencyDistribution, LinkConditions, NetworkSimulation, TransportRouter,
+};
 use ic_p2p_test_utils::{
     ConnectivityChecker,
     mocks::MockStateSync,
@@ -23,7 +25,7 @@ use ic_p2p_test_utils::{
     },
 };
 use ic_test_utilities_logger::with_test_replica_logger;
-use ic_types::{Height, RegistryVersion, crypto::CryptoHash};
+use ic_types::{Height, NodeId, PrincipalId, RegistryVersion, crypto::CryptoHash};
 use ic_types_test_utils::ids::{NODE_1, NODE_2, NODE_3};
 use tokio::sync::Notify;
 use turmoil::Builder;
@@ -189,6 +191,92 @@ fn test_full_subnet_mini_chunks() {
     });
 }
 
+/// Test one node syncing the state in a 5 node subnet over lossy links with varying latency,
+/// while the syncing node is partitioned from the rest of the subnet for a while.
+///
+/// The test runs on a current-thread runtime with paused time, so that the simulation is
+/// reproducible from its seed.
+#[test]
+fn test_sync_under_adverse_network_conditions() {
+    const HEAL_AFTER: Duration = Duration::from_secs(10);
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .start_paused(true)
+        .build()
+        .unwrap();
+    let rt_handle = runtime.handle().clone();
+    with_test_replica_logger(|log| {
+        runtime.block_on(async move {
+            let subnet_size = 5;
+            let node_id = |i| NodeId::from(PrincipalId::new_node_test_id(i));
+            let start = tokio::time::Instant::now();
+            let simulation = NetworkSimulation::new(17)
+                .with_default_link(LinkConditions {
+                    latency: LatencyDistribution::Uniform {
+                        min: Duration::from_millis(10),
+                        max: Duration::from_millis(150),
+                    },
+                    loss_probability: 0.05,
+                    bandwidth_bytes_per_second: Some(50_000_000),
+                })
+                .with_partition(
+                    Duration::ZERO,
+                    vec![vec![node_id(0)], (1..subnet_size).map(node_id).collect()],
+                )
+                .with_heal(HEAL_AFTER);
+            let mut transport_router = TransportRouter::with_network_simulation(simulation);
+            let global_state = State::new();
+
+            // Create empty node
+            let (state_sync_empty, _join_handle_empty) = create_node(
+                0,
+                log.clone(),
+                &mut transport_router,
+                &rt_handle,
+                false,
+                global_state.clone(),
+                latency_50ms_throughput_300mbits(),
+            );
+
+            let mut join_handles = Vec::new();
+            let mut states = Vec::new();
+            // Create nodes that provide global state.
+            for i in 1..subnet_size {
+                let (state_sync, join_handle) = create_node(
+                    i,
+                    log.clone(),
+                    &mut transport_router,
+                    &rt_handle,
+                    true,
+                    global_state.clone(),
+                    latency_30ms_throughput_1000mbits(),
+                );
+                join_handles.push(join_handle);
+                states.push(state_sync);
+            }
+            global_state.add_new_chunks(100, 1_000_000);
+
+            // The empty node can't sync while it is partitioned.
+            tokio::time::sleep_until(start + HEAL_AFTER - Duration::from_millis(100)).await;
+            assert!(
+                states.iter().all(|s| !s.is_equal(&state_sync_empty)),
+                "The partitioned node synced the state before the heal."
+            );
+
+            // Verify that empty node has caught up
+            let fut = async move {
+                while !states.is_empty() {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                    states.retain(|s| !s.is_equal(&state_sync_empty));
+                }
+            };
+            tokio::time::timeout(TEST_STATE_SYNC_TIMEOUT, fut)
+                .await
+                .unwrap();
+        });
+    });
+}
+
 /// Test one node syncing the state in a 13 node subnet with a quickly changing state to sync.
 #[test]
 fn test_full_subnet_fast_changing_state() {
@@ -18,7 +18,9 @@ use ic_protobuf::registry::{
     node::v1::{ConnectionEndpoint, NodeRecord},
     subnet::v1::SubnetRecord,
 };
-use ic_quic_transport::{ConnId, QuicTransport, SubnetTopology, Transport, create_udp_socket};
+use ic_quic_transport::{
+    ConnId, QuicTransport, SubnetTopology, TrafficShapingConfig, Transport, create_udp_socket,
+};
 use ic_registry_client_fake::FakeRegistryClient;
 use ic_registry_keys::make_node_record_key;
 use ic_registry_local_registry::LocalRegistry;
@@ -213,6 +215,7 @@ pub fn fully_connected_localhost_subnet(
             topology_watcher.clone(),
             create_udp_socket(rt, socket),
             router,
+            TrafficShapingConfig::default(),
         )) as Arc<_>;
         registry_handler.add_node(
             RegistryVersion::from(i as u64 + 1),
@@ -27,7 +27,7 @@ use ic_interfaces::{
 use ic_logger::ReplicaLogger;
 use ic_metrics::MetricsRegistry;
 use ic_quic_transport::SubnetTopology;
-use ic_quic_transport::{QuicTransport, Transport};
+use ic_quic_transport::{QuicTransport, TrafficShapingConfig, Transport};
 use ic_state_manager::state_sync::types::StateSyncMessage;
 use ic_types::{NodeId, RegistryVersion};
 use quinn::{self, AsyncUdpSocket, UdpPoller, udp::EcnCodepoint};
@@ -406,6 +406,7 @@ pub fn add_transport_to_sim<F>(
                 topology_watcher_clone.clone(),
                 Arc::new(custom_udp),
                 router.unwrap_or_default(),
+                TrafficShapingConfig::default(),
             ));
 
             if let Some((_, con_manager)) = con {
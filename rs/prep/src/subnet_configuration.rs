@@ -317,6 +317,7 @@ impl SubnetConfig {
             ssh_backup_access: self.ssh_backup_access,
             chain_key_config: self.chain_key_config,
             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+            adaptive_block_time_config: None,
         };
 
         let dkg_dealing_encryption_pubkeys: BTreeMap<_, _> = initialized_nodes
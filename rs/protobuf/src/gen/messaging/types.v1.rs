@@ -67,8 +67,15 @@ pub struct VetKdKeyId {
     pub name: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
+pub struct BlsKeyId {
+    #[prost(enumeration = "BlsCurve", tag = "1")]
+    pub curve: i32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
 pub struct MasterPublicKeyId {
-    #[prost(oneof = "master_public_key_id::KeyId", tags = "1, 2, 3")]
+    #[prost(oneof = "master_public_key_id::KeyId", tags = "1, 2, 3, 4")]
     pub key_id: ::core::option::Option<master_public_key_id::KeyId>,
 }
 /// Nested message and enum types in `MasterPublicKeyId`.
@@ -81,6 +88,8 @@ pub mod master_public_key_id {
         Schnorr(super::SchnorrKeyId),
         #[prost(message, tag = "3")]
         Vetkd(super::VetKdKeyId),
+        #[prost(message, tag = "4")]
+        Bls(super::BlsKeyId),
     }
 }
 /// A non-interactive distributed key generation (NI-DKG) tag.
@@ -146,6 +155,7 @@ impl NiDkgTag {
 pub enum EcdsaCurve {
     Unspecified = 0,
     Secp256k1 = 1,
+    Secp256r1 = 2,
 }
 impl EcdsaCurve {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -156,6 +166,7 @@ impl EcdsaCurve {
         match self {
             Self::Unspecified => "ECDSA_CURVE_UNSPECIFIED",
             Self::Secp256k1 => "ECDSA_CURVE_SECP256K1",
+            Self::Secp256r1 => "ECDSA_CURVE_SECP256R1",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -163,6 +174,7 @@ impl EcdsaCurve {
         match value {
             "ECDSA_CURVE_UNSPECIFIED" => Some(Self::Unspecified),
             "ECDSA_CURVE_SECP256K1" => Some(Self::Secp256k1),
+            "ECDSA_CURVE_SECP256R1" => Some(Self::Secp256r1),
             _ => None,
         }
     }
@@ -247,6 +259,44 @@ impl VetKdCurve {
         }
     }
 }
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum BlsCurve {
+    Unspecified = 0,
+    Bls12381G2 = 1,
+}
+impl BlsCurve {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "BLS_CURVE_UNSPECIFIED",
+            Self::Bls12381G2 => "BLS_CURVE_BLS12_381_G2",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BLS_CURVE_UNSPECIFIED" => Some(Self::Unspecified),
+            "BLS_CURVE_BLS12_381_G2" => Some(Self::Bls12381G2),
+            _ => None,
+        }
+    }
+}
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
 pub struct BasicSignature {
     #[prost(bytes = "vec", tag = "1")]
@@ -74,6 +74,11 @@ pub struct SubnetRecord {
     /// means to behave according to the `subnet_type` field.
     #[prost(enumeration = "CanisterCyclesCostSchedule", tag = "30")]
     pub canister_cycles_cost_schedule: i32,
+    /// If set, the subnet uses an adaptive block time: the notary delay is derived
+    /// from the load of the preceding blocks, within the configured bounds, instead
+    /// of being fixed to `initial_notary_delay_millis`.
+    #[prost(message, optional, tag = "31")]
+    pub adaptive_block_time_config: ::core::option::Option<AdaptiveBlockTimeConfig>,
 }
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
 pub struct EcdsaInitialization {
@@ -342,6 +347,29 @@ pub struct ChainKeyConfig {
     #[prost(uint32, optional, tag = "4")]
     pub max_parallel_pre_signature_transcripts_in_creation: ::core::option::Option<u32>,
 }
+/// Bounds for the adaptive block time of a subnet.
+///
+/// The notary delay starts at `min_notary_delay_millis` and doubles with every
+/// consecutive block without ingress, xnet or other batch input, up to
+/// `max_notary_delay_millis`. A block carrying input resets it to the minimum.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    candid::CandidType,
+    Eq,
+    Clone,
+    Copy,
+    PartialEq,
+    ::prost::Message,
+)]
+pub struct AdaptiveBlockTimeConfig {
+    /// The notary delay (in milliseconds) used while the subnet is under load.
+    #[prost(uint64, tag = "1")]
+    pub min_notary_delay_millis: u64,
+    /// The upper bound (in milliseconds) for the notary delay of an idle subnet.
+    #[prost(uint64, tag = "2")]
+    pub max_notary_delay_millis: u64,
+}
 #[derive(
     serde::Serialize,
     serde::Deserialize,
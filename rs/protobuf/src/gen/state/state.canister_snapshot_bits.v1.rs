@@ -6,6 +6,17 @@ pub struct CanisterTimer {
     #[prost(uint64, optional, tag = "1")]
     pub global_timer_nanos: ::core::option::Option<u64>,
 }
+/// Set for incremental snapshots, which only store the wasm and stable memory
+/// pages that differ from their parent snapshot.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnapshotParent {
+    #[prost(uint64, tag = "1")]
+    pub snapshot_id: u64,
+    #[prost(uint64, repeated, tag = "2")]
+    pub wasm_memory_pages: ::prost::alloc::vec::Vec<u64>,
+    #[prost(uint64, repeated, tag = "3")]
+    pub stable_memory_pages: ::prost::alloc::vec::Vec<u64>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CanisterSnapshotBits {
     #[prost(uint64, tag = "1")]
@@ -44,4 +55,6 @@ pub struct CanisterSnapshotBits {
         tag = "14"
     )]
     pub source: i32,
+    #[prost(message, optional, tag = "15")]
+    pub parent: ::core::option::Option<SnapshotParent>,
 }
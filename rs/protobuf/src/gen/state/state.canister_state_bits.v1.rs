@@ -723,6 +723,9 @@ pub struct CanisterStateBits {
         ::prost::alloc::string::String,
         ::prost::alloc::string::String,
     >,
+    /// Names of the methods whose messages are preferred by the scheduler.
+    #[prost(string, repeated, tag = "57")]
+    pub latency_sensitive_methods: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
     #[prost(oneof = "canister_state_bits::CanisterStatus", tags = "11, 12, 13")]
     pub canister_status: ::core::option::Option<canister_state_bits::CanisterStatus>,
 }
@@ -847,6 +850,7 @@ pub enum CyclesUseCase {
     SchnorrOutcalls = 13,
     VetKd = 14,
     DroppedMessages = 15,
+    BlsOutcalls = 16,
 }
 impl CyclesUseCase {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -873,6 +877,7 @@ impl CyclesUseCase {
             Self::SchnorrOutcalls => "CYCLES_USE_CASE_SCHNORR_OUTCALLS",
             Self::VetKd => "CYCLES_USE_CASE_VET_KD",
             Self::DroppedMessages => "CYCLES_USE_CASE_DROPPED_MESSAGES",
+            Self::BlsOutcalls => "CYCLES_USE_CASE_BLS_OUTCALLS",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -896,6 +901,7 @@ impl CyclesUseCase {
             "CYCLES_USE_CASE_SCHNORR_OUTCALLS" => Some(Self::SchnorrOutcalls),
             "CYCLES_USE_CASE_VET_KD" => Some(Self::VetKd),
             "CYCLES_USE_CASE_DROPPED_MESSAGES" => Some(Self::DroppedMessages),
+            "CYCLES_USE_CASE_BLS_OUTCALLS" => Some(Self::BlsOutcalls),
             _ => None,
         }
     }
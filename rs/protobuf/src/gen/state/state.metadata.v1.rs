@@ -126,8 +126,22 @@ pub struct VetKdArguments {
     pub height: u64,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlsArguments {
+    #[prost(message, optional, tag = "1")]
+    pub key_id: ::core::option::Option<super::super::super::types::v1::BlsKeyId>,
+    /// Absent if only the public key is requested.
+    #[prost(bytes = "vec", optional, tag = "2")]
+    pub message: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag = "3")]
+    pub ni_dkg_id: ::core::option::Option<super::super::super::types::v1::NiDkgId>,
+    #[prost(uint64, tag = "4")]
+    pub height: u64,
+    #[prost(message, optional, tag = "5")]
+    pub canister_id: ::core::option::Option<super::super::super::types::v1::CanisterId>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ThresholdArguments {
-    #[prost(oneof = "threshold_arguments::ThresholdScheme", tags = "1, 2, 3")]
+    #[prost(oneof = "threshold_arguments::ThresholdScheme", tags = "1, 2, 3, 4")]
     pub threshold_scheme: ::core::option::Option<threshold_arguments::ThresholdScheme>,
 }
 /// Nested message and enum types in `ThresholdArguments`.
@@ -140,6 +154,8 @@ pub mod threshold_arguments {
         Schnorr(super::SchnorrArguments),
         #[prost(message, tag = "3")]
         Vetkd(super::VetKdArguments),
+        #[prost(message, tag = "4")]
+        Bls(super::BlsArguments),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -35,6 +35,10 @@ pub struct Stream {
     pub reject_signals: ::prost::alloc::vec::Vec<RejectSignal>,
     #[prost(message, optional, tag = "7")]
     pub reverse_stream_flags: ::core::option::Option<StreamFlags>,
+    #[prost(uint32, tag = "9")]
+    pub congestion_level: u32,
+    #[prost(uint32, tag = "10")]
+    pub reverse_stream_congestion_level: u32,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct StreamEntry {
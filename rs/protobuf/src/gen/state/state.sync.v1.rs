@@ -35,3 +35,14 @@ pub struct MetaManifest {
     #[prost(bytes = "vec", repeated, tag = "2")]
     pub sub_manifest_hashes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
+/// Progress marker of a state sync, persisted alongside the partially fetched
+/// state so that the sync can be resumed across replica restarts.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StateSyncProgress {
+    #[prost(uint64, tag = "1")]
+    pub height: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub root_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub manifest: ::core::option::Option<Manifest>,
+}
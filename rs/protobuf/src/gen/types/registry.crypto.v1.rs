@@ -59,6 +59,7 @@ pub enum AlgorithmId {
     ThresholdSchnorrBip340 = 18,
     ThresholdEd25519 = 19,
     Vetkd = 20,
+    MlDsa65 = 21,
 }
 impl AlgorithmId {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -88,6 +89,7 @@ impl AlgorithmId {
             Self::ThresholdSchnorrBip340 => "ALGORITHM_ID_THRESHOLD_SCHNORR_BIP340",
             Self::ThresholdEd25519 => "ALGORITHM_ID_THRESHOLD_ED25519",
             Self::Vetkd => "ALGORITHM_ID_VETKD",
+            Self::MlDsa65 => "ALGORITHM_ID_ML_DSA_65",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -114,6 +116,7 @@ impl AlgorithmId {
             "ALGORITHM_ID_THRESHOLD_SCHNORR_BIP340" => Some(Self::ThresholdSchnorrBip340),
             "ALGORITHM_ID_THRESHOLD_ED25519" => Some(Self::ThresholdEd25519),
             "ALGORITHM_ID_VETKD" => Some(Self::Vetkd),
+            "ALGORITHM_ID_ML_DSA_65" => Some(Self::MlDsa65),
             _ => None,
         }
     }
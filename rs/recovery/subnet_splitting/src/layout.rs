@@ -16,6 +16,7 @@ use std::path::{Path, PathBuf};
 /// |  |-- original_source_manifest.data
 /// |  |-- nns.pem
 /// |  |-- pruned_state_tree.cbor
+/// |  |-- destination_download/  (subnet merging only)
 /// |  |-- (destination_)work_dir/
 /// |  |   |-- data/
 /// |  |   |   |-- cups/cup.types.v1.CatchUpPackage.pb
@@ -86,6 +87,12 @@ impl Layout {
             .join("cup.types.v1.CatchUpPackage.pb")
     }
 
+    /// Directory that the state of the destination subnet is downloaded into,
+    /// when merging the source subnet into it.
+    pub(crate) fn destination_download_dir(&self) -> PathBuf {
+        self.root.join("destination_download")
+    }
+
     pub(crate) fn work_dir(&self, target_subnet: TargetSubnet) -> PathBuf {
         match target_subnet {
             TargetSubnet::Source => self.source_working_dir.clone(),
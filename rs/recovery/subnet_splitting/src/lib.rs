@@ -1,3 +1,4 @@
+pub mod subnet_merging;
 pub mod subnet_splitting;
 pub mod utils;
 pub mod validation;
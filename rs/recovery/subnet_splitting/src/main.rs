@@ -2,6 +2,7 @@ use clap::Parser;
 use ic_base_types::SubnetId;
 use ic_recovery::{NeuronArgs, RecoveryArgs, cli, error::RecoveryResult, util};
 use ic_subnet_splitting::{
+    subnet_merging::{SubnetMerging, SubnetMergingArgs},
     subnet_splitting::{SubnetSplitting, SubnetSplittingArgs},
     utils::canister_id_ranges_to_strings,
     validation::validate_artifacts,
@@ -57,6 +58,48 @@ struct SplitArgs {
     subnet_splitting_args: SubnetSplittingArgs,
 }
 
+#[derive(Parser)]
+struct MergeArgs {
+    #[clap(
+        short = 'r',
+        long,
+        alias = "registry-url",
+        default_value = "https://ic0.app"
+    )]
+    /// The URL of an NNS entry point. That is, the URL of any replica on the
+    /// NNS subnet.
+    nns_url: Url,
+
+    /// replica version of ic-admin binary
+    #[clap(long)]
+    replica_version: Option<ReplicaVersion>,
+
+    /// The directory to perform the subnet merging in
+    #[clap(long)]
+    dir: PathBuf,
+
+    /// The path to a private key to be considered for admin SSH connections
+    #[clap(long)]
+    admin_key_file: Option<PathBuf>,
+
+    /// Flag to enter test mode
+    #[clap(long)]
+    test: bool,
+
+    /// Flag to make the tool non interactive. No input from the user is requested.
+    #[clap(long)]
+    pub skip_prompts: bool,
+
+    /// Flag to indicate we're running recovery directly on a node, and should use
+    /// the locally available binaries. If this option is not set, missing binaries
+    /// will be downloaded.
+    #[clap(long)]
+    pub use_local_binaries: bool,
+
+    #[clap(flatten)]
+    subnet_merging_args: SubnetMergingArgs,
+}
+
 #[derive(Parser)]
 struct ValidateArgs {
     /// Path to the State Tree signed by the NNS
@@ -87,6 +130,9 @@ enum Subcommand {
     /// Perform Subnet Splitting
     Split(SplitArgs),
 
+    /// Perform Subnet Merging
+    Merge(MergeArgs),
+
     /// Validate artifacts produced during subnet splitting
     Validate(ValidateArgs),
 }
@@ -167,6 +213,70 @@ fn do_split(args: SplitArgs, logger: Logger) -> RecoveryResult<()> {
     Ok(())
 }
 
+fn subnet_merging(
+    logger: Logger,
+    recovery_args: RecoveryArgs,
+    subnet_merging_args: SubnetMergingArgs,
+    mut neuron_args: Option<NeuronArgs>,
+) {
+    cli::print_step(&logger, "Subnet Merging");
+
+    info!(
+        logger,
+        "Merging subnet with id {} (hosting canisters within ranges {:?}) \
+        into subnet with id {}",
+        subnet_merging_args.source_subnet_id,
+        canister_id_ranges_to_strings(&subnet_merging_args.canister_id_ranges_to_move),
+        subnet_merging_args.destination_subnet_id
+    );
+    warn!(
+        logger,
+        "The source subnet remains halted after the merge. \
+        Removing it from the registry is a separate step"
+    );
+
+    if !recovery_args.skip_prompts {
+        cli::wait_for_confirmation(&logger);
+    }
+
+    if neuron_args.is_none() && !recovery_args.test_mode {
+        neuron_args = Some(cli::read_neuron_args(&logger));
+    }
+
+    let subnet_merging = SubnetMerging::new(
+        logger.clone(),
+        recovery_args.clone(),
+        neuron_args,
+        subnet_merging_args,
+    );
+
+    cli::execute_steps(&logger, recovery_args.skip_prompts, subnet_merging);
+}
+
+fn do_merge(args: MergeArgs, logger: Logger) -> RecoveryResult<()> {
+    let recovery_args = RecoveryArgs {
+        dir: args.dir,
+        nns_url: args.nns_url,
+        replica_version: args.replica_version,
+        admin_key_file: args.admin_key_file,
+        test_mode: args.test,
+        skip_prompts: args.skip_prompts,
+        use_local_binaries: args.use_local_binaries,
+    };
+
+    let subnet_merging_state =
+        cli::read_and_maybe_update_state(&logger, recovery_args, Some(args.subnet_merging_args));
+
+    subnet_merging(
+        logger,
+        subnet_merging_state.recovery_args,
+        subnet_merging_state.subcommand_args,
+        subnet_merging_state.neuron_args,
+    );
+
+    Ok(())
+}
+
 fn do_validate(args: ValidateArgs, logger: Logger) -> RecoveryResult<()> {
     validate_artifacts(
         args.state_tree_path,
@@ -185,6 +295,7 @@ fn main() -> RecoveryResult<()> {
 
     match args.subcommand {
         Subcommand::Split(split_args) => do_split(split_args, logger),
+        Subcommand::Merge(merge_args) => do_merge(merge_args, logger),
         Subcommand::Validate(validate_args) => do_validate(validate_args, logger),
     }
 }
@@ -20,7 +20,7 @@ use ic_recovery::{
 };
 use ic_registry_routing_table::CanisterIdRange;
 use ic_registry_subnet_type::SubnetType;
-use ic_state_manager::split::resolve_ranges_and_split;
+use ic_state_manager::{merge::merge, split::resolve_ranges_and_split};
 use ic_types::Height;
 use slog::{Logger, error, info};
 use url::Url;
@@ -172,6 +172,65 @@ impl Step for SplitStateStep {
     }
 }
 
+pub(crate) struct MergeStatesStep {
+    pub(crate) source_subnet_id: SubnetId,
+    pub(crate) destination_subnet_id: SubnetId,
+    pub(crate) layout: Layout,
+    pub(crate) logger: Logger,
+}
+
+impl Step for MergeStatesStep {
+    fn descr(&self) -> String {
+        format!(
+            "Merging the state of the subnet {} (at {}) into the state of the subnet {} \
+             (at {}) and removing all but the highest checkpoints.",
+            self.source_subnet_id,
+            self.layout.work_dir(TargetSubnet::Source).display(),
+            self.destination_subnet_id,
+            self.layout.work_dir(TargetSubnet::Destination).display(),
+        )
+    }
+
+    fn exec(&self) -> RecoveryResult<()> {
+        // 1. Merge the states.
+        info!(self.logger, "Merging the states");
+        merge(
+            self.layout.ic_state_dir(TargetSubnet::Destination),
+            self.layout.ic_state_dir(TargetSubnet::Source),
+            &MetricsRegistry::new(),
+            self.logger.clone().into(),
+        )
+        .map_err(RecoveryError::OutputError)?;
+
+        // 2. Compute the manifest
+        info!(self.logger, "Computing the state manifest");
+        let latest_checkpoint_dir = self
+            .layout
+            .latest_checkpoint_dir(TargetSubnet::Destination)?;
+        let manifest_path = self.layout.actual_manifest_file(self.destination_subnet_id);
+
+        StateToolHelper::compute_manifest(&latest_checkpoint_dir, &manifest_path)?;
+
+        // 3. Validate the manifest
+        info!(self.logger, "Validating the manifest");
+        StateToolHelper::verify_manifest(&manifest_path)
+            .map_err(|err| RecoveryError::validation_failed("Manifest verification failed", err))?;
+        info!(
+            self.logger,
+            "State hash after merge: {}",
+            get_state_hash(&latest_checkpoint_dir)?
+        );
+
+        // 4. Remove all the other checkpoints
+        info!(self.logger, "Removing past checkpoints");
+        Recovery::remove_all_but_highest_checkpoints(
+            &self.layout.checkpoints_dir(TargetSubnet::Destination),
+            &self.logger,
+        )
+        .map(|_| ())
+    }
+}
+
 pub(crate) struct ComputeExpectedManifestsStep {
     pub(crate) state_tool_helper: StateToolHelper,
     pub(crate) source_subnet_id: SubnetId,
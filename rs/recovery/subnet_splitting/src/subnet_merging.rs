@@ -0,0 +1,499 @@
+use crate::{
+    admin_helper::{
+        get_halt_subnet_at_cup_height_command, get_propose_to_complete_canister_migration_command,
+        get_propose_to_prepare_canister_migration_command,
+        get_propose_to_reroute_canister_ranges_command,
+    },
+    layout::Layout,
+    steps::{MergeStatesStep, ReadRegistryStep, WaitForCUPStep},
+    subnet_splitting::SubnetSplitting,
+    target_subnet::TargetSubnet,
+    utils::get_state_hash,
+};
+
+use clap::Parser;
+use ic_base_types::SubnetId;
+use ic_recovery::{
+    IC_CONSENSUS_POOL_PATH, IC_REGISTRY_LOCAL_STORE, NeuronArgs, Recovery, RecoveryArgs,
+    cli::{consent_given, read_optional},
+    error::{RecoveryError, RecoveryResult},
+    recovery_iterator::RecoveryIterator,
+    recovery_state::{HasRecoveryState, RecoveryState},
+    registry_helper::RegistryPollingStrategy,
+    steps::{AdminStep, DownloadIcStateStep, Step, UploadAndRestartStep},
+    util::{DataLocation, SshUser},
+};
+use ic_registry_routing_table::{CanisterIdRange, RoutingTable};
+use serde::{Deserialize, Serialize};
+use slog::Logger;
+use strum::{EnumMessage, IntoEnumIterator};
+use strum_macros::{EnumIter, EnumString};
+
+use std::{collections::HashMap, iter::Peekable, net::IpAddr, path::PathBuf};
+
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Debug,
+    Deserialize,
+    EnumIter,
+    EnumMessage,
+    EnumString,
+    Serialize,
+    clap::ValueEnum,
+)]
+pub enum StepType {
+    PrepareCanisterMigration,
+    CheckRegistryForCanisterMigrationsEntry,
+    HaltSourceSubnetAtCupHeight,
+    HaltDestinationSubnetAtCupHeight,
+    RerouteCanisterRanges,
+    CheckRegistryForRoutingTableEntry,
+    DownloadStateFromSourceSubnet,
+    DownloadStateFromDestinationSubnet,
+    MergeStates,
+    ProposeCupForDestinationSubnet,
+    UploadStateToDestinationSubnet,
+    WaitForCUPOnDestinationSubnet,
+    UnhaltDestinationSubnet,
+    CompleteCanisterMigration,
+    CheckRegistryForCanisterMigrationsEntryAgain,
+    Cleanup,
+}
+
+#[derive(Clone, PartialEq, Debug, Deserialize, Parser, Serialize)]
+#[clap(version = "1.0")]
+pub struct SubnetMergingArgs {
+    /// Id of the subnet whose state will be merged into the destination subnet.
+    /// The subnet remains halted afterwards.
+    #[clap(long, value_parser=ic_recovery::util::subnet_id_from_str)]
+    pub source_subnet_id: SubnetId,
+
+    /// Id of the subnet that the source subnet will be merged into.
+    #[clap(long, value_parser=ic_recovery::util::subnet_id_from_str)]
+    pub destination_subnet_id: SubnetId,
+
+    /// Public ssh key to be deployed to both subnets for read only access.
+    #[clap(long)]
+    pub readonly_pub_key: Option<String>,
+
+    /// The path to a file containing the private key associated with `readonly_pub_key`.
+    #[clap(long)]
+    pub readonly_key_file: Option<PathBuf>,
+
+    /// If the downloaded states should be backed up locally.
+    #[clap(long)]
+    pub keep_downloaded_state: Option<bool>,
+
+    /// IP address of the node from the source subnet to download the state from.
+    #[clap(long)]
+    pub download_node_source: Option<IpAddr>,
+
+    /// IP address of the node from the destination subnet to download the state from.
+    #[clap(long)]
+    pub download_node_destination: Option<IpAddr>,
+
+    /// IP address of the node to upload the merged subnet state to.
+    #[clap(long)]
+    pub upload_node_destination: Option<IpAddr>,
+
+    /// If present the tool will start execution for the provided step, skipping the initial ones.
+    #[clap(long = "resume")]
+    #[clap(value_enum)]
+    pub next_step: Option<StepType>,
+
+    /// All canister ID ranges hosted by the source subnet.
+    #[clap(long, num_args(1..), required = true)]
+    pub canister_id_ranges_to_move: Vec<CanisterIdRange>,
+}
+
+pub struct SubnetMerging {
+    step_iterator: Peekable<StepTypeIter>,
+    params: SubnetMergingArgs,
+    recovery_args: RecoveryArgs,
+    neuron_args: Option<NeuronArgs>,
+    recovery: Recovery,
+    layout: Layout,
+    logger: Logger,
+}
+
+impl SubnetMerging {
+    pub fn new(
+        logger: Logger,
+        recovery_args: RecoveryArgs,
+        neuron_args: Option<NeuronArgs>,
+        subnet_merging_args: SubnetMergingArgs,
+    ) -> Self {
+        let recovery = Recovery::new(
+            logger.clone(),
+            recovery_args.clone(),
+            neuron_args.clone(),
+            recovery_args.nns_url.clone(),
+            RegistryPollingStrategy::WithEveryRead,
+        )
+        .expect("Failed to initialize recovery");
+
+        Self::check_subnets_preconditions(
+            &recovery,
+            subnet_merging_args.source_subnet_id,
+            subnet_merging_args.destination_subnet_id,
+        )
+        .expect("Subnets should satisfy all the preconditions");
+
+        Self {
+            step_iterator: StepType::iter().peekable(),
+            params: subnet_merging_args,
+            recovery_args,
+            neuron_args,
+            layout: Layout::new(&recovery),
+            recovery,
+            logger,
+        }
+    }
+
+    /// Checks whether the subnets satisfy the following preconditions:
+    ///
+    /// 1) Both are `Application` subnets
+    /// 2) Neither is a Chain key subnet
+    /// 3) They are of the same type
+    fn check_subnets_preconditions(
+        recovery: &Recovery,
+        source_subnet_id: SubnetId,
+        destination_subnet_id: SubnetId,
+    ) -> RecoveryResult<()> {
+        let source_subnet_record = SubnetSplitting::get_and_pre_validate_subnet_record(
+            recovery,
+            source_subnet_id,
+            /*other_subnet_record=*/ None,
+            /*check_whether_halted=*/ false,
+            /*check_height=*/ false,
+        )?;
+        let destination_subnet_record = SubnetSplitting::get_and_pre_validate_subnet_record(
+            recovery,
+            destination_subnet_id,
+            /*other_subnet_record=*/ None,
+            /*check_whether_halted=*/ false,
+            /*check_height=*/ false,
+        )?;
+
+        if source_subnet_record.subnet_type() != destination_subnet_record.subnet_type() {
+            return Err(RecoveryError::ValidationFailed(format!(
+                "Both subnets should have the same subnet type. \
+                 Source subnet type = {:?}, destination subnet type = {:?}",
+                source_subnet_record.subnet_type(),
+                destination_subnet_record.subnet_type(),
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn download_state_step(&self, target_subnet: TargetSubnet) -> RecoveryResult<Box<dyn Step>> {
+        let download_node = match target_subnet {
+            TargetSubnet::Source => self.params.download_node_source,
+            TargetSubnet::Destination => self.params.download_node_destination,
+        };
+        let Some(node_ip) = download_node else {
+            return Err(RecoveryError::StepSkipped);
+        };
+
+        let (ssh_user, key_file) = if self.params.readonly_pub_key.is_some() {
+            (SshUser::Readonly, self.params.readonly_key_file.clone())
+        } else {
+            (SshUser::Admin, self.recovery.admin_key_file.clone())
+        };
+        let keep_downloaded_state = self.params.keep_downloaded_state == Some(true);
+        let additional_excludes = vec![
+            "orchestrator",
+            IC_CONSENSUS_POOL_PATH,
+            IC_REGISTRY_LOCAL_STORE,
+        ];
+
+        Ok(match target_subnet {
+            TargetSubnet::Source => self
+                .recovery
+                .get_download_state_step(
+                    node_ip,
+                    ssh_user,
+                    key_file,
+                    keep_downloaded_state,
+                    additional_excludes,
+                )
+                .into(),
+            TargetSubnet::Destination => DownloadIcStateStep {
+                logger: self.recovery.logger.clone(),
+                ssh_user,
+                node_ip,
+                target: self.layout.destination_download_dir().display().to_string(),
+                working_dir: self
+                    .layout
+                    .work_dir(TargetSubnet::Destination)
+                    .display()
+                    .to_string(),
+                keep_downloaded_state,
+                require_confirmation: !self.recovery_args.test_mode,
+                key_file,
+                additional_excludes: additional_excludes
+                    .iter()
+                    .map(std::string::ToString::to_string)
+                    .collect(),
+            }
+            .into(),
+        })
+    }
+
+    fn halt_at_cup_height(&self, subnet_id: SubnetId) -> AdminStep {
+        AdminStep {
+            logger: self.recovery.logger.clone(),
+            ic_admin_cmd: get_halt_subnet_at_cup_height_command(
+                &self.recovery.admin_helper,
+                subnet_id,
+                &self.params.readonly_pub_key,
+            ),
+        }
+    }
+
+    fn propose_cup(&self) -> RecoveryResult<impl Step + use<>> {
+        let checkpoints_dir = self.layout.checkpoints_dir(TargetSubnet::Destination);
+
+        let (max_name, max_height) =
+            Recovery::get_latest_checkpoint_name_and_height(&checkpoints_dir)?;
+
+        let max_checkpoint_dir = checkpoints_dir.join(max_name);
+        let state_hash = get_state_hash(max_checkpoint_dir)?;
+
+        self.recovery.update_recovery_cup(
+            self.params.destination_subnet_id,
+            Recovery::get_recovery_height(max_height),
+            state_hash,
+            /*replacement_nodes=*/ &[],
+            /*registry_params=*/ None,
+            /*chain_key_subnet_id=*/ None,
+        )
+    }
+}
+
+impl RecoveryIterator<StepType, StepTypeIter> for SubnetMerging {
+    fn get_step_iterator(&mut self) -> &mut Peekable<StepTypeIter> {
+        &mut self.step_iterator
+    }
+
+    fn store_next_step(&mut self, step_type: Option<StepType>) {
+        self.params.next_step = step_type;
+    }
+
+    fn get_logger(&self) -> &Logger {
+        &self.logger
+    }
+
+    fn interactive(&self) -> bool {
+        !self.recovery_args.skip_prompts
+    }
+
+    fn read_step_params(&mut self, step_type: StepType) {
+        match step_type {
+            StepType::HaltSourceSubnetAtCupHeight => {
+                if self.params.readonly_pub_key.is_none() {
+                    self.params.readonly_pub_key = read_optional(
+                        &self.logger,
+                        "Enter public key to add readonly SSH access to both subnets. Ensure the right format.\n\
+                        Format:   ssh-ed25519 <pubkey> <identity>\n\
+                        Example:  ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIPwS/0S6xH0g/xLDV0Tz7VeMZE9AKPeSbLmCsq9bY3F1 foo@dfinity.org\n\
+                        Enter your key: ",
+                    )
+                }
+            }
+
+            StepType::DownloadStateFromSourceSubnet => {
+                if self.params.download_node_source.is_none() {
+                    self.params.download_node_source =
+                        read_optional(&self.logger, "Enter download IP on the Source Subnet:");
+                }
+
+                self.params.keep_downloaded_state = Some(consent_given(
+                    &self.logger,
+                    "Preserve original downloaded states locally?",
+                ));
+            }
+
+            StepType::DownloadStateFromDestinationSubnet => {
+                if self.params.download_node_destination.is_none() {
+                    self.params.download_node_destination =
+                        read_optional(&self.logger, "Enter download IP on the Destination Subnet:");
+                }
+            }
+
+            StepType::UploadStateToDestinationSubnet => {
+                if self.params.upload_node_destination.is_none() {
+                    self.params.upload_node_destination = read_optional(
+                        &self.logger,
+                        "Enter IP of node in the Destination Subnet with admin access: ",
+                    );
+                }
+            }
+
+            _ => (),
+        }
+    }
+
+    fn get_step_impl(&self, step_type: StepType) -> RecoveryResult<Box<dyn Step>> {
+        let step: Box<dyn Step> = match step_type {
+            StepType::PrepareCanisterMigration => AdminStep {
+                logger: self.recovery.logger.clone(),
+                ic_admin_cmd: get_propose_to_prepare_canister_migration_command(
+                    &self.recovery.admin_helper,
+                    &self.params.canister_id_ranges_to_move,
+                    self.params.source_subnet_id,
+                    self.params.destination_subnet_id,
+                ),
+            }
+            .into(),
+
+            StepType::CheckRegistryForCanisterMigrationsEntry
+            | StepType::CheckRegistryForCanisterMigrationsEntryAgain => {
+                let registry_helper = self.recovery.registry_helper.clone();
+
+                ReadRegistryStep {
+                    logger: self.recovery.logger.clone(),
+                    label: "Canister Migrations".to_string(),
+                    querier: move || registry_helper.get_canister_migrations(),
+                    interactive: !self.recovery_args.skip_prompts,
+                }
+                .into()
+            }
+
+            StepType::HaltSourceSubnetAtCupHeight => {
+                self.halt_at_cup_height(self.params.source_subnet_id).into()
+            }
+            StepType::HaltDestinationSubnetAtCupHeight => self
+                .halt_at_cup_height(self.params.destination_subnet_id)
+                .into(),
+
+            StepType::RerouteCanisterRanges => AdminStep {
+                logger: self.recovery.logger.clone(),
+                ic_admin_cmd: get_propose_to_reroute_canister_ranges_command(
+                    &self.recovery.admin_helper,
+                    &self.params.canister_id_ranges_to_move,
+                    self.params.source_subnet_id,
+                    self.params.destination_subnet_id,
+                ),
+            }
+            .into(),
+
+            StepType::CheckRegistryForRoutingTableEntry => {
+                let registry_helper = self.recovery.registry_helper.clone();
+                let source_subnet = self.params.source_subnet_id;
+                let destination_subnet = self.params.destination_subnet_id;
+
+                let get_ranges = move |routing_table: RoutingTable| {
+                    HashMap::from([
+                        (source_subnet, routing_table.ranges(source_subnet)),
+                        (destination_subnet, routing_table.ranges(destination_subnet)),
+                    ])
+                };
+
+                ReadRegistryStep {
+                    logger: self.recovery.logger.clone(),
+                    label: "Routing Table".to_string(),
+                    querier: move || {
+                        registry_helper.get_routing_table().map(
+                            |(registry_version, routing_table)| {
+                                (registry_version, routing_table.map(get_ranges))
+                            },
+                        )
+                    },
+                    interactive: !self.recovery_args.skip_prompts,
+                }
+                .into()
+            }
+
+            StepType::DownloadStateFromSourceSubnet => {
+                self.download_state_step(TargetSubnet::Source)?
+            }
+            StepType::DownloadStateFromDestinationSubnet => {
+                self.download_state_step(TargetSubnet::Destination)?
+            }
+
+            StepType::MergeStates => MergeStatesStep {
+                source_subnet_id: self.params.source_subnet_id,
+                destination_subnet_id: self.params.destination_subnet_id,
+                layout: self.layout.clone(),
+                logger: self.recovery.logger.clone(),
+            }
+            .into(),
+
+            StepType::ProposeCupForDestinationSubnet => self.propose_cup()?.into(),
+            StepType::UploadStateToDestinationSubnet => match self.params.upload_node_destination {
+                Some(node_ip) => UploadAndRestartStep {
+                    logger: self.recovery.logger.clone(),
+                    upload_method: DataLocation::Remote(node_ip),
+                    work_dir: self.layout.work_dir(TargetSubnet::Destination),
+                    data_src: self.layout.ic_state_dir(TargetSubnet::Destination),
+                    require_confirmation: !self.recovery_args.skip_prompts,
+                    key_file: self.recovery.admin_key_file.clone(),
+                    check_ic_replay_height: false,
+                }
+                .into(),
+                None => return Err(RecoveryError::StepSkipped),
+            },
+            StepType::WaitForCUPOnDestinationSubnet => match self.params.upload_node_destination {
+                Some(node_ip) => WaitForCUPStep {
+                    logger: self.recovery.logger.clone(),
+                    layout: self.layout.clone(),
+                    node_ip,
+                    target_subnet: TargetSubnet::Destination,
+                }
+                .into(),
+                None => return Err(RecoveryError::StepSkipped),
+            },
+            StepType::UnhaltDestinationSubnet => self
+                .recovery
+                .halt_subnet(
+                    self.params.destination_subnet_id,
+                    /*is_halted=*/ false,
+                    /*keys=*/ &[],
+                )
+                .into(),
+
+            StepType::CompleteCanisterMigration => AdminStep {
+                logger: self.recovery.logger.clone(),
+                ic_admin_cmd: get_propose_to_complete_canister_migration_command(
+                    &self.recovery.admin_helper,
+                    &self.params.canister_id_ranges_to_move,
+                    self.params.source_subnet_id,
+                    self.params.destination_subnet_id,
+                ),
+            }
+            .into(),
+
+            StepType::Cleanup => self.recovery.get_cleanup_step().into(),
+        };
+
+        Ok(step)
+    }
+}
+
+impl Iterator for SubnetMerging {
+    type Item = (StepType, Box<dyn Step>);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_step()
+    }
+}
+
+impl HasRecoveryState for SubnetMerging {
+    type StepType = StepType;
+    type SubcommandArgsType = SubnetMergingArgs;
+
+    fn get_next_step(&self) -> Option<Self::StepType> {
+        self.params.next_step
+    }
+
+    fn get_state(&self) -> RecoveryResult<RecoveryState<Self::SubcommandArgsType>> {
+        Ok(RecoveryState {
+            recovery_args: self.recovery_args.clone(),
+            neuron_args: self.neuron_args.clone(),
+            subcommand_args: self.params.clone(),
+        })
+    }
+}
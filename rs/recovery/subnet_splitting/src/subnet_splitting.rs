@@ -220,7 +220,7 @@ impl SubnetSplitting {
         Ok(subnet_type)
     }
 
-    fn get_and_pre_validate_subnet_record(
+    pub(crate) fn get_and_pre_validate_subnet_record(
         recovery: &Recovery,
         subnet_id: SubnetId,
         other_subnet_record: Option<SubnetRecord>,
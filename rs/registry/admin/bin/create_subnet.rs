@@ -1,6 +1,7 @@
 use crate::ProposalTitle;
 use crate::helpers::{
-    get_proposer_and_sender, parse_proposal_url, shortened_pids_string, summary_from_string_or_file,
+    adaptive_block_time_config, get_proposer_and_sender, parse_proposal_url, shortened_pids_string,
+    summary_from_string_or_file,
 };
 use crate::types::{ProposalMetadata, ProposalPayload};
 use async_trait::async_trait;
@@ -58,6 +59,17 @@ pub(crate) struct ProposeToCreateSubnetCmd {
     /// propagation.
     pub initial_notary_delay_millis: Option<u64>,
 
+    /// Minimum notary delay (in milliseconds) under adaptive block time, used
+    /// while the subnet is under load. Enables adaptive block time; must be
+    /// given together with `--adaptive-max-notary-delay-millis`.
+    #[clap(long, requires = "adaptive_max_notary_delay_millis")]
+    pub adaptive_min_notary_delay_millis: Option<u64>,
+
+    /// Maximum notary delay (in milliseconds) under adaptive block time, that
+    /// an idle subnet backs off to.
+    #[clap(long, requires = "adaptive_min_notary_delay_millis")]
+    pub adaptive_max_notary_delay_millis: Option<u64>,
+
     #[clap(long)]
     /// ID of the Replica version to run.
     pub replica_version_id: Option<ReplicaVersion>,
@@ -297,6 +309,10 @@ impl ProposeToCreateSubnetCmd {
             canister_cycles_cost_schedule: Some(
                 do_create_subnet::CanisterCyclesCostSchedule::Normal,
             ),
+            adaptive_block_time_config: adaptive_block_time_config(
+                self.adaptive_min_notary_delay_millis,
+                self.adaptive_max_notary_delay_millis,
+            ),
 
             // Deprecated fields.
             ingress_bytes_per_block_soft_cap: Default::default(),
@@ -362,6 +378,8 @@ mod tests {
             max_block_payload_size: None,
             unit_delay_millis: None,
             initial_notary_delay_millis: None,
+            adaptive_min_notary_delay_millis: None,
+            adaptive_max_notary_delay_millis: None,
             replica_version_id: None,
             dkg_interval_length: None,
             dkg_dealings_per_block: None,
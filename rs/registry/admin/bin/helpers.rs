@@ -7,7 +7,10 @@ use ic_nervous_system_common_test_keys::{TEST_NEURON_1_ID, TEST_NEURON_1_OWNER_K
 use ic_nns_common::types::NeuronId;
 use ic_protobuf::registry::{
     node::v1::NodeRecord as NodeRecordPb,
-    subnet::v1::{SubnetListRecord as SubnetListRecordPb, SubnetRecord as SubnetRecordPb},
+    subnet::v1::{
+        AdaptiveBlockTimeConfig as AdaptiveBlockTimeConfigPb,
+        SubnetListRecord as SubnetListRecordPb, SubnetRecord as SubnetRecordPb,
+    },
 };
 use ic_registry_keys::{make_node_record_key, make_subnet_list_record_key, make_subnet_record_key};
 use ic_registry_nns_data_provider::registry::RegistryCanister;
@@ -166,6 +169,28 @@ pub(crate) fn extract_subnet_ids(subnet_list_record: &SubnetListRecordPb) -> Vec
         .collect()
 }
 
+/// Returns the adaptive block time config given by the minimum and maximum
+/// notary delay arguments; or `None` if neither is set. Clap ensures that
+/// they are only ever given together.
+pub(crate) fn adaptive_block_time_config(
+    min_notary_delay_millis: Option<u64>,
+    max_notary_delay_millis: Option<u64>,
+) -> Option<AdaptiveBlockTimeConfigPb> {
+    match (min_notary_delay_millis, max_notary_delay_millis) {
+        (Some(min_notary_delay_millis), Some(max_notary_delay_millis)) => {
+            Some(AdaptiveBlockTimeConfigPb {
+                min_notary_delay_millis,
+                max_notary_delay_millis,
+            })
+        }
+        (None, None) => None,
+        _ => panic!(
+            "--adaptive-min-notary-delay-millis and --adaptive-max-notary-delay-millis must be \
+             specified together."
+        ),
+    }
+}
+
 /// Shortens the provided `PrincipalId`s to make them easier to display.
 pub(crate) fn shortened_pids_string(pids: &[PrincipalId]) -> String {
     let mut pids_string = "[".to_string();
@@ -1,6 +1,6 @@
 use crate::helpers::{
-    get_proposer_and_sender, get_subnet_record, parse_proposal_url, shortened_subnet_string,
-    summary_from_string_or_file,
+    adaptive_block_time_config, get_proposer_and_sender, get_subnet_record, parse_proposal_url,
+    shortened_subnet_string, summary_from_string_or_file,
 };
 use crate::types::{ProposalMetadata, ProposalPayload, SubnetRecord};
 use crate::{ProposalTitle, SubnetDescriptor};
@@ -56,6 +56,17 @@ pub(crate) struct ProposeToUpdateSubnetCmd {
     /// of this field.
     pub initial_notary_delay_millis: Option<u64>,
 
+    /// Minimum notary delay (in milliseconds) under adaptive block time, used
+    /// while the subnet is under load. Enables adaptive block time; must be
+    /// given together with `--adaptive-max-notary-delay-millis`.
+    #[clap(long, requires = "adaptive_max_notary_delay_millis")]
+    pub adaptive_min_notary_delay_millis: Option<u64>,
+
+    /// Maximum notary delay (in milliseconds) under adaptive block time, that
+    /// an idle subnet backs off to.
+    #[clap(long, requires = "adaptive_min_notary_delay_millis")]
+    pub adaptive_max_notary_delay_millis: Option<u64>,
+
     #[clap(long)]
     /// If set, the created proposal will contain a desired override of that
     /// field to the value set. See `ProposeToCreateSubnetCmd` for the semantic
@@ -333,6 +344,10 @@ impl ProposeToUpdateSubnetCmd {
             ssh_readonly_access: self.ssh_readonly_access.clone(),
             ssh_backup_access: self.ssh_backup_access.clone(),
             max_number_of_canisters: self.max_number_of_canisters,
+            adaptive_block_time_config: adaptive_block_time_config(
+                self.adaptive_min_notary_delay_millis,
+                self.adaptive_max_notary_delay_millis,
+            ),
 
             chain_key_config,
             chain_key_signing_enable,
@@ -399,6 +414,7 @@ mod tests {
             max_number_of_canisters: None,
             ssh_readonly_access: None,
             ssh_backup_access: None,
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -421,6 +437,8 @@ mod tests {
             max_block_payload_size: None,
             unit_delay_millis: None,
             initial_notary_delay_millis: None,
+            adaptive_min_notary_delay_millis: None,
+            adaptive_max_notary_delay_millis: None,
             dkg_interval_length: None,
             dkg_dealings_per_block: None,
             start_as_nns: None,
@@ -5,7 +5,7 @@ use dfn_core::{
     over, over_async, stable,
 };
 use ic_base_types::{NodeId, PrincipalId};
-use ic_certified_map::{AsHashTree, HashTree};
+use ic_certified_map::HashTree;
 use ic_nervous_system_string::clamp_debug_len;
 use ic_nns_constants::{GOVERNANCE_CANISTER_ID, MIGRATION_CANISTER_ID, ROOT_CANISTER_ID};
 use ic_protobuf::registry::{
@@ -24,15 +24,15 @@ use ic_registry_transport::{
         CertifiedResponse, HighCapacityRegistryGetChangesSinceResponse,
         HighCapacityRegistryGetValueResponse, HighCapacityRegistryValue,
         RegistryAtomicMutateResponse, RegistryError, RegistryGetChangesSinceRequest,
-        RegistryGetLatestVersionResponse, high_capacity_registry_get_value_response,
-        registry_error::Code,
+        RegistryGetKeyHistoryRequest, RegistryGetLatestVersionResponse,
+        high_capacity_registry_get_value_response, registry_error::Code,
     },
     serialize_atomic_mutate_response, serialize_get_changes_since_response,
     serialize_get_value_response,
 };
 use prost::Message;
 use registry_canister::{
-    certification::{current_version_tree, hash_tree_to_proto},
+    certification::{certified_tree, hash_tree_to_proto, key_history_tree},
     common::LOG_PREFIX,
     init::{RegistryCanisterInitPayload, RegistryCanisterInitPayloadWithOptionalFlags},
     mutations::{
@@ -292,7 +292,6 @@ fn get_certified_changes_since() {
     over(
         protobuf,
         |req: RegistryGetChangesSinceRequest| -> CertifiedResponse {
-            use ic_certified_map::{fork, labeled, labeled_hash};
             let latest_version = registry().latest_version();
             let from_version = EncodedVersion::from(req.version.saturating_add(1));
 
@@ -305,13 +304,10 @@ fn get_certified_changes_since() {
                 .changelog()
                 .value_range(from_version.as_ref(), to_version.as_ref());
 
-            let hash_tree = fork(
-                current_version_tree(latest_version),
-                if req.version < latest_version {
-                    labeled(b"delta", delta_tree)
-                } else {
-                    HashTree::Pruned(labeled_hash(b"delta", &registry().changelog().root_hash()))
-                },
+            let hash_tree = certified_tree(
+                registry(),
+                (req.version < latest_version).then_some(delta_tree),
+                None,
             );
 
             certified_response(hash_tree)
@@ -319,6 +315,52 @@ fn get_certified_changes_since() {
     )
 }
 
+#[unsafe(export_name = "canister_query get_certified_key_history")]
+fn get_certified_key_history() {
+    over(
+        protobuf,
+        |req: RegistryGetKeyHistoryRequest| -> CertifiedResponse {
+            let registry = registry();
+            let latest_version = registry.latest_version();
+            let to_version = if req.to_version == 0 {
+                latest_version
+            } else {
+                req.to_version.min(latest_version)
+            };
+
+            if req.from_version > to_version {
+                return certified_response(certified_tree(registry, None, None));
+            }
+
+            let mut versions = registry.get_key_history_versions(
+                &req.key,
+                req.key_is_prefix,
+                req.from_version,
+                to_version,
+            );
+            let max_versions = registry
+                .count_fitting_versions(&versions, MAX_REGISTRY_DELTAS_SIZE)
+                .min(MAX_VERSIONS_PER_QUERY);
+            // If not all versions fit, the certified index is revealed up to
+            // and including the first omitted version, which tells the client
+            // where to continue.
+            let index_to_version = match versions.get(max_versions) {
+                Some(next_version) => *next_version,
+                None => to_version,
+            };
+            versions.truncate(max_versions);
+
+            certified_response(key_history_tree(
+                registry,
+                &req.key,
+                req.key_is_prefix,
+                &versions,
+                req.from_version..=index_to_version,
+            ))
+        },
+    )
+}
+
 #[unsafe(export_name = "canister_query get_value")]
 fn get_value() {
     let response_pb = match deserialize_get_value_request(arg_data()) {
@@ -399,13 +441,7 @@ fn get_latest_version() {
 #[unsafe(export_name = "canister_query get_certified_latest_version")]
 fn get_certified_latest_version() {
     over(protobuf, |_: Vec<u8>| -> CertifiedResponse {
-        use ic_certified_map::{fork, labeled_hash};
-        let latest_version = registry().latest_version();
-        let hash_tree = fork(
-            current_version_tree(latest_version),
-            HashTree::Pruned(labeled_hash(b"delta", &registry().changelog().root_hash())),
-        );
-        certified_response(hash_tree)
+        certified_response(certified_tree(registry(), None, None))
     });
 }
 
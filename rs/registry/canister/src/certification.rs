@@ -8,23 +8,33 @@
 //! |
 //! +-- current_version -- [ LEB128-encoded VERSION ]
 //! |
-//! `-- delta --+-- [ big-endian encoded 1u64    ] -- [ serialized protobuf ]
-//!             |
-//!             …
-//!             |
-//!             `-- [ big-endian encoded VERSION ] -- [ serialized protobuf ]
+//! +-- delta --+-- [ big-endian encoded 1u64    ] -- [ serialized protobuf ]
+//! |           |
+//! |           …
+//! |           |
+//! |           `-- [ big-endian encoded VERSION ] -- [ serialized protobuf ]
+//! |
+//! `-- key_history --+-- [ KEY ] --+-- [ big-endian encoded version ] -- [ empty ]
+//!                   |             |
+//!                   …             …
 //! ```
 //!
-//! where lebels under "delta" form contiguous range [1,VERSION].
+//! where lebels under "delta" form contiguous range [1,VERSION], and the labels
+//! under "key_history/KEY" are the versions at which KEY was mutated.
+//!
+//! Responses to `get_certified_key_history` reveal only the deltas at the
+//! versions that touch the requested key(s); all other deltas are pruned. The
+//! revealed part of "key_history" proves that no such version is missing.
 
 #[cfg(target_arch = "wasm32")]
 use dfn_core::api::set_certified_data;
 #[cfg(all(not(target_arch = "wasm32"), test))]
 use ic_cdk::println;
-use ic_certified_map::{HashTree, labeled};
+use ic_certified_map::{AsHashTree, HashTree, fork, labeled, labeled_hash};
 use ic_protobuf::messaging::xnet::v1 as pb;
+use std::ops::RangeInclusive;
 
-use crate::registry::{Registry, Version};
+use crate::registry::{EncodedVersion, Registry, Version};
 
 /// The maximum amount of bytes a 64-bit number can occupy when encoded in
 /// LEB128.
@@ -39,6 +49,110 @@ pub fn current_version_tree(v: Version) -> HashTree<'static> {
     )
 }
 
+/// Builds the hash tree of a certified response, where the "delta" and
+/// "key_history" subtrees are replaced by the given witnesses, or pruned if
+/// `None`.
+pub fn certified_tree<'a>(
+    registry: &'a Registry,
+    delta_tree: Option<HashTree<'a>>,
+    key_history_tree: Option<HashTree<'a>>,
+) -> HashTree<'a> {
+    fork(
+        current_version_tree(registry.latest_version()),
+        fork(
+            match delta_tree {
+                Some(delta_tree) => labeled(b"delta", delta_tree),
+                None => HashTree::Pruned(labeled_hash(b"delta", &registry.changelog().root_hash())),
+            },
+            match key_history_tree {
+                Some(key_history_tree) => labeled(b"key_history", key_history_tree),
+                None => HashTree::Pruned(labeled_hash(
+                    b"key_history",
+                    &registry.key_history().root_hash(),
+                )),
+            },
+        ),
+    )
+}
+
+/// Builds the hash tree for a response to `get_certified_key_history`.
+///
+/// The deltas at `versions` are revealed, all other deltas are pruned. The
+/// key history index reveals all the versions within `index_range` at which
+/// `key` (or, if `key_is_prefix`, any key starting with `key`) was mutated,
+/// proving that the response does not omit any of them.
+pub fn key_history_tree<'a>(
+    registry: &'a Registry,
+    key: &[u8],
+    key_is_prefix: bool,
+    versions: &[Version],
+    index_range: RangeInclusive<Version>,
+) -> HashTree<'a> {
+    let changelog = registry.changelog();
+    let delta_tree = versions
+        .iter()
+        .map(|version| changelog.witness(EncodedVersion::from(*version).as_ref()))
+        .reduce(merge_witnesses);
+
+    let key_history = registry.key_history();
+    let first = EncodedVersion::from(*index_range.start());
+    let last = EncodedVersion::from(*index_range.end());
+    let versions_witness = |key: &[u8]| {
+        key_history.nested_witness(key, |versions| {
+            versions.value_range(first.as_ref(), last.as_ref())
+        })
+    };
+    let key_history_tree = if key_is_prefix {
+        registry
+            .store
+            .range(key.to_vec()..)
+            .map(|(k, _)| k)
+            .take_while(|k| k.starts_with(key))
+            .map(|k| versions_witness(k.as_slice()))
+            .fold(key_history.keys_with_prefix(key), merge_witnesses)
+    } else {
+        versions_witness(key)
+    };
+
+    certified_tree(registry, delta_tree, Some(key_history_tree))
+}
+
+/// Merges two witnesses of the same tree into a single witness revealing
+/// everything that either of them reveals.
+///
+/// Wherever one witness prunes a subtree that the other one reveals, the
+/// revealed subtree is kept. Since both witnesses stem from the same tree,
+/// their structure agrees everywhere else.
+fn merge_witnesses<'a>(a: HashTree<'a>, b: HashTree<'a>) -> HashTree<'a> {
+    use HashTree::*;
+
+    match (a, b) {
+        (Pruned(_), b) => b,
+        (a, Pruned(_)) => a,
+        (Fork(a), Fork(b)) => {
+            let (a_left, a_right) = *a;
+            let (b_left, b_right) = *b;
+            fork(
+                merge_witnesses(a_left, b_left),
+                merge_witnesses(a_right, b_right),
+            )
+        }
+        (Labeled(label, a), Labeled(other_label, b)) => {
+            assert_eq!(
+                label, other_label,
+                "witnesses of the same tree must have the same labels"
+            );
+            labeled(label, merge_witnesses(*a, *b))
+        }
+        (a @ (Empty | Leaf(_)), _) => a,
+        (a, b) => panic!(
+            "witnesses of the same tree must have the same structure, got {:?} and {:?}",
+            a.reconstruct(),
+            b.reconstruct()
+        ),
+    }
+}
+
 /// Encodes a hash tree into the protobuf representation expected by
 /// the registry client.
 pub fn hash_tree_to_proto(tree: HashTree<'_>) -> pb::MixedHashTree {
@@ -67,12 +181,7 @@ pub fn hash_tree_to_proto(tree: HashTree<'_>) -> pb::MixedHashTree {
 #[cfg(target_arch = "wasm32")]
 /// Updates the certified data for the canister from the current registry state
 pub fn recertify_registry(registry: &Registry) {
-    use ic_certified_map::{AsHashTree, fork_hash, labeled_hash};
-
-    let root_hash = fork_hash(
-        &current_version_tree(registry.latest_version()).reconstruct(),
-        &labeled_hash(b"delta", &registry.changelog().root_hash()),
-    );
+    let root_hash = certified_tree(registry, None, None).reconstruct();
 
     // For benchmarks, we still want the above to execute, except that we cannot actually set the
     // certified data as the benchmarks are executed within a query call.
@@ -90,3 +199,176 @@ pub fn recertify_registry(_: &Registry) {
 pub fn recertify_registry(_: &Registry) {
     println!("recertify_registry called in test context");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ic_registry_transport::upsert;
+    use std::collections::BTreeMap;
+
+    fn registry_with_versions(n: u8) -> Registry {
+        let mut registry = Registry::new();
+        for i in 0..n {
+            registry.apply_mutations_for_test(vec![upsert([i], [i])]);
+        }
+        registry
+    }
+
+    /// Key "node_1" is mutated at odd versions, "node_2" at multiples of 3,
+    /// and "subnet" at all other versions up to 20.
+    fn registry_with_key_history() -> Registry {
+        let mut registry = Registry::new();
+        for version in 1..=20_u64 {
+            let value = version.to_be_bytes();
+            let mut mutations = vec![];
+            if version % 2 == 1 {
+                mutations.push(upsert(b"node_1", value));
+            }
+            if version % 3 == 0 {
+                mutations.push(upsert(b"node_2", value));
+            }
+            if mutations.is_empty() {
+                mutations.push(upsert(b"subnet", value));
+            }
+            registry.apply_mutations_for_test(mutations);
+        }
+        registry
+    }
+
+    fn labeled_children<'t, 'a>(tree: &'t HashTree<'a>) -> Vec<(&'a [u8], &'t HashTree<'a>)> {
+        fn collect<'t, 'a>(
+            tree: &'t HashTree<'a>,
+            children: &mut Vec<(&'a [u8], &'t HashTree<'a>)>,
+        ) {
+            match tree {
+                HashTree::Fork(lr) => {
+                    collect(&lr.0, children);
+                    collect(&lr.1, children);
+                }
+                HashTree::Labeled(label, subtree) => children.push((*label, &**subtree)),
+                _ => {}
+            }
+        }
+        let mut children = vec![];
+        collect(tree, &mut children);
+        children
+    }
+
+    /// Returns, for every key whose history is revealed, the versions revealed
+    /// in the key history index.
+    fn revealed_index(tree: &HashTree<'_>) -> BTreeMap<Vec<u8>, Vec<Version>> {
+        let Some((_, index)) = labeled_children(tree)
+            .into_iter()
+            .find(|(label, _)| *label == b"key_history")
+        else {
+            return BTreeMap::new();
+        };
+        labeled_children(index)
+            .into_iter()
+            .filter(|(_, versions)| !matches!(versions, HashTree::Pruned(_)))
+            .map(|(key, versions)| {
+                let versions = labeled_children(versions)
+                    .into_iter()
+                    .filter(|(_, value)| matches!(value, HashTree::Leaf(_)))
+                    .map(|(version, _)| Version::from_be_bytes(version.try_into().unwrap()))
+                    .collect();
+                (key.to_vec(), versions)
+            })
+            .collect()
+    }
+
+    fn revealed_versions(tree: &HashTree<'_>) -> Vec<Version> {
+        fn collect(tree: &HashTree<'_>, under_delta: bool, versions: &mut Vec<Version>) {
+            match tree {
+                HashTree::Fork(lr) => {
+                    collect(&lr.0, under_delta, versions);
+                    collect(&lr.1, under_delta, versions);
+                }
+                HashTree::Labeled(b"delta", subtree) => collect(subtree, true, versions),
+                HashTree::Labeled(label, subtree) if under_delta => {
+                    if let HashTree::Leaf(_) = **subtree {
+                        versions.push(Version::from_be_bytes((*label).try_into().unwrap()));
+                    }
+                }
+                _ => {}
+            }
+        }
+        let mut versions = vec![];
+        collect(tree, false, &mut versions);
+        versions
+    }
+
+    fn full_tree(registry: &Registry) -> HashTree<'_> {
+        certified_tree(
+            registry,
+            Some(registry.changelog().as_hash_tree()),
+            Some(registry.key_history().as_hash_tree()),
+        )
+    }
+
+    #[test]
+    fn key_history_tree_reveals_exactly_the_requested_versions() {
+        let registry = registry_with_versions(20);
+
+        for versions in [
+            vec![],
+            vec![1],
+            vec![20],
+            vec![2, 3, 11],
+            vec![1, 7, 13, 20],
+        ] {
+            let tree = key_history_tree(&registry, &[0], false, &versions, 1..=20);
+
+            assert_eq!(revealed_versions(&tree), versions);
+            assert_eq!(
+                tree.reconstruct(),
+                full_tree(&registry).reconstruct(),
+                "witness for {versions:?} has the wrong root hash"
+            );
+        }
+    }
+
+    #[test]
+    fn key_history_tree_reveals_the_index_of_the_requested_keys() {
+        let registry = registry_with_key_history();
+
+        for (key, key_is_prefix, versions, index_range, expected_index) in [
+            (
+                &b"node_1"[..],
+                false,
+                vec![3, 5],
+                3..=7,
+                BTreeMap::from([(b"node_1".to_vec(), vec![3, 5, 7])]),
+            ),
+            (
+                &b"node_"[..],
+                true,
+                vec![3],
+                2..=4,
+                BTreeMap::from([(b"node_1".to_vec(), vec![3]), (b"node_2".to_vec(), vec![3])]),
+            ),
+            (&b"node_3"[..], false, vec![], 1..=20, BTreeMap::new()),
+            (&b"node_3"[..], true, vec![], 1..=20, BTreeMap::new()),
+        ] {
+            let tree = key_history_tree(&registry, key, key_is_prefix, &versions, index_range);
+
+            assert_eq!(revealed_versions(&tree), versions);
+            assert_eq!(revealed_index(&tree), expected_index);
+            assert_eq!(
+                tree.reconstruct(),
+                full_tree(&registry).reconstruct(),
+                "witness for {key:?} has the wrong root hash"
+            );
+        }
+    }
+
+    #[test]
+    fn certified_tree_without_witnesses_has_the_root_hash_of_the_full_tree() {
+        let registry = registry_with_key_history();
+
+        assert_eq!(
+            certified_tree(&registry, None, None).reconstruct(),
+            full_tree(&registry).reconstruct()
+        );
+    }
+}
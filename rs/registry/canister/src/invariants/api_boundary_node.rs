@@ -4,10 +4,11 @@ use std::{
 };
 
 use ic_base_types::NodeId;
+use ic_registry_keys::{make_api_boundary_node_record_key, make_node_record_key};
 
 use super::common::{
-    InvariantCheckError, RegistrySnapshot, get_api_boundary_node_ids_from_snapshot,
-    get_node_record_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot,
+    get_api_boundary_node_ids_from_snapshot, get_node_record_from_snapshot,
 };
 
 /// Checks API Boundary Node invariants:
@@ -30,58 +31,112 @@ pub(crate) fn check_api_boundary_node_invariants(
     // - As a result, the subnet is stalled
     let api_boundary_node_ids = get_api_boundary_node_ids_from_snapshot(snapshot)?;
     for api_bn_id in api_boundary_node_ids {
-        let node_record = get_node_record_from_snapshot(api_bn_id, snapshot)?;
-        let Some(node_record) = node_record else {
+        let domain = get_api_boundary_node_domain(snapshot, api_bn_id)?;
+
+        if let Some(existing_api_bn) = domain_to_id.get(&domain) {
             return Err(InvariantCheckError {
                 msg: format!(
-                    "API Boundary Node with id={api_bn_id} doesn't have a corresponding NodeRecord"
+                    "domain {domain} has two nodes associated with it with id={existing_api_bn} and id={api_bn_id}"
                 ),
                 source: None,
             });
-        };
+        }
+        domain_to_id.insert(domain, api_bn_id);
+    }
 
-        let Some(domain) = node_record.domain else {
-            return Err(InvariantCheckError {
-                msg: format!("domain field of the NodeRecord with id={api_bn_id} is None"),
-                source: None,
-            });
-        };
+    Ok(())
+}
 
-        let Some(http) = node_record.http else {
-            return Err(InvariantCheckError {
-                msg: format!("http field of the NodeRecord with id={api_bn_id} is None"),
-                source: None,
-            });
+/// Returns every violation of the API Boundary Node invariants.
+pub(crate) fn api_boundary_node_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = Vec::new();
+    let mut domain_to_id: HashMap<String, NodeId> = HashMap::new();
+    // Follows the structure of `check_api_boundary_node_invariants`.
+    let api_boundary_node_ids = match get_api_boundary_node_ids_from_snapshot(snapshot) {
+        Ok(api_boundary_node_ids) => api_boundary_node_ids,
+        Err(error) => return vec![error.for_keys(Vec::<String>::new())],
+    };
+    for api_bn_id in api_boundary_node_ids {
+        let keys = [
+            make_api_boundary_node_record_key(api_bn_id),
+            make_node_record_key(api_bn_id),
+        ];
+        let domain = match get_api_boundary_node_domain(snapshot, api_bn_id) {
+            Ok(domain) => domain,
+            Err(error) => {
+                errors.push(error.for_keys(keys));
+                continue;
+            }
         };
 
-        let Ok(_ipv6) = http.ip_addr.parse::<Ipv6Addr>() else {
-            return Err(InvariantCheckError {
-                msg: "failed to parse ipv6 address of the node".to_string(),
+        if let Some(&existing_api_bn) = domain_to_id.get(&domain) {
+            let error = InvariantCheckError {
+                msg: format!(
+                    "domain {domain} has two nodes associated with it with id={existing_api_bn} and id={api_bn_id}"
+                ),
                 source: None,
-            });
-        };
-
-        if let Some(ipv4_config) = node_record.public_ipv4_config {
-            let Ok(_ipv4) = ipv4_config.ip_addr.parse::<Ipv4Addr>() else {
-                return Err(InvariantCheckError {
-                    msg: "failed to parse ipv4 address of the node".to_string(),
-                    source: None,
-                });
             };
+            errors.push(error.for_keys(keys.into_iter().chain([
+                make_api_boundary_node_record_key(existing_api_bn),
+                make_node_record_key(existing_api_bn),
+            ])));
+            continue;
         }
+        domain_to_id.insert(domain, api_bn_id);
+    }
 
-        if let Some(existing_api_bn) = domain_to_id.get(&domain) {
+    errors
+}
+
+/// Returns the domain of the API Boundary Node, after checking that its
+/// NodeRecord is complete and well-formed.
+fn get_api_boundary_node_domain(
+    snapshot: &RegistrySnapshot,
+    api_bn_id: NodeId,
+) -> Result<String, InvariantCheckError> {
+    let node_record = get_node_record_from_snapshot(api_bn_id, snapshot)?;
+    let Some(node_record) = node_record else {
+        return Err(InvariantCheckError {
+            msg: format!(
+                "API Boundary Node with id={api_bn_id} doesn't have a corresponding NodeRecord"
+            ),
+            source: None,
+        });
+    };
+
+    let Some(domain) = node_record.domain else {
+        return Err(InvariantCheckError {
+            msg: format!("domain field of the NodeRecord with id={api_bn_id} is None"),
+            source: None,
+        });
+    };
+
+    let Some(http) = node_record.http else {
+        return Err(InvariantCheckError {
+            msg: format!("http field of the NodeRecord with id={api_bn_id} is None"),
+            source: None,
+        });
+    };
+
+    let Ok(_ipv6) = http.ip_addr.parse::<Ipv6Addr>() else {
+        return Err(InvariantCheckError {
+            msg: "failed to parse ipv6 address of the node".to_string(),
+            source: None,
+        });
+    };
+
+    if let Some(ipv4_config) = node_record.public_ipv4_config {
+        let Ok(_ipv4) = ipv4_config.ip_addr.parse::<Ipv4Addr>() else {
             return Err(InvariantCheckError {
-                msg: format!(
-                    "domain {domain} has two nodes associated with it with id={existing_api_bn} and id={api_bn_id}"
-                ),
+                msg: "failed to parse ipv4 address of the node".to_string(),
                 source: None,
             });
-        }
-        domain_to_id.insert(domain, api_bn_id);
+        };
     }
 
-    Ok(())
+    Ok(domain)
 }
 
 #[cfg(test)]
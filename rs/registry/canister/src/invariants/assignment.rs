@@ -1,11 +1,12 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use ic_base_types::{NodeId, PrincipalId};
+use ic_registry_keys::{make_api_boundary_node_record_key, make_node_record_key};
 
 use super::{
     common::{
-        InvariantCheckError, RegistrySnapshot, get_api_boundary_node_records_from_snapshot,
-        get_node_records_from_snapshot,
+        InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot,
+        get_api_boundary_node_records_from_snapshot, get_node_records_from_snapshot,
     },
     subnet::get_subnet_records_map,
 };
@@ -18,22 +19,48 @@ use super::{
 pub(crate) fn check_node_assignment_invariants(
     snapshot: &RegistrySnapshot,
 ) -> Result<(), InvariantCheckError> {
-    // Replica
-    let replicas: HashSet<NodeId> = get_subnet_records_map(snapshot)
+    let errors: Vec<String> = node_assignment_violations(snapshot)
         .into_iter()
-        .flat_map(|(_, s)| s.membership)
-        .map(|v| NodeId::from(PrincipalId::try_from(v).unwrap()))
+        .map(|violation| violation.error.msg)
         .collect();
 
+    if !errors.is_empty() {
+        return Err(InvariantCheckError {
+            msg: errors.join("\n"),
+            source: None,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns a violation of the node assignment invariants for every node with
+/// an invalid assignment.
+pub(crate) fn node_assignment_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    // Replica, together with the keys of the subnet records it is a member of
+    let mut replicas: BTreeMap<NodeId, BTreeSet<String>> = BTreeMap::new();
+    for (key, subnet_record) in get_subnet_records_map(snapshot) {
+        let subnet_key = String::from_utf8_lossy(&key).into_owned();
+        for member in subnet_record.membership {
+            let node_id = NodeId::from(PrincipalId::try_from(member).unwrap());
+            replicas
+                .entry(node_id)
+                .or_default()
+                .insert(subnet_key.clone());
+        }
+    }
+
     // ApiBoundaryNode
     let api_boundary_nodes: HashSet<NodeId> = get_api_boundary_node_records_from_snapshot(snapshot)
         .into_keys()
         .collect();
 
-    let errors: Vec<String> = get_node_records_from_snapshot(snapshot).keys().map(|node_id| {
+    get_node_records_from_snapshot(snapshot).into_keys().filter_map(|node_id| {
             let (is_replica, is_api_boundary_node) = (
-                replicas.contains(node_id),
-                api_boundary_nodes.contains(node_id),
+                replicas.contains_key(&node_id),
+                api_boundary_nodes.contains(&node_id),
             );
 
             match (is_replica, is_api_boundary_node) {
@@ -44,21 +71,22 @@ pub(crate) fn check_node_assignment_invariants(
                 (false, true) |
 
                 // unassigned
-                (false, false) => Ok(()),
+                (false, false) => None,
 
                 // invalid
-                _ => Err(format!("invalid assignment for node {node_id}: is_replica = {is_replica}, is_api_boundary_node = {is_api_boundary_node}")),
+                _ => {
+                    let error = InvariantCheckError {
+                        msg: format!("invalid assignment for node {node_id}: is_replica = {is_replica}, is_api_boundary_node = {is_api_boundary_node}"),
+                        source: None,
+                    };
+                    let keys = [make_node_record_key(node_id)]
+                        .into_iter()
+                        .chain(replicas[&node_id].iter().cloned())
+                        .chain([make_api_boundary_node_record_key(node_id)]);
+                    Some(error.for_keys(keys))
+                }
             }
-        }).filter_map(Result::err).collect();
-
-    if !errors.is_empty() {
-        return Err(InvariantCheckError {
-            msg: errors.join("\n"),
-            source: None,
-        });
-    }
-
-    Ok(())
+        }).collect()
 }
 
 #[cfg(test)]
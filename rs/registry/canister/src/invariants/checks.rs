@@ -1,19 +1,24 @@
 use crate::{
     common::LOG_PREFIX,
     invariants::{
-        api_boundary_node::check_api_boundary_node_invariants,
-        assignment::check_node_assignment_invariants,
-        common::RegistrySnapshot,
-        crypto::check_node_crypto_keys_invariants,
-        endpoint::check_endpoint_invariants,
-        firewall::check_firewall_invariants,
-        hostos_version::check_hostos_version_invariants,
-        node_operator::check_node_operator_invariants,
-        node_record::check_node_record_invariants,
-        replica_version::check_replica_version_invariants,
-        routing_table::{check_canister_migrations_invariants, check_routing_table_invariants},
-        subnet::check_subnet_invariants,
-        unassigned_nodes_config::check_unassigned_nodes_config_invariants,
+        api_boundary_node::{api_boundary_node_violations, check_api_boundary_node_invariants},
+        assignment::{check_node_assignment_invariants, node_assignment_violations},
+        common::{KeyedInvariantCheckError, RegistrySnapshot},
+        crypto::{check_node_crypto_keys_invariants, node_crypto_keys_violations},
+        endpoint::{check_endpoint_invariants, endpoint_violations},
+        firewall::{check_firewall_invariants, firewall_violations},
+        hostos_version::{check_hostos_version_invariants, hostos_version_violations},
+        node_operator::{check_node_operator_invariants, node_operator_violations},
+        node_record::{check_node_record_invariants, node_record_violations},
+        replica_version::{check_replica_version_invariants, replica_version_violations},
+        routing_table::{
+            canister_migrations_violations, check_canister_migrations_invariants,
+            check_routing_table_invariants, routing_table_violations,
+        },
+        subnet::{check_subnet_invariants, subnet_violations},
+        unassigned_nodes_config::{
+            check_unassigned_nodes_config_invariants, unassigned_nodes_config_violations,
+        },
     },
     registry::Registry,
     storage::with_chunks,
@@ -26,6 +31,82 @@ use ic_registry_canister_chunkify::dechunkify;
 use ic_registry_transport::pb::v1::{
     RegistryMutation, high_capacity_registry_value, registry_mutation::Type,
 };
+use std::panic::catch_unwind;
+
+type InvariantCheck = fn(&RegistrySnapshot) -> Vec<KeyedInvariantCheckError>;
+
+/// The groups of invariants that must hold for the registry state as a whole,
+/// by name, in the order in which they are checked.
+const GLOBAL_STATE_INVARIANTS: &[(&str, InvariantCheck)] = &[
+    ("node_operator", |snapshot| {
+        node_operator_violations(snapshot, false)
+    }),
+    ("node_crypto_keys", node_crypto_keys_violations),
+    ("node_assignment", node_assignment_violations),
+    ("routing_table", routing_table_violations),
+    ("canister_migrations", canister_migrations_violations),
+    ("subnet", subnet_violations),
+    ("replica_version", replica_version_violations),
+    ("api_boundary_node", api_boundary_node_violations),
+    ("hostos_version", hostos_version_violations),
+    ("endpoint", |snapshot| endpoint_violations(snapshot, false)),
+    ("firewall", firewall_violations),
+    (
+        "unassigned_nodes_config",
+        unassigned_nodes_config_violations,
+    ),
+    ("node_record", node_record_violations),
+];
+
+/// A violated invariant of a registry snapshot.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct InvariantViolation {
+    /// The name of the violated group of invariants, e.g., "subnet".
+    pub invariant: &'static str,
+    pub message: String,
+    /// The keys of the records of the snapshot that violate the invariant, as
+    /// reported by the check. Empty if the check panicked.
+    pub keys: Vec<String>,
+}
+
+/// Checks the same invariants that the registry canister checks before
+/// applying mutations, against an arbitrary snapshot, e.g., a local store.
+///
+/// Unlike the canister, this does not stop at the first violation: every
+/// violation of every group of invariants is returned. A check that panics,
+/// e.g., because a record cannot be decoded, counts as a violation of its
+/// group, too.
+pub fn check_invariants(snapshot: &RegistrySnapshot) -> Vec<InvariantViolation> {
+    GLOBAL_STATE_INVARIANTS
+        .iter()
+        .flat_map(
+            |&(invariant, check)| match catch_unwind(|| check(snapshot)) {
+                Ok(errors) => errors
+                    .into_iter()
+                    .map(
+                        |KeyedInvariantCheckError { keys, error }| InvariantViolation {
+                            invariant,
+                            message: match error.source {
+                                Some(source) => format!("{}, cause: {source}", error.msg),
+                                None => error.msg,
+                            },
+                            keys,
+                        },
+                    )
+                    .collect(),
+                Err(panic) => vec![InvariantViolation {
+                    invariant,
+                    message: panic
+                        .downcast_ref::<String>()
+                        .cloned()
+                        .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                        .unwrap_or_else(|| "check panicked".to_string()),
+                    keys: vec![],
+                }],
+            },
+        )
+        .collect()
+}
 
 impl Registry {
     pub fn check_changelog_version_invariants(&self) {
@@ -144,7 +225,8 @@ impl Registry {
         snapshot
     }
 
-    fn take_latest_snapshot(&self) -> RegistrySnapshot {
+    /// Returns the latest value of every key that is present.
+    pub fn take_latest_snapshot(&self) -> RegistrySnapshot {
         let mut snapshot = RegistrySnapshot::new();
 
         for (key, values) in self.store.iter() {
@@ -180,25 +262,28 @@ impl Registry {
 
 #[cfg(test)]
 mod tests {
+    use crate::common::test_helpers::invariant_compliant_registry;
     use crate::registry::EncodedVersion;
 
     use super::*;
     use ic_base_types::CanisterId;
     use ic_nervous_system_common_test_keys::TEST_USER1_PRINCIPAL;
     use ic_protobuf::registry::{
+        node::v1::NodeRecord,
         node_operator::v1::NodeOperatorRecord,
         routing_table::v1::{
             CanisterMigrations as PbCanisterMigrations, RoutingTable as PbRoutingTable,
         },
     };
     use ic_registry_keys::{
-        make_canister_migrations_record_key, make_canister_ranges_key,
+        NODE_RECORD_KEY_PREFIX, make_canister_migrations_record_key, make_canister_ranges_key,
         make_node_operator_record_key,
     };
     use ic_registry_routing_table::{CanisterIdRange, CanisterMigrations, RoutingTable};
     use ic_registry_transport::{
         delete, insert,
         pb::v1::{RegistryAtomicMutateRequest, RegistryMutation},
+        upsert,
     };
     use ic_test_utilities_types::ids::subnet_test_id;
     use maplit::btreemap;
@@ -308,6 +393,104 @@ mod tests {
         registry.check_global_state_invariants(&mutations);
     }
 
+    // Returns a compliant registry, and mutations that make one of its node
+    // records violate the invariants as done by `modify_node_record`, and
+    // that add canister migrations that are not hosted by any subnet.
+    fn registry_and_invalid_mutations(
+        modify_node_record: impl FnOnce(&mut NodeRecord),
+    ) -> (Registry, Vec<RegistryMutation>) {
+        let registry = invariant_compliant_registry(0);
+        let (node_record_key, node_record_value) = registry
+            .take_latest_snapshot()
+            .into_iter()
+            .find(|(key, _)| key.starts_with(NODE_RECORD_KEY_PREFIX.as_bytes()))
+            .unwrap();
+        let mut node_record = NodeRecord::decode(node_record_value.as_slice()).unwrap();
+        modify_node_record(&mut node_record);
+        let canister_migrations = CanisterMigrations::try_from(btreemap! {
+            CanisterIdRange{ start: CanisterId::from(0x200), end: CanisterId::from(0x2ff) } => vec![subnet_test_id(1), subnet_test_id(2)],
+        }).unwrap();
+
+        let mutations = vec![
+            upsert(node_record_key, node_record.encode_to_vec()),
+            upsert(
+                make_canister_migrations_record_key(),
+                PbCanisterMigrations::from(canister_migrations).encode_to_vec(),
+            ),
+        ];
+        (registry, mutations)
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invariant check failed with message: canister ID range CanisterIdRange"
+    )]
+    fn global_state_invariants_panic_with_first_error() {
+        // The endpoint invariants are checked after the canister migrations
+        // invariants, so only the latter error is reported.
+        let (registry, mutations) = registry_and_invalid_mutations(|node_record| {
+            node_record.xnet = None;
+        });
+
+        registry.check_global_state_invariants(&mutations);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not find HostOS version: unknown")]
+    fn global_state_invariants_panic_in_later_check_despite_earlier_error() {
+        // All invariants are checked even if an earlier one is violated, so a
+        // panicking check takes precedence over the canister migrations error.
+        let (registry, mutations) = registry_and_invalid_mutations(|node_record| {
+            node_record.hostos_version_id = Some("unknown".to_string());
+        });
+
+        registry.check_global_state_invariants(&mutations);
+    }
+
+    #[test]
+    fn check_invariants_reports_nothing_for_compliant_snapshot() {
+        let snapshot = invariant_compliant_registry(0).take_latest_snapshot();
+
+        assert_eq!(check_invariants(&snapshot), vec![]);
+    }
+
+    #[test]
+    fn check_invariants_reports_every_violated_invariant_with_keys() {
+        let mut snapshot = invariant_compliant_registry(0).take_latest_snapshot();
+        let (node_record_key, node_record_value) = snapshot
+            .iter_mut()
+            .find(|(key, _)| key.starts_with(NODE_RECORD_KEY_PREFIX.as_bytes()))
+            .unwrap();
+        let node_record_key = String::from_utf8(node_record_key.clone()).unwrap();
+        let mut node_record = NodeRecord::decode(node_record_value.as_slice()).unwrap();
+        node_record.xnet = None;
+        *node_record_value = node_record.encode_to_vec();
+        let canister_migrations = CanisterMigrations::try_from(btreemap! {
+            CanisterIdRange{ start: CanisterId::from(0x200), end: CanisterId::from(0x2ff) } => vec![subnet_test_id(1), subnet_test_id(2)],
+            CanisterIdRange{ start: CanisterId::from(0x300), end: CanisterId::from(0x3ff) } => vec![subnet_test_id(1), subnet_test_id(2)],
+        }).unwrap();
+        snapshot.insert(
+            make_canister_migrations_record_key().into_bytes(),
+            PbCanisterMigrations::from(canister_migrations).encode_to_vec(),
+        );
+
+        let violations = check_invariants(&snapshot);
+
+        let invariants: Vec<_> = violations.iter().map(|v| v.invariant).collect();
+        assert_eq!(
+            invariants,
+            vec!["canister_migrations", "canister_migrations", "endpoint"]
+        );
+        assert!(
+            violations[0]
+                .keys
+                .contains(&make_canister_migrations_record_key()),
+            "{:?}",
+            violations[0]
+        );
+        assert_eq!(violations[2].keys, vec![node_record_key]);
+    }
+
     #[test]
     fn snapshot_reflects_latest_registry_state() {
         let routing_table_shard_key = make_canister_ranges_key(CanisterId::from(0));
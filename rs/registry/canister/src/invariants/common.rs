@@ -21,7 +21,7 @@ use url::Url;
 /// A representation of the data held by the registry.
 /// It is kept in-memory only, for global consistency checks before mutations
 /// are finalized.
-pub(crate) type RegistrySnapshot = BTreeMap<Vec<u8>, Vec<u8>>;
+pub type RegistrySnapshot = BTreeMap<Vec<u8>, Vec<u8>>;
 
 #[derive(Debug)]
 pub(crate) struct InvariantCheckError {
@@ -45,6 +45,27 @@ impl error::Error for InvariantCheckError {
     }
 }
 
+impl InvariantCheckError {
+    /// Attributes the error to the records with the given keys.
+    pub(crate) fn for_keys<K: Into<String>>(
+        self,
+        keys: impl IntoIterator<Item = K>,
+    ) -> KeyedInvariantCheckError {
+        KeyedInvariantCheckError {
+            keys: keys.into_iter().map(Into::into).collect(),
+            error: self,
+        }
+    }
+}
+
+/// A violation of an invariant, together with the keys of the records that
+/// violate it.
+#[derive(Debug)]
+pub(crate) struct KeyedInvariantCheckError {
+    pub keys: Vec<String>,
+    pub error: InvariantCheckError,
+}
+
 /// Returns all node records in the snapshot.
 pub(crate) fn get_all_node_records(snapshot: &RegistrySnapshot) -> Vec<NodeRecord> {
     let mut nodes: Vec<NodeRecord> = Vec::new();
@@ -204,37 +225,66 @@ pub(crate) fn get_subnet_ids_from_snapshot(snapshot: &RegistrySnapshot) -> Vec<S
         .unwrap_or_default()
 }
 
-pub(crate) fn assert_sha256(s: &str) {
+pub(crate) fn assert_valid_urls_and_hash(urls: &[String], hash: &str, allow_file_url: bool) {
+    if let Err(e) = check_valid_urls_and_hash(urls, hash, allow_file_url) {
+        panic!("{}", e.msg);
+    }
+}
+
+pub(crate) fn check_sha256(s: &str) -> Result<(), InvariantCheckError> {
     if s.bytes().any(|x| !x.is_ascii_hexdigit()) {
-        panic!("Hash contains at least one invalid character: `{s}`");
+        return Err(InvariantCheckError {
+            msg: format!("Hash contains at least one invalid character: `{s}`"),
+            source: None,
+        });
     }
 
     if s.len() != 64 {
-        panic!("Hash is an invalid length: `{s}`");
+        return Err(InvariantCheckError {
+            msg: format!("Hash is an invalid length: `{s}`"),
+            source: None,
+        });
     }
+    Ok(())
 }
 
-pub(crate) fn assert_valid_urls_and_hash(urls: &[String], hash: &str, allow_file_url: bool) {
+pub(crate) fn check_valid_urls_and_hash(
+    urls: &[String],
+    hash: &str,
+    allow_file_url: bool,
+) -> Result<(), InvariantCheckError> {
     // Either both, the URL and the hash are set, or both are not set.
     if (urls.is_empty() as i32 ^ hash.is_empty() as i32) > 0 {
-        panic!("Either both, an url and a hash must be set, or none.");
+        return Err(InvariantCheckError {
+            msg: "Either both, an url and a hash must be set, or none.".to_string(),
+            source: None,
+        });
     }
     if urls.is_empty() {
-        return;
+        return Ok(());
     }
 
-    assert_sha256(hash);
+    check_sha256(hash)?;
 
-    urls.iter().for_each(|url|
+    for url in urls {
         // File URLs are used in test deployments. We only disallow non-ASCII.
         if allow_file_url && url.starts_with("file://") {
-            assert!(url.is_ascii(), "file-URL {url} contains non-ASCII characters.");
+            if !url.is_ascii() {
+                return Err(InvariantCheckError {
+                    msg: format!("file-URL {url} contains non-ASCII characters."),
+                    source: None,
+                });
+            }
         }
         // if it's not a file URL, it should be a valid URL.
         else if let Err(e) = Url::parse(url) {
-            panic!("Release package URL {url} is not valid: {e}");
+            return Err(InvariantCheckError {
+                msg: format!("Release package URL {url} is not valid: {e}"),
+                source: None,
+            });
         }
-    );
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -1,8 +1,8 @@
 use crate::common::LOG_PREFIX;
 use crate::invariants::{
     common::{
-        InvariantCheckError, RegistrySnapshot, get_node_records_from_snapshot,
-        get_subnet_ids_from_snapshot, get_value_from_snapshot,
+        InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot,
+        get_node_records_from_snapshot, get_subnet_ids_from_snapshot, get_value_from_snapshot,
     },
     subnet::get_subnet_records_map,
 };
@@ -14,8 +14,9 @@ use ic_protobuf::types::v1::MasterPublicKeyId;
 use ic_registry_keys::{
     CRYPTO_RECORD_KEY_PREFIX, CRYPTO_TLS_CERT_KEY_PREFIX, NODE_RECORD_KEY_PREFIX,
     get_master_public_key_id_from_signing_subnet_list_key, make_catch_up_package_contents_key,
-    make_crypto_threshold_signing_pubkey_key, make_node_record_key, make_subnet_record_key,
-    maybe_parse_crypto_node_key, maybe_parse_crypto_tls_cert_key,
+    make_crypto_node_key, make_crypto_threshold_signing_pubkey_key, make_crypto_tls_cert_key,
+    make_node_record_key, make_subnet_record_key, maybe_parse_crypto_node_key,
+    maybe_parse_crypto_tls_cert_key,
 };
 use ic_registry_subnet_features::ChainKeyConfig;
 use ic_types::crypto::KeyPurpose;
@@ -226,37 +227,7 @@ fn get_all_nodes_public_keys_and_certs(
     let mut certs = BTreeMap::new();
 
     for (k, v) in snapshot {
-        if k.starts_with(CRYPTO_RECORD_KEY_PREFIX.as_bytes()) {
-            let key = String::from_utf8(k.to_owned()).map_err(|e| InvariantCheckError {
-                msg: format!("invalid crypto node key bytes: {e}"),
-                source: None,
-            })?;
-            let (node_id, key_purpose) =
-                maybe_parse_crypto_node_key(&key).ok_or(InvariantCheckError {
-                    msg: "invalid crypto node key".to_string(),
-                    source: None,
-                })?;
-            let pk = PublicKey::decode(v.as_slice()).map_err(|e| InvariantCheckError {
-                msg: format!("invalid serialised public key: {e}"),
-                source: None,
-            })?;
-            pks.insert((node_id, key_purpose), pk);
-        } else if k.starts_with(CRYPTO_TLS_CERT_KEY_PREFIX.as_bytes()) {
-            let key = String::from_utf8(k.to_owned()).map_err(|e| InvariantCheckError {
-                msg: format!("invalid tls cert key bytes: {e}"),
-                source: None,
-            })?;
-            let node_id = maybe_parse_crypto_tls_cert_key(&key).ok_or(InvariantCheckError {
-                msg: "invalid tls cert key".to_string(),
-                source: None,
-            })?;
-            let cert =
-                X509PublicKeyCert::decode(v.as_slice()).map_err(|e| InvariantCheckError {
-                    msg: format!("invalid serialised public key: {e}"),
-                    source: None,
-                })?;
-            certs.insert(node_id, cert);
-        }
+        parse_node_crypto_record(k, v, &mut pks, &mut certs)?;
     }
     Ok((pks, certs))
 }
@@ -326,7 +297,7 @@ fn check_chain_key_signing_subnet_lists(
     get_all_chain_key_signing_subnet_list_records(snapshot)
         .iter()
         .try_for_each(|(key_id, chain_key_signing_subnet_list)| {
-            let master_key_id =  get_master_public_key_id_from_signing_subnet_list_key(key_id)
+            let master_key_id = get_master_public_key_id_from_signing_subnet_list_key(key_id)
                 .map_err(|err| InvariantCheckError {
                     msg: format!(
                         "Registry key_id {key_id} could not be converted to an MasterPublicKeyId",
@@ -338,39 +309,20 @@ fn check_chain_key_signing_subnet_lists(
                 .subnets
                 .iter()
                 .try_for_each(|subnet_id_bytes| {
-                    let subnet_id = subnet_id_try_from_protobuf(subnet_id_bytes.clone())
-                    .map_err(|err| InvariantCheckError {
-                        msg: "Failed to deserialize subnet id from protobuf".to_string(),
-                        source: Some(Box::new(err)),
-                    })?;
-
-                    if subnet_records_map
-                        .get(&make_subnet_record_key(subnet_id).into_bytes())
-                        .ok_or(InvariantCheckError {
-                            msg: format!(
-                                "A non-existent subnet {subnet_id} was set as the holder of a key_id {key_id}"
-                            ),
-                            source: None,
-                        })?
-                        .chain_key_config
-                        .as_ref()
-                        .ok_or(InvariantCheckError {
-                            msg: format!("The subnet {subnet_id} does not have a ChainKeyConfig"),
-                            source: None,
-                        })?
-                        .key_configs
-                        .iter()
-                        .filter_map(|config| config.key_id.clone())
-                        .any(|key| key == MasterPublicKeyId::from(&master_key_id)) {
-                            Ok(())
-                        } else {
-                            Err(InvariantCheckError {
-                                msg: format!(
-                                    "The subnet {subnet_id} does not have the key with {key_id} in its chain key configurations"
-                                ),
-                                source: None,
-                            })
-                        }
+                    let subnet_id =
+                        subnet_id_try_from_protobuf(subnet_id_bytes.clone()).map_err(|err| {
+                            InvariantCheckError {
+                                msg: "Failed to deserialize subnet id from protobuf".to_string(),
+                                source: Some(Box::new(err)),
+                            }
+                        })?;
+
+                    check_subnet_holds_chain_key(
+                        subnet_records_map.get(&make_subnet_record_key(subnet_id).into_bytes()),
+                        subnet_id,
+                        key_id,
+                        &MasterPublicKeyId::from(&master_key_id),
+                    )
                 })
         })
 }
@@ -416,9 +368,43 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
 ) -> Result<(), InvariantCheckError> {
     println!("{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check_start");
 
+    let (bad_subnets, ok_subnet_count) =
+        get_subnets_with_inconsistent_high_threshold_public_key(snapshot);
+    let bad_subnet_count = bad_subnets.len();
+    let result = if !bad_subnets.is_empty() {
+        Err(InvariantCheckError {
+            msg: format!(
+                "high_threshold_public_key and cup_contents are inconsistent for subnet(s) {}",
+                bad_subnets
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            source: None,
+        })
+    } else {
+        Ok(())
+    };
+    let label = if result.is_ok() {
+        "high_threshold_public_key_matches_the_one_in_cup_check_success"
+    } else {
+        "high_threshold_public_key_matches_the_one_in_cup_check_failure"
+    };
+    println!(
+        "{LOG_PREFIX}{label}: # of ok subnets: {ok_subnet_count}, # of bad subnets: {bad_subnet_count}, result: {result:?}"
+    );
+    result
+}
+
+/// Returns the subnets whose high threshold public key is missing or does not
+/// match the one in their CUP, together with the number of subnets where it
+/// matches.
+fn get_subnets_with_inconsistent_high_threshold_public_key(
+    snapshot: &RegistrySnapshot,
+) -> (Vec<SubnetId>, usize) {
     let mut bad_subnets: Vec<SubnetId> = vec![];
     let mut ok_subnet_count = 0;
-    let mut bad_subnet_count = 0;
 
     for subnet_id in get_subnet_ids_from_snapshot(snapshot) {
         let high_threshold_public_key_bytes: Option<PublicKey> = get_value_from_snapshot(
@@ -436,7 +422,6 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
                 Ok(pk) => pk,
                 Err(e) => {
                     bad_subnets.push(subnet_id);
-                    bad_subnet_count += 1;
                     println!(
                         "{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check: error converting high threshold public key proto to ThresholdSigPublicKey for subnet {subnet_id}: {e:?}"
                     );
@@ -453,7 +438,6 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
                 }
                 None => {
                     bad_subnets.push(subnet_id);
-                    bad_subnet_count += 1;
                     println!(
                         "{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check: high threshold public key set, but no high threshold public key in cup contents for subnet {subnet_id}"
                     );
@@ -466,7 +450,6 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
                 Ok(public_key_bytes_from_cup) => public_key_bytes_from_cup.into_bytes(),
                 Err(e) => {
                     bad_subnets.push(subnet_id);
-                    bad_subnet_count += 1;
                     println!(
                         "{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check: error extracting high threshold public key bytes from cup contents for subnet {subnet_id}: {e:?}"
                     );
@@ -476,7 +459,6 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
 
             if separate_pk_bytes != public_key_bytes_from_cup {
                 bad_subnets.push(subnet_id);
-                bad_subnet_count += 1;
                 println!(
                     "{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check: explicitly set high threshold public key does not match the one in cup contents for subnet {subnet_id}"
                 );
@@ -485,34 +467,428 @@ fn check_high_threshold_public_key_matches_the_one_in_cup(
             }
         } else {
             bad_subnets.push(subnet_id);
-            bad_subnet_count += 1;
             println!(
                 "{LOG_PREFIX}high_threshold_public_key_matches_the_one_in_cup_check: high threshold public key and/or cup contents not found for subnet {subnet_id}"
             );
         }
     }
-    let result = if !bad_subnets.is_empty() {
+    (bad_subnets, ok_subnet_count)
+}
+
+// Adds the public key or TLS cert stored under `k`, if any, to `pks` or `certs`.
+fn parse_node_crypto_record(
+    k: &[u8],
+    v: &[u8],
+    pks: &mut AllPublicKeys,
+    certs: &mut AllTlsCertificates,
+) -> Result<(), InvariantCheckError> {
+    if k.starts_with(CRYPTO_RECORD_KEY_PREFIX.as_bytes()) {
+        let key = String::from_utf8(k.to_owned()).map_err(|e| InvariantCheckError {
+            msg: format!("invalid crypto node key bytes: {e}"),
+            source: None,
+        })?;
+        let (node_id, key_purpose) =
+            maybe_parse_crypto_node_key(&key).ok_or(InvariantCheckError {
+                msg: "invalid crypto node key".to_string(),
+                source: None,
+            })?;
+        let pk = PublicKey::decode(v).map_err(|e| InvariantCheckError {
+            msg: format!("invalid serialised public key: {e}"),
+            source: None,
+        })?;
+        pks.insert((node_id, key_purpose), pk);
+    } else if k.starts_with(CRYPTO_TLS_CERT_KEY_PREFIX.as_bytes()) {
+        let key = String::from_utf8(k.to_owned()).map_err(|e| InvariantCheckError {
+            msg: format!("invalid tls cert key bytes: {e}"),
+            source: None,
+        })?;
+        let node_id = maybe_parse_crypto_tls_cert_key(&key).ok_or(InvariantCheckError {
+            msg: "invalid tls cert key".to_string(),
+            source: None,
+        })?;
+        let cert = X509PublicKeyCert::decode(v).map_err(|e| InvariantCheckError {
+            msg: format!("invalid serialised public key: {e}"),
+            source: None,
+        })?;
+        certs.insert(node_id, cert);
+    }
+    Ok(())
+}
+
+fn check_subnet_holds_chain_key(
+    subnet_record: Option<&SubnetRecord>,
+    subnet_id: SubnetId,
+    key_id: &str,
+    master_key_id: &MasterPublicKeyId,
+) -> Result<(), InvariantCheckError> {
+    if subnet_record
+        .ok_or(InvariantCheckError {
+            msg: format!(
+                "A non-existent subnet {subnet_id} was set as the holder of a key_id {key_id}"
+            ),
+            source: None,
+        })?
+        .chain_key_config
+        .as_ref()
+        .ok_or(InvariantCheckError {
+            msg: format!("The subnet {subnet_id} does not have a ChainKeyConfig"),
+            source: None,
+        })?
+        .key_configs
+        .iter()
+        .filter_map(|config| config.key_id.as_ref())
+        .any(|key| key == master_key_id)
+    {
+        Ok(())
+    } else {
         Err(InvariantCheckError {
             msg: format!(
-                "high_threshold_public_key and cup_contents are inconsistent for subnet(s) {}",
-                bad_subnets
-                    .iter()
-                    .map(|s| s.to_string())
-                    .collect::<Vec<String>>()
-                    .join(", ")
+                "The subnet {subnet_id} does not have the key with {key_id} in its chain key configurations"
             ),
             source: None,
         })
-    } else {
-        Ok(())
+    }
+}
+
+/// Returns every violation of the invariants checked by
+/// `check_node_crypto_keys_invariants`.
+pub(crate) fn node_crypto_keys_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = node_crypto_keys_exist_and_are_unique_violations(snapshot);
+    errors.extend(orphaned_node_crypto_records_violations(snapshot));
+    errors.extend(chain_key_configs_violations(snapshot));
+    errors.extend(chain_key_signing_subnet_lists_violations(snapshot));
+    errors.extend(high_threshold_public_key_violations(snapshot));
+    errors
+}
+
+fn node_crypto_keys_exist_and_are_unique_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    println!("{LOG_PREFIX}node_crypto_keys_invariants_check_start");
+    let nodes = get_node_records_from_snapshot(snapshot);
+    let (pks, certs) = match get_all_nodes_public_keys_and_certs_or_violations(snapshot) {
+        Ok(pks_and_certs) => pks_and_certs,
+        Err(errors) => return errors,
     };
-    let label = if result.is_ok() {
-        "high_threshold_public_key_matches_the_one_in_cup_check_success"
+
+    let mut ok_node_count = 0;
+    let mut bad_node_count = 0;
+    let mut errors = Vec::new();
+    for node_id in nodes.keys() {
+        // Check that all the nodes' keys and certs are present, and node_id is consistent
+        let node_errors = node_keys_and_cert_violations(node_id, &pks, &certs);
+        if node_errors.is_empty() {
+            ok_node_count += 1;
+        } else {
+            bad_node_count += 1;
+            errors.extend(node_errors);
+        }
+    }
+
+    // Check that all the keys and certs are unique.
+    errors.extend(nodes_crypto_keys_and_certs_uniqueness_violations(
+        pks, certs,
+    ));
+
+    let label = if errors.is_empty() {
+        "node_crypto_keys_invariants_check_success"
     } else {
-        "high_threshold_public_key_matches_the_one_in_cup_check_failure"
+        "node_crypto_keys_invariants_check_failure"
     };
     println!(
-        "{LOG_PREFIX}{label}: # of ok subnets: {ok_subnet_count}, # of bad subnets: {bad_subnet_count}, result: {result:?}"
+        "{LOG_PREFIX}{label}: # of ok nodes: {ok_node_count}, # of bad nodes: {bad_node_count}, result: {:?}",
+        errors.first()
     );
-    result
+    errors
+}
+
+fn node_keys_and_cert_violations(
+    node_id: &NodeId,
+    pks: &AllPublicKeys,
+    certs: &AllTlsCertificates,
+) -> Vec<KeyedInvariantCheckError> {
+    let node_record_key = make_node_record_key(*node_id);
+    let mut errors = Vec::new();
+    for key_purpose in [
+        KeyPurpose::NodeSigning,
+        KeyPurpose::CommitteeSigning,
+        KeyPurpose::DkgDealingEncryption,
+        KeyPurpose::IDkgMEGaEncryption,
+    ] {
+        match pks.get(&(*node_id, key_purpose)) {
+            Some(pk) => {
+                if key_purpose == KeyPurpose::NodeSigning
+                    && let Err(err) = node_id_is_consistently_derived(node_id, pk)
+                {
+                    println!("{} {}", LOG_PREFIX, err.msg);
+                    errors.push(err.for_keys([
+                        node_record_key.clone(),
+                        make_crypto_node_key(*node_id, key_purpose),
+                    ]));
+                }
+            }
+            None => {
+                let msg = format!("node {node_id} has no key for purpose {key_purpose:?} ");
+                println!("{LOG_PREFIX} {msg}");
+                let err = InvariantCheckError { msg, source: None };
+                errors.push(err.for_keys([node_record_key.clone()]));
+            }
+        }
+    }
+    if certs.get(node_id).is_none() {
+        let msg = format!("node {node_id} has no TLS cert");
+        println!("{LOG_PREFIX} {msg}");
+        let err = InvariantCheckError { msg, source: None };
+        errors.push(err.for_keys([node_record_key]));
+    }
+    errors
+}
+
+// Note: this function intentionally checks that all crypto key material is unique across both
+// public keys and TLS certs, just to avoid potential abuse of key material in different contexts.
+fn nodes_crypto_keys_and_certs_uniqueness_violations(
+    pks: AllPublicKeys,
+    certs: AllTlsCertificates,
+) -> Vec<KeyedInvariantCheckError> {
+    // The node using the key material first, and the key of the record holding it.
+    let mut unique_pks_and_certs: HashMap<Vec<u8>, (NodeId, String)> = HashMap::new();
+    let mut errors = Vec::new();
+    for ((node_id, purpose), pk) in pks {
+        let record_key = make_crypto_node_key(node_id, purpose);
+        match unique_pks_and_certs.get(&pk.key_value) {
+            Some((prev, prev_record_key)) => {
+                let msg = format!(
+                    "nodes {} and {} use the same public key {:?}",
+                    prev, node_id, pk.key_value
+                );
+                println!("{LOG_PREFIX} {msg}");
+                let err = InvariantCheckError { msg, source: None };
+                errors.push(err.for_keys([prev_record_key.clone(), record_key]));
+            }
+            None => {
+                unique_pks_and_certs.insert(pk.key_value, (node_id, record_key));
+            }
+        }
+    }
+    for (node_id, cert) in certs {
+        let record_key = make_crypto_tls_cert_key(node_id);
+        match unique_pks_and_certs.get(&cert.certificate_der) {
+            Some((prev, prev_record_key)) => {
+                let msg = format!(
+                    "nodes {} and {} use the same certificate {:?}",
+                    prev, node_id, cert.certificate_der
+                );
+                println!("{LOG_PREFIX} {msg}");
+                let err = InvariantCheckError { msg, source: None };
+                errors.push(err.for_keys([prev_record_key.clone(), record_key]));
+            }
+            None => {
+                unique_pks_and_certs.insert(cert.certificate_der, (node_id, record_key));
+            }
+        }
+    }
+    errors
+}
+
+// Returns all nodes' public keys and TLS certs in the snapshot, or a violation
+// for every crypto record that cannot be parsed.
+fn get_all_nodes_public_keys_and_certs_or_violations(
+    snapshot: &RegistrySnapshot,
+) -> Result<(AllPublicKeys, AllTlsCertificates), Vec<KeyedInvariantCheckError>> {
+    let mut pks = BTreeMap::new();
+    let mut certs = BTreeMap::new();
+    let mut errors = Vec::new();
+
+    for (k, v) in snapshot {
+        if let Err(err) = parse_node_crypto_record(k, v, &mut pks, &mut certs) {
+            errors.push(err.for_keys([String::from_utf8_lossy(k)]));
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok((pks, certs))
+}
+
+/// Checks the chain key configuration of every subnet in the subnet list.
+///
+/// Subnets without a record are skipped, as the subnet invariants already
+/// reject them.
+fn chain_key_configs_violations(snapshot: &RegistrySnapshot) -> Vec<KeyedInvariantCheckError> {
+    let mut subnet_records_map = get_subnet_records_map(snapshot);
+    let subnet_id_list = get_subnet_ids_from_snapshot(snapshot);
+    let mut errors = Vec::new();
+    for subnet_id in subnet_id_list {
+        let subnet_key = make_subnet_record_key(subnet_id);
+        let Some(subnet_record): Option<SubnetRecord> =
+            subnet_records_map.remove(subnet_key.as_bytes())
+        else {
+            continue;
+        };
+
+        let Some(chain_key_config_pb) = subnet_record.chain_key_config else {
+            continue;
+        };
+
+        let chain_key_config = match ChainKeyConfig::try_from(chain_key_config_pb.clone()) {
+            Ok(chain_key_config) => chain_key_config,
+            Err(err) => {
+                let err = InvariantCheckError {
+                    msg: format!(
+                        "ChainKeyConfig {chain_key_config_pb:?} of subnet {subnet_id:} could not be deserialized: {err}",
+                    ),
+                    source: None,
+                };
+                errors.push(err.for_keys([subnet_key]));
+                continue;
+            }
+        };
+
+        let mut key_ids = BTreeSet::new();
+        for key_config in chain_key_config.key_configs {
+            let key_id = key_config.key_id.clone();
+            if key_config.pre_signatures_to_create_in_advance == 0
+                && key_id.requires_pre_signatures()
+            {
+                let err = InvariantCheckError {
+                    msg: format!(
+                        "`pre_signatures_to_create_in_advance` for key {key_id} of subnet {subnet_id:} cannot be zero.",
+                    ),
+                    source: None,
+                };
+                errors.push(err.for_keys([subnet_key.clone()]));
+            }
+            if !key_ids.insert(key_id) {
+                let err = InvariantCheckError {
+                    msg: format!(
+                        "ChainKeyConfig of subnet {:} contains multiple entries for key ID {}.",
+                        subnet_id, key_config.key_id,
+                    ),
+                    source: None,
+                };
+                errors.push(err.for_keys([subnet_key.clone()]));
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that the chain key signing subnet list is consistent with the chain key configurations
+///
+/// In particular, this function checks:
+/// - That every subnet refered to by the signing subnet list exists
+/// - That the subnet has a chain key configuration that contains the corresponding key
+fn chain_key_signing_subnet_lists_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let subnet_records_map = get_subnet_records_map(snapshot);
+    let mut errors = Vec::new();
+
+    for (key_id, chain_key_signing_subnet_list) in
+        get_all_chain_key_signing_subnet_list_records(snapshot)
+    {
+        let master_key_id = match get_master_public_key_id_from_signing_subnet_list_key(&key_id) {
+            Ok(master_key_id) => master_key_id,
+            Err(err) => {
+                let err = InvariantCheckError {
+                    msg: format!(
+                        "Registry key_id {key_id} could not be converted to an MasterPublicKeyId",
+                    ),
+                    source: Some(Box::new(err)),
+                };
+                errors.push(err.for_keys([key_id]));
+                continue;
+            }
+        };
+
+        for subnet_id_bytes in &chain_key_signing_subnet_list.subnets {
+            let subnet_id = match subnet_id_try_from_protobuf(subnet_id_bytes.clone()) {
+                Ok(subnet_id) => subnet_id,
+                Err(err) => {
+                    let err = InvariantCheckError {
+                        msg: "Failed to deserialize subnet id from protobuf".to_string(),
+                        source: Some(Box::new(err)),
+                    };
+                    errors.push(err.for_keys([key_id.clone()]));
+                    continue;
+                }
+            };
+
+            let subnet_key = make_subnet_record_key(subnet_id);
+            if let Err(err) = check_subnet_holds_chain_key(
+                subnet_records_map.get(subnet_key.as_bytes()),
+                subnet_id,
+                &key_id,
+                &MasterPublicKeyId::from(&master_key_id),
+            ) {
+                errors.push(err.for_keys([key_id.clone(), subnet_key]));
+            }
+        }
+    }
+    errors
+}
+
+fn orphaned_node_crypto_records_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    // Collect unique node_ids from crypto and tls records, together with the
+    // keys of their records
+    let mut nodes_with_records: BTreeMap<NodeId, Vec<String>> = BTreeMap::new();
+    for key in snapshot.keys() {
+        let key_string = String::from_utf8_lossy(key).into_owned();
+        if let Some((node_id, _)) = maybe_parse_crypto_node_key(&key_string) {
+            nodes_with_records
+                .entry(node_id)
+                .or_default()
+                .push(key_string);
+        } else if let Some(node_id) = maybe_parse_crypto_tls_cert_key(&key_string) {
+            nodes_with_records
+                .entry(node_id)
+                .or_default()
+                .push(key_string);
+        }
+    }
+
+    // Filter to only node_ids that do not have a node_record in the registry
+    nodes_with_records.retain(|node_id, _| {
+        snapshot
+            .get(make_node_record_key(*node_id).as_bytes())
+            .is_none()
+    });
+
+    // There should be no crypto or tls records without a node_record
+    if nodes_with_records.is_empty() {
+        return vec![];
+    }
+    let nodes_with_orphaned_records = nodes_with_records.keys().collect::<Vec<_>>();
+    let err = InvariantCheckError {
+        msg: format!(
+            "There are {CRYPTO_RECORD_KEY_PREFIX} or {CRYPTO_TLS_CERT_KEY_PREFIX} entries without a corresponding {NODE_RECORD_KEY_PREFIX} entry: {nodes_with_orphaned_records:?}"
+        ),
+        source: None,
+    };
+    vec![err.for_keys(nodes_with_records.into_values().flatten())]
+}
+
+fn high_threshold_public_key_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let (bad_subnets, _) = get_subnets_with_inconsistent_high_threshold_public_key(snapshot);
+    bad_subnets
+        .into_iter()
+        .map(|subnet_id| {
+            let err = InvariantCheckError {
+                msg: format!(
+                    "high_threshold_public_key and cup_contents are inconsistent for subnet {subnet_id}"
+                ),
+                source: None,
+            };
+            err.for_keys([
+                make_crypto_threshold_signing_pubkey_key(subnet_id),
+                make_catch_up_package_contents_key(subnet_id),
+            ])
+        })
+        .collect()
 }
@@ -1,8 +1,9 @@
 use crate::invariants::common::{
-    InvariantCheckError, RegistrySnapshot, get_node_records_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, get_node_records_from_snapshot,
 };
 
 use std::{
+    collections::BTreeMap,
     convert::TryFrom,
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
     str,
@@ -10,7 +11,9 @@ use std::{
 
 use prost::alloc::collections::BTreeSet;
 
-use ic_protobuf::registry::node::v1::ConnectionEndpoint;
+use ic_base_types::NodeId;
+use ic_protobuf::registry::node::v1::{ConnectionEndpoint, NodeRecord};
+use ic_registry_keys::make_node_record_key;
 
 /// Node records are valid with connection endpoints containing
 /// syntactically correct data ("ip_addr" field parses as an IP address,
@@ -38,76 +41,153 @@ pub(crate) fn check_endpoint_invariants(
     );
     for (node_id, node_record) in node_records {
         let error_prefix = format!("{common_error_prefix} (checking failed for node {node_id})");
+        let mut new_valid_endpoints = validate_node_endpoints(node_record, strict, &error_prefix)?;
 
-        // The Boolean indicates whether an unspecified address should be tolerated
-        let mut endpoints_to_check = Vec::<(ConnectionEndpoint, bool)>::new();
-
-        if node_record.xnet.is_none() {
-            return Err(InvariantCheckError {
-                msg: format!("{error_prefix}: No Xnet endpoint found for node"),
-                source: None,
-            });
+        // Check that there is no intersection with other nodes
+        if !new_valid_endpoints.is_disjoint(&valid_endpoints) {
+            return Err(duplicate_endpoints_error(
+                &error_prefix,
+                &new_valid_endpoints,
+                |x| valid_endpoints.contains(x),
+            ));
         }
-        endpoints_to_check.push((node_record.xnet.unwrap(), false));
 
-        if node_record.http.is_none() {
-            return Err(InvariantCheckError {
-                msg: format!("{error_prefix}: No HTTP/Public API endpoint found"),
-                source: None,
-            });
-        }
-        endpoints_to_check.push((node_record.http.unwrap(), false));
+        // All is good -- add current endpoints to global set
+        valid_endpoints.append(&mut new_valid_endpoints);
+    }
 
-        let mut new_valid_endpoints = BTreeSet::<(IpAddr, u16)>::new();
+    Ok(())
+}
 
-        // Validate all endpoints of this node (excluding p2p flow endpoints which are
-        // validated separately)
-        for (endpoint, tolerate_unspecified_ip) in endpoints_to_check {
-            let valid_endpoint = validate_endpoint(&endpoint, tolerate_unspecified_ip, strict)?;
-            // Multiple nodes may have unspecified addresses, so duplicates should be avoided only for specified endpoints
-            if !valid_endpoint.0.is_unspecified() && !new_valid_endpoints.insert(valid_endpoint) {
-                return Err(InvariantCheckError {
-                    msg: format!(
-                        "{error_prefix}: Duplicate endpoint ({:?}, {:?}); previous endpoints: {new_valid_endpoints:?}",
-                        &endpoint.ip_addr, &endpoint.port
-                    ),
-                    source: None,
-                });
+/// Returns every violation of the endpoint invariants.
+///
+/// Unlike `check_endpoint_invariants`, which stops at the first violation,
+/// this checks every node.
+pub(crate) fn endpoint_violations(
+    snapshot: &RegistrySnapshot,
+    strict: bool,
+) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = Vec::new();
+    // The valid endpoints of all nodes checked so far, and the node they belong to
+    let mut valid_endpoints = BTreeMap::<(IpAddr, u16), NodeId>::new();
+    let node_records = get_node_records_from_snapshot(snapshot);
+    let common_error_prefix = format!(
+        "Invariant violation detected among {} node records",
+        node_records.len()
+    );
+    for (node_id, node_record) in node_records {
+        let error_prefix = format!("{common_error_prefix} (checking failed for node {node_id})");
+        let new_valid_endpoints = match validate_node_endpoints(node_record, strict, &error_prefix)
+        {
+            Ok(new_valid_endpoints) => new_valid_endpoints,
+            Err(error) => {
+                errors.push(error.for_keys([make_node_record_key(node_id)]));
+                continue;
             }
-        }
+        };
 
-        // Check that there are _some_ node endpoints
-        if new_valid_endpoints.is_empty() {
-            return Err(InvariantCheckError {
-                msg: format!("{error_prefix}: No endpoints to validate"),
-                source: None,
+        // Check that there is no intersection with other nodes
+        let other_nodes: BTreeSet<NodeId> = new_valid_endpoints
+            .iter()
+            .filter_map(|endpoint| valid_endpoints.get(endpoint).copied())
+            .collect();
+        if !other_nodes.is_empty() {
+            let error = duplicate_endpoints_error(&error_prefix, &new_valid_endpoints, |x| {
+                valid_endpoints.contains_key(x)
             });
+            let keys = [node_id]
+                .into_iter()
+                .chain(other_nodes)
+                .map(make_node_record_key);
+            errors.push(error.for_keys(keys));
+            continue;
         }
 
-        // Check that there is no intersection with other nodes
-        if !new_valid_endpoints.is_disjoint(&valid_endpoints) {
+        // All is good -- add current endpoints to global set
+        valid_endpoints.extend(
+            new_valid_endpoints
+                .into_iter()
+                .map(|endpoint| (endpoint, node_id)),
+        );
+    }
+
+    errors
+}
+
+fn duplicate_endpoints_error(
+    error_prefix: &str,
+    new_valid_endpoints: &BTreeSet<(IpAddr, u16)>,
+    is_duplicate: impl Fn(&(IpAddr, u16)) -> bool,
+) -> InvariantCheckError {
+    InvariantCheckError {
+        msg: format!(
+            "{error_prefix}: Duplicate endpoints detected across nodes; new_valid_endpoints = {}",
+            new_valid_endpoints
+                .iter()
+                .map(|x| if is_duplicate(x) {
+                    format!("{x:?} (duplicate)")
+                } else {
+                    format!("{x:?} (new)")
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        source: None,
+    }
+}
+
+/// Validates the endpoints of a single node and returns them.
+fn validate_node_endpoints(
+    node_record: NodeRecord,
+    strict: bool,
+    error_prefix: &str,
+) -> Result<BTreeSet<(IpAddr, u16)>, InvariantCheckError> {
+    // The Boolean indicates whether an unspecified address should be tolerated
+    let mut endpoints_to_check = Vec::<(ConnectionEndpoint, bool)>::new();
+
+    let Some(xnet) = node_record.xnet else {
+        return Err(InvariantCheckError {
+            msg: format!("{error_prefix}: No Xnet endpoint found for node"),
+            source: None,
+        });
+    };
+    endpoints_to_check.push((xnet, false));
+
+    let Some(http) = node_record.http else {
+        return Err(InvariantCheckError {
+            msg: format!("{error_prefix}: No HTTP/Public API endpoint found"),
+            source: None,
+        });
+    };
+    endpoints_to_check.push((http, false));
+
+    let mut new_valid_endpoints = BTreeSet::<(IpAddr, u16)>::new();
+
+    // Validate all endpoints of this node (excluding p2p flow endpoints which are
+    // validated separately)
+    for (endpoint, tolerate_unspecified_ip) in endpoints_to_check {
+        let valid_endpoint = validate_endpoint(&endpoint, tolerate_unspecified_ip, strict)?;
+        // Multiple nodes may have unspecified addresses, so duplicates should be avoided only for specified endpoints
+        if !valid_endpoint.0.is_unspecified() && !new_valid_endpoints.insert(valid_endpoint) {
             return Err(InvariantCheckError {
                 msg: format!(
-                    "{error_prefix}: Duplicate endpoints detected across nodes; new_valid_endpoints = {}",
-                    new_valid_endpoints
-                        .iter()
-                        .map(|x| if valid_endpoints.contains(x) {
-                            format!("{x:?} (duplicate)")
-                        } else {
-                            format!("{x:?} (new)")
-                        })
-                        .collect::<Vec<String>>()
-                        .join(", ")
+                    "{error_prefix}: Duplicate endpoint ({:?}, {:?}); previous endpoints: {new_valid_endpoints:?}",
+                    &endpoint.ip_addr, &endpoint.port
                 ),
                 source: None,
             });
         }
+    }
 
-        // All is good -- add current endpoints to global set
-        valid_endpoints.append(&mut new_valid_endpoints);
+    // Check that there are _some_ node endpoints
+    if new_valid_endpoints.is_empty() {
+        return Err(InvariantCheckError {
+            msg: format!("{error_prefix}: No endpoints to validate"),
+            source: None,
+        });
     }
 
-    Ok(())
+    Ok(new_valid_endpoints)
 }
 
 /// A helper function that validates invariants for a single endpoint
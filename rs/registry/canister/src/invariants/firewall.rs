@@ -1,6 +1,6 @@
 use crate::invariants::common::{
-    InvariantCheckError, RegistrySnapshot, get_node_records_from_snapshot,
-    get_subnet_ids_from_snapshot, get_value_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot,
+    get_node_records_from_snapshot, get_subnet_ids_from_snapshot, get_value_from_snapshot,
 };
 
 use std::{
@@ -58,6 +58,39 @@ pub(crate) fn check_firewall_invariants(
     Ok(())
 }
 
+/// Returns every violation of the firewall invariants.
+///
+/// Unlike `check_firewall_invariants`, which stops at the first violation,
+/// this checks every ruleset.
+pub(crate) fn firewall_violations(snapshot: &RegistrySnapshot) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = firewall_rule_principals_violations(snapshot);
+
+    let mut scopes: Vec<FirewallRulesScope> = get_node_records_from_snapshot(snapshot)
+        .into_keys()
+        .map(FirewallRulesScope::Node)
+        .collect();
+    scopes.extend(
+        get_subnet_ids_from_snapshot(snapshot)
+            .into_iter()
+            .map(FirewallRulesScope::Subnet),
+    );
+    scopes.extend([
+        FirewallRulesScope::ReplicaNodes,
+        FirewallRulesScope::ApiBoundaryNodes,
+        FirewallRulesScope::Global,
+    ]);
+
+    for scope in scopes {
+        let firewall_record_key = make_firewall_rules_record_key(&scope);
+        let ruleset = get_firewall_rules(snapshot, firewall_record_key.clone());
+        if let Err(error) = validate_firewall_ruleset(ruleset) {
+            errors.push(error.for_keys([firewall_record_key]));
+        }
+    }
+
+    errors
+}
+
 /// A helper function that checks the invariant that each node and subnet specific
 /// ruleset refers either to an existing node/subnet principal or is empty.
 fn validate_firewall_rule_principals(
@@ -93,6 +126,43 @@ fn validate_firewall_rule_principals(
     Ok(())
 }
 
+/// Returns a violation for every node and subnet specific ruleset that refers
+/// to a non-existing principal and is not empty.
+fn firewall_rule_principals_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let mut principal_ids: BTreeSet<PrincipalId> = get_node_records_from_snapshot(snapshot)
+        .keys()
+        .map(|s| s.get())
+        .collect();
+
+    principal_ids.extend(
+        get_subnet_ids_from_snapshot(snapshot)
+            .iter()
+            .map(|s| s.get()),
+    );
+
+    let mut errors = Vec::new();
+    for key in snapshot.keys() {
+        let record_key = String::from_utf8(key.clone()).unwrap();
+        if let Some(principal_id) = get_firewall_rules_record_principal_id(&record_key)
+            && let Some(firewall_rules) = get_firewall_rules(snapshot, record_key.to_string())
+            && !principal_ids.contains(&principal_id)
+            && !firewall_rules.entries.is_empty()
+        {
+            let error = InvariantCheckError {
+                msg: format!(
+                    "Firewall rule entry refers to non-existing principal: {record_key:?}"
+                ),
+                source: None,
+            };
+            errors.push(error.for_keys([record_key]));
+        }
+    }
+
+    errors
+}
+
 /// A helper function that validates invariants for a firewall ruleset by checking
 /// one rule after another. The ruleset is only valid if every single rule within
 /// is valid.
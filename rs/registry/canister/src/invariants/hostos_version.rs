@@ -1,6 +1,7 @@
 use crate::invariants::common::{
-    InvariantCheckError, RegistrySnapshot, assert_valid_urls_and_hash,
-    get_all_hostos_version_records, get_node_records_from_snapshot, get_value_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, assert_valid_urls_and_hash,
+    check_valid_urls_and_hash, get_all_hostos_version_records, get_node_records_from_snapshot,
+    get_value_from_snapshot,
 };
 
 use ic_protobuf::registry::hostos_version::v1::HostosVersionRecord;
@@ -18,19 +19,7 @@ use ic_registry_keys::make_hostos_version_key;
 pub(crate) fn check_hostos_version_invariants(
     snapshot: &RegistrySnapshot,
 ) -> Result<(), InvariantCheckError> {
-    let mut all_versions = Vec::new();
-
-    // Collect all referenced HostOS versions
-    let node_versions = get_all_hostos_versions_of_nodes(snapshot);
-    all_versions.extend(node_versions);
-
-    // Get the current list of registered HostOS versions
-    let registered_versions = get_all_hostos_version_records(snapshot);
-
-    all_versions.extend(registered_versions.into_iter().map(|v| v.hostos_version_id));
-    all_versions.dedup();
-
-    for version in all_versions {
+    for version in get_all_hostos_versions(snapshot) {
         // Check that every referenced version exists, i.e. we can only set a
         // Node's version to one that has already been added to the registry.
         let r = get_hostos_version_record(snapshot, version);
@@ -48,11 +37,65 @@ pub(crate) fn check_hostos_version_invariants(
     Ok(())
 }
 
+/// Returns every violation of the HostOS version invariants.
+///
+/// Unlike `check_hostos_version_invariants`, which panics at the first
+/// violation, this checks every version.
+pub(crate) fn hostos_version_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    get_all_hostos_versions(snapshot)
+        .into_iter()
+        .filter_map(|version| {
+            let key = make_hostos_version_key(&version);
+            check_hostos_version_record(snapshot, version)
+                .err()
+                .map(|error| error.for_keys([key]))
+        })
+        .collect()
+}
+
+/// Returns all HostOS versions that are either referred to by a node or
+/// registered.
+fn get_all_hostos_versions(snapshot: &RegistrySnapshot) -> Vec<String> {
+    let mut all_versions = Vec::new();
+
+    // Collect all referenced HostOS versions
+    let node_versions = get_all_hostos_versions_of_nodes(snapshot);
+    all_versions.extend(node_versions);
+
+    // Get the current list of registered HostOS versions
+    let registered_versions = get_all_hostos_version_records(snapshot);
+
+    all_versions.extend(registered_versions.into_iter().map(|v| v.hostos_version_id));
+    all_versions.dedup();
+    all_versions
+}
+
 fn get_hostos_version_record(snapshot: &RegistrySnapshot, version: String) -> HostosVersionRecord {
     get_value_from_snapshot(snapshot, make_hostos_version_key(version.clone()))
         .unwrap_or_else(|| panic!("Could not find HostOS version: {version}"))
 }
 
+fn check_hostos_version_record(
+    snapshot: &RegistrySnapshot,
+    version: String,
+) -> Result<(), InvariantCheckError> {
+    let r: HostosVersionRecord =
+        get_value_from_snapshot(snapshot, make_hostos_version_key(version.clone())).ok_or_else(
+            || InvariantCheckError {
+                msg: format!("Could not find HostOS version: {version}"),
+                source: None,
+            },
+        )?;
+
+    check_valid_urls_and_hash(
+        &r.release_package_urls,
+        &r.release_package_sha256_hex,
+        false,
+    )
+}
+
 /// Returns the list of HostOS versions where each version is referred to
 /// by at least one node.
 fn get_all_hostos_versions_of_nodes(snapshot: &RegistrySnapshot) -> Vec<String> {
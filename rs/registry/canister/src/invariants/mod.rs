@@ -12,3 +12,6 @@ mod replica_version;
 mod routing_table;
 mod subnet;
 mod unassigned_nodes_config;
+
+pub use checks::{InvariantViolation, check_invariants};
+pub use common::RegistrySnapshot;
@@ -2,10 +2,13 @@ use std::convert::TryFrom;
 
 use ic_base_types::PrincipalId;
 use ic_protobuf::registry::node_operator::v1::NodeOperatorRecord;
-use ic_registry_keys::make_node_operator_record_key;
+use ic_registry_keys::{make_node_operator_record_key, make_node_record_key};
 use prost::Message;
 
-use crate::invariants::common::{InvariantCheckError, RegistrySnapshot, get_all_node_records};
+use crate::invariants::common::{
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, get_all_node_records,
+    get_node_records_from_snapshot,
+};
 
 /// Node operator invariants hold iff:
 ///    * All node operators referred to in node records are registered
@@ -16,19 +19,49 @@ pub(crate) fn check_node_operator_invariants(
     if strict {
         for node_record in get_all_node_records(snapshot) {
             let node_operator_id = PrincipalId::try_from(node_record.node_operator_id).unwrap();
-            let key = make_node_operator_record_key(node_operator_id);
-            match snapshot.get(key.as_bytes()) {
-                Some(node_operator_record_vec) => {
-                    NodeOperatorRecord::decode(node_operator_record_vec.as_slice()).unwrap();
-                }
-                None => {
-                    return Err(InvariantCheckError {
-                        msg: format!("Node operator {node_operator_id:} not in snapshot"),
-                        source: None,
-                    });
-                }
-            }
+            check_node_operator_registered(snapshot, node_operator_id)?;
         }
     }
     Ok(())
 }
+
+/// Returns every violation of the node operator invariants.
+pub(crate) fn node_operator_violations(
+    snapshot: &RegistrySnapshot,
+    strict: bool,
+) -> Vec<KeyedInvariantCheckError> {
+    if !strict {
+        return vec![];
+    }
+    get_node_records_from_snapshot(snapshot)
+        .into_iter()
+        .filter_map(|(node_id, node_record)| {
+            let node_operator_id = PrincipalId::try_from(node_record.node_operator_id).unwrap();
+            check_node_operator_registered(snapshot, node_operator_id)
+                .err()
+                .map(|error| {
+                    error.for_keys([
+                        make_node_record_key(node_id),
+                        make_node_operator_record_key(node_operator_id),
+                    ])
+                })
+        })
+        .collect()
+}
+
+fn check_node_operator_registered(
+    snapshot: &RegistrySnapshot,
+    node_operator_id: PrincipalId,
+) -> Result<(), InvariantCheckError> {
+    let key = make_node_operator_record_key(node_operator_id);
+    match snapshot.get(key.as_bytes()) {
+        Some(node_operator_record_vec) => {
+            NodeOperatorRecord::decode(node_operator_record_vec.as_slice()).unwrap();
+            Ok(())
+        }
+        None => Err(InvariantCheckError {
+            msg: format!("Node operator {node_operator_id:} not in snapshot"),
+            source: None,
+        }),
+    }
+}
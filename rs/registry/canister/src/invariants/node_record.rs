@@ -1,27 +1,50 @@
-use crate::invariants::common::{InvariantCheckError, RegistrySnapshot, get_all_node_records};
+use crate::invariants::common::{
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, get_all_node_records,
+    get_node_records_from_snapshot,
+};
 use ic_nns_common::registry::MAX_NUM_SSH_KEYS;
+use ic_protobuf::registry::node::v1::NodeRecord;
+use ic_registry_keys::make_node_record_key;
 
 pub(crate) fn check_node_record_invariants(
     snapshot: &RegistrySnapshot,
 ) -> Result<(), InvariantCheckError> {
     for node_record in get_all_node_records(snapshot) {
-        // Enforce that the ssh_node_state_write_access field does not have too many elements.
-        if node_record.ssh_node_state_write_access.len() > MAX_NUM_SSH_KEYS {
-            return Err(InvariantCheckError {
-                msg: format!(
-                    "The `ssh_node_state_write_access` field of a `NodeReocrd` has too many elements. \
-                     {MAX_NUM_SSH_KEYS} is the maximum allowed; whereas, the `NodeRecord` with `chip_id`=\
-                     {} had {} elements \
-                     in this field.",
-                    node_record
-                        .http
-                        .map(|http| format!("{http:?}"))
-                        .unwrap_or_else(|| "?-UNKNOWN-?".to_string()),
-                    node_record.ssh_node_state_write_access.len()
-                ),
-                source: None,
-            });
-        }
+        check_node_record(node_record)?;
+    }
+
+    Ok(())
+}
+
+/// Returns every violation of the node record invariants.
+pub(crate) fn node_record_violations(snapshot: &RegistrySnapshot) -> Vec<KeyedInvariantCheckError> {
+    get_node_records_from_snapshot(snapshot)
+        .into_iter()
+        .filter_map(|(node_id, node_record)| {
+            check_node_record(node_record)
+                .err()
+                .map(|error| error.for_keys([make_node_record_key(node_id)]))
+        })
+        .collect()
+}
+
+fn check_node_record(node_record: NodeRecord) -> Result<(), InvariantCheckError> {
+    // Enforce that the ssh_node_state_write_access field does not have too many elements.
+    if node_record.ssh_node_state_write_access.len() > MAX_NUM_SSH_KEYS {
+        return Err(InvariantCheckError {
+            msg: format!(
+                "The `ssh_node_state_write_access` field of a `NodeReocrd` has too many elements. \
+                 {MAX_NUM_SSH_KEYS} is the maximum allowed; whereas, the `NodeRecord` with `chip_id`=\
+                 {} had {} elements \
+                 in this field.",
+                node_record
+                    .http
+                    .map(|http| format!("{http:?}"))
+                    .unwrap_or_else(|| "?-UNKNOWN-?".to_string()),
+                node_record.ssh_node_state_write_access.len()
+            ),
+            source: None,
+        });
     }
 
     Ok(())
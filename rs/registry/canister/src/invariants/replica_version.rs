@@ -1,9 +1,9 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::invariants::common::{
-    InvariantCheckError, RegistrySnapshot, assert_valid_urls_and_hash,
-    get_api_boundary_node_records_from_snapshot, get_subnet_ids_from_snapshot,
-    get_value_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, assert_valid_urls_and_hash,
+    check_valid_urls_and_hash, get_api_boundary_node_records_from_snapshot,
+    get_subnet_ids_from_snapshot, get_value_from_snapshot,
 };
 
 use ic_base_types::SubnetId;
@@ -13,8 +13,8 @@ use ic_protobuf::registry::{
     unassigned_nodes_config::v1::UnassignedNodesConfigRecord,
 };
 use ic_registry_keys::{
-    make_blessed_replica_versions_key, make_replica_version_key, make_subnet_record_key,
-    make_unassigned_nodes_config_record_key,
+    make_api_boundary_node_record_key, make_blessed_replica_versions_key, make_replica_version_key,
+    make_subnet_record_key, make_unassigned_nodes_config_record_key,
 };
 use prost::Message;
 
@@ -82,6 +82,106 @@ pub(crate) fn check_replica_version_invariants(
     Ok(())
 }
 
+/// Returns every violation of the replica version invariants.
+///
+/// Unlike `check_replica_version_invariants`, which panics at the first
+/// violation, this checks every version.
+pub(crate) fn replica_version_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = Vec::new();
+
+    let mut versions_in_use = get_keyed_replica_versions_of_subnets(snapshot);
+    let unassigned_nodes_config_key = make_unassigned_nodes_config_record_key();
+    let unassigned_version_id = snapshot
+        .get(unassigned_nodes_config_key.as_bytes())
+        .map(|bytes| {
+            let unassigned_nodes_config =
+                UnassignedNodesConfigRecord::decode(bytes.as_slice()).unwrap();
+            unassigned_nodes_config.replica_version
+        });
+    if let Some(version) = unassigned_version_id {
+        versions_in_use
+            .entry(version)
+            .or_default()
+            .insert(unassigned_nodes_config_key);
+    }
+    for (version, keys) in get_keyed_api_boundary_node_versions(snapshot) {
+        versions_in_use.entry(version).or_default().extend(keys);
+    }
+
+    let blessed_versions_key = make_blessed_replica_versions_key();
+    let blessed_version_ids = snapshot
+        .get(blessed_versions_key.as_bytes())
+        .map(|bytes| {
+            let version_list = BlessedReplicaVersions::decode(bytes.as_slice()).unwrap();
+            version_list.blessed_version_ids
+        })
+        .unwrap_or_default();
+
+    let num_blessed = blessed_version_ids.len();
+    let blessed_set = BTreeSet::from_iter(blessed_version_ids);
+    if blessed_set.len() != num_blessed {
+        let error = InvariantCheckError {
+            msg: "A version was blessed multiple times.".to_string(),
+            source: None,
+        };
+        errors.push(error.for_keys([blessed_versions_key.clone()]));
+    }
+    for (version, keys) in &versions_in_use {
+        if !blessed_set.contains(version) {
+            let error = InvariantCheckError {
+                msg: format!(
+                    "Using a version that isn't blessed. Blessed versions: {blessed_set:?}, in use: {:?}.",
+                    versions_in_use.keys().collect::<BTreeSet<_>>()
+                ),
+                source: None,
+            };
+            let keys = keys.iter().cloned().chain([blessed_versions_key.clone()]);
+            errors.push(error.for_keys(keys));
+        }
+    }
+    if blessed_set.iter().any(|v| v.trim().is_empty()) {
+        let error = InvariantCheckError {
+            msg: "Blessed an empty version ID.".to_string(),
+            source: None,
+        };
+        errors.push(error.for_keys([blessed_versions_key]));
+    }
+
+    // Check whether release package URLs (iso image) and corresponding hash is well-formed.
+    // As file-based URLs are only used in test-deployments, we disallow file:/// URLs.
+    for version in blessed_set {
+        if version.trim().is_empty() {
+            continue;
+        }
+        let key = make_replica_version_key(&version);
+        if let Err(error) = check_replica_version_record(snapshot, version) {
+            errors.push(error.for_keys([key]));
+        }
+    }
+
+    errors
+}
+
+fn check_replica_version_record(
+    snapshot: &RegistrySnapshot,
+    version: String,
+) -> Result<(), InvariantCheckError> {
+    let r: ReplicaVersionRecord =
+        get_value_from_snapshot(snapshot, make_replica_version_key(version.clone())).ok_or_else(
+            || InvariantCheckError {
+                msg: format!("Could not find replica version: {version}"),
+                source: None,
+            },
+        )?;
+    check_valid_urls_and_hash(
+        &r.release_package_urls,
+        &r.release_package_sha256_hex,
+        false, // allow_file_url
+    )
+}
+
 fn get_replica_version_record(
     snapshot: &RegistrySnapshot,
     version: String,
@@ -112,6 +212,43 @@ fn get_all_api_boundary_node_versions(snapshot: &RegistrySnapshot) -> BTreeSet<S
         .collect()
 }
 
+/// Returns the replica versions referred to by at least one subnet, together
+/// with the keys of the subnet records referring to them.
+///
+/// Subnets without a record are skipped, as the subnet invariants already
+/// reject them.
+fn get_keyed_replica_versions_of_subnets(
+    snapshot: &RegistrySnapshot,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let mut versions = BTreeMap::<String, BTreeSet<String>>::new();
+    for subnet_id in get_subnet_ids_from_snapshot(snapshot) {
+        let key = make_subnet_record_key(subnet_id);
+        if let Some(subnet_record) = get_value_from_snapshot::<SubnetRecord>(snapshot, key.clone())
+        {
+            versions
+                .entry(subnet_record.replica_version_id)
+                .or_default()
+                .insert(key);
+        }
+    }
+    versions
+}
+
+/// Returns the replica versions currently in use by the API boundary nodes,
+/// together with the keys of the API boundary node records using them.
+fn get_keyed_api_boundary_node_versions(
+    snapshot: &RegistrySnapshot,
+) -> BTreeMap<String, BTreeSet<String>> {
+    let mut versions = BTreeMap::<String, BTreeSet<String>>::new();
+    for (node_id, record) in get_api_boundary_node_records_from_snapshot(snapshot) {
+        versions
+            .entry(record.version)
+            .or_default()
+            .insert(make_api_boundary_node_record_key(node_id));
+    }
+    versions
+}
+
 #[cfg(test)]
 mod tests {
     use crate::common::test_helpers::invariant_compliant_registry;
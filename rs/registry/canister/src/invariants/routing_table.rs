@@ -1,13 +1,15 @@
-use crate::invariants::common::{InvariantCheckError, RegistrySnapshot};
+use crate::invariants::common::{InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot};
 
 use std::convert::TryFrom;
 
-use ic_base_types::CanisterId;
+use ic_base_types::{CanisterId, SubnetId};
 use ic_protobuf::registry::routing_table::v1::{
     CanisterMigrations as pbCanisterMigrations, RoutingTable as pbRoutingTable,
 };
-use ic_registry_keys::{make_canister_migrations_record_key, make_canister_ranges_key};
-use ic_registry_routing_table::{CanisterMigrations, RoutingTable};
+use ic_registry_keys::{
+    CANISTER_RANGES_PREFIX, make_canister_migrations_record_key, make_canister_ranges_key,
+};
+use ic_registry_routing_table::{CanisterIdRange, CanisterMigrations, RoutingTable};
 use prost::Message;
 
 /// Routing table invariants hold if reading and conversion succeed.
@@ -18,6 +20,23 @@ pub(crate) fn check_routing_table_invariants(
     Ok(())
 }
 
+/// Returns the violation of the routing table invariants, if any.
+pub(crate) fn routing_table_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    match try_get_routing_table(snapshot) {
+        Ok(_) => vec![],
+        Err(error) => {
+            let start = make_canister_ranges_key(CanisterId::from_u64(0)).into_bytes();
+            let end = make_canister_ranges_key(CanisterId::from_u64(u64::MAX)).into_bytes();
+            let keys = snapshot
+                .range(start..=end)
+                .map(|(key, _)| String::from_utf8_lossy(key).into_owned());
+            vec![error.for_keys(keys)]
+        }
+    }
+}
+
 // Return routing table from snapshot
 fn get_routing_table(snapshot: &RegistrySnapshot) -> RoutingTable {
     // If there are shards, they should match the routing table record.
@@ -25,6 +44,16 @@ fn get_routing_table(snapshot: &RegistrySnapshot) -> RoutingTable {
     RoutingTable::try_from(shards).unwrap()
 }
 
+/// Like `get_routing_table`, but returns an error instead of panicking if the
+/// routing table is not well formed.
+fn try_get_routing_table(snapshot: &RegistrySnapshot) -> Result<RoutingTable, InvariantCheckError> {
+    let shards = get_routing_table_shards(snapshot);
+    RoutingTable::try_from(shards).map_err(|e| InvariantCheckError {
+        msg: "Routing table is not well formed".to_string(),
+        source: Some(Box::new(e)),
+    })
+}
+
 fn get_routing_table_shards(snapshot: &RegistrySnapshot) -> Vec<pbRoutingTable> {
     let start = make_canister_ranges_key(CanisterId::from_u64(0)).into_bytes();
     let end = make_canister_ranges_key(CanisterId::from_u64(u64::MAX)).into_bytes();
@@ -50,37 +79,95 @@ pub(crate) fn check_canister_migrations_invariants(
         let canister_migrations = CanisterMigrations::try_from(canister_migrations_proto).unwrap();
 
         let routing_table = get_routing_table(snapshot);
-        // Check if each canister range is assigned to one of the subnets on the migration trace.
-        // The subnet could be either the source before the migration or the destination after migration.
         for (canister_migrations_range, trace) in canister_migrations.iter() {
-            match routing_table.lookup_entry(canister_migrations_range.start) {
-                Some((routing_table_range, subnet_id)) => {
-                    // The assertion should always hold otherwise `lookup_entry` won't return such an entry.
-                    assert!(canister_migrations_range.start >= routing_table_range.start);
-                    // The assigned subnet should be on the trace.
-                    // The `canister_migrations_range` should be fully contained within the `routing_table_range`.
-                    if !trace.contains(&subnet_id)
-                        || canister_migrations_range.end > routing_table_range.end
-                    {
-                        return Err(InvariantCheckError {
-                            msg: format!(
-                                "canister ID range {canister_migrations_range:?} in `canister_migrations` is not hosted by any subnet in trace {trace:?}."
-                            ),
-                            source: None,
-                        });
-                    }
-                }
-                None => {
-                    return Err(InvariantCheckError {
-                        msg: format!(
-                            "canister ID range {canister_migrations_range:?} in `canister_migrations` is not hosted by any subnet in trace {trace:?}."
-                        ),
-                        source: None,
-                    });
-                }
+            check_canister_migration_is_hosted(&routing_table, canister_migrations_range, trace)?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns every violation of the canister migrations invariants.
+///
+/// A routing table that is not well formed violates the routing table
+/// invariants, so canister migrations are not checked against it.
+pub(crate) fn canister_migrations_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    let canister_migrations_key = make_canister_migrations_record_key();
+    let Some(canister_migrations_bytes) = snapshot.get(canister_migrations_key.as_bytes()) else {
+        return vec![];
+    };
+
+    // Check if canister migrations are well formed.
+    let canister_migrations_proto =
+        pbCanisterMigrations::decode(canister_migrations_bytes.as_slice()).unwrap();
+    let canister_migrations = match CanisterMigrations::try_from(canister_migrations_proto) {
+        Ok(canister_migrations) => canister_migrations,
+        Err(e) => {
+            let error = InvariantCheckError {
+                msg: "Canister migrations are not well formed".to_string(),
+                source: Some(Box::new(e)),
             };
+            return vec![error.for_keys([canister_migrations_key])];
+        }
+    };
+
+    let Ok(routing_table) = try_get_routing_table(snapshot) else {
+        return vec![];
+    };
+    let mut errors = Vec::new();
+    for (canister_migrations_range, trace) in canister_migrations.iter() {
+        if let Err(error) =
+            check_canister_migration_is_hosted(&routing_table, canister_migrations_range, trace)
+        {
+            // The routing table shard that would host the start of the range.
+            let shard_key = snapshot
+                .range(..=make_canister_ranges_key(canister_migrations_range.start).into_bytes())
+                .next_back()
+                .map(|(key, _)| String::from_utf8_lossy(key).into_owned())
+                .filter(|key| key.starts_with(CANISTER_RANGES_PREFIX));
+            let keys = [canister_migrations_key.clone()]
+                .into_iter()
+                .chain(shard_key);
+            errors.push(error.for_keys(keys));
         }
     }
+    errors
+}
+
+/// Checks that the canister range is assigned to one of the subnets on its migration trace.
+/// The subnet could be either the source before the migration or the destination after migration.
+fn check_canister_migration_is_hosted(
+    routing_table: &RoutingTable,
+    canister_migrations_range: &CanisterIdRange,
+    trace: &[SubnetId],
+) -> Result<(), InvariantCheckError> {
+    match routing_table.lookup_entry(canister_migrations_range.start) {
+        Some((routing_table_range, subnet_id)) => {
+            // The assertion should always hold otherwise `lookup_entry` won't return such an entry.
+            assert!(canister_migrations_range.start >= routing_table_range.start);
+            // The assigned subnet should be on the trace.
+            // The `canister_migrations_range` should be fully contained within the `routing_table_range`.
+            if !trace.contains(&subnet_id)
+                || canister_migrations_range.end > routing_table_range.end
+            {
+                return Err(InvariantCheckError {
+                    msg: format!(
+                        "canister ID range {canister_migrations_range:?} in `canister_migrations` is not hosted by any subnet in trace {trace:?}."
+                    ),
+                    source: None,
+                });
+            }
+        }
+        None => {
+            return Err(InvariantCheckError {
+                msg: format!(
+                    "canister ID range {canister_migrations_range:?} in `canister_migrations` is not hosted by any subnet in trace {trace:?}."
+                ),
+                source: None,
+            });
+        }
+    };
     Ok(())
 }
 
@@ -1,16 +1,19 @@
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     convert::TryFrom,
 };
 
 use crate::invariants::common::{
-    InvariantCheckError, RegistrySnapshot, get_subnet_ids_from_snapshot,
+    InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, get_subnet_ids_from_snapshot,
 };
 
 use ic_base_types::{NodeId, PrincipalId};
 use ic_nns_common::registry::MAX_NUM_SSH_KEYS;
 use ic_protobuf::registry::subnet::v1::{SubnetRecord, SubnetType};
-use ic_registry_keys::{SUBNET_RECORD_KEY_PREFIX, make_node_record_key, make_subnet_record_key};
+use ic_registry_keys::{
+    SUBNET_RECORD_KEY_PREFIX, make_node_record_key, make_subnet_list_record_key,
+    make_subnet_record_key,
+};
 use prost::Message;
 
 /// Subnet invariants hold iff:
@@ -116,6 +119,144 @@ pub(crate) fn check_subnet_invariants(
     Ok(())
 }
 
+/// Returns every violation of the subnet invariants.
+///
+/// Unlike `check_subnet_invariants`, which stops at the first violation,
+/// this checks every subnet.
+pub(crate) fn subnet_violations(snapshot: &RegistrySnapshot) -> Vec<KeyedInvariantCheckError> {
+    let mut errors = Vec::new();
+    // The nodes of all subnets checked so far, and the key of the subnet record
+    // they belong to
+    let mut accumulated_nodes_in_subnets: BTreeMap<NodeId, String> = BTreeMap::new();
+    let mut system_subnet_count = 0;
+    let mut subnet_records_map = get_subnet_records_map(snapshot);
+    let subnet_id_list = get_subnet_ids_from_snapshot(snapshot);
+    for subnet_id in subnet_id_list {
+        let subnet_key = make_subnet_record_key(subnet_id);
+        // Subnets in the subnet list have a subnet record
+        let Some(subnet_record) = subnet_records_map.remove(subnet_key.as_bytes()) else {
+            let error = InvariantCheckError {
+                msg: format!("Subnet {subnet_id:} is in subnet list but no record exists"),
+                source: None,
+            };
+            errors.push(error.for_keys([make_subnet_list_record_key(), subnet_key]));
+            continue;
+        };
+
+        // Count occurrence of system subnets
+        if subnet_record.subnet_type == i32::from(SubnetType::System) {
+            system_subnet_count += 1;
+        }
+
+        if subnet_record.ssh_readonly_access.len() > MAX_NUM_SSH_KEYS
+            || subnet_record.ssh_backup_access.len() > MAX_NUM_SSH_KEYS
+        {
+            let error = InvariantCheckError {
+                msg: format!(
+                    "Mutation would have resulted in an SSH key access list that is too long, \
+                    the maximum allowable length is {}, and the `readonly` and `backup` lists had \
+                    {} and {} keys, respectively",
+                    MAX_NUM_SSH_KEYS,
+                    subnet_record.ssh_readonly_access.len(),
+                    subnet_record.ssh_backup_access.len()
+                ),
+                source: None,
+            };
+            errors.push(error.for_keys([subnet_key.clone()]));
+        }
+
+        let num_nodes = subnet_record.membership.len();
+        let mut subnet_members: HashSet<NodeId> = subnet_record
+            .membership
+            .iter()
+            .map(|v| NodeId::from(PrincipalId::try_from(v).unwrap()))
+            .collect();
+
+        // Subnet membership must contain registered nodes only
+        let missing_members: BTreeSet<NodeId> = subnet_members
+            .iter()
+            .copied()
+            .filter(|&k| !snapshot.contains_key(make_node_record_key(k).as_bytes()))
+            .collect();
+        for &k in &missing_members {
+            let error = InvariantCheckError {
+                msg: format!("Node {k} does not exist in Subnet {subnet_id}"),
+                source: None,
+            };
+            errors.push(error.for_keys([subnet_key.clone(), make_node_record_key(k)]));
+        }
+        if !missing_members.is_empty() {
+            continue;
+        }
+
+        // Each node appears at most once in a subnet membership
+        if num_nodes > subnet_members.len() {
+            let error = InvariantCheckError {
+                msg: format!("Repeated nodes in subnet {subnet_id:}"),
+                source: None,
+            };
+            errors.push(error.for_keys([subnet_key]));
+            continue;
+        }
+        // Each subnet contains at least one node
+        if subnet_members.is_empty() {
+            let error = InvariantCheckError {
+                msg: format!("No node in subnet {subnet_id:}"),
+                source: None,
+            };
+            errors.push(error.for_keys([subnet_key]));
+            continue;
+        }
+        let intersection: BTreeSet<NodeId> = subnet_members
+            .iter()
+            .copied()
+            .filter(|node_id| accumulated_nodes_in_subnets.contains_key(node_id))
+            .collect();
+        // Each node appears at most once in at most one subnet membership
+        if !intersection.is_empty() {
+            let error = InvariantCheckError {
+                msg: format!("Nodes in subnet {subnet_id:} also belong to other subnets"),
+                source: None,
+            };
+            let mut keys = BTreeSet::from([subnet_key.clone()]);
+            for node_id in &intersection {
+                keys.insert(make_node_record_key(*node_id));
+                keys.insert(accumulated_nodes_in_subnets[node_id].clone());
+            }
+            errors.push(error.for_keys(keys));
+            subnet_members.retain(|node_id| !intersection.contains(node_id));
+        }
+        accumulated_nodes_in_subnets.extend(
+            subnet_members
+                .into_iter()
+                .map(|node_id| (node_id, subnet_key.clone())),
+        );
+    }
+    // There is at least one system subnet. Note that we disable this invariant for benchmarks, as
+    // the code to set up "invariants compliant" registry mostly depends on "test-only" code, and
+    // it's very difficult to conform canbench benchmarks to test-only code. It's also risky to move
+    // those "test-only" code towards "non-test-only" code.
+    if system_subnet_count < 1 && !cfg!(feature = "canbench-rs") {
+        let error = InvariantCheckError {
+            msg: "no system subnet".to_string(),
+            source: None,
+        };
+        errors.push(error.for_keys([make_subnet_list_record_key()]));
+    }
+    // TODO (OR1-22): uncomment the following when NNS subnet recovery
+    // has fully been implemented which guarantees that no unnecessary
+    // subnet records are in the registry.
+    // All subnet records have been listed
+    // if !subnet_records_map.is_empty() {
+    //    panic!(
+    //        "Subnets {:?} has not been listed in the snapshot",
+    //       subnet_records_map.keys()
+    //    );
+    //}
+
+    errors
+}
+
 // Return all subnet records in the snapshot
 pub(crate) fn get_subnet_records_map(
     snapshot: &RegistrySnapshot,
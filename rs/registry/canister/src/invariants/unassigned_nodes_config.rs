@@ -1,6 +1,8 @@
 use crate::{
     common::LOG_PREFIX,
-    invariants::common::{InvariantCheckError, RegistrySnapshot, get_value_from_snapshot},
+    invariants::common::{
+        InvariantCheckError, KeyedInvariantCheckError, RegistrySnapshot, get_value_from_snapshot,
+    },
 };
 
 #[cfg(target_arch = "wasm32")]
@@ -35,3 +37,14 @@ pub(crate) fn check_unassigned_nodes_config_invariants(
 
     Ok(())
 }
+
+/// Returns the violation of the unassigned nodes config invariants, if any.
+pub(crate) fn unassigned_nodes_config_violations(
+    snapshot: &RegistrySnapshot,
+) -> Vec<KeyedInvariantCheckError> {
+    check_unassigned_nodes_config_invariants(snapshot)
+        .err()
+        .map(|error| error.for_keys([make_unassigned_nodes_config_record_key()]))
+        .into_iter()
+        .collect()
+}
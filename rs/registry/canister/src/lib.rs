@@ -5,6 +5,7 @@ pub mod flags;
 pub mod get_node_operators_and_dcs_of_node_provider;
 pub mod get_node_providers_monthly_xdr_rewards;
 pub mod init;
+pub mod invariants;
 pub mod mutations;
 pub mod pb;
 pub mod proto_on_wire;
@@ -12,6 +13,5 @@ pub mod registry;
 pub mod registry_lifecycle;
 pub mod storage;
 
-mod invariants;
 mod max_rewardable_nodes_mapping;
 mod rate_limits;
@@ -1,7 +1,8 @@
 use crate::registry::Registry;
 use ic_base_types::{NodeId, PrincipalId, SubnetId};
 use ic_protobuf::registry::{
-    replica_version::v1::BlessedReplicaVersions, subnet::v1::SubnetListRecord,
+    replica_version::v1::BlessedReplicaVersions,
+    subnet::v1::{AdaptiveBlockTimeConfig, SubnetListRecord},
     unassigned_nodes_config::v1::UnassignedNodesConfigRecord,
 };
 use ic_registry_keys::{
@@ -121,9 +122,30 @@ where
     s.len() < v.len()
 }
 
+/// Validates that the notary delay bounds of an adaptive block time config are
+/// non-zero and that the minimum does not exceed the maximum.
+pub(crate) fn validate_adaptive_block_time_config(
+    config: &AdaptiveBlockTimeConfig,
+) -> Result<(), String> {
+    if config.min_notary_delay_millis == 0 {
+        return Err(
+            "AdaptiveBlockTimeConfig.min_notary_delay_millis must be positive.".to_string(),
+        );
+    }
+    if config.min_notary_delay_millis > config.max_notary_delay_millis {
+        return Err(format!(
+            "AdaptiveBlockTimeConfig.min_notary_delay_millis ({}) must not exceed \
+             max_notary_delay_millis ({}).",
+            config.min_notary_delay_millis, config.max_notary_delay_millis
+        ));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 pub(crate) mod test {
-    use crate::mutations::common::check_ipv6_format;
+    use crate::mutations::common::{check_ipv6_format, validate_adaptive_block_time_config};
+    use ic_protobuf::registry::subnet::v1::AdaptiveBlockTimeConfig;
 
     pub(crate) const TEST_NODE_ID: &str = "2vxsx-fae";
 
@@ -141,4 +163,16 @@ pub(crate) mod test {
         assert!(check_ipv6_format("0:0:0:0:0:0:0:0"));
         assert!(check_ipv6_format("123:221:4567:323:4123:2111:7:7"));
     }
+
+    #[test]
+    fn test_validate_adaptive_block_time_config() {
+        let config = |min_notary_delay_millis, max_notary_delay_millis| AdaptiveBlockTimeConfig {
+            min_notary_delay_millis,
+            max_notary_delay_millis,
+        };
+        assert!(validate_adaptive_block_time_config(&config(300, 2_000)).is_ok());
+        assert!(validate_adaptive_block_time_config(&config(300, 300)).is_ok());
+        assert!(validate_adaptive_block_time_config(&config(0, 2_000)).is_err());
+        assert!(validate_adaptive_block_time_config(&config(2_000, 300)).is_err());
+    }
 }
@@ -1,5 +1,7 @@
 use crate::chain_key::{InitialChainKeyConfigInternal, KeyConfigRequestInternal};
-use crate::{common::LOG_PREFIX, registry::Registry};
+use crate::{
+    common::LOG_PREFIX, mutations::common::validate_adaptive_block_time_config, registry::Registry,
+};
 use candid::{CandidType, Deserialize, Encode};
 use dfn_core::api::{CanisterId, call};
 #[cfg(target_arch = "wasm32")]
@@ -11,6 +13,7 @@ use ic_management_canister_types_private::{
 use ic_protobuf::registry::{
     node::v1::NodeRecord,
     subnet::v1::{
+        AdaptiveBlockTimeConfig as AdaptiveBlockTimeConfigPb,
         CanisterCyclesCostSchedule as CanisterCyclesCostSchedulePb, CatchUpPackageContents,
         ChainKeyConfig as ChainKeyConfigPb, SubnetFeatures as SubnetFeaturesPb, SubnetRecord,
     },
@@ -176,6 +179,12 @@ impl Registry {
     /// Ensures that a valid `subnet_id` is specified for `KeyConfigRequest`s.
     /// Ensures that master public keys (a) exist and (b) are present on the requested subnet.
     fn validate_create_subnet_payload(&self, payload: &CreateSubnetPayload) {
+        if let Some(adaptive_block_time_config) = &payload.adaptive_block_time_config {
+            validate_adaptive_block_time_config(adaptive_block_time_config).unwrap_or_else(|err| {
+                panic!("{LOG_PREFIX}Invalid CreateSubnetPayload.adaptive_block_time_config: {err}")
+            });
+        }
+
         // Verify that all Nodes exist
         payload.node_ids.iter().for_each(|node_id| {
             match self.get(
@@ -284,6 +293,10 @@ pub struct CreateSubnetPayload {
     /// preferred over None though, because explicit is better than implicit.
     pub canister_cycles_cost_schedule: Option<CanisterCyclesCostSchedule>,
 
+    /// If set, the subnet uses an adaptive block time within these bounds
+    /// instead of the fixed `initial_notary_delay_millis`.
+    pub adaptive_block_time_config: Option<AdaptiveBlockTimeConfigPb>,
+
     // TODO(NNS1-2444): The fields below are deprecated and they are not read anywhere.
     pub ingress_bytes_per_block_soft_cap: u64,
     pub gossip_max_artifact_streams_per_peer: u32,
@@ -543,6 +556,7 @@ impl From<CreateSubnetPayload> for SubnetRecord {
                 .map(CanisterCyclesCostSchedulePb::from)
                 .unwrap_or(CanisterCyclesCostSchedulePb::Normal)
                 as i32,
+            adaptive_block_time_config: val.adaptive_block_time_config,
         }
     }
 }
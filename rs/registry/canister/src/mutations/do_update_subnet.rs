@@ -1,10 +1,15 @@
-use crate::{common::LOG_PREFIX, mutations::common::has_duplicates, registry::Registry};
+use crate::{
+    common::LOG_PREFIX,
+    mutations::common::{has_duplicates, validate_adaptive_block_time_config},
+    registry::Registry,
+};
 use candid::{CandidType, Deserialize};
 use dfn_core::println;
 use ic_base_types::{SubnetId, subnet_id_into_protobuf};
 use ic_management_canister_types_private::MasterPublicKeyId;
 use ic_protobuf::registry::subnet::v1::{
-    SubnetFeatures as SubnetFeaturesPb, SubnetRecord as SubnetRecordPb,
+    AdaptiveBlockTimeConfig as AdaptiveBlockTimeConfigPb, SubnetFeatures as SubnetFeaturesPb,
+    SubnetRecord as SubnetRecordPb,
 };
 use ic_registry_keys::{make_chain_key_enabled_subnet_list_key, make_subnet_record_key};
 use ic_registry_subnet_features::{
@@ -26,6 +31,11 @@ impl Registry {
 
         self.validate_update_payload_chain_key_config(&payload);
         self.validate_update_sev_feature(&payload);
+        if let Some(adaptive_block_time_config) = &payload.adaptive_block_time_config {
+            validate_adaptive_block_time_config(adaptive_block_time_config).unwrap_or_else(|err| {
+                panic!("{LOG_PREFIX}Invalid UpdateSubnetPayload.adaptive_block_time_config: {err}")
+            });
+        }
 
         let subnet_id = payload.subnet_id;
 
@@ -240,6 +250,10 @@ pub struct UpdateSubnetPayload {
     pub ssh_readonly_access: Option<Vec<String>>,
     pub ssh_backup_access: Option<Vec<String>>,
 
+    /// Enables (or changes the bounds of) adaptive block time. Setting both
+    /// bounds to the same value yields a fixed notary delay.
+    pub adaptive_block_time_config: Option<AdaptiveBlockTimeConfigPb>,
+
     // TODO(NNS1-2444): The fields below are deprecated and they are not read anywhere.
     pub max_artifact_streams_per_peer: Option<u32>,
     pub max_chunk_wait_ms: Option<u32>,
@@ -435,6 +449,7 @@ fn merge_subnet_record(
         max_number_of_canisters,
         ssh_readonly_access,
         ssh_backup_access,
+        adaptive_block_time_config,
         // Deprecated/unused values follow
         max_artifact_streams_per_peer: _,
         max_chunk_wait_ms: _,
@@ -480,6 +495,8 @@ fn merge_subnet_record(
     maybe_set!(subnet_record, ssh_readonly_access);
     maybe_set!(subnet_record, ssh_backup_access);
 
+    maybe_set_option!(subnet_record, adaptive_block_time_config);
+
     subnet_record
 }
 
@@ -524,6 +541,7 @@ mod tests {
             max_number_of_canisters: None,
             ssh_readonly_access: None,
             ssh_backup_access: None,
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -562,6 +580,7 @@ mod tests {
             ssh_backup_access: vec![],
             chain_key_config: None,
             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+            adaptive_block_time_config: None,
         };
 
         let key_id = EcdsaKeyId {
@@ -608,6 +627,7 @@ mod tests {
             max_number_of_canisters: Some(10),
             ssh_readonly_access: Some(vec!["pub_key_0".to_string()]),
             ssh_backup_access: Some(vec!["pub_key_1".to_string()]),
+            adaptive_block_time_config: None,
             chain_key_config: Some(chain_key_config.clone()),
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -654,6 +674,7 @@ mod tests {
                 ssh_readonly_access: vec!["pub_key_0".to_string()],
                 ssh_backup_access: vec!["pub_key_1".to_string()],
                 canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+                adaptive_block_time_config: None,
             }
         );
     }
@@ -680,6 +701,7 @@ mod tests {
             ssh_backup_access: vec![],
             chain_key_config: None,
             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+            adaptive_block_time_config: None,
         };
 
         let payload = UpdateSubnetPayload {
@@ -704,6 +726,7 @@ mod tests {
             max_number_of_canisters: Some(50),
             ssh_readonly_access: None,
             ssh_backup_access: None,
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -740,6 +763,7 @@ mod tests {
                 ssh_backup_access: vec![],
                 chain_key_config: None,
                 canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+                adaptive_block_time_config: None,
             }
         );
     }
@@ -853,6 +877,36 @@ mod tests {
         registry.do_update_subnet(payload);
     }
 
+    #[test]
+    #[should_panic(expected = "Invalid UpdateSubnetPayload.adaptive_block_time_config: \
+        AdaptiveBlockTimeConfig.min_notary_delay_millis (2000) must not exceed \
+        max_notary_delay_millis (300).")]
+    fn test_disallow_inverted_adaptive_block_time_bounds() {
+        let mut registry = invariant_compliant_registry(0);
+        let (mutate_request, node_ids_and_dkg_pks) = prepare_registry_with_nodes(1, 1);
+        registry.maybe_apply_mutation_internal(mutate_request.mutations);
+        let mut subnet_list_record = registry.get_subnet_list_record();
+        let (node_id, dkg_pk) = node_ids_and_dkg_pks
+            .iter()
+            .next()
+            .expect("should contain at least one node ID and key");
+        let subnet_id = subnet_test_id(1000);
+        registry.maybe_apply_mutation_internal(add_fake_subnet(
+            subnet_id,
+            &mut subnet_list_record,
+            get_invariant_compliant_subnet_record(vec![*node_id]),
+            &btreemap!(*node_id => dkg_pk.clone()),
+        ));
+
+        let mut payload = make_empty_update_payload(subnet_id);
+        payload.adaptive_block_time_config = Some(AdaptiveBlockTimeConfigPb {
+            min_notary_delay_millis: 2_000,
+            max_notary_delay_millis: 300,
+        });
+
+        registry.do_update_subnet(payload);
+    }
+
     #[test]
     #[should_panic(
         expected = "[Registry] Chain key with id 'ecdsa:Secp256k1:existing_key_id' already exists. \
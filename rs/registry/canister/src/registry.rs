@@ -21,7 +21,7 @@ use ic_registry_transport::{
 use ic_types::messages::MAX_INTER_CANISTER_PAYLOAD_IN_BYTES_U64;
 use prost::Message;
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     fmt,
 };
 
@@ -95,6 +95,19 @@ pub struct Registry {
     /// retained to ensure that hash trees stay the same even if the protobuf
     /// schema evolves.
     pub(crate) changelog: RbTree<EncodedVersion, Vec<u8>>,
+
+    /// For every key, the versions at which it was mutated.
+    ///
+    /// This index is derived from the changelog and certified alongside it,
+    /// so that responses to `get_certified_key_history` can prove that they
+    /// contain all the versions at which the requested key(s) were mutated.
+    /// Values are empty.
+    pub(crate) key_history: RbTree<Vec<u8>, RbTree<EncodedVersion, Vec<u8>>>,
+
+    /// If set, mutations are applied without checking the global state
+    /// invariants. Never set in the canister, see
+    /// [`Self::disable_global_state_invariant_checks`].
+    skip_global_state_invariant_checks: bool,
 }
 
 impl Registry {
@@ -102,6 +115,16 @@ impl Registry {
         Self::default()
     }
 
+    /// Makes the registry apply mutations without checking the global state
+    /// invariants.
+    ///
+    /// This is meant for offline tools that apply proposals to a copy of a
+    /// registry that may already violate invariants, and that check the
+    /// invariants themselves, e.g., to only report new violations.
+    pub fn disable_global_state_invariant_checks(&mut self) {
+        self.skip_global_state_invariant_checks = true;
+    }
+
     /// Returns the deltas applied since `version`, exclusive; optionally
     /// limited to the subsequent `max_versions` (i.e. changes applied in
     /// versions `(version, version + max_versions]`).
@@ -195,6 +218,51 @@ impl Registry {
             .count()
     }
 
+    /// Returns, in ascending order, the versions in `[from_version,
+    /// to_version]` at which `key` was mutated, or, if `key_is_prefix`, at
+    /// which any key starting with `key` was mutated.
+    pub fn get_key_history_versions(
+        &self,
+        key: &[u8],
+        key_is_prefix: bool,
+        from_version: Version,
+        to_version: Version,
+    ) -> Vec<Version> {
+        self.store
+            .range(key.to_vec()..)
+            .take_while(|(k, _)| {
+                if key_is_prefix {
+                    k.starts_with(key)
+                } else {
+                    k.as_slice() == key
+                }
+            })
+            .flat_map(|(_, values)| values)
+            .map(|value| value.version)
+            .filter(|version| (from_version..=to_version).contains(version))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Computes the number of leading `versions` whose deltas fit into the
+    /// specified byte limit.
+    ///
+    /// Like [`Self::count_fitting_deltas`], but for a (possibly sparse) list
+    /// of versions.
+    pub fn count_fitting_versions(&self, versions: &[Version], max_bytes: usize) -> usize {
+        versions
+            .iter()
+            .scan(0, |size, version| {
+                let key = EncodedVersion::from(*version);
+                let delta_size = self.changelog().get(key.as_ref()).map_or(0, Vec::len);
+                *size += delta_size + key.as_ref().len();
+                Some(*size)
+            })
+            .take_while(|size| *size <= max_bytes)
+            .count()
+    }
+
     pub(crate) fn get(&self, key: &[u8], version: Version) -> Option<RegistryValue> {
         let HighCapacityRegistryValue {
             version,
@@ -296,6 +364,7 @@ impl Registry {
                 timestamp_nanoseconds,
             };
 
+            self.key_history_insert(&key, version);
             self.store.entry(key).or_default().push_back(registry_value);
         }
 
@@ -387,7 +456,9 @@ impl Registry {
             );
         }
 
-        self.check_global_state_invariants(mutations.as_slice());
+        if !self.skip_global_state_invariant_checks {
+            self.check_global_state_invariants(mutations.as_slice());
+        }
     }
 
     pub fn serializable_form(&self) -> RegistryStableStorage {
@@ -408,6 +479,20 @@ impl Registry {
         &self.changelog
     }
 
+    pub fn key_history(&self) -> &RbTree<Vec<u8>, RbTree<EncodedVersion, Vec<u8>>> {
+        &self.key_history
+    }
+
+    /// Records in the key history index that `key` was mutated at `version`.
+    fn key_history_insert(&mut self, key: &[u8], version: Version) {
+        if self.key_history.get(key).is_none() {
+            self.key_history.insert(key.to_vec(), RbTree::new());
+        }
+        self.key_history.modify(key, |versions| {
+            versions.insert(EncodedVersion::from(version), vec![]);
+        });
+    }
+
     /// Inserts a changelog entry at the given version, while enforcing the
     /// [`MAX_REGISTRY_DELTAS_SIZE`] limit.
     fn changelog_insert(&mut self, version: u64, req: HighCapacityRegistryAtomicMutateRequest) {
@@ -443,6 +528,7 @@ impl Registry {
     pub fn from_serializable_form(&mut self, stable_repr: RegistryStableStorage) {
         assert!(self.store.is_empty());
         assert!(self.changelog.is_empty());
+        assert!(self.key_history.is_empty());
         assert_eq!(self.version, 0);
 
         let repr_version = ReprVersion::try_from(stable_repr.version).unwrap_or_else(|_| {
@@ -716,6 +802,102 @@ mod tests {
         serialize_then_deserialize(registry);
     }
 
+    #[test]
+    fn test_get_key_history_versions() {
+        let mut registry = Registry::new();
+        let key1 = b"node_1".to_vec();
+        let key2 = b"node_2".to_vec();
+        let other_key = b"subnet_1".to_vec();
+        let value = vec![1, 2, 3];
+        // @1: key1 and other_key inserted
+        // @2: key2 inserted
+        // @3: other_key updated
+        // @4: key1 deleted, key2 updated
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![insert(&key1, &value), insert(&other_key, &value)]
+        ));
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![insert(&key2, &value)]
+        ));
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![update(&other_key, &value)]
+        ));
+        assert_empty!(apply_mutations_skip_invariant_checks(
+            &mut registry,
+            vec![delete(&key1), update(&key2, &value)]
+        ));
+
+        assert_eq!(
+            registry.get_key_history_versions(&key1, false, 1, 4),
+            vec![1, 4]
+        );
+        assert_eq!(
+            registry.get_key_history_versions(&key2, false, 1, 4),
+            vec![2, 4]
+        );
+        assert_eq!(
+            registry.get_key_history_versions(b"node_", true, 1, 4),
+            vec![1, 2, 4]
+        );
+        assert_eq!(
+            registry.get_key_history_versions(b"node_", true, 2, 3),
+            vec![2]
+        );
+        // Without key_is_prefix, the prefix is treated as an exact key.
+        assert_empty!(registry.get_key_history_versions(b"node_", false, 1, 4));
+        assert_eq!(
+            registry.get_key_history_versions(b"", true, 1, 4),
+            vec![1, 2, 3, 4]
+        );
+
+        // The certified key history index agrees.
+        let indexed_versions = |key: &[u8]| -> Vec<Version> {
+            registry
+                .key_history()
+                .get(key)
+                .unwrap()
+                .iter()
+                .map(|(version, _)| version.as_version())
+                .collect()
+        };
+        assert_eq!(indexed_versions(&key1), vec![1, 4]);
+        assert_eq!(indexed_versions(&key2), vec![2, 4]);
+        assert!(registry.key_history().get(b"node_").is_none());
+    }
+
+    #[test]
+    fn test_count_fitting_versions() {
+        let mut registry = Registry::new();
+        for i in 0..3_u8 {
+            assert_empty!(apply_mutations_skip_invariant_checks(
+                &mut registry,
+                vec![insert([i], [i])]
+            ));
+        }
+        let delta_size = |version: Version| {
+            registry
+                .changelog()
+                .get(EncodedVersion::from(version).as_ref())
+                .unwrap()
+                .len()
+                + std::mem::size_of::<Version>()
+        };
+
+        assert_eq!(registry.count_fitting_versions(&[1, 3], usize::MAX), 2);
+        assert_eq!(
+            registry.count_fitting_versions(&[1, 3], delta_size(1) + delta_size(3)),
+            2
+        );
+        assert_eq!(
+            registry.count_fitting_versions(&[1, 3], delta_size(1) + delta_size(3) - 1),
+            1
+        );
+        assert_eq!(registry.count_fitting_versions(&[1, 3], 0), 0);
+    }
+
     #[test]
     fn test_insert() {
         let mut registry = Registry::new();
@@ -1331,6 +1513,7 @@ mod tests {
             content: Some(content),
             timestamp_nanoseconds: req.timestamp_nanoseconds,
         });
+        registry.key_history_insert(key, version);
         registry.version = version;
 
         // Serialize.
@@ -422,6 +422,7 @@ fn make_create_subnet_payload(node_ids: Vec<NodeId>) -> CreateSubnetPayload {
         max_number_of_canisters: 0,
         ssh_readonly_access: vec![],
         ssh_backup_access: vec![],
+        adaptive_block_time_config: None,
         chain_key_config: None,
         canister_cycles_cost_schedule: Some(CanisterCyclesCostSchedule::Normal),
 
@@ -73,6 +73,7 @@ fn test_the_anonymous_user_cannot_update_a_subnets_configuration() {
             max_number_of_canisters: Some(10),
             ssh_readonly_access: Some(vec!["pub_key_0".to_string()]),
             ssh_backup_access: Some(vec!["pub_key_1".to_string()]),
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -153,6 +154,7 @@ fn test_a_canister_other_than_the_governance_canister_cannot_update_a_subnets_co
             ssh_backup_access: vec![],
             chain_key_config: None,
             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+            adaptive_block_time_config: None,
         };
 
         // An attacker got a canister that is trying to pass for the governance
@@ -197,6 +199,7 @@ fn test_a_canister_other_than_the_governance_canister_cannot_update_a_subnets_co
             max_number_of_canisters: Some(100),
             ssh_readonly_access: None,
             ssh_backup_access: None,
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -277,6 +280,7 @@ fn test_the_governance_canister_can_update_a_subnets_configuration() {
                             chain_key_config: None,
                             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal
                                 as i32,
+                            adaptive_block_time_config: None,
                         }
                         .encode_to_vec(),
                     )],
@@ -313,6 +317,7 @@ fn test_the_governance_canister_can_update_a_subnets_configuration() {
             max_number_of_canisters: Some(42),
             ssh_readonly_access: Some(vec!["pub_key_0".to_string()]),
             ssh_backup_access: Some(vec!["pub_key_1".to_string()]),
+            adaptive_block_time_config: None,
             chain_key_config: None,
             chain_key_signing_enable: None,
             chain_key_signing_disable: None,
@@ -368,6 +373,7 @@ fn test_the_governance_canister_can_update_a_subnets_configuration() {
                 ssh_backup_access: vec!["pub_key_1".to_string()],
                 chain_key_config: None,
                 canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+                adaptive_block_time_config: None,
             }
         );
 
@@ -451,6 +457,7 @@ fn test_subnets_configuration_chain_key_fields_are_updated_correctly(key_id: Mas
             ssh_backup_access: vec![],
             chain_key_config: None,
             canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+            adaptive_block_time_config: None,
         };
 
         // Just create the registry canister and wait until the subnet_handler ID is
@@ -664,6 +671,7 @@ fn empty_update_subnet_payload(subnet_id: SubnetId) -> UpdateSubnetPayload {
         max_number_of_canisters: None,
         ssh_readonly_access: None,
         ssh_backup_access: None,
+        adaptive_block_time_config: None,
         chain_key_config: None,
         chain_key_signing_enable: None,
         chain_key_signing_disable: None,
@@ -1,6 +1,7 @@
 //! Implementation of the registry client. Calls to the API always return
 //! immediately. The provided data provider is polled periodically in the
-//! background when start_polling() is called.
+//! background when start_polling() is called. Components can watch keys to be
+//! notified about the changes fetched by polling, see [`crate::watch`].
 use crossbeam_channel::{RecvTimeoutError, Sender, TrySendError};
 pub use ic_interfaces_registry::{
     POLLING_PERIOD, RegistryClient, RegistryClientVersionedResult, RegistryDataProvider,
@@ -14,10 +15,11 @@ pub use ic_types::{
     time::current_time,
 };
 use ic_utils_thread::JoinOnDrop;
-use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
 use std::{collections::BTreeMap, thread::JoinHandle};
 
 use crate::metrics::Metrics;
+use crate::watch::{PrefixWatcher, RegistryWatch, Watcher};
 
 #[derive(Clone)]
 pub struct RegistryClientImpl {
@@ -25,6 +27,9 @@ pub struct RegistryClientImpl {
     data_provider: Arc<dyn RegistryDataProvider>,
     metrics: Arc<Metrics>,
     poll_thread: Arc<RwLock<Option<PollThread>>>,
+    /// Held while updating the cache and notifying the watchers, so that
+    /// registering a watch cannot interleave with an update.
+    watchers: Arc<Mutex<Vec<Box<dyn Watcher>>>>,
 }
 
 /// RegistryClientImpl polls the data provider and caches the received results.
@@ -44,9 +49,37 @@ impl RegistryClientImpl {
             data_provider,
             metrics,
             poll_thread: Arc::new(RwLock::new(None)),
+            watchers: Arc::new(Mutex::new(vec![])),
         }
     }
 
+    /// Registers a watch for all keys starting with `key_prefix`, whose values
+    /// are passed as raw bytes.
+    ///
+    /// See [`Self::watch_with`].
+    pub fn watch(&self, key_prefix: &str) -> RegistryWatch<Vec<u8>> {
+        self.watch_with(key_prefix, |_key, value| value.to_vec())
+    }
+
+    /// Registers a watch for all keys starting with `key_prefix`.
+    ///
+    /// Whenever the client fetches registry versions that touch such keys, the
+    /// returned watch receives one event per version. The values of the
+    /// changed keys are decoded with `decode`, once per watch and change,
+    /// which is given the key and the raw value. Decoding errors are left to
+    /// the caller, e.g., by returning a `Result`.
+    pub fn watch_with<T, F>(&self, key_prefix: &str, decode: F) -> RegistryWatch<T>
+    where
+        T: Send + 'static,
+        F: Fn(&str, &[u8]) -> T + Send + Sync + 'static,
+    {
+        let mut watchers = self.watchers.lock().unwrap();
+        let start_version = self.cache.read().unwrap().latest_version;
+        let (watcher, watch) = PrefixWatcher::new(key_prefix.to_string(), decode, start_version);
+        watchers.push(Box::new(watcher));
+        watch
+    }
+
     /// Calls `poll_once()` synchronously, if it succeeds a background task is
     /// spawned that continuously polls for updates.
     /// The background task is stopped when the object is dropped.
@@ -82,7 +115,14 @@ impl RegistryClientImpl {
     /// provider failed. Returns `Ok` if querying the data provider succeeded,
     /// regardless of whether a newer registry version was available or not.
     pub fn poll_once(&self) -> Result<(), RegistryClientError> {
-        let (records, version) = {
+        // Unregister the watchers whose watch was dropped on every poll, also
+        // if there are no updates, so that they do not pile up.
+        self.watchers
+            .lock()
+            .unwrap()
+            .retain(|watcher| !watcher.is_dropped());
+
+        let (records, version, oldest_version) = {
             let latest_version = self.cache.read().unwrap().latest_version;
             let records = match self
                 .data_provider
@@ -97,17 +137,43 @@ impl RegistryClientImpl {
                 .max_by_key(|r| r.version)
                 .map(|r| r.version)
                 .unwrap_or(latest_version);
-
-            (records, new_version)
+            // Query the oldest version after the updates, so that pruning the
+            // history in between can only make it too high, never too low.
+            let oldest_version = self.data_provider.get_oldest_version()?;
+
+            (
+                records,
+                new_version,
+                (oldest_version > latest_version).then_some(oldest_version),
+            )
         };
 
-        // Ensure exclusive access to the cache.
-        let mut cache_state = self.cache.write().unwrap();
+        let mut watchers = self.watchers.lock().unwrap();
+        {
+            // Ensure exclusive access to the cache.
+            let mut cache_state = self.cache.write().unwrap();
 
-        // Check version again under write lock, to prevent race conditions.
-        if version > cache_state.latest_version {
+            // Check version again under write lock, to prevent race conditions.
+            if version <= cache_state.latest_version {
+                return Ok(());
+            }
             self.metrics.registry_version.set(version.get() as i64);
-            cache_state.update(records, version);
+            // The records do not represent the registry below the oldest
+            // version of the data provider.
+            if let Some(oldest_version) = oldest_version {
+                cache_state.oldest_version = cache_state.oldest_version.max(oldest_version);
+            }
+            if watchers.is_empty() {
+                cache_state.update(records, version);
+                return Ok(());
+            }
+            cache_state.update(records.clone(), version);
+        }
+
+        // Notify the watchers only after releasing the cache, so that decoding
+        // the changed values does not block readers.
+        for watcher in watchers.iter() {
+            watcher.notify(&records, version);
         }
         Ok(())
     }
@@ -154,7 +220,7 @@ impl RegistryClientImpl {
         version: RegistryVersion,
     ) -> Result<RwLockReadGuard<'_, CacheState>, RegistryClientError> {
         let cache_state = self.cache.read().unwrap();
-        if version > cache_state.latest_version {
+        if version > cache_state.latest_version || version < cache_state.oldest_version {
             return Err(RegistryClientError::VersionNotAvailable { version });
         }
         Ok(cache_state)
@@ -191,6 +257,9 @@ struct CacheState {
     records: Vec<RegistryRecord>,
     timestamps: BTreeMap<RegistryVersion, Time>,
     latest_version: RegistryVersion,
+    /// The lowest version that the records represent, as the history below it
+    /// was pruned at the data provider.
+    oldest_version: RegistryVersion,
 }
 
 impl CacheState {
@@ -198,6 +267,7 @@ impl CacheState {
         Self {
             records: vec![],
             latest_version: ZERO_REGISTRY_VERSION,
+            oldest_version: ZERO_REGISTRY_VERSION,
             timestamps: Default::default(),
         }
     }
@@ -350,11 +420,15 @@ impl RegistryClient for RegistryClientImpl {
 #[allow(dead_code, unused_imports)]
 mod tests {
     use super::*;
+    use crate::watch::{
+        RegistryChangeEvent, RegistryKeyChange, RegistryWatchEvent, WATCH_CHANNEL_CAPACITY,
+    };
     use assert_matches::assert_matches;
     use ic_interfaces_registry::ZERO_REGISTRY_VERSION;
     use ic_registry_client_helpers::test_proto::TestProtoHelper;
     use ic_registry_common_proto::pb::test_protos::v1::TestProto;
     use ic_registry_proto_data_provider::ProtoRegistryDataProvider;
+    use prost::Message;
     use std::collections::HashSet;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::{Arc, RwLock};
@@ -517,6 +591,128 @@ mod tests {
         assert!(get("B2", 7).is_err());
     }
 
+    #[test]
+    fn watch_receives_one_event_per_version_touching_watched_keys() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry = RegistryClientImpl::new(data_provider.clone(), None);
+        data_provider.add("A", v(1), Some(value(1))).unwrap();
+        registry.poll_once().unwrap();
+
+        let watch = registry.watch_with("B", |_key, bytes| TestProto::decode(bytes).unwrap());
+        assert_eq!(watch.start_version(), v(1));
+
+        data_provider.add("A", v(2), Some(value(2))).unwrap();
+        data_provider.add("B1", v(3), Some(value(3))).unwrap();
+        data_provider.add("B2", v(3), Some(value(3))).unwrap();
+        data_provider.add("C", v(4), Some(value(4))).unwrap();
+        data_provider.add::<TestProto>("B1", v(5), None).unwrap();
+        registry.poll_once().unwrap();
+
+        let events: Vec<_> = watch.receiver().try_iter().collect();
+        assert_eq!(
+            events,
+            vec![
+                RegistryWatchEvent::Changed(RegistryChangeEvent {
+                    version: v(3),
+                    changes: vec![
+                        RegistryKeyChange {
+                            key: "B1".to_string(),
+                            value: Some(value(3)),
+                        },
+                        RegistryKeyChange {
+                            key: "B2".to_string(),
+                            value: Some(value(3)),
+                        },
+                    ],
+                }),
+                RegistryWatchEvent::Changed(RegistryChangeEvent {
+                    version: v(5),
+                    changes: vec![RegistryKeyChange {
+                        key: "B1".to_string(),
+                        value: None,
+                    }],
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn watch_does_not_receive_versions_up_to_start_version() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry = RegistryClientImpl::new(data_provider.clone(), None);
+        data_provider.add("A", v(1), Some(value(1))).unwrap();
+        registry.poll_once().unwrap();
+        let watch = registry.watch("A");
+
+        registry.poll_once().unwrap();
+        assert!(watch.receiver().try_recv().is_err());
+
+        data_provider.add("A", v(2), Some(value(2))).unwrap();
+        registry.poll_once().unwrap();
+        let event = assert_matches!(
+            watch.receiver().try_recv(),
+            Ok(RegistryWatchEvent::Changed(event)) => event
+        );
+        assert_eq!(event.version, v(2));
+        assert_eq!(
+            event.changes[0].value.as_deref().map(TestProto::decode),
+            Some(Ok(value(2)))
+        );
+        assert!(watch.receiver().try_recv().is_err());
+    }
+
+    #[test]
+    fn dropped_watch_is_unregistered() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry = RegistryClientImpl::new(data_provider.clone(), None);
+        let dropped_watch = registry.watch("A");
+        let kept_watch = registry.watch("A");
+        drop(dropped_watch);
+
+        // Dropped watches are unregistered even if there are no updates.
+        registry.poll_once().unwrap();
+        assert_eq!(registry.watchers.lock().unwrap().len(), 1);
+
+        data_provider.add("A", v(1), Some(value(1))).unwrap();
+        registry.poll_once().unwrap();
+
+        assert_eq!(registry.watchers.lock().unwrap().len(), 1);
+        assert_eq!(kept_watch.receiver().len(), 1);
+    }
+
+    #[test]
+    fn watch_falling_behind_receives_resync() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry = RegistryClientImpl::new(data_provider.clone(), None);
+        let watch = registry.watch("A");
+
+        let latest_version = WATCH_CHANNEL_CAPACITY as u64 + 2;
+        for version in 1..=latest_version {
+            data_provider
+                .add("A", v(version), Some(value(version)))
+                .unwrap();
+        }
+        registry.poll_once().unwrap();
+
+        // All pending events are replaced by a single resync.
+        assert_eq!(
+            watch.receiver().try_iter().collect::<Vec<_>>(),
+            vec![RegistryWatchEvent::Resync {
+                version: v(latest_version)
+            }]
+        );
+
+        // Subsequent changes are delivered as usual.
+        data_provider
+            .add("A", v(latest_version + 1), Some(value(0)))
+            .unwrap();
+        registry.poll_once().unwrap();
+        assert_matches!(
+            watch.receiver().try_recv(),
+            Ok(RegistryWatchEvent::Changed(event)) if event.version == v(latest_version + 1)
+        );
+    }
+
     #[test]
     fn start_polling_actually_polls_data_provider() {
         let data_provider = Arc::new(FakeDataProvider {
@@ -614,6 +810,48 @@ mod tests {
         assert_eq!(get("C", 7).unwrap(), Some(value(7)));
     }
 
+    #[test]
+    fn lookups_below_pruned_history_fail() {
+        let data_provider = Arc::new(ProtoRegistryDataProvider::new());
+        let registry = RegistryClientImpl::new(
+            Arc::new(PruningDataProvider::new(v(3), data_provider.clone())),
+            None,
+        );
+        let get = |key: &str, t: u64| registry.get_test_proto(key, v(t));
+        let set = |key: &str, ver: u64| data_provider.add(key, v(ver), Some(value(ver))).unwrap();
+
+        set("A", 1);
+        set("B", 2);
+        set("A", 3);
+        set("B", 4);
+
+        registry.poll_once().unwrap();
+
+        assert_eq!(registry.get_latest_version(), v(4));
+        assert!(get("A", 0).unwrap().is_none());
+        for t in 1..3 {
+            assert_matches!(
+                get("A", t),
+                Err(RegistryClientError::VersionNotAvailable { version }) if version == v(t)
+            );
+            assert_matches!(
+                registry.get_key_family("", v(t)),
+                Err(RegistryClientError::VersionNotAvailable { .. })
+            );
+        }
+        assert_eq!(get("A", 3).unwrap(), Some(value(3)));
+        assert_eq!(get("B", 3).unwrap(), Some(value(2)));
+        assert_eq!(get("B", 4).unwrap(), Some(value(4)));
+        assert_eq!(registry.get_key_family("", v(3)).unwrap(), vec!["A", "B"]);
+
+        // Later polls do not lower the oldest version.
+        set("C", 5);
+        registry.poll_once().unwrap();
+
+        assert_eq!(get("C", 5).unwrap(), Some(value(5)));
+        assert!(get("A", 2).is_err());
+    }
+
     fn v(v: u64) -> RegistryVersion {
         RegistryVersion::new(v)
     }
@@ -663,6 +901,47 @@ mod tests {
             Ok(res)
         }
     }
+    /// Serves the latest record of every key up to `oldest_version` instead of
+    /// the history below it, like a local store whose history was pruned.
+    struct PruningDataProvider {
+        oldest_version: RegistryVersion,
+        data_provider: Arc<dyn RegistryDataProvider>,
+    }
+
+    impl PruningDataProvider {
+        fn new(
+            oldest_version: RegistryVersion,
+            data_provider: Arc<dyn RegistryDataProvider>,
+        ) -> Self {
+            Self {
+                oldest_version,
+                data_provider,
+            }
+        }
+    }
+
+    impl RegistryDataProvider for PruningDataProvider {
+        fn get_updates_since(
+            &self,
+            version: RegistryVersion,
+        ) -> Result<Vec<RegistryRecord>, RegistryDataProviderError> {
+            let mut res = self.data_provider.get_updates_since(version)?;
+            if version < self.oldest_version {
+                let mut latest = BTreeMap::new();
+                for r in res.iter().filter(|r| r.version <= self.oldest_version) {
+                    latest.insert(r.key.clone(), r.clone());
+                }
+                res.retain(|r| r.version > self.oldest_version);
+                res.extend(latest.into_values());
+            }
+            Ok(res)
+        }
+
+        fn get_oldest_version(&self) -> Result<RegistryVersion, RegistryDataProviderError> {
+            Ok(self.oldest_version)
+        }
+    }
+
     #[cfg(test)]
     mod metrics {
         use ic_test_utilities_metrics::fetch_int_gauge;
@@ -1,2 +1,3 @@
 pub mod client;
 mod metrics;
+pub mod watch;
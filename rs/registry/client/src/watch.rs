@@ -0,0 +1,169 @@
+//! Push-based notifications about registry changes.
+//!
+//! Instead of periodically re-reading (and re-decoding) the records they are
+//! interested in, components can watch a key prefix and receive one
+//! [`RegistryChangeEvent`] per registry version that touches a key with that
+//! prefix. Events are produced by the registry client whenever it fetches new
+//! versions from its data provider, e.g., in its polling thread.
+//!
+//! Events are buffered in a bounded channel. A consumer that falls behind by
+//! more than [`WATCH_CHANNEL_CAPACITY`] events receives a
+//! [`RegistryWatchEvent::Resync`] instead of the discarded events.
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use ic_interfaces_registry::RegistryRecord;
+use ic_types::RegistryVersion;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Weak};
+
+/// The maximum number of events buffered for a [`RegistryWatch`].
+///
+/// If a consumer falls further behind, its pending events are replaced by a
+/// single [`RegistryWatchEvent::Resync`].
+pub const WATCH_CHANNEL_CAPACITY: usize = 1024;
+
+/// An event delivered to a [`RegistryWatch`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RegistryWatchEvent<T> {
+    /// The changes to the watched keys in a single registry version.
+    Changed(RegistryChangeEvent<T>),
+    /// The consumer did not keep up and the pending events were discarded.
+    ///
+    /// The consumer must re-read the watched keys at `version`. All
+    /// subsequent events are for versions greater than `version`.
+    Resync { version: RegistryVersion },
+}
+
+/// The changes to the watched keys in a single registry version.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RegistryChangeEvent<T> {
+    pub version: RegistryVersion,
+    /// The changed keys, in lexicographic order.
+    pub changes: Vec<RegistryKeyChange<T>>,
+}
+
+/// A change of a single registry key.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RegistryKeyChange<T> {
+    pub key: String,
+    /// The decoded new value, or `None` if the key was deleted.
+    pub value: Option<T>,
+}
+
+/// The receiving end of a watch registered with
+/// [`RegistryClientImpl::watch`](crate::client::RegistryClientImpl::watch).
+///
+/// Once the `RegistryWatch` is dropped, the watch is unregistered the next
+/// time the registry client polls its data provider.
+pub struct RegistryWatch<T> {
+    start_version: RegistryVersion,
+    receiver: Receiver<RegistryWatchEvent<T>>,
+    _alive: Arc<()>,
+}
+
+impl<T> RegistryWatch<T> {
+    /// The latest registry version at the time the watch was registered.
+    ///
+    /// The watch yields an event for every version greater than this one that
+    /// touches a watched key, and for no other version. A component can thus
+    /// read its initial state at `start_version()` and then apply events,
+    /// without missing or duplicating any change.
+    pub fn start_version(&self) -> RegistryVersion {
+        self.start_version
+    }
+
+    /// The channel over which events are delivered, in ascending order of
+    /// versions.
+    pub fn receiver(&self) -> &Receiver<RegistryWatchEvent<T>> {
+        &self.receiver
+    }
+}
+
+/// A registered watch, type-erased so that watches of different value types
+/// can be kept together.
+pub(crate) trait Watcher: Send + Sync {
+    /// Returns `true` if the watch has been dropped and can be unregistered.
+    fn is_dropped(&self) -> bool;
+
+    /// Sends an event for every version among `records` that touches a watched
+    /// key, where `latest_version` is the version of the registry client once
+    /// `records` are applied.
+    fn notify(&self, records: &[RegistryRecord], latest_version: RegistryVersion);
+}
+
+pub(crate) struct PrefixWatcher<T, F> {
+    key_prefix: String,
+    decode: F,
+    sender: Sender<RegistryWatchEvent<T>>,
+    /// Used to discard the pending events when the channel is full.
+    receiver: Receiver<RegistryWatchEvent<T>>,
+    watch_alive: Weak<()>,
+}
+
+impl<T, F> PrefixWatcher<T, F> {
+    pub(crate) fn new(
+        key_prefix: String,
+        decode: F,
+        start_version: RegistryVersion,
+    ) -> (Self, RegistryWatch<T>) {
+        let (sender, receiver) = crossbeam_channel::bounded(WATCH_CHANNEL_CAPACITY);
+        let alive = Arc::new(());
+        let watcher = Self {
+            key_prefix,
+            decode,
+            sender,
+            receiver: receiver.clone(),
+            watch_alive: Arc::downgrade(&alive),
+        };
+        let watch = RegistryWatch {
+            start_version,
+            receiver,
+            _alive: alive,
+        };
+        (watcher, watch)
+    }
+}
+
+impl<T, F> Watcher for PrefixWatcher<T, F>
+where
+    T: Send,
+    F: Fn(&str, &[u8]) -> T + Send + Sync,
+{
+    fn is_dropped(&self) -> bool {
+        self.watch_alive.strong_count() == 0
+    }
+
+    fn notify(&self, records: &[RegistryRecord], latest_version: RegistryVersion) {
+        let mut changes_by_version = BTreeMap::<_, Vec<_>>::new();
+        for record in records
+            .iter()
+            .filter(|r| r.key.starts_with(&self.key_prefix))
+        {
+            changes_by_version
+                .entry(record.version)
+                .or_default()
+                .push(RegistryKeyChange {
+                    key: record.key.clone(),
+                    value: record
+                        .value
+                        .as_deref()
+                        .map(|value| (self.decode)(&record.key, value)),
+                });
+        }
+
+        for (version, mut changes) in changes_by_version {
+            changes.sort_by(|a, b| a.key.cmp(&b.key));
+            let event = RegistryWatchEvent::Changed(RegistryChangeEvent { version, changes });
+            if let Err(TrySendError::Full(_)) = self.sender.try_send(event) {
+                // The consumer fell behind. Replace all pending events by a
+                // resync at the latest version, which also covers the changes
+                // not sent yet. Since this is the only sender, there is room
+                // for the resync once the channel is drained.
+                while self.receiver.try_recv().is_ok() {}
+                let _ = self.sender.try_send(RegistryWatchEvent::Resync {
+                    version: latest_version,
+                });
+                return;
+            }
+        }
+    }
+}
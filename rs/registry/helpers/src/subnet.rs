@@ -7,7 +7,10 @@ use ic_protobuf::{
     registry::{
         node::v1::NodeRecord,
         replica_version::v1::ReplicaVersionRecord,
-        subnet::v1::{CatchUpPackageContents, SubnetListRecord, SubnetRecord, SubnetType},
+        subnet::v1::{
+            AdaptiveBlockTimeConfig, CatchUpPackageContents, SubnetListRecord, SubnetRecord,
+            SubnetType,
+        },
     },
     types::v1::SubnetId as SubnetIdProto,
 };
@@ -26,6 +29,19 @@ use std::{convert::TryFrom, time::Duration};
 pub struct NotarizationDelaySettings {
     pub unit_delay: Duration,
     pub initial_notary_delay: Duration,
+    /// If set, `initial_notary_delay` is replaced by a delay that adapts to the
+    /// load of the subnet, within the given bounds.
+    pub adaptive_block_time: Option<AdaptiveBlockTimeSettings>,
+}
+
+impl NotarizationDelaySettings {
+    /// Returns the smallest initial notary delay that may be used by the subnet.
+    pub fn min_initial_notary_delay(&self) -> Duration {
+        match &self.adaptive_block_time {
+            Some(adaptive) => adaptive.min_notary_delay,
+            None => self.initial_notary_delay,
+        }
+    }
 }
 
 impl Default for NotarizationDelaySettings {
@@ -33,6 +49,26 @@ impl Default for NotarizationDelaySettings {
         Self {
             initial_notary_delay: INITIAL_NOTARY_DELAY,
             unit_delay: UNIT_DELAY_APP_SUBNET,
+            adaptive_block_time: None,
+        }
+    }
+}
+
+/// Bounds of the initial notary delay of a subnet with adaptive block time.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct AdaptiveBlockTimeSettings {
+    pub min_notary_delay: Duration,
+    pub max_notary_delay: Duration,
+}
+
+impl From<AdaptiveBlockTimeConfig> for AdaptiveBlockTimeSettings {
+    fn from(config: AdaptiveBlockTimeConfig) -> Self {
+        let min_notary_delay = Duration::from_millis(config.min_notary_delay_millis);
+        Self {
+            min_notary_delay,
+            // An inverted range in the registry is treated as a fixed delay.
+            max_notary_delay: Duration::from_millis(config.max_notary_delay_millis)
+                .max(min_notary_delay),
         }
     }
 }
@@ -299,6 +335,9 @@ impl<T: RegistryClient + ?Sized> SubnetRegistry for T {
                 NotarizationDelaySettings {
                     unit_delay: Duration::from_millis(subnet.unit_delay_millis),
                     initial_notary_delay: Duration::from_millis(subnet.initial_notary_delay_millis),
+                    adaptive_block_time: subnet
+                        .adaptive_block_time_config
+                        .map(AdaptiveBlockTimeSettings::from),
                 }
             }),
         )
@@ -1,16 +1,22 @@
-use ic_interfaces_registry::{RegistryDataProvider, RegistryRecord};
+use ic_interfaces_registry::{RegistryDataProvider, RegistryRecord, ZERO_REGISTRY_VERSION};
 use ic_registry_common_proto::pb::local_store::v1::{
-    ChangelogEntry as PbChangelogEntry, Delta as PbDelta, KeyMutation as PbKeyMutation,
-    MutationType,
+    ChangelogEntry as PbChangelogEntry, Checkpoint as PbCheckpoint, Delta as PbDelta,
+    KeyMutation as PbKeyMutation, MutationType, VersionedKeyMutation as PbVersionedKeyMutation,
 };
 use ic_sys::fs::{sync_path, write_protobuf_simple, write_protobuf_using_tmp_file};
 use ic_types::RegistryVersion;
 use ic_types::registry::RegistryDataProviderError;
 use prost::Message;
 use std::{
+    collections::BTreeMap,
+    fmt,
     io::{self},
     path::{Path, PathBuf},
 };
+
+const CHECKPOINT_FILE_PREFIX: &str = "checkpoint_";
+const CHECKPOINT_FILE_SUFFIX: &str = ".pb";
+
 pub trait LocalStore: LocalStoreWriter + LocalStoreReader {}
 
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
@@ -37,6 +43,9 @@ pub trait LocalStoreReader: Send + Sync {
     /// `cl` where the subsequence `cl[0..i]`, `0 <= i <= len(ds)`, applied
     /// to a registry at latest version `v` represents the registry at
     /// version `v+i+1`.
+    ///
+    /// Fails with a [`VersionPrunedError`] if the changelog entries after
+    /// `version` were deleted by compaction.
     fn get_changelog_since_version(&self, version: RegistryVersion) -> io::Result<Changelog>;
 }
 
@@ -55,12 +64,70 @@ pub trait LocalStoreWriter: Send + Sync {
     fn clear(&self) -> io::Result<()>;
 }
 
+/// The error returned when reading changelog entries that were deleted by
+/// [`LocalStoreImpl::compact`].
+///
+/// It is wrapped in an [`io::Error`] of kind [`io::ErrorKind::NotFound`], see
+/// [`VersionPrunedError::is_version_pruned`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct VersionPrunedError {
+    /// The version since which the changelog was requested.
+    pub version: RegistryVersion,
+    /// The lowest version since which the changelog is still available.
+    pub oldest_available_version: RegistryVersion,
+}
+
+impl VersionPrunedError {
+    /// Returns whether `e` is a [`VersionPrunedError`].
+    pub fn is_version_pruned(e: &io::Error) -> bool {
+        e.get_ref().is_some_and(|e| e.is::<Self>())
+    }
+}
+
+impl fmt::Display for VersionPrunedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "The changelog since version {} was pruned; it is only available since version {}.",
+            self.version, self.oldest_available_version
+        )
+    }
+}
+
+impl std::error::Error for VersionPrunedError {}
+
+impl From<VersionPrunedError> for io::Error {
+    fn from(e: VersionPrunedError) -> Self {
+        io::Error::new(io::ErrorKind::NotFound, e)
+    }
+}
+
+/// How much of the history up to a checkpoint [`LocalStoreImpl::compact`]
+/// keeps.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum HistoryRetention {
+    /// Keep the changelog entries of all versions.
+    All,
+    /// Keep the changelog entries of the given number of versions up to and
+    /// including the checkpoint version, and delete older ones.
+    Versions(u64),
+}
+
 #[derive(Clone, Debug)]
 pub struct LocalStoreImpl {
-    /// Directory with one .pb file per registry version.
+    /// Directory with one .pb file per registry version and, once compacted,
+    /// a checkpoint file holding the registry up to some version.
+    ///
+    /// Changelog entries up to the checkpoint version may have been deleted.
+    /// Reading the changelog since a version below the oldest remaining entry
+    /// fails, whereas registry records since such a version are served from
+    /// the checkpoint.
     path: PathBuf,
 }
 
+/// The latest version and value of every key, including deleted keys.
+type LatestMutations = BTreeMap<String, (u64, Option<Vec<u8>>)>;
+
 impl LocalStoreImpl {
     pub fn new<P: AsRef<Path>>(path: P) -> Self {
         Self {
@@ -131,6 +198,225 @@ impl LocalStoreImpl {
         self.path.join(v_path.as_path())
     }
 
+    /// Writes a checkpoint of the registry at the latest version and deletes
+    /// the changelog entries that are not retained according to `retention`.
+    ///
+    /// New versions can be stored as before, and the registry records since
+    /// any version can still be read through [`RegistryDataProvider`]. Reading
+    /// the changelog since a version whose successor was deleted fails with a
+    /// [`VersionPrunedError`]. Stores written without checkpoints are migrated
+    /// by compacting them once.
+    ///
+    /// Returns the version of the checkpoint.
+    pub fn compact(&self, retention: HistoryRetention) -> io::Result<RegistryVersion> {
+        let checkpoint_version = self.checkpoint_version()?;
+        let mut latest = match checkpoint_version {
+            Some(checkpoint_version) => self.read_checkpoint(checkpoint_version)?,
+            None => LatestMutations::new(),
+        };
+        let base_version = checkpoint_version.unwrap_or(0);
+        let changelog = self.read_changelog_entries_from(base_version + 1)?;
+        let version = base_version + changelog.len() as u64;
+        for (i, changelog_entry) in changelog.into_iter().enumerate() {
+            for km in changelog_entry {
+                latest.insert(km.key, (base_version + i as u64 + 1, km.value));
+            }
+        }
+
+        if version > base_version {
+            self.write_checkpoint(version, latest)?;
+        }
+        if let HistoryRetention::Versions(retained_versions) = retention
+            && version > retained_versions
+        {
+            self.delete_changelog_entries_up_to(version - retained_versions)?;
+        }
+        Ok(RegistryVersion::from(version))
+    }
+
+    /// Compacts the store like [`Self::compact`], keeping the history of
+    /// `retained_versions` versions, once at least `retained_versions` versions
+    /// were stored since the latest checkpoint.
+    ///
+    /// The store thus holds between `retained_versions` and twice as many
+    /// changelog entries, and each compaction amortizes over
+    /// `retained_versions` versions.
+    ///
+    /// Returns the version of the new checkpoint, if the store was compacted.
+    pub fn compact_if_due(&self, retained_versions: u64) -> io::Result<Option<RegistryVersion>> {
+        let base_version = self.checkpoint_version()?.unwrap_or(0);
+        // Changelog entries are contiguous, so the last one that must exist
+        // suffices.
+        if !self
+            .get_path(base_version + retained_versions.max(1))
+            .exists()
+        {
+            return Ok(None);
+        }
+        self.compact(HistoryRetention::Versions(retained_versions))
+            .map(Some)
+    }
+
+    /// Returns the version of the latest checkpoint, if any.
+    fn checkpoint_version(&self) -> io::Result<Option<u64>> {
+        Ok(self.checkpoint_versions()?.into_iter().max())
+    }
+
+    fn checkpoint_versions(&self) -> io::Result<Vec<u64>> {
+        let dir_entries = match std::fs::read_dir(&self.path) {
+            Ok(dir_entries) => dir_entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e),
+        };
+        dir_entries
+            .filter_map(|dir_entry| {
+                let file_name = match dir_entry {
+                    Ok(dir_entry) => dir_entry.file_name(),
+                    Err(e) => return Some(Err(e)),
+                };
+                file_name
+                    .to_str()
+                    .and_then(|name| name.strip_prefix(CHECKPOINT_FILE_PREFIX))
+                    .and_then(|name| name.strip_suffix(CHECKPOINT_FILE_SUFFIX))
+                    .and_then(|version| u64::from_str_radix(version, 16).ok())
+                    .map(Ok)
+            })
+            .collect()
+    }
+
+    fn get_checkpoint_path(&self, version: u64) -> PathBuf {
+        self.path.join(format!(
+            "{CHECKPOINT_FILE_PREFIX}{version:016x}{CHECKPOINT_FILE_SUFFIX}"
+        ))
+    }
+
+    fn read_checkpoint(&self, version: u64) -> io::Result<LatestMutations> {
+        let bytes = std::fs::read(self.get_checkpoint_path(version))?;
+        let checkpoint = PbCheckpoint::decode(bytes.as_slice()).map_err(io::Error::other)?;
+        let invalid_data = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg);
+        if checkpoint.registry_version != version {
+            return Err(invalid_data("Checkpoint version does not match file name."));
+        }
+        checkpoint.key_mutations.into_iter().try_fold(
+            LatestMutations::new(),
+            |mut res, versioned| {
+                if versioned.version == 0 || versioned.version > version {
+                    return Err(invalid_data("Invalid version of checkpoint mutation."));
+                }
+                let km = key_mutation_try_from_proto(
+                    &versioned
+                        .key_mutation
+                        .ok_or_else(|| invalid_data("Missing checkpoint mutation."))?,
+                )?;
+                res.insert(km.key, (versioned.version, km.value));
+                Ok(res)
+            },
+        )
+    }
+
+    /// Atomically writes the checkpoint and then deletes older ones.
+    fn write_checkpoint(&self, version: u64, latest: LatestMutations) -> io::Result<()> {
+        let key_mutations = latest
+            .into_iter()
+            .map(|(key, (version, value))| PbVersionedKeyMutation {
+                version,
+                key_mutation: Some(key_mutation_to_protobuf(&KeyMutation { key, value })),
+            })
+            .collect();
+        let checkpoint = PbCheckpoint {
+            registry_version: version,
+            key_mutations,
+        };
+        std::fs::create_dir_all(&self.path)?;
+        write_protobuf_using_tmp_file(self.get_checkpoint_path(version), &checkpoint)?;
+
+        for old_version in self.checkpoint_versions()? {
+            if old_version != version {
+                std::fs::remove_file(self.get_checkpoint_path(old_version))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the changelog entries of all versions up to `version`, as well
+    /// as directories that become empty.
+    fn delete_changelog_entries_up_to(&self, version: u64) -> io::Result<()> {
+        for path in (1..=version)
+            .rev()
+            .map(|v| self.get_path(v))
+            .take_while(|p| p.exists())
+        {
+            std::fs::remove_file(&path)?;
+            for dir in path
+                .ancestors()
+                .skip(1)
+                .take_while(|dir| *dir != self.path.as_path())
+            {
+                if std::fs::read_dir(dir)?.next().is_some() {
+                    break;
+                }
+                std::fs::remove_dir(dir)?;
+            }
+        }
+        sync_path(&self.path)
+    }
+
+    /// Returns the error for reading the changelog since `version`, whose
+    /// successor was deleted by the compaction at `checkpoint_version`.
+    fn version_pruned_error(&self, version: u64, checkpoint_version: u64) -> io::Error {
+        let oldest_available_version = (1..=checkpoint_version)
+            .rev()
+            .find(|v| !self.get_path(*v).exists())
+            .unwrap_or(0);
+        VersionPrunedError {
+            version: RegistryVersion::from(version),
+            oldest_available_version: RegistryVersion::from(oldest_available_version),
+        }
+        .into()
+    }
+
+    /// Returns the registry records since `version` from the checkpoint at
+    /// `checkpoint_version`, i.e., the latest record of every key that changed
+    /// after `version`, followed by the records after the checkpoint.
+    fn get_records_since_version_from_checkpoint(
+        &self,
+        version: u64,
+        checkpoint_version: u64,
+    ) -> io::Result<Vec<RegistryRecord>> {
+        let mut records: Vec<_> = self
+            .read_checkpoint(checkpoint_version)?
+            .into_iter()
+            .filter(|(_, (v, _))| *v > version)
+            .map(|(key, (v, value))| RegistryRecord {
+                version: RegistryVersion::from(v),
+                key,
+                value,
+            })
+            .collect();
+        records.sort_by_key(|record| record.version);
+        records.extend(changelog_to_records(
+            RegistryVersion::from(checkpoint_version),
+            self.read_changelog_entries_from(checkpoint_version + 1)?,
+        ));
+        Ok(records)
+    }
+
+    fn read_changelog_entries_from(&self, start: u64) -> io::Result<Changelog> {
+        let mut res = vec![];
+        for p in (start..).map(|i| self.get_path(i)) {
+            // Compaction may delete the entries concurrently, from the newest
+            // to the oldest one, so an entry that vanished while reading ends
+            // the changelog like a missing one.
+            let entry = match Self::read_changelog_entry(p) {
+                Ok(entry) => entry,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => break,
+                Err(e) => return Err(e),
+            };
+            res.push(changelog_entry_try_from_proto(entry)?);
+        }
+        Ok(res)
+    }
+
     fn read_changelog_entry<P: AsRef<Path>>(p: P) -> io::Result<PbChangelogEntry> {
         let bytes = std::fs::read(p)?;
         PbChangelogEntry::decode(bytes.as_slice()).map_err(io::Error::other)
@@ -144,7 +430,21 @@ impl LocalStoreImpl {
         if version == 0 {
             panic!("Version must be > 0.")
         }
-        if version > 1 && !self.get_path(version - 1).exists() {
+        let checkpoint_version = self.checkpoint_version()?;
+        if let Some(checkpoint_version) = checkpoint_version
+            && version <= checkpoint_version
+        {
+            return Err(io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "Version {version} is part of the checkpoint at version {checkpoint_version}."
+                ),
+            ));
+        }
+        if version > 1
+            && !self.get_path(version - 1).exists()
+            && checkpoint_version != Some(version - 1)
+        {
             return Err(io::Error::new(
                 std::io::ErrorKind::NotFound,
                 format!("Version {} does not exist.", version - 1),
@@ -169,15 +469,15 @@ impl LocalStore for LocalStoreImpl {}
 impl LocalStoreReader for LocalStoreImpl {
     fn get_changelog_since_version(&self, version: RegistryVersion) -> io::Result<Changelog> {
         let start = version.get() + 1;
-        (start..)
-            .map(|i| self.get_path(i))
-            .take_while(|p| p.exists())
-            .try_fold(vec![], |mut res, p| {
-                res.push(changelog_entry_try_from_proto(Self::read_changelog_entry(
-                    p,
-                )?)?);
-                Ok(res)
-            })
+        // Only look for a checkpoint if the changelog entry is missing, which
+        // is the case if there are no new versions, or if it was deleted.
+        if !self.get_path(start).exists()
+            && let Some(checkpoint_version) = self.checkpoint_version()?
+            && version.get() < checkpoint_version
+        {
+            return Err(self.version_pruned_error(version.get(), checkpoint_version));
+        }
+        self.read_changelog_entries_from(start)
     }
 }
 
@@ -190,9 +490,16 @@ impl LocalStoreWriter for LocalStoreImpl {
 
     fn clear(&self) -> io::Result<()> {
         std::fs::read_dir(self.path.as_path())?.try_for_each(|de| {
-            let path = de?.path();
+            let de = de?;
+            let path = de.path();
             if path.is_dir() {
                 std::fs::remove_dir_all(path)
+            } else if de
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(CHECKPOINT_FILE_PREFIX))
+            {
+                std::fs::remove_file(path)
             } else {
                 Ok(())
             }
@@ -205,25 +512,57 @@ impl RegistryDataProvider for LocalStoreImpl {
         &self,
         version: RegistryVersion,
     ) -> Result<Vec<RegistryRecord>, RegistryDataProviderError> {
-        let changelog = self.get_changelog_since_version(version).map_err(|e| {
-            RegistryDataProviderError::Transfer {
-                source: format!("Error when reading changelog from local storage: {e:?}"),
+        let to_transfer_error = |e: io::Error| RegistryDataProviderError::Transfer {
+            source: format!("Error when reading changelog from local storage: {e:?}"),
+        };
+        match self.get_changelog_since_version(version) {
+            Ok(changelog) => Ok(changelog_to_records(version, changelog)),
+            // The history since `version` was pruned, but the latest record of
+            // every key is in the checkpoint, see `get_oldest_version`.
+            Err(e) if VersionPrunedError::is_version_pruned(&e) => {
+                let checkpoint_version = self
+                    .checkpoint_version()
+                    .map_err(to_transfer_error)?
+                    .ok_or_else(|| to_transfer_error(e))?;
+                self.get_records_since_version_from_checkpoint(version.get(), checkpoint_version)
+                    .map_err(to_transfer_error)
             }
-        })?;
-        let res: Vec<_> = changelog
-            .iter()
-            .enumerate()
-            .flat_map(|(i, cle)| cle.iter().map(move |km| (i, km)))
-            .map(|(i, km)| RegistryRecord {
-                version: version + RegistryVersion::from((i as u64) + 1),
-                key: km.key.clone(),
-                value: km.value.clone(),
-            })
-            .collect();
-        Ok(res)
+            Err(e) => Err(to_transfer_error(e)),
+        }
+    }
+
+    /// Returns the version of the checkpoint if the history before it was
+    /// pruned, as the registry at older versions cannot be reconstructed from
+    /// the checkpoint.
+    fn get_oldest_version(&self) -> Result<RegistryVersion, RegistryDataProviderError> {
+        let checkpoint_version =
+            self.checkpoint_version()
+                .map_err(|e| RegistryDataProviderError::Transfer {
+                    source: format!("Error when reading checkpoint from local storage: {e:?}"),
+                })?;
+        Ok(match checkpoint_version {
+            Some(checkpoint_version) if !self.get_path(1).exists() => {
+                RegistryVersion::from(checkpoint_version)
+            }
+            _ => ZERO_REGISTRY_VERSION,
+        })
     }
 }
 
+/// Returns the records of the `changelog` since `version`.
+fn changelog_to_records(version: RegistryVersion, changelog: Changelog) -> Vec<RegistryRecord> {
+    changelog
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, cle)| cle.into_iter().map(move |km| (i, km)))
+        .map(|(i, km)| RegistryRecord {
+            version: version + RegistryVersion::from((i as u64) + 1),
+            key: km.key,
+            value: km.value,
+        })
+        .collect()
+}
+
 fn changelog_entry_try_from_proto(value: PbChangelogEntry) -> Result<ChangelogEntry, io::Error> {
     if value.key_mutations.is_empty() {
         return Err(io::Error::new(
@@ -332,24 +671,23 @@ pub fn changelog_to_compact_delta(
 
 fn changelog_entry_to_protobuf(ce: ChangelogEntry) -> PbChangelogEntry {
     assert!(!ce.is_empty());
-    let key_mutations = ce
-        .iter()
-        .map(|km| {
-            let mutation_type = if km.value.is_some() {
-                MutationType::Set as i32
-            } else {
-                MutationType::Unset as i32
-            };
-            PbKeyMutation {
-                key: km.key.clone(),
-                value: km.value.clone().unwrap_or_default(),
-                mutation_type,
-            }
-        })
-        .collect();
+    let key_mutations = ce.iter().map(key_mutation_to_protobuf).collect();
     PbChangelogEntry { key_mutations }
 }
 
+fn key_mutation_to_protobuf(km: &KeyMutation) -> PbKeyMutation {
+    let mutation_type = if km.value.is_some() {
+        MutationType::Set as i32
+    } else {
+        MutationType::Unset as i32
+    };
+    PbKeyMutation {
+        key: km.key.clone(),
+        value: km.value.clone().unwrap_or_default(),
+        mutation_type,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -438,6 +776,253 @@ mod tests {
         }
     }
 
+    #[test]
+    fn compacted_store_yields_same_registry_from_every_version() {
+        let tempdir = TempDir::new().unwrap();
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(100, &mut rng);
+        let store = LocalStoreImpl::from_changelog(changelog.clone(), tempdir.path()).unwrap();
+
+        assert_eq!(
+            store.compact(HistoryRetention::Versions(0)).unwrap(),
+            RegistryVersion::from(100)
+        );
+
+        assert_eq!(count_changelog_entry_files(tempdir.path()), 0);
+        for v in 0..=changelog.len() {
+            let records = store
+                .get_updates_since(RegistryVersion::from(v as u64))
+                .unwrap();
+            assert!(records.iter().all(|r| r.version.get() > v as u64));
+            assert_eq!(
+                apply_records(registry_at(&changelog, v), &records),
+                registry_at(&changelog, changelog.len())
+            );
+        }
+    }
+
+    #[test]
+    fn reading_pruned_changelog_fails() {
+        let tempdir = TempDir::new().unwrap();
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(100, &mut rng);
+        let store = LocalStoreImpl::from_changelog(changelog.clone(), tempdir.path()).unwrap();
+
+        store.compact(HistoryRetention::Versions(10)).unwrap();
+
+        for v in [0, 50, 89] {
+            let err = store
+                .get_changelog_since_version(RegistryVersion::from(v))
+                .unwrap_err();
+            assert!(VersionPrunedError::is_version_pruned(&err), "{err:?}");
+            let err = err.into_inner().unwrap().downcast::<VersionPrunedError>();
+            assert_eq!(
+                *err.unwrap(),
+                VersionPrunedError {
+                    version: RegistryVersion::from(v),
+                    oldest_available_version: RegistryVersion::from(90),
+                }
+            );
+        }
+        let cl = store
+            .get_changelog_since_version(RegistryVersion::from(90))
+            .unwrap();
+        assert_eq!(&changelog[90..], cl.as_slice());
+    }
+
+    #[test]
+    fn oldest_version_is_checkpoint_version_once_history_is_pruned() {
+        let tempdir = TempDir::new().unwrap();
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(100, &mut rng);
+        let store = LocalStoreImpl::from_changelog(changelog, tempdir.path()).unwrap();
+        assert_eq!(store.get_oldest_version().unwrap(), ZERO_REGISTRY_VERSION);
+
+        store.compact(HistoryRetention::All).unwrap();
+        assert_eq!(store.get_oldest_version().unwrap(), ZERO_REGISTRY_VERSION);
+
+        store.compact(HistoryRetention::Versions(10)).unwrap();
+        assert_eq!(
+            store.get_oldest_version().unwrap(),
+            RegistryVersion::from(100)
+        );
+    }
+
+    #[test]
+    fn compaction_keeps_retained_history_and_later_versions() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let mut changelog = get_random_changelog(100, &mut rng);
+        store_changelog(&store, 1, &changelog);
+
+        store.compact(HistoryRetention::Versions(10)).unwrap();
+        let new_changelog = get_random_changelog(20, &mut rng);
+        store_changelog(&store, 101, &new_changelog);
+        changelog.extend(new_changelog);
+
+        assert_eq!(count_changelog_entry_files(tempdir.path()), 30);
+        for v in 90..=changelog.len() {
+            let cl = store
+                .get_changelog_since_version(RegistryVersion::from(v as u64))
+                .unwrap();
+            assert_eq!(&changelog[v..], cl.as_slice());
+        }
+        let records = store.get_updates_since(RegistryVersion::from(0)).unwrap();
+        assert_eq!(
+            apply_records(BTreeMap::new(), &records),
+            registry_at(&changelog, 120)
+        );
+    }
+
+    #[test]
+    fn compacting_again_merges_checkpoints() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(60, &mut rng);
+        store_changelog(&store, 1, &changelog[..30]);
+        store.compact(HistoryRetention::Versions(0)).unwrap();
+        store_changelog(&store, 31, &changelog[30..]);
+
+        assert_eq!(
+            store.compact(HistoryRetention::All).unwrap(),
+            RegistryVersion::from(60)
+        );
+
+        assert_eq!(store.checkpoint_versions().unwrap(), vec![60]);
+        assert_eq!(count_changelog_entry_files(tempdir.path()), 30);
+        for v in (0..=changelog.len()).step_by(5) {
+            let records = store
+                .get_updates_since(RegistryVersion::from(v as u64))
+                .unwrap();
+            assert_eq!(
+                apply_records(registry_at(&changelog, v), &records),
+                registry_at(&changelog, changelog.len())
+            );
+        }
+    }
+
+    #[test]
+    fn compaction_is_due_after_retained_versions() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(50, &mut rng);
+
+        store_changelog(&store, 1, &changelog[..9]);
+        assert_eq!(store.compact_if_due(10).unwrap(), None);
+        store_changelog(&store, 10, &changelog[9..10]);
+        assert_eq!(
+            store.compact_if_due(10).unwrap(),
+            Some(RegistryVersion::from(10))
+        );
+        store_changelog(&store, 11, &changelog[10..19]);
+        assert_eq!(store.compact_if_due(10).unwrap(), None);
+        assert_eq!(count_changelog_entry_files(tempdir.path()), 19);
+        store_changelog(&store, 20, &changelog[19..]);
+        assert_eq!(
+            store.compact_if_due(10).unwrap(),
+            Some(RegistryVersion::from(50))
+        );
+
+        assert_eq!(count_changelog_entry_files(tempdir.path()), 10);
+        let records = store.get_updates_since(RegistryVersion::from(0)).unwrap();
+        assert_eq!(
+            apply_records(BTreeMap::new(), &records),
+            registry_at(&changelog, 50)
+        );
+    }
+
+    #[test]
+    fn cannot_store_versions_covered_by_checkpoint() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        let changelog = get_random_changelog(10, &mut rng);
+        store_changelog(&store, 1, &changelog);
+        store.compact(HistoryRetention::All).unwrap();
+
+        let err = store
+            .store(RegistryVersion::from(10), changelog[0].clone())
+            .unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn empty_changelog_after_clearing_compacted_store() {
+        let tempdir = TempDir::new().unwrap();
+        let store = LocalStoreImpl::new(tempdir.path());
+        let mut rng = rand::thread_rng();
+        store_changelog(&store, 1, &get_random_changelog(10, &mut rng));
+        store.compact(HistoryRetention::Versions(0)).unwrap();
+
+        store.clear().unwrap();
+
+        assert!(
+            store
+                .get_changelog_since_version(RegistryVersion::from(0))
+                .unwrap()
+                .is_empty()
+        );
+        let changelog = get_random_changelog(1, &mut rng);
+        store_changelog(&store, 1, &changelog);
+        assert_eq!(
+            store
+                .get_changelog_since_version(RegistryVersion::from(0))
+                .unwrap(),
+            changelog
+        );
+    }
+
+    fn store_changelog(store: &LocalStoreImpl, first_version: u64, changelog: &[ChangelogEntry]) {
+        changelog.iter().enumerate().for_each(|(i, c)| {
+            store
+                .store(RegistryVersion::from(first_version + i as u64), c.clone())
+                .unwrap()
+        });
+    }
+
+    fn count_changelog_entry_files(path: &Path) -> usize {
+        std::fs::read_dir(path)
+            .unwrap()
+            .map(|de| de.unwrap().path())
+            .map(|p| {
+                if p.is_dir() {
+                    count_changelog_entry_files(&p)
+                } else {
+                    usize::from(p.parent() != Some(path))
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the registry after applying the first `version` entries.
+    fn registry_at(changelog: &[ChangelogEntry], version: usize) -> BTreeMap<String, Vec<u8>> {
+        apply_records(
+            BTreeMap::new(),
+            &changelog_to_records(RegistryVersion::from(0), changelog[..version].to_vec()),
+        )
+    }
+
+    fn apply_records(
+        mut registry: BTreeMap<String, Vec<u8>>,
+        records: &[RegistryRecord],
+    ) -> BTreeMap<String, Vec<u8>> {
+        for record in records {
+            match &record.value {
+                Some(value) => {
+                    registry.insert(record.key.clone(), value.clone());
+                }
+                None => {
+                    registry.remove(&record.key);
+                }
+            }
+        }
+        registry
+    }
+
     fn get_random_changelog(n: usize, rng: &mut ThreadRng) -> Changelog {
         // some pseudo random entries
         (0..n)
@@ -1,16 +1,23 @@
 use ic_certification::{CertificateValidationError, verify_certified_data};
-use ic_crypto_tree_hash::{LabeledTree, MixedHashTree};
+use ic_crypto_tree_hash::{LabeledTree, LookupStatus, MixedHashTree};
 use ic_interfaces_registry::RegistryRecord;
 use ic_registry_transport::{
     GetChunk, dechunkify_mutation_value,
-    pb::v1::{CertifiedResponse, HighCapacityRegistryAtomicMutateRequest},
+    pb::v1::{
+        CertifiedResponse, HighCapacityRegistryAtomicMutateRequest, RegistryGetKeyHistoryRequest,
+    },
 };
 use ic_types::{
     CanisterId, RegistryVersion, SubnetId, Time, crypto::threshold_sig::ThresholdSigPublicKey,
 };
 use prost::Message;
 use serde::Deserialize;
-use std::{collections::BTreeMap, convert::TryFrom, fmt::Debug};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    fmt::Debug,
+};
 use tree_deserializer::{LabeledTreeDeserializer, types::Leb128EncodedU64};
 
 #[cfg(test)]
@@ -118,12 +125,237 @@ fn validate_version_range(
     Ok(p.current_version.0)
 }
 
-/// Decodes registry deltas from their hash tree representation.
-pub async fn decode_hash_tree(
-    since_version: u64,
+/// A certified, possibly partial, history of the registry key(s) requested
+/// from "get_certified_key_history".
+#[derive(Debug, PartialEq)]
+pub struct CertifiedKeyHistory {
+    /// The changes to the requested key(s), in ascending order of versions.
+    pub changes: Vec<RegistryRecord>,
+    /// The latest version available.
+    pub current_version: RegistryVersion,
+    /// If the changes did not fit into the response, the version of the first
+    /// omitted change, from which the request should be repeated.
+    pub next_version: Option<RegistryVersion>,
+    /// The time when the received data was last certified by the subnet.
+    pub time: Time,
+}
+
+/// The labeled children of a node of a mixed hash tree, in the order of their
+/// labels, where `None` stands for a pruned subtree that may hide any number
+/// of children.
+fn labeled_children(
+    tree: &MixedHashTree,
+) -> Result<Vec<Option<(&[u8], &MixedHashTree)>>, CertificationError> {
+    fn collect<'a>(
+        tree: &'a MixedHashTree,
+        children: &mut Vec<Option<(&'a [u8], &'a MixedHashTree)>>,
+    ) -> Result<(), CertificationError> {
+        match tree {
+            MixedHashTree::Empty => {}
+            MixedHashTree::Fork(lr) => {
+                collect(&lr.0, children)?;
+                collect(&lr.1, children)?;
+            }
+            MixedHashTree::Labeled(label, subtree) => {
+                children.push(Some((label.as_bytes(), subtree)));
+            }
+            MixedHashTree::Pruned(_) => children.push(None),
+            MixedHashTree::Leaf(_) => {
+                return Err(CertificationError::MalformedHashTree(
+                    "unexpected leaf in the key history index".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    let mut children = vec![];
+    collect(tree, &mut children)?;
+    Ok(children)
+}
+
+/// Checks that the witness of a node of the key history index reveals all of
+/// its labels within some range, i.e., that no pruned subtree lies between two
+/// labels (or the ends of the node) that are not both below or both above the
+/// range. `position` returns whether a label is below (`Less`), within
+/// (`Equal`) or above (`Greater`) the range.
+fn check_complete<L>(
+    labels: &[Option<L>],
+    position: impl Fn(&L) -> Ordering,
+    what: impl Fn() -> String,
+) -> Result<(), CertificationError> {
+    for (i, label) in labels.iter().enumerate() {
+        if label.is_some() {
+            continue;
+        }
+        let followed_by_label_below = labels[i + 1..]
+            .iter()
+            .find_map(Option::as_ref)
+            .is_some_and(|next| position(next) == Ordering::Less);
+        let preceded_by_label_above = labels[..i]
+            .iter()
+            .rev()
+            .find_map(Option::as_ref)
+            .is_some_and(|prev| position(prev) == Ordering::Greater);
+        if !followed_by_label_below && !preceded_by_label_above {
+            return Err(CertificationError::InvalidDeltas(format!(
+                "the key history index does not reveal all {}",
+                what()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Validates the payload of a "get_certified_key_history" response to
+/// `request`, whose hash tree is `hash_tree`, and returns the current version
+/// and the version of the first change omitted from the response, if any.
+///
+/// The certified key history index in the hash tree must reveal all versions
+/// in `[from_version, to_version]` (where a `to_version` of 0 stands for the
+/// current version) at which a requested key was mutated, and the payload must
+/// contain the deltas at all of those versions; or, if the response was
+/// truncated, at all of those versions below the first omitted one.
+fn validate_key_history(
+    request: &RegistryGetKeyHistoryRequest,
+    hash_tree: &MixedHashTree,
+    p: &CertifiedPayload,
+) -> Result<(u64, Option<u64>), CertificationError> {
+    let current_version = p.current_version.0;
+    let from_version = request.from_version;
+    let to_version = if request.to_version == 0 {
+        current_version
+    } else {
+        request.to_version.min(current_version)
+    };
+    let key_matches = |key: &[u8]| {
+        if request.key_is_prefix {
+            key.starts_with(&request.key)
+        } else {
+            key == request.key
+        }
+    };
+
+    for (version, atomic_mutation) in &p.delta {
+        if !(from_version..=to_version).contains(version) {
+            return Err(CertificationError::InvalidDeltas(format!(
+                "version {version} is outside of the requested range [{from_version}, {to_version}]",
+            )));
+        }
+        if !atomic_mutation
+            .0
+            .mutations
+            .iter()
+            .any(|mutation| key_matches(&mutation.key))
+        {
+            return Err(CertificationError::InvalidDeltas(format!(
+                "delta at version {version} does not mutate any requested key",
+            )));
+        }
+    }
+    if from_version > to_version {
+        return Ok((current_version, None));
+    }
+
+    let index = match hash_tree.lookup(&[b"key_history"]) {
+        LookupStatus::Found(index) => index,
+        LookupStatus::Absent | LookupStatus::Unknown => {
+            return Err(CertificationError::InvalidDeltas(
+                "the response does not reveal the key history index".to_string(),
+            ));
+        }
+    };
+    let keys = labeled_children(index)?;
+    check_complete(
+        &keys,
+        |(key, _)| {
+            if key_matches(*key) {
+                Ordering::Equal
+            } else {
+                (*key).cmp(request.key.as_slice())
+            }
+        },
+        || "requested keys".to_string(),
+    )?;
+
+    let mut versions_per_key = vec![];
+    for (key, versions) in keys.into_iter().flatten() {
+        if !key_matches(key) {
+            continue;
+        }
+        let versions = labeled_children(versions)?
+            .into_iter()
+            .map(|version| {
+                version
+                    .map(|(label, _)| {
+                        <[u8; 8]>::try_from(label)
+                            .map(u64::from_be_bytes)
+                            .map_err(|_| {
+                                CertificationError::MalformedHashTree(format!(
+                                    "invalid version label {label:?} in the key history index"
+                                ))
+                            })
+                    })
+                    .transpose()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        versions_per_key.push((key, versions));
+    }
+
+    // The versions in range at which a requested key was mutated, and the
+    // first of them whose delta is not part of the response.
+    let versions: BTreeSet<u64> = versions_per_key
+        .iter()
+        .flat_map(|(_, versions)| versions.iter().flatten().copied())
+        .filter(|version| (from_version..=to_version).contains(version))
+        .collect();
+    let next_version = versions
+        .iter()
+        .find(|version| !p.delta.contains_key(version))
+        .copied();
+
+    let complete_to_version = next_version.unwrap_or(to_version);
+    for (key, versions) in &versions_per_key {
+        check_complete(
+            versions,
+            |version| {
+                if *version < from_version {
+                    Ordering::Less
+                } else if *version > complete_to_version {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            },
+            || {
+                format!(
+                    "versions in [{from_version}, {complete_to_version}] of key {}",
+                    String::from_utf8_lossy(key)
+                )
+            },
+        )?;
+    }
+    for version in p.delta.keys() {
+        if !versions.contains(version) {
+            return Err(CertificationError::InvalidDeltas(format!(
+                "version {version} is not in the key history index of the requested keys",
+            )));
+        }
+        if let Some(next_version) = next_version
+            && *version > next_version
+        {
+            return Err(CertificationError::InvalidDeltas(format!(
+                "delta at version {version} follows the omitted version {next_version}",
+            )));
+        }
+    }
+
+    Ok((current_version, next_version))
+}
+
+fn decode_certified_payload(
     hash_tree: MixedHashTree,
-    get_chunk: &(impl GetChunk + Sync),
-) -> Result<(Vec<RegistryRecord>, RegistryVersion), CertificationError> {
+) -> Result<CertifiedPayload, CertificationError> {
     // Extract structured deltas from their tree representation.
     let labeled_tree = LabeledTree::<Vec<u8>>::try_from(hash_tree).map_err(|err| {
         CertificationError::MalformedHashTree(format!(
@@ -131,24 +363,28 @@ pub async fn decode_hash_tree(
         ))
     })?;
 
-    let certified_payload = CertifiedPayload::deserialize(LabeledTreeDeserializer::new(
-        &labeled_tree,
-    ))
-    .map_err(|err| {
+    CertifiedPayload::deserialize(LabeledTreeDeserializer::new(&labeled_tree)).map_err(|err| {
         CertificationError::DeserError(format!(
             "failed to unpack certified payload from the labeled tree: {err}"
         ))
-    })?;
-
-    // Validate that the deltas form a proper range and convert them to the
-    // format that RegistryClient wants.
-    let current_version = validate_version_range(since_version, &certified_payload)?;
+    })
+}
 
+/// Converts the mutations of the given deltas whose key matches `key_matches`
+/// to the format that RegistryClient wants.
+async fn deltas_to_registry_records(
+    deltas: BTreeMap<u64, Protobuf<HighCapacityRegistryAtomicMutateRequest>>,
+    key_matches: impl Fn(&[u8]) -> bool,
+    get_chunk: &(impl GetChunk + Sync),
+) -> Result<Vec<RegistryRecord>, CertificationError> {
     let mut changes = vec![];
-    for (version, atomic_mutation) in certified_payload.delta {
+    for (version, atomic_mutation) in deltas {
         let version = RegistryVersion::from(version);
 
         for mutation in atomic_mutation.0.mutations {
+            if !key_matches(&mutation.key) {
+                continue;
+            }
             let key = String::from_utf8_lossy(&mutation.key[..]).to_string();
             let value: Option<Vec<u8>> = dechunkify_mutation_value(mutation, get_chunk)
                 .await
@@ -161,23 +397,33 @@ pub async fn decode_hash_tree(
             });
         }
     }
+    Ok(changes)
+}
+
+/// Decodes registry deltas from their hash tree representation.
+pub async fn decode_hash_tree(
+    since_version: u64,
+    hash_tree: MixedHashTree,
+    get_chunk: &(impl GetChunk + Sync),
+) -> Result<(Vec<RegistryRecord>, RegistryVersion), CertificationError> {
+    let certified_payload = decode_certified_payload(hash_tree)?;
+
+    // Validate that the deltas form a proper range and convert them to the
+    // format that RegistryClient wants.
+    let current_version = validate_version_range(since_version, &certified_payload)?;
+    let changes = deltas_to_registry_records(certified_payload.delta, |_| true, get_chunk).await?;
 
     Ok((changes, RegistryVersion::from(current_version)))
 }
 
-/// Parses a response of the "get_certified_changes_since" registry method,
-/// validates data integrity and authenticity and returns
-///   * The list of changes to apply.
-///   * The latest version available (might be greater than the version of the
-///     last received delta if there were too many deltas to send in one go).
-///   * The time when the received data was last certified by the subnet.
-pub(crate) async fn decode_certified_deltas(
-    since_version: u64,
+/// Verifies the certificate of a response to any "*_certified" registry
+/// method, and returns the hash tree of the response together with the time
+/// on the certificate.
+fn verify_certified_response(
     canister_id: &CanisterId,
     nns_pk: &ThresholdSigPublicKey,
     payload: &[u8],
-    get_chunk: &(impl GetChunk + Sync),
-) -> Result<(Vec<RegistryRecord>, RegistryVersion, Time), CertificationError> {
+) -> Result<(MixedHashTree, Time), CertificationError> {
     let certified_response = CertifiedResponse::decode(payload).map_err(|err| {
         CertificationError::DeserError(format!(
             "failed to decode certified response from {canister_id}: {err:?}"
@@ -206,12 +452,69 @@ pub(crate) async fn decode_certified_deltas(
     )
     .map_err(embed_certificate_error)?;
 
+    Ok((mixed_hash_tree, time))
+}
+
+/// Parses a response of the "get_certified_changes_since" registry method,
+/// validates data integrity and authenticity and returns
+///   * The list of changes to apply.
+///   * The latest version available (might be greater than the version of the
+///     last received delta if there were too many deltas to send in one go).
+///   * The time when the received data was last certified by the subnet.
+pub(crate) async fn decode_certified_deltas(
+    since_version: u64,
+    canister_id: &CanisterId,
+    nns_pk: &ThresholdSigPublicKey,
+    payload: &[u8],
+    get_chunk: &(impl GetChunk + Sync),
+) -> Result<(Vec<RegistryRecord>, RegistryVersion, Time), CertificationError> {
+    let (mixed_hash_tree, time) = verify_certified_response(canister_id, nns_pk, payload)?;
+
     let (changes, current_version) =
         decode_hash_tree(since_version, mixed_hash_tree, get_chunk).await?;
 
     Ok((changes, current_version, time))
 }
 
+/// Parses a response of the "get_certified_key_history" registry method to
+/// `request`, validates data integrity and authenticity and returns the
+/// certified history of the requested key(s).
+///
+/// The certified key history index proves that the returned changes are all
+/// the changes to the requested key(s) in the requested range of versions. The
+/// canister omits versions beyond a size limit, in which case the history is
+/// complete up to (excluding) `next_version`, and the request should be
+/// repeated starting from there.
+pub(crate) async fn decode_certified_key_history(
+    request: &RegistryGetKeyHistoryRequest,
+    canister_id: &CanisterId,
+    nns_pk: &ThresholdSigPublicKey,
+    payload: &[u8],
+    get_chunk: &(impl GetChunk + Sync),
+) -> Result<CertifiedKeyHistory, CertificationError> {
+    let (mixed_hash_tree, time) = verify_certified_response(canister_id, nns_pk, payload)?;
+    let certified_payload = decode_certified_payload(mixed_hash_tree.clone())?;
+
+    let (current_version, next_version) =
+        validate_key_history(request, &mixed_hash_tree, &certified_payload)?;
+    let key_matches = |mutation_key: &[u8]| {
+        if request.key_is_prefix {
+            mutation_key.starts_with(&request.key)
+        } else {
+            mutation_key == request.key
+        }
+    };
+    let changes =
+        deltas_to_registry_records(certified_payload.delta, key_matches, get_chunk).await?;
+
+    Ok(CertifiedKeyHistory {
+        changes,
+        current_version: RegistryVersion::from(current_version),
+        next_version: next_version.map(RegistryVersion::from),
+        time,
+    })
+}
+
 /// An auxiliary type that instructs serde to deserialize blob as a protobuf
 /// message.
 struct Protobuf<T>(T);
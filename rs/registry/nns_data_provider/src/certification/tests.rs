@@ -10,8 +10,9 @@ use ic_interfaces_registry::RegistryRecord;
 use ic_registry_transport::{
     MockGetChunk, delete,
     pb::v1::{
-        CertifiedResponse, HighCapacityRegistryMutation, LargeValueChunkKeys, RegistryMutation,
-        high_capacity_registry_mutation, registry_mutation,
+        CertifiedResponse, HighCapacityRegistryMutation, LargeValueChunkKeys,
+        RegistryGetKeyHistoryRequest, RegistryMutation, high_capacity_registry_mutation,
+        registry_mutation,
     },
     upsert,
 };
@@ -21,7 +22,10 @@ use ic_types::{
 };
 use pretty_assertions::assert_eq;
 use prost::Message;
-use std::string::ToString;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    string::ToString,
+};
 
 const REPLICA_TIME: u64 = 1234567;
 
@@ -62,8 +66,26 @@ fn make_certified_delta(
     deltas: Vec<HighCapacityRegistryAtomicMutateRequest>,
     selection: impl std::ops::RangeBounds<u64>,
     garble_response: GarbleResponse,
+) -> (CanisterId, ThresholdSigPublicKey, EncodedResponse) {
+    make_certified_response(
+        deltas,
+        |version| selection.contains(&version),
+        None,
+        garble_response,
+    )
+}
+
+/// Like `make_certified_delta`, but reveals the deltas of arbitrary (i.e.,
+/// not necessarily contiguous) versions, and the given witness of the key
+/// history index.
+fn make_certified_response(
+    deltas: Vec<HighCapacityRegistryAtomicMutateRequest>,
+    is_selected: impl Fn(u64) -> bool,
+    key_history_witness: Option<LabeledTree<Vec<u8>>>,
+    garble_response: GarbleResponse,
 ) -> (CanisterId, ThresholdSigPublicKey, EncodedResponse) {
     let cid = CanisterId::from_u64(1);
+    let index = key_history_index(&deltas);
 
     let mut encoded_version = vec![];
 
@@ -95,11 +117,26 @@ fn make_certified_delta(
             b.write_leaf(&buf[..]);
             b.finish_leaf();
 
-            if selection.contains(&version) && !garble_response.should_drop_version(version) {
+            if is_selected(version) && !garble_response.should_drop_version(version) {
                 map.try_append(label, LabeledTree::Leaf(buf)).unwrap();
             }
         }
         b.finish_subtree();
+
+        b.new_edge(Label::from("key_history"));
+        b.start_subtree();
+        for (key, versions) in &index {
+            b.new_edge(Label::from(key));
+            b.start_subtree();
+            for version in versions {
+                b.new_edge(Label::from(version.to_be_bytes()));
+                b.start_leaf();
+                b.write_leaf(b"");
+                b.finish_leaf();
+            }
+            b.finish_subtree();
+        }
+        b.finish_subtree();
     }
     b.finish_subtree();
 
@@ -116,6 +153,10 @@ fn make_certified_delta(
         root.try_append(Label::from("delta"), LabeledTree::SubTree(map))
             .unwrap();
     }
+    if let Some(key_history_witness) = key_history_witness {
+        root.try_append(Label::from("key_history"), key_history_witness)
+            .unwrap();
+    }
     let data_tree = LabeledTree::SubTree(root);
 
     let mixed_hash_tree = witness_gen.mixed_hash_tree(&data_tree).unwrap();
@@ -460,3 +501,347 @@ fn test_evil_chunked() {
         _ => panic!("{result:?}"),
     }
 }
+
+/// Returns, for every key, the versions at which it was mutated.
+fn key_history_index(
+    deltas: &[HighCapacityRegistryAtomicMutateRequest],
+) -> BTreeMap<Vec<u8>, BTreeSet<u64>> {
+    let mut index: BTreeMap<Vec<u8>, BTreeSet<u64>> = BTreeMap::new();
+    for (i, delta) in deltas.iter().enumerate() {
+        for mutation in &delta.mutations {
+            index
+                .entry(mutation.key.clone())
+                .or_default()
+                .insert((i + 1) as u64);
+        }
+    }
+    index
+}
+
+fn key_history_request(
+    key: &str,
+    key_is_prefix: bool,
+    from_version: u64,
+    to_version: u64,
+) -> RegistryGetKeyHistoryRequest {
+    RegistryGetKeyHistoryRequest {
+        key: key.as_bytes().to_vec(),
+        key_is_prefix,
+        from_version,
+        to_version,
+    }
+}
+
+/// Builds the partial tree of the key history index that the registry
+/// canister reveals in response to `request`, i.e., all the versions of the
+/// requested key(s) in `[request.from_version, index_to_version]`, and the
+/// neighbors proving that there are no others.
+fn key_history_index_witness(
+    deltas: &[HighCapacityRegistryAtomicMutateRequest],
+    request: &RegistryGetKeyHistoryRequest,
+    index_to_version: u64,
+) -> LabeledTree<Vec<u8>> {
+    let key_matches = |key: &[u8]| {
+        if request.key_is_prefix {
+            key.starts_with(&request.key)
+        } else {
+            key == request.key
+        }
+    };
+
+    // Labels that are absent from the index are replaced by their neighbors.
+    let mut keys = BTreeMap::new();
+    keys.insert(request.key.clone(), LabeledTree::SubTree(FlatMap::new()));
+    if request.key_is_prefix {
+        let mut after_prefix = request.key.clone();
+        while after_prefix.last() == Some(&u8::MAX) {
+            after_prefix.pop();
+        }
+        if let Some(last) = after_prefix.last_mut() {
+            *last += 1;
+            keys.insert(after_prefix, LabeledTree::SubTree(FlatMap::new()));
+        }
+    }
+    for (key, versions) in key_history_index(deltas) {
+        if !key_matches(key.as_slice()) {
+            continue;
+        }
+        let versions: BTreeSet<u64> = versions
+            .range(request.from_version..=index_to_version)
+            .copied()
+            .chain(request.from_version.checked_sub(1))
+            .chain([index_to_version + 1])
+            .collect();
+        let versions = versions
+            .into_iter()
+            .map(|version| {
+                (
+                    Label::from(version.to_be_bytes()),
+                    LabeledTree::Leaf(vec![]),
+                )
+            })
+            .collect();
+        keys.insert(
+            key,
+            LabeledTree::SubTree(FlatMap::from_key_values(versions)),
+        );
+    }
+
+    LabeledTree::SubTree(FlatMap::from_key_values(
+        keys.into_iter()
+            .map(|(key, subtree)| (Label::from(key), subtree))
+            .collect(),
+    ))
+}
+
+/// Builds a response to `request` like the registry canister does, where at
+/// most `max_versions` versions fit into the response.
+fn make_certified_key_history(
+    deltas: Vec<HighCapacityRegistryAtomicMutateRequest>,
+    request: &RegistryGetKeyHistoryRequest,
+    max_versions: usize,
+    garble_response: GarbleResponse,
+) -> (CanisterId, ThresholdSigPublicKey, EncodedResponse) {
+    let current_version = deltas.len() as u64;
+    let to_version = if request.to_version == 0 {
+        current_version
+    } else {
+        request.to_version.min(current_version)
+    };
+    let key_matches = |key: &[u8]| {
+        if request.key_is_prefix {
+            key.starts_with(&request.key)
+        } else {
+            key == request.key
+        }
+    };
+    let versions: Vec<u64> = key_history_index(&deltas)
+        .into_iter()
+        .filter(|(key, _)| key_matches(key.as_slice()))
+        .flat_map(|(_, versions)| versions)
+        .filter(|version| (request.from_version..=to_version).contains(version))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let index_to_version = versions.get(max_versions).copied().unwrap_or(to_version);
+    let selected: Vec<u64> = versions.into_iter().take(max_versions).collect();
+
+    let key_history_witness = key_history_index_witness(&deltas, request, index_to_version);
+    make_certified_response(
+        deltas,
+        |version| selected.contains(&version),
+        Some(key_history_witness),
+        garble_response,
+    )
+}
+
+fn decode_certified_key_history_no_chunks(
+    request: &RegistryGetKeyHistoryRequest,
+    canister_id: &CanisterId,
+    nns_pk: &ThresholdSigPublicKey,
+    payload: &[u8],
+) -> Result<CertifiedKeyHistory, CertificationError> {
+    decode_certified_key_history(request, canister_id, nns_pk, payload, &MockGetChunk::new())
+        .now_or_never()
+        .unwrap()
+}
+
+fn key_history_deltas() -> Vec<HighCapacityRegistryAtomicMutateRequest> {
+    vec![
+        make_change(vec![upsert("node_1", "a"), upsert("subnet_1", "b")]),
+        make_change(vec![upsert("subnet_1", "c")]),
+        make_change(vec![upsert("node_2", "d")]),
+        make_change(vec![delete("node_1"), upsert("subnet_1", "e")]),
+    ]
+}
+
+fn certified_key_history(
+    changes: Vec<RegistryRecord>,
+    next_version: Option<u64>,
+) -> CertifiedKeyHistory {
+    CertifiedKeyHistory {
+        changes,
+        current_version: RegistryVersion::from(4u64),
+        next_version: next_version.map(RegistryVersion::from),
+        time: Time::from_nanos_since_unix_epoch(REPLICA_TIME),
+    }
+}
+
+#[test]
+fn test_decode_key_history() {
+    let request = key_history_request("node_1", false, 1, 0);
+    let (cid, pk, payload) = make_certified_key_history(
+        key_history_deltas(),
+        &request,
+        usize::MAX,
+        GarbleResponse::LeaveAsIs,
+    );
+    assert_eq!(
+        decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]).unwrap(),
+        certified_key_history(vec![set_key(1, "node_1", "a"), rem_key(4, "node_1")], None),
+    )
+}
+
+#[test]
+fn test_decode_key_history_with_prefix() {
+    let request = key_history_request("node_", true, 1, 4);
+    let (cid, pk, payload) = make_certified_key_history(
+        key_history_deltas(),
+        &request,
+        usize::MAX,
+        GarbleResponse::LeaveAsIs,
+    );
+    assert_eq!(
+        decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]).unwrap(),
+        certified_key_history(
+            vec![
+                set_key(1, "node_1", "a"),
+                set_key(3, "node_2", "d"),
+                rem_key(4, "node_1"),
+            ],
+            None
+        ),
+    )
+}
+
+#[test]
+fn test_decode_empty_key_history() {
+    for request in [
+        key_history_request("node_3", false, 1, 0),
+        key_history_request("node_3", true, 1, 0),
+        key_history_request("node_1", false, 2, 3),
+    ] {
+        let (cid, pk, payload) = make_certified_key_history(
+            key_history_deltas(),
+            &request,
+            usize::MAX,
+            GarbleResponse::LeaveAsIs,
+        );
+        assert_eq!(
+            decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]).unwrap(),
+            certified_key_history(vec![], None),
+        )
+    }
+}
+
+#[test]
+fn test_decode_truncated_key_history() {
+    let request = key_history_request("node_", true, 1, 0);
+    let (cid, pk, payload) =
+        make_certified_key_history(key_history_deltas(), &request, 2, GarbleResponse::LeaveAsIs);
+    assert_eq!(
+        decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]).unwrap(),
+        certified_key_history(
+            vec![set_key(1, "node_1", "a"), set_key(3, "node_2", "d")],
+            Some(4)
+        ),
+    )
+}
+
+#[test]
+fn test_decode_key_history_rejects_omitted_delta() {
+    // The index proves that node_1 was mutated at version 1, so the response
+    // may not omit it while returning the delta at version 4.
+    let request = key_history_request("node_1", false, 1, 0);
+    let (cid, pk, payload) = make_certified_key_history(
+        key_history_deltas(),
+        &request,
+        usize::MAX,
+        GarbleResponse::DropVersion(1),
+    );
+    match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+        Err(CertificationError::InvalidDeltas(_)) => (),
+        other => panic!("Expected InvalidDeltas error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_decode_key_history_rejects_incomplete_index() {
+    // Only version 1 is revealed in the index of node_1, so the response does
+    // not prove that node_1 was not mutated at later versions.
+    let request = key_history_request("node_1", false, 1, 0);
+    let key_history_witness = LabeledTree::SubTree(flatmap!(
+        Label::from("node_1") => LabeledTree::SubTree(flatmap!(
+            Label::from(1u64.to_be_bytes()) => LabeledTree::Leaf(vec![]),
+        )),
+        Label::from("node_2") => LabeledTree::SubTree(FlatMap::new()),
+    ));
+    let (cid, pk, payload) = make_certified_response(
+        key_history_deltas(),
+        |version| version == 1,
+        Some(key_history_witness),
+        GarbleResponse::LeaveAsIs,
+    );
+    match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+        Err(CertificationError::InvalidDeltas(_)) => (),
+        other => panic!("Expected InvalidDeltas error, got {other:?}"),
+    }
+
+    // Without the index, the response proves nothing about completeness.
+    let (cid, pk, payload) = make_certified_response(
+        key_history_deltas(),
+        |version| [1, 4].contains(&version),
+        None,
+        GarbleResponse::LeaveAsIs,
+    );
+    match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+        Err(CertificationError::InvalidDeltas(_)) => (),
+        other => panic!("Expected InvalidDeltas error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_decode_key_history_rejects_delta_not_touching_key() {
+    let deltas = key_history_deltas();
+    let request = key_history_request("node_1", false, 1, 0);
+    let key_history_witness = key_history_index_witness(&deltas, &request, 4);
+    let (cid, pk, payload) = make_certified_response(
+        deltas,
+        |version| [1, 2, 4].contains(&version),
+        Some(key_history_witness),
+        GarbleResponse::LeaveAsIs,
+    );
+    match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+        Err(CertificationError::InvalidDeltas(_)) => (),
+        other => panic!("Expected InvalidDeltas error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_decode_key_history_rejects_delta_outside_of_range() {
+    let deltas = key_history_deltas();
+    let key_history_witness =
+        key_history_index_witness(&deltas, &key_history_request("node_1", false, 1, 0), 4);
+    let (cid, pk, payload) = make_certified_response(
+        deltas,
+        |version| [1, 4].contains(&version),
+        Some(key_history_witness),
+        GarbleResponse::LeaveAsIs,
+    );
+    for request in [
+        key_history_request("node_1", false, 2, 0),
+        key_history_request("node_1", false, 1, 3),
+    ] {
+        match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+            Err(CertificationError::InvalidDeltas(_)) => (),
+            other => panic!("Expected InvalidDeltas error, got {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_decode_key_history_bad_root_hash() {
+    let bad_digest = Digest([0u8; 32]);
+
+    let request = key_history_request("node_1", false, 1, 0);
+    let (cid, pk, payload) = make_certified_key_history(
+        key_history_deltas(),
+        &request,
+        usize::MAX,
+        GarbleResponse::OverrideCertifiedData(bad_digest.clone()),
+    );
+    match decode_certified_key_history_no_chunks(&request, &cid, &pk, &payload[..]) {
+        Err(CertificationError::CertifiedDataMismatch { .. }) => (),
+        other => panic!("Expected CertifiedDataMismatch error, got {other:?}"),
+    }
+}
@@ -5,6 +5,7 @@ use rand::seq::SliceRandom;
 use std::time::Duration;
 use url::Url;
 
+use crate::certification::CertifiedKeyHistory;
 use ic_canister_client::{Agent, Sender};
 use ic_interfaces_registry::RegistryRecord;
 use ic_registry_canister_api::{Chunk, GetChunkRequest};
@@ -12,7 +13,10 @@ use ic_registry_transport::{
     Error, GetChunk, dechunkify_delta, dechunkify_get_value_response_content,
     deserialize_atomic_mutate_response, deserialize_get_changes_since_response,
     deserialize_get_value_response,
-    pb::v1::{Precondition, RegistryDelta, RegistryGetLatestVersionResponse, RegistryMutation},
+    pb::v1::{
+        Precondition, RegistryDelta, RegistryGetKeyHistoryRequest,
+        RegistryGetLatestVersionResponse, RegistryMutation,
+    },
     serialize_atomic_mutate_request, serialize_get_changes_since_request,
     serialize_get_value_request,
 };
@@ -206,6 +210,63 @@ impl RegistryCanister {
         .map_err(|err| Error::UnknownError(format!("{err:?}")))
     }
 
+    /// Returns the certified changes to `key` (or, if `key_is_prefix`, to all
+    /// keys starting with `key`) at versions in `[from_version, to_version]`,
+    /// where a `to_version` of 0 stands for the latest version.
+    ///
+    /// The changes are certified to be complete. Like
+    /// `get_certified_changes_since`, a single call may not return all changes
+    /// if they are too large to fit into one response; in that case, call
+    /// again starting from the returned `next_version`.
+    pub async fn get_certified_key_history(
+        &self,
+        key: &[u8],
+        key_is_prefix: bool,
+        from_version: u64,
+        to_version: u64,
+        nns_public_key: &ThresholdSigPublicKey,
+    ) -> Result<CertifiedKeyHistory, Error> {
+        let request = RegistryGetKeyHistoryRequest {
+            key: key.to_vec(),
+            key_is_prefix,
+            from_version,
+            to_version,
+        };
+        let response = self
+            .choose_random_agent()
+            .execute_query(
+                &self.canister_id,
+                "get_certified_key_history",
+                request.encode_to_vec(),
+            )
+            .await
+            .map_err(|err| {
+                Error::UnknownError(format!(
+                    "Failed to query get_certified_key_history on canister {}: {}",
+                    self.canister_id, err,
+                ))
+            })?
+            .ok_or_else(|| {
+                Error::UnknownError(format!(
+                    "No response was received when queried get_certified_key_history on {}",
+                    self.canister_id,
+                ))
+            })?;
+
+        crate::certification::decode_certified_key_history(
+            &request,
+            &self.canister_id,
+            nns_public_key,
+            &response[..],
+            &AgentBasedGetChunk {
+                registry_canister_id: self.canister_id,
+                agent: self.choose_random_agent(),
+            },
+        )
+        .await
+        .map_err(|err| Error::UnknownError(format!("{err:?}")))
+    }
+
     pub async fn get_latest_version(&self) -> Result<u64, Error> {
         let agent = self.choose_random_agent();
         match agent
@@ -35,6 +35,25 @@ pub struct Delta {
     #[prost(message, repeated, tag = "2")]
     pub changelog: ::prost::alloc::vec::Vec<ChangelogEntry>,
 }
+/// The latest mutation of every key up to a registry version. A checkpoint
+/// replaces the changelog entries up to its version.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Checkpoint {
+    #[prost(uint64, tag = "1")]
+    pub registry_version: u64,
+    /// Ordered by key. Deleted keys are kept as UNSET mutations, so that the
+    /// changes since any earlier version can be derived from the checkpoint.
+    #[prost(message, repeated, tag = "2")]
+    pub key_mutations: ::prost::alloc::vec::Vec<VersionedKeyMutation>,
+}
+/// A key mutation along with the registry version at which it was applied.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VersionedKeyMutation {
+    #[prost(uint64, tag = "1")]
+    pub version: u64,
+    #[prost(message, optional, tag = "2")]
+    pub key_mutation: ::core::option::Option<KeyMutation>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum MutationType {
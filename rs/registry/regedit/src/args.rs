@@ -2,6 +2,7 @@ use anyhow::{Context, Result, bail};
 use clap::Parser;
 use ic_crypto_utils_threshold_sig_der::threshold_sig_public_key_from_der;
 use ic_registry_client::client::RegistryVersion;
+use ic_registry_local_store::HistoryRetention;
 use ic_types::crypto::threshold_sig::ThresholdSigPublicKey;
 use serde_json::Value;
 use std::fmt;
@@ -129,6 +130,91 @@ pub enum CommandArg {
         /// Path to the local store (may not be specified together with --url).
         snapshot_file: PathBuf,
     },
+    /// Prints the registry canister proposal payloads that turn the given
+    /// registry version into the snapshot, and validates them in a dry run.
+    ///
+    /// Note: The dry run executes the registry canister's code, which logs to
+    /// stdout.
+    ProposalPayloads {
+        /// The registry version the snapshot is based on. (default: latest
+        /// available version.)
+        #[clap(short, long, allow_hyphen_values = true)]
+        version: Option<i64>,
+
+        /// Path to the local store (may not be specified together with --url).
+        local_store_path: PathBuf,
+
+        /// Path to the edited snapshot.
+        snapshot_file: PathBuf,
+    },
+    CanisterProposalPayloads {
+        /// Url to a node hosting the registry canister (may not be specified
+        /// together with --local-store).
+        #[clap(long)]
+        url: Url,
+
+        /// Optional path to the threshold public key of the root subnet
+        /// (a.k.a. NNS public key). One way to get this key is via
+        /// "ic-admin --nns-url https://nns.ic0.app  get-subnet-public-key"
+        #[clap(long)]
+        nns_public_key: Option<PathBuf>,
+
+        /// The registry version the snapshot is based on. (default: latest
+        /// available version.)
+        #[clap(short, long, allow_hyphen_values = true)]
+        version: Option<i64>,
+
+        /// Path to the edited snapshot.
+        snapshot_file: PathBuf,
+    },
+    /// Checks the registry canister's invariants against the given registry
+    /// version or, if provided, against the snapshot, and lists every violated
+    /// invariant. Exits with a non-zero status if any invariant is violated.
+    CheckInvariants {
+        /// The registry version to check or, if a snapshot is provided, the
+        /// version the snapshot is based on. (default: latest available
+        /// version.)
+        #[clap(short, long, allow_hyphen_values = true)]
+        version: Option<i64>,
+
+        /// Path to the local store (may not be specified together with --url).
+        local_store_path: PathBuf,
+
+        /// Optional path to an edited snapshot.
+        snapshot_file: Option<PathBuf>,
+    },
+    CanisterCheckInvariants {
+        /// Url to a node hosting the registry canister (may not be specified
+        /// together with --local-store).
+        #[clap(long)]
+        url: Url,
+
+        /// Optional path to the threshold public key of the root subnet
+        /// (a.k.a. NNS public key). One way to get this key is via
+        /// "ic-admin --nns-url https://nns.ic0.app  get-subnet-public-key"
+        #[clap(long)]
+        nns_public_key: Option<PathBuf>,
+
+        /// The registry version to check or, if a snapshot is provided, the
+        /// version the snapshot is based on. (default: latest available
+        /// version.)
+        #[clap(short, long, allow_hyphen_values = true)]
+        version: Option<i64>,
+
+        /// Optional path to an edited snapshot.
+        snapshot_file: Option<PathBuf>,
+    },
+    /// Folds the changelog of the local store into a checkpoint and, if
+    /// requested, deletes the changelog entries that are not retained.
+    CompactLocalStore {
+        /// The number of most recent versions whose changelog entries are
+        /// kept. (default: all versions are kept.)
+        #[clap(long)]
+        retained_versions: Option<u64>,
+
+        /// Path to the local store.
+        local_store_path: PathBuf,
+    },
 }
 
 impl CliArgs {
@@ -223,6 +309,75 @@ impl CliArgs {
                     snapshot,
                 }
             }
+            CommandArg::ProposalPayloads {
+                local_store_path,
+                version,
+                snapshot_file,
+            } => {
+                let version: VersionSpec = version.into();
+                let source = SourceSpec::LocalStore(Self::is_dir(local_store_path)?);
+                let snapshot = Self::read_json_value(snapshot_file)?;
+
+                Command::ProposalPayloads {
+                    registry_spec: RegistrySpec { version, source },
+                    snapshot,
+                }
+            }
+            CommandArg::CanisterProposalPayloads {
+                url,
+                nns_public_key,
+                version,
+                snapshot_file,
+            } => {
+                let version: VersionSpec = version.into();
+                let nns_key_material = get_key_material(nns_public_key)?;
+                let source = SourceSpec::Canister(url, nns_key_material);
+                let snapshot = Self::read_json_value(snapshot_file)?;
+
+                Command::ProposalPayloads {
+                    registry_spec: RegistrySpec { version, source },
+                    snapshot,
+                }
+            }
+            CommandArg::CheckInvariants {
+                local_store_path,
+                version,
+                snapshot_file,
+            } => {
+                let version: VersionSpec = version.into();
+                let source = SourceSpec::LocalStore(Self::is_dir(local_store_path)?);
+                let snapshot = snapshot_file.map(Self::read_json_value).transpose()?;
+
+                Command::CheckInvariants {
+                    registry_spec: RegistrySpec { version, source },
+                    snapshot,
+                }
+            }
+            CommandArg::CanisterCheckInvariants {
+                url,
+                nns_public_key,
+                version,
+                snapshot_file,
+            } => {
+                let version: VersionSpec = version.into();
+                let nns_key_material = get_key_material(nns_public_key)?;
+                let source = SourceSpec::Canister(url, nns_key_material);
+                let snapshot = snapshot_file.map(Self::read_json_value).transpose()?;
+
+                Command::CheckInvariants {
+                    registry_spec: RegistrySpec { version, source },
+                    snapshot,
+                }
+            }
+            CommandArg::CompactLocalStore {
+                retained_versions,
+                local_store_path,
+            } => Command::CompactLocalStore {
+                local_store_path: Self::is_dir(local_store_path)?,
+                retention: retained_versions
+                    .map(HistoryRetention::Versions)
+                    .unwrap_or(HistoryRetention::All),
+            },
         };
         Ok(res)
     }
@@ -319,6 +474,18 @@ pub enum Command {
         snapshot: Value,
         amend: bool,
     },
+    ProposalPayloads {
+        registry_spec: RegistrySpec,
+        snapshot: Value,
+    },
+    CheckInvariants {
+        registry_spec: RegistrySpec,
+        snapshot: Option<Value>,
+    },
+    CompactLocalStore {
+        local_store_path: PathBuf,
+        retention: HistoryRetention,
+    },
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
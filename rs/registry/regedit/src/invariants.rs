@@ -0,0 +1,44 @@
+//! Offline checks of the invariants that the registry canister enforces when
+//! mutations are applied on chain.
+use crate::{
+    protobuf,
+    snapshot::{SPECIAL_FIELD_PREFIX, Snapshot},
+};
+use anyhow::{Result, anyhow};
+use registry_canister::invariants::RegistrySnapshot;
+use serde_json::{Value, json};
+
+/// Converts an (expanded) snapshot into the raw key/value pairs the invariant
+/// checks operate on.
+pub fn snapshot_to_registry_snapshot(snapshot: Snapshot) -> Result<RegistrySnapshot> {
+    let obj = snapshot
+        .0
+        .as_object()
+        .ok_or_else(|| anyhow!("Expected an object."))?;
+    Ok(obj
+        .iter()
+        .filter(|(k, _)| !k.starts_with(SPECIAL_FIELD_PREFIX))
+        .map(|(k, v)| {
+            (
+                k.clone().into_bytes(),
+                protobuf::value_to_raw_data(k, v.clone()),
+            )
+        })
+        .collect())
+}
+
+/// Runs the full invariant suite against the snapshot and lists every
+/// violation along with the keys of the records that the check tripped on.
+pub fn check_invariants(snapshot: &RegistrySnapshot) -> Value {
+    let violations: Vec<_> = registry_canister::invariants::check_invariants(snapshot)
+        .into_iter()
+        .map(|violation| {
+            json!({
+                "invariant": violation.invariant,
+                "message": violation.message,
+                "keys": violation.keys,
+            })
+        })
+        .collect();
+    json!({ "violations": violations })
+}
@@ -1,8 +1,10 @@
 pub mod args;
 mod diff;
+mod invariants;
 mod json;
 mod normalization;
 mod projection;
+mod proposal;
 mod protobuf;
 mod snapshot;
 mod source;
@@ -12,7 +14,7 @@ use anyhow::{Result, anyhow};
 use args::{Command, RegistrySpec, SourceSpec, VersionSpec, universal_projection};
 use ic_base_types::RegistryVersion;
 use ic_registry_local_store::{
-    KeyMutation, LocalStoreImpl, LocalStoreWriter, changelog_to_compact_delta,
+    HistoryRetention, KeyMutation, LocalStoreImpl, LocalStoreWriter, changelog_to_compact_delta,
 };
 use normalization::NormalizedSnapshot;
 use serde_json::Value;
@@ -131,6 +133,61 @@ pub fn execute_command(cmd: Command) -> Result<Value> {
             local_store.store(v, changelog_entry)?;
             diff.0
         }
+        Command::ProposalPayloads {
+            registry_spec,
+            snapshot,
+        } => {
+            let changelog = source::get_changelog(registry_spec.source)?;
+            let base_snapshot =
+                snapshot::changelog_to_snapshot(changelog.clone(), registry_spec.version)?;
+            let base_version = RegistryVersion::from(diff::snapshot_to_version(&base_snapshot.0)?);
+
+            let (_, inv_map) = normalization::normalize(base_snapshot.0.clone());
+            let expanded_snapshot = normalization::expand(&inv_map, NormalizedSnapshot(snapshot));
+            let diff = diff::make_diff(base_snapshot, expanded_snapshot)?;
+            let (_, changelog_entry) = diff::diff_to_changelog_entry(diff)?;
+            proposal::make_proposal_payloads(changelog, base_version, changelog_entry)?
+        }
+        Command::CheckInvariants {
+            registry_spec,
+            snapshot,
+        } => {
+            let changelog = source::get_changelog(registry_spec.source)?;
+            let registry_snapshot = match snapshot {
+                None => {
+                    let (values, _) =
+                        snapshot::changelog_to_raw_values(changelog, registry_spec.version)?;
+                    values
+                        .into_iter()
+                        .map(|(k, v)| (k.into_bytes(), v))
+                        .collect()
+                }
+                Some(snapshot) => {
+                    let base_snapshot =
+                        snapshot::changelog_to_snapshot(changelog, registry_spec.version)?;
+                    let (_, inv_map) = normalization::normalize(base_snapshot.0);
+                    let expanded_snapshot =
+                        normalization::expand(&inv_map, NormalizedSnapshot(snapshot));
+                    invariants::snapshot_to_registry_snapshot(expanded_snapshot)?
+                }
+            };
+            invariants::check_invariants(&registry_snapshot)
+        }
+        Command::CompactLocalStore {
+            local_store_path,
+            retention,
+        } => {
+            let local_store = LocalStoreImpl::new(&local_store_path);
+            let checkpoint_version = local_store.compact(retention)?;
+            let retained = match retention {
+                HistoryRetention::All => "all versions".to_string(),
+                HistoryRetention::Versions(n) => format!("the latest {n} versions"),
+            };
+            Value::String(format!(
+                "Compacted local store at {} into a checkpoint at version {checkpoint_version}, keeping the changelog of {retained}.",
+                local_store_path.display()
+            ))
+        }
     };
     Ok(res)
 }
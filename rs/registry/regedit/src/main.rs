@@ -6,8 +6,15 @@ use clap::Parser;
 #[tokio::main]
 async fn main() -> Result<()> {
     let cmd = ic_regedit::args::CliArgs::parse().validate()?;
+    let is_invariant_check = matches!(cmd, ic_regedit::args::Command::CheckInvariants { .. });
     let out = ic_regedit::execute_command(cmd)?;
+    let has_violations = out["violations"]
+        .as_array()
+        .is_some_and(|violations| !violations.is_empty());
     let out = serde_json::to_string_pretty(&out).expect("Could not pretty print value.");
     println!("{out}");
+    if is_invariant_check && has_violations {
+        std::process::exit(1);
+    }
     Ok(())
 }
@@ -0,0 +1,414 @@
+//! Translates an edited snapshot into registry canister proposal payloads.
+//!
+//! Each changed record is mapped to the registry canister method that can
+//! produce the change, e.g., `update_subnet` for a changed subnet record. The
+//! resulting payloads are then applied, in a dry run, to a registry canister
+//! state built from the base version, which runs the same validation as the
+//! canister on mainnet. The invariants are checked after every payload, and
+//! only violations that do not already exist at the base version are
+//! reported.
+use crate::source::Changelog;
+use anyhow::Result;
+use candid::Encode;
+use ic_base_types::{PrincipalId, RegistryVersion, SubnetId};
+use ic_protobuf::registry::{node_operator::v1::NodeOperatorRecord, subnet::v1::SubnetRecord};
+use ic_registry_client::client::RegistryRecord;
+use ic_registry_keys::{
+    NODE_OPERATOR_RECORD_KEY_PREFIX, SUBNET_RECORD_KEY_PREFIX, get_node_operator_id_from_record_key,
+};
+use ic_registry_local_store::ChangelogEntry;
+use ic_registry_transport::pb::v1::{
+    HighCapacityRegistryAtomicMutateRequest, HighCapacityRegistryMutation,
+    high_capacity_registry_mutation, high_capacity_registry_value, registry_mutation,
+};
+use prost::Message;
+use registry_canister::{
+    invariants::{InvariantViolation, check_invariants},
+    mutations::{
+        do_update_node_operator_config::UpdateNodeOperatorConfigPayload,
+        do_update_subnet::UpdateSubnetPayload,
+    },
+    pb::v1::{
+        ChangelogEntry as StableChangelogEntry, RegistryStableStorage, registry_stable_storage,
+    },
+    registry::Registry,
+};
+use serde_json::{Value, json};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    panic::{AssertUnwindSafe, catch_unwind},
+    str::FromStr,
+};
+
+/// A call of a registry canister method, as executed by an adopted proposal.
+#[derive(Clone, Debug)]
+pub enum ProposalPayload {
+    UpdateSubnet(UpdateSubnetPayload),
+    UpdateNodeOperatorConfig(UpdateNodeOperatorConfigPayload),
+}
+
+impl ProposalPayload {
+    pub fn method_name(&self) -> &'static str {
+        match self {
+            Self::UpdateSubnet(_) => "update_subnet",
+            Self::UpdateNodeOperatorConfig(_) => "update_node_operator_config",
+        }
+    }
+
+    /// Returns the method, the payload in readable form, and the payload as
+    /// hex-encoded Candid, which is what `ic-admin` expects.
+    fn to_json(&self) -> Value {
+        let (payload, candid) = match self {
+            Self::UpdateSubnet(payload) => (json!(payload), Encode!(payload)),
+            Self::UpdateNodeOperatorConfig(payload) => (json!(payload), Encode!(payload)),
+        };
+        let candid = candid.expect("Failed to encode the payload as Candid.");
+        json!({
+            "method": self.method_name(),
+            "payload": payload,
+            "candid_hex": hex::encode(candid),
+        })
+    }
+
+    fn apply(self, registry: &mut Registry) {
+        match self {
+            Self::UpdateSubnet(payload) => registry.do_update_subnet(payload),
+            Self::UpdateNodeOperatorConfig(payload) => {
+                registry.do_update_node_operator_config(payload)
+            }
+        }
+    }
+}
+
+/// A payload together with the record it is expected to produce.
+struct ProposedChange {
+    key: String,
+    payload: ProposalPayload,
+    expected_value: Vec<u8>,
+}
+
+/// Maps the `changes` to the base version of `changelog` to proposal payloads
+/// and validates them in a dry run.
+///
+/// Changes that cannot be expressed by any supported registry canister method
+/// are listed in `unsupported_changes` and are not part of the dry run.
+pub fn make_proposal_payloads(
+    changelog: Changelog,
+    base_version: RegistryVersion,
+    changes: ChangelogEntry,
+) -> Result<Value> {
+    let (mut records, _) = changelog;
+    records.retain(|r| r.version <= base_version);
+    records.sort_by_key(|r| r.version);
+
+    let mut base_values = BTreeMap::new();
+    for record in records.iter() {
+        match &record.value {
+            Some(value) => base_values.insert(record.key.as_str(), value.as_slice()),
+            None => base_values.remove(record.key.as_str()),
+        };
+    }
+
+    let mut proposed_changes = vec![];
+    let mut unsupported_changes = vec![];
+    for mutation in changes {
+        let old_value = base_values.get(mutation.key.as_str()).copied();
+        match to_proposal_payload(&mutation.key, old_value, mutation.value.as_deref()) {
+            Ok(payload) => proposed_changes.push(ProposedChange {
+                key: mutation.key,
+                payload,
+                expected_value: mutation.value.unwrap_or_default(),
+            }),
+            Err(reason) => {
+                unsupported_changes.push(json!({ "key": mutation.key, "reason": reason }))
+            }
+        }
+    }
+
+    let payloads: Vec<_> = proposed_changes
+        .iter()
+        .map(|c| c.payload.to_json())
+        .collect();
+    let dry_run_errors = dry_run(&records, proposed_changes);
+
+    Ok(json!({
+        "base_version": base_version.get(),
+        "payloads": payloads,
+        "unsupported_changes": unsupported_changes,
+        "dry_run": {
+            "valid": dry_run_errors.is_empty(),
+            "errors": dry_run_errors,
+        },
+    }))
+}
+
+fn to_proposal_payload(
+    key: &str,
+    old_value: Option<&[u8]>,
+    new_value: Option<&[u8]>,
+) -> Result<ProposalPayload, String> {
+    let (old_value, new_value) = match (old_value, new_value) {
+        (Some(old_value), Some(new_value)) => (old_value, new_value),
+        (None, _) => return Err("creating records is not supported".to_string()),
+        (_, None) => return Err("deleting records is not supported".to_string()),
+    };
+    let decode_error = |e: prost::DecodeError| format!("failed to decode record: {e}");
+
+    if let Some(subnet_id) = key.strip_prefix(SUBNET_RECORD_KEY_PREFIX) {
+        let subnet_id = PrincipalId::from_str(subnet_id)
+            .map(SubnetId::from)
+            .map_err(|e| format!("invalid subnet id: {e}"))?;
+        let old = SubnetRecord::decode(old_value).map_err(decode_error)?;
+        let new = SubnetRecord::decode(new_value).map_err(decode_error)?;
+        update_subnet_payload(subnet_id, old, &new).map(ProposalPayload::UpdateSubnet)
+    } else if key.starts_with(NODE_OPERATOR_RECORD_KEY_PREFIX) {
+        let node_operator_id = get_node_operator_id_from_record_key(key)
+            .ok_or_else(|| "invalid node operator id".to_string())?;
+        let old = NodeOperatorRecord::decode(old_value).map_err(decode_error)?;
+        let new = NodeOperatorRecord::decode(new_value).map_err(decode_error)?;
+        update_node_operator_config_payload(node_operator_id, old, &new)
+            .map(ProposalPayload::UpdateNodeOperatorConfig)
+    } else {
+        Err("no proposal type is known for this key".to_string())
+    }
+}
+
+/// Copies `$field` from `$new` to `$old` and sets it in `$payload` if it
+/// changed.
+macro_rules! set_if_changed {
+    ($payload:ident, $old:ident, $new:ident, $field:ident) => {
+        if $old.$field != $new.$field {
+            $old.$field = $new.$field.clone();
+            $payload.$field = Some($new.$field.clone());
+        }
+    };
+}
+
+/// Returns the payload that turns `old` into `new`. Fails if a field changed
+/// that `update_subnet` cannot set.
+fn update_subnet_payload(
+    subnet_id: SubnetId,
+    mut old: SubnetRecord,
+    new: &SubnetRecord,
+) -> Result<UpdateSubnetPayload, String> {
+    let mut payload = UpdateSubnetPayload {
+        subnet_id,
+        max_ingress_bytes_per_message: None,
+        max_ingress_messages_per_block: None,
+        max_block_payload_size: None,
+        unit_delay_millis: None,
+        initial_notary_delay_millis: None,
+        dkg_interval_length: None,
+        dkg_dealings_per_block: None,
+        start_as_nns: None,
+        subnet_type: None,
+        is_halted: None,
+        halt_at_cup_height: None,
+        features: None,
+        chain_key_config: None,
+        chain_key_signing_enable: None,
+        chain_key_signing_disable: None,
+        max_number_of_canisters: None,
+        ssh_readonly_access: None,
+        ssh_backup_access: None,
+        adaptive_block_time_config: None,
+        max_artifact_streams_per_peer: None,
+        max_chunk_wait_ms: None,
+        max_duplicity: None,
+        max_chunk_size: None,
+        receive_check_cache_size: None,
+        pfn_evaluation_period_ms: None,
+        registry_poll_period_ms: None,
+        retransmission_request_ms: None,
+        set_gossip_config_to_default: false,
+    };
+
+    set_if_changed!(payload, old, new, max_ingress_bytes_per_message);
+    set_if_changed!(payload, old, new, max_ingress_messages_per_block);
+    set_if_changed!(payload, old, new, max_block_payload_size);
+    set_if_changed!(payload, old, new, unit_delay_millis);
+    set_if_changed!(payload, old, new, initial_notary_delay_millis);
+    set_if_changed!(payload, old, new, dkg_interval_length);
+    set_if_changed!(payload, old, new, dkg_dealings_per_block);
+    set_if_changed!(payload, old, new, start_as_nns);
+    set_if_changed!(payload, old, new, is_halted);
+    set_if_changed!(payload, old, new, halt_at_cup_height);
+    set_if_changed!(payload, old, new, max_number_of_canisters);
+    set_if_changed!(payload, old, new, ssh_readonly_access);
+    set_if_changed!(payload, old, new, ssh_backup_access);
+    if old.features != new.features && new.features.is_some() {
+        old.features = new.features.clone();
+        payload.features = new.features.clone();
+    }
+    if old.adaptive_block_time_config != new.adaptive_block_time_config
+        && new.adaptive_block_time_config.is_some()
+    {
+        old.adaptive_block_time_config = new.adaptive_block_time_config;
+        payload.adaptive_block_time_config = new.adaptive_block_time_config;
+    }
+
+    if old != *new {
+        return Err(
+            "update_subnet cannot change the membership, replica version, subnet type, \
+             chain key configuration, or unset the features or adaptive block time \
+             configuration of a subnet"
+                .to_string(),
+        );
+    }
+    Ok(payload)
+}
+
+/// Returns the payload that turns `old` into `new`. Fails if a field changed
+/// that `update_node_operator_config` cannot set.
+fn update_node_operator_config_payload(
+    node_operator_id: PrincipalId,
+    mut old: NodeOperatorRecord,
+    new: &NodeOperatorRecord,
+) -> Result<UpdateNodeOperatorConfigPayload, String> {
+    let mut payload = UpdateNodeOperatorConfigPayload {
+        node_operator_id: Some(node_operator_id),
+        ..Default::default()
+    };
+
+    set_if_changed!(payload, old, new, node_allowance);
+    set_if_changed!(payload, old, new, dc_id);
+    if old.ipv6 != new.ipv6 {
+        old.ipv6 = new.ipv6.clone();
+        match &new.ipv6 {
+            Some(ipv6) => payload.ipv6 = Some(ipv6.clone()),
+            None => payload.set_ipv6_to_none = Some(true),
+        }
+    }
+    if old.node_provider_principal_id != new.node_provider_principal_id {
+        let node_provider_id = PrincipalId::try_from(new.node_provider_principal_id.as_slice())
+            .map_err(|e| format!("invalid node provider id: {e}"))?;
+        old.node_provider_principal_id = new.node_provider_principal_id.clone();
+        payload.node_provider_id = Some(node_provider_id);
+    }
+    // Empty maps in the payload leave the record unchanged.
+    if old.rewardable_nodes != new.rewardable_nodes && !new.rewardable_nodes.is_empty() {
+        old.rewardable_nodes = new.rewardable_nodes.clone();
+        payload.rewardable_nodes = new.rewardable_nodes.clone();
+    }
+    if old.max_rewardable_nodes != new.max_rewardable_nodes && !new.max_rewardable_nodes.is_empty()
+    {
+        old.max_rewardable_nodes = new.max_rewardable_nodes.clone();
+        payload.max_rewardable_nodes = Some(new.max_rewardable_nodes.clone());
+    }
+
+    if old != *new {
+        return Err(
+            "update_node_operator_config cannot change the node operator id or clear \
+             the (maximum) rewardable nodes of a node operator"
+                .to_string(),
+        );
+    }
+    Ok(payload)
+}
+
+/// Applies the changes, in order, to the registry canister state at the base
+/// version and returns an error for every change that is rejected, does not
+/// produce the expected record, or violates an invariant that holds at the
+/// base version.
+fn dry_run(records: &[RegistryRecord], changes: Vec<ProposedChange>) -> Vec<Value> {
+    let mut registry = registry_from_records(records);
+    // The base version may already violate invariants, in which case the
+    // canister would reject every change. The invariants are checked below
+    // instead, so that such violations are not attributed to the changes.
+    registry.disable_global_state_invariant_checks();
+    let mut known_violations: BTreeSet<_> = check_invariants(&registry.take_latest_snapshot())
+        .into_iter()
+        .map(|violation| (violation.invariant, violation.message))
+        .collect();
+
+    let mut errors = vec![];
+    for change in changes {
+        let method = change.payload.method_name();
+        // The registry canister rejects invalid mutations by panicking.
+        if let Err(panic) = catch_unwind(AssertUnwindSafe(|| change.payload.apply(&mut registry))) {
+            let error = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "unknown error".to_string());
+            errors.push(json!({ "key": change.key, "method": method, "error": error }));
+            continue;
+        }
+
+        let content = registry
+            .get_high_capacity(change.key.as_bytes(), registry.latest_version())
+            .and_then(|value| value.content.clone());
+        if content
+            != Some(high_capacity_registry_value::Content::Value(
+                change.expected_value,
+            ))
+        {
+            errors.push(json!({
+                "key": change.key,
+                "method": method,
+                "error": "the resulting record differs from the edited snapshot",
+            }));
+        }
+
+        for InvariantViolation {
+            invariant,
+            message,
+            keys,
+        } in check_invariants(&registry.take_latest_snapshot())
+        {
+            if known_violations.insert((invariant, message.clone())) {
+                errors.push(json!({
+                    "key": change.key,
+                    "method": method,
+                    "error": format!("violates the {invariant} invariants: {message}"),
+                    "invariant_keys": keys,
+                }));
+            }
+        }
+    }
+    errors
+}
+
+fn registry_from_records(records: &[RegistryRecord]) -> Registry {
+    let mut mutations_by_version = BTreeMap::<_, Vec<_>>::new();
+    for record in records {
+        let mutation = match &record.value {
+            Some(value) => HighCapacityRegistryMutation {
+                mutation_type: registry_mutation::Type::Upsert as i32,
+                key: record.key.as_bytes().to_vec(),
+                content: Some(high_capacity_registry_mutation::Content::Value(
+                    value.clone(),
+                )),
+            },
+            None => HighCapacityRegistryMutation {
+                mutation_type: registry_mutation::Type::Delete as i32,
+                key: record.key.as_bytes().to_vec(),
+                content: None,
+            },
+        };
+        mutations_by_version
+            .entry(record.version.get())
+            .or_default()
+            .push(mutation);
+    }
+
+    let changelog = mutations_by_version
+        .into_iter()
+        .map(|(version, mutations)| StableChangelogEntry {
+            version,
+            encoded_mutation: HighCapacityRegistryAtomicMutateRequest {
+                mutations,
+                preconditions: vec![],
+                timestamp_nanoseconds: 0,
+            }
+            .encode_to_vec(),
+        })
+        .collect();
+
+    let mut registry = Registry::new();
+    registry.from_serializable_form(RegistryStableStorage {
+        version: registry_stable_storage::Version::Version1 as i32,
+        changelog,
+    });
+    registry
+}
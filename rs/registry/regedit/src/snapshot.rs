@@ -11,6 +11,28 @@ pub const SPECIAL_FIELD_PREFIX: &str = "__";
 pub struct Snapshot(pub Value);
 
 pub fn changelog_to_snapshot(changelog: Changelog, version: VersionSpec) -> Result<Snapshot> {
+    let (values, latest_version) = changelog_to_raw_values(changelog, version)?;
+
+    let mut res: BTreeMap<String, Value> = values
+        .iter()
+        .map(|(k, v)| (k.to_string(), raw_data_to_value(k, v)))
+        .collect();
+    res.insert(
+        VERSION_FIELD.to_string(),
+        json::assert_to_value(latest_version),
+    );
+
+    let json_val = json::assert_to_value(res);
+
+    Ok(Snapshot(json_val))
+}
+
+/// Returns the raw values of all keys present at the given version, along with
+/// the latest version that is not newer than the given version.
+pub fn changelog_to_raw_values(
+    changelog: Changelog,
+    version: VersionSpec,
+) -> Result<(BTreeMap<String, Vec<u8>>, u64)> {
     let (mut changelog, v) = changelog;
     let bound = match version {
         VersionSpec::RelativeToLatest(r) => {
@@ -39,18 +61,7 @@ pub fn changelog_to_snapshot(changelog: Changelog, version: VersionSpec) -> Resu
         }
     }
 
-    let mut res: BTreeMap<String, Value> = res
-        .iter()
-        .map(|(k, v)| (k.to_string(), raw_data_to_value(k, v)))
-        .collect();
-    res.insert(
-        VERSION_FIELD.to_string(),
-        json::assert_to_value(latest_version),
-    );
-
-    let json_val = json::assert_to_value(res);
-
-    Ok(Snapshot(json_val))
+    Ok((res, latest_version))
 }
 
 #[derive(Debug, Error)]
@@ -14,6 +14,7 @@ use ic_prep_lib::{
 use ic_registry_provisional_whitelist::ProvisionalWhitelist;
 use ic_registry_subnet_type::SubnetType;
 use ic_types::ReplicaVersion;
+use registry_canister::mutations::do_update_subnet::UpdateSubnetPayload;
 use std::{
     collections::{BTreeMap, HashSet},
     net::SocketAddr,
@@ -111,6 +112,157 @@ fn adding_deleting_values_shows_up_in_diff() {
     assert_eq!(expected_snapshot.0, final_snapshot);
 }
 
+#[test]
+fn editing_subnet_record_yields_update_subnet_payload() {
+    let (_guard, ic_prep_dir) = run_ic_prep();
+    let registry_spec = local_store_latest_snapshot(ic_prep_dir.registry_local_store_path());
+    let mut snapshot = execute_command(Command::Snapshot {
+        registry_spec: registry_spec.clone(),
+        projection: universal_projection(),
+    })
+    .unwrap();
+
+    let obj = snapshot.as_object_mut().unwrap();
+    let subnet_record_key = obj
+        .keys()
+        .find(|k| k.starts_with("subnet_record_"))
+        .unwrap()
+        .clone();
+    let subnet_id = subnet_record_key.strip_prefix("subnet_record_").unwrap();
+    obj.get_mut(&subnet_record_key)
+        .unwrap()
+        .as_object_mut()
+        .unwrap()
+        .insert("max_number_of_canisters".into(), 1234.into());
+    let new_key = "a_key_that_does_not_exist".to_string();
+    obj.insert(new_key.clone(), "(binary-data)00".into());
+
+    let out = execute_command(Command::ProposalPayloads {
+        registry_spec,
+        snapshot,
+    })
+    .unwrap();
+
+    let payloads = out["payloads"].as_array().unwrap();
+    assert_eq!(payloads.len(), 1);
+    assert_eq!(payloads[0]["method"], "update_subnet");
+    let payload = payloads[0]["payload"].as_object().unwrap();
+    assert_eq!(payload["subnet_id"], subnet_id);
+    assert_eq!(payload["max_number_of_canisters"], 1234);
+    assert!(payload["max_block_payload_size"].is_null());
+    let candid = hex::decode(payloads[0]["candid_hex"].as_str().unwrap()).unwrap();
+    let decoded = candid::Decode!(&candid, UpdateSubnetPayload).unwrap();
+    assert_eq!(decoded.subnet_id.to_string(), subnet_id);
+    assert_eq!(decoded.max_number_of_canisters, Some(1234));
+
+    let unsupported_changes = out["unsupported_changes"].as_array().unwrap();
+    assert_eq!(unsupported_changes.len(), 1);
+    assert_eq!(unsupported_changes[0]["key"], new_key.as_str());
+    // The base version violates the endpoint invariants, which must not be
+    // attributed to the edit.
+    assert_eq!(out["dry_run"]["errors"], serde_json::json!([]));
+    assert_eq!(out["dry_run"]["valid"], true);
+}
+
+#[test]
+fn edit_violating_an_invariant_is_rejected_in_dry_run() {
+    let (_guard, ic_prep_dir) = run_ic_prep();
+    let registry_spec = local_store_latest_snapshot(ic_prep_dir.registry_local_store_path());
+    let mut snapshot = execute_command(Command::Snapshot {
+        registry_spec: registry_spec.clone(),
+        projection: universal_projection(),
+    })
+    .unwrap();
+
+    let obj = snapshot.as_object_mut().unwrap();
+    let subnet_record_key = obj
+        .keys()
+        .find(|k| k.starts_with("subnet_record_"))
+        .unwrap()
+        .clone();
+    // More SSH keys than a subnet may have.
+    obj.get_mut(&subnet_record_key)
+        .unwrap()
+        .as_object_mut()
+        .unwrap()
+        .insert(
+            "ssh_readonly_access".into(),
+            vec!["ssh-ed25519 AAAA"; 100].into(),
+        );
+
+    let out = execute_command(Command::ProposalPayloads {
+        registry_spec,
+        snapshot,
+    })
+    .unwrap();
+
+    assert_eq!(out["payloads"].as_array().unwrap().len(), 1);
+    assert_eq!(out["dry_run"]["valid"], false);
+    let errors = out["dry_run"]["errors"].as_array().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0]["key"], subnet_record_key.as_str());
+    assert_eq!(errors[0]["method"], "update_subnet");
+    let error = errors[0]["error"].as_str().unwrap();
+    assert!(error.contains("subnet invariants"), "{error}");
+    assert!(error.contains("SSH key access list"), "{error}");
+}
+
+#[test]
+fn check_invariants_reports_violations_with_keys() {
+    let (_guard, ic_prep_dir) = run_ic_prep();
+    let registry_spec = local_store_latest_snapshot(ic_prep_dir.registry_local_store_path());
+
+    // The node in the local store has an unspecified XNet address.
+    let out = execute_command(Command::CheckInvariants {
+        registry_spec: registry_spec.clone(),
+        snapshot: None,
+    })
+    .unwrap();
+    let endpoint_violation = endpoint_violation(&out).expect("no endpoint violation");
+    let node_record_key = endpoint_violation["keys"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|k| k.as_str())
+        .find(|k| k.starts_with("node_record_"))
+        .expect("node record key not reported")
+        .to_string();
+
+    let mut snapshot = execute_command(Command::Snapshot {
+        registry_spec: registry_spec.clone(),
+        projection: universal_projection(),
+    })
+    .unwrap();
+    let node_record = snapshot
+        .get_mut(&node_record_key)
+        .unwrap()
+        .as_object_mut()
+        .unwrap();
+    node_record.insert(
+        "xnet".into(),
+        serde_json::json!({ "ip_addr": "127.0.0.1", "port": 2497 }),
+    );
+    node_record.insert(
+        "http".into(),
+        serde_json::json!({ "ip_addr": "127.0.0.1", "port": 8080 }),
+    );
+
+    let out = execute_command(Command::CheckInvariants {
+        registry_spec,
+        snapshot: Some(snapshot),
+    })
+    .unwrap();
+    assert_eq!(endpoint_violation(&out), None);
+}
+
+fn endpoint_violation(out: &serde_json::Value) -> Option<&serde_json::Value> {
+    out["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["invariant"] == "endpoint")
+}
+
 pub fn local_store_latest_snapshot(path: PathBuf) -> RegistrySpec {
     let source = SourceSpec::LocalStore(path);
     let version = VersionSpec::RelativeToLatest(0);
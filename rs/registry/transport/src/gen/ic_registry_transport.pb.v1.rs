@@ -143,6 +143,28 @@ pub struct RegistryGetChangesSinceRequest {
     #[prost(uint64, tag = "1")]
     pub version: u64,
 }
+/// Message to retrieve the versions at which a registry key (or any key
+/// with a given prefix) was mutated, within a range of versions.
+///
+/// The response is a CertifiedResponse whose hash tree reveals the deltas
+/// at those versions, and prunes all other deltas.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegistryGetKeyHistoryRequest {
+    /// The key whose history to retrieve, or the prefix of the keys if
+    /// key_is_prefix is set.
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bool, tag = "2")]
+    pub key_is_prefix: bool,
+    /// The first version (inclusive) to consider.
+    #[prost(uint64, tag = "3")]
+    pub from_version: u64,
+    /// The last version (inclusive) to consider.
+    /// Optional: If not set (or set to the default value, 0), the method
+    /// considers all versions up to the latest one.
+    #[prost(uint64, tag = "4")]
+    pub to_version: u64,
+}
 /// Message to retrieve a version of some registry key
 /// from the registry canister.
 #[derive(Clone, PartialEq, ::prost::Message)]
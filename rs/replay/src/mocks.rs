@@ -33,4 +33,8 @@ impl PayloadBuilder for MockPayloadBuilder {
     ) -> ValidationResult<PayloadValidationError> {
         Ok(())
     }
+
+    fn has_pending_ingress(&self) -> bool {
+        false
+    }
 }
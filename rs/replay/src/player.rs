@@ -902,22 +902,24 @@ impl Player {
         let input = QueryExecutionInput {
             query,
             certificate_delegation_with_metadata: None,
+            continuation_token: None,
         };
         match self
             .runtime
             .block_on(self.query_handler.clone().oneshot(input))
             .unwrap()
         {
-            Ok((Ok(wasm_result), _)) => match wasm_result {
+            Ok((Ok(wasm_result), _, _)) => match wasm_result {
                 WasmResult::Reply(v) => deserialize_get_latest_version_response(v)
                     .map(RegistryVersion::from)
                     .map_err(|err| format!("{err}")),
                 WasmResult::Reject(e) => Err(format!("Query rejected: {e}")),
             },
-            Ok((Err(err), _)) => Err(format!("Query failed: {err:?}")),
+            Ok((Err(err), _, _)) => Err(format!("Query failed: {err:?}")),
             Err(QueryExecutionError::CertifiedStateUnavailable) => {
                 panic!("Certified state unavailable for query call.")
             }
+            Err(err) => panic!("Query call failed: {err}"),
         }
     }
 
@@ -1213,8 +1215,10 @@ impl PerformQuery for Arc<Mutex<QueryExecutionService>> {
         let input = QueryExecutionInput {
             query,
             certificate_delegation_with_metadata: None,
+            continuation_token: None,
         };
-        query_execution_service.oneshot(input).await
+        let result = query_execution_service.oneshot(input).await?;
+        Ok(result.map(|(result, time, _continuation_token)| (result, time)))
     }
 }
 
@@ -1270,6 +1274,7 @@ async fn get_changes_since(
         Err(QueryExecutionError::CertifiedStateUnavailable) => {
             Err("Certified state unavailable for query call.".to_string())
         }
+        Err(err) => Err(format!("Query call failed: {err}")),
     }
 }
 
@@ -1314,6 +1319,7 @@ impl<PerformQueryImpl: PerformQuery + Sync> GetChunk for GetChunkImpl<'_, Perfor
                     String::from_utf8_lossy(chunk_content_sha256),
                 ));
             }
+            Err(err) => return Err(format!("Registry get_chunk query call failed: {err}")),
         };
 
         // Handle more problems...
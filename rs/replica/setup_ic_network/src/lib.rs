@@ -9,7 +9,10 @@ use ic_artifact_pool::{
     consensus_pool::ConsensusPoolImpl, dkg_pool::DkgPoolImpl, idkg_pool::IDkgPoolImpl,
     ingress_pool::IngressPoolImpl,
 };
-use ic_config::{artifact_pool::ArtifactPoolConfig, transport::TransportConfig};
+use ic_config::{
+    artifact_pool::ArtifactPoolConfig,
+    transport::{self, TransportConfig},
+};
 use ic_consensus::consensus::{
     ConsensusBouncer, ConsensusImpl, MAX_CONSENSUS_THREADS, build_thread_pool,
 };
@@ -29,6 +32,7 @@ use ic_https_outcalls_consensus::{
 use ic_ingress_manager::{IngressManager, RandomStateKind, bouncer::IngressBouncer};
 use ic_interfaces::{
     batch_payload::BatchPayloadBuilder,
+    consensus::ConsensusTraceReader,
     consensus_pool::ConsensusPoolCache,
     execution_environment::IngressHistoryReader,
     messaging::{MessageRouting, XNetPayloadBuilder},
@@ -41,7 +45,9 @@ use ic_interfaces_registry::RegistryClient;
 use ic_interfaces_state_manager::{StateManager, StateReader};
 use ic_logger::{info, replica_logger::ReplicaLogger};
 use ic_metrics::MetricsRegistry;
-use ic_quic_transport::create_udp_socket;
+use ic_quic_transport::{
+    TrafficClass, TrafficClassConfig, TrafficShapingConfig, create_udp_socket,
+};
 use ic_registry_client_helpers::subnet::SubnetRegistry;
 use ic_registry_subnet_type::SubnetType;
 use ic_replicated_state::ReplicatedState;
@@ -361,6 +367,7 @@ pub fn setup_consensus_and_p2p(
 ) -> (
     Arc<RwLock<IngressPoolImpl>>,
     Sender<UnvalidatedArtifactMutation<SignedIngress>>,
+    Arc<dyn ConsensusTraceReader>,
     Vec<Box<dyn JoinGuard>>,
 ) {
     let time_source = Arc::new(SysTimeSource::new());
@@ -430,6 +437,7 @@ pub fn setup_consensus_and_p2p(
         topology_watcher.clone(),
         create_udp_socket(rt_handle, transport_addr),
         p2p_router,
+        traffic_shaping_config(&transport_config.traffic_shaping),
     ));
 
     // Start the main event loops for StateSync and Consensus
@@ -469,6 +477,38 @@ pub fn setup_consensus_and_p2p(
 /// The function creates the consensus protocols and the event loops that drive them forward.
 /// The event loops are written in SANS-IO style (https://www.firezone.dev/blog/sans-io, )
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+/// Returns the default traffic shaping of the transport with the overrides of the
+/// replica config applied.
+fn traffic_shaping_config(overrides: &transport::TrafficShapingConfig) -> TrafficShapingConfig {
+    fn traffic_class(class: transport::TrafficClass) -> TrafficClass {
+        match class {
+            transport::TrafficClass::Critical => TrafficClass::Critical,
+            transport::TrafficClass::Default => TrafficClass::Default,
+            transport::TrafficClass::Bulk => TrafficClass::Bulk,
+        }
+    }
+
+    let mut config = TrafficShapingConfig::default();
+    config.routes.extend(
+        overrides
+            .routes
+            .iter()
+            .map(|(path, class)| (path.clone(), traffic_class(*class))),
+    );
+    config
+        .classes
+        .extend(overrides.classes.iter().map(|(class, class_config)| {
+            (
+                traffic_class(*class),
+                TrafficClassConfig {
+                    weight: class_config.weight,
+                    max_bytes_per_second_per_peer: class_config.max_bytes_per_second_per_peer,
+                },
+            )
+        }));
+    config
+}
+
 fn start_consensus(
     log: &ReplicaLogger,
     metrics_registry: &MetricsRegistry,
@@ -500,6 +540,7 @@ fn start_consensus(
 ) -> (
     Arc<RwLock<IngressPoolImpl>>,
     Sender<UnvalidatedArtifactMutation<SignedIngress>>,
+    Arc<dyn ConsensusTraceReader>,
     Vec<Box<dyn JoinGuard>>,
 ) {
     let consensus_pool_cache = consensus_pool.read().unwrap().get_cache();
@@ -580,6 +621,7 @@ fn start_consensus(
         metrics_registry.clone(),
         log.clone(),
     );
+    let consensus_trace: Arc<dyn ConsensusTraceReader> = consensus_impl.trace();
     // Create the consensus client.
     join_handles.push(create_artifact_handler(
         abortable_broadcast_channels.consensus,
@@ -676,5 +718,10 @@ fn start_consensus(
         metrics_registry.clone(),
     ));
 
-    (artifact_pools.ingress_pool, user_ingress_tx, join_handles)
+    (
+        artifact_pools.ingress_pool,
+        user_ingress_tx,
+        consensus_trace,
+        join_handles,
+    )
 }
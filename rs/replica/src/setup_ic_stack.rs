@@ -228,6 +228,7 @@ pub fn construct_ic_stack(
             config.malicious_behavior.malicious_flags.clone(),
         )
     };
+    let xnet_zstd_compression = config.message_routing.xnet_zstd_compression;
     let xnet_endpoint = XNetEndpoint::new(
         rt_handle_xnet.clone(),
         Arc::clone(&certified_stream_store),
@@ -246,6 +247,7 @@ pub fn construct_ic_stack(
         rt_handle_xnet.clone(),
         node_id,
         subnet_id,
+        xnet_zstd_compression,
         metrics_registry,
         log.clone(),
     ));
@@ -306,7 +308,7 @@ pub fn construct_ic_stack(
     let state_sync = StateSync::new(state_manager.clone(), log.clone());
     let (max_certified_height_tx, max_certified_height_rx) = watch::channel(Height::from(0));
 
-    let (ingress_throttler, ingress_tx, p2p_runner) = setup_consensus_and_p2p(
+    let (ingress_throttler, ingress_tx, consensus_trace, p2p_runner) = setup_consensus_and_p2p(
         log,
         metrics_registry,
         rt_handle_p2p,
@@ -345,6 +347,7 @@ pub fn construct_ic_stack(
         config.http_handler.clone(),
         execution_services.ingress_filter,
         execution_services.query_execution_service.clone(),
+        execution_services.dry_run_execution_service,
         ingress_throttler,
         ingress_tx.clone(),
         Arc::clone(&state_manager) as Arc<_>,
@@ -357,6 +360,7 @@ pub fn construct_ic_stack(
         root_subnet_id,
         log.clone(),
         consensus_pool_cache,
+        consensus_trace,
         subnet_type,
         config.malicious_behavior.malicious_flags,
         nns_delegation_watcher,
@@ -604,12 +604,14 @@ impl LocalTestRuntime {
         let input = QueryExecutionInput {
             query,
             certificate_delegation_with_metadata: None,
+            continuation_token: None,
         };
         let result = match query_svc.oneshot(input).await.unwrap() {
-            Ok((result, _)) => result,
+            Ok((result, _, _)) => result,
             Err(QueryExecutionError::CertifiedStateUnavailable) => {
                 panic!("Certified state unavailable for query call.")
             }
+            Err(err) => panic!("Query call failed: {err}"),
         };
         if let Ok(WasmResult::Reply(result)) = result.clone() {
             info!(
@@ -734,6 +734,7 @@ fn can_get_canister_information() {
                 Some(DEFAULT_WASM_MEMORY_LIMIT.get()),
                 0u64,
                 Default::default(),
+                vec![],
             )
         );
 
@@ -803,6 +804,7 @@ fn can_get_canister_information() {
                     Some(DEFAULT_WASM_MEMORY_LIMIT.get()),
                     0u64,
                     Default::default(),
+                    vec![],
                 ),
                 CanisterStatusResultV2::decode(&res).unwrap(),
                 2 * BALANCE_EPSILON,
@@ -5,7 +5,7 @@ use crate::{
         execution_state::{Memory, WasmExecutionMode},
         system_state::wasm_chunk_store::{self, ValidatedChunk, WasmChunkStore},
     },
-    page_map::{Buffer, PageAllocatorFileDescriptor, PersistenceError},
+    page_map::{Buffer, PageAllocatorFileDescriptor, PageIndex, PersistenceError},
 };
 use ic_config::embedders::{MAX_GLOBALS, WASM_MAX_SIZE};
 use ic_management_canister_types_private::{
@@ -219,6 +219,96 @@ impl CanisterSnapshots {
         self.snapshots.contains_key(snapshot_id)
     }
 
+    /// Returns true if at least one incremental snapshot uses the snapshot
+    /// identified by `snapshot_id` as its parent.
+    ///
+    /// Such a snapshot must neither be deleted nor modified, since that would
+    /// invalidate the incremental snapshots taken on top of it.
+    pub fn has_dependents(&self, snapshot_id: SnapshotId) -> bool {
+        let Some(snapshot_ids) = self.snapshot_ids.get(&snapshot_id.get_canister_id()) else {
+            return false;
+        };
+        snapshot_ids.iter().any(|id| {
+            self.snapshots
+                .get(id)
+                .and_then(|snapshot| snapshot.parent())
+                .is_some_and(|parent| parent.snapshot_id == snapshot_id)
+        })
+    }
+
+    /// Returns the full wasm and stable memories of the snapshot identified by
+    /// `snapshot_id`.
+    ///
+    /// For an incremental snapshot, this walks the chain of parents back to the
+    /// full snapshot at its root and then applies the pages stored by each
+    /// incremental snapshot on the way back, oldest first.
+    ///
+    /// If `fd_factory` is provided, the result is built on top of clean copies
+    /// of the root snapshot's memories using a fresh page allocator, as needed
+    /// when loading the snapshot onto a different canister.
+    ///
+    /// The result is not cached: it is only needed for the duration of a single
+    /// operation and keeping it alive would hold on to memory that is neither
+    /// accounted for in the snapshot's size nor in the subnet's memory usage.
+    pub fn reassemble_memories(
+        &self,
+        snapshot_id: SnapshotId,
+        fd_factory: Option<Arc<dyn PageAllocatorFileDescriptor>>,
+    ) -> Result<SnapshotMemories, CanisterSnapshotError> {
+        let mut chain = vec![];
+        let mut current = snapshot_id;
+        loop {
+            let snapshot = self
+                .get(current)
+                .ok_or(CanisterSnapshotError::SnapshotNotFound(current))?;
+            chain.push(snapshot);
+            match snapshot.parent() {
+                Some(parent) => current = parent.snapshot_id,
+                None => break,
+            }
+        }
+
+        // The last element of the chain is a full snapshot.
+        let root = chain.pop().unwrap();
+        let mut memories = match fd_factory {
+            None => SnapshotMemories {
+                wasm_memory: root.wasm_memory().clone(),
+                stable_memory: root.stable_memory().clone(),
+                root_snapshot_id: current,
+            },
+            Some(fd_factory) => {
+                let clean_copy = |memory: &PageMemory| {
+                    memory
+                        .page_map
+                        .clean_copy(Arc::clone(&fd_factory))
+                        .map(|page_map| PageMemory {
+                            page_map,
+                            size: memory.size,
+                        })
+                        .map_err(|_| CanisterSnapshotError::NotLoadable(snapshot_id))
+                };
+                SnapshotMemories {
+                    wasm_memory: clean_copy(root.wasm_memory())?,
+                    stable_memory: clean_copy(root.stable_memory())?,
+                    root_snapshot_id: current,
+                }
+            }
+        };
+
+        for snapshot in chain.into_iter().rev() {
+            // All snapshots except for the root are incremental.
+            let parent = snapshot.parent().unwrap();
+            memories
+                .wasm_memory
+                .apply_pages(snapshot.wasm_memory(), &parent.wasm_memory_pages);
+            memories
+                .stable_memory
+                .apply_pages(snapshot.stable_memory(), &parent.stable_memory_pages);
+        }
+
+        Ok(memories)
+    }
+
     /// Splits the `CanisterSnapshots` as part of subnet splitting phase 1.
     ///
     /// A subnet split starts with a subnet A and results in two subnets, A' and B.
@@ -260,6 +350,32 @@ impl CanisterSnapshots {
         result
     }
 
+    /// Merges the canister snapshots of another subnet into `self`, as part of a
+    /// subnet merge.
+    ///
+    /// Snapshot IDs embed the canister ID, so snapshots belonging to canisters
+    /// hosted by different subnets can never collide. Returns an error if they
+    /// do nonetheless.
+    pub(crate) fn merge(&mut self, other: CanisterSnapshots) -> Result<(), String> {
+        // Destructure `other`, in order for the compiler to enforce explicit
+        // decisions whenever new fields are added.
+        let CanisterSnapshots {
+            snapshots,
+            snapshot_ids: _,
+            memory_usage: _,
+        } = other;
+
+        for (snapshot_id, snapshot) in snapshots {
+            if self.snapshots.contains_key(&snapshot_id) {
+                return Err(format!(
+                    "Snapshot {snapshot_id} is present in both states being merged"
+                ));
+            }
+            self.push(snapshot_id, snapshot);
+        }
+        Ok(())
+    }
+
     /// Returns the amount of memory taken by all canister snapshots on
     /// this subnet.
     pub fn memory_taken(&self) -> NumBytes {
@@ -288,6 +404,97 @@ pub struct PageMemory {
     pub size: NumWasmPages,
 }
 
+impl PageMemory {
+    /// Overwrites the pages with the given indices with the respective pages
+    /// of `delta` and takes over the size of `delta`.
+    fn apply_pages(&mut self, delta: &PageMemory, page_indices: &[PageIndex]) {
+        let pages: Vec<_> = page_indices
+            .iter()
+            .map(|index| (*index, delta.page_map.get_page(*index)))
+            .collect();
+        self.page_map.update(&pages);
+        self.size = delta.size;
+    }
+
+    /// Returns the size of the memory in bytes.
+    fn size_bytes(&self) -> NumBytes {
+        NumBytes::new((self.size.get() * WASM_PAGE_SIZE_IN_BYTES) as u64)
+    }
+}
+
+/// Returns the indices of all pages whose contents differ between `base` and
+/// `page_map`.
+///
+/// Only the pages that may differ according to the deltas and overlay files of
+/// the page maps are compared. All pages are compared only if the page maps are
+/// not backed by the same base file, e.g. because the canister's files were
+/// merged since the parent snapshot was taken.
+fn changed_pages(base: &PageMap, page_map: &PageMap) -> Vec<PageIndex> {
+    let differs = |index: &PageIndex| base.get_page(*index) != page_map.get_page(*index);
+    match base.pages_possibly_differing_from(page_map) {
+        Some(candidates) => candidates.into_iter().filter(differs).collect(),
+        None => {
+            let num_pages = base.num_host_pages().max(page_map.num_host_pages());
+            (0..num_pages as u64)
+                .map(PageIndex::new)
+                .filter(differs)
+                .collect()
+        }
+    }
+}
+
+/// Returns a new `PageMap` that only contains the pages of `page_map` with the
+/// given indices.
+fn page_map_subset(
+    page_map: &PageMap,
+    page_indices: &[PageIndex],
+    fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
+) -> PageMap {
+    let pages: Vec<_> = page_indices
+        .iter()
+        .map(|index| (*index, page_map.get_page(*index)))
+        .collect();
+    let mut subset = PageMap::new(fd_factory);
+    subset.update(&pages);
+    subset
+}
+
+/// The full wasm and stable memories of a (possibly incremental) snapshot.
+#[derive(Clone, Debug)]
+pub struct SnapshotMemories {
+    pub wasm_memory: PageMemory,
+    pub stable_memory: PageMemory,
+    /// The full snapshot at the root of the chain. The memories consist of the
+    /// memories of this snapshot plus the pages of all incremental snapshots
+    /// in the chain, applied as page deltas on top.
+    pub root_snapshot_id: SnapshotId,
+}
+
+/// Describes how an incremental snapshot relates to its parent snapshot.
+///
+/// An incremental snapshot only stores the wasm and stable memory pages that
+/// differ from the (reassembled) memories of its parent. All other pages are
+/// read from the parent, which may itself be an incremental snapshot.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct SnapshotParent {
+    /// The snapshot that this snapshot is based on. Always belongs to the same
+    /// canister as the incremental snapshot.
+    pub snapshot_id: SnapshotId,
+    /// The indices of the wasm memory pages stored in this snapshot.
+    pub wasm_memory_pages: Vec<PageIndex>,
+    /// The indices of the stable memory pages stored in this snapshot.
+    pub stable_memory_pages: Vec<PageIndex>,
+}
+
+impl SnapshotParent {
+    /// Returns the number of bytes of memory stored in the incremental snapshot.
+    pub fn delta_size(&self) -> NumBytes {
+        NumBytes::new(
+            ((self.wasm_memory_pages.len() + self.stable_memory_pages.len()) * PAGE_SIZE) as u64,
+        )
+    }
+}
+
 impl From<&Memory> for PageMemory {
     fn from(memory: &Memory) -> Self {
         Self {
@@ -357,6 +564,8 @@ pub struct CanisterSnapshot {
     chunk_store: WasmChunkStore,
     #[validate_eq(CompareWithValidateEq)]
     execution_snapshot: ExecutionStateSnapshot,
+    /// The parent snapshot, if this is an incremental snapshot.
+    parent: Option<SnapshotParent>,
 }
 
 impl CanisterSnapshot {
@@ -369,6 +578,7 @@ impl CanisterSnapshot {
         chunk_store: WasmChunkStore,
         execution_snapshot: ExecutionStateSnapshot,
         size: NumBytes,
+        parent: Option<SnapshotParent>,
     ) -> CanisterSnapshot {
         Self {
             canister_id,
@@ -379,6 +589,7 @@ impl CanisterSnapshot {
             chunk_store,
             execution_snapshot,
             size,
+            parent,
         }
     }
 
@@ -417,9 +628,62 @@ impl CanisterSnapshot {
             chunk_store: canister.system_state.wasm_chunk_store.clone(),
             execution_snapshot,
             size: canister.snapshot_size_bytes(),
+            parent: None,
         })
     }
 
+    /// Creates an incremental snapshot from a canister.
+    ///
+    /// Only the wasm and stable memory pages that differ from `parent_memories`,
+    /// the reassembled memories of the parent snapshot, are stored; and only
+    /// these pages count towards the size of the snapshot. The wasm module,
+    /// the globals, the certified data and the wasm chunk store are stored in
+    /// full, as for a regular snapshot.
+    ///
+    /// Same as `from_canister`, this method fails early, before any expensive
+    /// computations are performed.
+    pub fn incremental_from_canister(
+        canister: &CanisterState,
+        taken_at_timestamp: Time,
+        parent_snapshot_id: SnapshotId,
+        parent_memories: &SnapshotMemories,
+        fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
+    ) -> Result<Self, CanisterSnapshotError> {
+        let mut snapshot = Self::from_canister(canister, taken_at_timestamp)?;
+
+        let execution_snapshot = &mut snapshot.execution_snapshot;
+        let full_memory_size = execution_snapshot.wasm_memory.size_bytes()
+            + execution_snapshot.stable_memory.size_bytes();
+
+        let wasm_memory_pages = changed_pages(
+            &parent_memories.wasm_memory.page_map,
+            &execution_snapshot.wasm_memory.page_map,
+        );
+        execution_snapshot.wasm_memory.page_map = page_map_subset(
+            &execution_snapshot.wasm_memory.page_map,
+            &wasm_memory_pages,
+            Arc::clone(&fd_factory),
+        );
+        let stable_memory_pages = changed_pages(
+            &parent_memories.stable_memory.page_map,
+            &execution_snapshot.stable_memory.page_map,
+        );
+        execution_snapshot.stable_memory.page_map = page_map_subset(
+            &execution_snapshot.stable_memory.page_map,
+            &stable_memory_pages,
+            fd_factory,
+        );
+
+        let parent = SnapshotParent {
+            snapshot_id: parent_snapshot_id,
+            wasm_memory_pages,
+            stable_memory_pages,
+        };
+        snapshot.size = snapshot.size.saturating_sub(&full_memory_size) + parent.delta_size();
+        snapshot.parent = Some(parent);
+        Ok(snapshot)
+    }
+
     pub fn from_metadata(
         metadata: &ValidatedSnapshotMetadata,
         taken_at_timestamp: Time,
@@ -461,6 +725,7 @@ impl CanisterSnapshot {
             certified_data: metadata.certified_data.clone(),
             chunk_store,
             execution_snapshot,
+            parent: None,
         }
     }
 
@@ -468,6 +733,11 @@ impl CanisterSnapshot {
         self.canister_id
     }
 
+    /// Returns the parent snapshot if this is an incremental snapshot.
+    pub fn parent(&self) -> Option<&SnapshotParent> {
+        self.parent.as_ref()
+    }
+
     pub fn source(&self) -> SnapshotSource {
         self.source
     }
@@ -586,6 +856,10 @@ pub enum CanisterSnapshotError {
     InvalidSubslice { offset: u64, size: u64 },
     /// Metadata is invalid.
     InvalidMetadata { reason: String },
+    /// A snapshot in the chain of an incremental snapshot does not exist.
+    SnapshotNotFound(SnapshotId),
+    /// The memories of the snapshot could not be copied for loading.
+    NotLoadable(SnapshotId),
 }
 
 #[derive(Clone, Debug)]
@@ -721,6 +995,8 @@ pub enum MetadataValidationError {
 mod tests {
     use super::*;
     use super::{CanisterSnapshot, CanisterSnapshots, PageMap};
+    use crate::page_map::TestPageAllocatorFileDescriptorImpl;
+    use assert_matches::assert_matches;
     use ic_test_utilities_types::ids::canister_test_id;
     use ic_types::NumBytes;
     use ic_types::time::UNIX_EPOCH;
@@ -753,6 +1029,7 @@ mod tests {
             WasmChunkStore::new_for_testing(),
             execution_snapshot,
             NumBytes::from(0),
+            None,
         );
 
         let snapshot_id = SnapshotId::from((canister_id, local_id));
@@ -760,6 +1037,24 @@ mod tests {
         (snapshot_id, snapshot)
     }
 
+    /// Creates a snapshot whose wasm memory holds `wasm_memory` and, if `parent`
+    /// is provided, makes it an incremental snapshot storing the given pages.
+    fn fake_snapshot_with_wasm_memory(
+        canister_id: CanisterId,
+        local_id: u64,
+        wasm_memory: &[u8],
+        parent: Option<SnapshotParent>,
+    ) -> (SnapshotId, Arc<CanisterSnapshot>) {
+        let (snapshot_id, mut snapshot) = fake_canister_snapshot(canister_id, local_id);
+        snapshot.wasm_memory_mut().page_map = PageMap::from(wasm_memory);
+        snapshot.parent = parent;
+        (snapshot_id, Arc::new(snapshot))
+    }
+
+    fn read_page(memory: &PageMemory, index: u64) -> Vec<u8> {
+        memory.page_map.get_page(PageIndex::new(index)).to_vec()
+    }
+
     #[test]
     fn test_push_and_remove_snapshot() {
         let canister_id = canister_test_id(0);
@@ -875,4 +1170,123 @@ mod tests {
             NumBytes::from(0)
         );
     }
+
+    #[test]
+    fn test_changed_pages() {
+        let base = PageMap::from(&[1_u8; 3 * PAGE_SIZE][..]);
+
+        let mut contents = vec![1_u8; 4 * PAGE_SIZE];
+        contents[PAGE_SIZE] = 2;
+        contents[3 * PAGE_SIZE] = 3;
+        let page_map = PageMap::from(&contents[..]);
+        assert_eq!(
+            changed_pages(&base, &page_map),
+            vec![PageIndex::new(1), PageIndex::new(3)]
+        );
+
+        // Pages that only exist in the base are reported as changed, too.
+        assert_eq!(
+            changed_pages(&page_map, &PageMap::new_for_testing()),
+            vec![
+                PageIndex::new(0),
+                PageIndex::new(1),
+                PageIndex::new(2),
+                PageIndex::new(3)
+            ]
+        );
+        assert!(changed_pages(&base, &base.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_reassemble_memories_of_incremental_snapshots() {
+        let canister_id = canister_test_id(0);
+        let (root_id, root) =
+            fake_snapshot_with_wasm_memory(canister_id, 0, &[1_u8; 3 * PAGE_SIZE], None);
+
+        // The first incremental snapshot overwrites page 1.
+        let (first_id, first) = fake_snapshot_with_wasm_memory(
+            canister_id,
+            1,
+            &[[0_u8; PAGE_SIZE], [2_u8; PAGE_SIZE]].concat(),
+            Some(SnapshotParent {
+                snapshot_id: root_id,
+                wasm_memory_pages: vec![PageIndex::new(1)],
+                stable_memory_pages: vec![],
+            }),
+        );
+        // The second incremental snapshot zeroes page 0 and overwrites page 2.
+        let (second_id, second) = fake_snapshot_with_wasm_memory(
+            canister_id,
+            2,
+            &[[0_u8; PAGE_SIZE], [0_u8; PAGE_SIZE], [3_u8; PAGE_SIZE]].concat(),
+            Some(SnapshotParent {
+                snapshot_id: first_id,
+                wasm_memory_pages: vec![PageIndex::new(0), PageIndex::new(2)],
+                stable_memory_pages: vec![],
+            }),
+        );
+        let snapshots = CanisterSnapshots::new(btreemap! {
+            root_id => root,
+            first_id => first,
+            second_id => second,
+        });
+
+        let memories = snapshots.reassemble_memories(root_id, None).unwrap();
+        assert_eq!(memories.root_snapshot_id, root_id);
+        assert_eq!(read_page(&memories.wasm_memory, 0), vec![1; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 1), vec![1; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 2), vec![1; PAGE_SIZE]);
+
+        let memories = snapshots.reassemble_memories(first_id, None).unwrap();
+        assert_eq!(read_page(&memories.wasm_memory, 0), vec![1; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 1), vec![2; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 2), vec![1; PAGE_SIZE]);
+
+        let memories = snapshots
+            .reassemble_memories(
+                second_id,
+                Some(Arc::new(TestPageAllocatorFileDescriptorImpl::new())),
+            )
+            .unwrap();
+        assert_eq!(memories.root_snapshot_id, root_id);
+        assert_eq!(read_page(&memories.wasm_memory, 0), vec![0; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 1), vec![2; PAGE_SIZE]);
+        assert_eq!(read_page(&memories.wasm_memory, 2), vec![3; PAGE_SIZE]);
+
+        assert!(snapshots.has_dependents(root_id));
+        assert!(snapshots.has_dependents(first_id));
+        assert!(!snapshots.has_dependents(second_id));
+    }
+
+    #[test]
+    fn test_reassemble_memories_fails_for_missing_parent() {
+        let canister_id = canister_test_id(0);
+        let missing_id = SnapshotId::from((canister_id, 0));
+        let (snapshot_id, snapshot) = fake_snapshot_with_wasm_memory(
+            canister_id,
+            1,
+            &[1_u8; PAGE_SIZE],
+            Some(SnapshotParent {
+                snapshot_id: missing_id,
+                wasm_memory_pages: vec![PageIndex::new(0)],
+                stable_memory_pages: vec![],
+            }),
+        );
+        let snapshots = CanisterSnapshots::new(btreemap! { snapshot_id => snapshot });
+
+        assert_matches!(
+            snapshots.reassemble_memories(snapshot_id, None),
+            Err(CanisterSnapshotError::SnapshotNotFound(id)) if id == missing_id
+        );
+    }
+
+    #[test]
+    fn test_snapshot_parent_delta_size() {
+        let parent = SnapshotParent {
+            snapshot_id: SnapshotId::from((canister_test_id(0), 0)),
+            wasm_memory_pages: vec![PageIndex::new(0), PageIndex::new(7)],
+            stable_memory_pages: vec![PageIndex::new(3)],
+        };
+        assert_eq!(parent.delta_size(), NumBytes::from(3 * PAGE_SIZE as u64));
+    }
 }
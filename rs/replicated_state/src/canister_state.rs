@@ -214,6 +214,11 @@ impl CanisterState {
         self.system_state.has_input()
     }
 
+    /// See `SystemState::has_latency_sensitive_input` for documentation.
+    pub fn has_latency_sensitive_input(&self) -> bool {
+        self.system_state.has_latency_sensitive_input()
+    }
+
     /// Returns what the canister is going to execute next.
     pub fn next_execution(&self) -> NextExecution {
         let next_task = self.system_state.task_queue.front();
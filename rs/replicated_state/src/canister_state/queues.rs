@@ -1012,6 +1012,41 @@ impl CanisterQueues {
         self.store.has_output()
     }
 
+    /// Returns the IDs of the canisters whose output queue has a request at its
+    /// front, i.e. the receivers of requests not yet routed into streams.
+    pub fn output_request_receivers(&self) -> impl Iterator<Item = &CanisterId> {
+        self.canister_queues.keys().filter(|receiver| {
+            matches!(
+                self.peek_output(receiver),
+                Some(RequestOrResponse::Request(_))
+            )
+        })
+    }
+
+    /// Returns `true` if `ingress_queue` or any canister input queue holds a call
+    /// to one of the given `methods`, regardless of its position in the queue;
+    /// `false` otherwise.
+    pub fn has_input_for_methods(&self, methods: &BTreeSet<String>) -> bool {
+        if methods.is_empty() {
+            return false;
+        }
+        if self
+            .ingress_queue
+            .iter()
+            .any(|ingress| methods.contains(&ingress.method_name))
+        {
+            return true;
+        }
+        self.canister_queues.values().any(|(input_queue, _)| {
+            input_queue.iter().any(|reference| {
+                matches!(
+                    self.store.pool.get(*reference),
+                    Some(RequestOrResponse::Request(request)) if methods.contains(&request.method_name)
+                )
+            })
+        })
+    }
+
     /// Peeks the ingress or inter-canister input message that would be returned by
     /// `pop_input()`.
     ///
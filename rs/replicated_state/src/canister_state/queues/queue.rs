@@ -391,6 +391,12 @@ impl IngressQueue {
         Some(Arc::clone(ingress))
     }
 
+    /// Returns an iterator over all ingress messages in the queue, in no
+    /// particular order.
+    pub(super) fn iter(&self) -> impl Iterator<Item = &Arc<Ingress>> {
+        self.queues.values().flatten()
+    }
+
     /// Returns the number of Ingress messages in the queue.
     pub(super) fn size(&self) -> usize {
         self.total_ingress_count
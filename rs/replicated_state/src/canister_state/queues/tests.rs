@@ -326,6 +326,33 @@ fn can_push_output_response_after_input_request() {
     fixture.push_output_response(NO_DEADLINE);
 }
 
+/// Only receivers with a request at the front of their output queue are
+/// returned.
+#[test]
+fn output_request_receivers_only_returns_receivers_of_requests() {
+    let mut fixture = CanisterQueuesFixture::new();
+    assert_eq!(0, fixture.queues.output_request_receivers().count());
+
+    // A response at the front of the output queue does not count.
+    fixture.push_input_request(NO_DEADLINE).unwrap();
+    fixture.pop_input().unwrap();
+    fixture.push_output_response(NO_DEADLINE);
+    assert_eq!(0, fixture.queues.output_request_receivers().count());
+
+    // Neither does a request behind a response.
+    fixture.push_output_request(NO_DEADLINE).unwrap();
+    assert_eq!(0, fixture.queues.output_request_receivers().count());
+
+    fixture.pop_output().unwrap();
+    assert_eq!(
+        vec![&fixture.other],
+        fixture
+            .queues
+            .output_request_receivers()
+            .collect::<Vec<_>>()
+    );
+}
+
 /// Can push one request to the induction pool.
 #[test]
 fn can_push_input_request() {
@@ -935,6 +962,63 @@ fn test_message_picking_ingress_only() {
     assert!(queues.pop_input().is_none());
 }
 
+/// `has_input_for_methods()` detects matching messages anywhere in the input
+/// queues, not just at their fronts.
+#[test]
+fn test_has_input_for_methods() {
+    let this = canister_test_id(13);
+    let methods = BTreeSet::from(["fast".to_string()]);
+
+    let mut queues = CanisterQueues::default();
+    assert!(!queues.has_input_for_methods(&methods));
+
+    for method_name in ["bulk", "fast"] {
+        queues.push_ingress(
+            IngressBuilder::default()
+                .receiver(this)
+                .method_name(method_name)
+                .build(),
+        );
+    }
+    // A `fast` ingress message is enqueued, behind a `bulk` one.
+    assert!(queues.has_input_for_methods(&methods));
+    assert!(!queues.has_input_for_methods(&BTreeSet::from(["other".to_string()])));
+
+    // Pop both ingress messages.
+    queues.pop_input().unwrap();
+    queues.pop_input().unwrap();
+    assert!(!queues.has_input_for_methods(&methods));
+
+    push_requests(
+        &mut queues,
+        RemoteSubnet,
+        &vec![
+            RequestBuilder::default()
+                .sender(canister_test_id(14))
+                .receiver(this)
+                .method_name("fast")
+                .build(),
+        ],
+    );
+    assert!(queues.has_input_for_methods(&methods));
+    assert!(!queues.has_input_for_methods(&BTreeSet::new()));
+
+    // A `bulk` request enqueued behind the `fast` one does not hide it.
+    push_requests(
+        &mut queues,
+        RemoteSubnet,
+        &vec![
+            RequestBuilder::default()
+                .sender(canister_test_id(14))
+                .receiver(this)
+                .method_name("bulk")
+                .build(),
+        ],
+    );
+    queues.pop_input().unwrap();
+    assert!(!queues.has_input_for_methods(&methods));
+}
+
 /// Wrapper for `CanisterQueues` for tests using requests/responses to/from
 /// arbitrary remote canisters.
 struct CanisterQueuesMultiFixture {
@@ -79,6 +79,7 @@ pub enum CyclesUseCase {
     SchnorrOutcalls = 13,
     VetKd = 14,
     DroppedMessages = 15,
+    BlsOutcalls = 16,
 }
 
 impl CyclesUseCase {
@@ -101,6 +102,7 @@ impl CyclesUseCase {
             Self::SchnorrOutcalls => "SchnorrOutcalls",
             Self::VetKd => "VetKd",
             Self::DroppedMessages => "DroppedMessages",
+            Self::BlsOutcalls => "BlsOutcalls",
         }
     }
 }
@@ -356,6 +358,11 @@ pub struct SystemState {
 
     /// Environment variables.
     pub environment_variables: EnvironmentVariables,
+
+    /// Names of the methods declared latency-sensitive in the canister
+    /// settings. The scheduler prefers the canister while a message for one
+    /// of these methods is at the head of one of its input queues.
+    pub latency_sensitive_methods: BTreeSet<String>,
 }
 
 /// A wrapper around the different canister statuses.
@@ -505,6 +512,7 @@ impl SystemState {
             reserved_balance_limit: None,
             memory_allocation: MemoryAllocation::BestEffort,
             environment_variables: Default::default(),
+            latency_sensitive_methods: Default::default(),
             wasm_memory_threshold: NumBytes::new(0),
             freeze_threshold,
             status,
@@ -552,6 +560,7 @@ impl SystemState {
         next_snapshot_id: u64,
         snapshots_memory_usage: NumBytes,
         environment_variables: BTreeMap<String, String>,
+        latency_sensitive_methods: BTreeSet<String>,
         metrics: &dyn CheckpointLoadingMetrics,
     ) -> Self {
         let system_state = Self {
@@ -583,6 +592,7 @@ impl SystemState {
             next_snapshot_id,
             snapshots_memory_usage,
             environment_variables: EnvironmentVariables::new(environment_variables),
+            latency_sensitive_methods,
         };
         system_state.check_invariants().unwrap_or_else(|msg| {
             metrics.observe_broken_soft_invariant(msg);
@@ -991,6 +1001,13 @@ impl SystemState {
         self.queues.has_input()
     }
 
+    /// Returns true if the ingress queue or any canister input queue holds a
+    /// call to one of the canister's latency-sensitive methods.
+    pub fn has_latency_sensitive_input(&self) -> bool {
+        self.queues
+            .has_input_for_methods(&self.latency_sensitive_methods)
+    }
+
     /// Pushes a `RequestOrResponse` into the induction pool.
     ///
     /// If the message is a `Request`, reserves a slot in the corresponding output
@@ -1671,6 +1688,7 @@ impl SystemState {
             | CyclesUseCase::ECDSAOutcalls
             | CyclesUseCase::SchnorrOutcalls
             | CyclesUseCase::VetKd
+            | CyclesUseCase::BlsOutcalls
             | CyclesUseCase::HTTPOutcalls
             | CyclesUseCase::DeletedCanisters
             | CyclesUseCase::NonConsumed
@@ -2238,6 +2256,7 @@ pub mod testing {
             next_snapshot_id: Default::default(),
             snapshots_memory_usage: Default::default(),
             environment_variables: Default::default(),
+            latency_sensitive_methods: Default::default(),
         };
     }
 }
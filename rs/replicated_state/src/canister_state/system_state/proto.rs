@@ -22,6 +22,7 @@ impl From<CyclesUseCase> for pb::CyclesUseCase {
             CyclesUseCase::SchnorrOutcalls => pb::CyclesUseCase::SchnorrOutcalls,
             CyclesUseCase::VetKd => pb::CyclesUseCase::VetKd,
             CyclesUseCase::DroppedMessages => pb::CyclesUseCase::DroppedMessages,
+            CyclesUseCase::BlsOutcalls => pb::CyclesUseCase::BlsOutcalls,
         }
     }
 }
@@ -51,6 +52,7 @@ impl TryFrom<pb::CyclesUseCase> for CyclesUseCase {
             pb::CyclesUseCase::SchnorrOutcalls => Ok(Self::SchnorrOutcalls),
             pb::CyclesUseCase::VetKd => Ok(Self::VetKd),
             pb::CyclesUseCase::DroppedMessages => Ok(Self::DroppedMessages),
+            pb::CyclesUseCase::BlsOutcalls => Ok(Self::BlsOutcalls),
         }
     }
 }
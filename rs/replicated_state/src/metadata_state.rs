@@ -33,8 +33,8 @@ use ic_types::{
     subnet_id_into_protobuf, subnet_id_try_from_protobuf,
     time::{Time, UNIX_EPOCH},
     xnet::{
-        RejectReason, RejectSignal, StreamFlags, StreamHeader, StreamIndex, StreamIndexedQueue,
-        StreamSlice,
+        MAX_STREAM_CONGESTION_LEVEL, RejectReason, RejectSignal, StreamFlags, StreamHeader,
+        StreamIndex, StreamIndexedQueue, StreamSlice,
     },
 };
 use ic_validate_eq::ValidateEq;
@@ -343,6 +343,7 @@ impl SubnetMetrics {
                 | CyclesUseCase::CanisterCreation
                 | CyclesUseCase::SchnorrOutcalls
                 | CyclesUseCase::VetKd
+                | CyclesUseCase::BlsOutcalls
                 | CyclesUseCase::DroppedMessages
                 | CyclesUseCase::BurnedCycles => total += *cycles,
             }
@@ -588,6 +589,130 @@ impl SystemMetadata {
         Ok(res)
     }
 
+    /// Merges the `SystemMetadata` of subnet B into that of subnet A (`self`), as
+    /// part of a subnet merge. The merged subnet retains the subnet ID of subnet A.
+    ///
+    /// The streams between the two subnets are dropped. Messages in these streams
+    /// that had not yet been inducted by the respective receiving subnet (i.e.
+    /// beyond the reverse stream's `signals_end`, or with a reject signal) are
+    /// returned, to be inducted into the merged state by the caller. Subnet B may
+    /// not have any messages in its streams to third subnets.
+    ///
+    /// Other than that:
+    ///  * the two ingress histories are merged;
+    ///  * subnet B's bitcoin, canister HTTP and `RawRand` subnet call contexts are
+    ///    taken over by subnet A (see [`SubnetCallContextManager::merge()`]);
+    ///  * `batch_time` is the later of the two batch times, to ensure monotonic
+    ///    time for all canisters;
+    ///  * all other metadata is that of subnet A.
+    ///
+    /// Returns an error if either state is in the middle of a subnet split or if
+    /// the two subnets are of different types.
+    pub fn merge(mut self, other: Self) -> Result<(Self, Vec<StreamMessage>), String> {
+        assert_eq!(0, self.heap_delta_estimate.get());
+        assert!(self.expected_compiled_wasms.is_empty());
+
+        // Destructure `other` in order for the compiler to enforce an explicit
+        // decision whenever new fields are added.
+        //
+        // (!) DO NOT USE THE ".." WILDCARD, THIS SERVES THE SAME FUNCTION AS a `match`!
+        let SystemMetadata {
+            ingress_history,
+            streams,
+            // Canister IDs are only ever allocated from subnet A's ranges.
+            canister_allocation_ranges: _,
+            last_generated_canister_id: _,
+            prev_state_hash: _,
+            batch_time,
+            // Overwritten as soon as the round begins, no explicit action needed.
+            network_topology: _,
+            own_subnet_id: other_subnet_id,
+            own_subnet_type: other_subnet_type,
+            // Overwritten as soon as the round begins, no explicit action needed.
+            own_subnet_features: _,
+            // Overwritten as soon as the round begins, no explicit action needed.
+            node_public_keys: _,
+            api_boundary_nodes: _,
+            split_from: other_split_from,
+            subnet_call_context_manager,
+            // Set by `commit_and_certify()` at the end of the round. Not used before.
+            state_sync_version: _,
+            // Set by `commit_and_certify()` at the end of the round. Not used before.
+            certification_version: _,
+            heap_delta_estimate,
+            // Subnet metrics are those of subnet A.
+            subnet_metrics: _,
+            expected_compiled_wasms,
+            bitcoin_get_successors_follow_up_responses,
+            blockmaker_metrics_time_series: _,
+            unflushed_checkpoint_ops: _,
+            cost_schedule: _,
+        } = other;
+
+        assert_eq!(0, heap_delta_estimate.get());
+        assert!(expected_compiled_wasms.is_empty());
+
+        if self.own_subnet_id == other_subnet_id {
+            return Err(format!("Cannot merge subnet {other_subnet_id} into itself"));
+        }
+        if self.split_from.is_some() || other_split_from.is_some() {
+            return Err("Cannot merge a state resulting from an incomplete subnet split".into());
+        }
+        if self.own_subnet_type != other_subnet_type {
+            return Err(format!(
+                "Cannot merge a {:?} subnet into a {:?} subnet",
+                other_subnet_type, self.own_subnet_type
+            ));
+        }
+
+        let mut other_streams = Arc::unwrap_or_clone(streams);
+        let other_to_self = other_streams
+            .remove(&self.own_subnet_id)
+            .unwrap_or_default();
+        if let Some((subnet_id, _)) = other_streams
+            .iter()
+            .find(|(_, stream)| !stream.messages().is_empty())
+        {
+            return Err(format!(
+                "Subnet {other_subnet_id} still has messages in its stream to {subnet_id}"
+            ));
+        }
+        let self_to_other = Arc::make_mut(&mut self.streams)
+            .remove(&other_subnet_id)
+            .unwrap_or_default();
+
+        // Collect the messages not (successfully) inducted by their destination.
+        let not_inducted = |stream: &Stream, reverse_stream: &Stream| {
+            let signals_end = reverse_stream.signals_end();
+            let rejected: BTreeSet<_> = reverse_stream
+                .reject_signals()
+                .iter()
+                .map(|signal| signal.index)
+                .collect();
+            stream
+                .messages()
+                .iter()
+                .filter(|(index, _)| *index >= signals_end || rejected.contains(index))
+                .map(|(_, msg)| msg.clone())
+                .collect::<Vec<_>>()
+        };
+        let mut messages = not_inducted(&self_to_other, &other_to_self);
+        messages.extend(not_inducted(&other_to_self, &self_to_other));
+
+        self.ingress_history.merge(ingress_history);
+        self.subnet_call_context_manager
+            .merge(subnet_call_context_manager)?;
+        for (canister_id, blocks) in bitcoin_get_successors_follow_up_responses {
+            self.bitcoin_get_successors_follow_up_responses
+                .entry(canister_id)
+                .or_default()
+                .extend(blocks);
+        }
+        self.batch_time = self.batch_time.max(batch_time);
+
+        Ok((self, messages))
+    }
+
     /// Adjusts the `MetadataState` as part of the second phase of subnet splitting,
     /// during the new subnets' startup.
     ///
@@ -834,6 +959,14 @@ pub struct Stream {
     /// Stream flags observed in the header of the reverse stream.
     reverse_stream_flags: StreamFlags,
 
+    /// Congestion level signalled to the remote subnet in the stream header,
+    /// derived from the input queue occupancy of the local canisters targeted
+    /// by requests in the reverse stream.
+    congestion_level: u8,
+
+    /// Congestion level observed in the header of the reverse stream.
+    reverse_stream_congestion_level: u8,
+
     /// Number of guaranteed responses per responding canister.
     guaranteed_response_counts: BTreeMap<CanisterId, usize>,
 }
@@ -854,6 +987,8 @@ impl Default for Stream {
             reject_signals,
             messages_size_bytes,
             reverse_stream_flags,
+            congestion_level: 0,
+            reverse_stream_congestion_level: 0,
             guaranteed_response_counts,
         }
     }
@@ -870,6 +1005,8 @@ impl Stream {
             reject_signals: VecDeque::new(),
             messages_size_bytes,
             reverse_stream_flags: Default::default(),
+            congestion_level: 0,
+            reverse_stream_congestion_level: 0,
             guaranteed_response_counts,
         }
     }
@@ -888,6 +1025,8 @@ impl Stream {
             reject_signals,
             messages_size_bytes,
             reverse_stream_flags: Default::default(),
+            congestion_level: 0,
+            reverse_stream_congestion_level: 0,
             guaranteed_response_counts,
         }
     }
@@ -907,6 +1046,7 @@ impl Stream {
             self.signals_end,
             self.reject_signals.clone(),
             StreamFlags::default(),
+            self.congestion_level,
         )
     }
 
@@ -1092,6 +1232,29 @@ impl Stream {
     pub fn set_reverse_stream_flags(&mut self, flags: StreamFlags) {
         self.reverse_stream_flags = flags;
     }
+
+    /// Returns the congestion level signalled in the stream header.
+    pub fn congestion_level(&self) -> u8 {
+        self.congestion_level
+    }
+
+    /// Sets the congestion level signalled in the stream header.
+    ///
+    /// Panics if `congestion_level` is greater than `MAX_STREAM_CONGESTION_LEVEL`.
+    pub fn set_congestion_level(&mut self, congestion_level: u8) {
+        assert!(congestion_level <= MAX_STREAM_CONGESTION_LEVEL);
+        self.congestion_level = congestion_level;
+    }
+
+    /// Returns the congestion level observed in the header of the reverse stream.
+    pub fn reverse_stream_congestion_level(&self) -> u8 {
+        self.reverse_stream_congestion_level
+    }
+
+    /// Sets the congestion level observed in the header of the reverse stream.
+    pub fn set_reverse_stream_congestion_level(&mut self, congestion_level: u8) {
+        self.reverse_stream_congestion_level = congestion_level;
+    }
 }
 
 impl CountBytes for Stream {
@@ -1327,6 +1490,53 @@ impl IngressHistoryState {
         statuses.values().map(|status| status.payload_bytes()).sum()
     }
 
+    /// Merges the ingress history of another subnet into `self`, as part of a
+    /// subnet merge.
+    ///
+    /// Entries for the same message ID may be present in both histories if the
+    /// two subnets resulted from a split (terminal states are preserved on both
+    /// sides). In that case the most recently updated status is retained.
+    fn merge(&mut self, other: IngressHistoryState) {
+        // Destructure `other` in order for the compiler to enforce an explicit
+        // decision whenever new fields are added.
+        //
+        // (!) DO NOT USE THE ".." WILDCARD, THIS SERVES THE SAME FUNCTION AS a `match`!
+        let IngressHistoryState {
+            statuses: other_statuses,
+            pruning_times: other_pruning_times,
+            next_terminal_time: other_next_terminal_time,
+            memory_usage: _,
+        } = other;
+
+        let status_time = |status: &IngressStatus| match status {
+            IngressStatus::Known { time, .. } => Some(*time),
+            IngressStatus::Unknown => None,
+        };
+
+        let statuses = Arc::make_mut(&mut self.statuses);
+        for (message_id, other_status) in other_statuses.iter() {
+            match statuses.get(message_id) {
+                Some(status) if status_time(status) >= status_time(other_status) => {}
+                _ => {
+                    statuses.insert(message_id.clone(), Arc::clone(other_status));
+                }
+            }
+        }
+
+        // Pruning `Done` or missing entries is a no-op, so the union of the two
+        // pruning schedules is safe to use.
+        let pruning_times = Arc::make_mut(&mut self.pruning_times);
+        for (time, message_ids) in other_pruning_times.iter() {
+            pruning_times
+                .entry(*time)
+                .or_default()
+                .extend(message_ids.iter().cloned());
+        }
+
+        self.next_terminal_time = self.next_terminal_time.min(other_next_terminal_time);
+        self.memory_usage = Self::compute_memory_usage(&self.statuses);
+    }
+
     /// Prunes the ingress history (as part of subnet splitting phase 2), retaining:
     ///
     ///  * all terminal states (since they are immutable and will get pruned); and
@@ -1562,8 +1772,17 @@ pub enum UnflushedCheckpointOp {
     DeleteSnapshot(SnapshotId),
     /// A new snapshot was taken from a canister.
     TakeSnapshot(CanisterId, SnapshotId),
+    /// A new incremental snapshot was taken from a canister. Unlike for
+    /// `TakeSnapshot`, the canister's memory files are not copied, as the
+    /// snapshot only holds the pages that differ from its parent snapshot.
+    TakeIncrementalSnapshot(CanisterId, SnapshotId),
     /// A snapshot was loaded to a canister.
     LoadSnapshot(CanisterId, SnapshotId),
+    /// An incremental snapshot (the second element) was loaded to a canister.
+    /// The canister's memories are restored from the full snapshot at the root
+    /// of its chain (the third element), the pages of the incremental snapshots
+    /// in the chain are flushed on top as part of the canister's page deltas.
+    LoadIncrementalSnapshot(CanisterId, SnapshotId, SnapshotId),
     /// A snapshot was created via metadata upload.
     UploadSnapshotMetadata(SnapshotId),
     /// Binary data was uploaded to a snapshot
@@ -1604,6 +1823,14 @@ impl UnflushedCheckpointOps {
         ));
     }
 
+    pub fn take_incremental_snapshot(&mut self, canister_id: CanisterId, snapshot_id: SnapshotId) {
+        self.operations
+            .push(UnflushedCheckpointOp::TakeIncrementalSnapshot(
+                canister_id,
+                snapshot_id,
+            ));
+    }
+
     pub fn load_snapshot(&mut self, canister_id: CanisterId, snapshot_id: SnapshotId) {
         self.operations.push(UnflushedCheckpointOp::LoadSnapshot(
             canister_id,
@@ -1611,6 +1838,20 @@ impl UnflushedCheckpointOps {
         ));
     }
 
+    pub fn load_incremental_snapshot(
+        &mut self,
+        canister_id: CanisterId,
+        snapshot_id: SnapshotId,
+        root_snapshot_id: SnapshotId,
+    ) {
+        self.operations
+            .push(UnflushedCheckpointOp::LoadIncrementalSnapshot(
+                canister_id,
+                snapshot_id,
+                root_snapshot_id,
+            ));
+    }
+
     pub fn create_snapshot_from_metadata(&mut self, snapshot_id: SnapshotId) {
         self.operations
             .push(UnflushedCheckpointOp::UploadSnapshotMetadata(snapshot_id));
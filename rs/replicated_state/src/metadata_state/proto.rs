@@ -491,6 +491,8 @@ impl From<&Stream> for pb_queues::Stream {
             reverse_stream_flags: Some(pb_queues::StreamFlags {
                 deprecated_responses_only: item.reverse_stream_flags.deprecated_responses_only,
             }),
+            congestion_level: item.congestion_level as u32,
+            reverse_stream_congestion_level: item.reverse_stream_congestion_level as u32,
         }
     }
 }
@@ -533,6 +535,17 @@ impl TryFrom<pb_queues::Stream> for Stream {
             }
         }
 
+        if item.congestion_level > MAX_STREAM_CONGESTION_LEVEL as u32
+            || item.reverse_stream_congestion_level > MAX_STREAM_CONGESTION_LEVEL as u32
+        {
+            return Err(ProxyDecodeError::Other(format!(
+                "congestion level above maximum {}: {}, reverse stream: {}",
+                MAX_STREAM_CONGESTION_LEVEL,
+                item.congestion_level,
+                item.reverse_stream_congestion_level
+            )));
+        }
+
         Ok(Self {
             messages,
             signals_end,
@@ -544,6 +557,8 @@ impl TryFrom<pb_queues::Stream> for Stream {
                     deprecated_responses_only: flags.deprecated_responses_only,
                 })
                 .unwrap_or_default(),
+            congestion_level: item.congestion_level as u8,
+            reverse_stream_congestion_level: item.reverse_stream_congestion_level as u8,
             guaranteed_response_counts,
         })
     }
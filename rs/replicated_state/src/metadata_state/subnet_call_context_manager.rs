@@ -3,7 +3,7 @@ pub mod proto;
 use ic_btc_replica_types::{GetSuccessorsRequestInitial, SendTransactionRequest};
 use ic_logger::{ReplicaLogger, info};
 use ic_management_canister_types_private::{
-    EcdsaKeyId, MasterPublicKeyId, SchnorrKeyId, VetKdKeyId,
+    BlsKeyId, EcdsaKeyId, MasterPublicKeyId, SchnorrKeyId, VetKdKeyId,
 };
 use ic_types::{
     CanisterId, ExecutionRound, Height, NodeId, RegistryVersion, Time,
@@ -14,6 +14,7 @@ use ic_types::{
             EcdsaPreSignatureQuadruple, SchnorrPreSignatureTranscript, idkg::IDkgTranscript,
         },
         threshold_sig::ni_dkg::{NiDkgId, NiDkgTargetId, id::ni_dkg_target_id},
+        vetkd::{VetKdArgs, VetKdDerivationContext, VetKdOutput},
     },
     messages::{CallbackId, CanisterCall, Request, StopCanisterCallId},
     node_id_into_protobuf, node_id_try_from_option,
@@ -433,6 +434,69 @@ impl SubnetCallContextManager {
         removed
     }
 
+    /// Merges the subnet call contexts of another subnet into `self`, as part of
+    /// a subnet merge.
+    ///
+    /// Bitcoin and canister HTTP contexts are re-registered under fresh callback
+    /// IDs (their responses are only ever delivered by the merged subnet's own
+    /// consensus, so the old IDs are never referenced again); `RawRand` contexts
+    /// are appended as they are. All other contexts are bound to the other
+    /// subnet's chain keys or to in-progress executions and the merge is refused
+    /// if any of them is present.
+    pub(crate) fn merge(&mut self, other: SubnetCallContextManager) -> Result<(), String> {
+        // Destructure `other` in order for the compiler to enforce an explicit
+        // decision whenever new fields are added.
+        //
+        // (!) DO NOT USE THE ".." WILDCARD, THIS SERVES THE SAME FUNCTION AS a `match`!
+        let SubnetCallContextManager {
+            next_callback_id: _,
+            setup_initial_dkg_contexts,
+            sign_with_threshold_contexts,
+            canister_http_request_contexts,
+            reshare_chain_key_contexts,
+            bitcoin_get_successors_contexts,
+            bitcoin_send_transaction_internal_contexts,
+            canister_management_calls,
+            raw_rand_contexts,
+            pre_signature_stashes,
+        } = other;
+
+        let non_mergeable = [
+            ("setup_initial_dkg", setup_initial_dkg_contexts.len()),
+            ("sign_with_threshold", sign_with_threshold_contexts.len()),
+            ("reshare_chain_key", reshare_chain_key_contexts.len()),
+            ("pre_signature_stash", pre_signature_stashes.len()),
+            (
+                "install_code",
+                canister_management_calls.install_code_calls_len(),
+            ),
+            (
+                "stop_canister",
+                canister_management_calls.stop_canister_calls_len(),
+            ),
+        ];
+        for (kind, count) in non_mergeable {
+            if count != 0 {
+                return Err(format!(
+                    "Cannot merge a subnet with {count} in-progress {kind} contexts"
+                ));
+            }
+        }
+
+        for context in canister_http_request_contexts.into_values() {
+            self.push_context(SubnetCallContext::CanisterHttpRequest(context));
+        }
+        for context in bitcoin_get_successors_contexts.into_values() {
+            self.push_context(SubnetCallContext::BitcoinGetSuccessors(context));
+        }
+        for context in bitcoin_send_transaction_internal_contexts.into_values() {
+            self.push_context(SubnetCallContext::BitcoinSendTransactionInternal(context));
+        }
+        self.raw_rand_contexts.extend(raw_rand_contexts);
+
+        Ok(())
+    }
+
     /// Returns the number of `sign_with_threshold_contexts` per key id.
     pub fn sign_with_threshold_contexts_count(&self, key_id: &MasterPublicKeyId) -> usize {
         self.sign_with_threshold_contexts
@@ -447,6 +511,9 @@ impl SubnetCallContextManager {
                 (MasterPublicKeyId::VetKd(vetkd_key_id), ThresholdArguments::VetKd(args)) => {
                     args.key_id == *vetkd_key_id
                 }
+                (MasterPublicKeyId::Bls(bls_key_id), ThresholdArguments::Bls(args)) => {
+                    args.key_id == *bls_key_id
+                }
                 _ => false,
             })
             .count()
@@ -475,6 +542,14 @@ impl SubnetCallContextManager {
             .map(|(cid, context)| (*cid, context.clone()))
             .collect()
     }
+
+    pub fn sign_with_bls_contexts(&self) -> BTreeMap<CallbackId, SignWithThresholdContext> {
+        self.sign_with_threshold_contexts
+            .iter()
+            .filter(|(_, context)| context.is_bls())
+            .map(|(cid, context)| (*cid, context.clone()))
+            .collect()
+    }
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -526,11 +601,23 @@ pub struct VetKdArguments {
     pub height: Height,
 }
 
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BlsArguments {
+    pub key_id: BlsKeyId,
+    /// The canister that the key is derived for.
+    pub canister_id: CanisterId,
+    /// The message to sign, or `None` if only the public key is requested.
+    pub message: Option<Arc<Vec<u8>>>,
+    pub ni_dkg_id: NiDkgId,
+    pub height: Height,
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum ThresholdArguments {
     Ecdsa(EcdsaArguments),
     Schnorr(SchnorrArguments),
     VetKd(VetKdArguments),
+    Bls(BlsArguments),
 }
 
 impl ThresholdArguments {
@@ -540,6 +627,7 @@ impl ThresholdArguments {
             ThresholdArguments::Ecdsa(args) => MasterPublicKeyId::Ecdsa(args.key_id.clone()),
             ThresholdArguments::Schnorr(args) => MasterPublicKeyId::Schnorr(args.key_id.clone()),
             ThresholdArguments::VetKd(args) => MasterPublicKeyId::VetKd(args.key_id.clone()),
+            ThresholdArguments::Bls(args) => MasterPublicKeyId::Bls(args.key_id.clone()),
         }
     }
 }
@@ -603,6 +691,7 @@ impl SignWithThresholdContext {
             ThresholdArguments::Ecdsa(args) => MasterPublicKeyId::Ecdsa(args.key_id.clone()),
             ThresholdArguments::Schnorr(args) => MasterPublicKeyId::Schnorr(args.key_id.clone()),
             ThresholdArguments::VetKd(args) => MasterPublicKeyId::VetKd(args.key_id.clone()),
+            ThresholdArguments::Bls(args) => MasterPublicKeyId::Bls(args.key_id.clone()),
         }
     }
 
@@ -614,7 +703,7 @@ impl SignWithThresholdContext {
             ThresholdArguments::Schnorr(args) => {
                 self.matched_pre_signature.is_none() && args.pre_signature.is_none()
             }
-            ThresholdArguments::VetKd(_) => false,
+            ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => false,
         }
     }
 
@@ -633,11 +722,25 @@ impl SignWithThresholdContext {
         matches!(&self.args, ThresholdArguments::VetKd(_))
     }
 
+    /// Returns true if arguments are for BLS.
+    pub fn is_bls(&self) -> bool {
+        matches!(&self.args, ThresholdArguments::Bls(_))
+    }
+
     /// Returns true if arguments are for a context handled by IDKG.
     pub fn is_idkg(&self) -> bool {
         match &self.args {
             ThresholdArguments::Ecdsa(_) | ThresholdArguments::Schnorr(_) => true,
-            ThresholdArguments::VetKd(_) => false,
+            ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => false,
+        }
+    }
+
+    /// Returns true if arguments are for a context handled by NiDkg, i.e., a
+    /// context that is answered by running the vetKD protocol.
+    pub fn is_nidkg(&self) -> bool {
+        match &self.args {
+            ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => true,
+            ThresholdArguments::Ecdsa(_) | ThresholdArguments::Schnorr(_) => false,
         }
     }
 
@@ -671,6 +774,61 @@ impl SignWithThresholdContext {
         }
     }
 
+    /// Returns BLS arguments.
+    /// Panics if arguments are not for BLS
+    /// Should only be called if `is_bls` returns true.
+    pub fn bls_args(&self) -> &BlsArguments {
+        match &self.args {
+            ThresholdArguments::Bls(args) => args,
+            _ => panic!("BLS arguments not found."),
+        }
+    }
+
+    /// Returns the height at which the NiDkg transcript of this context was
+    /// chosen, or `None` if the context is not handled by NiDkg.
+    pub fn nidkg_height(&self) -> Option<Height> {
+        match &self.args {
+            ThresholdArguments::VetKd(args) => Some(args.height),
+            ThresholdArguments::Bls(args) => Some(args.height),
+            ThresholdArguments::Ecdsa(_) | ThresholdArguments::Schnorr(_) => None,
+        }
+    }
+
+    /// Returns the arguments of the vetKD protocol run that answers this
+    /// context, or `None` if the context is not handled by NiDkg.
+    ///
+    /// BLS public keys and signatures are created by the vetKD protocol with
+    /// [`VetKdOutput::BlsPublicKey`] and [`VetKdOutput::BlsSignature`], which
+    /// do not use a transport public key.
+    pub fn vetkd_protocol_args(&self) -> Option<VetKdArgs> {
+        match &self.args {
+            ThresholdArguments::VetKd(args) => Some(VetKdArgs {
+                context: VetKdDerivationContext {
+                    caller: self.request.sender.into(),
+                    context: self.derivation_path.iter().flatten().cloned().collect(),
+                },
+                ni_dkg_id: args.ni_dkg_id.clone(),
+                input: args.input.to_vec(),
+                transport_public_key: args.transport_public_key.clone(),
+                output: VetKdOutput::EncryptedKey,
+            }),
+            ThresholdArguments::Bls(args) => Some(VetKdArgs {
+                context: VetKdDerivationContext::for_bls_signature(
+                    args.canister_id.get(),
+                    &self.derivation_path,
+                ),
+                ni_dkg_id: args.ni_dkg_id.clone(),
+                input: args.message.as_deref().cloned().unwrap_or_default(),
+                transport_public_key: vec![],
+                output: match args.message {
+                    Some(_) => VetKdOutput::BlsSignature,
+                    None => VetKdOutput::BlsPublicKey,
+                },
+            }),
+            ThresholdArguments::Ecdsa(_) | ThresholdArguments::Schnorr(_) => None,
+        }
+    }
+
     /// Return all IDkgTranscripts included in this context
     pub fn iter_idkg_transcripts(&self) -> impl Iterator<Item = &IDkgTranscript> {
         let refs = match &self.args {
@@ -697,7 +855,7 @@ impl SignWithThresholdContext {
                     ]
                 })
                 .unwrap_or_default(),
-            ThresholdArguments::VetKd(_) => vec![],
+            ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => vec![],
         };
         refs.into_iter()
     }
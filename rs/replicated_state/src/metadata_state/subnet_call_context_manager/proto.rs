@@ -489,6 +489,31 @@ impl TryFrom<pb_metadata::VetKdArguments> for VetKdArguments {
     }
 }
 
+impl From<&BlsArguments> for pb_metadata::BlsArguments {
+    fn from(args: &BlsArguments) -> Self {
+        Self {
+            key_id: Some((&args.key_id).into()),
+            message: args.message.as_ref().map(|message| message.to_vec()),
+            ni_dkg_id: Some((args.ni_dkg_id.clone()).into()),
+            height: args.height.get(),
+            canister_id: Some(args.canister_id.into()),
+        }
+    }
+}
+
+impl TryFrom<pb_metadata::BlsArguments> for BlsArguments {
+    type Error = ProxyDecodeError;
+    fn try_from(context: pb_metadata::BlsArguments) -> Result<Self, Self::Error> {
+        Ok(BlsArguments {
+            key_id: try_from_option_field(context.key_id, "BlsArguments::key_id")?,
+            canister_id: try_from_option_field(context.canister_id, "BlsArguments::canister_id")?,
+            message: context.message.map(Arc::new),
+            ni_dkg_id: try_from_option_field(context.ni_dkg_id, "BlsArguments::ni_dkg_id")?,
+            height: Height::from(context.height),
+        })
+    }
+}
+
 impl From<&ThresholdArguments> for pb_metadata::ThresholdArguments {
     fn from(context: &ThresholdArguments) -> Self {
         let threshold_scheme = match context {
@@ -501,6 +526,9 @@ impl From<&ThresholdArguments> for pb_metadata::ThresholdArguments {
             ThresholdArguments::VetKd(args) => {
                 pb_metadata::threshold_arguments::ThresholdScheme::Vetkd(args.into())
             }
+            ThresholdArguments::Bls(args) => {
+                pb_metadata::threshold_arguments::ThresholdScheme::Bls(args.into())
+            }
         };
         Self {
             threshold_scheme: Some(threshold_scheme),
@@ -525,6 +553,9 @@ impl TryFrom<pb_metadata::ThresholdArguments> for ThresholdArguments {
             pb_metadata::threshold_arguments::ThresholdScheme::Vetkd(args) => {
                 Ok(ThresholdArguments::VetKd(VetKdArguments::try_from(args)?))
             }
+            pb_metadata::threshold_arguments::ThresholdScheme::Bls(args) => {
+                Ok(ThresholdArguments::Bls(BlsArguments::try_from(args)?))
+            }
         }
     }
 }
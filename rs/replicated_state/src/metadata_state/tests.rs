@@ -1,8 +1,8 @@
 use super::*;
 use crate::metadata_state::subnet_call_context_manager::{
-    EcdsaArguments, EcdsaMatchedPreSignature, InstallCodeCall, PreSignatureStash, RawRandContext,
-    SchnorrArguments, SchnorrMatchedPreSignature, SignWithThresholdContext, StopCanisterCall,
-    SubnetCallContext, SubnetCallContextManager, ThresholdArguments,
+    BlsArguments, EcdsaArguments, EcdsaMatchedPreSignature, InstallCodeCall, PreSignatureStash,
+    RawRandContext, SchnorrArguments, SchnorrMatchedPreSignature, SignWithThresholdContext,
+    StopCanisterCall, SubnetCallContext, SubnetCallContextManager, ThresholdArguments,
 };
 use assert_matches::assert_matches;
 use ic_crypto_test_utils_canister_threshold_sigs::{
@@ -13,7 +13,8 @@ use ic_crypto_test_utils_reproducible_rng::{ReproducibleRng, reproducible_rng};
 use ic_error_types::{ErrorCode, UserError};
 use ic_limits::MAX_INGRESS_TTL;
 use ic_management_canister_types_private::{
-    EcdsaCurve, EcdsaKeyId, IC_00, MasterPublicKeyId, SchnorrAlgorithm, SchnorrKeyId,
+    BlsCurve, BlsKeyId, EcdsaCurve, EcdsaKeyId, IC_00, MasterPublicKeyId, SchnorrAlgorithm,
+    SchnorrKeyId,
 };
 use ic_protobuf::proxy::ProxyDecodeError;
 use ic_protobuf::state::queues::v1 as pb_queues;
@@ -38,6 +39,7 @@ use ic_types::{
             SchnorrPreSignatureTranscript,
             idkg::{IDkgDealers, IDkgReceivers, IDkgTranscript},
         },
+        threshold_sig::ni_dkg::{NiDkgId, NiDkgMasterPublicKeyId, NiDkgTag, NiDkgTargetSubnet},
     },
     ingress::WasmResult,
     messages::{CallbackId, CanisterCall, Payload, Refund, Request, RequestMetadata},
@@ -901,6 +903,68 @@ fn sign_with_threshold_context_roundtrip() {
     );
 }
 
+#[test]
+fn sign_with_bls_context_roundtrip() {
+    let key_id = BlsKeyId {
+        curve: BlsCurve::Bls12_381_G2,
+        name: "bls_key".to_string(),
+    };
+    let ni_dkg_id = NiDkgId {
+        start_block_height: Height::new(5),
+        dealer_subnet: subnet_test_id(1),
+        dkg_tag: NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::Bls(key_id.clone())),
+        target_subnet: NiDkgTargetSubnet::Local,
+    };
+    let context = SignWithThresholdContext {
+        request: RequestBuilder::new().build(),
+        args: ThresholdArguments::Bls(BlsArguments {
+            key_id,
+            canister_id: canister_test_id(3),
+            message: Some(Arc::new(vec![1; 32])),
+            ni_dkg_id,
+            height: Height::new(7),
+        }),
+        derivation_path: Arc::new(vec![vec![2; 4]]),
+        pseudo_random_id: [1; 32],
+        batch_time: UNIX_EPOCH,
+        matched_pre_signature: None,
+        nonce: None,
+    };
+    assert!(context.is_bls());
+    assert!(context.is_nidkg());
+    assert!(!context.requires_pre_signature());
+    assert_eq!(context.nidkg_height(), Some(Height::new(7)));
+
+    let mut public_key_context = context.clone();
+    if let ThresholdArguments::Bls(args) = &mut public_key_context.args {
+        args.message = None;
+    }
+
+    let mut subnet_call_context_manager = SubnetCallContextManager::default();
+    subnet_call_context_manager
+        .sign_with_threshold_contexts
+        .insert(CallbackId::new(1), context);
+    subnet_call_context_manager
+        .sign_with_threshold_contexts
+        .insert(CallbackId::new(2), public_key_context);
+    assert_eq!(
+        subnet_call_context_manager.sign_with_bls_contexts().len(),
+        2
+    );
+
+    // Encode and decode.
+    let subnet_call_context_manager_proto: pb_metadata::SubnetCallContextManager =
+        (&subnet_call_context_manager).into();
+    let deserialized_subnet_call_context_manager: SubnetCallContextManager =
+        SubnetCallContextManager::try_from((UNIX_EPOCH, subnet_call_context_manager_proto))
+            .unwrap();
+
+    assert_eq!(
+        subnet_call_context_manager,
+        deserialized_subnet_call_context_manager
+    );
+}
+
 #[test]
 fn empty_network_topology() {
     let network_topology = NetworkTopology {
@@ -1800,6 +1864,8 @@ fn stream_roundtrip_encoding() {
     stream.set_reverse_stream_flags(StreamFlags {
         deprecated_responses_only: true,
     });
+    stream.set_congestion_level(2);
+    stream.set_reverse_stream_congestion_level(MAX_STREAM_CONGESTION_LEVEL);
 
     let proto_stream: pb_queues::Stream = (&stream).into();
     let deserialized_stream: Stream = proto_stream.try_into().expect("bad conversion");
@@ -1814,6 +1880,8 @@ fn deserializing_stream_fails_for_bad_reject_signals() {
         signals_end: 153,
         reject_signals: Vec::new(),
         reverse_stream_flags: None,
+        congestion_level: 0,
+        reverse_stream_congestion_level: 0,
     };
 
     // Deserializing a stream with duplicate reject signals (by index) should fail.
@@ -1857,6 +1925,24 @@ fn deserializing_stream_fails_for_bad_reject_signals() {
     );
 }
 
+#[test]
+fn deserializing_stream_fails_for_congestion_level_above_maximum() {
+    let bad_stream = pb_queues::Stream {
+        messages_begin: 0,
+        messages: Vec::new(),
+        signals_end: 0,
+        reject_signals: Vec::new(),
+        reverse_stream_flags: None,
+        congestion_level: 0,
+        reverse_stream_congestion_level: MAX_STREAM_CONGESTION_LEVEL as u32 + 1,
+    };
+    let deserialized_result: Result<Stream, _> = bad_stream.try_into();
+    assert_matches!(
+        deserialized_result,
+        Err(ProxyDecodeError::Other(err_msg)) if err_msg == "congestion level above maximum 4: 0, reverse stream: 5"
+    );
+}
+
 #[test]
 fn reject_reason_proto_roundtrip() {
     for initial in RejectReason::iter() {
@@ -30,7 +30,7 @@ use libc::off_t;
 use page_allocator::Page;
 use prometheus::{Histogram, HistogramVec, IntCounter, IntCounterVec};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::ops::Range;
 use std::os::unix::io::RawFd;
 use std::sync::Arc;
@@ -806,6 +806,21 @@ impl PageMap {
         std::mem::take(&mut self.unflushed_delta);
     }
 
+    /// Returns the indices of all pages that may differ between `self` and
+    /// `other`: the pages of both page deltas and of the overlay files that are
+    /// not shared by the two page maps. Returns `None` if the page maps are not
+    /// backed by the same base file, in which case any page may differ.
+    ///
+    /// For page maps that derive from a common version, e.g. the memory of a
+    /// canister and of a snapshot of it, this only depends on the size of the
+    /// changes since that version, not on the size of the page maps.
+    pub fn pages_possibly_differing_from(&self, other: &PageMap) -> Option<BTreeSet<PageIndex>> {
+        let mut pages = self.storage.pages_in_unshared_overlays(&other.storage)?;
+        pages.extend(self.page_delta.iter().map(|(index, _)| *index));
+        pages.extend(other.page_delta.iter().map(|(index, _)| *index));
+        Some(pages)
+    }
+
     pub fn get_page_delta_indices(&self) -> Vec<PageIndex> {
         self.page_delta.iter().map(|(index, _)| *index).collect()
     }
@@ -6,6 +6,7 @@ use ic_sys::{PageBytes, page_bytes_from_ptr};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::fs::{File, OpenOptions};
+use std::os::unix::fs::MetadataExt;
 use std::os::unix::io::AsRawFd;
 use std::os::unix::prelude::FromRawFd;
 use std::path::Path;
@@ -31,6 +32,8 @@ pub(crate) struct Mapping {
     mmap: ScopedMmap,
     _file: File, // It is not used but it keeps the `file_descriptor` alive.
     file_descriptor: FileDescriptor,
+    /// The device and inode numbers of the file, if they could be determined.
+    file_id: Option<(u64, u64)>,
 }
 
 impl Mapping {
@@ -56,10 +59,15 @@ impl Mapping {
                 }
             })?;
             let fd = file.as_raw_fd();
+            let file_id = file
+                .metadata()
+                .ok()
+                .map(|metadata| (metadata.dev(), metadata.ino()));
             Ok(Some(Mapping {
                 _file: file,
                 file_descriptor: FileDescriptor { fd },
                 mmap,
+                file_id,
             }))
         }
     }
@@ -109,6 +117,13 @@ impl Mapping {
         Mapping::new(file, serialized_mapping.file_len as usize, None)
     }
 
+    /// Returns true if both mappings are backed by the same file, e.g. because
+    /// one file is a hardlink of the other. Files are immutable once mapped, so
+    /// such mappings have the same contents.
+    pub(crate) fn is_same_file(&self, other: &Mapping) -> bool {
+        std::ptr::eq(self, other) || (self.file_id.is_some() && self.file_id == other.file_id)
+    }
+
     /// Returns the `PageBytes` read from bytes `[PAGE_SIZE * page_index..PAGE_SIZE * (page_index + 1))`
     pub(crate) fn get_page(&self, page_index: PageIndex) -> &PageBytes {
         let num_pages = self.mmap.len() / PAGE_SIZE;
@@ -181,6 +196,16 @@ impl Checkpoint {
         })
     }
 
+    /// Returns true if both checkpoints are backed by the same file, or are
+    /// both empty.
+    pub fn is_same_file(&self, other: &Checkpoint) -> bool {
+        match (&self.mapping, &other.mapping) {
+            (None, None) => true,
+            (Some(mapping), Some(other_mapping)) => mapping.is_same_file(other_mapping),
+            _ => false,
+        }
+    }
+
     /// Returns the page with the specified `page_number`.
     pub fn get_page(&self, page_index: PageIndex) -> &PageBytes {
         match self.mapping {
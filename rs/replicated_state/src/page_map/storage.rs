@@ -230,6 +230,15 @@ impl Storage {
         self.init_or_die().num_logical_pages()
     }
 
+    /// See `StorageImpl::pages_in_unshared_overlays`.
+    pub fn pages_in_unshared_overlays(&self, other: &Storage) -> Option<BTreeSet<PageIndex>> {
+        if Arc::ptr_eq(&self.storage_impl, &other.storage_impl) {
+            return Some(BTreeSet::new());
+        }
+        self.init_or_die()
+            .pages_in_unshared_overlays(other.init_or_die())
+    }
+
     pub fn serialize(&self) -> StorageSerialization {
         self.init_or_die().serialize()
     }
@@ -372,6 +381,46 @@ impl StorageImpl {
         }
     }
 
+    /// Returns the indices of all pages contained in the overlays of either
+    /// storage after the longest common prefix of overlays, i.e. the only pages
+    /// whose contents may differ between the two storages.
+    ///
+    /// Returns `None` if the storages are not backed by the same base, in which
+    /// case any page may differ. Files are considered the same if they are
+    /// backed by the same inode, as is the case for the hardlinked files of a
+    /// canister and its snapshots.
+    fn pages_in_unshared_overlays(&self, other: &StorageImpl) -> Option<BTreeSet<PageIndex>> {
+        let same_base = match (&self.base, &other.base) {
+            (BaseFile::Base(base), BaseFile::Base(other_base)) => base.is_same_file(other_base),
+            (BaseFile::Overlay(overlays), BaseFile::Overlay(other_overlays)) => {
+                overlays.len() == other_overlays.len()
+                    && overlays
+                        .iter()
+                        .zip(other_overlays)
+                        .all(|(overlay, other_overlay)| overlay.is_same_file(other_overlay))
+            }
+            _ => false,
+        };
+        if !same_base {
+            return None;
+        }
+        let num_shared = self
+            .overlays
+            .iter()
+            .zip(&other.overlays)
+            .take_while(|(overlay, other_overlay)| overlay.is_same_file(other_overlay))
+            .count();
+        Some(
+            self.overlays[num_shared..]
+                .iter()
+                .chain(&other.overlays[num_shared..])
+                .flat_map(|overlay| overlay.index_iter())
+                .flat_map(|range| range.start_page.get()..range.end_page.get())
+                .map(PageIndex::from)
+                .collect(),
+        )
+    }
+
     /// Number of (logical) pages contained in this `Storage`.
     pub(crate) fn num_logical_pages(&self) -> usize {
         let base = match &self.base {
@@ -443,6 +492,11 @@ impl OverlayFile {
             })
     }
 
+    /// Returns true if both overlays are backed by the same file.
+    fn is_same_file(&self, other: &OverlayFile) -> bool {
+        self.mapping.is_same_file(&other.mapping)
+    }
+
     /// Get the page at `page_index`.
     /// Returns `None` for pages not contained in this overlay.
     fn get_page(&self, page_index: PageIndex) -> Option<&PageBytes> {
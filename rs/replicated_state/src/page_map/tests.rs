@@ -891,3 +891,76 @@ fn restrict_to_range() {
 
     assert_eq!(memory_instructions, expected_memory_instructions);
 }
+
+#[test]
+fn pages_possibly_differing_only_include_unshared_overlays_and_deltas() {
+    let tmp = tempfile::Builder::new()
+        .prefix("checkpoints")
+        .tempdir()
+        .unwrap();
+    let metrics = StorageMetrics::new(&MetricsRegistry::new());
+    let lsmt_config = LsmtConfig {
+        shard_num_pages: u64::MAX,
+    };
+    let storage_layout = |dir: &str| {
+        let dir_path = tmp.path().join(dir);
+        std::fs::create_dir_all(&dir_path).unwrap();
+        ShardedTestStorageLayout {
+            base: dir_path.join("vmemory_0.bin"),
+            dir_path,
+            overlay_suffix: "vmemory_0.overlay".into(),
+        }
+    };
+    let open = |dir: &str, height: u64| {
+        PageMap::open(
+            Box::new(storage_layout(dir)),
+            Height::new(height),
+            Arc::new(TestPageAllocatorFileDescriptorImpl::new()),
+        )
+        .unwrap()
+    };
+
+    let pages: Vec<_> = (0..10).map(|i| [i as u8; PAGE_SIZE]).collect();
+    let mut page_map = PageMap::new_for_testing();
+    page_map.update(
+        &pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| (PageIndex::new(i as u64), page))
+            .collect::<Vec<_>>(),
+    );
+    page_map
+        .persist_delta(&storage_layout("a"), Height::new(0), &lsmt_config, &metrics)
+        .unwrap();
+    // `b` holds hardlinks of the files of `a`, and `c` holds copies.
+    for (dir, link) in [("b", true), ("c", false)] {
+        let src = storage_layout("a").overlay(Height::new(0), Shard::new(0));
+        let dst = storage_layout(dir).overlay(Height::new(0), Shard::new(0));
+        if link {
+            std::fs::hard_link(src, dst).unwrap();
+        } else {
+            std::fs::copy(src, dst).unwrap();
+        }
+    }
+
+    let mut linked = open("b", 0);
+    assert_eq!(
+        open("a", 0).pages_possibly_differing_from(&linked),
+        Some(Default::default())
+    );
+    assert_eq!(
+        open("a", 0).pages_possibly_differing_from(&open("c", 0)),
+        None
+    );
+
+    let mut page_map = open("a", 0);
+    page_map.update(&[(PageIndex::new(3), &[42; PAGE_SIZE])]);
+    page_map
+        .persist_delta(&storage_layout("a"), Height::new(1), &lsmt_config, &metrics)
+        .unwrap();
+    linked.update(&[(PageIndex::new(5), &[42; PAGE_SIZE])]);
+    assert_eq!(
+        open("a", 1).pages_possibly_differing_from(&linked),
+        Some([PageIndex::new(3), PageIndex::new(5)].into())
+    );
+}
@@ -32,6 +32,7 @@ use ic_types::{
     ingress::IngressStatus,
     messages::{
         CallbackId, CanisterMessage, Ingress, MessageId, Refund, RequestOrResponse, Response,
+        StreamMessage,
     },
     time::CoarseTime,
 };
@@ -1277,9 +1278,15 @@ impl ReplicatedState {
         snapshot_id: SnapshotId,
         snapshot: Arc<CanisterSnapshot>,
     ) -> SnapshotId {
-        self.metadata
-            .unflushed_checkpoint_ops
-            .take_snapshot(snapshot.canister_id(), snapshot_id);
+        if snapshot.parent().is_some() {
+            self.metadata
+                .unflushed_checkpoint_ops
+                .take_incremental_snapshot(snapshot.canister_id(), snapshot_id);
+        } else {
+            self.metadata
+                .unflushed_checkpoint_ops
+                .take_snapshot(snapshot.canister_id(), snapshot_id);
+        }
         self.canister_snapshots.push(snapshot_id, snapshot)
     }
 
@@ -1483,6 +1490,134 @@ impl ReplicatedState {
         );
     }
 
+    /// Merges the replicated state of subnet B (`other`) into that of subnet A
+    /// (`self`), the reverse of a subnet split. The merged subnet retains the
+    /// subnet ID of subnet A.
+    ///
+    /// Subnet B is expected to have been halted after draining its streams to all
+    /// subnets other than A; and to have no in-progress threshold signature or
+    /// canister management calls. Specifically, the merge consists of:
+    ///  * Taking over the canisters and canister snapshots of subnet B.
+    ///  * Merging the two subnets' metadata (see [`SystemMetadata::merge()`]),
+    ///    including the ingress history and subnet call contexts.
+    ///  * Inducting all messages in the streams between A and B that had not yet
+    ///    been inducted by the respective destination subnet.
+    ///  * Moving all messages in subnet B's subnet input queues into A's subnet
+    ///    queues; and all of B's pooled refunds into A's refund pool.
+    ///
+    /// Returns an error if the preconditions above do not hold or if any of the
+    /// messages could not be inducted. The result is a regular state, without a
+    /// split marker, requiring no further adjustments on subnet startup.
+    pub fn merge(self, other: Self) -> Result<Self, String> {
+        // Destructure `self` and `other` and put them back together, in order for
+        // the compiler to enforce an explicit decision whenever new fields are added.
+        let Self {
+            mut canister_states,
+            metadata,
+            mut subnet_queues,
+            mut refunds,
+            consensus_queue,
+            epoch_query_stats: _,
+            mut canister_snapshots,
+        } = self;
+        let Self {
+            canister_states: other_canister_states,
+            metadata: other_metadata,
+            subnet_queues: mut other_subnet_queues,
+            refunds: other_refunds,
+            consensus_queue: other_consensus_queue,
+            epoch_query_stats: _,
+            canister_snapshots: other_canister_snapshots,
+        } = other;
+
+        // Consensus queue is always empty at the end of the round.
+        assert!(consensus_queue.is_empty());
+        assert!(other_consensus_queue.is_empty());
+
+        for (canister_id, canister_state) in other_canister_states {
+            if canister_states
+                .insert(canister_id, canister_state)
+                .is_some()
+            {
+                return Err(format!(
+                    "Canister {canister_id} is present in both states being merged"
+                ));
+            }
+        }
+        canister_snapshots.merge(other_canister_snapshots)?;
+
+        for refund in other_refunds.iter() {
+            refunds.add(refund.recipient(), refund.amount());
+        }
+
+        // Subnet B's output responses would be routed from the wrong subnet.
+        if other_subnet_queues.has_output() {
+            return Err("Subnet queues of the merged subnet have undelivered output".into());
+        }
+        let own_subnet_type = metadata.own_subnet_type;
+        // Memory limits are not enforced: all messages were already accounted
+        // for on one of the two subnets.
+        let mut available_memory = i64::MAX;
+        while let Some(input) = other_subnet_queues.pop_input() {
+            match input {
+                CanisterInput::Ingress(ingress) => {
+                    subnet_queues.push_ingress(Arc::unwrap_or_clone(ingress))
+                }
+                CanisterInput::Request(request) => {
+                    let input_queue_type = if canister_states.contains_key(&request.sender) {
+                        InputQueueType::LocalSubnet
+                    } else {
+                        InputQueueType::RemoteSubnet
+                    };
+                    push_input(
+                        &mut subnet_queues,
+                        RequestOrResponse::Request(request),
+                        &mut available_memory,
+                        own_subnet_type,
+                        input_queue_type,
+                    )
+                    .map_err(|(err, _)| format!("Failed to move subnet request: {err}"))?;
+                }
+                CanisterInput::Response(_)
+                | CanisterInput::DeadlineExpired(_)
+                | CanisterInput::ResponseDropped(_) => {
+                    return Err("Unexpected response in subnet input queues".into());
+                }
+            }
+        }
+
+        let (metadata, messages) = metadata.merge(other_metadata)?;
+
+        let mut state = Self {
+            canister_states,
+            metadata,
+            subnet_queues,
+            refunds,
+            consensus_queue,
+            epoch_query_stats: RawQueryStats::default(), // Don't preserve query stats during subnet merging.
+            canister_snapshots,
+        };
+
+        // Induct the messages that were in flight between the two subnets.
+        for msg in messages {
+            let msg = match msg {
+                StreamMessage::Request(request) => RequestOrResponse::Request(request),
+                StreamMessage::Response(response) => RequestOrResponse::Response(response),
+                StreamMessage::Refund(refund) => {
+                    state.refunds.add(refund.recipient(), refund.amount());
+                    continue;
+                }
+            };
+            state
+                .push_input(msg, &mut available_memory)
+                .map_err(|(err, msg)| {
+                    format!("Failed to induct in-flight message {msg:?}: {err}")
+                })?;
+        }
+
+        Ok(state)
+    }
+
     /// Records the loss of `cycles` due to dropping messages (e.g. late best-effort
     /// responses to deleted canisters).
     pub fn observe_lost_cycles_due_to_dropped_messages(&mut self, cycles: Cycles) {
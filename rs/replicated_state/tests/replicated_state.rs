@@ -1223,6 +1223,129 @@ fn split() {
     assert_eq!(expected, state_b);
 }
 
+#[test]
+fn merge() {
+    // We will be merging subnet B into subnet A.
+    const SUBNET_A: SubnetId = SUBNET_ID;
+    const SUBNET_B: SubnetId = SUBNET_1;
+
+    const CANISTER_1: CanisterId = CANISTER_ID;
+    const CANISTER_2: CanisterId = OTHER_CANISTER_ID;
+    const CANISTERS: [CanisterId; 2] = [CANISTER_1, CANISTER_2];
+
+    // Obtain the two states to merge by splitting a fixture with 2 canisters.
+    let routing_table = RoutingTable::try_from(btreemap! {
+        CanisterIdRange {start: CANISTER_1, end: CANISTER_1} => SUBNET_A,
+        CanisterIdRange {start: CANISTER_2, end: CANISTER_2} => SUBNET_B,
+    })
+    .unwrap();
+    let mut fixture = ReplicatedStateFixture::with_canisters(&CANISTERS);
+
+    // One `Received` ingress message addressed to each canister.
+    for (i, canister) in CANISTERS.iter().enumerate() {
+        fixture.state.metadata.ingress_history.insert(
+            message_test_id(i as u64),
+            IngressStatus::Known {
+                receiver: canister.get(),
+                user_id: user_test_id(i as u64),
+                time: UNIX_EPOCH,
+                state: IngressState::Received,
+            },
+            UNIX_EPOCH,
+            NumBytes::from(u64::MAX),
+            |_| {},
+        );
+    }
+    let full_ingress_history = fixture.state.metadata.ingress_history.clone();
+
+    let mut state_a = fixture
+        .state
+        .clone()
+        .split(SUBNET_A, &routing_table, None)
+        .unwrap();
+    state_a.after_split();
+    let mut state_b = fixture
+        .state
+        .clone()
+        .split(SUBNET_B, &routing_table, None)
+        .unwrap();
+    state_b.after_split();
+
+    // A request from `CANISTER_1` to `CANISTER_2`, not yet inducted by subnet B.
+    let mut streams = state_a.take_streams();
+    streams
+        .entry(SUBNET_B)
+        .or_default()
+        .push(RequestOrResponse::from(request_to(CANISTER_2)).into());
+    state_a.put_streams(streams);
+
+    // A refund pooled on subnet B.
+    state_b.add_refund(CANISTER_2, Cycles::new(200));
+
+    let canister_2_inputs = state_b
+        .canister_state(&CANISTER_2)
+        .unwrap()
+        .system_state
+        .queues()
+        .input_queues_message_count();
+
+    let merged = state_a.merge(state_b).unwrap();
+
+    // Both canisters are hosted by the merged subnet, which retains subnet A's ID.
+    assert_eq!(SUBNET_A, merged.metadata.own_subnet_id);
+    assert!(
+        CANISTERS
+            .iter()
+            .all(|id| merged.canister_state(id).is_some())
+    );
+    assert_eq!(None, merged.metadata.split_from);
+    // The stream to subnet B was dropped and its request inducted.
+    assert!(!merged.metadata.streams().contains_key(&SUBNET_B));
+    assert_eq!(
+        canister_2_inputs + 1,
+        merged
+            .canister_state(&CANISTER_2)
+            .unwrap()
+            .system_state
+            .queues()
+            .input_queues_message_count()
+    );
+    // The ingress histories were merged.
+    assert_eq!(full_ingress_history, merged.metadata.ingress_history);
+    // Subnet B's refund was taken over.
+    assert_eq!(
+        vec![Refund::anonymous(CANISTER_2, Cycles::new(200))],
+        merged.refunds().iter().cloned().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn merge_rejects_invalid_states() {
+    let state_a = ReplicatedState::new(SUBNET_ID, SubnetType::Application);
+
+    // Cannot merge a subnet into itself.
+    assert!(state_a.clone().merge(state_a.clone()).is_err());
+
+    // Cannot merge a state that is in the middle of a split.
+    let mut state_b = ReplicatedState::new(SUBNET_1, SubnetType::Application);
+    state_b.metadata.split_from = Some(SUBNET_1);
+    assert!(state_a.clone().merge(state_b).is_err());
+
+    // Cannot merge subnets of different types.
+    let state_b = ReplicatedState::new(SUBNET_1, SubnetType::System);
+    assert!(state_a.clone().merge(state_b).is_err());
+
+    // Cannot merge states hosting the same canister.
+    let fixture = ReplicatedStateFixture::with_canisters(&[CANISTER_ID]);
+    let mut state_b = fixture.state.clone();
+    state_b.metadata.own_subnet_id = SUBNET_1;
+    assert!(fixture.state.merge(state_b).is_err());
+
+    // A state with an empty subnet B merges successfully.
+    let state_b = ReplicatedState::new(SUBNET_1, SubnetType::Application);
+    assert_eq!(state_a.clone(), state_a.merge(state_b).unwrap());
+}
+
 #[test]
 fn input_source_roundtrip() {
     use ic_protobuf::state::queues::v1::canister_queues as pb;
@@ -11,6 +11,7 @@ use ic_protobuf::state::{
 };
 use ic_replicated_state::{
     CanisterStatus, ExportedFunctions, NumWasmPages,
+    canister_snapshots::SnapshotParent,
     canister_state::{
         execution_state::{NextScheduledMethod, WasmMetadata},
         system_state::{
@@ -72,6 +73,7 @@ pub const SYSTEM_METADATA_FILE: &str = "system_metadata.pbuf";
 pub const STATS_FILE: &str = "stats.pbuf";
 pub const WASM_FILE: &str = "software.wasm";
 pub const UNVERIFIED_CHECKPOINT_MARKER: &str = "unverified_checkpoint_marker";
+pub const STATE_SYNC_PROGRESS_MARKER: &str = "state_sync_progress.pbuf";
 pub const OVERLAY: &str = "overlay";
 pub const VMEMORY_0: &str = "vmemory_0";
 pub const STABLE_MEMORY: &str = "stable_memory";
@@ -195,6 +197,7 @@ pub struct CanisterStateBits {
     pub snapshots_memory_usage: NumBytes,
     pub task_queue: TaskQueue,
     pub environment_variables: BTreeMap<String, String>,
+    pub latency_sensitive_methods: BTreeSet<String>,
 }
 
 /// This struct contains bits of the `CanisterSnapshot` that are not already
@@ -229,6 +232,8 @@ pub struct CanisterSnapshotBits {
     pub global_timer: Option<CanisterTimer>,
     /// The state of the low memory hook.
     pub on_low_wasm_memory_hook_status: Option<OnLowWasmMemoryHookStatus>,
+    /// The parent snapshot, if this is an incremental snapshot.
+    pub parent: Option<SnapshotParent>,
 }
 
 #[derive(Clone)]
@@ -325,6 +330,9 @@ struct CheckpointRefData {
 /// ├── diverged_state_markers
 /// │   └──<hex(round)>
 /// │
+/// ├── state_sync_resumable
+/// │   └──<hex(height)>
+/// │
 /// ├── tmp
 /// └── fs_tmp
 /// ```
@@ -487,6 +495,59 @@ impl TipHandler {
         Ok(())
     }
 
+    /// Copies the directories of all canisters and canister snapshots in `cp` into
+    /// tip, as part of a subnet merge. Fails if any of them is already present in
+    /// tip.
+    pub fn import_canisters_from(
+        &mut self,
+        state_layout: &StateLayout,
+        height: Height,
+        cp: &CheckpointLayout<ReadOnly>,
+        mut thread_pool: Option<&mut scoped_threadpool::Pool>,
+    ) -> Result<(), LayoutError> {
+        let tip = self.tip(height)?;
+
+        let mut dirs = Vec::new();
+        for id in cp.canister_ids()? {
+            dirs.push((cp.canister(&id)?.raw_path(), tip.canister(&id)?.raw_path()));
+        }
+        for id in cp.snapshot_ids()? {
+            dirs.push((cp.snapshot(&id)?.raw_path(), tip.snapshot(&id)?.raw_path()));
+        }
+
+        for (src, dst) in dirs {
+            if dst.exists() {
+                return Err(LayoutError::CorruptedLayout {
+                    path: dst,
+                    message: "Imported canister directory already exists in tip".to_string(),
+                });
+            }
+            // Protobufs are written out anew by the checkpointing logic.
+            let file_copy_instruction = |path: &Path| {
+                if path.extension() == Some(OsStr::new("pbuf")) {
+                    CopyInstruction::Skip
+                } else {
+                    CopyInstruction::ReadOnly
+                }
+            };
+            copy_recursively(
+                &state_layout.log,
+                &state_layout.metrics,
+                &src,
+                &dst,
+                FSync::No,
+                file_copy_instruction,
+                thread_pool.as_deref_mut(),
+            )
+            .map_err(|err| LayoutError::IoError {
+                path: src,
+                message: "Failed to import canister directory into tip".to_string(),
+                io_err: err,
+            })?;
+        }
+        Ok(())
+    }
+
     /// Moves the entire canister directory from one canister id to another.
     pub fn move_canister_directory(
         &mut self,
@@ -599,6 +660,7 @@ impl StateLayout {
 
     fn init(&self) -> Result<(), LayoutError> {
         self.cleanup_tip()?;
+        self.preserve_resumable_state_sync()?;
         self.cleanup_tmp()?;
         // This is for testing only. In production the Guest OS setup
         // would have already created the page_deltas directory, however
@@ -743,6 +805,118 @@ impl StateLayout {
         Ok(tmp.join(format!("state_sync_cache_{:016x}", height.get())))
     }
 
+    /// Returns the path to the directory holding the partially fetched state of
+    /// an interrupted state sync, preserved across restarts.
+    fn state_sync_resumable(&self) -> PathBuf {
+        self.root.join("state_sync_resumable")
+    }
+
+    /// Returns the height and path of the preserved state sync data, if any.
+    fn preserved_state_sync(&self) -> Result<Option<(Height, PathBuf)>, LayoutError> {
+        let resumable = self.state_sync_resumable();
+        let names = dir_file_names(&resumable).map_err(|err| LayoutError::IoError {
+            path: resumable.clone(),
+            message: "Failed to list preserved state sync data".to_string(),
+            io_err: err,
+        })?;
+        Ok(parse_and_sort_checkpoint_heights(&names[..])?
+            .pop()
+            .map(|height| (height, resumable.join(Self::checkpoint_name(height)))))
+    }
+
+    /// Moves the most recent state sync scratchpad or cache under `tmp` that
+    /// carries a `STATE_SYNC_PROGRESS_MARKER` out of `tmp`, so that the state
+    /// sync can be resumed after `cleanup_tmp()`.
+    ///
+    /// Preserved data at a greater or equal height is retained instead.
+    fn preserve_resumable_state_sync(&self) -> Result<(), LayoutError> {
+        let tmp = self.tmp();
+        if !tmp.exists() {
+            return Ok(());
+        }
+        let io_err = |path: &Path, message: &str, io_err| LayoutError::IoError {
+            path: path.to_path_buf(),
+            message: message.to_string(),
+            io_err,
+        };
+
+        let mut latest: Option<(Height, PathBuf)> = None;
+        for entry in std::fs::read_dir(&tmp)
+            .map_err(|err| io_err(&tmp, "Failed to list temporary directory", err))?
+        {
+            let path = entry
+                .map_err(|err| io_err(&tmp, "Failed to list temporary directory", err))?
+                .path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let Some(height) = name
+                .strip_prefix("state_sync_scratchpad_")
+                .or_else(|| name.strip_prefix("state_sync_cache_"))
+                .and_then(|hex| u64::from_str_radix(hex, 16).ok())
+                .map(Height::new)
+            else {
+                continue;
+            };
+            // On a tie, prefer the scratchpad of the state sync that was in progress.
+            if path.join(STATE_SYNC_PROGRESS_MARKER).exists()
+                && latest.as_ref().is_none_or(|(h, _)| {
+                    height > *h || (height == *h && name.starts_with("state_sync_scratchpad_"))
+                })
+            {
+                latest = Some((height, path));
+            }
+        }
+        let Some((height, path)) = latest else {
+            return Ok(());
+        };
+        if let Some((preserved_height, _)) = self.preserved_state_sync()?
+            && preserved_height >= height
+        {
+            return Ok(());
+        }
+
+        let resumable = self.state_sync_resumable();
+        if resumable.exists() {
+            std::fs::remove_dir_all(&resumable).map_err(|err| {
+                io_err(
+                    &resumable,
+                    "Failed to remove preserved state sync data",
+                    err,
+                )
+            })?;
+        }
+        WriteOnly::check_dir(&resumable)?;
+        let dst = resumable.join(Self::checkpoint_name(height));
+        std::fs::rename(&path, &dst)
+            .map_err(|err| io_err(&path, "Failed to preserve state sync data", err))?;
+        sync_path(&resumable)
+            .map_err(|err| io_err(&resumable, "Failed to sync preserved state sync data", err))?;
+        info!(
+            self.log,
+            "Preserved state sync data @{} for resuming the state sync", height
+        );
+        Ok(())
+    }
+
+    /// Takes ownership of the state sync data preserved across a restart, if
+    /// any, by moving it to `state_sync_cache(height)`.
+    ///
+    /// Returns the height of the interrupted state sync and the new path of its
+    /// data.
+    pub fn take_resumable_state_sync(&self) -> Result<Option<(Height, PathBuf)>, LayoutError> {
+        let Some((height, path)) = self.preserved_state_sync()? else {
+            return Ok(None);
+        };
+        let cache = self.state_sync_cache(height)?;
+        std::fs::rename(&path, &cache).map_err(|err| LayoutError::IoError {
+            path: path.clone(),
+            message: "Failed to move preserved state sync data".to_string(),
+            io_err: err,
+        })?;
+        Ok(Some((height, cache)))
+    }
+
     fn cleanup_tip(&self) -> Result<(), LayoutError> {
         if self.tip_path().exists() {
             std::fs::remove_dir_all(self.tip_path()).map_err(|err| LayoutError::IoError {
@@ -6,6 +6,7 @@ use ic_protobuf::{
         canister_state_bits::v1 as pb_canister_state_bits,
     },
 };
+use ic_replicated_state::PageIndex;
 use ic_types::default_log_memory_limit;
 
 impl From<CanisterStateBits> for pb_canister_state_bits::CanisterStateBits {
@@ -73,6 +74,7 @@ impl From<CanisterStateBits> for pb_canister_state_bits::CanisterStateBits {
             snapshots_memory_usage: item.snapshots_memory_usage.get(),
             tasks: Some((&item.task_queue).into()),
             environment_variables: item.environment_variables.into_iter().collect(),
+            latency_sensitive_methods: item.latency_sensitive_methods.into_iter().collect(),
         }
     }
 }
@@ -209,6 +211,7 @@ impl TryFrom<pb_canister_state_bits::CanisterStateBits> for CanisterStateBits {
             snapshots_memory_usage: NumBytes::from(value.snapshots_memory_usage),
             task_queue,
             environment_variables: value.environment_variables.into_iter().collect(),
+            latency_sensitive_methods: value.latency_sensitive_methods.into_iter().collect(),
         })
     }
 }
@@ -300,6 +303,21 @@ impl From<CanisterSnapshotBits> for pb_canister_snapshot_bits::CanisterSnapshotB
                 .on_low_wasm_memory_hook_status
                 .map(|x| pb_canister_state_bits::OnLowWasmMemoryHookStatus::from(&x).into()),
             source: pb_canister_state_bits::SnapshotSource::from(item.source).into(),
+            parent: item
+                .parent
+                .map(|parent| pb_canister_snapshot_bits::SnapshotParent {
+                    snapshot_id: parent.snapshot_id.get_local_snapshot_id(),
+                    wasm_memory_pages: parent
+                        .wasm_memory_pages
+                        .iter()
+                        .map(|index| index.get())
+                        .collect(),
+                    stable_memory_pages: parent
+                        .stable_memory_pages
+                        .iter()
+                        .map(|index| index.get())
+                        .collect(),
+                }),
         }
     }
 }
@@ -336,6 +354,20 @@ impl TryFrom<pb_canister_snapshot_bits::CanisterSnapshotBits> for CanisterSnapsh
         let source =
             pb_canister_state_bits::SnapshotSource::try_from(item.source).unwrap_or_default();
         let source = SnapshotSource::try_from(source).unwrap_or_default();
+        // The parent of an incremental snapshot always belongs to the same canister.
+        let parent = item.parent.map(|parent| SnapshotParent {
+            snapshot_id: SnapshotId::from((canister_id, parent.snapshot_id)),
+            wasm_memory_pages: parent
+                .wasm_memory_pages
+                .into_iter()
+                .map(PageIndex::new)
+                .collect(),
+            stable_memory_pages: parent
+                .stable_memory_pages
+                .into_iter()
+                .map(PageIndex::new)
+                .collect(),
+        });
         Ok(Self {
             snapshot_id: SnapshotId::from((canister_id, item.snapshot_id)),
             canister_id,
@@ -355,6 +387,7 @@ impl TryFrom<pb_canister_snapshot_bits::CanisterSnapshotBits> for CanisterSnapsh
             global_timer,
             on_low_wasm_memory_hook_status,
             source,
+            parent,
         })
     }
 }
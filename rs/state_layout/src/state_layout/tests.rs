@@ -6,7 +6,7 @@ use ic_management_canister_types_private::{
 use ic_replicated_state::ExecutionTask;
 use ic_replicated_state::canister_state::system_state::PausedExecutionId;
 use ic_replicated_state::{
-    NumWasmPages, canister_state::system_state::CanisterHistory,
+    NumWasmPages, PageIndex, canister_state::system_state::CanisterHistory,
     metadata_state::subnet_call_context_manager::InstallCodeCallId, page_map::Shard,
 };
 use ic_test_utilities_logger::with_test_replica_logger;
@@ -62,6 +62,7 @@ fn default_canister_state_bits() -> CanisterStateBits {
         next_snapshot_id: 0,
         snapshots_memory_usage: NumBytes::from(0),
         environment_variables: BTreeMap::new(),
+        latency_sensitive_methods: BTreeSet::new(),
     }
 }
 
@@ -249,6 +250,39 @@ fn test_canister_snapshots_decode() {
         source: SnapshotSource::taken_from_canister(),
         global_timer: Some(CanisterTimer::Inactive),
         on_low_wasm_memory_hook_status: Some(OnLowWasmMemoryHookStatus::ConditionNotSatisfied),
+        parent: None,
+    };
+
+    let pb_bits =
+        pb_canister_snapshot_bits::CanisterSnapshotBits::from(canister_snapshot_bits.clone());
+    let new_canister_snapshot_bits = CanisterSnapshotBits::try_from(pb_bits).unwrap();
+
+    assert_eq!(canister_snapshot_bits, new_canister_snapshot_bits);
+}
+
+#[test]
+fn test_incremental_canister_snapshots_decode() {
+    let canister_id = canister_test_id(7);
+    let canister_snapshot_bits = CanisterSnapshotBits {
+        snapshot_id: SnapshotId::from((canister_id, 5)),
+        canister_id,
+        taken_at_timestamp: UNIX_EPOCH,
+        canister_version: 3,
+        binary_hash: WasmHash::from(&CanisterModule::new(vec![2, 3, 4])),
+        certified_data: vec![3, 4, 7],
+        wasm_chunk_store_metadata: WasmChunkStoreMetadata::default(),
+        stable_memory_size: NumWasmPages::new(10),
+        wasm_memory_size: NumWasmPages::new(10),
+        total_size: NumBytes::new(100),
+        exported_globals: vec![Global::I32(1), Global::I64(2), Global::F64(0.1)],
+        source: SnapshotSource::taken_from_canister(),
+        global_timer: Some(CanisterTimer::Inactive),
+        on_low_wasm_memory_hook_status: Some(OnLowWasmMemoryHookStatus::ConditionNotSatisfied),
+        parent: Some(SnapshotParent {
+            snapshot_id: SnapshotId::from((canister_id, 2)),
+            wasm_memory_pages: vec![PageIndex::new(0), PageIndex::new(17)],
+            stable_memory_pages: vec![PageIndex::new(3)],
+        }),
     };
 
     let pb_bits =
@@ -293,6 +327,24 @@ fn test_encode_decode_non_empty_environment_variables() {
     );
 }
 
+#[test]
+fn test_encode_decode_latency_sensitive_methods() {
+    let latency_sensitive_methods =
+        BTreeSet::from(["place_order".to_string(), "get_price".to_string()]);
+
+    // A canister state with latency-sensitive methods.
+    let canister_state_bits = CanisterStateBits {
+        latency_sensitive_methods: latency_sensitive_methods.clone(),
+        ..default_canister_state_bits()
+    };
+    let pb_bits = pb_canister_state_bits::CanisterStateBits::from(canister_state_bits);
+    let decoded_canister_state_bits = CanisterStateBits::try_from(pb_bits).unwrap();
+    assert_eq!(
+        decoded_canister_state_bits.latency_sensitive_methods,
+        latency_sensitive_methods
+    );
+}
+
 #[test]
 fn test_encode_decode_task_queue() {
     let ingress = Arc::new(IngressBuilder::new().method_name("test_ingress").build());
@@ -4,6 +4,7 @@ use ed25519_dalek::{SigningKey, pkcs8::EncodePrivateKey};
 use ic_artifact_pool::canister_http_pool::CanisterHttpPoolImpl;
 use ic_btc_adapter_client::setup_bitcoin_adapter_clients;
 use ic_btc_consensus::BitcoinPayloadBuilder;
+use ic_canister_snapshot_archive::{ArchiveError, Manifest as SnapshotArchiveManifest};
 use ic_config::{
     adapters::AdaptersConfig,
     bitcoin_payload_builder_config::Config as BitcoinPayloadBuilderConfig,
@@ -35,7 +36,8 @@ use ic_interfaces::{
     consensus::{PayloadBuilder as ConsensusPayloadBuilder, PayloadValidationError},
     consensus_pool::ConsensusTime,
     execution_environment::{
-        IngressFilterService, IngressHistoryReader, QueryExecutionInput, QueryExecutionService,
+        IngressFilterService, IngressHistoryReader, QueryContinuationToken, QueryExecutionError,
+        QueryExecutionInput, QueryExecutionService,
     },
     ingress_pool::{
         IngressPool, IngressPoolObject, PoolSection, UnvalidatedIngressArtifact,
@@ -58,11 +60,11 @@ use ic_management_canister_types_private::{
     UploadCanisterSnapshotMetadataArgs, UploadCanisterSnapshotMetadataResponse,
 };
 use ic_management_canister_types_private::{
-    CanisterHttpResponsePayload, CanisterInstallMode, CanisterSettingsArgs,
+    BlsPublicKeyResponse, CanisterHttpResponsePayload, CanisterInstallMode, CanisterSettingsArgs,
     CanisterSnapshotResponse, CanisterStatusResultV2, ClearChunkStoreArgs, EcdsaCurve, EcdsaKeyId,
-    InstallChunkedCodeArgs, LoadCanisterSnapshotArgs, SchnorrAlgorithm, SignWithECDSAReply,
-    SignWithSchnorrReply, TakeCanisterSnapshotArgs, UpdateSettingsArgs, UploadChunkArgs,
-    UploadChunkReply, VetKdDeriveKeyResult,
+    InstallChunkedCodeArgs, LoadCanisterSnapshotArgs, SchnorrAlgorithm, SignWithBlsReply,
+    SignWithECDSAReply, SignWithSchnorrReply, TakeCanisterSnapshotArgs, UpdateSettingsArgs,
+    UploadChunkArgs, UploadChunkReply, VetKdDeriveKeyResult,
 };
 use ic_messaging::SyncMessageRouting;
 use ic_metrics::MetricsRegistry;
@@ -156,6 +158,7 @@ use ic_types::{
         threshold_sig::ni_dkg::{
             NiDkgId, NiDkgMasterPublicKeyId, NiDkgTag, NiDkgTargetSubnet, NiDkgTranscript,
         },
+        vetkd::VetKdOutput,
     },
     malicious_flags::MaliciousFlags,
     messages::{
@@ -205,6 +208,28 @@ const COMPLETED_EXECUTION_MESSAGES_BUFFER_SIZE: usize = 10_000;
 
 const SNAPSHOT_DATA_CHUNK_SIZE: u64 = 2_000_000;
 
+/// Drives the management canister calls of `ic_canister_snapshot_archive`
+/// as ingress messages sent by a controller of the canister.
+struct SnapshotArchiveManagementCanister<'a> {
+    env: &'a StateMachine,
+    sender: PrincipalId,
+}
+
+impl ic_canister_snapshot_archive::ManagementCanister for SnapshotArchiveManagementCanister<'_> {
+    type Error = String;
+
+    fn update_call(&self, method: &str, arg: Vec<u8>) -> Result<Vec<u8>, String> {
+        match self
+            .env
+            .execute_ingress_as(self.sender, ic00::IC_00, method, arg)
+        {
+            Ok(WasmResult::Reply(data)) => Ok(data),
+            Ok(WasmResult::Reject(reason)) => Err(reason),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -870,9 +895,11 @@ impl StateMachineNode {
 #[allow(clippy::large_enum_variant)]
 enum SignatureSecretKey {
     EcdsaSecp256k1(ic_secp256k1::PrivateKey),
+    EcdsaSecp256r1(ic_secp256r1::PrivateKey),
     SchnorrBip340(ic_secp256k1::PrivateKey),
     Ed25519(ic_ed25519::DerivedPrivateKey),
     VetKD(ic_crypto_test_utils_vetkd::PrivateKey),
+    Bls(ic_crypto_test_utils_vetkd::PrivateKey),
 }
 
 /// Represents a replicated state machine detached from the network layer that
@@ -994,6 +1021,7 @@ pub struct StateMachineBuilder {
     ecdsa_signature_fee: Option<Cycles>,
     schnorr_signature_fee: Option<Cycles>,
     vetkd_derive_key_fee: Option<Cycles>,
+    bls_signature_fee: Option<Cycles>,
     is_ecdsa_signing_enabled: bool,
     is_schnorr_signing_enabled: bool,
     is_vetkd_enabled: bool,
@@ -1034,6 +1062,7 @@ impl StateMachineBuilder {
             ecdsa_signature_fee: None,
             schnorr_signature_fee: None,
             vetkd_derive_key_fee: None,
+            bls_signature_fee: None,
             is_ecdsa_signing_enabled: true,
             is_schnorr_signing_enabled: true,
             is_vetkd_enabled: true,
@@ -1204,6 +1233,13 @@ impl StateMachineBuilder {
         }
     }
 
+    pub fn with_bls_signature_fee(self, fee: u128) -> Self {
+        Self {
+            bls_signature_fee: Some(Cycles::new(fee)),
+            ..self
+        }
+    }
+
     pub fn with_features(self, features: SubnetFeatures) -> Self {
         Self { features, ..self }
     }
@@ -1308,6 +1344,7 @@ impl StateMachineBuilder {
             self.ecdsa_signature_fee,
             self.schnorr_signature_fee,
             self.vetkd_derive_key_fee,
+            self.bls_signature_fee,
             self.is_ecdsa_signing_enabled,
             self.is_schnorr_signing_enabled,
             self.is_vetkd_enabled,
@@ -1662,6 +1699,7 @@ impl StateMachine {
         ecdsa_signature_fee: Option<Cycles>,
         schnorr_signature_fee: Option<Cycles>,
         vetkd_derive_key_fee: Option<Cycles>,
+        bls_signature_fee: Option<Cycles>,
         is_ecdsa_signing_enabled: bool,
         is_schnorr_signing_enabled: bool,
         is_vetkd_enabled: bool,
@@ -1708,6 +1746,11 @@ impl StateMachine {
         if let Some(vetkd_derive_key_fee) = vetkd_derive_key_fee {
             subnet_config.cycles_account_manager_config.vetkd_fee = vetkd_derive_key_fee;
         }
+        if let Some(bls_signature_fee) = bls_signature_fee {
+            subnet_config
+                .cycles_account_manager_config
+                .bls_signature_fee = bls_signature_fee;
+        }
 
         let mut node_rng = StdRng::from_seed(seed);
         let nodes: Vec<StateMachineNode> = (0..subnet_size)
@@ -1879,26 +1922,48 @@ impl StateMachine {
 
                     (public_key, private_key)
                 }
-                MasterPublicKeyId::Ecdsa(id) => {
-                    use ic_secp256k1::{DerivationIndex, DerivationPath, PrivateKey};
+                MasterPublicKeyId::Ecdsa(id) => match id.curve {
+                    EcdsaCurve::Secp256k1 => {
+                        use ic_secp256k1::{DerivationIndex, DerivationPath, PrivateKey};
+
+                        let path =
+                            DerivationPath::new(vec![DerivationIndex(id.name.as_bytes().to_vec())]);
 
-                    let path =
-                        DerivationPath::new(vec![DerivationIndex(id.name.as_bytes().to_vec())]);
+                        // We use a fixed seed here so that all subnets in PocketIC share the same keys.
+                        let private_key = PrivateKey::generate_from_seed(&[42; 32])
+                            .derive_subkey(&path)
+                            .0;
 
-                    // We use a fixed seed here so that all subnets in PocketIC share the same keys.
-                    let private_key = PrivateKey::generate_from_seed(&[42; 32])
-                        .derive_subkey(&path)
-                        .0;
+                        let public_key = MasterPublicKey {
+                            algorithm_id: AlgorithmId::ThresholdEcdsaSecp256k1,
+                            public_key: private_key.public_key().serialize_sec1(true),
+                        };
 
-                    let public_key = MasterPublicKey {
-                        algorithm_id: AlgorithmId::ThresholdEcdsaSecp256k1,
-                        public_key: private_key.public_key().serialize_sec1(true),
-                    };
+                        let private_key = SignatureSecretKey::EcdsaSecp256k1(private_key);
 
-                    let private_key = SignatureSecretKey::EcdsaSecp256k1(private_key);
+                        (public_key, private_key)
+                    }
+                    EcdsaCurve::Secp256r1 => {
+                        use ic_secp256r1::{DerivationIndex, DerivationPath, PrivateKey};
 
-                    (public_key, private_key)
-                }
+                        let path =
+                            DerivationPath::new(vec![DerivationIndex(id.name.as_bytes().to_vec())]);
+
+                        // We use a fixed seed here so that all subnets in PocketIC share the same keys.
+                        let private_key = PrivateKey::generate_insecure_key_for_testing(42)
+                            .derive_subkey(&path)
+                            .0;
+
+                        let public_key = MasterPublicKey {
+                            algorithm_id: AlgorithmId::ThresholdEcdsaSecp256r1,
+                            public_key: private_key.public_key().serialize_sec1(true),
+                        };
+
+                        let private_key = SignatureSecretKey::EcdsaSecp256r1(private_key);
+
+                        (public_key, private_key)
+                    }
+                },
                 MasterPublicKeyId::Schnorr(id) => match id.algorithm {
                     SchnorrAlgorithm::Bip340Secp256k1 => {
                         use ic_secp256k1::{DerivationIndex, DerivationPath, PrivateKey};
@@ -1964,6 +2029,31 @@ impl StateMachine {
 
                     ni_dkg_ids.insert(NiDkgMasterPublicKeyId::VetKd(id.clone()), nidkg_id);
 
+                    (public_key, private_key)
+                }
+                MasterPublicKeyId::Bls(id) => {
+                    use ic_crypto_test_utils_vetkd::PrivateKey;
+
+                    let private_key = PrivateKey::generate(id.name.as_bytes());
+
+                    let public_key = MasterPublicKey {
+                        algorithm_id: AlgorithmId::ThresBls12_381,
+                        public_key: private_key.public_key_bytes(),
+                    };
+
+                    let private_key = SignatureSecretKey::Bls(private_key);
+
+                    let nidkg_id = NiDkgId {
+                        start_block_height: Height::new(0),
+                        dealer_subnet: subnet_id,
+                        dkg_tag: NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::Bls(
+                            id.clone(),
+                        )),
+                        target_subnet: NiDkgTargetSubnet::Local,
+                    };
+
+                    ni_dkg_ids.insert(NiDkgMasterPublicKeyId::Bls(id.clone()), nidkg_id);
+
                     (public_key, private_key)
                 }
             };
@@ -2394,27 +2484,38 @@ impl StateMachine {
     ) -> Result<SignWithECDSAReply, UserError> {
         assert!(context.is_ecdsa());
 
-        if let Some(SignatureSecretKey::EcdsaSecp256k1(k)) =
-            self.chain_key_subnet_secret_keys.get(&context.key_id())
-        {
-            let path = ic_secp256k1::DerivationPath::from_canister_id_and_path(
-                context.request.sender.get().as_slice(),
-                &context.derivation_path,
-            );
-            let dk = k.derive_subkey(&path).0;
-            let signature = dk
-                .sign_digest_with_ecdsa(&context.ecdsa_args().message_hash)
-                .to_vec();
-            Ok(SignWithECDSAReply { signature })
-        } else {
-            Err(UserError::new(
+        match self.chain_key_subnet_secret_keys.get(&context.key_id()) {
+            Some(SignatureSecretKey::EcdsaSecp256k1(k)) => {
+                let path = ic_secp256k1::DerivationPath::from_canister_id_and_path(
+                    context.request.sender.get().as_slice(),
+                    &context.derivation_path,
+                );
+                let dk = k.derive_subkey(&path).0;
+                let signature = dk
+                    .sign_digest_with_ecdsa(&context.ecdsa_args().message_hash)
+                    .to_vec();
+                Ok(SignWithECDSAReply { signature })
+            }
+            Some(SignatureSecretKey::EcdsaSecp256r1(k)) => {
+                let path = ic_secp256r1::DerivationPath::from_canister_id_and_path(
+                    context.request.sender.get().as_slice(),
+                    &context.derivation_path,
+                );
+                let dk = k.derive_subkey(&path).0;
+                let signature = dk
+                    .sign_digest(&context.ecdsa_args().message_hash)
+                    .expect("message hash should be 32 bytes")
+                    .to_vec();
+                Ok(SignWithECDSAReply { signature })
+            }
+            _ => Err(UserError::new(
                 ErrorCode::CanisterRejectedMessage,
                 format!(
                     "Subnet {} does not hold threshold key {}.",
                     self.subnet_id,
                     context.key_id()
                 ),
-            ))
+            )),
         }
     }
 
@@ -2512,6 +2613,44 @@ impl StateMachine {
         }
     }
 
+    /// Returns the encoded reply to a `sign_with_bls` or `bls_public_key` call.
+    fn build_bls_reply(&self, context: &SignWithThresholdContext) -> Result<Vec<u8>, UserError> {
+        assert!(context.is_bls());
+
+        if let (Some(SignatureSecretKey::Bls(k)), Some(args)) = (
+            self.chain_key_subnet_secret_keys.get(&context.key_id()),
+            context.vetkd_protocol_args(),
+        ) {
+            let message = match args.output {
+                VetKdOutput::BlsSignature => Some(args.input.as_slice()),
+                VetKdOutput::BlsPublicKey | VetKdOutput::EncryptedKey => None,
+            };
+            let (public_key, signature) = k.bls_protocol(
+                args.context.caller.as_slice(),
+                &args.context.context,
+                message,
+            );
+
+            Ok(match signature {
+                Some(signature) => SignWithBlsReply {
+                    signature,
+                    public_key,
+                }
+                .encode(),
+                None => BlsPublicKeyResponse { public_key }.encode(),
+            })
+        } else {
+            Err(UserError::new(
+                ErrorCode::CanisterRejectedMessage,
+                format!(
+                    "Subnet {} does not hold threshold key {}.",
+                    self.subnet_id,
+                    context.key_id()
+                ),
+            ))
+        }
+    }
+
     fn process_threshold_signing_request(
         &self,
         id: &CallbackId,
@@ -2567,6 +2706,21 @@ impl StateMachine {
                     }
                 }
             }
+            ThresholdArguments::Bls(_) if self.is_vetkd_enabled => {
+                match self.build_bls_reply(context) {
+                    Ok(response) => {
+                        payload
+                            .consensus_responses
+                            .push(ConsensusResponse::new(*id, MsgPayload::Data(response)));
+                    }
+                    Err(user_error) => {
+                        payload.consensus_responses.push(ConsensusResponse::new(
+                            *id,
+                            MsgPayload::Reject(RejectContext::from(user_error)),
+                        ));
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -2581,7 +2735,8 @@ impl StateMachine {
         self.is_schnorr_signing_enabled = value;
     }
 
-    /// If set to true, the state machine will handle vetkd_derive_key calls during `tick()`.
+    /// If set to true, the state machine will handle vetkd_derive_key, sign_with_bls and
+    /// bls_public_key calls during `tick()`.
     pub fn set_vetkd_enabled(&mut self, value: bool) {
         self.is_vetkd_enabled = value;
     }
@@ -3846,6 +4001,52 @@ impl StateMachine {
         Ok(())
     }
 
+    /// Downloads a canister snapshot into a local snapshot archive in the
+    /// (empty or non-existing) directory `archive_dir`.
+    ///
+    /// See `ic_canister_snapshot_archive` for a description of the format.
+    pub fn download_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        snapshot_id: SnapshotId,
+        archive_dir: &Path,
+    ) -> Result<SnapshotArchiveManifest, ArchiveError> {
+        ic_canister_snapshot_archive::download_snapshot(
+            &SnapshotArchiveManagementCanister {
+                env: self,
+                sender: self.get_controller(&canister_id),
+            },
+            canister_id.get().0,
+            snapshot_id.to_vec(),
+            archive_dir,
+        )
+    }
+
+    /// Uploads the local snapshot archive in `archive_dir` as a new snapshot of
+    /// `canister_id`, optionally replacing an existing snapshot.
+    ///
+    /// See `ic_canister_snapshot_archive` for a description of the format.
+    pub fn upload_canister_snapshot(
+        &self,
+        canister_id: CanisterId,
+        replace_snapshot: Option<SnapshotId>,
+        archive_dir: &Path,
+    ) -> Result<SnapshotId, ArchiveError> {
+        let snapshot_id = ic_canister_snapshot_archive::upload_snapshot(
+            &SnapshotArchiveManagementCanister {
+                env: self,
+                sender: self.get_controller(&canister_id),
+            },
+            canister_id.get().0,
+            replace_snapshot.map(|snapshot_id| snapshot_id.to_vec()),
+            archive_dir,
+        )?;
+        SnapshotId::try_from(snapshot_id).map_err(|err| ArchiveError::Call {
+            method: Method::UploadCanisterSnapshotMetadata.to_string(),
+            message: err.to_string(),
+        })
+    }
+
     /// Upload a chunk to the wasm chunk store.
     pub fn upload_chunk(&self, args: UploadChunkArgs) -> Result<UploadChunkReply, UserError> {
         let state = self.state_manager.get_latest_state().take();
@@ -3978,14 +4179,51 @@ impl StateMachine {
         let input = QueryExecutionInput {
             query: user_query,
             certificate_delegation_with_metadata: delegation,
+            continuation_token: None,
         };
-        if let Ok((result, _)) = self.runtime.block_on(query_svc.oneshot(input)).unwrap() {
+        if let Ok((result, _, _)) = self.runtime.block_on(query_svc.oneshot(input)).unwrap() {
             result
         } else {
             unreachable!()
         }
     }
 
+    /// Queries the canister with the specified ID, optionally against the
+    /// state pinned by a continuation token returned by an earlier query.
+    ///
+    /// Returns the query result and a continuation token for the state the
+    /// query was executed against.
+    pub fn query_with_continuation_token(
+        &self,
+        sender: PrincipalId,
+        receiver: CanisterId,
+        method: impl ToString,
+        method_payload: Vec<u8>,
+        continuation_token: Option<QueryContinuationToken>,
+    ) -> Result<(Result<WasmResult, UserError>, QueryContinuationToken), QueryExecutionError> {
+        self.certify_latest_state();
+        let user_query = Query {
+            source: QuerySource::User {
+                user_id: UserId::from(sender),
+                ingress_expiry: 0,
+                nonce: None,
+            },
+            receiver,
+            method_name: method.to_string(),
+            method_payload,
+        };
+        let query_svc = self.query_handler.lock().unwrap().clone();
+        let input = QueryExecutionInput {
+            query: user_query,
+            certificate_delegation_with_metadata: None,
+            continuation_token,
+        };
+        self.runtime
+            .block_on(query_svc.oneshot(input))
+            .unwrap()
+            .map(|(result, _time, continuation_token)| (result, continuation_token))
+    }
+
     /// Returns the module hash of the specified canister.
     pub fn module_hash(&self, canister_id: CanisterId) -> Option<[u8; 32]> {
         let state = self.state_manager.get_latest_state().take();
@@ -4550,6 +4788,15 @@ impl StateMachine {
             .vetkd_derive_key_contexts()
     }
 
+    /// Returns `sign_with_bls` contexts from internal subnet call context manager.
+    pub fn sign_with_bls_contexts(&self) -> BTreeMap<CallbackId, SignWithThresholdContext> {
+        let state = self.state_manager.get_latest_state().take();
+        state
+            .metadata
+            .subnet_call_context_manager
+            .sign_with_bls_contexts()
+    }
+
     /// Returns canister HTTP request contexts from internal subnet call context manager.
     pub fn canister_http_request_contexts(
         &self,
@@ -381,6 +381,7 @@ pub(crate) fn flush_canister_snapshots_and_page_maps(
         // will have PageMaps that need to be flushed. They will have a corresponding `CreateSnapshot` or `UploadSnapshotData`
         // in the unflushed operations list.
         if let UnflushedCheckpointOp::TakeSnapshot(.., snapshot_id)
+        | UnflushedCheckpointOp::TakeIncrementalSnapshot(.., snapshot_id)
         | UnflushedCheckpointOp::UploadSnapshotData(snapshot_id)
         | UnflushedCheckpointOp::UploadSnapshotMetadata(snapshot_id) = op
         {
@@ -908,6 +909,7 @@ pub fn load_canister_state(
         canister_state_bits.next_snapshot_id,
         canister_state_bits.snapshots_memory_usage,
         canister_state_bits.environment_variables,
+        canister_state_bits.latency_sensitive_methods,
         metrics,
     );
 
@@ -1043,6 +1045,7 @@ pub fn load_snapshot(
         wasm_chunk_store,
         execution_snapshot,
         canister_snapshot_bits.total_size,
+        canister_snapshot_bits.parent,
     );
 
     let metrics = LoadCanisterMetrics { durations };
@@ -3,6 +3,7 @@
 pub mod checkpoint;
 pub mod labeled_tree_visitor;
 pub mod manifest;
+pub mod merge;
 pub mod split;
 pub mod state_sync;
 pub mod stream_encoding;
@@ -127,6 +128,7 @@ const LABEL_VALUE_REUSED: &str = "reused";
 const LABEL_FETCH: &str = "fetch";
 const LABEL_HARDLINK_FILES: &str = "hardlink_files";
 const LABEL_COPY_CHUNKS: &str = "copy_chunks";
+const LABEL_COPY_HISTORICAL_CHUNKS: &str = "copy_historical_chunks";
 const LABEL_PREALLOCATE: &str = "preallocate";
 const LABEL_PREALLOCATE_DIRECTORIES: &str = "preallocate_directories";
 const LABEL_PREALLOCATE_FILES: &str = "preallocate_files";
@@ -603,7 +605,7 @@ impl StateSyncMetrics {
     pub fn new(metrics_registry: &MetricsRegistry) -> Self {
         let size = metrics_registry.int_counter_vec(
             "state_sync_size_bytes_total",
-            "Size of chunks synchronized by different operations ('fetch', 'hardlink_files', 'copy_chunks', 'preallocate') during all the state sync in bytes.",
+            "Size of chunks synchronized by different operations ('fetch', 'hardlink_files', 'copy_chunks', 'copy_historical_chunks', 'preallocate') during all the state sync in bytes.",
             &["op"],
         );
 
@@ -612,6 +614,7 @@ impl StateSyncMetrics {
             LABEL_FETCH,
             LABEL_HARDLINK_FILES,
             LABEL_COPY_CHUNKS,
+            LABEL_COPY_HISTORICAL_CHUNKS,
             LABEL_PREALLOCATE,
         ] {
             size.with_label_values(&[*op]);
@@ -644,7 +647,7 @@ impl StateSyncMetrics {
 
         let step_duration = metrics_registry.histogram_vec(
             "state_sync_step_duration_seconds",
-            "Duration of state sync sub-steps in seconds indexed by step ('hardlink_files', 'copy_chunks', 'fetch', 'state_sync_make_checkpoint', 'preallocate_directories', 'preallocate_files', 'load_and_validate_checkpoint', 'on_synced_checkpoint')",
+            "Duration of state sync sub-steps in seconds indexed by step ('hardlink_files', 'copy_chunks', 'copy_historical_chunks', 'fetch', 'state_sync_make_checkpoint', 'preallocate_directories', 'preallocate_files', 'load_and_validate_checkpoint', 'on_synced_checkpoint')",
             // 0.1s, 0.2s, 0.5s, 1s, 2s, 5s, …, 1000s, 2000s, 5000s
             decimal_buckets(-1, 3),
             &["step"],
@@ -654,6 +657,7 @@ impl StateSyncMetrics {
         for step in &[
             LABEL_HARDLINK_FILES,
             LABEL_COPY_CHUNKS,
+            LABEL_COPY_HISTORICAL_CHUNKS,
             LABEL_FETCH,
             LABEL_STATE_SYNC_MAKE_CHECKPOINT,
             LABEL_PREALLOCATE_DIRECTORIES,
@@ -669,7 +673,7 @@ impl StateSyncMetrics {
 
         let corrupted_chunks = metrics_registry.int_counter_vec(
             "state_sync_corrupted_chunks",
-            "Number of chunks not copied/applied during state sync due to hash mismatch by source ('hardlink_files', 'copy_chunks', 'fetch_meta_manifest_chunk', 'fetch_manifest_chunk', 'fetch_state_chunk')",
+            "Number of chunks not copied/applied during state sync due to hash mismatch by source ('hardlink_files', 'copy_chunks', 'copy_historical_chunks', 'fetch_meta_manifest_chunk', 'fetch_manifest_chunk', 'fetch_state_chunk')",
             &["source"],
         );
 
@@ -677,6 +681,7 @@ impl StateSyncMetrics {
         for source in &[
             LABEL_HARDLINK_FILES,
             LABEL_COPY_CHUNKS,
+            LABEL_COPY_HISTORICAL_CHUNKS,
             LABEL_FETCH_META_MANIFEST_CHUNK,
             LABEL_FETCH_MANIFEST_CHUNK,
             LABEL_FETCH_STATE_CHUNK,
@@ -877,6 +882,8 @@ pub struct StateManagerImpl {
     latest_height_update_time: Arc<Mutex<Instant>>,
     /// The height at which this StateManager was started. Set once during initialization and never modified.
     started_height: Height,
+    /// Optional read-only directory of old states that state sync may copy chunks from.
+    state_sync_old_states_dir: Option<PathBuf>,
 }
 
 #[cfg(debug_assertions)]
@@ -1539,6 +1546,7 @@ impl StateManagerImpl {
             malicious_flags,
             latest_height_update_time: Arc::new(Mutex::new(Instant::now())),
             started_height,
+            state_sync_old_states_dir: config.state_sync_old_states_dir.clone(),
         }
     }
 
@@ -1756,6 +1764,21 @@ impl StateManagerImpl {
             })
     }
 
+    /// Returns the manifests and layouts of all local checkpoints for which a
+    /// manifest is available, in ascending order of height.
+    fn checkpoint_manifests(&self) -> Vec<(Manifest, CheckpointLayout<ReadOnly>)> {
+        let states = self.states.read();
+        states
+            .states_metadata
+            .values()
+            .filter_map(|metadata| {
+                let manifest = metadata.manifest()?.clone();
+                let checkpoint_layout = metadata.checkpoint_layout.clone()?;
+                Some((manifest, checkpoint_layout))
+            })
+            .collect()
+    }
+
     fn compute_certification_metadata(
         metrics: &StateManagerMetrics,
         log: &ReplicaLogger,
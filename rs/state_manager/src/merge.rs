@@ -0,0 +1,84 @@
+//! Merges two replicated states into one, as part of a subnet merge.
+use crate::{
+    NUMBER_OF_CHECKPOINT_THREADS, StateManagerMetrics,
+    split::{read_checkpoint, write_checkpoint},
+};
+
+use ic_config::state_manager::Config;
+use ic_logger::{ReplicaLogger, info};
+use ic_metrics::MetricsRegistry;
+use ic_replicated_state::{
+    page_map::PageAllocatorFileDescriptor, page_map::TestPageAllocatorFileDescriptorImpl,
+};
+use ic_state_layout::StateLayout;
+use scoped_threadpool::Pool;
+use std::{path::PathBuf, sync::Arc};
+
+#[cfg(test)]
+mod tests;
+
+/// Loads the latest checkpoints under `root` (subnet A) and `other_root`
+/// (subnet B); merges the state of subnet B into that of subnet A (see
+/// `ReplicatedState::merge()`); and writes back the merged state as a new
+/// checkpoint under `root`. The merged state retains the subnet ID of subnet A.
+///
+/// `other_root` is only read from.
+pub fn merge(
+    root: PathBuf,
+    other_root: PathBuf,
+    metrics_registry: &MetricsRegistry,
+    log: ReplicaLogger,
+) -> Result<(), String> {
+    let config = Config::new(root);
+    let state_layout =
+        StateLayout::try_new(log.clone(), config.state_root.clone(), metrics_registry).unwrap();
+    let other_state_layout =
+        StateLayout::try_new(log.clone(), other_root, metrics_registry).unwrap();
+
+    // A thread pool to use for reading and writing checkpoints.
+    let mut thread_pool = Pool::new(NUMBER_OF_CHECKPOINT_THREADS);
+
+    // Create the file descriptor factory that is used to create files for PageMaps.
+    let fd_factory: Arc<dyn PageAllocatorFileDescriptor> =
+        Arc::new(TestPageAllocatorFileDescriptorImpl::new());
+
+    let metrics = StateManagerMetrics::new(metrics_registry, log.clone());
+    let (cp, state) = read_checkpoint(
+        &state_layout,
+        &mut thread_pool,
+        Arc::clone(&fd_factory),
+        &metrics,
+    )?;
+    let (other_cp, other_state) = read_checkpoint(
+        &other_state_layout,
+        &mut thread_pool,
+        Arc::clone(&fd_factory),
+        &metrics,
+    )?;
+
+    info!(
+        log,
+        "Merging subnet {} @{} into subnet {} @{}",
+        other_state.metadata.own_subnet_id,
+        other_cp.height(),
+        state.metadata.own_subnet_id,
+        cp.height()
+    );
+
+    // Merge the states.
+    let merged_state = state.merge(other_state)?;
+
+    // Write the merged state as a new checkpoint, importing the canister and
+    // snapshot files of subnet B.
+    write_checkpoint(
+        merged_state,
+        state_layout,
+        &cp,
+        Some(&other_cp),
+        &mut thread_pool,
+        &config,
+        Arc::clone(&fd_factory),
+        &metrics,
+        log,
+    )
+}
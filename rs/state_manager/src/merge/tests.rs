@@ -0,0 +1,318 @@
+use super::*;
+use crate::{
+    ManifestMetrics,
+    checkpoint::make_unvalidated_checkpoint,
+    checkpoint::validate_and_finalize_checkpoint_and_remove_unverified_marker,
+    flush_canister_snapshots_and_page_maps,
+    manifest::RehashManifest,
+    state_sync::types::{FileInfo, Manifest},
+    tip::{flush_tip_channel, spawn_tip_thread},
+};
+use assert_matches::assert_matches;
+use ic_base_types::{CanisterId, NumSeconds, SnapshotId};
+use ic_config::state_manager::lsmt_config_default;
+use ic_registry_subnet_type::SubnetType;
+use ic_replicated_state::{
+    ReplicatedState, canister_snapshots::CanisterSnapshot, testing::ReplicatedStateTesting,
+};
+use ic_state_layout::{INGRESS_HISTORY_FILE, SYSTEM_METADATA_FILE};
+use ic_test_utilities_logger::with_test_replica_logger;
+use ic_test_utilities_state::new_canister_state_with_execution;
+use ic_test_utilities_tmpdir::tmpdir;
+use ic_test_utilities_types::ids::{SUBNET_1, SUBNET_2, user_test_id};
+use ic_types::state_sync::CURRENT_STATE_SYNC_VERSION;
+use ic_types::{
+    Cycles, Height, SubnetId, Time,
+    ingress::{IngressState, IngressStatus},
+    malicious_flags::MaliciousFlags,
+    messages::MessageId,
+    time::UNIX_EPOCH,
+};
+use std::path::Path;
+use tempfile::TempDir;
+
+/// ID of subnet A, which subnet B is merged into.
+const SUBNET_A: SubnetId = SUBNET_1;
+/// Subnet B ID.
+const SUBNET_B: SubnetId = SUBNET_2;
+
+/// Fictitious controller of all other canisters.
+const CANISTER_0: CanisterId = CanisterId::from_u64(0);
+/// Canister hosted by subnet A.
+const CANISTER_1: CanisterId = CanisterId::from_u64(1);
+/// Canister hosted by subnet B.
+const CANISTER_2: CanisterId = CanisterId::from_u64(2);
+
+const HEIGHT: Height = Height::new(42);
+const INITIAL_CYCLES: Cycles = Cycles::new(1 << 36);
+
+/// Canister files of subnet A (including its snapshot).
+fn subnet_a_canister_files() -> &'static [&'static str] {
+    &[
+        "canister_states/00000000000000010101/canister.pbuf",
+        "canister_states/00000000000000010101/software.wasm",
+        "snapshots/00000000000000010101/000000000000000000000000000000010101/snapshot.pbuf",
+        "snapshots/00000000000000010101/000000000000000000000000000000010101/software.wasm",
+    ]
+}
+
+/// Canister files of subnet B.
+fn subnet_b_canister_files() -> &'static [&'static str] {
+    &[
+        "canister_states/00000000000000020101/canister.pbuf",
+        "canister_states/00000000000000020101/software.wasm",
+    ]
+}
+
+/// Full list of files expected to be listed in the manifest of the merged subnet.
+fn merged_files() -> &'static [&'static str] {
+    &[
+        "canister_states/00000000000000010101/canister.pbuf",
+        "canister_states/00000000000000010101/software.wasm",
+        "canister_states/00000000000000020101/canister.pbuf",
+        "canister_states/00000000000000020101/software.wasm",
+        INGRESS_HISTORY_FILE,
+        "snapshots/00000000000000010101/000000000000000000000000000000010101/snapshot.pbuf",
+        "snapshots/00000000000000010101/000000000000000000000000000000010101/software.wasm",
+        SYSTEM_METADATA_FILE,
+    ]
+}
+
+/// Tests merging subnet B (hosting canister 2) into subnet A (hosting canister
+/// 1 and a snapshot of it).
+#[test]
+fn merge_subnet_b_into_subnet_a() {
+    with_test_replica_logger(|log| {
+        let (tmp_a, batch_time_a) = new_state_layout(SUBNET_A, CANISTER_1, 1, log.clone());
+        let (tmp_b, batch_time_b) = new_state_layout(SUBNET_B, CANISTER_2, 2, log.clone());
+        let root_a = tmp_a.path().to_path_buf();
+        let root_b = tmp_b.path().to_path_buf();
+
+        let (manifest_a, height_a) = compute_manifest_for_root(&root_a, &log);
+        let (manifest_b, height_b) = compute_manifest_for_root(&root_b, &log);
+
+        merge(
+            root_a.clone(),
+            root_b.clone(),
+            &MetricsRegistry::new(),
+            log.clone(),
+        )
+        .unwrap();
+
+        let (manifest_merged, height_merged) = compute_manifest_for_root(&root_a, &log);
+        assert_eq!(merged_files(), manifest_files(&manifest_merged).as_slice());
+
+        // Checkpoint height is that of subnet A plus 1.
+        assert_eq!(height_a.increment(), height_merged);
+        // Subnet B's checkpoint is left untouched.
+        assert_eq!(
+            (manifest_b.clone(), height_b),
+            compute_manifest_for_root(&root_b, &log)
+        );
+
+        // Canister and snapshot files are taken over unmodified.
+        for &file in subnet_a_canister_files() {
+            assert_eq!(
+                file_info(file, &manifest_a),
+                file_info(file, &manifest_merged)
+            );
+        }
+        for &file in subnet_b_canister_files() {
+            assert_eq!(
+                file_info(file, &manifest_b),
+                file_info(file, &manifest_merged)
+            );
+        }
+
+        // The merged state retains subnet A's ID and the later batch time; and
+        // contains both subnets' canisters and ingress history.
+        let merged = load_latest_state(&root_a, &log);
+        assert_eq!(SUBNET_A, merged.metadata.own_subnet_id);
+        assert_eq!(batch_time_a.max(batch_time_b), merged.metadata.batch_time);
+        assert_eq!(
+            vec![&CANISTER_1, &CANISTER_2],
+            merged.canister_states.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(2, merged.metadata.ingress_history.len());
+    })
+}
+
+/// Tests that a subnet cannot be merged with itself.
+#[test]
+fn merge_subnet_into_itself_fails() {
+    with_test_replica_logger(|log| {
+        let (tmp_a, _) = new_state_layout(SUBNET_A, CANISTER_1, 1, log.clone());
+        let (tmp_b, _) = new_state_layout(SUBNET_A, CANISTER_2, 2, log.clone());
+
+        let res = merge(
+            tmp_a.path().to_path_buf(),
+            tmp_b.path().to_path_buf(),
+            &MetricsRegistry::new(),
+            log,
+        );
+
+        assert_matches!(res, Err(_));
+    })
+}
+
+/// Creates a state layout under a temporary directory, for subnet `subnet_id`
+/// hosting the single canister `canister_id` (plus a snapshot of it, on subnet
+/// A) and having an ingress history entry for it. `seed` is used to derive a
+/// distinct message ID and batch time.
+///
+/// Returns a handle to the `TempDir` holding the state layout; and the batch
+/// time of the last (and only) checkpoint within.
+fn new_state_layout(
+    subnet_id: SubnetId,
+    canister_id: CanisterId,
+    seed: u8,
+    log: ReplicaLogger,
+) -> (TempDir, Time) {
+    let tmp = tmpdir("checkpoint");
+    let root = tmp.path().to_path_buf();
+    let metrics_registry = MetricsRegistry::new();
+    let layout = StateLayout::try_new(log.clone(), root, &metrics_registry).unwrap();
+    let tip_handler = layout.capture_tip_handler();
+    let state_manager_metrics = StateManagerMetrics::new(&metrics_registry, log.clone());
+    let (_tip_thread, tip_channel) = spawn_tip_thread(
+        log,
+        tip_handler,
+        layout.clone(),
+        lsmt_config_default(),
+        state_manager_metrics.clone(),
+        MaliciousFlags::default(),
+    );
+
+    let mut state = ReplicatedState::new(subnet_id, SubnetType::Application);
+    state.put_canister_state(new_canister_state_with_execution(
+        canister_id,
+        CANISTER_0.get(),
+        INITIAL_CYCLES,
+        NumSeconds::from(100_000),
+    ));
+    state.metadata.ingress_history.insert(
+        MessageId::from([seed; 32]),
+        IngressStatus::Known {
+            receiver: canister_id.get(),
+            user_id: user_test_id(123),
+            time: UNIX_EPOCH,
+            state: IngressState::Received,
+        },
+        UNIX_EPOCH,
+        (1u64 << 30).into(),
+        |_| {},
+    );
+    state.metadata.batch_time = Time::from_secs_since_unix_epoch(1234567890 + seed as u64).unwrap();
+
+    if subnet_id == SUBNET_A {
+        let snapshot_id = SnapshotId::from((canister_id, 0));
+        let snapshot = CanisterSnapshot::from_canister(
+            state.canister_state(&canister_id).unwrap(),
+            state.time(),
+        )
+        .unwrap();
+        state.take_snapshot(snapshot_id, Arc::new(snapshot));
+    }
+
+    flush_canister_snapshots_and_page_maps(&mut state, HEIGHT, &tip_channel);
+
+    let (state, cp_layout) = make_unvalidated_checkpoint(
+        state,
+        HEIGHT,
+        &tip_channel,
+        &state_manager_metrics.checkpoint_metrics,
+        Arc::new(TestPageAllocatorFileDescriptorImpl::new()),
+    )
+    .unwrap_or_else(|err| panic!("Expected make_unvalidated_checkpoint to succeed, got {err:?}"));
+    flush_tip_channel(&tip_channel);
+    validate_and_finalize_checkpoint_and_remove_unverified_marker(
+        &cp_layout,
+        None,
+        SubnetType::Application,
+        Arc::new(TestPageAllocatorFileDescriptorImpl::new()),
+        &state_manager_metrics.checkpoint_metrics,
+        Some(&mut thread_pool()),
+    )
+    .unwrap();
+
+    (tmp, state.metadata.batch_time)
+}
+
+/// Loads the state from the latest checkpoint under `root`.
+fn load_latest_state(root: &Path, log: &ReplicaLogger) -> ReplicatedState {
+    let metrics_registry = MetricsRegistry::new();
+    let layout = StateLayout::try_new(log.clone(), root.to_path_buf(), &metrics_registry).unwrap();
+    let metrics = StateManagerMetrics::new(&metrics_registry, log.clone());
+    let (_, state) = read_checkpoint(
+        &layout,
+        &mut thread_pool(),
+        Arc::new(TestPageAllocatorFileDescriptorImpl::new()),
+        &metrics,
+    )
+    .unwrap();
+    state
+}
+
+/// Computes the manifest of the latest checkpoint under the state layout at
+/// `root`.
+fn compute_manifest_for_root(root: &Path, log: &ReplicaLogger) -> (Manifest, Height) {
+    let metrics_registry = MetricsRegistry::new();
+    let layout = StateLayout::try_new(log.clone(), root.to_path_buf(), &metrics_registry).unwrap();
+    let metrics = StateManagerMetrics::new(&metrics_registry, log.clone());
+    compute_manifest(&layout, &metrics.manifest_metrics, log)
+}
+
+/// Computes the manifest of the last checkpoint under `state_layout`.
+fn compute_manifest(
+    state_layout: &StateLayout,
+    manifest_metrics: &ManifestMetrics,
+    log: &ReplicaLogger,
+) -> (Manifest, Height) {
+    let last_checkpoint_height = state_layout.checkpoint_heights().unwrap().pop().unwrap();
+    let last_checkpoint_layout = state_layout
+        .checkpoint_verified(last_checkpoint_height)
+        .unwrap();
+    let manifest = crate::manifest::compute_manifest(
+        &mut thread_pool(),
+        manifest_metrics,
+        log,
+        CURRENT_STATE_SYNC_VERSION,
+        &last_checkpoint_layout,
+        1024,
+        None,
+        RehashManifest::No,
+    )
+    .expect("failed to compute manifest");
+
+    (manifest, last_checkpoint_height)
+}
+
+/// Creates a thread pool to be used for checkpointing.
+fn thread_pool() -> scoped_threadpool::Pool {
+    scoped_threadpool::Pool::new(NUMBER_OF_CHECKPOINT_THREADS)
+}
+
+/// Extracts the list of relative file names from the manifest.
+fn manifest_files(manifest: &Manifest) -> Vec<String> {
+    manifest
+        .file_table
+        .iter()
+        .map(|file_info| {
+            file_info
+                .relative_path
+                .as_os_str()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+/// Retrieves the `FileInfo` for `file` from `manifest`. Panics if not found.
+fn file_info<'a>(file: &str, manifest: &'a Manifest) -> &'a FileInfo {
+    for file_info in manifest.file_table.iter() {
+        if file_info.relative_path.as_os_str().to_string_lossy() == file {
+            return file_info;
+        }
+    }
+    panic!("file '{file}' not found in manifest: {manifest:?}")
+}
@@ -99,6 +99,7 @@ pub fn split(
         split_state,
         state_layout,
         &cp,
+        None,
         &mut thread_pool,
         &config,
         Arc::clone(&fd_factory),
@@ -136,7 +137,7 @@ fn resolve(
 }
 
 /// Reads the `ReplicatedState` from the latest checkpoint under `state_layout`.
-fn read_checkpoint(
+pub(crate) fn read_checkpoint(
     state_layout: &StateLayout,
     thread_pool: &mut Pool,
     fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
@@ -173,11 +174,14 @@ fn read_checkpoint(
 }
 
 /// Writes the given `ReplicatedState` into a new checkpoint under
-/// `state_layout`, based off of `old_cp`.
-fn write_checkpoint(
+/// `state_layout`, based off of `old_cp`. If `import_cp` is provided, the
+/// canister and canister snapshot directories of the latter are also copied
+/// over (see [`merge`](crate::merge::merge)).
+pub(crate) fn write_checkpoint(
     mut state: ReplicatedState,
     state_layout: StateLayout,
     old_cp: &CheckpointLayout<ReadOnly>,
+    import_cp: Option<&CheckpointLayout<ReadOnly>>,
     thread_pool: &mut Pool,
     config: &Config,
     fd_factory: Arc<dyn PageAllocatorFileDescriptor>,
@@ -190,6 +194,11 @@ fn write_checkpoint(
     tip_handler
         .reset_tip_to(&state_layout, old_cp, Some(thread_pool))
         .map_err(|e| e.to_string())?;
+    if let Some(import_cp) = import_cp {
+        tip_handler
+            .import_canisters_from(&state_layout, old_height, import_cp, Some(thread_pool))
+            .map_err(|e| e.to_string())?;
+    }
     let (_tip_thread, tip_channel) = spawn_tip_thread(
         log,
         tip_handler,
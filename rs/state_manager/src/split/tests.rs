@@ -161,6 +161,7 @@ fn read_write_roundtrip() {
             state,
             layout.clone(),
             &cp,
+            None,
             &mut thread_pool,
             &Config::new(root),
             fd_factory.clone(),
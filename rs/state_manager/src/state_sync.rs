@@ -4,22 +4,29 @@ pub mod types;
 use super::StateManagerImpl;
 use crate::{
     EXTRA_CHECKPOINTS_TO_KEEP, LABEL_LOAD_AND_VALIDATE_CHECKPOINT, LABEL_ON_SYNCED_CHECKPOINT,
-    NUMBER_OF_CHECKPOINT_THREADS, StateSyncRefs,
-    manifest::build_file_group_chunks,
-    state_sync::types::{FileGroupChunks, Manifest, MetaManifest, StateSyncMessage},
+    ManifestMetrics, NUMBER_OF_CHECKPOINT_THREADS, StateSyncRefs,
+    manifest::{RehashManifest, build_file_group_chunks, compute_manifest},
+    state_sync::types::{
+        DEFAULT_CHUNK_SIZE, FileGroupChunks, Manifest, MetaManifest, StateSyncMessage,
+    },
 };
 use ic_interfaces::p2p::state_sync::{
     Chunk, ChunkId, Chunkable, StateSyncArtifactId, StateSyncClient,
 };
 use ic_interfaces_state_manager::StateReader;
 use ic_logger::{ReplicaLogger, fatal, info, warn};
-use ic_types::{CryptoHashOfState, Height};
-use std::sync::{Arc, Mutex};
+use ic_state_layout::{CheckpointLayout, ReadOnly};
+use ic_types::{CryptoHashOfState, Height, state_sync::CURRENT_STATE_SYNC_VERSION};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
 
 #[derive(Clone)]
 pub struct StateSync {
     state_manager: Arc<StateManagerImpl>,
     state_sync_refs: StateSyncRefs,
+    /// Manifests of the checkpoints under the configured directory of old
+    /// states, set by a background thread once computed.
+    old_states: Arc<OnceLock<Vec<(Manifest, PathBuf)>>>,
     log: ReplicaLogger,
     #[cfg(debug_assertions)]
     pub test_force_validate: bool,
@@ -27,9 +34,33 @@ pub struct StateSync {
 
 impl StateSync {
     pub fn new(state_manager: Arc<StateManagerImpl>, log: ReplicaLogger) -> Self {
+        let state_sync_refs = StateSyncRefs::new(log.clone());
+        // Resume a state sync interrupted by a restart, if any.
+        state_sync_refs.cache.write().restore(
+            &state_manager.state_layout,
+            state_manager.latest_state_height(),
+        );
+        let old_states: Arc<OnceLock<Vec<(Manifest, PathBuf)>>> = Default::default();
+        if let Some(dir) = state_manager.state_sync_old_states_dir.clone() {
+            let old_states = Arc::clone(&old_states);
+            let metrics = state_manager.metrics.manifest_metrics.clone();
+            let thread_log = log.clone();
+            if let Err(err) = std::thread::Builder::new()
+                .name("OldStatesIndex".to_string())
+                .spawn(move || {
+                    let _ = old_states.set(index_old_states(&dir, &metrics, &thread_log));
+                })
+            {
+                warn!(
+                    log,
+                    "Failed to spawn the old states indexing thread: {}", err
+                );
+            }
+        }
         Self {
             state_manager,
-            state_sync_refs: StateSyncRefs::new(log.clone()),
+            state_sync_refs,
+            old_states,
             log,
             #[cfg(debug_assertions)]
             test_force_validate: false,
@@ -45,6 +76,7 @@ impl StateSync {
         Self {
             state_manager,
             state_sync_refs,
+            old_states: Default::default(),
             log,
             #[cfg(debug_assertions)]
             test_force_validate: false,
@@ -56,6 +88,16 @@ impl StateSync {
         self.test_force_validate
     }
 
+    /// Returns the manifests and paths of the checkpoints under the read-only
+    /// directory of old states that have been indexed so far.
+    ///
+    /// The manifests are computed by a background thread started on creation,
+    /// so that state sync never waits for them; until indexing completes no old
+    /// states are returned.
+    fn old_states(&self) -> &[(Manifest, PathBuf)] {
+        self.old_states.get().map(Vec::as_slice).unwrap_or_default()
+    }
+
     /// Returns requested state as a Chunkable artifact for StateSync.
     fn create_chunkable_state(
         &self,
@@ -358,3 +400,68 @@ impl StateSyncClient for StateSync {
         msg.get_chunk(chunk_id)
     }
 }
+
+/// Computes the manifests of the checkpoints under `dir`, a read-only directory
+/// of old states.
+fn index_old_states(
+    dir: &Path,
+    metrics: &ManifestMetrics,
+    log: &ReplicaLogger,
+) -> Vec<(Manifest, PathBuf)> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(
+                log,
+                "Failed to list old states under {}: {}",
+                dir.display(),
+                err
+            );
+            return vec![];
+        }
+    };
+
+    let mut thread_pool = scoped_threadpool::Pool::new(NUMBER_OF_CHECKPOINT_THREADS);
+    let mut old_states = vec![];
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        let Some(height) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| u64::from_str_radix(name, 16).ok())
+            .map(Height::new)
+        else {
+            continue;
+        };
+        let manifest = CheckpointLayout::<ReadOnly>::new_untracked(path.clone(), height)
+            .map_err(|err| err.to_string())
+            .and_then(|checkpoint_layout| {
+                compute_manifest(
+                    &mut thread_pool,
+                    metrics,
+                    log,
+                    CURRENT_STATE_SYNC_VERSION,
+                    &checkpoint_layout,
+                    DEFAULT_CHUNK_SIZE,
+                    None,
+                    RehashManifest::No,
+                )
+                .map_err(|err| err.to_string())
+            });
+        match manifest {
+            Ok(manifest) => old_states.push((manifest, path)),
+            Err(err) => warn!(
+                log,
+                "Failed to compute manifest of old state {}: {}",
+                path.display(),
+                err
+            ),
+        }
+    }
+    info!(
+        log,
+        "Indexed {} old states under {} for state sync",
+        old_states.len(),
+        dir.display()
+    );
+    old_states
+}
@@ -1,9 +1,9 @@
 use crate::{
-    CRITICAL_ERROR_STATE_SYNC_CORRUPTED_CHUNKS, LABEL_COPY_CHUNKS, LABEL_FETCH,
-    LABEL_FETCH_MANIFEST_CHUNK, LABEL_FETCH_META_MANIFEST_CHUNK, LABEL_FETCH_STATE_CHUNK,
-    LABEL_HARDLINK_FILES, LABEL_PREALLOCATE, LABEL_PREALLOCATE_DIRECTORIES,
-    LABEL_PREALLOCATE_FILES, LABEL_STATE_SYNC_MAKE_CHECKPOINT, StateManagerMetrics,
-    StateSyncMetrics, StateSyncRefs,
+    CRITICAL_ERROR_STATE_SYNC_CORRUPTED_CHUNKS, LABEL_COPY_CHUNKS, LABEL_COPY_HISTORICAL_CHUNKS,
+    LABEL_FETCH, LABEL_FETCH_MANIFEST_CHUNK, LABEL_FETCH_META_MANIFEST_CHUNK,
+    LABEL_FETCH_STATE_CHUNK, LABEL_HARDLINK_FILES, LABEL_PREALLOCATE,
+    LABEL_PREALLOCATE_DIRECTORIES, LABEL_PREALLOCATE_FILES, LABEL_STATE_SYNC_MAKE_CHECKPOINT,
+    StateManagerMetrics, StateSyncMetrics, StateSyncRefs,
     manifest::{DiffScript, build_file_group_chunks, filter_out_zero_chunks},
     state_sync::StateSync,
     state_sync::types::{
@@ -15,7 +15,10 @@ use crate::{
 };
 use ic_interfaces::p2p::state_sync::{AddChunkError, Chunk, ChunkId, Chunkable};
 use ic_logger::{ReplicaLogger, debug, error, fatal, info, trace, warn};
-use ic_state_layout::{CheckpointLayout, ReadOnly, RwPolicy, StateLayout, error::LayoutError};
+use ic_state_layout::{
+    CheckpointLayout, ReadOnly, RwPolicy, STATE_SYNC_PROGRESS_MARKER, StateLayout,
+    error::LayoutError,
+};
 use ic_sys::mmap::ScopedMmap;
 use ic_types::{CryptoHashOfState, Height, malicious_flags::MaliciousFlags};
 use ic_utils::thread::parallel_map;
@@ -903,6 +906,187 @@ impl IncompleteState {
         }
     }
 
+    /// Copies chunks that still need to be fetched from any of the
+    /// `historical_sources` (other local checkpoints and old states) holding a
+    /// chunk with the same hash. Each chunk is validated against `manifest_new`
+    /// before being copied, as the sources may be arbitrarily old.
+    ///
+    /// Returns the number of bytes copied.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn copy_historical_chunks(
+        &self,
+        log: &ReplicaLogger,
+        metrics: &StateSyncMetrics,
+        thread_pool: &mut scoped_threadpool::Pool,
+        historical_sources: &[(&Manifest, &Path)],
+        root_new: &Path,
+        manifest_new: &Manifest,
+        fetch_chunks: &mut HashSet<usize>,
+    ) -> u64 {
+        if historical_sources.is_empty() || fetch_chunks.is_empty() {
+            return 0;
+        }
+
+        let _timer = metrics
+            .step_duration
+            .with_label_values(&[LABEL_COPY_HISTORICAL_CHUNKS])
+            .start_timer();
+
+        // Index the chunks of all sources by hash, restricted to the hashes of the
+        // chunks still to be fetched.
+        let mut sources_by_hash: HashMap<[u8; 32], (usize, usize)> = fetch_chunks
+            .iter()
+            .map(|id| {
+                (
+                    manifest_new.chunk_table[*id - FILE_CHUNK_ID_OFFSET].hash,
+                    (0, 0),
+                )
+            })
+            .collect();
+        let mut located = HashSet::new();
+        for (source_index, (manifest_old, _)) in historical_sources.iter().enumerate() {
+            for (chunk_index, chunk) in manifest_old.chunk_table.iter().enumerate() {
+                if let Some(source) = sources_by_hash.get_mut(&chunk.hash)
+                    && located.insert(chunk.hash)
+                {
+                    *source = (source_index, chunk_index);
+                }
+            }
+        }
+
+        type ChunkGroup = Vec<(usize, usize)>;
+        // Group chunks by destination file and source file to lower the cost of
+        // opening files. Values are pairs of destination and source chunk indices.
+        let mut chunk_groups: HashMap<(usize, usize, usize), ChunkGroup> = HashMap::default();
+        for id in fetch_chunks.iter() {
+            let dst_chunk_index = *id - FILE_CHUNK_ID_OFFSET;
+            let hash = &manifest_new.chunk_table[dst_chunk_index].hash;
+            if !located.contains(hash) {
+                continue;
+            }
+            let (source_index, src_chunk_index) = sources_by_hash[hash];
+            let dst_file_index = manifest_new.chunk_table[dst_chunk_index].file_index as usize;
+            let src_file_index =
+                historical_sources[source_index].0.chunk_table[src_chunk_index].file_index as usize;
+            chunk_groups
+                .entry((dst_file_index, source_index, src_file_index))
+                .or_default()
+                .push((dst_chunk_index, src_chunk_index));
+        }
+
+        info!(
+            log,
+            "state sync: copy_historical_chunks for {} chunks from {} sources",
+            chunk_groups.values().map(Vec::len).sum::<usize>(),
+            historical_sources.len()
+        );
+
+        let copied_chunks = Arc::new(Mutex::new(Vec::new()));
+
+        thread_pool.scoped(|scope| {
+            for ((dst_file_index, source_index, src_file_index), chunk_group) in chunk_groups.iter()
+            {
+                let (manifest_old, root_old) = historical_sources[*source_index];
+                let dst_path =
+                    root_new.join(&manifest_new.file_table[*dst_file_index].relative_path);
+                let src_path =
+                    root_old.join(&manifest_old.file_table[*src_file_index].relative_path);
+                let copied_chunks = Arc::clone(&copied_chunks);
+                scope.execute(move || {
+                    // Historical sources may disappear or change underneath us, the
+                    // affected chunks are simply fetched instead.
+                    let src = match std::fs::File::open(&src_path) {
+                        Ok(src) => src,
+                        Err(err) => {
+                            warn!(
+                                log,
+                                "Failed to open file {} for read: {}",
+                                src_path.display(),
+                                err
+                            );
+                            return;
+                        }
+                    };
+                    let src_len = match src.metadata() {
+                        Ok(metadata) => metadata.len() as usize,
+                        Err(err) => {
+                            warn!(
+                                log,
+                                "Failed to get metadata of file {}: {}",
+                                src_path.display(),
+                                err
+                            );
+                            return;
+                        }
+                    };
+                    let src_map = match ScopedMmap::from_readonly_file(&src, src_len) {
+                        Ok(src_map) => src_map,
+                        Err(err) => {
+                            warn!(log, "Failed to mmap file {}: {}", src_path.display(), err);
+                            return;
+                        }
+                    };
+                    let dst = std::fs::OpenOptions::new()
+                        .write(true)
+                        .create(false)
+                        .open(&dst_path)
+                        .unwrap_or_else(|err| {
+                            fatal!(log, "Failed to open file {}: {}", dst_path.display(), err)
+                        });
+
+                    for (dst_chunk_index, src_chunk_index) in chunk_group {
+                        let dst_chunk = &manifest_new.chunk_table[*dst_chunk_index];
+                        let byte_range = manifest_old.chunk_table[*src_chunk_index].byte_range();
+                        if src_map.len() < byte_range.end {
+                            continue;
+                        }
+                        let src_data = &src_map.as_slice()[byte_range];
+
+                        if let Err(err) = crate::manifest::validate_chunk(
+                            *dst_chunk_index,
+                            src_data,
+                            manifest_new,
+                        ) {
+                            debug!(
+                                log,
+                                "Historical chunk {} ({}) doesn't pass validation: {}",
+                                *src_chunk_index,
+                                src_path.display(),
+                                err
+                            );
+                            metrics
+                                .corrupted_chunks
+                                .with_label_values(&[LABEL_COPY_HISTORICAL_CHUNKS])
+                                .inc();
+                            continue;
+                        }
+
+                        dst.write_all_at(src_data, dst_chunk.offset)
+                            .unwrap_or_else(|err| {
+                                fatal!(
+                                    log,
+                                    "Failed to write chunk (offset = {}, size = {}) to file {}: {}",
+                                    dst_chunk.offset,
+                                    dst_chunk.size_bytes,
+                                    dst_path.display(),
+                                    err
+                                )
+                            });
+                        copied_chunks.lock().unwrap().push(*dst_chunk_index);
+                    }
+                });
+            }
+        });
+
+        let mut copied_bytes = 0;
+        for chunk_index in copied_chunks.lock().unwrap().iter() {
+            fetch_chunks.remove(&(*chunk_index + FILE_CHUNK_ID_OFFSET));
+            copied_bytes += manifest_new.chunk_table[*chunk_index].size_bytes as u64;
+            metrics.remaining.sub(1);
+        }
+        copied_bytes
+    }
+
     pub(crate) fn apply_chunk(
         log: &ReplicaLogger,
         metrics: &StateSyncMetrics,
@@ -954,6 +1138,19 @@ impl IncompleteState {
             "state sync: start to make a checkpoint from the scratchpad"
         );
 
+        // The progress marker is not part of the state.
+        let progress_marker = root.join(STATE_SYNC_PROGRESS_MARKER);
+        if progress_marker.exists() {
+            std::fs::remove_file(&progress_marker).unwrap_or_else(|err| {
+                fatal!(
+                    log,
+                    "Failed to remove state sync progress marker {}: {}",
+                    progress_marker.display(),
+                    err
+                )
+            });
+        }
+
         let scratchpad_layout =
             CheckpointLayout::<RwPolicy<()>>::new_untracked(root.to_path_buf(), height)
                 .expect("failed to create checkpoint layout");
@@ -1038,6 +1235,11 @@ impl IncompleteState {
             .state_sync_metrics
             .size
             .with_label_values(&[LABEL_COPY_CHUNKS]);
+        let state_sync_size_copy_historical_chunks = self
+            .metrics
+            .state_sync_metrics
+            .size
+            .with_label_values(&[LABEL_COPY_HISTORICAL_CHUNKS]);
         let state_sync_size_preallocate = self
             .metrics
             .state_sync_metrics
@@ -1084,7 +1286,7 @@ impl IncompleteState {
                         // StateSyncCacheEntry, so cloning the path is safe
                         root_old: cache_entry.path().to_path_buf(),
                         height_old: cache_entry.height,
-                        validate_data: false,
+                        validate_data: cache_entry.validate_data,
                     })
                 } else {
                     // This should be a special case that can only happen if the source of the
@@ -1105,7 +1307,7 @@ impl IncompleteState {
                 missing_chunks: cache_entry.missing_chunks.clone(),
                 root_old: cache_entry.path().to_path_buf(),
                 height_old: cache_entry.height,
-                validate_data: false,
+                validate_data: cache_entry.validate_data,
             }),
             (None, Some((checkpoint_manifest, checkpoint_old))) => {
                 let checkpoint_height = checkpoint_old.height();
@@ -1121,7 +1323,24 @@ impl IncompleteState {
             (None, None) => None,
         };
 
-        if let Some(DiffData {
+        // Any other local checkpoint, or old state, may hold chunks that are
+        // neither in `root_old` nor all zeros.
+        let checkpoint_manifests = self.state_sync.state_manager.checkpoint_manifests();
+        let historical_sources = |root_old: Option<&Path>| {
+            checkpoint_manifests
+                .iter()
+                .map(|(manifest, checkpoint_layout)| (manifest, checkpoint_layout.raw_path()))
+                .chain(
+                    self.state_sync
+                        .old_states()
+                        .iter()
+                        .map(|(manifest, path)| (manifest, path.as_path())),
+                )
+                .filter(|(_, path)| Some(*path) != root_old)
+                .collect::<Vec<_>>()
+        };
+
+        let (mut fetch_chunks, diff_bytes, historical_sources) = if let Some(DiffData {
             manifest_old,
             missing_chunks,
             root_old,
@@ -1174,7 +1393,6 @@ impl IncompleteState {
             let copy_chunks_bytes: u64 =
                 total_bytes - diff_bytes - preallocate_bytes - hardlink_files_bytes;
 
-            state_sync_size_fetch.inc_by(diff_bytes);
             state_sync_size_preallocate.inc_by(preallocate_bytes);
             state_sync_size_hardlink_files.inc_by(hardlink_files_bytes);
             state_sync_size_copy_chunks.inc_by(copy_chunks_bytes);
@@ -1232,7 +1450,11 @@ impl IncompleteState {
                 &mut fetch_chunks,
             );
 
-            fetch_chunks
+            (
+                fetch_chunks,
+                diff_bytes,
+                historical_sources(Some(root_old.as_path())),
+            )
         } else {
             info!(
                 self.log,
@@ -1253,7 +1475,6 @@ impl IncompleteState {
                 .iter()
                 .map(|i| manifest_new.chunk_table[*i].size_bytes as u64)
                 .sum();
-            state_sync_size_fetch.inc_by(diff_bytes);
             state_sync_size_preallocate.inc_by(total_bytes - diff_bytes);
 
             let zeros_chunks = manifest_new.chunk_table.len() - non_zero_chunks.len();
@@ -1263,11 +1484,29 @@ impl IncompleteState {
                 .remaining
                 .sub(zeros_chunks as i64);
 
-            non_zero_chunks
-                .iter()
-                .map(|i| *i + FILE_CHUNK_ID_OFFSET)
-                .collect()
-        }
+            (
+                non_zero_chunks
+                    .iter()
+                    .map(|i| *i + FILE_CHUNK_ID_OFFSET)
+                    .collect(),
+                diff_bytes,
+                historical_sources(None),
+            )
+        };
+
+        let historical_bytes = self.copy_historical_chunks(
+            &self.log,
+            &self.metrics.state_sync_metrics,
+            &mut self.thread_pool.lock().unwrap(),
+            &historical_sources,
+            &self.root,
+            manifest_new,
+            &mut fetch_chunks,
+        );
+        state_sync_size_fetch.inc_by(diff_bytes.saturating_sub(historical_bytes));
+        state_sync_size_copy_historical_chunks.inc_by(historical_bytes);
+
+        fetch_chunks
     }
 }
 
@@ -1581,6 +1820,22 @@ impl Chunkable<StateSyncMessage> for IncompleteState {
                             fetch_chunks.insert(chunk_id as usize);
                         }
 
+                        // Persist a progress marker, so that the chunks fetched from now on
+                        // can be reused if the replica restarts before the sync completes.
+                        if let Err(err) = cache::write_progress_marker(
+                            &self.root,
+                            self.height,
+                            &self.root_hash,
+                            &manifest,
+                        ) {
+                            warn!(
+                                self.log,
+                                "Failed to persist state sync progress marker @{}: {}",
+                                self.height,
+                                err
+                            );
+                        }
+
                         let num_fetch_chunks = fetch_chunks.len();
                         self.state = DownloadState::Loading {
                             meta_manifest,
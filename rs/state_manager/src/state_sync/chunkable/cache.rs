@@ -1,4 +1,8 @@
 use super::*;
+use ic_protobuf::{proxy::try_from_option_field, state::sync::v1 as pb};
+use ic_state_layout::STATE_SYNC_PROGRESS_MARKER;
+use ic_types::crypto::CryptoHash;
+use prost::Message;
 
 #[cfg(test)]
 mod tests;
@@ -15,6 +19,48 @@ fn delete_folder(log: &ReplicaLogger, path: &Path) {
     };
 }
 
+/// Persists a progress marker for the state sync @`height` under `root`, tying
+/// the partially fetched state to the manifest with root hash `root_hash`.
+///
+/// On restart, `StateLayout` preserves the state sync data carrying such a
+/// marker and `StateSyncCache::restore()` turns it back into a cache entry.
+pub(crate) fn write_progress_marker(
+    root: &Path,
+    height: Height,
+    root_hash: &CryptoHashOfState,
+    manifest: &Manifest,
+) -> std::io::Result<()> {
+    let progress = pb::StateSyncProgress {
+        height: height.get(),
+        root_hash: root_hash.get_ref().0.clone(),
+        manifest: Some(manifest.clone().into()),
+    };
+    ic_sys::fs::write_protobuf_using_tmp_file(root.join(STATE_SYNC_PROGRESS_MARKER), &progress)
+}
+
+/// Reads the progress marker under `root` and returns the manifest of the
+/// state sync @`height`, after validating it against the recorded root hash.
+fn read_progress_marker(root: &Path, height: Height) -> Result<Manifest, String> {
+    let path = root.join(STATE_SYNC_PROGRESS_MARKER);
+    let bytes = std::fs::read(&path)
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let progress = pb::StateSyncProgress::decode(bytes.as_slice())
+        .map_err(|err| format!("failed to decode {}: {}", path.display(), err))?;
+    if progress.height != height.get() {
+        return Err(format!(
+            "progress marker is for height {}, expected {}",
+            progress.height, height
+        ));
+    }
+    let manifest: Manifest =
+        try_from_option_field(progress.manifest, "StateSyncProgress::manifest")
+            .map_err(|err| format!("failed to decode manifest: {err}"))?;
+    let root_hash = CryptoHashOfState::from(CryptoHash(progress.root_hash));
+    crate::manifest::validate_manifest(&manifest, &root_hash)
+        .map_err(|err| format!("invalid manifest: {err}"))?;
+    Ok(manifest)
+}
+
 /// A cache for unfinished state sync artifacts.
 ///
 /// It contains the most recent incomplete state (i.e., with the largest
@@ -35,6 +81,10 @@ pub struct StateSyncCacheEntry {
     pub height: Height,
     path: PathBuf,
     pub missing_chunks: HashSet<usize>,
+    /// Whether the chunks on disk must be validated against `manifest` before
+    /// being reused. This is the case for state sync data that was written
+    /// before a restart and may not have made it to disk in full.
+    pub validate_data: bool,
     log: ReplicaLogger,
 }
 
@@ -58,6 +108,62 @@ impl StateSyncCache {
         Self { entry: None, log }
     }
 
+    /// Restores the state sync data preserved by `state_layout` across a
+    /// restart, if any, as the cache entry. The data is discarded if it is not
+    /// above `latest_checkpoint_height` or if its progress marker is invalid.
+    ///
+    /// Chunks of the restored entry are validated before being reused.
+    pub fn restore(&mut self, state_layout: &StateLayout, latest_checkpoint_height: Height) {
+        let (height, path) = match state_layout.take_resumable_state_sync() {
+            Ok(Some(resumable)) => resumable,
+            Ok(None) => return,
+            Err(err) => {
+                warn!(
+                    self.log,
+                    "Failed to restore interrupted state sync data: {}", err
+                );
+                return;
+            }
+        };
+
+        if height <= latest_checkpoint_height {
+            info!(
+                self.log,
+                "Discarding interrupted state sync @{}: checkpoint @{} is available",
+                height,
+                latest_checkpoint_height
+            );
+            delete_folder(&self.log, &path);
+            return;
+        }
+
+        match read_progress_marker(&path, height) {
+            Ok(manifest) => {
+                info!(
+                    self.log,
+                    "Restored interrupted state sync @{} from {}",
+                    height,
+                    path.display()
+                );
+                self.entry = Some(Arc::new(StateSyncCacheEntry {
+                    manifest,
+                    height,
+                    path,
+                    missing_chunks: Default::default(),
+                    validate_data: true,
+                    log: self.log.clone(),
+                }));
+            }
+            Err(err) => {
+                warn!(
+                    self.log,
+                    "Discarding interrupted state sync @{}: {}", height, err
+                );
+                delete_folder(&self.log, &path);
+            }
+        }
+    }
+
     /// Returns a reference to the cached entry if there is one available.
     pub fn get(&self) -> Option<Arc<StateSyncCacheEntry>> {
         self.entry.clone()
@@ -132,6 +238,7 @@ impl StateSyncCache {
             height: sync.height,
             path: cache_root,
             missing_chunks,
+            validate_data: false,
             log: self.log.clone(),
         };
         self.entry = Some(Arc::new(entry));
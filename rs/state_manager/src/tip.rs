@@ -718,9 +718,25 @@ fn flush_unflushed_checkpoint_ops(
             UnflushedCheckpointOp::TakeSnapshot(canister_id, snapshot_id) => {
                 backup(log, &tip_handler.tip(height)?, canister_id, snapshot_id)?;
             }
+            UnflushedCheckpointOp::TakeIncrementalSnapshot(canister_id, snapshot_id) => {
+                backup_incremental(log, &tip_handler.tip(height)?, canister_id, snapshot_id)?;
+            }
             UnflushedCheckpointOp::LoadSnapshot(canister_id, snapshot_id) => {
                 restore(log, &tip_handler.tip(height)?, canister_id, snapshot_id)?;
             }
+            UnflushedCheckpointOp::LoadIncrementalSnapshot(
+                canister_id,
+                snapshot_id,
+                root_snapshot_id,
+            ) => {
+                restore_incremental(
+                    log,
+                    &tip_handler.tip(height)?,
+                    canister_id,
+                    snapshot_id,
+                    root_snapshot_id,
+                )?;
+            }
             UnflushedCheckpointOp::RenameCanister(src, dst) => {
                 tip_handler.move_canister_directory(height, src, dst)?;
             }
@@ -769,6 +785,30 @@ fn backup<T>(
     Ok(())
 }
 
+/// Represent a backup operation on disk for an incremental snapshot.
+/// Same as `backup`, except that the memory files are not copied: the `PageMap`s of an incremental snapshot
+/// are new and only hold the pages that differ from the parent snapshot, which are flushed as part of
+/// `FlushPageMapDelta`.
+fn backup_incremental<T>(
+    log: &ReplicaLogger,
+    layout: &CheckpointLayout<RwPolicy<T>>,
+    canister_id: CanisterId,
+    snapshot_id: SnapshotId,
+) -> Result<(), LayoutError> {
+    let canister_layout = layout.canister(&canister_id)?;
+    let snapshot_layout = layout.snapshot(&snapshot_id)?;
+
+    PageMapLayout::copy_or_hardlink_files(
+        log,
+        &canister_layout.wasm_chunk_store(),
+        &snapshot_layout.wasm_chunk_store(),
+    )?;
+
+    WasmFile::hardlink_file(&canister_layout.wasm(), &snapshot_layout.wasm())?;
+
+    Ok(())
+}
+
 /// Represent a restore operation on disk.
 /// When a restore is triggered, execution creates a `CanisterState` from a `CanisterSnapshot` by copying all its `PageMaps` as well as its wasm binary.
 /// This function will run at an unspecified point afterwards (but before the next checkpoint) and it copies all files the snapshot had in the tip
@@ -807,6 +847,46 @@ fn restore<T>(
     Ok(())
 }
 
+/// Represent a restore operation on disk for an incremental snapshot.
+/// The memory files are copied from the full snapshot at the root of the snapshot's chain, the pages stored by
+/// the incremental snapshots in the chain are part of the canister's `PageMap` deltas and flushed on top of them
+/// as part of `FlushPageMapDelta`. The wasm chunk store and the wasm binary are copied from the snapshot itself.
+fn restore_incremental<T>(
+    log: &ReplicaLogger,
+    layout: &CheckpointLayout<RwPolicy<T>>,
+    canister_id: CanisterId,
+    snapshot_id: SnapshotId,
+    root_snapshot_id: SnapshotId,
+) -> Result<(), LayoutError> {
+    let canister_layout = layout.canister(&canister_id)?;
+    let snapshot_layout = layout.snapshot(&snapshot_id)?;
+    let root_snapshot_layout = layout.snapshot(&root_snapshot_id)?;
+
+    canister_layout.vmemory_0().delete_files()?;
+    PageMapLayout::copy_or_hardlink_files(
+        log,
+        &root_snapshot_layout.vmemory_0(),
+        &canister_layout.vmemory_0(),
+    )?;
+    canister_layout.stable_memory().delete_files()?;
+    PageMapLayout::copy_or_hardlink_files(
+        log,
+        &root_snapshot_layout.stable_memory(),
+        &canister_layout.stable_memory(),
+    )?;
+    canister_layout.wasm_chunk_store().delete_files()?;
+    PageMapLayout::copy_or_hardlink_files(
+        log,
+        &snapshot_layout.wasm_chunk_store(),
+        &canister_layout.wasm_chunk_store(),
+    )?;
+
+    canister_layout.wasm().try_delete_file()?;
+    WasmFile::hardlink_file(&snapshot_layout.wasm(), &canister_layout.wasm())?;
+
+    Ok(())
+}
+
 struct StorageInfo {
     disk_size: u64,
     mem_size: u64,
@@ -1344,6 +1424,10 @@ fn serialize_canister_protos_to_checkpoint_readwrite(
                 .environment_variables
                 .clone()
                 .into(),
+            latency_sensitive_methods: canister_state
+                .system_state
+                .latency_sensitive_methods
+                .clone(),
         }
         .into(),
     )?;
@@ -1376,6 +1460,7 @@ fn serialize_snapshot_protos_to_checkpoint_readwrite(
             on_low_wasm_memory_hook_status: canister_snapshot
                 .execution_snapshot()
                 .on_low_wasm_memory_hook_status,
+            parent: canister_snapshot.parent().cloned(),
         }
         .into(),
     )?;
@@ -30,7 +30,8 @@ use ic_replicated_state::{
     testing::ReplicatedStateTesting,
 };
 use ic_state_layout::{
-    CANISTER_FILE, CheckpointLayout, ReadOnly, SYSTEM_METADATA_FILE, StateLayout, WASM_FILE,
+    CANISTER_FILE, CheckpointLayout, ReadOnly, STATE_SYNC_PROGRESS_MARKER, SYSTEM_METADATA_FILE,
+    StateLayout, WASM_FILE,
 };
 use ic_state_machine_tests::{StateMachine, StateMachineBuilder};
 use ic_state_manager::manifest::{build_meta_manifest, manifest_from_path, validate_manifest};
@@ -433,6 +434,7 @@ fn lazy_wasms() {
         .take_canister_snapshot(TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         })
         .unwrap()
         .snapshot_id();
@@ -3021,6 +3023,101 @@ fn can_state_sync_from_cache_alone() {
     })
 }
 
+#[test]
+fn can_resume_state_sync_after_restart() {
+    state_manager_test_with_state_sync(|src_metrics, src_state_manager, src_state_sync| {
+        let (_height, mut state) = src_state_manager.take_tip();
+        insert_dummy_canister(&mut state, canister_test_id(100));
+        insert_dummy_canister(&mut state, canister_test_id(200));
+
+        src_state_manager.commit_and_certify(state, height(1), CertificationScope::Full, None);
+        let hash = wait_for_checkpoint(&*src_state_manager, height(1));
+        let id = StateSyncArtifactId {
+            height: height(1),
+            hash: hash.get_ref().clone(),
+        };
+
+        let msg = src_state_sync
+            .get(&id)
+            .expect("failed to get state sync messages");
+
+        let state = src_state_manager.get_latest_state().take();
+        assert_error_counters(src_metrics);
+
+        state_manager_restart_test_with_state_sync(
+            |dst_metrics, dst_state_manager, dst_state_sync, restart_fn| {
+                // The first state sync fetches everything but the file group chunk.
+                {
+                    let mut chunkable = set_fetch_state_and_start_state_sync(
+                        &dst_state_manager,
+                        &dst_state_sync,
+                        &id,
+                    );
+                    let omit: HashSet<ChunkId> =
+                        maplit::hashset! {ChunkId::new(FILE_GROUP_CHUNK_ID_OFFSET)};
+                    let completion = pipe_partial_state_sync(&msg, &mut *chunkable, &omit, false);
+                    assert_matches!(completion, Ok(false), "Unexpectedly completed state sync");
+                }
+                assert_no_remaining_chunks(dst_metrics);
+
+                // The partially fetched state carries a progress marker.
+                let cache_root = dst_state_manager
+                    .state_layout()
+                    .state_sync_cache(height(1))
+                    .expect("failed to get directory for state sync cache");
+                assert!(cache_root.join(STATE_SYNC_PROGRESS_MARKER).exists());
+
+                // Restart the dst state manager.
+                drop(dst_state_sync);
+                let dst_state_manager = match Arc::try_unwrap(dst_state_manager) {
+                    Ok(sm) => sm,
+                    Err(_) => panic!(
+                        "Please make sure other strong references of dst_state_manager have been dropped"
+                    ),
+                };
+                let (dst_metrics, dst_state_manager, dst_state_sync) =
+                    restart_fn(dst_state_manager, None);
+
+                // The second state sync only needs to fetch the file group chunk, all other
+                // chunks are validated and reused from before the restart.
+                let mut chunkable =
+                    set_fetch_state_and_start_state_sync(&dst_state_manager, &dst_state_sync, &id);
+                let _res = pipe_meta_manifest(&msg, &mut *chunkable, false);
+                let is_finished = pipe_manifest(&msg, &mut *chunkable, false);
+                assert_matches!(is_finished, Ok(false), "Unexpectedly completed state sync");
+                assert_eq!(
+                    vec![ChunkId::new(FILE_GROUP_CHUNK_ID_OFFSET)],
+                    chunkable.chunks_to_download().collect::<Vec<_>>()
+                );
+
+                pipe_state_sync(msg, chunkable);
+
+                let recovered_state = dst_state_manager
+                    .get_state_at(height(1))
+                    .expect("Destination state manager didn't receive the state")
+                    .take();
+                assert_eq!(height(1), dst_state_manager.latest_state_height());
+                assert_eq!(state, recovered_state);
+
+                // The progress marker did not make it into the checkpoint.
+                let checkpoint = dst_state_manager
+                    .state_layout()
+                    .checkpoint_verified(height(1))
+                    .unwrap();
+                assert!(
+                    !checkpoint
+                        .raw_path()
+                        .join(STATE_SYNC_PROGRESS_MARKER)
+                        .exists()
+                );
+
+                assert_no_remaining_chunks(&dst_metrics);
+                assert_error_counters(&dst_metrics);
+            },
+        )
+    })
+}
+
 #[test]
 fn copied_chunks_from_file_group_can_be_skipped_when_applying() {
     use std::os::unix::fs::MetadataExt;
@@ -3772,6 +3869,84 @@ fn can_state_sync_into_existing_checkpoint() {
     })
 }
 
+#[test]
+fn can_state_sync_from_older_checkpoints() {
+    fn insert_canister_with_stable_memory(state: &mut ReplicatedState) {
+        insert_dummy_canister(state, canister_test_id(100));
+        let canister_state = state.canister_state_mut(&canister_test_id(100)).unwrap();
+        canister_state
+            .execution_state
+            .as_mut()
+            .unwrap()
+            .stable_memory
+            .page_map
+            .update(&[(PageIndex::new(0), &[7u8; PAGE_SIZE])]);
+    }
+
+    state_manager_test_with_state_sync(|src_metrics, src_state_manager, src_state_sync| {
+        let (_height, mut state) = src_state_manager.take_tip();
+        insert_canister_with_stable_memory(&mut state);
+        src_state_manager.commit_and_certify(state, height(1), CertificationScope::Full, None);
+        let (_height, state) = src_state_manager.take_tip();
+        src_state_manager.commit_and_certify(state, height(2), CertificationScope::Full, None);
+        let (_height, state) = src_state_manager.take_tip();
+        src_state_manager.commit_and_certify(state, height(3), CertificationScope::Full, None);
+
+        let hash = wait_for_checkpoint(&*src_state_manager, height(3));
+        let id = StateSyncArtifactId {
+            height: height(3),
+            hash: hash.get(),
+        };
+        let msg = src_state_sync
+            .get(&id)
+            .expect("failed to get state sync messages");
+        let state = src_state_manager.get_latest_state().take();
+        assert_error_counters(src_metrics);
+
+        state_manager_test_with_state_sync(|dst_metrics, dst_state_manager, dst_state_sync| {
+            // Only the older of the two local checkpoints holds the canister.
+            let (_height, mut dst_state) = dst_state_manager.take_tip();
+            insert_canister_with_stable_memory(&mut dst_state);
+            dst_state_manager.commit_and_certify(
+                dst_state,
+                height(1),
+                CertificationScope::Full,
+                None,
+            );
+            wait_for_checkpoint(&*dst_state_manager, height(1));
+
+            let (_height, mut dst_state) = dst_state_manager.take_tip();
+            dst_state.take_canister_state(&canister_test_id(100));
+            dst_state_manager.commit_and_certify(
+                dst_state,
+                height(2),
+                CertificationScope::Full,
+                None,
+            );
+            wait_for_checkpoint(&*dst_state_manager, height(2));
+
+            let chunkable =
+                set_fetch_state_and_start_state_sync(&dst_state_manager, &dst_state_sync, &id);
+            pipe_state_sync(msg, chunkable);
+
+            let recovered_state = dst_state_manager
+                .get_state_at(height(3))
+                .expect("Destination state manager didn't receive the state")
+                .take();
+            assert_eq!(state, recovered_state);
+
+            let op_key =
+                maplit::btreemap! {"op".to_string() => "copy_historical_chunks".to_string()};
+            let copied_bytes =
+                fetch_int_counter_vec(dst_metrics, "state_sync_size_bytes_total")[&op_key];
+            assert!(copied_bytes > 0);
+
+            assert_no_remaining_chunks(dst_metrics);
+            assert_error_counters(dst_metrics);
+        })
+    })
+}
+
 #[test]
 fn can_group_small_files_in_state_sync() {
     state_manager_test_with_state_sync(|src_metrics, src_state_manager, src_state_sync| {
@@ -7456,6 +7631,7 @@ fn restore_heap_from_snapshot() {
         .take_canister_snapshot(TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         })
         .unwrap()
         .snapshot_id();
@@ -7528,6 +7704,7 @@ fn restore_stable_memory_from_snapshot() {
         .take_canister_snapshot(TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         })
         .unwrap()
         .snapshot_id();
@@ -7600,6 +7777,7 @@ fn restore_binary_from_snapshot() {
         .take_canister_snapshot(TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         })
         .unwrap()
         .snapshot_id();
@@ -7670,6 +7848,7 @@ fn restore_chunk_store_from_snapshot() {
         .take_canister_snapshot(TakeCanisterSnapshotArgs {
             canister_id: canister_id.into(),
             replace_snapshot: None,
+            parent_snapshot: None,
         })
         .unwrap()
         .snapshot_id();
@@ -7,7 +7,9 @@ pub mod decode;
 pub mod import_state;
 pub mod list;
 pub mod manifest;
+pub mod merge;
 pub mod parse_overlay;
+pub mod snapshot_archive;
 pub mod split;
 pub mod split_manifest;
 mod utils;
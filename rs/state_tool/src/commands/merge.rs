@@ -0,0 +1,12 @@
+//! Merges two replicated states into one, as part of a subnet merge.
+
+use ic_logger::replica_logger::no_op_logger;
+use ic_metrics::MetricsRegistry;
+use std::path::PathBuf;
+
+/// Loads the latest checkpoints under `root` and `other_root`; merges the state
+/// under `other_root` into the one under `root`; and writes back the merged
+/// state as a new checkpoint, under `root`.
+pub fn do_merge(root: PathBuf, other_root: PathBuf) -> Result<(), String> {
+    ic_state_manager::merge::merge(root, other_root, &MetricsRegistry::new(), no_op_logger())
+}
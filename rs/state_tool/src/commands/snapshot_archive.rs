@@ -0,0 +1,186 @@
+//! Exports canister states and snapshots from a checkpoint as snapshot
+//! archives and verifies snapshot archives.
+
+use candid::{Decode, Encode};
+use ic_canister_snapshot_archive::{
+    MAX_SLICE_SIZE, SnapshotArchive, SnapshotArchiveWriter, types::SnapshotDataKind,
+};
+use ic_management_canister_types_private::{
+    ChunkHash, GlobalTimer, ReadCanisterSnapshotMetadataResponse,
+};
+use ic_registry_subnet_type::SubnetType;
+use ic_replicated_state::{
+    canister_snapshots::{CanisterSnapshot, PageMemory},
+    canister_state::WASM_PAGE_SIZE_IN_BYTES,
+    page_map::TestPageAllocatorFileDescriptorImpl,
+};
+use ic_state_layout::CompleteCheckpointLayout;
+use ic_state_manager::{CheckpointMetrics, checkpoint::load_checkpoint};
+use ic_types::{CanisterId, Height, SnapshotId};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Exports the snapshot `snapshot_id` of `canister_id` (or the canister's
+/// current state, if no snapshot is given) from the checkpoint at `path`
+/// into a new snapshot archive at `output`.
+pub fn do_export(
+    path: PathBuf,
+    canister_id: CanisterId,
+    snapshot_id: Option<SnapshotId>,
+    output: PathBuf,
+) -> Result<(), String> {
+    let cp_layout = CompleteCheckpointLayout::new_untracked(path.clone(), Height::new(0))
+        .map_err(|e| format!("failed to create checkpoint layout: {e}"))?;
+
+    let dummy_metrics_registry = ic_metrics::MetricsRegistry::new();
+    let dummy_metrics = CheckpointMetrics::new(&dummy_metrics_registry, crate::commands::logger());
+
+    let state = load_checkpoint(
+        &cp_layout,
+        SubnetType::Application,
+        &dummy_metrics,
+        None,
+        Arc::new(TestPageAllocatorFileDescriptorImpl::new()),
+    )
+    .map_err(|e| format!("failed to load checkpoint at {}: {}", path.display(), e))?;
+
+    let manifest = match snapshot_id {
+        Some(snapshot_id) => {
+            let snapshot = state
+                .canister_snapshots
+                .get(snapshot_id)
+                .filter(|snapshot| snapshot.canister_id() == canister_id)
+                .ok_or_else(|| format!("snapshot {snapshot_id} of {canister_id} not found"))?;
+            // Incremental snapshots only hold the pages changed since their
+            // parent, so the full memories are reassembled from the chain.
+            let memories = state
+                .canister_snapshots
+                .reassemble_memories(snapshot_id, None)
+                .map_err(|e| format!("failed to reassemble snapshot {snapshot_id}: {e:?}"))?;
+            export(
+                snapshot,
+                &memories.wasm_memory,
+                &memories.stable_memory,
+                snapshot_id.to_vec(),
+                &output,
+            )?
+        }
+        None => {
+            let canister = state
+                .canister_state(&canister_id)
+                .ok_or_else(|| format!("canister {canister_id} not found"))?;
+            let snapshot = CanisterSnapshot::from_canister(canister, state.time())
+                .map_err(|e| format!("failed to export canister {canister_id}: {e:?}"))?;
+            export(
+                &snapshot,
+                snapshot.wasm_memory(),
+                snapshot.stable_memory(),
+                vec![],
+                &output,
+            )?
+        }
+    };
+
+    println!(
+        "Exported {} files of canister {} to {}",
+        manifest.files.len(),
+        canister_id,
+        output.display()
+    );
+    Ok(())
+}
+
+/// Writes `snapshot` with the given (reassembled) memories as a snapshot
+/// archive to `output`.
+fn export(
+    snapshot: &CanisterSnapshot,
+    wasm_memory: &PageMemory,
+    stable_memory: &PageMemory,
+    snapshot_id: Vec<u8>,
+    output: &Path,
+) -> Result<ic_canister_snapshot_archive::Manifest, String> {
+    // The last global is the instruction counter appended during Wasm
+    // instrumentation, which is not part of the user-visible metadata.
+    let mut globals = snapshot.exported_globals().clone();
+    globals.pop();
+    let metadata = ReadCanisterSnapshotMetadataResponse {
+        source: snapshot.source(),
+        taken_at_timestamp: snapshot.taken_at_timestamp().as_nanos_since_unix_epoch(),
+        wasm_module_size: snapshot.execution_snapshot().wasm_binary.len() as u64,
+        globals,
+        wasm_memory_size: (wasm_memory.size.get() * WASM_PAGE_SIZE_IN_BYTES) as u64,
+        stable_memory_size: (stable_memory.size.get() * WASM_PAGE_SIZE_IN_BYTES) as u64,
+        wasm_chunk_store: snapshot
+            .chunk_store()
+            .keys()
+            .map(|hash| ChunkHash {
+                hash: hash.to_vec(),
+            })
+            .collect(),
+        canister_version: snapshot.canister_version(),
+        certified_data: snapshot.certified_data().clone(),
+        global_timer: snapshot
+            .execution_snapshot()
+            .global_timer
+            .map(GlobalTimer::from),
+        on_low_wasm_memory_hook_status: snapshot
+            .execution_snapshot()
+            .on_low_wasm_memory_hook_status,
+    };
+    // Both types describe the same Candid record of the management canister
+    // interface, so the metadata is converted via its Candid encoding.
+    let metadata = Decode!(
+        &Encode!(&metadata).map_err(|e| e.to_string())?,
+        ic_canister_snapshot_archive::types::ReadCanisterSnapshotMetadataResponse
+    )
+    .map_err(|e| e.to_string())?;
+
+    let mut writer = SnapshotArchiveWriter::create(
+        output,
+        snapshot.canister_id().get().0,
+        snapshot_id,
+        metadata,
+    )
+    .map_err(|e| e.to_string())?;
+    for request in writer.read_requests(MAX_SLICE_SIZE) {
+        let data = match &request.kind {
+            SnapshotDataKind::WasmModule { offset, size } => snapshot
+                .get_wasm_module_chunk(*offset, *size)
+                .map_err(|e| format!("{e:?}"))?,
+            SnapshotDataKind::WasmMemory { offset, size } => {
+                CanisterSnapshot::get_memory_chunk(wasm_memory.clone(), *offset, *size)
+                    .map_err(|e| format!("{e:?}"))?
+            }
+            SnapshotDataKind::StableMemory { offset, size } => {
+                CanisterSnapshot::get_memory_chunk(stable_memory.clone(), *offset, *size)
+                    .map_err(|e| format!("{e:?}"))?
+            }
+            SnapshotDataKind::WasmChunk { hash } => {
+                let hash: [u8; 32] = hash
+                    .as_slice()
+                    .try_into()
+                    .map_err(|_| format!("invalid chunk hash {}", hex::encode(hash)))?;
+                snapshot
+                    .chunk_store()
+                    .get_chunk_complete(&hash)
+                    .ok_or_else(|| format!("chunk {} not found", hex::encode(hash)))?
+            }
+        };
+        writer.write(&request, &data).map_err(|e| e.to_string())?;
+    }
+    writer.finish().map_err(|e| e.to_string())
+}
+
+/// Verifies the snapshot archive at `path` against its manifest.
+pub fn do_verify(path: PathBuf) -> Result<(), String> {
+    let archive = SnapshotArchive::open(&path)
+        .map_err(|e| format!("invalid snapshot archive at {}: {}", path.display(), e))?;
+    let manifest = archive.manifest();
+    println!(
+        "Snapshot archive of canister {} (format version {}, {} files) is valid",
+        manifest.canister_id,
+        manifest.format_version,
+        manifest.files.len()
+    );
+    Ok(())
+}
@@ -8,7 +8,7 @@ use clap::Parser;
 use ic_registry_routing_table::CanisterIdRange;
 use ic_registry_subnet_type::SubnetType;
 use ic_state_tool::commands;
-use ic_types::{Height, PrincipalId, Time};
+use ic_types::{CanisterId, Height, PrincipalId, SnapshotId, Time};
 use std::{error::Error, path::PathBuf};
 
 /// Supported `state_tool` commands and their arguments.
@@ -138,6 +138,17 @@ enum Opt {
         batch_time_nanos: Option<u64>,
     },
 
+    /// Merges a replicated state into another, as part of a subnet merge.
+    #[clap(name = "merge")]
+    Merge {
+        /// Path to the state layout of the subnet retaining its subnet ID.
+        #[clap(long, required = true)]
+        root: PathBuf,
+        /// Path to the state layout of the subnet being merged (only read from).
+        #[clap(long, required = true)]
+        other_root: PathBuf,
+    },
+
     /// Splits a manifest, to verify the manifests resulting from a subnet split.
     #[clap(name = "split_manifest")]
     SplitManifest {
@@ -168,6 +179,33 @@ enum Opt {
         #[clap(long, required = true)]
         path: PathBuf,
     },
+
+    /// Exports a canister snapshot (or the current state of a canister) from a
+    /// checkpoint as a snapshot archive that can be uploaded to a canister.
+    #[clap(name = "export_snapshot")]
+    ExportSnapshot {
+        /// Path to a checkpoint.
+        #[clap(long = "state")]
+        path: PathBuf,
+        /// ID of the canister to export.
+        #[clap(long, required = true)]
+        canister_id: PrincipalId,
+        /// Hex-encoded ID of the snapshot to export. If not specified, the
+        /// current state of the canister is exported.
+        #[clap(long, value_parser = parse_snapshot_id)]
+        snapshot_id: Option<SnapshotId>,
+        /// Path to the (empty or non-existing) archive directory.
+        #[clap(long, required = true)]
+        output: PathBuf,
+    },
+
+    /// Verifies the files of a snapshot archive against its manifest.
+    #[clap(name = "verify_snapshot_archive")]
+    VerifySnapshotArchive {
+        /// Path to the archive directory.
+        #[clap(long, required = true)]
+        path: PathBuf,
+    },
 }
 
 /// Command line arguments for the `copy` command with eith
@@ -218,6 +256,12 @@ fn parse_height_pair(
     }
 }
 
+/// Parser for a hex-encoded snapshot ID.
+/// Used to parse the `--snapshot_id` argument of the `export_snapshot` command.
+fn parse_snapshot_id(s: &str) -> Result<SnapshotId, Box<dyn Error + Send + Sync + 'static>> {
+    SnapshotId::try_from(hex::decode(s)?).map_err(|e| e.to_string().into())
+}
+
 fn main() {
     let args = std::env::args().collect();
     main_inner(args);
@@ -264,6 +308,7 @@ pub(crate) fn main_inner(args: Vec<String>) {
             drop,
             batch_time_nanos.map(Time::from_nanos_since_unix_epoch),
         ),
+        Opt::Merge { root, other_root } => commands::merge::do_merge(root, other_root),
         Opt::SplitManifest {
             path,
             from_subnet,
@@ -280,6 +325,18 @@ pub(crate) fn main_inner(args: Vec<String>) {
             migrated_ranges,
         ),
         Opt::ParseOverlay { path } => commands::parse_overlay::do_parse_overlay(path),
+        Opt::ExportSnapshot {
+            path,
+            canister_id,
+            snapshot_id,
+            output,
+        } => commands::snapshot_archive::do_export(
+            path,
+            CanisterId::unchecked_from_principal(canister_id),
+            snapshot_id,
+            output,
+        ),
+        Opt::VerifySnapshotArchive { path } => commands::snapshot_archive::do_verify(path),
     };
 
     if let Err(e) = result {
@@ -291,7 +348,9 @@ pub(crate) fn main_inner(args: Vec<String>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ic_canister_snapshot_archive::SnapshotArchive;
     use ic_logger::no_op_logger;
+    use ic_management_canister_types_private::TakeCanisterSnapshotArgs;
     use ic_metrics::MetricsRegistry;
     use ic_state_layout::StateLayout;
     use ic_state_machine_tests::StateMachineBuilder;
@@ -437,4 +496,59 @@ mod tests {
             vec![Height::new(1), Height::new(3)]
         );
     }
+
+    #[test]
+    fn export_snapshot_command_line_test() {
+        let env = StateMachineBuilder::new().build();
+        let canister_id = env.install_canister_wat(
+            r#"(module
+                (memory (export "memory") 2)
+                (data (i32.const 70000) "snapshot data")
+            )"#,
+            vec![],
+            None,
+        );
+        let snapshot_id = env
+            .take_canister_snapshot(TakeCanisterSnapshotArgs::new(canister_id, None))
+            .unwrap()
+            .snapshot_id();
+        env.checkpointed_tick();
+        env.state_manager.flush_tip_channel();
+
+        let state_layout = env.state_manager.state_layout();
+        let height = *state_layout.checkpoint_heights().unwrap().last().unwrap();
+        let checkpoint = state_layout.checkpoint_verified(height).unwrap();
+        let tmp = TempDir::new().unwrap();
+        let exported = tmp.path().join("exported");
+        let downloaded = tmp.path().join("downloaded");
+
+        main_inner(vec![
+            "state-tool".to_string(),
+            "export_snapshot".to_string(),
+            "--state".to_string(),
+            checkpoint.raw_path().display().to_string(),
+            "--canister-id".to_string(),
+            canister_id.to_string(),
+            "--snapshot-id".to_string(),
+            hex::encode(snapshot_id.to_vec()),
+            "--output".to_string(),
+            exported.display().to_string(),
+        ]);
+        main_inner(vec![
+            "state-tool".to_string(),
+            "verify_snapshot_archive".to_string(),
+            "--path".to_string(),
+            exported.display().to_string(),
+        ]);
+
+        // The exported archive is identical to one downloaded via the
+        // management canister.
+        let downloaded_manifest = env
+            .download_canister_snapshot(canister_id, snapshot_id, &downloaded)
+            .unwrap();
+        assert_eq!(
+            SnapshotArchive::open(&exported).unwrap().manifest(),
+            &downloaded_manifest
+        );
+    }
 }
@@ -7,19 +7,19 @@ use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
 use ic_crypto_tree_hash::{LabeledTree, MatchPatternPath, MixedHashTree};
 use ic_interfaces_state_manager::{CertifiedStateSnapshot, Labeled};
 use ic_management_canister_types_private::{
-    EcdsaKeyId, MasterPublicKeyId, SchnorrAlgorithm, SchnorrKeyId, VetKdKeyId,
+    BlsKeyId, EcdsaKeyId, MasterPublicKeyId, SchnorrAlgorithm, SchnorrKeyId, VetKdKeyId,
 };
 use ic_replicated_state::{
     ReplicatedState,
     metadata_state::subnet_call_context_manager::{
-        EcdsaArguments, EcdsaMatchedPreSignature, PreSignatureStash, ReshareChainKeyContext,
-        SchnorrArguments, SchnorrMatchedPreSignature, SignWithThresholdContext, ThresholdArguments,
-        VetKdArguments,
+        BlsArguments, EcdsaArguments, EcdsaMatchedPreSignature, PreSignatureStash,
+        ReshareChainKeyContext, SchnorrArguments, SchnorrMatchedPreSignature,
+        SignWithThresholdContext, ThresholdArguments, VetKdArguments,
     },
 };
 use ic_test_utilities_state::ReplicatedStateBuilder;
 use ic_test_utilities_types::{
-    ids::{node_test_id, subnet_test_id},
+    ids::{canister_test_id, node_test_id, subnet_test_id},
     messages::RequestBuilder,
 };
 use ic_types::{
@@ -115,7 +115,9 @@ pub fn pre_signature_for_tests(key_id: &IDkgMasterPublicKeyId) -> PreSignature {
                 SchnorrPreSignatureTranscript::new(blinder_transcript).unwrap(),
             ))
         }
-        MasterPublicKeyId::VetKd(_) => panic!("No pre-signatures for vetKD"),
+        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+            panic!("No pre-signatures for NiDkg keys")
+        }
     }
 }
 
@@ -144,7 +146,9 @@ pub fn fake_pre_signature_stash(key_id: &IDkgMasterPublicKeyId, size: u64) -> Pr
                     SchnorrPreSignatureTranscript::new(blinder_transcript).unwrap(),
                 ))
             }
-            MasterPublicKeyId::VetKd(_) => panic!("Not an IDkgMasterPublicKeyId"),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                panic!("Not an IDkgMasterPublicKeyId")
+            }
         };
         pre_signatures.insert(PreSigId(i), pre_signature);
     }
@@ -226,7 +230,14 @@ fn fake_signature_request_args(
             key_id: key_id.clone(),
             input: Arc::new(vec![1; 32]),
             transport_public_key: vec![1; 32],
-            ni_dkg_id: fake_dkg_id(key_id),
+            ni_dkg_id: fake_dkg_id(NiDkgMasterPublicKeyId::VetKd(key_id)),
+            height,
+        }),
+        MasterPublicKeyId::Bls(key_id) => ThresholdArguments::Bls(BlsArguments {
+            key_id: key_id.clone(),
+            canister_id: canister_test_id(1),
+            message: Some(Arc::new(vec![1; 32])),
+            ni_dkg_id: fake_dkg_id(NiDkgMasterPublicKeyId::Bls(key_id)),
             height,
         }),
     }
@@ -318,8 +329,8 @@ pub fn fake_malformed_signature_request_context_from_id(
             key_transcript.algorithm_id = AlgorithmId::Tls;
             schnorr.pre_signature.as_mut().unwrap().key_transcript = Arc::new(key_transcript);
         }
-        // VetKd contexts cannot be malformed in this way.
-        ThresholdArguments::VetKd(_) => {}
+        // NiDkg contexts cannot be malformed in this way.
+        ThresholdArguments::VetKd(_) | ThresholdArguments::Bls(_) => {}
     };
 
     (callback_id, context)
@@ -491,6 +502,7 @@ pub fn key_id_with_name(key_id: &MasterPublicKeyId, name: &str) -> MasterPublicK
         MasterPublicKeyId::Ecdsa(ref mut key_id) => key_id.name = name.into(),
         MasterPublicKeyId::Schnorr(ref mut key_id) => key_id.name = name.into(),
         MasterPublicKeyId::VetKd(ref mut key_id) => key_id.name = name.into(),
+        MasterPublicKeyId::Bls(ref mut key_id) => key_id.name = name.into(),
     }
     key_id
 }
@@ -505,6 +517,16 @@ pub fn fake_ecdsa_idkg_master_public_key_id() -> IDkgMasterPublicKeyId {
         .unwrap()
 }
 
+pub fn fake_ecdsa_secp256r1_key_id() -> EcdsaKeyId {
+    EcdsaKeyId::from_str("Secp256r1:some_key").unwrap()
+}
+
+pub fn fake_ecdsa_secp256r1_idkg_master_public_key_id() -> IDkgMasterPublicKeyId {
+    MasterPublicKeyId::Ecdsa(fake_ecdsa_secp256r1_key_id())
+        .try_into()
+        .unwrap()
+}
+
 pub fn fake_schnorr_key_id(algorithm: SchnorrAlgorithm) -> SchnorrKeyId {
     SchnorrKeyId {
         algorithm,
@@ -536,11 +558,19 @@ pub fn fake_vetkd_master_public_key_id() -> MasterPublicKeyId {
     MasterPublicKeyId::VetKd(fake_vetkd_key_id())
 }
 
-pub fn fake_dkg_id(key_id: VetKdKeyId) -> NiDkgId {
+pub fn fake_bls_key_id() -> BlsKeyId {
+    BlsKeyId::from_str("bls12_381_g2:some_key").unwrap()
+}
+
+pub fn fake_bls_master_public_key_id() -> MasterPublicKeyId {
+    MasterPublicKeyId::Bls(fake_bls_key_id())
+}
+
+pub fn fake_dkg_id(key_id: NiDkgMasterPublicKeyId) -> NiDkgId {
     NiDkgId {
         start_block_height: Height::from(0),
         dealer_subnet: subnet_test_id(0),
-        dkg_tag: NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::VetKd(key_id)),
+        dkg_tag: NiDkgTag::HighThresholdForKey(key_id),
         target_subnet: NiDkgTargetSubnet::Local,
     }
 }
@@ -549,6 +579,9 @@ pub fn fake_master_public_key_ids_for_all_idkg_algorithms() -> Vec<IDkgMasterPub
     AlgorithmId::iter()
         .flat_map(|alg| match alg {
             AlgorithmId::ThresholdEcdsaSecp256k1 => Some(fake_ecdsa_idkg_master_public_key_id()),
+            AlgorithmId::ThresholdEcdsaSecp256r1 => {
+                Some(fake_ecdsa_secp256r1_idkg_master_public_key_id())
+            }
             AlgorithmId::ThresholdSchnorrBip340 => Some(fake_schnorr_idkg_master_public_key_id(
                 SchnorrAlgorithm::Bip340Secp256k1,
             )),
@@ -624,7 +657,9 @@ pub fn create_pre_sig_ref_with_args(
         MasterPublicKeyId::Schnorr(key_id) => {
             create_schnorr_pre_sig_ref_with_args(caller, receivers, key_unmasked, height, key_id)
         }
-        MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+            panic!("not applicable to NiDkg keys")
+        }
     }
 }
 
@@ -843,6 +878,8 @@ pub fn create_threshold_sig_inputs(
                 pre_sig.key_transcript.as_ref().clone(),
             ))
         }
-        MasterPublicKeyId::VetKd(_) => panic!("not applicable to vetKD"),
+        MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+            panic!("not applicable to NiDkg keys")
+        }
     }
 }
@@ -2414,6 +2414,11 @@ impl ExecutionTestBuilder {
         self
     }
 
+    pub fn with_incremental_canister_snapshots(mut self) -> Self {
+        self.execution_config.incremental_canister_snapshots = FlagStatus::Enabled;
+        self
+    }
+
     pub fn with_environment_variables_flag(
         mut self,
         environment_variables_flag: FlagStatus,
@@ -2568,28 +2573,27 @@ impl ExecutionTestBuilder {
                         public_key: VETKD_PUB_KEY.to_vec(),
                     },
                 ),
+                MasterPublicKeyId::Bls(_) => (
+                    key_id,
+                    MasterPublicKey {
+                        algorithm_id: AlgorithmId::ThresBls12_381,
+                        public_key: VETKD_PUB_KEY.to_vec(),
+                    },
+                ),
             })
             .collect::<BTreeMap<_, _>>();
 
         let nidkg_ids = chain_key_subnet_public_keys
             .keys()
             .flat_map(|key_id| {
-                if let MasterPublicKeyId::VetKd(vetkd_key_id) = key_id {
-                    let nidkg_id = NiDkgId {
-                        start_block_height: Height::new(0),
-                        dealer_subnet: self.own_subnet_id,
-                        dkg_tag: NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::VetKd(
-                            vetkd_key_id.clone(),
-                        )),
-                        target_subnet: NiDkgTargetSubnet::Local,
-                    };
-                    Some((
-                        NiDkgMasterPublicKeyId::VetKd(vetkd_key_id.clone()),
-                        nidkg_id,
-                    ))
-                } else {
-                    None
-                }
+                let nidkg_key_id = NiDkgMasterPublicKeyId::try_from(key_id.clone()).ok()?;
+                let nidkg_id = NiDkgId {
+                    start_block_height: Height::new(0),
+                    dealer_subnet: self.own_subnet_id,
+                    dkg_tag: NiDkgTag::HighThresholdForKey(nidkg_key_id.clone()),
+                    target_subnet: NiDkgTargetSubnet::Local,
+                };
+                Some((nidkg_key_id, nidkg_id))
             })
             .collect::<BTreeMap<_, _>>();
 
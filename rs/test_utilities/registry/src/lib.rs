@@ -1,15 +1,13 @@
 use ic_crypto_test_utils_ni_dkg::dummy_transcript_for_tests_with_params;
 use ic_limits::INITIAL_NOTARY_DELAY;
-use ic_management_canister_types_private::VetKdKeyId;
 use ic_protobuf::registry::crypto::v1::AlgorithmId;
 use ic_protobuf::registry::crypto::v1::PublicKey as PublicKeyProto;
 use ic_protobuf::registry::subnet::v1::ChainKeyInitialization;
 use ic_protobuf::registry::subnet::v1::chain_key_initialization::Initialization;
 use ic_protobuf::registry::subnet::v1::{
-    CanisterCyclesCostSchedule, CatchUpPackageContents, InitialNiDkgTranscriptRecord,
-    SubnetListRecord, SubnetRecord,
+    AdaptiveBlockTimeConfig, CanisterCyclesCostSchedule, CatchUpPackageContents,
+    InitialNiDkgTranscriptRecord, SubnetListRecord, SubnetRecord,
 };
-use ic_protobuf::types::v1::master_public_key_id::KeyId;
 use ic_registry_client_fake::FakeRegistryClient;
 use ic_registry_keys::{
     make_catch_up_package_contents_key, make_crypto_threshold_signing_pubkey_key,
@@ -104,18 +102,17 @@ pub fn insert_initial_dkg_transcript(
         .iter()
         .flat_map(|config| config.key_configs.iter())
         .filter_map(|config| config.key_id.clone())
-        .filter_map(|key_id| match key_id.key_id {
-            Some(KeyId::Vetkd(ref vet_key_id)) => Some((
-                key_id.clone(),
+        .filter_map(|key_id| {
+            // Only keys that run on NiDkg (vetKD and BLS) need an initial transcript
+            let ni_dkg_key_id = NiDkgMasterPublicKeyId::try_from(key_id.clone()).ok()?;
+            Some((
+                key_id,
                 empty_ni_dkg_transcript_with_committee(
                     &committee,
                     version,
-                    NiDkgTag::HighThresholdForKey(NiDkgMasterPublicKeyId::VetKd(
-                        VetKdKeyId::try_from(vet_key_id.clone()).unwrap(),
-                    )),
+                    NiDkgTag::HighThresholdForKey(ni_dkg_key_id),
                 ),
-            )),
-            _ => None,
+            ))
         })
         .map(|(key_id, transcript)| ChainKeyInitialization {
             key_id: Some(key_id),
@@ -234,6 +231,7 @@ pub fn test_subnet_record() -> SubnetRecord {
         ssh_backup_access: vec![],
         chain_key_config: None,
         canister_cycles_cost_schedule: CanisterCyclesCostSchedule::Normal as i32,
+        adaptive_block_time_config: None,
     }
 }
 
@@ -347,6 +345,18 @@ impl SubnetRecordBuilder {
         self
     }
 
+    pub fn with_adaptive_block_time(
+        mut self,
+        min_notary_delay: Duration,
+        max_notary_delay: Duration,
+    ) -> Self {
+        self.record.adaptive_block_time_config = Some(AdaptiveBlockTimeConfig {
+            min_notary_delay_millis: min_notary_delay.as_millis() as u64,
+            max_notary_delay_millis: max_notary_delay.as_millis() as u64,
+        });
+        self
+    }
+
     pub fn build(self) -> SubnetRecord {
         self.record
     }
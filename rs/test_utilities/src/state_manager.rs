@@ -359,6 +359,7 @@ struct SerializableStreamHeader {
     signals_end: StreamIndex,
     reject_signals: VecDeque<SerializableRejectSignal>,
     flags: SerializableStreamFlags,
+    congestion_level: u8,
 }
 
 impl From<&StreamHeader> for SerializableStreamHeader {
@@ -369,6 +370,7 @@ impl From<&StreamHeader> for SerializableStreamHeader {
             signals_end: header.signals_end(),
             reject_signals: header.reject_signals().iter().map(From::from).collect(),
             flags: header.flags().into(),
+            congestion_level: header.congestion_level(),
         }
     }
 }
@@ -381,6 +383,7 @@ impl From<SerializableStreamHeader> for StreamHeader {
             header.signals_end,
             header.reject_signals.into_iter().map(From::from).collect(),
             header.flags.into(),
+            header.congestion_level,
         )
     }
 }
@@ -1007,6 +1007,7 @@ prop_compose! {
             StreamFlags {
                 deprecated_responses_only: responses_only,
             },
+            0,
         )
     }
 }
@@ -1024,11 +1025,12 @@ prop_compose! {
         let signals_end = valid_stream_header.signals_end();
         let mut reject_signals = valid_stream_header.reject_signals().clone();
         let flags = *valid_stream_header.flags();
+        let congestion_level = valid_stream_header.congestion_level();
 
         // `reject_signals` may not contain the `signals_end`.
         reject_signals.push_back(RejectSignal::new(reason, signals_end));
 
-        StreamHeader::new(begin, end, signals_end, reject_signals, flags)
+        StreamHeader::new(begin, end, signals_end, reject_signals, flags, congestion_level)
     }
 }
 
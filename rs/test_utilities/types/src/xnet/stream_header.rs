@@ -10,6 +10,7 @@ pub struct StreamHeaderBuilder {
     signals_end: StreamIndex,
     reject_signals: VecDeque<RejectSignal>,
     flags: StreamFlags,
+    congestion_level: u8,
 }
 
 impl StreamHeaderBuilder {
@@ -48,6 +49,12 @@ impl StreamHeaderBuilder {
         self
     }
 
+    /// Sets the `congestion_level` field.
+    pub fn congestion_level(mut self, congestion_level: u8) -> Self {
+        self.congestion_level = congestion_level;
+        self
+    }
+
     /// Returns the built `StreamHeader`.
     pub fn build(self) -> StreamHeader {
         StreamHeader::new(
@@ -56,6 +63,7 @@ impl StreamHeaderBuilder {
             self.signals_end,
             self.reject_signals,
             self.flags,
+            self.congestion_level,
         )
     }
 }
@@ -305,6 +305,9 @@ pub fn tecdsa_performance_test(
                 MasterPublicKeyId::VetKd(key_id) => {
                     ChainSignatureRequest::large_vetkd_method_and_payload(MSG_SIZE_BYTES, 0, key_id)
                 }
+                MasterPublicKeyId::Bls(_) => {
+                    panic!("The signer canister does not support BLS signatures yet")
+                }
             };
 
             requests.push(ChainSignatureRequest {
@@ -68,6 +68,7 @@ fn test(env: TestEnv) {
                 MasterPublicKeyId::Ecdsa(_) => "sign_with_ecdsa",
                 MasterPublicKeyId::Schnorr(_) => "sign_with_schnorr",
                 MasterPublicKeyId::VetKd(_) => "vetkd_derive_key",
+                MasterPublicKeyId::Bls(_) => "sign_with_bls",
             };
             let expected_reject = RejectResponse {
                 reject_code: RejectCode::CanisterReject,
@@ -53,6 +53,7 @@ fn public_key_method_name(key_id: &MasterPublicKeyId) -> &str {
         MasterPublicKeyId::Ecdsa(_) => "ecdsa_public_key",
         MasterPublicKeyId::Schnorr(_) => "schnorr_public_key",
         MasterPublicKeyId::VetKd(_) => "vetkd_public_key",
+        MasterPublicKeyId::Bls(_) => "bls_public_key",
     }
 }
 
@@ -61,6 +62,7 @@ fn protocol_method_name(key_id: &MasterPublicKeyId) -> &str {
         MasterPublicKeyId::Ecdsa(_) => "sign_with_ecdsa",
         MasterPublicKeyId::Schnorr(_) => "sign_with_schnorr",
         MasterPublicKeyId::VetKd(_) => "vetkd_derive_key",
+        MasterPublicKeyId::Bls(_) => "sign_with_bls",
     }
 }
 
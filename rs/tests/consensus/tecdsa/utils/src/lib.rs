@@ -7,12 +7,15 @@ use ic_canister_client::Sender;
 use ic_cdk::management_canister::{
     SignWithEcdsaResult, SignWithSchnorrResult, VetKDDeriveKeyResult,
 };
-use ic_config::subnet_config::{ECDSA_SIGNATURE_FEE, SCHNORR_SIGNATURE_FEE, VETKD_FEE};
+use ic_config::subnet_config::{
+    BLS_SIGNATURE_FEE, ECDSA_SIGNATURE_FEE, SCHNORR_SIGNATURE_FEE, VETKD_FEE,
+};
 use ic_limits::SMALL_APP_SUBNET_MAX_SIZE;
 use ic_management_canister_types_private::{
-    DerivationPath, ECDSAPublicKeyArgs, ECDSAPublicKeyResponse, EcdsaCurve, EcdsaKeyId,
-    MasterPublicKeyId, Payload, SchnorrAlgorithm, SchnorrKeyId, SchnorrPublicKeyArgs,
-    SchnorrPublicKeyResponse, SignWithECDSAArgs, SignWithECDSAReply, SignWithSchnorrArgs,
+    BlsCurve, BlsKeyId, BlsPublicKeyArgs, BlsPublicKeyResponse, DerivationPath, ECDSAPublicKeyArgs,
+    ECDSAPublicKeyResponse, EcdsaCurve, EcdsaKeyId, MasterPublicKeyId, Payload, SchnorrAlgorithm,
+    SchnorrKeyId, SchnorrPublicKeyArgs, SchnorrPublicKeyResponse, SignWithBlsArgs,
+    SignWithBlsReply, SignWithECDSAArgs, SignWithECDSAReply, SignWithSchnorrArgs,
     SignWithSchnorrAux, SignWithSchnorrReply, VetKdCurve, VetKdDeriveKeyArgs, VetKdDeriveKeyResult,
     VetKdKeyId, VetKdPublicKeyArgs, VetKdPublicKeyResult,
 };
@@ -97,6 +100,13 @@ pub fn make_vetkd_key_id() -> MasterPublicKeyId {
     })
 }
 
+pub fn make_bls_key_id() -> MasterPublicKeyId {
+    MasterPublicKeyId::Bls(BlsKeyId {
+        curve: BlsCurve::Bls12_381_G2,
+        name: "some_bls_key".to_string(),
+    })
+}
+
 pub fn make_key_ids_for_all_schemes() -> Vec<MasterPublicKeyId> {
     vec![
         make_ecdsa_key_id(),
@@ -219,6 +229,7 @@ pub fn empty_subnet_update() -> UpdateSubnetPayload {
         max_number_of_canisters: None,
         ssh_readonly_access: None,
         ssh_backup_access: None,
+        adaptive_block_time_config: None,
         // Deprecated/unused values follow
         max_artifact_streams_per_peer: None,
         max_chunk_wait_ms: None,
@@ -353,6 +364,9 @@ async fn get_public_key_with_retries_impl(
         MasterPublicKeyId::VetKd(key_id) => {
             get_vetkd_public_key_with_retries(canister_id, key_id, msg_can, logger, retries).await
         }
+        MasterPublicKeyId::Bls(key_id) => {
+            get_bls_public_key_with_retries(canister_id, key_id, msg_can, logger, retries).await
+        }
     }
 }
 
@@ -528,6 +542,61 @@ pub async fn get_vetkd_public_key_with_retries(
     Ok(public_key)
 }
 
+pub async fn get_bls_public_key_with_retries(
+    canister_id: Option<CanisterId>,
+    key_id: &BlsKeyId,
+    msg_can: &MessageCanister<'_>,
+    logger: &Logger,
+    retries: u64,
+) -> Result<Vec<u8>, AgentError> {
+    let public_key_request = BlsPublicKeyArgs {
+        canister_id,
+        derivation_path: DerivationPath::new(vec![]),
+        key_id: key_id.clone(),
+    };
+    info!(
+        logger,
+        "Sending a 'get bls public key' request: {:?}", public_key_request
+    );
+
+    let mut count = 0;
+    let public_key = loop {
+        // The BLS public key is computed by the subnet like a signature.
+        let res = msg_can
+            .forward_with_cycles_to(
+                &Principal::management_canister(),
+                "bls_public_key",
+                Encode!(&public_key_request).unwrap(),
+                BLS_SIGNATURE_FEE,
+            )
+            .await;
+        match res {
+            Ok(bytes) => {
+                let key = BlsPublicKeyResponse::decode(&bytes)
+                    .expect("failed to decode BlsPublicKeyResponse");
+                break key.public_key;
+            }
+            Err(err) => {
+                count += 1;
+                if count < retries {
+                    debug!(
+                        logger,
+                        "bls_public_key returns `{}`. Trying again in 2 seconds...", err
+                    );
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+    };
+    let _key =
+        blst::min_pk::PublicKey::key_validate(&public_key).expect("Failed to parse bls public key");
+
+    info!(logger, "bls_public_key returns {:?}", public_key);
+    Ok(public_key)
+}
+
 pub async fn get_public_key_with_logger(
     key_id: &MasterPublicKeyId,
     msg_can: &MessageCanister<'_>,
@@ -650,6 +719,9 @@ pub async fn get_signature_with_logger(
         MasterPublicKeyId::VetKd(key_id) => {
             get_vetkd_with_logger(message, vec![], cycles, key_id, msg_can, logger).await
         }
+        MasterPublicKeyId::Bls(key_id) => {
+            get_bls_signature_with_logger(message, cycles, key_id, msg_can, logger).await
+        }
     }
 }
 
@@ -817,6 +889,61 @@ pub async fn get_vetkd_with_logger(
     Ok(result)
 }
 
+pub async fn get_bls_signature_with_logger(
+    message: Vec<u8>,
+    cycles: Cycles,
+    key_id: &BlsKeyId,
+    msg_can: &MessageCanister<'_>,
+    logger: &Logger,
+) -> Result<Vec<u8>, AgentError> {
+    let signature_request = SignWithBlsArgs {
+        message,
+        derivation_path: DerivationPath::new(Vec::new()),
+        key_id: key_id.clone(),
+    };
+    info!(
+        logger,
+        "Sending a {} request of size: {}",
+        key_id,
+        signature_request.message.len(),
+    );
+
+    let mut count = 0;
+    let signature = loop {
+        let res = msg_can
+            .forward_with_cycles_to(
+                &Principal::management_canister(),
+                "sign_with_bls",
+                Encode!(&signature_request).unwrap(),
+                cycles,
+            )
+            .await;
+        match res {
+            Ok(reply) => {
+                let signature = SignWithBlsReply::decode(&reply)
+                    .expect("failed to decode SignWithBlsReply")
+                    .signature;
+                break signature;
+            }
+            Err(err) => {
+                count += 1;
+                if count < GET_SIGNATURE_RETRIES {
+                    debug!(
+                        logger,
+                        "sign_with_bls returns `{}`. Trying again in 2 seconds...", err
+                    );
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                } else {
+                    return Err(err);
+                }
+            }
+        }
+    };
+    info!(logger, "sign_with_bls returns {:?}", signature);
+
+    Ok(signature)
+}
+
 pub async fn generate_dummy_ecdsa_signature_with_logger(
     derivation_path_length: usize,
     derivation_path_element_size: usize,
@@ -1100,6 +1227,7 @@ pub async fn create_new_subnet_with_keys(
         max_number_of_canisters: 4,
         ssh_readonly_access: vec![],
         ssh_backup_access: vec![],
+        adaptive_block_time_config: None,
         chain_key_config: Some(chain_key_config),
         canister_cycles_cost_schedule: Some(CanisterCyclesCostSchedule::Normal),
 
@@ -1147,6 +1275,13 @@ pub fn verify_ecdsa_signature(pk: &[u8], sig: &[u8], msg: &[u8]) -> bool {
     pk.verify_prehash(msg, &signature).is_ok()
 }
 
+pub fn verify_ecdsa_secp256r1_signature(pk: &[u8], sig: &[u8], msg: &[u8]) -> bool {
+    use p256::ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+    let pk = VerifyingKey::from_sec1_bytes(pk).expect("Bytes are not a valid public key");
+    let signature = Signature::try_from(sig).expect("Bytes are not a valid signature");
+    pk.verify_prehash(msg, &signature).is_ok()
+}
+
 pub fn verify_vetkey(public_key: &[u8], encrypted_key: &[u8], input: &[u8]) -> bool {
     let dpk = DerivedPublicKey::deserialize(public_key).expect("Failed to deserialize public key");
 
@@ -1161,10 +1296,21 @@ pub fn verify_vetkey(public_key: &[u8], encrypted_key: &[u8], input: &[u8]) -> b
         .is_ok()
 }
 
+pub fn verify_bls_signature(public_key: &[u8], sig: &[u8], msg: &[u8]) -> bool {
+    const DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    let public_key = blst::min_pk::PublicKey::key_validate(public_key)
+        .expect("Failed to deserialize public key");
+    let Ok(signature) = blst::min_pk::Signature::sig_validate(sig, true) else {
+        return false;
+    };
+    signature.verify(true, msg, DST, &[], &public_key, true) == blst::BLST_ERROR::BLST_SUCCESS
+}
+
 pub fn verify_signature(key_id: &MasterPublicKeyId, msg: &[u8], pk: &[u8], sig: &[u8]) {
     let res = match key_id {
         MasterPublicKeyId::Ecdsa(key_id) => match key_id.curve {
             EcdsaCurve::Secp256k1 => verify_ecdsa_signature(pk, sig, msg),
+            EcdsaCurve::Secp256r1 => verify_ecdsa_secp256r1_signature(pk, sig, msg),
         },
         MasterPublicKeyId::Schnorr(key_id) => match key_id.algorithm {
             SchnorrAlgorithm::Bip340Secp256k1 => verify_bip340_signature(pk, sig, msg),
@@ -1173,6 +1319,9 @@ pub fn verify_signature(key_id: &MasterPublicKeyId, msg: &[u8], pk: &[u8], sig:
         MasterPublicKeyId::VetKd(key_id) => match key_id.curve {
             VetKdCurve::Bls12_381_G2 => verify_vetkey(pk, sig, msg),
         },
+        MasterPublicKeyId::Bls(key_id) => match key_id.curve {
+            BlsCurve::Bls12_381_G2 => verify_bls_signature(pk, sig, msg),
+        },
     };
     assert!(res);
 }
@@ -1182,12 +1331,16 @@ pub enum SignWithChainKeyReply {
     Ecdsa(SignWithECDSAReply),
     Schnorr(SignWithSchnorrReply),
     VetKd(VetKdDeriveKeyResult),
+    Bls(SignWithBlsReply),
 }
 
 fn cast_ecdsa_key_id(key_id: EcdsaKeyId) -> ic_cdk::management_canister::EcdsaKeyId {
     ic_cdk::management_canister::EcdsaKeyId {
         curve: match key_id.curve {
             EcdsaCurve::Secp256k1 => ic_cdk::management_canister::EcdsaCurve::Secp256k1,
+            EcdsaCurve::Secp256r1 => {
+                panic!("The signer canister does not support ECDSA over secp256r1 yet")
+            }
         },
         name: key_id.name,
     }
@@ -1244,6 +1397,7 @@ impl ChainSignatureRequest {
                 Self::schnorr_params(schnorr_key_id, schnorr_message_size)
             }
             MasterPublicKeyId::VetKd(vetkd_key_id) => Self::vetkd_params(vetkd_key_id),
+            MasterPublicKeyId::Bls(bls_key_id) => Self::bls_params(bls_key_id),
         };
         let payload = Encode!(&params).unwrap();
 
@@ -1299,6 +1453,20 @@ impl ChainSignatureRequest {
         }
     }
 
+    fn bls_params(bls_key_id: BlsKeyId) -> ForwardParams {
+        let signature_request = SignWithBlsArgs {
+            message: vec![1; 32],
+            derivation_path: DerivationPath::new(Vec::new()),
+            key_id: bls_key_id,
+        };
+        ForwardParams {
+            receiver: Principal::management_canister(),
+            method: "sign_with_bls".to_string(),
+            cycles: BLS_SIGNATURE_FEE.get() * 2,
+            payload: Encode!(&signature_request).unwrap(),
+        }
+    }
+
     pub fn large_ecdsa_method_and_payload(
         derivation_path_length: usize,
         derivation_path_element_size: usize,
@@ -1374,6 +1542,9 @@ impl Request<SignWithChainKeyReply> for ChainSignatureRequest {
             MasterPublicKeyId::VetKd(_) => {
                 SignWithChainKeyReply::VetKd(VetKdDeriveKeyResult::decode(raw_response)?)
             }
+            MasterPublicKeyId::Bls(_) => {
+                SignWithChainKeyReply::Bls(SignWithBlsReply::decode(raw_response)?)
+            }
         })
     }
 }
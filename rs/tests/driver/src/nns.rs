@@ -732,6 +732,7 @@ pub async fn submit_create_application_subnet_proposal(
         max_number_of_canisters: 4,
         ssh_readonly_access: vec![],
         ssh_backup_access: vec![],
+        adaptive_block_time_config: None,
         chain_key_config: None,
         canister_cycles_cost_schedule: cost_schedule,
 
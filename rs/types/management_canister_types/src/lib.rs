@@ -98,6 +98,10 @@ pub enum Method {
     #[strum(serialize = "vetkd_derive_key")]
     VetKdDeriveKey,
 
+    // Threshold BLS interface.
+    BlsPublicKey,
+    SignWithBls,
+
     // Bitcoin Interface.
     BitcoinGetBalance,
     BitcoinGetUtxos,
@@ -1236,6 +1240,7 @@ impl TryFrom<pb_canister_state_bits::LogVisibilityV2> for LogVisibilityV2 {
 ///   wasm_memory_limit : nat;
 ///   wasm_memory_threshold : nat;
 ///   environment_variables : vec environment_variable;
+///   latency_sensitive_methods : vec text;
 /// }
 /// ```
 #[derive(Clone, Eq, PartialEq, Debug, CandidType, Deserialize)]
@@ -1251,6 +1256,7 @@ pub struct DefiniteCanisterSettingsArgs {
     wasm_memory_limit: candid::Nat,
     wasm_memory_threshold: candid::Nat,
     environment_variables: Vec<EnvironmentVariable>,
+    latency_sensitive_methods: Vec<String>,
 }
 
 impl DefiniteCanisterSettingsArgs {
@@ -1266,6 +1272,7 @@ impl DefiniteCanisterSettingsArgs {
         wasm_memory_limit: Option<u64>,
         wasm_memory_threshold: u64,
         environment_variables: EnvironmentVariables,
+        latency_sensitive_methods: Vec<String>,
     ) -> Self {
         let memory_allocation = candid::Nat::from(memory_allocation.unwrap_or(0));
         let reserved_cycles_limit = candid::Nat::from(reserved_cycles_limit.unwrap_or(0));
@@ -1289,6 +1296,7 @@ impl DefiniteCanisterSettingsArgs {
             wasm_memory_limit,
             wasm_memory_threshold: candid::Nat::from(wasm_memory_threshold),
             environment_variables,
+            latency_sensitive_methods,
         }
     }
 
@@ -1331,6 +1339,10 @@ impl DefiniteCanisterSettingsArgs {
     pub fn environment_variables(&self) -> &[EnvironmentVariable] {
         &self.environment_variables
     }
+
+    pub fn latency_sensitive_methods(&self) -> &[String] {
+        &self.latency_sensitive_methods
+    }
 }
 
 impl Payload<'_> for DefiniteCanisterSettingsArgs {}
@@ -1441,6 +1453,7 @@ impl CanisterStatusResultV2 {
         wasm_memory_limit: Option<u64>,
         wasm_memory_threshold: u64,
         environment_variables: EnvironmentVariables,
+        latency_sensitive_methods: Vec<String>,
     ) -> Self {
         Self {
             status,
@@ -1475,6 +1488,7 @@ impl CanisterStatusResultV2 {
                 wasm_memory_limit,
                 wasm_memory_threshold,
                 environment_variables,
+                latency_sensitive_methods,
             ),
             freezing_threshold: candid::Nat::from(freezing_threshold),
             idle_cycles_burned_per_day: candid::Nat::from(idle_cycles_burned_per_day),
@@ -2193,6 +2207,7 @@ pub struct EnvironmentVariable {
 ///   wasm_memory_limit : opt nat;
 ///   wasm_memory_threshold : opt nat;
 ///   environment_variables : opt vec environment_variable;
+///   latency_sensitive_methods : opt vec text;
 /// }
 /// ```
 #[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
@@ -2207,6 +2222,7 @@ pub struct CanisterSettingsArgs {
     pub wasm_memory_limit: Option<candid::Nat>,
     pub wasm_memory_threshold: Option<candid::Nat>,
     pub environment_variables: Option<Vec<EnvironmentVariable>>,
+    pub latency_sensitive_methods: Option<Vec<String>>,
 }
 
 impl Payload<'_> for CanisterSettingsArgs {}
@@ -2226,6 +2242,7 @@ impl CanisterSettingsArgs {
             wasm_memory_limit: None,
             wasm_memory_threshold: None,
             environment_variables: None,
+            latency_sensitive_methods: None,
         }
     }
 }
@@ -2242,6 +2259,7 @@ pub struct CanisterSettingsArgsBuilder {
     wasm_memory_limit: Option<candid::Nat>,
     wasm_memory_threshold: Option<candid::Nat>,
     environment_variables: Option<Vec<EnvironmentVariable>>,
+    latency_sensitive_methods: Option<Vec<String>>,
 }
 
 #[allow(dead_code)]
@@ -2262,6 +2280,7 @@ impl CanisterSettingsArgsBuilder {
             wasm_memory_limit: self.wasm_memory_limit,
             wasm_memory_threshold: self.wasm_memory_threshold,
             environment_variables: self.environment_variables,
+            latency_sensitive_methods: self.latency_sensitive_methods,
         }
     }
 
@@ -2376,6 +2395,15 @@ impl CanisterSettingsArgsBuilder {
             ..self
         }
     }
+
+    /// Sets the names of the methods whose messages the scheduler should
+    /// prefer when building the round schedule.
+    pub fn with_latency_sensitive_methods(self, latency_sensitive_methods: Vec<String>) -> Self {
+        Self {
+            latency_sensitive_methods: Some(latency_sensitive_methods),
+            ..self
+        }
+    }
 }
 
 /// Struct used for encoding/decoding
@@ -2521,7 +2549,7 @@ impl SetupInitialDKGResponse {
 
 /// Types of curves that can be used for ECDSA signing.
 /// ```text
-/// variant { secp256k1; }
+/// variant { secp256k1; secp256r1; }
 /// ```
 #[derive(
     Copy,
@@ -2540,6 +2568,8 @@ impl SetupInitialDKGResponse {
 pub enum EcdsaCurve {
     #[serde(rename = "secp256k1")]
     Secp256k1,
+    #[serde(rename = "secp256r1")]
+    Secp256r1,
 }
 
 impl TryFrom<u32> for EcdsaCurve {
@@ -2548,6 +2578,7 @@ impl TryFrom<u32> for EcdsaCurve {
     fn try_from(value: u32) -> Result<Self, Self::Error> {
         match value {
             0 => Ok(EcdsaCurve::Secp256k1),
+            1 => Ok(EcdsaCurve::Secp256r1),
             _ => Err(format!(
                 "{value} is not a recognized EcdsaCurve variant identifier."
             )),
@@ -2559,6 +2590,7 @@ impl From<&EcdsaCurve> for pb_types::EcdsaCurve {
     fn from(item: &EcdsaCurve) -> Self {
         match item {
             EcdsaCurve::Secp256k1 => pb_types::EcdsaCurve::Secp256k1,
+            EcdsaCurve::Secp256r1 => pb_types::EcdsaCurve::Secp256r1,
         }
     }
 }
@@ -2569,6 +2601,7 @@ impl TryFrom<pb_types::EcdsaCurve> for EcdsaCurve {
     fn try_from(item: pb_types::EcdsaCurve) -> Result<Self, Self::Error> {
         match item {
             pb_types::EcdsaCurve::Secp256k1 => Ok(EcdsaCurve::Secp256k1),
+            pb_types::EcdsaCurve::Secp256r1 => Ok(EcdsaCurve::Secp256r1),
             pb_types::EcdsaCurve::Unspecified => Err(ProxyDecodeError::ValueOutOfRange {
                 typ: "EcdsaCurve",
                 err: format!("Unable to convert {item:?} to an EcdsaCurve"),
@@ -2589,6 +2622,7 @@ impl FromStr for EcdsaCurve {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
             "secp256k1" => Ok(Self::Secp256k1),
+            "secp256r1" => Ok(Self::Secp256r1),
             _ => Err(format!("{s} is not a recognized ECDSA curve")),
         }
     }
@@ -2923,10 +2957,144 @@ impl FromStr for VetKdKeyId {
     }
 }
 
+/// Types of curves that can be used for threshold BLS signatures.
+/// ```text
+/// variant { bls12_381_g2; }
+/// ```
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Debug,
+    CandidType,
+    Deserialize,
+    EnumIter,
+    Serialize,
+)]
+pub enum BlsCurve {
+    #[serde(rename = "bls12_381_g2")]
+    #[allow(non_camel_case_types)]
+    Bls12_381_G2,
+}
+
+impl TryFrom<u32> for BlsCurve {
+    type Error = String;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(BlsCurve::Bls12_381_G2),
+            _ => Err(format!(
+                "{value} is not a recognized BlsCurve variant identifier."
+            )),
+        }
+    }
+}
+
+impl From<&BlsCurve> for pb_types::BlsCurve {
+    fn from(item: &BlsCurve) -> Self {
+        match item {
+            BlsCurve::Bls12_381_G2 => pb_types::BlsCurve::Bls12381G2,
+        }
+    }
+}
+
+impl TryFrom<pb_types::BlsCurve> for BlsCurve {
+    type Error = ProxyDecodeError;
+
+    fn try_from(item: pb_types::BlsCurve) -> Result<Self, Self::Error> {
+        match item {
+            pb_types::BlsCurve::Bls12381G2 => Ok(BlsCurve::Bls12_381_G2),
+            pb_types::BlsCurve::Unspecified => Err(ProxyDecodeError::ValueOutOfRange {
+                typ: "BlsCurve",
+                err: format!("Unable to convert {item:?} to a BlsCurve"),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Display for BlsCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+impl FromStr for BlsCurve {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bls12_381_g2" => Ok(Self::Bls12_381_G2),
+            _ => Err(format!("{s} is not a recognized BLS curve")),
+        }
+    }
+}
+
+/// Unique identifier for a key that can be used for threshold BLS signatures.
+/// The name is just an identifier, but it may be used to convey some
+/// information about the key (e.g. that the key is meant to be used for
+/// testing purposes).
+/// ```text
+/// record { curve : bls_curve; name : text}
+/// ```
+#[derive(
+    Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, CandidType, Deserialize, Serialize,
+)]
+pub struct BlsKeyId {
+    pub curve: BlsCurve,
+    pub name: String,
+}
+
+impl From<&BlsKeyId> for pb_types::BlsKeyId {
+    fn from(item: &BlsKeyId) -> Self {
+        Self {
+            curve: pb_types::BlsCurve::from(&item.curve) as i32,
+            name: item.name.clone(),
+        }
+    }
+}
+
+impl TryFrom<pb_types::BlsKeyId> for BlsKeyId {
+    type Error = ProxyDecodeError;
+    fn try_from(item: pb_types::BlsKeyId) -> Result<Self, Self::Error> {
+        Ok(Self {
+            curve: BlsCurve::try_from(pb_types::BlsCurve::try_from(item.curve).map_err(|_| {
+                ProxyDecodeError::ValueOutOfRange {
+                    typ: "BlsKeyId",
+                    err: format!("Unable to convert {} to a BlsCurve", item.curve),
+                }
+            })?)?,
+            name: item.name,
+        })
+    }
+}
+
+impl std::fmt::Display for BlsKeyId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.curve, self.name)
+    }
+}
+
+impl FromStr for BlsKeyId {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (curve, name) = s
+            .split_once(':')
+            .ok_or_else(|| format!("BLS key id {s} does not contain a ':'"))?;
+        Ok(BlsKeyId {
+            curve: curve.parse::<BlsCurve>()?,
+            name: name.to_string(),
+        })
+    }
+}
+
 /// Unique identifier for a key that can be used for one of the signature schemes
 /// supported on the IC.
 /// ```text
-/// variant { Ecdsa : ecdsa_key_id; Schnorr : schnorr_key_id; VetKd : vetkd_key_id }
+/// variant { Ecdsa : ecdsa_key_id; Schnorr : schnorr_key_id; VetKd : vetkd_key_id; Bls : bls_key_id }
 /// ```
 #[derive(
     Clone,
@@ -2945,6 +3113,7 @@ pub enum MasterPublicKeyId {
     Ecdsa(EcdsaKeyId),
     Schnorr(SchnorrKeyId),
     VetKd(VetKdKeyId),
+    Bls(BlsKeyId),
 }
 
 impl From<&MasterPublicKeyId> for pb_types::MasterPublicKeyId {
@@ -2954,6 +3123,7 @@ impl From<&MasterPublicKeyId> for pb_types::MasterPublicKeyId {
             MasterPublicKeyId::Schnorr(schnorr_key_id) => KeyId::Schnorr(schnorr_key_id.into()),
             MasterPublicKeyId::Ecdsa(ecdsa_key_id) => KeyId::Ecdsa(ecdsa_key_id.into()),
             MasterPublicKeyId::VetKd(vetkd_key_id) => KeyId::Vetkd(vetkd_key_id.into()),
+            MasterPublicKeyId::Bls(bls_key_id) => KeyId::Bls(bls_key_id.into()),
         };
         Self {
             key_id: Some(key_id_pb),
@@ -2974,6 +3144,7 @@ impl TryFrom<pb_types::MasterPublicKeyId> for MasterPublicKeyId {
             }
             KeyId::Ecdsa(ecdsa_key_id) => MasterPublicKeyId::Ecdsa(ecdsa_key_id.try_into()?),
             KeyId::Vetkd(vetkd_key_id) => MasterPublicKeyId::VetKd(vetkd_key_id.try_into()?),
+            KeyId::Bls(bls_key_id) => MasterPublicKeyId::Bls(bls_key_id.try_into()?),
         };
         Ok(master_public_key_id)
     }
@@ -2994,6 +3165,10 @@ impl std::fmt::Display for MasterPublicKeyId {
                 write!(f, "vetkd:")?;
                 vetkd_key_id.fmt(f)
             }
+            Self::Bls(bls_key_id) => {
+                write!(f, "bls:")?;
+                bls_key_id.fmt(f)
+            }
         }
     }
 }
@@ -3003,7 +3178,15 @@ impl MasterPublicKeyId {
     pub fn is_idkg_key(&self) -> bool {
         match self {
             Self::Ecdsa(_) | Self::Schnorr(_) => true,
-            Self::VetKd(_) => false,
+            Self::VetKd(_) | Self::Bls(_) => false,
+        }
+    }
+
+    /// Check whether this type of [`MasterPublicKeyId`] requires to run on the NiDkg protocol
+    pub fn is_nidkg_key(&self) -> bool {
+        match self {
+            Self::VetKd(_) | Self::Bls(_) => true,
+            Self::Ecdsa(_) | Self::Schnorr(_) => false,
         }
     }
 
@@ -3016,7 +3199,7 @@ impl MasterPublicKeyId {
     pub fn requires_pre_signatures(&self) -> bool {
         match self {
             Self::Ecdsa(_) | Self::Schnorr(_) => true,
-            Self::VetKd(_) => false,
+            Self::VetKd(_) | Self::Bls(_) => false,
         }
     }
 }
@@ -3031,6 +3214,7 @@ impl FromStr for MasterPublicKeyId {
             "ecdsa" => Ok(Self::Ecdsa(EcdsaKeyId::from_str(key_id)?)),
             "schnorr" => Ok(Self::Schnorr(SchnorrKeyId::from_str(key_id)?)),
             "vetkd" => Ok(Self::VetKd(VetKdKeyId::from_str(key_id)?)),
+            "bls" => Ok(Self::Bls(BlsKeyId::from_str(key_id)?)),
             _ => Err(format!(
                 "Scheme {scheme} in master public key id {s} is not supported."
             )),
@@ -3357,6 +3541,79 @@ pub struct VetKdPublicKeyResult {
 
 impl Payload<'_> for VetKdPublicKeyResult {}
 
+/// Represents the argument of the sign_with_bls API.
+///
+/// The returned signature is a 96-byte compressed G2 point that verifies under
+/// the returned 48-byte compressed G1 public key with the
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_` ciphersuite, i.e. the
+/// minimal-public-key-size variant used by the Ethereum beacon chain.
+/// ```text
+/// record {
+///   message : blob;
+///   derivation_path : vec blob;
+///   key_id : bls_key_id;
+/// }
+/// ```
+#[derive(Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct SignWithBlsArgs {
+    #[serde(with = "serde_bytes")]
+    pub message: Vec<u8>,
+    pub derivation_path: DerivationPath,
+    pub key_id: BlsKeyId,
+}
+
+impl Payload<'_> for SignWithBlsArgs {}
+
+/// Struct used to return a BLS signature together with the public key it
+/// verifies under, which is the one returned by `bls_public_key`.
+/// ```text
+/// record {
+///   signature : blob;
+///   public_key : blob;
+/// }
+/// ```
+#[derive(Debug, CandidType, Deserialize)]
+pub struct SignWithBlsReply {
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+}
+
+impl Payload<'_> for SignWithBlsReply {}
+
+/// Represents the argument of the bls_public_key API.
+/// ```text
+/// record {
+///   canister_id : opt canister_id;
+///   derivation_path : vec blob;
+///   key_id : bls_key_id;
+/// }
+/// ```
+#[derive(Eq, PartialEq, Debug, CandidType, Deserialize)]
+pub struct BlsPublicKeyArgs {
+    pub canister_id: Option<CanisterId>,
+    pub derivation_path: DerivationPath,
+    pub key_id: BlsKeyId,
+}
+
+impl Payload<'_> for BlsPublicKeyArgs {}
+
+/// Represents the response of the bls_public_key API, a 48-byte compressed G1
+/// point.
+/// ```text
+/// record {
+///   public_key : blob;
+/// }
+/// ```
+#[derive(Debug, CandidType, Deserialize)]
+pub struct BlsPublicKeyResponse {
+    #[serde(with = "serde_bytes")]
+    pub public_key: Vec<u8>,
+}
+
+impl Payload<'_> for BlsPublicKeyResponse {}
+
 // Export the bitcoin types.
 pub use ic_btc_interface::{
     GetBalanceRequest as BitcoinGetBalanceArgs,
@@ -3879,12 +4136,16 @@ impl Payload<'_> for StoredChunksReply {}
 /// record {
 ///   canister_id : principal;
 ///   replace_snapshot : opt blob;
+///   parent_snapshot : opt blob;
 /// }
 /// ```
 #[derive(Clone, Eq, PartialEq, Debug, Default, CandidType, Deserialize)]
 pub struct TakeCanisterSnapshotArgs {
     pub canister_id: PrincipalId,
     pub replace_snapshot: Option<SnapshotId>,
+    /// If set, an incremental snapshot is taken that only stores the memory
+    /// pages that differ from the given snapshot.
+    pub parent_snapshot: Option<SnapshotId>,
 }
 
 impl TakeCanisterSnapshotArgs {
@@ -3892,6 +4153,14 @@ impl TakeCanisterSnapshotArgs {
         Self {
             canister_id: canister_id.get(),
             replace_snapshot,
+            parent_snapshot: None,
+        }
+    }
+
+    pub fn with_parent_snapshot(self, parent_snapshot: SnapshotId) -> Self {
+        Self {
+            parent_snapshot: Some(parent_snapshot),
+            ..self
         }
     }
 
@@ -3902,6 +4171,10 @@ impl TakeCanisterSnapshotArgs {
     pub fn replace_snapshot(&self) -> Option<SnapshotId> {
         self.replace_snapshot
     }
+
+    pub fn parent_snapshot(&self) -> Option<SnapshotId> {
+        self.parent_snapshot
+    }
 }
 impl Payload<'_> for TakeCanisterSnapshotArgs {}
 
@@ -4722,6 +4995,7 @@ mod tests {
         for curve in EcdsaCurve::iter() {
             match curve {
                 EcdsaCurve::Secp256k1 => assert_eq!(EcdsaCurve::try_from(0).unwrap(), curve),
+                EcdsaCurve::Secp256r1 => assert_eq!(EcdsaCurve::try_from(1).unwrap(), curve),
             }
         }
     }
@@ -4753,6 +5027,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bls_from_u32_exhaustive() {
+        // If this test fails, make sure this trait impl covers all variants:
+        // `impl TryFrom<u32> for BlsCurve`
+        for curve in BlsCurve::iter() {
+            match curve {
+                BlsCurve::Bls12_381_G2 => assert_eq!(BlsCurve::try_from(0).unwrap(), curve),
+            }
+        }
+    }
+
     #[test]
     fn canister_install_mode_round_trip() {
         fn canister_install_mode_round_trip_aux(mode: CanisterInstallMode) {
@@ -4960,6 +5245,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn bls_curve_round_trip() {
+        for curve in BlsCurve::iter() {
+            assert_eq!(format!("{curve}").parse::<BlsCurve>().unwrap(), curve);
+        }
+    }
+
+    #[test]
+    fn bls_key_id_round_trip() {
+        for curve in BlsCurve::iter() {
+            for name in ["bls12_381_g2", "", "other_key", "other key", "other:key"] {
+                let key = BlsKeyId {
+                    curve,
+                    name: name.to_string(),
+                };
+                assert_eq!(format!("{key}").parse::<BlsKeyId>().unwrap(), key);
+            }
+        }
+    }
+
     #[test]
     fn master_public_key_id_round_trip() {
         for algorithm in SchnorrAlgorithm::iter() {
@@ -4991,6 +5296,16 @@ mod tests {
                 assert_eq!(format!("{key}").parse::<MasterPublicKeyId>().unwrap(), key);
             }
         }
+
+        for curve in BlsCurve::iter() {
+            for name in ["bls12_381_g2", "", "other_key", "other key", "other:key"] {
+                let key = MasterPublicKeyId::Bls(BlsKeyId {
+                    curve,
+                    name: name.to_string(),
+                });
+                assert_eq!(format!("{key}").parse::<MasterPublicKeyId>().unwrap(), key);
+            }
+        }
     }
 
     #[test]
@@ -27,6 +27,8 @@ type SignWithEcdsaArgs = SignWithECDSAArgs;
 type SignWithEcdsaResult = SignWithECDSAReply;
 type SchnorrPublicKeyResult = SchnorrPublicKeyResponse;
 type SignWithSchnorrResult = SignWithSchnorrReply;
+type BlsPublicKeyResult = BlsPublicKeyResponse;
+type SignWithBlsResult = SignWithBlsReply;
 type NodeMetricsHistoryResult = Vec<NodeMetricsHistoryResponse>;
 type ProvisionalCreateCanisterWithCyclesResult = CanisterIdRecord;
 type TakeCanisterSnapshotResult = CanisterSnapshotResponse;
@@ -145,6 +147,16 @@ fn sign_with_schnorr(_: SignWithSchnorrArgs) -> SignWithSchnorrResult {
     unreachable!()
 }
 
+#[candid_method(update)]
+fn bls_public_key(_: BlsPublicKeyArgs) -> BlsPublicKeyResult {
+    unreachable!()
+}
+
+#[candid_method(update)]
+fn sign_with_bls(_: SignWithBlsArgs) -> SignWithBlsResult {
+    unreachable!()
+}
+
 #[candid_method(update)]
 fn node_metrics_history(_: NodeMetricsHistoryArgs) -> NodeMetricsHistoryResult {
     unreachable!()
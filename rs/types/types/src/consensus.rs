@@ -32,6 +32,7 @@ pub mod hashed;
 pub mod idkg;
 mod payload;
 pub mod thunk;
+pub mod trace;
 
 pub use catchup::*;
 use hashed::Hashed;
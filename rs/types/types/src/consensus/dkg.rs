@@ -643,7 +643,7 @@ mod tests {
 
     use super::*;
     use crate::crypto::threshold_sig::ni_dkg::NiDkgMasterPublicKeyId;
-    use ic_management_canister_types_private::{VetKdCurve, VetKdKeyId};
+    use ic_management_canister_types_private::{BlsCurve, BlsKeyId, VetKdCurve, VetKdKeyId};
     use strum::EnumCount;
     use strum::IntoEnumIterator;
 
@@ -676,12 +676,17 @@ mod tests {
     }
 
     #[test]
-    #[allow(clippy::single_element_loop)]
     fn should_correctly_calculate_threshold_for_ni_dkg_tag_high_threshold_for_key() {
-        for ni_dkg_master_public_key_id in [NiDkgMasterPublicKeyId::VetKd(VetKdKeyId {
-            curve: VetKdCurve::Bls12_381_G2,
-            name: "some key".to_string(),
-        })] {
+        for ni_dkg_master_public_key_id in [
+            NiDkgMasterPublicKeyId::VetKd(VetKdKeyId {
+                curve: VetKdCurve::Bls12_381_G2,
+                name: "some key".to_string(),
+            }),
+            NiDkgMasterPublicKeyId::Bls(BlsKeyId {
+                curve: BlsCurve::Bls12_381_G2,
+                name: "some key".to_string(),
+            }),
+        ] {
             let tag = NiDkgTag::HighThresholdForKey(ni_dkg_master_public_key_id);
 
             assert_eq!(tag.threshold_for_subnet_of_size(0), 1);
@@ -694,8 +699,9 @@ mod tests {
             assert_eq!(tag.threshold_for_subnet_of_size(28), 19);
             assert_eq!(tag.threshold_for_subnet_of_size(64), 43);
         }
-        assert_eq!(NiDkgMasterPublicKeyId::COUNT, 1);
+        assert_eq!(NiDkgMasterPublicKeyId::COUNT, 2);
         assert_eq!(VetKdCurve::iter().count(), 1);
+        assert_eq!(BlsCurve::iter().count(), 1);
     }
 
     #[test]
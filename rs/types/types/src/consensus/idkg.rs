@@ -115,7 +115,9 @@ impl IDkgMasterPublicKeyId {
             MasterPublicKeyId::Ecdsa(_) => 2,
             // Schnorr pre-signatures consist of only 1 transcript
             MasterPublicKeyId::Schnorr(_) => 1,
-            MasterPublicKeyId::VetKd(_) => unreachable!("not an IDkg Key"),
+            MasterPublicKeyId::VetKd(_) | MasterPublicKeyId::Bls(_) => {
+                unreachable!("not an IDkg Key")
+            }
         }
     }
 }
@@ -0,0 +1,60 @@
+//! Types describing the per-height trace of consensus events that a replica
+//! records for offline analysis.
+//!
+//! Traces of all nodes of a subnet can be merged into a single timeline, which
+//! makes it possible to tell which nodes were slow to propose, notarize or
+//! finalize a given height.
+use crate::{Height, NodeId, Time};
+use serde::{Deserialize, Serialize};
+
+/// A consensus event observed or produced by a replica.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ConsensusTraceEvent {
+    /// This replica proposed a block.
+    BlockProposed { block_hash: String, rank: u64 },
+    /// A block proposal by another replica was validated.
+    BlockProposalValidated {
+        block_hash: String,
+        rank: u64,
+        block_maker: NodeId,
+    },
+    /// This replica created a notarization share.
+    NotarizationShareSent { block_hash: String },
+    /// A notarization share by another replica was validated.
+    NotarizationShareReceived { block_hash: String, signer: NodeId },
+    /// A block became notarized.
+    Notarized { block_hash: String },
+    /// This replica created a finalization share.
+    FinalizationShareSent { block_hash: String },
+    /// A block became finalized.
+    Finalized { block_hash: String },
+    /// The finalized block was delivered as a batch to message routing.
+    BatchDelivered,
+}
+
+/// A [`ConsensusTraceEvent`] along with the time at which it was recorded.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TracedConsensusEvent {
+    pub time: Time,
+    #[serde(flatten)]
+    pub event: ConsensusTraceEvent,
+}
+
+/// All consensus events a replica recorded at a given height.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct HeightTrace {
+    pub height: Height,
+    pub events: Vec<TracedConsensusEvent>,
+}
+
+/// The traces exported by a single replica.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ConsensusTraceExport {
+    pub node_id: NodeId,
+    pub heights: Vec<HeightTrace>,
+    /// If the export was cut short by the size limit, the height from which
+    /// to request the remaining traces.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_height: Option<Height>,
+}
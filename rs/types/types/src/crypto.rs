@@ -16,7 +16,7 @@ mod sign;
 use ic_management_canister_types_private::EcdsaCurve;
 use ic_management_canister_types_private::MasterPublicKeyId;
 use ic_management_canister_types_private::SchnorrAlgorithm;
-use ic_management_canister_types_private::VetKdCurve;
+use ic_management_canister_types_private::{BlsCurve, VetKdCurve};
 pub use sign::{Signable, SignableMock};
 
 pub mod error;
@@ -184,6 +184,7 @@ pub enum AlgorithmId {
     ThresholdSchnorrBip340 = 18,
     ThresholdEd25519 = 19,
     VetKD = 20, // Verifiably Encrypted Threshold Key Derivation
+    MlDsa65 = 21,
 }
 
 impl AlgorithmId {
@@ -262,6 +263,7 @@ impl From<i32> for AlgorithmId {
             18 => AlgorithmId::ThresholdSchnorrBip340,
             19 => AlgorithmId::ThresholdEd25519,
             20 => AlgorithmId::VetKD,
+            21 => AlgorithmId::MlDsa65,
             _ => AlgorithmId::Placeholder,
         }
     }
@@ -271,6 +273,7 @@ impl From<EcdsaCurve> for AlgorithmId {
     fn from(curve: EcdsaCurve) -> Self {
         match curve {
             EcdsaCurve::Secp256k1 => AlgorithmId::ThresholdEcdsaSecp256k1,
+            EcdsaCurve::Secp256r1 => AlgorithmId::ThresholdEcdsaSecp256r1,
         }
     }
 }
@@ -292,12 +295,21 @@ impl From<VetKdCurve> for AlgorithmId {
     }
 }
 
+impl From<BlsCurve> for AlgorithmId {
+    fn from(curve: BlsCurve) -> Self {
+        match curve {
+            BlsCurve::Bls12_381_G2 => AlgorithmId::ThresBls12_381,
+        }
+    }
+}
+
 impl From<&MasterPublicKeyId> for AlgorithmId {
     fn from(key_id: &MasterPublicKeyId) -> Self {
         match key_id {
             MasterPublicKeyId::Ecdsa(ecdsa) => AlgorithmId::from(ecdsa.curve),
             MasterPublicKeyId::Schnorr(schnorr) => AlgorithmId::from(schnorr.algorithm),
             MasterPublicKeyId::VetKd(vetkd) => AlgorithmId::from(vetkd.curve),
+            MasterPublicKeyId::Bls(bls) => AlgorithmId::from(bls.curve),
         }
     }
 }
@@ -9,7 +9,7 @@ use core::fmt;
 use ic_crypto_internal_types::sign::threshold_sig::ni_dkg::{CspNiDkgDealing, CspNiDkgTranscript};
 #[cfg(test)]
 use ic_exhaustive_derive::ExhaustiveSet;
-use ic_management_canister_types_private::{MasterPublicKeyId, VetKdKeyId};
+use ic_management_canister_types_private::{BlsKeyId, MasterPublicKeyId, VetKdKeyId};
 use ic_protobuf::proxy::ProxyDecodeError;
 use ic_protobuf::types::v1 as pb;
 use ic_protobuf::types::v1::NiDkgId as NiDkgIdProto;
@@ -35,12 +35,14 @@ mod tests;
 #[cfg_attr(test, derive(ExhaustiveSet))]
 pub enum NiDkgMasterPublicKeyId {
     VetKd(VetKdKeyId),
+    Bls(BlsKeyId),
 }
 
 impl From<NiDkgMasterPublicKeyId> for MasterPublicKeyId {
     fn from(val: NiDkgMasterPublicKeyId) -> Self {
         match val {
             NiDkgMasterPublicKeyId::VetKd(k) => MasterPublicKeyId::VetKd(k),
+            NiDkgMasterPublicKeyId::Bls(k) => MasterPublicKeyId::Bls(k),
         }
     }
 }
@@ -53,6 +55,7 @@ impl TryFrom<MasterPublicKeyId> for NiDkgMasterPublicKeyId {
             MasterPublicKeyId::VetKd(vet_kd_key_id) => {
                 Ok(NiDkgMasterPublicKeyId::VetKd(vet_kd_key_id))
             }
+            MasterPublicKeyId::Bls(bls_key_id) => Ok(NiDkgMasterPublicKeyId::Bls(bls_key_id)),
             MasterPublicKeyId::Ecdsa(_) | MasterPublicKeyId::Schnorr(_) => {
                 Err("This is not a NiDkg key")
             }
@@ -67,6 +70,9 @@ impl From<&NiDkgMasterPublicKeyId> for pb::MasterPublicKeyId {
                 NiDkgMasterPublicKeyId::VetKd(vetkd_key_id) => {
                     pb::master_public_key_id::KeyId::Vetkd(pb::VetKdKeyId::from(vetkd_key_id))
                 }
+                NiDkgMasterPublicKeyId::Bls(bls_key_id) => {
+                    pb::master_public_key_id::KeyId::Bls(pb::BlsKeyId::from(bls_key_id))
+                }
             }),
         }
     }
@@ -83,6 +89,9 @@ impl TryFrom<pb::MasterPublicKeyId> for NiDkgMasterPublicKeyId {
             KeyId::Vetkd(vetkd_key_id_pb) => {
                 NiDkgMasterPublicKeyId::VetKd(VetKdKeyId::try_from(vetkd_key_id_pb)?)
             }
+            KeyId::Bls(bls_key_id_pb) => {
+                NiDkgMasterPublicKeyId::Bls(BlsKeyId::try_from(bls_key_id_pb)?)
+            }
             KeyId::Ecdsa(_) | KeyId::Schnorr(_) => {
                 return Err(ProxyDecodeError::ValueOutOfRange {
                     typ: "NiDkgMasterPublicKeyId",
@@ -11,6 +11,31 @@ use std::fmt;
 #[cfg(test)]
 mod test;
 
+/// Size of a compressed BLS12-381 G1 point in bytes.
+const G1_BYTES: usize = 48;
+
+/// Size of a compressed BLS12-381 G2 point in bytes.
+const G2_BYTES: usize = 96;
+
+/// What running the vetKD protocol produces.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
+pub enum VetKdOutput {
+    /// The vetKey derived for the context and input, encrypted under the
+    /// transport public key.
+    EncryptedKey,
+    /// The BLS public key derived for the context, a compressed G1 point
+    /// (48 bytes). The input and the transport public key are ignored.
+    BlsPublicKey,
+    /// The BLS public key derived for the context, followed by a BLS signature
+    /// on the input under that key, a compressed G2 point (96 bytes). The
+    /// transport public key is ignored.
+    ///
+    /// This is the minimal-public-key-size variant with the proof-of-possession
+    /// ciphersuite `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`, as used on
+    /// the Ethereum beacon chain.
+    BlsSignature,
+}
+
 #[derive(Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub struct VetKdArgs {
     pub ni_dkg_id: NiDkgId,
@@ -19,6 +44,7 @@ pub struct VetKdArgs {
     pub context: VetKdDerivationContext,
     #[serde(with = "serde_bytes")]
     pub transport_public_key: Vec<u8>,
+    pub output: VetKdOutput,
 }
 
 impl fmt::Debug for VetKdArgs {
@@ -31,6 +57,7 @@ impl fmt::Debug for VetKdArgs {
                 "transport_public_key",
                 &HexEncoding::from(&self.transport_public_key),
             )
+            .field("output", &self.output)
             .finish()
     }
 }
@@ -87,6 +114,27 @@ impl std::fmt::Debug for VetKdEncryptedKey {
 }
 impl_display_using_debug!(VetKdEncryptedKey);
 
+impl VetKdEncryptedKey {
+    /// Splits a key derived for [`VetKdOutput::BlsSignature`] into the BLS
+    /// public key and the signature, or returns `None` if the key has an
+    /// unexpected length.
+    pub fn bls_public_key_and_signature(&self) -> Option<(&[u8], &[u8])> {
+        if self.encrypted_key.len() != G1_BYTES + G2_BYTES {
+            return None;
+        }
+        Some(self.encrypted_key.split_at(G1_BYTES))
+    }
+
+    /// Returns the key derived for [`VetKdOutput::BlsSignature`] that consists
+    /// of the given BLS public key and signature.
+    pub fn from_bls_public_key_and_signature(public_key: &[u8], signature: &[u8]) -> Self {
+        let mut encrypted_key = Vec::with_capacity(public_key.len() + signature.len());
+        encrypted_key.extend_from_slice(public_key);
+        encrypted_key.extend_from_slice(signature);
+        Self { encrypted_key }
+    }
+}
+
 /// Metadata used to derive keys for vetKD.
 #[derive(Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub struct VetKdDerivationContext {
@@ -105,6 +153,31 @@ impl std::fmt::Debug for VetKdDerivationContext {
 }
 impl_display_using_debug!(VetKdDerivationContext);
 
+impl VetKdDerivationContext {
+    /// Returns the context used to derive the key that `caller` signs with when
+    /// requesting a threshold BLS signature with the given derivation path.
+    pub fn for_bls_signature(caller: PrincipalId, derivation_path: &[Vec<u8>]) -> Self {
+        Self {
+            caller,
+            context: Self::encode_bls_derivation_path(derivation_path),
+        }
+    }
+
+    /// Encodes a BLS derivation path as a vetKD context.
+    ///
+    /// Each element is prefixed with its length, so that distinct derivation
+    /// paths result in distinct contexts. The empty path results in the empty
+    /// context, i.e., in the canister's key.
+    pub fn encode_bls_derivation_path(derivation_path: &[Vec<u8>]) -> Vec<u8> {
+        let mut context = Vec::new();
+        for element in derivation_path {
+            context.extend_from_slice(&(element.len() as u64).to_be_bytes());
+            context.extend_from_slice(element);
+        }
+        context
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum VetKdKeyShareCreationError {
     ThresholdSigDataNotFound(ThresholdSigDataNotFoundError),
@@ -2,6 +2,7 @@ use crate::Height;
 use crate::crypto::threshold_sig::ni_dkg::{NiDkgId, NiDkgTag, NiDkgTargetId, NiDkgTargetSubnet};
 use crate::crypto::vetkd::{
     VetKdArgs, VetKdEncryptedKey, VetKdEncryptedKeyShare, VetKdEncryptedKeyShareContent,
+    VetKdOutput,
 };
 use ic_base_types::PrincipalId;
 use ic_base_types::SubnetId;
@@ -28,12 +29,14 @@ mod display_and_debug {
             },
             input: b"input".to_vec(),
             transport_public_key: b"tpk".to_vec(),
+            output: VetKdOutput::EncryptedKey,
         };
         let output = "VetKdArgs { \
             ni_dkg_id: NiDkgId { start_block_height: 7, dealer_subnet: ot5wk-sbkaa-aaaaa-aaaap-yai, dkg_tag: HighThreshold, target_subnet: Remote(0x2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a) }, \
             input: 0x696e707574, \
             context: VetKdDerivationContext { caller: 7xzs3-rqraa-aaaaa-aaaap-2ai, context: 0x636f6e746578742d313233 }, \
-            transport_public_key: 0x74706b \
+            transport_public_key: 0x74706b, \
+            output: EncryptedKey \
         }"
         .to_string();
 
@@ -71,3 +74,52 @@ mod display_and_debug {
         assert_eq!(output, format!("{input}"));
     }
 }
+
+mod bls_signatures {
+    use crate::crypto::vetkd::VetKdDerivationContext;
+
+    use super::*;
+
+    #[test]
+    fn should_split_bls_public_key_and_signature() {
+        let public_key = [1; 48];
+        let signature = [2; 96];
+        let key = VetKdEncryptedKey::from_bls_public_key_and_signature(&public_key, &signature);
+
+        assert_eq!(key.encrypted_key.len(), 144);
+        assert_eq!(
+            key.bls_public_key_and_signature(),
+            Some((&public_key[..], &signature[..]))
+        );
+    }
+
+    #[test]
+    fn should_not_split_key_of_wrong_length() {
+        for len in [48, 143, 145, 192] {
+            let key = VetKdEncryptedKey {
+                encrypted_key: vec![0; len],
+            };
+
+            assert_eq!(key.bls_public_key_and_signature(), None);
+        }
+    }
+
+    #[test]
+    fn should_encode_distinct_derivation_paths_as_distinct_contexts() {
+        let caller = PrincipalId::new_user_test_id(1);
+        let paths: [Vec<Vec<u8>>; 4] = [
+            vec![],
+            vec![vec![]],
+            vec![b"ab".to_vec(), b"c".to_vec()],
+            vec![b"a".to_vec(), b"bc".to_vec()],
+        ];
+
+        let contexts: std::collections::BTreeSet<_> = paths
+            .iter()
+            .map(|path| VetKdDerivationContext::for_bls_signature(caller, path).context)
+            .collect();
+
+        assert_eq!(contexts.len(), paths.len());
+        assert!(VetKdDerivationContext::encode_bls_derivation_path(&[]).is_empty());
+    }
+}
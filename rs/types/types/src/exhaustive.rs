@@ -46,8 +46,8 @@ use ic_crypto_internal_types::NodeIndex;
 use ic_error_types::RejectCode;
 use ic_exhaustive_derive::ExhaustiveSet;
 use ic_management_canister_types_private::{
-    EcdsaCurve, EcdsaKeyId, MasterPublicKeyId, SchnorrAlgorithm, SchnorrKeyId, VetKdCurve,
-    VetKdKeyId,
+    BlsCurve, BlsKeyId, EcdsaCurve, EcdsaKeyId, MasterPublicKeyId, SchnorrAlgorithm, SchnorrKeyId,
+    VetKdCurve, VetKdKeyId,
 };
 use ic_protobuf::types::v1 as pb;
 use phantom_newtype::{AmountOf, Id};
@@ -403,18 +403,38 @@ impl ExhaustiveSet for VetKdKeyId {
     }
 }
 
+impl ExhaustiveSet for BlsCurve {
+    fn exhaustive_set<R: RngCore + CryptoRng>(_: &mut R) -> Vec<Self> {
+        BlsCurve::iter().collect()
+    }
+}
+
+impl ExhaustiveSet for BlsKeyId {
+    fn exhaustive_set<R: RngCore + CryptoRng>(rng: &mut R) -> Vec<Self> {
+        <(BlsCurve, String)>::exhaustive_set(rng)
+            .into_iter()
+            .map(|elem| Self {
+                curve: elem.0,
+                name: elem.1,
+            })
+            .collect()
+    }
+}
+
 impl ExhaustiveSet for MasterPublicKeyId {
     fn exhaustive_set<R: RngCore + CryptoRng>(rng: &mut R) -> Vec<Self> {
-        assert_eq!(MasterPublicKeyId::COUNT, 3);
+        assert_eq!(MasterPublicKeyId::COUNT, 4);
         let ecdsa_key_ids = EcdsaKeyId::exhaustive_set(rng);
         let schnorr_key_ids = SchnorrKeyId::exhaustive_set(rng);
         let vetkd_key_ids = VetKdKeyId::exhaustive_set(rng);
+        let bls_key_ids = BlsKeyId::exhaustive_set(rng);
 
         ecdsa_key_ids
             .into_iter()
             .map(MasterPublicKeyId::Ecdsa)
             .chain(schnorr_key_ids.into_iter().map(MasterPublicKeyId::Schnorr))
             .chain(vetkd_key_ids.into_iter().map(MasterPublicKeyId::VetKd))
+            .chain(bls_key_ids.into_iter().map(MasterPublicKeyId::Bls))
             .collect()
     }
 }
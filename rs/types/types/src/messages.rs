@@ -11,11 +11,11 @@ mod webauthn;
 pub use self::http::{
     Authentication, Certificate, CertificateDelegation, CertificateDelegationFormat,
     CertificateDelegationMetadata, Delegation, HasCanisterId, HttpCallContent, HttpCanisterUpdate,
-    HttpQueryContent, HttpQueryResponse, HttpQueryResponseReply, HttpReadState,
-    HttpReadStateContent, HttpReadStateResponse, HttpReply, HttpRequest, HttpRequestContent,
-    HttpRequestEnvelope, HttpRequestError, HttpSignedQueryResponse, HttpStatusResponse,
-    HttpUserQuery, NodeSignature, QueryResponseHash, RawHttpRequestVal, ReplicaHealthStatus,
-    SignedDelegation,
+    HttpDryRunRequest, HttpDryRunResponse, HttpQueryContent, HttpQueryRequestEnvelope,
+    HttpQueryResponse, HttpQueryResponseReply, HttpReadState, HttpReadStateContent,
+    HttpReadStateResponse, HttpReply, HttpRequest, HttpRequestContent, HttpRequestEnvelope,
+    HttpRequestError, HttpSignedQueryResponse, HttpStatusResponse, HttpUserQuery, NodeSignature,
+    QueryResponseHash, RawHttpRequestVal, ReplicaHealthStatus, SignedDelegation,
 };
 pub use crate::methods::SystemMethod;
 use crate::time::CoarseTime;
@@ -268,6 +268,22 @@ pub struct HttpRequestEnvelope<C> {
     pub sender_delegation: Option<Vec<SignedDelegation>>,
 }
 
+/// The body of a `/api/v2/canister/_/query` request: an [`HttpRequestEnvelope`]
+/// with an optional continuation token.
+///
+/// The token is not part of the request content, so it does not change the
+/// request id and is not covered by the sender's signature.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct HttpQueryRequestEnvelope {
+    #[serde(flatten)]
+    pub envelope: HttpRequestEnvelope<HttpQueryContent>,
+    /// The continuation token returned with the response to an earlier query
+    /// to the same canister. If set, the query is executed against the same
+    /// state as that query.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<Blob>,
+}
+
 /// A strongly-typed version of [`HttpRequestEnvelope`].
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Deserialize, Serialize)]
 pub struct HttpRequest<C> {
@@ -666,6 +682,11 @@ pub struct HttpSignedQueryResponse {
     #[serde(serialize_with = "serialize_node_signature_to_1_tuple")]
     #[serde(rename = "signatures")]
     pub node_signature: NodeSignature,
+
+    /// A token to execute follow-up queries to the same canister against the
+    /// same state. It is not covered by the node signature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub continuation_token: Option<Blob>,
 }
 
 /// Serializes a `NodeSignature` to a 1-tuple containing only that one signature.
@@ -697,6 +718,35 @@ pub struct HttpQueryResponseReply {
     pub arg: Blob,
 }
 
+/// The response to a dry run of an update call, i.e. an execution of the call
+/// against the latest certified state whose changes were discarded.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HttpDryRunResponse {
+    /// The response of the call, or `None` if the call did not reply and
+    /// would have awaited the responses to its outgoing requests.
+    pub response: Option<HttpQueryResponse>,
+    /// The batch time of the state the call was executed against.
+    pub timestamp: Time,
+    pub instructions_used: u64,
+    pub cycles_charged: u128,
+    /// The number of 4 KiB pages of heap and stable memory dirtied by the call.
+    pub dirty_pages: u64,
+    /// The growth of the canister memory usage in bytes.
+    pub heap_growth: u64,
+    pub outgoing_requests: Vec<HttpDryRunRequest>,
+}
+
+/// A request the canister would have sent during a dry run.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct HttpDryRunRequest {
+    pub receiver: CanisterId,
+    pub method_name: String,
+    pub payment: u128,
+    pub payload_size: u64,
+}
+
 /// The response to a `read_state` request.
 #[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
 pub struct HttpReadStateResponse {
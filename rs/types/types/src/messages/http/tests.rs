@@ -341,7 +341,8 @@ mod try_from {
             Authentication, HttpQueryContent, HttpRequestError, HttpUserQuery,
         };
         use crate::messages::{
-            Blob, HttpRequest, HttpRequestEnvelope, Query, QuerySource, UserSignature,
+            Blob, HttpQueryRequestEnvelope, HttpRequest, HttpRequestEnvelope, Query, QuerySource,
+            UserSignature,
         };
         use assert_matches::assert_matches;
 
@@ -391,6 +392,32 @@ mod try_from {
             )
         }
 
+        #[test]
+        fn should_roundtrip_query_request_envelope_with_continuation_token_through_cbor() {
+            for continuation_token in [None, Some(Blob(vec![1, 2, 3]))] {
+                let envelope = HttpQueryRequestEnvelope {
+                    envelope: HttpRequestEnvelope {
+                        content: HttpQueryContent::Query {
+                            query: default_http_user_query_content(),
+                        },
+                        sender_pubkey: Some(Blob(fixed::pubkey())),
+                        sender_sig: Some(Blob(fixed::sig())),
+                        sender_delegation: None,
+                    },
+                    continuation_token,
+                };
+
+                let bytes = serde_cbor::to_vec(&envelope).unwrap();
+
+                assert_eq!(serde_cbor::from_slice(&bytes).ok(), Some(envelope.clone()));
+                // Requests with a token are still valid envelopes without one.
+                assert_eq!(
+                    serde_cbor::from_slice::<HttpRequestEnvelope<HttpQueryContent>>(&bytes).ok(),
+                    Some(envelope.envelope)
+                );
+            }
+        }
+
         #[test]
         fn should_successfully_create_authenticated_http_request_for_valid_data() {
             for (sender_pubkey, sender_sig, sender_delegation) in [
@@ -674,6 +701,7 @@ mod cbor_serialization {
                     signature: Blob(b"Some node signature bytes.".to_vec()),
                     identity: node_id,
                 },
+                continuation_token: None,
             },
             Value::Map(btreemap! {
                 text("status") => text("replied"),
@@ -709,6 +737,7 @@ mod cbor_serialization {
                     signature: Blob(b"Some node signature bytes.".to_vec()),
                     identity: node_id,
                 },
+                continuation_token: None,
             },
             Value::Map(btreemap! {
                 text("status") => text("rejected"),
@@ -591,6 +591,8 @@ pub fn extract_effective_canister_id(
         | Ok(Method::SignWithSchnorr)
         | Ok(Method::VetKdPublicKey)
         | Ok(Method::VetKdDeriveKey)
+        | Ok(Method::BlsPublicKey)
+        | Ok(Method::SignWithBls)
         | Ok(Method::BitcoinGetBalance)
         | Ok(Method::BitcoinGetUtxos)
         | Ok(Method::BitcoinGetBlockHeaders)
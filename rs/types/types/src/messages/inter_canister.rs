@@ -266,6 +266,8 @@ impl Request {
             | Ok(Method::SignWithSchnorr)
             | Ok(Method::VetKdPublicKey)
             | Ok(Method::VetKdDeriveKey)
+            | Ok(Method::BlsPublicKey)
+            | Ok(Method::SignWithBls)
             | Ok(Method::BitcoinGetBalance)
             | Ok(Method::BitcoinGetUtxos)
             | Ok(Method::BitcoinGetBlockHeaders)
@@ -174,6 +174,11 @@ pub struct StreamHeader {
 
     /// Flags informing the other subnet e.g. what kinds of messages will be accepted.
     flags: StreamFlags,
+
+    /// Congestion of the receiving end of the reverse stream, derived from the
+    /// input queue occupancy of the canisters targeted by its requests. In the
+    /// range `[0, MAX_STREAM_CONGESTION_LEVEL]`, `0` meaning not congested.
+    congestion_level: u8,
 }
 
 /// Reasons for why inter canister messages may fail to be inducted into the state.
@@ -267,6 +272,9 @@ impl RejectSignal {
     }
 }
 
+/// Upper bound of the congestion level signalled in a `StreamHeader`.
+pub const MAX_STREAM_CONGESTION_LEVEL: u8 = 4;
+
 /// Flags for `Stream`.
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
 pub struct StreamFlags {
@@ -281,6 +289,7 @@ impl StreamHeader {
         signals_end: StreamIndex,
         reject_signals: VecDeque<RejectSignal>,
         flags: StreamFlags,
+        congestion_level: u8,
     ) -> Self {
         Self {
             begin,
@@ -288,6 +297,7 @@ impl StreamHeader {
             signals_end,
             reject_signals,
             flags,
+            congestion_level,
         }
     }
 
@@ -310,6 +320,10 @@ impl StreamHeader {
     pub fn flags(&self) -> &StreamFlags {
         &self.flags
     }
+
+    pub fn congestion_level(&self) -> u8 {
+        self.congestion_level
+    }
 }
 
 /// A continuous slice of messages pulled from a remote subnet.  The slice also
@@ -397,6 +411,7 @@ pub mod testing {
         fn set_begin(&mut self, begin: StreamIndex);
         fn set_end(&mut self, end: StreamIndex);
         fn set_flags(&mut self, flags: StreamFlags);
+        fn set_congestion_level(&mut self, congestion_level: u8);
     }
 
     impl StreamHeaderTesting for super::StreamHeader {
@@ -411,6 +426,10 @@ pub mod testing {
         fn set_flags(&mut self, flags: StreamFlags) {
             self.flags = flags;
         }
+
+        fn set_congestion_level(&mut self, congestion_level: u8) {
+            self.congestion_level = congestion_level;
+        }
     }
 
     /// Provides test-only methods for `StreamSlice`.
@@ -1,5 +1,7 @@
-use crate::DirectAuthenticationScheme::{CanisterSignature, UserKeyPair};
-use ic_canister_client_sender::{Ed25519KeyPair, ed25519_public_key_to_der};
+use crate::DirectAuthenticationScheme::{CanisterSignature, MlDsa65UserKeyPair, UserKeyPair};
+use ic_canister_client_sender::{
+    Ed25519KeyPair, MlDsa65KeyPair, ed25519_public_key_to_der, ml_dsa_65_public_key_to_der,
+};
 use ic_certification_test_utils::{generate_root_of_trust, serialize_to_cbor};
 use ic_crypto_internal_basic_sig_iccsa_test_utils::CanisterState;
 use ic_crypto_internal_threshold_sig_bls12381::types::SecretKeyBytes;
@@ -414,6 +416,7 @@ impl CanisterSigner {
 #[derive(Clone, Eq, PartialEq, Debug)]
 pub enum DirectAuthenticationScheme {
     UserKeyPair(Ed25519KeyPair),
+    MlDsa65UserKeyPair(MlDsa65KeyPair),
     CanisterSignature(CanisterSigner),
 }
 
@@ -466,6 +469,7 @@ impl DirectAuthenticationScheme {
     pub fn public_key_raw(&self) -> Vec<u8> {
         match self {
             DirectAuthenticationScheme::UserKeyPair(keypair) => keypair.public_key.to_vec(),
+            DirectAuthenticationScheme::MlDsa65UserKeyPair(keypair) => keypair.public_key(),
             DirectAuthenticationScheme::CanisterSignature(signer) => {
                 signer.canister_public_key_raw()
             }
@@ -479,6 +483,9 @@ impl DirectAuthenticationScheme {
             DirectAuthenticationScheme::UserKeyPair(_) => {
                 ed25519_public_key_to_der(self.public_key_raw())
             }
+            DirectAuthenticationScheme::MlDsa65UserKeyPair(_) => {
+                ml_dsa_65_public_key_to_der(self.public_key_raw())
+            }
 
             DirectAuthenticationScheme::CanisterSignature { .. } => {
                 subject_public_key_info_der(oid_canister_signature(), &self.public_key_raw())
@@ -492,6 +499,9 @@ impl DirectAuthenticationScheme {
             DirectAuthenticationScheme::UserKeyPair(keypair) => {
                 keypair.sign(&message.as_signed_bytes()).to_vec()
             }
+            DirectAuthenticationScheme::MlDsa65UserKeyPair(keypair) => {
+                keypair.sign(&message.as_signed_bytes())
+            }
             DirectAuthenticationScheme::CanisterSignature(signer) => signer.sign(message).0,
         }
     }
@@ -745,6 +755,12 @@ pub fn random_user_key_pair<R: Rng + CryptoRng>(rng: &mut R) -> DirectAuthentica
     UserKeyPair(Ed25519KeyPair::generate(rng))
 }
 
+pub fn random_ml_dsa_65_user_key_pair<R: Rng + CryptoRng>(
+    rng: &mut R,
+) -> DirectAuthenticationScheme {
+    MlDsa65UserKeyPair(MlDsa65KeyPair::generate(rng))
+}
+
 pub fn canister_signature_with_hard_coded_root_of_trust() -> DirectAuthenticationScheme {
     canister_signature(hard_coded_root_of_trust())
 }
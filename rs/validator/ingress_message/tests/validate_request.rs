@@ -12,7 +12,7 @@ use ic_validator_http_request_test_utils::{
     AuthenticationScheme, CANISTER_ID_SIGNER, CANISTER_SIGNATURE_SEED, CURRENT_TIME,
     CanisterSigner, DirectAuthenticationScheme, HttpRequestBuilder, HttpRequestEnvelopeContent,
     RootOfTrust, all_authentication_schemes, all_authentication_schemes_except, canister_signature,
-    hard_coded_root_of_trust, random_user_key_pair,
+    hard_coded_root_of_trust, random_ml_dsa_65_user_key_pair, random_user_key_pair,
 };
 use ic_validator_ingress_message::AuthenticationError;
 use ic_validator_ingress_message::AuthenticationError::DelegationContainsCyclesError;
@@ -732,6 +732,80 @@ mod authenticated_requests_direct_ed25519 {
     }
 }
 
+mod authenticated_requests_direct_ml_dsa_65 {
+    use super::*;
+    use crate::RequestValidationError::InvalidSignature;
+    use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
+    use ic_validator_http_request_test_utils::AuthenticationScheme::Direct;
+
+    #[test]
+    fn should_validate_signed_request() {
+        let rng = &mut reproducible_rng();
+        let verifier = verifier_at_time(CURRENT_TIME).build();
+
+        test(&verifier, HttpRequestBuilder::new_update_call(), rng);
+        test(&verifier, HttpRequestBuilder::new_query(), rng);
+        test(&verifier, HttpRequestBuilder::new_read_state(), rng);
+
+        fn test<ReqContent, EnvContent, Verifier>(
+            verifier: &Verifier,
+            builder: HttpRequestBuilder<EnvContent>,
+            rng: &mut ReproducibleRng,
+        ) where
+            ReqContent: HttpRequestContent,
+            EnvContent: EnvelopeContent<ReqContent>,
+            Verifier: HttpRequestVerifier<ReqContent>,
+        {
+            let builder_info = format!("{builder:?}");
+            let request = builder
+                .with_ingress_expiry_at(CURRENT_TIME)
+                .with_authentication(Direct(random_ml_dsa_65_user_key_pair(rng)))
+                .build();
+
+            let result = verifier.validate_request(&request);
+
+            assert_eq!(result, Ok(()), "Test with {builder_info} failed");
+        }
+    }
+
+    #[test]
+    fn should_error_when_signature_corrupted() {
+        let rng = &mut reproducible_rng();
+        let verifier = verifier_at_time(CURRENT_TIME).build();
+
+        test(&verifier, HttpRequestBuilder::new_update_call(), rng);
+        test(&verifier, HttpRequestBuilder::new_query(), rng);
+        test(&verifier, HttpRequestBuilder::new_read_state(), rng);
+
+        fn test<ReqContent, EnvContent, Verifier>(
+            verifier: &Verifier,
+            builder: HttpRequestBuilder<EnvContent>,
+            rng: &mut ReproducibleRng,
+        ) where
+            ReqContent: HttpRequestContent,
+            EnvContent: EnvelopeContent<ReqContent>,
+            Verifier: HttpRequestVerifier<ReqContent>,
+        {
+            let builder_info = format!("{builder:?}");
+            let request = builder
+                .with_ingress_expiry_at(CURRENT_TIME)
+                .with_authentication(Direct(random_ml_dsa_65_user_key_pair(rng)))
+                .corrupt_authentication_sender_signature()
+                .build();
+
+            let result = verifier.validate_request(&request);
+
+            assert_matches!(
+                result,
+                Err(InvalidSignature(
+                    AuthenticationError::InvalidBasicSignature(_)
+                )),
+                "Test with {builder_info} failed"
+            )
+        }
+    }
+}
+
 mod authenticated_requests_direct_canister_signature {
     use super::*;
     use crate::RequestValidationError::InvalidSignature;
@@ -1092,6 +1166,28 @@ mod authenticated_requests_delegations {
         }
     }
 
+    #[test]
+    fn should_validate_delegation_chain_mixing_ed25519_and_ml_dsa_65() {
+        let rng = &mut reproducible_rng();
+        let verifier = verifier_at_time(CURRENT_TIME).build();
+        let chain = DelegationChain::rooted_at(random_ml_dsa_65_user_key_pair(rng))
+            .delegate_to(random_user_key_pair(rng), CURRENT_TIME)
+            .delegate_to(random_ml_dsa_65_user_key_pair(rng), CURRENT_TIME)
+            .build();
+
+        test_all_request_types_with_delegation_chain(
+            &verifier,
+            chain.clone(),
+            |result, builder_info| {
+                assert_eq!(
+                    result,
+                    Ok(()),
+                    "verification of delegation chain {chain:?} for request builder {builder_info} failed"
+                );
+            },
+        );
+    }
+
     #[test]
     fn should_validate_delegation_chains_of_length_up_to_20_containing_a_canister_signature() {
         let rng = &mut reproducible_rng();
@@ -1,6 +1,7 @@
 use crate::webauthn::validate_webauthn_sig;
 use AuthenticationError::*;
 use RequestValidationError::*;
+use batch::BatchVerifiedSigVerifier;
 use ic_crypto_interfaces_sig_verification::IngressSigVerifier;
 use ic_crypto_standalone_sig_verifier::{KeyBytesContentType, user_public_key_from_bytes};
 use ic_crypto_tree_hash::Path;
@@ -23,6 +24,7 @@ use std::{
 };
 use thiserror::Error;
 
+mod batch;
 #[cfg(test)]
 mod tests;
 
@@ -89,6 +91,24 @@ pub trait HttpRequestVerifier<C, R>: Send + Sync {
         current_time: Time,
         root_of_trust_provider: &R,
     ) -> Result<CanisterIdSet, RequestValidationError>;
+
+    /// Validates the given requests, e.g., all ingress messages of a block.
+    ///
+    /// Returns one result per request, in the order of `requests`, and each
+    /// result is the same as the one returned by `validate_request`.
+    /// Implementations may verify the signatures of all requests together,
+    /// which is considerably faster than validating the requests one by one.
+    fn validate_requests(
+        &self,
+        requests: &[&HttpRequest<C>],
+        current_time: Time,
+        root_of_trust_provider: &R,
+    ) -> Vec<Result<CanisterIdSet, RequestValidationError>> {
+        requests
+            .iter()
+            .map(|request| self.validate_request(request, current_time, root_of_trust_provider))
+            .collect()
+    }
 }
 
 pub struct HttpRequestVerifierImpl {
@@ -112,18 +132,56 @@ where
         current_time: Time,
         root_of_trust_provider: &R,
     ) -> Result<CanisterIdSet, RequestValidationError> {
-        validate_ingress_expiry(request, current_time)?;
-        let delegation_targets = validate_request_content(
+        validate_signed_ingress(
             request,
             self.validator.as_ref(),
             current_time,
             root_of_trust_provider,
-        )?;
-        validate_request_target(request, &delegation_targets)?;
-        Ok(delegation_targets)
+        )
+    }
+
+    fn validate_requests(
+        &self,
+        requests: &[&HttpRequest<SignedIngressContent>],
+        current_time: Time,
+        root_of_trust_provider: &R,
+    ) -> Vec<Result<CanisterIdSet, RequestValidationError>> {
+        // Expired requests are rejected before their signatures are looked at.
+        let unexpired_requests: Vec<_> = requests
+            .iter()
+            .copied()
+            .filter(|request| validate_ingress_expiry(request, current_time).is_ok())
+            .collect();
+        let validator = BatchVerifiedSigVerifier::new(self.validator.as_ref(), &unexpired_requests);
+        requests
+            .iter()
+            .map(|request| {
+                validate_signed_ingress(request, &validator, current_time, root_of_trust_provider)
+            })
+            .collect()
     }
 }
 
+fn validate_signed_ingress<R: RootOfTrustProvider>(
+    request: &HttpRequest<SignedIngressContent>,
+    ingress_signature_verifier: &dyn IngressSigVerifier,
+    current_time: Time,
+    root_of_trust_provider: &R,
+) -> Result<CanisterIdSet, RequestValidationError>
+where
+    R::Error: std::error::Error,
+{
+    validate_ingress_expiry(request, current_time)?;
+    let delegation_targets = validate_request_content(
+        request,
+        ingress_signature_verifier,
+        current_time,
+        root_of_trust_provider,
+    )?;
+    validate_request_target(request, &delegation_targets)?;
+    Ok(delegation_targets)
+}
+
 impl<R> HttpRequestVerifier<Query, R> for HttpRequestVerifierImpl
 where
     R: RootOfTrustProvider,
@@ -546,7 +604,8 @@ where
         }
         KeyBytesContentType::Ed25519PublicKeyDer
         | KeyBytesContentType::EcdsaP256PublicKeyDer
-        | KeyBytesContentType::EcdsaSecp256k1PublicKeyDer => {
+        | KeyBytesContentType::EcdsaSecp256k1PublicKeyDer
+        | KeyBytesContentType::MlDsa65PublicKeyDer => {
             let basic_sig = BasicSigOf::from(BasicSig(signature.signature.clone()));
             validate_signature_plain(validator, message_id, &basic_sig, &pk)
                 .map_err(InvalidSignature)?;
@@ -682,7 +741,8 @@ where
         KeyBytesContentType::Ed25519PublicKeyDer
         | KeyBytesContentType::EcdsaP256PublicKeyDer
         | KeyBytesContentType::EcdsaSecp256k1PublicKeyDer
-        | KeyBytesContentType::RsaSha256PublicKeyDer => {
+        | KeyBytesContentType::RsaSha256PublicKeyDer
+        | KeyBytesContentType::MlDsa65PublicKeyDer => {
             let basic_sig = BasicSigOf::from(BasicSig(signature.to_vec()));
             validator
                 .verify_basic_sig_by_public_key(&basic_sig, delegation, &pk)
@@ -0,0 +1,164 @@
+//! Batched verification of the basic signatures of several requests.
+use super::MAXIMUM_NUMBER_OF_DELEGATIONS;
+use ic_crypto_interfaces_sig_verification::{
+    BasicSigVerifierByPublicKey, CanisterSigVerifier, IngressSigVerifier,
+};
+use ic_crypto_standalone_sig_verifier::{
+    BatchVerificationItem, KeyBytesContentType, user_public_key_from_bytes,
+    verify_sigs_batch_by_public_key,
+};
+use ic_types::crypto::threshold_sig::IcRootOfTrust;
+use ic_types::crypto::{
+    AlgorithmId, BasicSigOf, CanisterSigOf, CryptoResult, Signable, UserPublicKey,
+};
+use ic_types::messages::{Authentication, HttpRequest, HttpRequestContent};
+use std::collections::HashSet;
+
+/// A basic signature, identified by everything that determines whether it
+/// verifies.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct BasicSigEntry {
+    algorithm_id: AlgorithmId,
+    public_key: Vec<u8>,
+    signed_bytes: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+/// An [`IngressSigVerifier`] that accepts basic signatures which were already
+/// verified successfully as part of a batch, and delegates all other
+/// verifications to the wrapped verifier.
+///
+/// Signatures that fail batch verification are not remembered, so that the
+/// wrapped verifier produces the same error as without batching.
+pub(super) struct BatchVerifiedSigVerifier<'a> {
+    inner: &'a dyn IngressSigVerifier,
+    verified: HashSet<BasicSigEntry>,
+}
+
+impl<'a> BatchVerifiedSigVerifier<'a> {
+    /// Verifies, in one batch, the sender signatures and delegation
+    /// signatures of `requests` that are plain basic signatures.
+    pub(super) fn new<C: HttpRequestContent>(
+        inner: &'a dyn IngressSigVerifier,
+        requests: &[&HttpRequest<C>],
+    ) -> Self {
+        let entries: Vec<BasicSigEntry> = requests
+            .iter()
+            .flat_map(|request| basic_sigs_of(request))
+            .collect();
+        let results = {
+            let items: Vec<_> = entries
+                .iter()
+                .map(|entry| BatchVerificationItem {
+                    algorithm_id: entry.algorithm_id,
+                    public_key: &entry.public_key,
+                    message: &entry.signed_bytes,
+                    signature: &entry.signature,
+                })
+                .collect();
+            verify_sigs_batch_by_public_key(&items, &mut rand::thread_rng())
+        };
+        let verified = entries
+            .into_iter()
+            .zip(results)
+            .filter_map(|(entry, result)| result.is_ok().then_some(entry))
+            .collect();
+        Self { inner, verified }
+    }
+}
+
+/// Returns the basic signatures that validating `request` will verify.
+///
+/// The chain of delegations is followed without validating it, so the result
+/// may contain signatures that validation never looks at. This is harmless
+/// since they are only used to answer verification queries.
+fn basic_sigs_of<C: HttpRequestContent>(request: &HttpRequest<C>) -> Vec<BasicSigEntry> {
+    let Authentication::Authenticated(signature) = request.authentication() else {
+        return vec![];
+    };
+    let delegations = signature.sender_delegation.as_deref().unwrap_or_default();
+    if delegations.len() > MAXIMUM_NUMBER_OF_DELEGATIONS {
+        return vec![];
+    }
+
+    let mut entries = Vec::with_capacity(delegations.len() + 1);
+    let mut pubkey = signature.signer_pubkey.as_slice();
+    for signed_delegation in delegations {
+        let delegation = signed_delegation.delegation();
+        entries.extend(basic_sig_entry(
+            pubkey,
+            delegation.as_signed_bytes(),
+            &signed_delegation.signature().0,
+        ));
+        pubkey = delegation.pubkey();
+    }
+    entries.extend(basic_sig_entry(
+        pubkey,
+        request.id().as_signed_bytes(),
+        &signature.signature,
+    ));
+    entries
+}
+
+fn basic_sig_entry(
+    public_key: &[u8],
+    signed_bytes: Vec<u8>,
+    signature: &[u8],
+) -> Option<BasicSigEntry> {
+    let (pk, pk_type) = user_public_key_from_bytes(public_key).ok()?;
+    match pk_type {
+        KeyBytesContentType::Ed25519PublicKeyDer
+        | KeyBytesContentType::EcdsaP256PublicKeyDer
+        | KeyBytesContentType::EcdsaSecp256k1PublicKeyDer
+        | KeyBytesContentType::RsaSha256PublicKeyDer
+        | KeyBytesContentType::MlDsa65PublicKeyDer => Some(BasicSigEntry {
+            algorithm_id: pk.algorithm_id,
+            public_key: pk.key,
+            signed_bytes,
+            signature: signature.to_vec(),
+        }),
+        KeyBytesContentType::EcdsaP256PublicKeyDerWrappedCose
+        | KeyBytesContentType::RsaSha256PublicKeyDerWrappedCose
+        | KeyBytesContentType::IcCanisterSignatureAlgPublicKeyDer => None,
+    }
+}
+
+impl<'a, T: Signable> BasicSigVerifierByPublicKey<T> for BatchVerifiedSigVerifier<'a>
+where
+    dyn IngressSigVerifier + 'a: BasicSigVerifierByPublicKey<T>,
+{
+    fn verify_basic_sig_by_public_key(
+        &self,
+        signature: &BasicSigOf<T>,
+        signed_bytes: &T,
+        public_key: &UserPublicKey,
+    ) -> CryptoResult<()> {
+        let entry = BasicSigEntry {
+            algorithm_id: public_key.algorithm_id,
+            public_key: public_key.key.clone(),
+            signed_bytes: signed_bytes.as_signed_bytes(),
+            signature: signature.get_ref().0.clone(),
+        };
+        if self.verified.contains(&entry) {
+            return Ok(());
+        }
+        self.inner
+            .verify_basic_sig_by_public_key(signature, signed_bytes, public_key)
+    }
+}
+
+impl<'a, T: Signable> CanisterSigVerifier<T> for BatchVerifiedSigVerifier<'a>
+where
+    dyn IngressSigVerifier + 'a: CanisterSigVerifier<T>,
+{
+    fn verify_canister_sig(
+        &self,
+        signature: &CanisterSigOf<T>,
+        signed_bytes: &T,
+        public_key: &UserPublicKey,
+        root_of_trust: &IcRootOfTrust,
+    ) -> CryptoResult<()> {
+        self.inner
+            .verify_canister_sig(signature, signed_bytes, public_key, root_of_trust)
+    }
+}
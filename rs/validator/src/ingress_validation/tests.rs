@@ -557,6 +557,101 @@ mod validate_ingress_expiry {
     }
 }
 
+mod validate_requests {
+    use super::*;
+    use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
+    use ic_types::crypto::Signable;
+    use ic_types::messages::{
+        Blob, HttpCallContent, HttpCanisterUpdate, HttpRequestEnvelope, SignedIngressContent,
+    };
+    use rand::{CryptoRng, Rng};
+
+    const CURRENT_TIME: Time = Time::from_nanos_since_unix_epoch(1_000_000_000);
+
+    #[test]
+    fn should_return_same_results_as_validating_requests_individually() {
+        let rng = &mut reproducible_rng();
+        let verifier = HttpRequestVerifierImpl::new(Arc::new(
+            temp_crypto_component_with_fake_registry(node_test_id(0)),
+        ));
+        let expired = Time::from_nanos_since_unix_epoch(1);
+        let requests = vec![
+            signed_request(rng, CURRENT_TIME, false),
+            signed_request(rng, CURRENT_TIME, true),
+            signed_request(rng, CURRENT_TIME, false),
+            signed_request(rng, expired, false),
+            signed_request(rng, CURRENT_TIME, false),
+        ];
+        let request_refs: Vec<_> = requests.iter().collect();
+        let root_of_trust_provider = MockRootOfTrustProvider::new();
+
+        let results =
+            verifier.validate_requests(&request_refs, CURRENT_TIME, &root_of_trust_provider);
+
+        assert_eq!(results.len(), requests.len());
+        for (request, result) in requests.iter().zip(&results) {
+            assert_eq!(
+                result,
+                &verifier.validate_request(request, CURRENT_TIME, &root_of_trust_provider)
+            );
+        }
+        assert_matches!(results[0], Ok(_));
+        assert_matches!(results[1], Err(InvalidSignature(InvalidBasicSignature(_))));
+        assert_matches!(results[2], Ok(_));
+        assert_matches!(results[3], Err(InvalidRequestExpiry(_)));
+        assert_matches!(results[4], Ok(_));
+    }
+
+    #[test]
+    fn should_accept_empty_batch() {
+        let verifier = HttpRequestVerifierImpl::new(Arc::new(
+            temp_crypto_component_with_fake_registry(node_test_id(0)),
+        ));
+
+        let results: Vec<Result<CanisterIdSet, RequestValidationError>> =
+            HttpRequestVerifier::<SignedIngressContent, _>::validate_requests(
+                &verifier,
+                &[],
+                CURRENT_TIME,
+                &MockRootOfTrustProvider::new(),
+            );
+
+        assert!(results.is_empty());
+    }
+
+    fn signed_request<R: Rng + CryptoRng>(
+        rng: &mut R,
+        ingress_expiry: Time,
+        corrupt_signature: bool,
+    ) -> HttpRequest<SignedIngressContent> {
+        let secret_key = ic_ed25519::PrivateKey::generate_using_rng(rng);
+        let public_key_der =
+            ed25519_public_key_to_der(secret_key.public_key().serialize_raw().to_vec())
+                .expect("invalid public key");
+        let update = HttpCanisterUpdate {
+            canister_id: Blob(canister_test_id(42).get().to_vec()),
+            method_name: "some_method".to_string(),
+            arg: Default::default(),
+            sender: Blob(PrincipalId::new_self_authenticating(&public_key_der).to_vec()),
+            nonce: None,
+            ingress_expiry: ingress_expiry.as_nanos_since_unix_epoch(),
+        };
+        let mut signature = secret_key
+            .sign_message(&update.id().as_signed_bytes())
+            .to_vec();
+        if corrupt_signature {
+            signature[0] ^= 1;
+        }
+        HttpRequest::try_from(HttpRequestEnvelope::<HttpCallContent> {
+            content: HttpCallContent::Call { update },
+            sender_pubkey: Some(Blob(public_key_der)),
+            sender_sig: Some(Blob(signature)),
+            sender_delegation: None,
+        })
+        .expect("invalid http envelope")
+    }
+}
+
 mod canister_id_set {
     use super::*;
     use ic_crypto_test_utils_reproducible_rng::reproducible_rng;
@@ -15,7 +15,10 @@ use crate::certified_slice_pool::{
 };
 use async_trait::async_trait;
 use http_body_util::BodyExt;
-use hyper::{Request, StatusCode, Uri};
+use hyper::{
+    Request, StatusCode, Uri,
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+};
 use hyper_util::client::legacy::Client;
 use hyper_util::rt::{TokioExecutor, TokioTimer};
 use ic_config::message_routing::MAX_STREAM_MESSAGES;
@@ -129,6 +132,7 @@ pub const METRIC_BUILD_PAYLOAD_DURATION: &str = "xnet_builder_build_payload_dura
 pub const METRIC_PULL_ATTEMPT_COUNT: &str = "xnet_builder_pull_attempt_count";
 pub const METRIC_QUERY_SLICE_DURATION: &str = "xnet_builder_query_slice_duration_seconds";
 pub const METRIC_RESPONSE_BODY_SIZE: &str = "xnet_builder_response_body_size_bytes";
+pub const METRIC_RESPONSE_COMPRESSION_RATIO: &str = "xnet_builder_response_compression_ratio";
 pub const METRIC_SLICE_MESSAGES: &str = "xnet_builder_slice_messages";
 pub const METRIC_SLICE_PAYLOAD_SIZE: &str = "xnet_builder_slice_payload_size_bytes";
 pub const METRIC_VALIDATE_PAYLOAD_DURATION: &str = "xnet_builder_validate_payload_duration_seconds";
@@ -317,6 +321,8 @@ pub struct EndpointLocator {
 impl XNetPayloadBuilderImpl {
     /// Creates a new `XNetPayloadBuilderImpl` for a node on `subnet_id`, using
     /// the given `StateManager`, `CertifiedStreamStore` and`RegistryClient`.
+    /// If `zstd_compression` is set, zstd compressed stream slices are
+    /// requested from remote subnets.
     ///
     /// # Panics
     ///
@@ -331,6 +337,7 @@ impl XNetPayloadBuilderImpl {
         runtime_handle: runtime::Handle,
         node_id: NodeId,
         subnet_id: SubnetId,
+        zstd_compression: bool,
         metrics_registry: &MetricsRegistry,
         log: ReplicaLogger,
     ) -> XNetPayloadBuilderImpl {
@@ -344,6 +351,7 @@ impl XNetPayloadBuilderImpl {
             metrics_registry,
             tls_handshake,
             proximity_map.clone(),
+            zstd_compression,
         ));
 
         let deterministic_rng_for_testing = Arc::new(None);
@@ -1662,26 +1670,47 @@ pub trait XNetClient: Sync + Send {
 
 type XNetRequestBody = http_body_util::Full<hyper::body::Bytes>;
 
+/// Content coding of zstd compressed stream slices. Advertised via
+/// `Accept-Encoding` if enabled in the message routing config; `XNetEndpoint`
+/// replicas that support it reply with a compressed body and a matching
+/// `Content-Encoding` header.
+///
+/// Compression only applies to the HTTP body: the decompressed body is the
+/// exact same encoded `CertifiedStreamSlice`, so certification and witness
+/// verification are unaffected.
+const ZSTD_ENCODING: &str = "zstd";
+
+/// Maximum size of a (compressed or decompressed) response body.
+const RESPONSE_BODY_BYTE_SIZE_MAX: usize = 5 * POOL_SLICE_BYTE_SIZE_MAX;
+
 /// The default `XNetClient` implementation, wrapping an HTTP client (for both
 /// configuration and connection pooling).
 struct XNetClientImpl {
     /// An HTTP client to be used for querying.
-    http_client: Client<TlsConnector, Request<XNetRequestBody>>,
+    http_client: Client<TlsConnector, XNetRequestBody>,
 
     /// Response body (encoded slice) size.
     response_body_size: HistogramVec,
 
+    /// Ratio of decompressed to compressed size of compressed response bodies.
+    response_compression_ratio: Histogram,
+
     /// Proximity map to update after every query with the time-to-first-byte.
     proximity_map: Arc<ProximityMap>,
+
+    /// Whether to request zstd compressed response bodies.
+    zstd_compression: bool,
 }
 
 impl XNetClientImpl {
     /// Creates a new `XNetClientImpl` with a request timeout of 1 second and at
-    /// most 1 idle connection per host.
+    /// most 1 idle connection per host. Requests zstd compressed slices iff
+    /// `zstd_compression` is set.
     fn new(
         metrics_registry: &MetricsRegistry,
         tls: Arc<dyn TlsConfig + Send + Sync>,
         proximity_map: Arc<ProximityMap>,
+        zstd_compression: bool,
     ) -> XNetClientImpl {
         #[cfg(not(test))]
         let https = TlsConnector::new(tls);
@@ -1689,7 +1718,7 @@ impl XNetClientImpl {
         let https = TlsConnector::new_for_tests(tls);
 
         // TODO(MR-28) Make timeout configurable.
-        let http_client: Client<TlsConnector, Request<XNetRequestBody>> =
+        let http_client: Client<TlsConnector, XNetRequestBody> =
             Client::builder(TokioExecutor::new())
                 .http2_only(true)
                 .pool_timer(TokioTimer::new())
@@ -1706,11 +1735,19 @@ impl XNetClientImpl {
         );
         response_body_size.with_label_values(&[STATUS_SUCCESS]);
         response_body_size.with_label_values(&[STATUS_DECODE_ERROR]);
+        let response_compression_ratio = metrics_registry.histogram(
+            METRIC_RESPONSE_COMPRESSION_RATIO,
+            "Ratio of decompressed to compressed size of zstd compressed response bodies.",
+            // 1 - 500
+            decimal_buckets(0, 2),
+        );
 
         XNetClientImpl {
             http_client,
             response_body_size,
+            response_compression_ratio,
             proximity_map,
+            zstd_compression,
         }
     }
 }
@@ -1725,9 +1762,16 @@ impl XNetClient for XNetClientImpl {
         let result = tokio::time::timeout(Duration::from_secs(5), async {
             let request_start = Instant::now();
 
+            let mut request = Request::get(endpoint.url.clone());
+            if self.zstd_compression {
+                request = request.header(ACCEPT_ENCODING, ZSTD_ENCODING);
+            }
+            let request = request
+                .body(XNetRequestBody::default())
+                .expect("Failed to build XNet request");
             let response = self
                 .http_client
-                .get(endpoint.url.clone())
+                .request(request)
                 .await
                 .map_err(XNetClientError::RequestFailed)?;
 
@@ -1741,19 +1785,41 @@ impl XNetClient for XNetClientImpl {
             );
 
             let status = response.status();
+            let content_encoding = response.headers().get(CONTENT_ENCODING).cloned();
 
             let content =
-                http_body_util::Limited::new(response.into_body(), 5 * POOL_SLICE_BYTE_SIZE_MAX)
+                http_body_util::Limited::new(response.into_body(), RESPONSE_BODY_BYTE_SIZE_MAX)
                     .collect()
                     .await
                     .map(|collected| collected.to_bytes())
                     .map_err(XNetClientError::BodyReadError)?;
 
-            Ok((status, content))
+            Ok((status, content_encoding, content))
         })
         .await;
 
-        let (status, bytes) = result.map_err(|_| XNetClientError::Timeout)??;
+        let (status, content_encoding, bytes) = result.map_err(|_| XNetClientError::Timeout)??;
+        let bytes = match content_encoding {
+            None => bytes,
+            Some(encoding) if encoding == ZSTD_ENCODING => {
+                // Decompression is CPU bound, keep it off the async runtime.
+                let compressed_len = bytes.len();
+                let decompressed = tokio::task::spawn_blocking(move || {
+                    zstd::bulk::decompress(&bytes, RESPONSE_BODY_BYTE_SIZE_MAX)
+                })
+                .await
+                .map_err(|err| XNetClientError::DecompressionError(err.into()))?
+                .map_err(XNetClientError::DecompressionError)?;
+                self.response_compression_ratio
+                    .observe(decompressed.len() as f64 / compressed_len.max(1) as f64);
+                decompressed.into()
+            }
+            Some(encoding) => {
+                return Err(XNetClientError::UnsupportedContentEncoding(
+                    String::from_utf8_lossy(encoding.as_bytes()).to_string(),
+                ));
+            }
+        };
 
         match status {
             StatusCode::OK => match pb::CertifiedStreamSlice::proxy_decode(bytes.as_ref()) {
@@ -1793,6 +1859,10 @@ pub enum XNetClientError {
     ErrorResponse(hyper::StatusCode, String),
     #[error("Error reading response body: {0}")]
     BodyReadError(Box<dyn std::error::Error + Send + Sync>),
+    #[error("Unsupported response content encoding: {0}")]
+    UnsupportedContentEncoding(String),
+    #[error("Error decompressing response body: {0}")]
+    DecompressionError(std::io::Error),
     #[error("Error decoding XNet proto into Rust struct: {0}")]
     ProxyDecodeError(ProxyDecodeError),
 }
@@ -1806,6 +1876,10 @@ impl XNetClientError {
             XNetClientError::NoContent => "NoContent".to_string(),
             XNetClientError::ErrorResponse(status, _) => format!("HTTP_{}", status.as_u16()),
             XNetClientError::BodyReadError(..) => "BodyReadError".to_string(),
+            XNetClientError::UnsupportedContentEncoding(..) => {
+                "UnsupportedContentEncoding".to_string()
+            }
+            XNetClientError::DecompressionError(..) => "DecompressionError".to_string(),
             XNetClientError::ProxyDecodeError(..) => STATUS_DECODE_ERROR.to_string(),
         }
     }
@@ -241,6 +241,7 @@ async fn validate_duplicate_messages_against_state_only() {
             tokio::runtime::Handle::current(),
             LOCAL_NODE,
             LOCAL_SUBNET,
+            false,
             &MetricsRegistry::new(),
             log,
         );
@@ -322,6 +323,7 @@ async fn validate_state_removed() {
             tokio::runtime::Handle::current(),
             LOCAL_NODE,
             LOCAL_SUBNET,
+            false,
             &MetricsRegistry::new(),
             log,
         );
@@ -359,6 +361,7 @@ async fn validate_state_not_yet_committed() {
             tokio::runtime::Handle::current(),
             LOCAL_NODE,
             LOCAL_SUBNET,
+            false,
             &MetricsRegistry::new(),
             log,
         );
@@ -455,6 +458,7 @@ impl PayloadBuilderTestFixture {
             tokio::runtime::Handle::current(),
             LOCAL_NODE,
             LOCAL_SUBNET,
+            false,
             &self.metrics,
             log,
         )
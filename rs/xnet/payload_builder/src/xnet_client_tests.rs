@@ -5,7 +5,10 @@ use super::test_fixtures::*;
 use super::*;
 use axum::{
     Router,
-    http::{HeaderMap, StatusCode, header::CONTENT_TYPE},
+    http::{
+        HeaderMap, StatusCode,
+        header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE},
+    },
     response::IntoResponse,
     routing::{MethodRouter, get},
 };
@@ -14,7 +17,9 @@ use ic_crypto_tls_interfaces_mocks::MockTlsConfig;
 use ic_protobuf::messaging::xnet::v1 as pb;
 use ic_protobuf::proxy::ProxyDecodeError;
 use ic_test_utilities_logger::with_test_replica_logger;
-use ic_test_utilities_metrics::{MetricVec, fetch_histogram_vec_count, metric_vec};
+use ic_test_utilities_metrics::{
+    MetricVec, fetch_histogram_stats, fetch_histogram_vec_count, metric_vec,
+};
 use ic_test_utilities_types::ids::SUBNET_6;
 use ic_types::{SubnetId, xnet::CertifiedStreamSlice};
 use std::{net::SocketAddr, sync::Arc};
@@ -45,11 +50,20 @@ where
 }
 
 fn make_xnet_client(metrics: &MetricsRegistry, log: ReplicaLogger) -> XNetClientImpl {
+    make_xnet_client_with_compression(metrics, log, true)
+}
+
+fn make_xnet_client_with_compression(
+    metrics: &MetricsRegistry,
+    log: ReplicaLogger,
+    zstd_compression: bool,
+) -> XNetClientImpl {
     let registry = get_empty_registry_for_test();
     XNetClientImpl::new(
         metrics,
         Arc::new(MockTlsConfig::new()) as Arc<_>,
         Arc::new(ProximityMap::new(LOCAL_NODE, registry, metrics, log)),
+        zstd_compression,
     )
 }
 
@@ -77,6 +91,130 @@ async fn query_success() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_zstd_compressed_success() {
+    let metrics = MetricsRegistry::new();
+    let slice = get_stream_slice_for_testing();
+    let expected = slice.clone();
+
+    // Only compress the slice if the client accepts zstd.
+    let respond_with_compressed_slice = get(move |request_headers: HeaderMap| {
+        let slice = slice.clone();
+        async move {
+            assert_eq!(
+                Some(ZSTD_ENCODING),
+                request_headers
+                    .get(ACCEPT_ENCODING)
+                    .map(|value| value.to_str().unwrap())
+            );
+            let buf = pb::CertifiedStreamSlice::proxy_encode(slice);
+            let compressed = zstd::bulk::compress(&buf, zstd::DEFAULT_COMPRESSION_LEVEL).unwrap();
+
+            let mut headers = HeaderMap::new();
+            headers.insert(CONTENT_TYPE, "application/x-protobuf".parse().unwrap());
+            headers.insert(CONTENT_ENCODING, ZSTD_ENCODING.parse().unwrap());
+            (headers, compressed)
+        }
+    });
+
+    let result = with_test_replica_logger(|log| async {
+        do_xnet_client_query(
+            make_xnet_client(&metrics, log),
+            respond_with_compressed_slice,
+        )
+        .await
+    })
+    .await;
+
+    assert_eq!(expected, result.unwrap());
+    assert_eq!(
+        metric_vec(&[
+            (&[("status", "success")], 1),
+            (&[("status", "ProxyDecodeError")], 0)
+        ]),
+        response_counts(&metrics)
+    );
+    assert_eq!(
+        1,
+        fetch_histogram_stats(&metrics, METRIC_RESPONSE_COMPRESSION_RATIO)
+            .unwrap()
+            .count
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_without_compression_does_not_accept_zstd() {
+    let metrics = MetricsRegistry::new();
+    let slice = get_stream_slice_for_testing();
+    let expected = slice.clone();
+
+    let respond_with_slice = get(move |request_headers: HeaderMap| {
+        let slice = slice.clone();
+        async move {
+            assert_eq!(None, request_headers.get(ACCEPT_ENCODING));
+            proto_axum_response::<_, pb::CertifiedStreamSlice>(slice).await
+        }
+    });
+
+    let result = with_test_replica_logger(|log| async {
+        do_xnet_client_query(
+            make_xnet_client_with_compression(&metrics, log, false),
+            respond_with_slice,
+        )
+        .await
+    })
+    .await;
+
+    assert_eq!(expected, result.unwrap());
+    assert_eq!(
+        0,
+        fetch_histogram_stats(&metrics, METRIC_RESPONSE_COMPRESSION_RATIO)
+            .map_or(0, |stats| stats.count)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_corrupt_zstd_response() {
+    let metrics = MetricsRegistry::new();
+
+    let respond_with_garbage = get(|| async {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, ZSTD_ENCODING.parse().unwrap());
+        (headers, b"garbage".to_vec())
+    });
+
+    let result = with_test_replica_logger(|log| async {
+        do_xnet_client_query(make_xnet_client(&metrics, log), respond_with_garbage).await
+    })
+    .await;
+
+    match result {
+        Err(XNetClientError::DecompressionError(_)) => (),
+        _ => panic!("Expecting Err(DecompressionError(_)), got {result:?}"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_unsupported_content_encoding() {
+    let metrics = MetricsRegistry::new();
+
+    let respond_with_gzip = get(|| async {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_ENCODING, "gzip".parse().unwrap());
+        (headers, b"garbage".to_vec())
+    });
+
+    let result = with_test_replica_logger(|log| async {
+        do_xnet_client_query(make_xnet_client(&metrics, log), respond_with_gzip).await
+    })
+    .await;
+
+    match result {
+        Err(XNetClientError::UnsupportedContentEncoding(encoding)) => assert_eq!("gzip", encoding),
+        _ => panic!("Expecting Err(UnsupportedContentEncoding(_)), got {result:?}"),
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn query_garbage_response() {
     let metrics = MetricsRegistry::new();